@@ -1,17 +1,42 @@
 use clap::{Parser, Subcommand};
+use rutify_client::i18n::{self, Language};
 use rutify_client::{
-    ClientState, WebSocketNotification, send_and_listen as client_send_and_listen,
+    ClientState, DEFAULT_LISTEN_TIMEOUT, GuiSettings, SavedServer, ThemePreference, TimeFormat,
+    TimeZoneSetting, WebSocketNotification, format_link_preview, format_progress, matches_search,
+    merge_sources, send_and_listen as client_send_and_listen, t,
 };
-use rutify_sdk::{CreateTokenRequest, LoginRequest, RegisterRequest, RutifyClient};
-use std::collections::VecDeque;
+use rutify_sdk::{
+    CreateTokenRequest, DeliveryChannel, DigestFrequency, LoginRequest, MAX_PRIORITY,
+    NotificationPreferences, NotifyId, NotifyItem, QuietHours, RegisterRequest, RutifyClient,
+    TokenId,
+};
+use std::collections::BTreeMap;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 #[derive(Parser)]
 #[command(name = "rutify-application")]
 #[command(about = "Rutify GUI application")]
 struct Cli {
-    #[arg(short, long, default_value = "http://127.0.0.1:8080")]
-    server: String,
+    /// Server URL (defaults to the one saved in settings, then http://127.0.0.1:8080)
+    #[arg(short, long)]
+    server: Option<String>,
+
+    /// Timezone for timestamps: local, UTC or an IANA name such as Europe/Berlin
+    #[arg(long, default_value = "local")]
+    timezone: TimeZoneSetting,
+
+    /// Show timestamps relative to now, e.g. "3 minutes ago"
+    #[arg(long)]
+    relative: bool,
+
+    /// Interface language: en or zh-CN (defaults to RUTIFY_LANG, then LANG)
+    #[arg(long)]
+    lang: Option<Language>,
+
+    /// User JWT for the preferences page (defaults to RUTIFY_USER_TOKEN)
+    #[arg(long)]
+    user_token: Option<String>,
 
     #[command(subcommand)]
     command: Option<Commands>,
@@ -34,6 +59,9 @@ enum Commands {
         /// Target device
         #[arg(long)]
         device: Option<String>,
+        /// Seconds to wait for delivery confirmation
+        #[arg(long, default_value_t = DEFAULT_LISTEN_TIMEOUT.as_secs())]
+        timeout: u64,
     },
     /// Token management
     Token {
@@ -104,7 +132,7 @@ enum AuthAction {
     /// Delete a token
     DeleteToken {
         /// Token ID
-        id: i32,
+        id: TokenId,
     },
 }
 
@@ -116,30 +144,163 @@ impl Default for Commands {
 
 slint::include_modules!();
 
+const DEFAULT_SERVER: &str = "http://127.0.0.1:8080";
+
+/// 列表中的通知及其来源服务器名称，只看当前服务器时来源为空
+type Listed = Vec<(String, NotifyItem)>;
+
+#[derive(Clone)]
 struct AppState {
-    client_state: ClientState,
+    /// 当前连接的服务器，切换服务器时整体替换
+    current: Arc<Mutex<ClientState>>,
+    /// 已保存的服务器、主题等 GUI 设置
+    settings: Arc<Mutex<GuiSettings>>,
+    listed: Arc<Mutex<Listed>>,
 }
 
 impl AppState {
-    fn new(server_url: &str) -> Self {
+    fn new(server_url: &str, time_format: TimeFormat, settings: GuiSettings) -> Self {
+        let client_state = client_for(server_url, settings.find_server(server_url), time_format);
         Self {
-            client_state: ClientState::new(server_url),
+            current: Arc::new(Mutex::new(client_state)),
+            settings: Arc::new(Mutex::new(settings)),
+            listed: Arc::default(),
         }
     }
 
-    fn notifications(&self) -> Arc<Mutex<VecDeque<rutify_sdk::NotifyItem>>> {
-        Arc::clone(&self.client_state.notifications)
+    fn client_state(&self) -> ClientState {
+        self.current.lock().unwrap().clone()
+    }
+
+    fn settings(&self) -> GuiSettings {
+        self.settings.lock().unwrap().clone()
     }
 
     fn stats(&self) -> Arc<Mutex<Option<rutify_sdk::Stats>>> {
-        Arc::clone(&self.client_state.stats)
+        Arc::clone(&self.current.lock().unwrap().stats)
+    }
+
+    /// 修改设置并写回文件
+    fn update_settings(&self, change: impl FnOnce(&mut GuiSettings)) -> anyhow::Result<()> {
+        let mut settings = self.settings.lock().unwrap();
+        change(&mut settings);
+        settings.save()
+    }
+
+    /// 切换到另一台服务器，使用该服务器保存的 Token，返回地址是否变化
+    fn switch_server(&self, server_url: &str) -> bool {
+        let mut current = self.current.lock().unwrap();
+        if current.client.base_url == server_url.trim_end_matches('/') {
+            return false;
+        }
+        let settings = self.settings.lock().unwrap();
+        let mut next = client_for(
+            server_url,
+            settings.find_server(server_url),
+            current.time_format,
+        );
+        next.stats = Arc::clone(&current.stats);
+        *current = next;
+        true
+    }
+
+    /// 通知所在服务器的客户端，合并列表中按来源名称查找
+    fn client_for_source(&self, source: &str) -> Option<ClientState> {
+        if source.is_empty() {
+            return Some(self.client_state());
+        }
+        let time_format = self.client_state().time_format;
+        let settings = self.settings.lock().unwrap();
+        let server = settings
+            .servers
+            .iter()
+            .find(|server| server.name == source)?;
+        Some(client_for(&server.url, Some(server), time_format))
+    }
+
+    /// 获取列表：合并模式下并发请求所有已保存的服务器，返回通知与请求失败的服务器名称
+    async fn fetch_listed(&self) -> (Listed, Vec<String>) {
+        let settings = self.settings();
+        if !settings.aggregate || settings.servers.is_empty() {
+            return match self.client_state().get_notifies().await {
+                Ok(items) => (
+                    items
+                        .into_iter()
+                        .map(|item| (String::new(), item))
+                        .collect(),
+                    Vec::new(),
+                ),
+                Err(e) => {
+                    eprintln!("Failed to refresh notifications: {}", e);
+                    (Vec::new(), vec![self.client_state().client.base_url])
+                }
+            };
+        }
+
+        let time_format = self.client_state().time_format;
+        let tasks: Vec<_> = settings
+            .servers
+            .into_iter()
+            .map(|server| {
+                let client_state = client_for(&server.url, Some(&server), time_format);
+                let task = tokio::spawn(async move { client_state.get_notifies().await });
+                (server.name, task)
+            })
+            .collect();
+
+        let mut sources = Vec::new();
+        let mut failed = Vec::new();
+        for (name, task) in tasks {
+            match task.await {
+                Ok(Ok(items)) => sources.push((name, items)),
+                Ok(Err(e)) => {
+                    eprintln!("Failed to refresh notifications from {}: {}", name, e);
+                    failed.push(name);
+                }
+                Err(_) => failed.push(name),
+            }
+        }
+        (merge_sources(sources), failed)
+    }
+}
+
+/// 按地址创建客户端，已保存的服务器带上各自的 Token
+fn client_for(url: &str, saved: Option<&SavedServer>, time_format: TimeFormat) -> ClientState {
+    let client_state = ClientState::new(url).with_time_format(time_format);
+    if let Some(saved) = saved {
+        if let Some(token) = &saved.token {
+            client_state.set_token(token);
+        }
+        if let Some(user_token) = &saved.user_token {
+            client_state.client.set_user_token(user_token);
+        }
     }
+    client_state
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
-    let state = AppState::new(&cli.server);
+    i18n::init(cli.lang.unwrap_or_else(|| Language::detect(None)));
+    let settings = GuiSettings::load().unwrap_or_else(|e| {
+        eprintln!("Failed to load settings: {:#}", e);
+        GuiSettings::default()
+    });
+    let server = cli
+        .server
+        .or_else(|| settings.server.clone())
+        .unwrap_or_else(|| DEFAULT_SERVER.to_string());
+    let state = AppState::new(
+        &server,
+        TimeFormat::new(cli.timezone, cli.relative),
+        settings,
+    );
+    if let Some(user_token) = cli
+        .user_token
+        .or_else(|| std::env::var("RUTIFY_USER_TOKEN").ok())
+    {
+        state.client_state().client.set_user_token(&user_token);
+    }
 
     match cli.command {
         Some(Commands::Gui) => {
@@ -152,14 +313,15 @@ async fn main() -> anyhow::Result<()> {
             message,
             title,
             device,
+            timeout,
         }) => {
-            send_and_listen(state, message, title, device).await?;
+            send_and_listen(state, message, title, device, timeout).await?;
         }
         Some(Commands::Token { action }) => {
-            handle_token_command(&state.client_state, action).await?;
+            handle_token_command(&state.client_state(), action).await?;
         }
         Some(Commands::Auth { action }) => {
-            handle_auth_command(&cli.server, action).await?;
+            handle_auth_command(&server, state.client_state().time_format, action).await?;
         }
         None => {
             // Default behavior - start GUI
@@ -172,43 +334,34 @@ async fn main() -> anyhow::Result<()> {
 
 async fn run_gui(state: AppState) -> anyhow::Result<()> {
     let ui = MainWindow::new()?;
+    select_translation();
+    let settings = state.settings();
+    ui.global::<Theme>()
+        .invoke_apply(theme_mode(settings.theme));
+    ui.set_settings_theme(theme_mode(settings.theme));
+    ui.set_aggregate(settings.aggregate);
+    update_ui_servers(&ui, &state);
 
     // Set up UI callbacks
-    let _client_state = state.client_state.clone();
 
     // Refresh button callback
     let ui_weak = ui.as_weak();
-    let client_state = state.client_state.clone();
-    let notifications = Arc::clone(&state.notifications());
+    let app_state = state.clone();
     ui.on_refresh_clicked(move || {
         let ui_weak = ui_weak.clone();
-        let client_state = client_state.clone();
-        let notifications = Arc::clone(&notifications);
+        let app_state = app_state.clone();
 
         tokio::spawn(async move {
-            match client_state.get_notifies().await {
-                Ok(items) => {
-                    let mut guard = notifications.lock().unwrap();
-                    guard.clear();
-                    guard.extend(items);
-
-                    if let Some(ui) = ui_weak.upgrade() {
-                        update_ui_notifications(&ui, &guard);
-                    }
-                }
-                Err(e) => {
-                    eprintln!("Failed to refresh notifications: {}", e);
-                }
-            }
+            refresh_notifications(ui_weak, app_state).await;
         });
     });
 
     // Send notification callback
     let ui_weak = ui.as_weak();
-    let client_state = state.client_state.clone();
+    let app_state = state.clone();
     ui.on_send_notification(move |message, title, device| {
         let ui_weak = ui_weak.clone();
-        let client_state = client_state.clone();
+        let client_state = app_state.client_state();
 
         let input = rutify_sdk::NotificationInput {
             notify: message.to_string(),
@@ -222,46 +375,252 @@ async fn run_gui(state: AppState) -> anyhow::Result<()> {
             } else {
                 Some(device.to_string())
             },
+            ..Default::default()
         };
 
         tokio::spawn(async move {
             match client_state.send_notification(&input).await {
                 Ok(_) => {
                     if let Some(ui) = ui_weak.upgrade() {
-                        ui.set_status("Notification sent successfully!".into());
+                        ui.set_status(t!("gui-sent").into());
                     }
                 }
                 Err(e) => {
                     if let Some(ui) = ui_weak.upgrade() {
-                        ui.set_status(format!("Failed to send: {}", e).into());
+                        ui.set_status(t!("gui-send-failed", error = e).into());
                     }
                 }
             }
         });
     });
 
-    // Initial data load
+    // Star toggle callback
     let ui_weak = ui.as_weak();
-    let client_state = state.client_state.clone();
-    let notifications = Arc::clone(&state.notifications());
-    let stats = Arc::clone(&state.stats());
+    let app_state = state.clone();
+    ui.on_toggle_star(move |source, id, starred| {
+        let ui_weak = ui_weak.clone();
+        let app_state = app_state.clone();
+        let source = source.to_string();
+        let Some(client_state) = app_state.client_for_source(&source) else {
+            return;
+        };
 
-    tokio::spawn(async move {
-        // Load notifications
-        match client_state.get_notifies().await {
-            Ok(items) => {
-                let mut guard = notifications.lock().unwrap();
-                guard.clear();
-                guard.extend(items);
+        tokio::spawn(async move {
+            let result = client_state
+                .client
+                .set_notification_starred(NotifyId(id), starred)
+                .await;
+            let _ = slint::invoke_from_event_loop(move || {
+                let Some(ui) = ui_weak.upgrade() else {
+                    return;
+                };
+                match result {
+                    Ok(()) => {
+                        let mut listed = app_state.listed.lock().unwrap();
+                        if let Some((_, item)) = listed
+                            .iter_mut()
+                            .find(|(from, item)| *from == source && item.id.get() == id)
+                        {
+                            item.starred = starred;
+                        }
+                        update_ui_notifications(&ui, &listed, &client_state.time_format);
+                    }
+                    Err(e) => ui.set_status(t!("gui-star-failed", error = e).into()),
+                }
+            });
+        });
+    });
 
-                if let Some(ui) = ui_weak.upgrade() {
-                    update_ui_notifications(&ui, &guard);
+    // Search callback
+    let ui_weak = ui.as_weak();
+    let app_state = state.clone();
+    ui.on_search_edited(move |_| {
+        if let Some(ui) = ui_weak.upgrade() {
+            let listed = app_state.listed.lock().unwrap();
+            update_ui_notifications(&ui, &listed, &app_state.client_state().time_format);
+        }
+    });
+
+    // Preferences callbacks
+    let ui_weak = ui.as_weak();
+    let app_state = state.clone();
+    ui.on_load_preferences(move || {
+        let ui_weak = ui_weak.clone();
+        let client_state = app_state.client_state();
+        if client_state.client.user_token().is_none() {
+            if let Some(ui) = ui_weak.upgrade() {
+                ui.set_status(t!("gui-preferences-login-required").into());
+            }
+            return;
+        }
+
+        tokio::spawn(async move {
+            let result = client_state.client.get_preferences().await;
+            let _ = slint::invoke_from_event_loop(move || {
+                let Some(ui) = ui_weak.upgrade() else {
+                    return;
+                };
+                match result {
+                    Ok(preferences) => {
+                        preferences_to_ui(&ui, &preferences);
+                        ui.set_status(t!("gui-preferences-loaded").into());
+                    }
+                    Err(e) => {
+                        ui.set_status(t!("gui-preferences-load-failed", error = e).into());
+                    }
                 }
+            });
+        });
+    });
+
+    let ui_weak = ui.as_weak();
+    let app_state = state.clone();
+    ui.on_save_preferences(move || {
+        let Some(ui) = ui_weak.upgrade() else {
+            return;
+        };
+        let client_state = app_state.client_state();
+        if client_state.client.user_token().is_none() {
+            ui.set_status(t!("gui-preferences-login-required").into());
+            return;
+        }
+        let preferences = preferences_from_ui(&ui);
+        let ui_weak = ui_weak.clone();
+
+        tokio::spawn(async move {
+            let result = client_state.client.update_preferences(&preferences).await;
+            let _ = slint::invoke_from_event_loop(move || {
+                let Some(ui) = ui_weak.upgrade() else {
+                    return;
+                };
+                match result {
+                    Ok(preferences) => {
+                        preferences_to_ui(&ui, &preferences);
+                        ui.set_status(t!("gui-preferences-saved").into());
+                    }
+                    Err(e) => {
+                        ui.set_status(t!("gui-preferences-save-failed", error = e).into());
+                    }
+                }
+            });
+        });
+    });
+
+    // Server callbacks
+    let ui_weak = ui.as_weak();
+    let app_state = state.clone();
+    ui.on_server_selected(move |index| {
+        let Some(ui) = ui_weak.upgrade() else {
+            return;
+        };
+        let Some(server) = app_state.settings().servers.get(index as usize).cloned() else {
+            return;
+        };
+        if app_state.switch_server(&server.url) {
+            let result = app_state.update_settings(|settings| settings.server = Some(server.url));
+            if let Err(e) = result {
+                ui.set_status(t!("gui-settings-save-failed", error = e).into());
             }
-            Err(e) => {
-                eprintln!("Failed to load notifications: {}", e);
+            // 合并模式下列表不随当前服务器变化
+            if !ui.get_aggregate() {
+                ui.set_selected(-1);
+                ui.set_show_detail(false);
+                ui.invoke_refresh_clicked();
+            }
+        }
+    });
+
+    let ui_weak = ui.as_weak();
+    let app_state = state.clone();
+    ui.on_aggregate_toggled(move |aggregate| {
+        let Some(ui) = ui_weak.upgrade() else {
+            return;
+        };
+        if let Err(e) = app_state.update_settings(|settings| settings.aggregate = aggregate) {
+            ui.set_status(t!("gui-settings-save-failed", error = e).into());
+        }
+        ui.set_selected(-1);
+        ui.set_show_detail(false);
+        ui.invoke_refresh_clicked();
+    });
+
+    let ui_weak = ui.as_weak();
+    let app_state = state.clone();
+    ui.on_add_server(move || {
+        let Some(ui) = ui_weak.upgrade() else {
+            return;
+        };
+        let name = ui.get_new_server_name().trim().to_string();
+        let url = ui.get_new_server_url().trim().to_string();
+        if name.is_empty() || url.is_empty() {
+            ui.set_status(t!("gui-server-incomplete").into());
+            return;
+        }
+        let optional = |text: slint::SharedString| {
+            let text = text.trim().to_string();
+            (!text.is_empty()).then_some(text)
+        };
+        let server = SavedServer {
+            name,
+            url,
+            token: optional(ui.get_new_server_token()),
+            user_token: optional(ui.get_new_server_user_token()),
+        };
+        match app_state.update_settings(|settings| settings.upsert_server(server)) {
+            Ok(()) => {
+                ui.set_new_server_name("".into());
+                ui.set_new_server_url("".into());
+                ui.set_new_server_token("".into());
+                ui.set_new_server_user_token("".into());
+                ui.set_status(t!("gui-settings-saved").into());
+            }
+            Err(e) => ui.set_status(t!("gui-settings-save-failed", error = e).into()),
+        }
+        update_ui_servers(&ui, &app_state);
+    });
+
+    let ui_weak = ui.as_weak();
+    let app_state = state.clone();
+    ui.on_remove_server(move |index| {
+        let Some(ui) = ui_weak.upgrade() else {
+            return;
+        };
+        let result = app_state.update_settings(|settings| {
+            if (index as usize) < settings.servers.len() {
+                settings.servers.remove(index as usize);
             }
+        });
+        match result {
+            Ok(()) => ui.set_status(t!("gui-settings-saved").into()),
+            Err(e) => ui.set_status(t!("gui-settings-save-failed", error = e).into()),
+        }
+        update_ui_servers(&ui, &app_state);
+    });
+
+    // Settings callback
+    let ui_weak = ui.as_weak();
+    let app_state = state.clone();
+    ui.on_apply_settings(move || {
+        let Some(ui) = ui_weak.upgrade() else {
+            return;
+        };
+        let mode = ui.get_settings_theme();
+        ui.global::<Theme>().invoke_apply(mode);
+        match app_state.update_settings(|settings| settings.theme = theme_preference(mode)) {
+            Ok(()) => ui.set_status(t!("gui-settings-saved").into()),
+            Err(e) => ui.set_status(t!("gui-settings-save-failed", error = e).into()),
         }
+    });
+
+    // Initial data load
+    let ui_weak = ui.as_weak();
+    let client_state = state.client_state();
+    let stats = Arc::clone(&state.stats());
+    let app_state = state.clone();
+
+    tokio::spawn(async move {
+        // Load notifications
+        refresh_notifications(ui_weak.clone(), app_state).await;
 
         // Load stats
         match client_state.get_stats().await {
@@ -283,10 +642,215 @@ async fn run_gui(state: AppState) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn update_ui_notifications(ui: &MainWindow, notifications: &VecDeque<rutify_sdk::NotifyItem>) {
-    // 简化版本，暂时不设置通知列表
-    // TODO: 实现通知列表显示
-    ui.set_status(format!("Loaded {} notifications", notifications.len()).into());
+/// 重新获取列表并刷新界面，部分服务器请求失败时在状态栏列出
+async fn refresh_notifications(ui_weak: slint::Weak<MainWindow>, app_state: AppState) {
+    let (listed, failed) = app_state.fetch_listed().await;
+    let time_format = app_state.client_state().time_format;
+    let _ = slint::invoke_from_event_loop(move || {
+        let Some(ui) = ui_weak.upgrade() else {
+            return;
+        };
+        let mut guard = app_state.listed.lock().unwrap();
+        *guard = listed;
+        update_ui_notifications(&ui, &guard, &time_format);
+        if !failed.is_empty() {
+            ui.set_status(t!("gui-servers-unreachable", servers = failed.join(", ")).into());
+        }
+    });
+}
+
+/// 同步已保存服务器到标题栏下拉框与设置页
+fn update_ui_servers(ui: &MainWindow, state: &AppState) {
+    let settings = state.settings();
+    let current = state.client_state().client.base_url;
+    let names: Vec<slint::SharedString> = settings
+        .servers
+        .iter()
+        .map(|server| server.name.clone().into())
+        .collect();
+    let rows: Vec<ServerRow> = settings
+        .servers
+        .iter()
+        .map(|server| ServerRow {
+            name: server.name.clone().into(),
+            url: server.url.clone().into(),
+            has_token: server.token.is_some() || server.user_token.is_some(),
+        })
+        .collect();
+    let index = settings
+        .servers
+        .iter()
+        .position(|server| server.url.trim_end_matches('/') == current)
+        .map_or(-1, |index| index as i32);
+
+    ui.set_server_names(slint::ModelRc::new(slint::VecModel::from(names)));
+    ui.set_servers(slint::ModelRc::new(slint::VecModel::from(rows)));
+    ui.set_current_server(index);
+    // 当前地址尚未保存时预填到新增表单
+    if index < 0 && ui.get_new_server_url().is_empty() {
+        ui.set_new_server_url(current.into());
+    }
+}
+
+fn theme_mode(theme: ThemePreference) -> ThemeMode {
+    match theme {
+        ThemePreference::System => ThemeMode::System,
+        ThemePreference::Light => ThemeMode::Light,
+        ThemePreference::Dark => ThemeMode::Dark,
+    }
+}
+
+fn theme_preference(mode: ThemeMode) -> ThemePreference {
+    match mode {
+        ThemeMode::Light => ThemePreference::Light,
+        ThemeMode::Dark => ThemePreference::Dark,
+        _ => ThemePreference::System,
+    }
+}
+
+/// 按当前语言切换 Slint 内置翻译，英文直接使用源文本
+fn select_translation() {
+    let code = i18n::language().slint_code();
+    if code.is_empty() {
+        return;
+    }
+    if let Err(e) = slint::select_bundled_translation(code) {
+        eprintln!("Failed to select translation '{}': {}", code, e);
+    }
+}
+
+fn update_ui_notifications(ui: &MainWindow, listed: &Listed, time_format: &TimeFormat) {
+    let search = ui.get_search_text();
+    let rows: Vec<NotificationRow> = listed
+        .iter()
+        .filter(|(_, notify)| matches_search(notify, &search))
+        .map(|(source, notify)| NotificationRow {
+            source: source.clone().into(),
+            id: notify.id.get(),
+            title: notify.title.clone().into(),
+            message: notify.notify.clone().into(),
+            device: notify.device.clone().into(),
+            timestamp: time_format.format(notify.received_at).into(),
+            pinned: notify.pinned,
+            has_progress: notify.progress.is_some(),
+            progress: notify
+                .progress
+                .map(|progress| progress.fraction().map_or(-1.0, |fraction| fraction as f32))
+                .unwrap_or_default(),
+            progress_label: notify
+                .progress
+                .map(|progress| format_progress(&progress))
+                .unwrap_or_default()
+                .into(),
+            link: notify
+                .link_previews
+                .first()
+                .map(format_link_preview)
+                .unwrap_or_default()
+                .into(),
+            verified: rutify_sdk::signing::verified_item_signer(notify).is_some(),
+            starred: notify.starred,
+        })
+        .collect();
+    // 列表变短时保持键盘选中项在范围内
+    let last = rows.len() as i32 - 1;
+    ui.set_selected(ui.get_selected().min(last));
+    if last < 0 {
+        ui.set_show_detail(false);
+    }
+    ui.set_status(t!("gui-loaded", count = rows.len()).into());
+    ui.set_notifications(slint::ModelRc::new(slint::VecModel::from(rows)));
+}
+
+/// 偏好页中逐个设置优先级门槛的推送渠道
+const THRESHOLD_CHANNELS: [DeliveryChannel; 5] = [
+    DeliveryChannel::UnifiedPush,
+    DeliveryChannel::WebPush,
+    DeliveryChannel::Telegram,
+    DeliveryChannel::Email,
+    DeliveryChannel::Webhook,
+];
+
+fn threshold(ui: &MainWindow, channel: DeliveryChannel) -> i32 {
+    match channel {
+        DeliveryChannel::UnifiedPush => ui.get_pref_min_unifiedpush(),
+        DeliveryChannel::WebPush => ui.get_pref_min_web_push(),
+        DeliveryChannel::Telegram => ui.get_pref_min_telegram(),
+        DeliveryChannel::Email => ui.get_pref_min_email(),
+        DeliveryChannel::Webhook => ui.get_pref_min_webhook(),
+        DeliveryChannel::Websocket => 0,
+    }
+}
+
+fn set_threshold(ui: &MainWindow, channel: DeliveryChannel, value: i32) {
+    match channel {
+        DeliveryChannel::UnifiedPush => ui.set_pref_min_unifiedpush(value),
+        DeliveryChannel::WebPush => ui.set_pref_min_web_push(value),
+        DeliveryChannel::Telegram => ui.set_pref_min_telegram(value),
+        DeliveryChannel::Email => ui.set_pref_min_email(value),
+        DeliveryChannel::Webhook => ui.set_pref_min_webhook(value),
+        DeliveryChannel::Websocket => {}
+    }
+}
+
+fn preferences_to_ui(ui: &MainWindow, preferences: &NotificationPreferences) {
+    ui.set_pref_channels(preferences.channels.join(", ").into());
+    ui.set_pref_quiet_enabled(preferences.quiet_hours.is_some());
+    if let Some(quiet) = &preferences.quiet_hours {
+        ui.set_pref_quiet_start(quiet.start.clone().into());
+        ui.set_pref_quiet_end(quiet.end.clone().into());
+        ui.set_pref_quiet_timezone(quiet.timezone.clone().into());
+        ui.set_pref_quiet_min_priority(quiet.min_priority.into());
+    }
+    ui.set_pref_digest(match preferences.digest {
+        DigestFrequency::Off => 0,
+        DigestFrequency::Hourly => 1,
+        DigestFrequency::Daily => 2,
+    });
+    for channel in THRESHOLD_CHANNELS {
+        let value = preferences
+            .min_priority
+            .get(channel.as_str())
+            .copied()
+            .unwrap_or(0);
+        set_threshold(ui, channel, value.into());
+    }
+}
+
+/// 门槛为 0 的渠道不写入，服务端对其不做限制
+fn preferences_from_ui(ui: &MainWindow) -> NotificationPreferences {
+    let quiet_hours = ui.get_pref_quiet_enabled().then(|| QuietHours {
+        start: ui.get_pref_quiet_start().to_string(),
+        end: ui.get_pref_quiet_end().to_string(),
+        timezone: ui.get_pref_quiet_timezone().to_string(),
+        min_priority: ui
+            .get_pref_quiet_min_priority()
+            .clamp(1, MAX_PRIORITY.into()) as u8,
+    });
+    let min_priority: BTreeMap<String, u8> = THRESHOLD_CHANNELS
+        .into_iter()
+        .filter_map(|channel| {
+            let value = threshold(ui, channel).clamp(0, MAX_PRIORITY.into()) as u8;
+            (value > 0).then(|| (channel.as_str().to_string(), value))
+        })
+        .collect();
+
+    NotificationPreferences {
+        channels: ui
+            .get_pref_channels()
+            .split(',')
+            .map(str::trim)
+            .filter(|channel| !channel.is_empty())
+            .map(str::to_string)
+            .collect(),
+        quiet_hours,
+        digest: match ui.get_pref_digest() {
+            1 => DigestFrequency::Hourly,
+            2 => DigestFrequency::Daily,
+            _ => DigestFrequency::Off,
+        },
+        min_priority,
+    }
 }
 
 fn update_ui_stats(ui: &MainWindow, stats: &std::sync::MutexGuard<Option<rutify_sdk::Stats>>) {
@@ -306,36 +870,44 @@ fn update_ui_stats(ui: &MainWindow, stats: &std::sync::MutexGuard<Option<rutify_
 }
 
 async fn listen_websocket(state: AppState) -> anyhow::Result<()> {
-    println!("🎧 Listening for WebSocket notifications...");
-    println!("   Press Ctrl+C to stop");
+    println!("{}", t!("listen-start"));
+    println!("   {}", t!("listen-stop-hint"));
 
-    match state.client_state.listen_websocket_updates().await {
+    let client_state = state.client_state();
+    match client_state.listen_websocket_updates().await {
         Ok(mut rx) => {
             while let Some(notification) = rx.recv().await {
                 match notification {
-                    WebSocketNotification::Event(event) => {
-                        println!("🔔 New notification:");
-                        println!("   Title: {}", event.data.title);
-                        println!("   Message: {}", event.data.notify);
-                        println!("   Device: {}", event.data.device);
-                        println!("   Time: {}", event.timestamp.format("%Y-%m-%d %H:%M:%S"));
+                    WebSocketNotification::Event(event) if event.event.is_notification() => {
+                        println!("{}", t!("new-notification"));
+                        println!("   {}", t!("event-title", value = event.data.title));
+                        println!("   {}", t!("event-message", value = event.data.notify));
+                        println!("   {}", t!("event-device", value = event.data.device));
+                        println!(
+                            "   {}",
+                            t!(
+                                "event-time",
+                                value = client_state.time_format.format(event.timestamp)
+                            )
+                        );
                         println!();
                     }
+                    WebSocketNotification::Event(_) => {}
                     WebSocketNotification::Text(text) => {
-                        println!("📝 Text message: {}", text);
+                        println!("{}", t!("text-message", text = text));
                     }
                     WebSocketNotification::Error { message } => {
-                        eprintln!("❌ Error: {}", message);
+                        eprintln!("{}", t!("error-message", message = message));
                     }
                     WebSocketNotification::Close => {
-                        println!("🔌 Connection closed");
+                        println!("{}", t!("connection-closed"));
                         break;
                     }
                 }
             }
         }
         Err(e) => {
-            eprintln!("❌ Failed to connect WebSocket: {}", e);
+            eprintln!("{}", t!("listen-failed", error = e));
             return Err(e.into());
         }
     }
@@ -348,33 +920,42 @@ async fn send_and_listen(
     message: String,
     title: Option<String>,
     device: Option<String>,
+    timeout: u64,
 ) -> anyhow::Result<()> {
-    println!("📤 Sending notification and listening for response...");
+    println!("{}", t!("send-listen-start"));
 
-    match client_send_and_listen(&state.client_state, message, title, device).await {
+    let timeout = Duration::from_secs(timeout);
+    let client_state = state.client_state();
+    match client_send_and_listen(&client_state, message, title, device, timeout).await {
         Ok(Some(notification)) => match notification {
             WebSocketNotification::Event(event) => {
-                println!("🔔 Response received:");
-                println!("   Title: {}", event.data.title);
-                println!("   Message: {}", event.data.notify);
-                println!("   Device: {}", event.data.device);
-                println!("   Time: {}", event.timestamp.format("%Y-%m-%d %H:%M:%S"));
+                println!("{}", t!("response-received"));
+                println!("   {}", t!("event-title", value = event.data.title));
+                println!("   {}", t!("event-message", value = event.data.notify));
+                println!("   {}", t!("event-device", value = event.data.device));
+                println!(
+                    "   {}",
+                    t!(
+                        "event-time",
+                        value = client_state.time_format.format(event.timestamp)
+                    )
+                );
             }
             WebSocketNotification::Text(text) => {
-                println!("📝 Response: {}", text);
+                println!("{}", t!("response-text", text = text));
             }
             WebSocketNotification::Error { message } => {
-                eprintln!("❌ Error: {}", message);
+                eprintln!("{}", t!("error-message", message = message));
             }
             WebSocketNotification::Close => {
-                println!("🔌 Connection closed");
+                println!("{}", t!("connection-closed"));
             }
         },
         Ok(None) => {
-            println!("⏰ No response received");
+            println!("{}", t!("no-response"));
         }
         Err(e) => {
-            eprintln!("❌ Failed to send and listen: {}", e);
+            eprintln!("{}", t!("send-listen-failed", error = e));
             return Err(e.into());
         }
     }
@@ -389,45 +970,61 @@ async fn handle_token_command(
     match action {
         TokenAction::Create { usage, expires_in } => {
             println!(
-                "🔑 Creating new token for usage: '{}', expires in {} hours",
-                usage, expires_in
+                "{}",
+                t!("token-creating-for", usage = usage, hours = expires_in)
             );
             match client_state.create_token(&usage, expires_in).await {
                 Ok(token_response) => {
-                    println!("✅ Token created successfully!");
-                    println!("   Token ID: {}", token_response.token_id);
-                    println!("   Usage: {}", token_response.usage);
-                    println!("   Expires at: {}", token_response.expires_at);
-                    println!("   Token: {}", token_response.token);
-                    println!("   💡 Save this token securely!");
+                    println!("{}", t!("token-created"));
+                    println!(
+                        "   {}",
+                        t!("label-token-id", value = token_response.token_id)
+                    );
+                    println!("   {}", t!("label-usage", value = token_response.usage));
+                    println!(
+                        "   {}",
+                        t!(
+                            "label-expires-at",
+                            value = client_state.time_format.format(token_response.expires_at)
+                        )
+                    );
+                    println!("   {}", t!("label-token", value = token_response.token));
+                    println!("   {}", t!("token-save-hint"));
                 }
-                Err(e) => eprintln!("❌ Failed to create token: {}", e),
+                Err(e) => eprintln!("{}", t!("token-create-failed", error = e)),
             }
         }
         TokenAction::Set { token } => {
-            println!("🔐 Setting authentication token...");
+            println!("{}", t!("token-setting"));
             println!(
-                "   Token set: {}...",
-                &token[..std::cmp::min(20, token.len())]
+                "   {}",
+                t!(
+                    "token-set",
+                    preview = &token[..std::cmp::min(20, token.len())]
+                )
             );
-            println!("   💡 Use this token for subsequent requests");
+            println!("   {}", t!("token-set-hint"));
         }
         TokenAction::Clear => {
-            println!("🗑️  Clearing stored token...");
-            println!("   Token cleared");
+            println!("{}", t!("token-clearing"));
+            println!("   {}", t!("token-cleared"));
         }
         TokenAction::Status => {
             if client_state.has_token() {
-                println!("✅ Token is configured");
+                println!("{}", t!("token-configured"));
             } else {
-                println!("❌ No token configured");
+                println!("{}", t!("token-missing"));
             }
         }
     }
     Ok(())
 }
 
-async fn handle_auth_command(server: &str, action: AuthAction) -> anyhow::Result<()> {
+async fn handle_auth_command(
+    server: &str,
+    time_format: TimeFormat,
+    action: AuthAction,
+) -> anyhow::Result<()> {
     let client = RutifyClient::new(server);
 
     match action {
@@ -436,30 +1033,35 @@ async fn handle_auth_command(server: &str, action: AuthAction) -> anyhow::Result
             password,
             email,
         } => {
-            println!("🔐 Registering new user...");
+            println!("{}", t!("registering"));
 
             let request = RegisterRequest {
                 username: username.clone(),
                 password,
                 email,
+                ..Default::default()
             };
 
             match client.register(&request).await {
                 Ok(_) => {
-                    println!("✅ User '{}' registered successfully!", username);
+                    println!("{}", t!("registered", username = username));
                     println!(
-                        "💡 You can now login with: rutify-application auth login --username {} --password <password>",
-                        username
+                        "{}",
+                        t!(
+                            "register-login-hint",
+                            bin = "rutify-application",
+                            username = username
+                        )
                     );
                 }
                 Err(e) => {
-                    eprintln!("❌ Registration failed: {}", e);
+                    eprintln!("{}", t!("register-failed", error = e));
                 }
             }
         }
 
         AuthAction::Login { username, password } => {
-            println!("🔑 Logging in...");
+            println!("{}", t!("logging-in"));
 
             let request = LoginRequest {
                 username: username.clone(),
@@ -468,51 +1070,71 @@ async fn handle_auth_command(server: &str, action: AuthAction) -> anyhow::Result
 
             match client.login(&request).await {
                 Ok(response) => {
-                    println!("✅ Login successful!");
-                    println!("👤 User: {}", response.username);
-                    println!("📧 Email: {}", response.email);
-                    println!("🔐 Role: {}", response.role);
-                    println!("⏰ Expires at: {}", response.expires_at);
-                    println!("🎫 JWT Token: {}", response.jwt_token);
+                    println!("{}", t!("login-success"));
+                    println!("{}", t!("label-user", value = response.username));
+                    println!("{}", t!("label-email", value = response.email));
+                    println!("{}", t!("label-role", value = response.role));
+                    println!(
+                        "{}",
+                        t!(
+                            "label-expires-at",
+                            value = time_format.format(response.expires_at)
+                        )
+                    );
+                    println!("{}", t!("label-jwt", value = response.jwt_token));
                     println!();
-                    println!("💡 Save this token for future API calls:");
+                    println!("{}", t!("save-jwt-hint"));
                     println!("   export RUTIFY_USER_TOKEN=\"{}\"", response.jwt_token);
                 }
                 Err(e) => {
-                    eprintln!("❌ Login failed: {}", e);
+                    eprintln!("{}", t!("login-failed", error = e));
                 }
             }
         }
 
         AuthAction::Profile => {
-            let user_token = std::env::var("RUTIFY_USER_TOKEN")
-                .unwrap_or_else(|_| {
-                    eprintln!("❌ RUTIFY_USER_TOKEN environment variable not set");
-                    eprintln!("💡 Please login first: rutify-application auth login --username <user> --password <pass>");
-                    String::new() // 返回空字符串而不是Ok(())
-                });
+            let user_token = std::env::var("RUTIFY_USER_TOKEN").unwrap_or_else(|_| {
+                eprintln!("{}", t!("user-token-env-missing"));
+                eprintln!("{}", t!("login-hint", bin = "rutify-application"));
+                String::new() // 返回空字符串而不是Ok(())
+            });
 
             let client = client.with_user_token(&user_token);
 
-            println!("👤 Getting user profile...");
+            println!("{}", t!("user-profile-loading"));
 
             match client.get_user_profile().await {
                 Ok(profile) => {
-                    println!("✅ User Profile:");
-                    println!("  🆔 ID: {}", profile.id);
-                    println!("  📝 Usage: {}", profile.usage);
-                    println!("  🔐 Type: {}", profile.token_type);
+                    println!("{}", t!("user-profile-header"));
+                    println!("  {}", t!("label-id", value = profile.id));
+                    println!("  {}", t!("label-usage", value = profile.usage));
+                    println!("  {}", t!("label-type", value = profile.token_type));
                     if let Some(device) = profile.device_info {
-                        println!("  📱 Device: {}", device);
+                        println!("  {}", t!("label-device", value = device));
                     }
-                    println!("  📅 Created: {}", profile.created_at);
-                    println!("  ⏰ Expires: {}", profile.expires_at);
+                    println!(
+                        "  {}",
+                        t!(
+                            "label-created",
+                            value = time_format.format(profile.created_at)
+                        )
+                    );
+                    println!(
+                        "  {}",
+                        t!(
+                            "label-expires",
+                            value = time_format.format(profile.expires_at)
+                        )
+                    );
                     if let Some(last_used) = profile.last_used_at {
-                        println!("  🔄 Last Used: {}", last_used);
+                        println!(
+                            "  {}",
+                            t!("label-last-used", value = time_format.format(last_used))
+                        );
                     }
                 }
                 Err(e) => {
-                    eprintln!("❌ Failed to get profile: {}", e);
+                    eprintln!("{}", t!("user-profile-failed", error = e));
                 }
             }
         }
@@ -522,59 +1144,65 @@ async fn handle_auth_command(server: &str, action: AuthAction) -> anyhow::Result
             device,
             expires,
         } => {
-            let user_token = std::env::var("RUTIFY_USER_TOKEN")
-                .unwrap_or_else(|_| {
-                    eprintln!("❌ RUTIFY_USER_TOKEN environment variable not set");
-                    eprintln!("💡 Please login first: rutify-application auth login --username <user> --password <pass>");
-                    String::new() // 返回空字符串而不是Ok(())
-                });
+            let user_token = std::env::var("RUTIFY_USER_TOKEN").unwrap_or_else(|_| {
+                eprintln!("{}", t!("user-token-env-missing"));
+                eprintln!("{}", t!("login-hint", bin = "rutify-application"));
+                String::new() // 返回空字符串而不是Ok(())
+            });
 
             let client = client.with_user_token(&user_token);
 
-            println!("🎫 Creating notification token...");
+            println!("{}", t!("token-creating"));
 
             let request = CreateTokenRequest {
                 usage: usage.clone(),
                 expires_in_hours: Some(expires),
                 device_info: device,
+                defaults: Default::default(),
+                signing_key: None,
             };
 
             match client.create_notify_token(&request).await {
                 Ok(response) => {
-                    println!("✅ Token created successfully!");
-                    println!("🎫 Token: {}", response.token);
-                    println!("🆔 Token ID: {}", response.token_id);
-                    println!("📝 Usage: {}", response.usage);
-                    println!("🔐 Type: {}", response.token_type);
-                    println!("⏰ Expires at: {}", response.expires_at);
+                    println!("{}", t!("token-created"));
+                    println!("{}", t!("label-token", value = response.token));
+                    println!("{}", t!("label-token-id", value = response.token_id));
+                    println!("{}", t!("label-usage", value = response.usage));
+                    println!("{}", t!("label-type", value = response.token_type));
+                    println!(
+                        "{}",
+                        t!(
+                            "label-expires-at",
+                            value = time_format.format(response.expires_at)
+                        )
+                    );
                     println!();
-                    println!("💡 Use this token for notifications:");
+                    println!("{}", t!("use-token-hint"));
                     println!("   export RUTIFY_TOKEN=\"{}\"", response.token);
                 }
                 Err(e) => {
-                    eprintln!("❌ Failed to create token: {}", e);
+                    eprintln!("{}", t!("token-create-failed", error = e));
                 }
             }
         }
 
         AuthAction::ListTokens => {
-            let user_token = std::env::var("RUTIFY_USER_TOKEN")
-                .unwrap_or_else(|_| {
-                    eprintln!("❌ RUTIFY_USER_TOKEN environment variable not set");
-                    eprintln!("💡 Please login first: rutify-application auth login --username <user> --password <pass>");
-                    String::new() // 返回空字符串而不是Ok(())
-                });
+            let user_token = std::env::var("RUTIFY_USER_TOKEN").unwrap_or_else(|_| {
+                eprintln!("{}", t!("user-token-env-missing"));
+                eprintln!("{}", t!("login-hint", bin = "rutify-application"));
+                String::new() // 返回空字符串而不是Ok(())
+            });
 
             let client = client.with_user_token(&user_token);
 
-            println!("📋 Listing user tokens...");
+            println!("{}", t!("tokens-listing"));
 
             match client.get_user_tokens().await {
                 Ok(tokens) => {
                     if tokens.is_empty() {
-                        println!("📭 No tokens found.");
+                        println!("{}", t!("tokens-empty"));
                     } else {
-                        println!("🎫 User Tokens ({} total):", tokens.len());
+                        println!("{}", t!("tokens-header", count = tokens.len()));
                         for (i, token) in tokens.iter().enumerate() {
                             println!(
                                 "  {}. 🆔 {} | 📝 {} | 🔐 {}",
@@ -588,7 +1216,7 @@ async fn handle_auth_command(server: &str, action: AuthAction) -> anyhow::Result
                             }
                             println!("     📅 {} | ⏰ {}", token.created_at, token.expires_at);
                             if let Some(last_used) = &token.last_used_at {
-                                println!("     🔄 Last Used: {}", last_used);
+                                println!("     {}", t!("label-last-used", value = last_used));
                             }
                             if i < tokens.len() - 1 {
                                 println!();
@@ -597,29 +1225,28 @@ async fn handle_auth_command(server: &str, action: AuthAction) -> anyhow::Result
                     }
                 }
                 Err(e) => {
-                    eprintln!("❌ Failed to list tokens: {}", e);
+                    eprintln!("{}", t!("tokens-list-failed", error = e));
                 }
             }
         }
 
         AuthAction::DeleteToken { id } => {
-            let user_token = std::env::var("RUTIFY_USER_TOKEN")
-                .unwrap_or_else(|_| {
-                    eprintln!("❌ RUTIFY_USER_TOKEN environment variable not set");
-                    eprintln!("💡 Please login first: rutify-application auth login --username <user> --password <pass>");
-                    String::new() // 返回空字符串而不是Ok(())
-                });
+            let user_token = std::env::var("RUTIFY_USER_TOKEN").unwrap_or_else(|_| {
+                eprintln!("{}", t!("user-token-env-missing"));
+                eprintln!("{}", t!("login-hint", bin = "rutify-application"));
+                String::new() // 返回空字符串而不是Ok(())
+            });
 
             let client = client.with_user_token(&user_token);
 
-            println!("🗑️  Deleting token {}...", id);
+            println!("{}", t!("token-deleting", id = id));
 
             match client.delete_user_token(id).await {
                 Ok(_) => {
-                    println!("✅ Token {} deleted successfully!", id);
+                    println!("{}", t!("token-deleted", id = id));
                 }
                 Err(e) => {
-                    eprintln!("❌ Failed to delete token: {}", e);
+                    eprintln!("{}", t!("token-delete-failed", error = e));
                 }
             }
         }
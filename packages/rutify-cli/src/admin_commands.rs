@@ -0,0 +1,299 @@
+use anyhow::Result;
+use clap::Subcommand;
+use rutify_client::{TimeFormat, t};
+use rutify_sdk::{AdminUser, PurgeNotifiesRequest, RutifyClient, SdkResult};
+use std::time::Duration;
+
+use crate::auth_commands::{print_tokens, require_user_token};
+use crate::silence_commands::parse_duration;
+
+/// `audit tail --follow` 查询新记录的间隔
+const AUDIT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Subcommand)]
+pub enum AdminAction {
+    /// User accounts
+    Users {
+        #[command(subcommand)]
+        action: UserAction,
+    },
+    /// Notification and user tokens
+    Tokens {
+        #[command(subcommand)]
+        action: TokensAction,
+    },
+    /// Delete notifications matching all given criteria
+    Purge {
+        /// Only notifications received before this long ago, e.g. 30d or 12h
+        #[arg(long)]
+        older_than: Option<String>,
+        /// Only notifications from this device
+        #[arg(long)]
+        device: Option<String>,
+        /// Only notifications sent to this channel
+        #[arg(long)]
+        channel: Option<String>,
+        /// Only acknowledged notifications
+        #[arg(long, conflicts_with = "unread")]
+        read: bool,
+        /// Only unacknowledged notifications
+        #[arg(long)]
+        unread: bool,
+        /// Report how many notifications match without deleting them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Server settings that can change without a restart
+    Settings {
+        #[command(subcommand)]
+        action: SettingsAction,
+    },
+    /// Log of changes made through the admin API
+    Audit {
+        #[command(subcommand)]
+        action: AuditAction,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum UserAction {
+    /// List all users
+    List,
+    /// Block a user from logging in
+    Disable { username: String },
+    /// Allow a disabled user to log in again
+    Enable { username: String },
+    /// Give a user the admin role
+    Promote { username: String },
+}
+
+#[derive(Subcommand)]
+pub enum TokensAction {
+    /// List your tokens, or every user's with --all
+    List {
+        /// Include tokens of all users
+        #[arg(long)]
+        all: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SettingsAction {
+    /// Show all settings, or only the given one
+    Get { key: Option<String> },
+    /// Change a setting until the server next reloads its configuration
+    Set { key: String, value: String },
+}
+
+#[derive(Subcommand)]
+pub enum AuditAction {
+    /// Print the latest audit entries
+    Tail {
+        /// Number of entries to print
+        #[arg(short = 'n', long, default_value_t = 20)]
+        lines: u64,
+        /// Keep printing new entries as they are recorded
+        #[arg(short, long)]
+        follow: bool,
+    },
+}
+
+pub async fn handle_admin_command(
+    client: &RutifyClient,
+    time_format: &TimeFormat,
+    action: AdminAction,
+) -> Result<()> {
+    require_user_token(client);
+
+    match action {
+        AdminAction::Users { action } => handle_user_action(client, time_format, action).await,
+        AdminAction::Tokens {
+            action: TokensAction::List { all },
+        } => {
+            let tokens = if all {
+                client.get_all_tokens().await
+            } else {
+                client.get_user_tokens().await
+            };
+            match tokens {
+                Ok(tokens) if tokens.is_empty() => println!("{}", t!("tokens-empty")),
+                Ok(tokens) => {
+                    let header = if all {
+                        "admin-tokens-header"
+                    } else {
+                        "tokens-header"
+                    };
+                    println!("{}", t!(header, count = tokens.len()));
+                    print_tokens(&tokens, time_format);
+                }
+                Err(e) => {
+                    eprintln!("{}", t!("tokens-list-failed", error = e));
+                    std::process::exit(1);
+                }
+            }
+        }
+        AdminAction::Settings { action } => handle_settings_action(client, action).await,
+        AdminAction::Audit {
+            action: AuditAction::Tail { lines, follow },
+        } => {
+            if let Err(e) = tail_audit(client, time_format, lines, follow).await {
+                eprintln!("{}", t!("audit-failed", error = e));
+                std::process::exit(1);
+            }
+        }
+        AdminAction::Purge {
+            older_than,
+            device,
+            channel,
+            read,
+            unread,
+            dry_run,
+        } => {
+            let request = PurgeNotifiesRequest {
+                older_than_secs: older_than.as_deref().map(parse_duration).transpose()?,
+                device,
+                channel,
+                read: (read || unread).then_some(read),
+                dry_run,
+            };
+
+            match client.purge_notifies(&request).await {
+                Ok(result) if result.dry_run => {
+                    println!("{}", t!("purge-dry-run", count = result.matched));
+                }
+                Ok(result) => println!("{}", t!("purge-done", count = result.deleted)),
+                Err(e) => {
+                    eprintln!("{}", t!("purge-failed", error = e));
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn handle_user_action(client: &RutifyClient, time_format: &TimeFormat, action: UserAction) {
+    let (result, message): (SdkResult<AdminUser>, &str) = match action {
+        UserAction::List => {
+            match client.get_admin_users().await {
+                Ok(users) if users.is_empty() => println!("{}", t!("admin-users-empty")),
+                Ok(users) => {
+                    println!("{}", t!("admin-users-header", count = users.len()));
+                    for user in users {
+                        let status = if user.disabled {
+                            t!("admin-user-status-disabled")
+                        } else {
+                            t!("admin-user-status-active")
+                        };
+                        println!(
+                            "  {}",
+                            t!(
+                                "admin-user-line",
+                                username = user.username,
+                                email = user.email,
+                                role = user.role,
+                                status = status,
+                                created = time_format.format(user.created_at),
+                            )
+                        );
+                    }
+                }
+                Err(e) => {
+                    eprintln!("{}", t!("admin-users-failed", error = e));
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        UserAction::Disable { username } => {
+            (client.disable_user(&username).await, "admin-user-disabled")
+        }
+        UserAction::Enable { username } => {
+            (client.enable_user(&username).await, "admin-user-enabled")
+        }
+        UserAction::Promote { username } => {
+            (client.promote_user(&username).await, "admin-user-promoted")
+        }
+    };
+
+    match result {
+        Ok(user) => println!("{}", t!(message, username = user.username)),
+        Err(e) => {
+            eprintln!("{}", t!("admin-user-update-failed", error = e));
+            std::process::exit(1);
+        }
+    }
+}
+
+async fn handle_settings_action(client: &RutifyClient, action: SettingsAction) {
+    match action {
+        SettingsAction::Get { key } => match client.get_settings().await {
+            Ok(settings) => {
+                let settings: Vec<_> = settings
+                    .into_iter()
+                    .filter(|setting| key.as_ref().is_none_or(|key| &setting.key == key))
+                    .collect();
+                if let (Some(key), true) = (&key, settings.is_empty()) {
+                    eprintln!("{}", t!("setting-unknown", key = key));
+                    std::process::exit(1);
+                }
+                for setting in settings {
+                    println!(
+                        "{}",
+                        t!("setting-line", key = setting.key, value = setting.value)
+                    );
+                    println!("    {}", setting.description);
+                }
+            }
+            Err(e) => {
+                eprintln!("{}", t!("settings-failed", error = e));
+                std::process::exit(1);
+            }
+        },
+        SettingsAction::Set { key, value } => match client.set_setting(&key, &value).await {
+            Ok(setting) => println!(
+                "{}",
+                t!("setting-updated", key = setting.key, value = setting.value)
+            ),
+            Err(e) => {
+                eprintln!("{}", t!("setting-update-failed", error = e));
+                std::process::exit(1);
+            }
+        },
+    }
+}
+
+/// 打印最近的审计记录，`follow` 时持续轮询新记录直到进程被中断
+async fn tail_audit(
+    client: &RutifyClient,
+    time_format: &TimeFormat,
+    lines: u64,
+    follow: bool,
+) -> SdkResult<()> {
+    let mut entries = client.get_audit_log(lines, None).await?;
+    if entries.is_empty() && !follow {
+        println!("{}", t!("audit-empty"));
+    }
+    let mut last_id = None;
+    loop {
+        for entry in entries {
+            println!(
+                "{}",
+                t!(
+                    "audit-line",
+                    time = time_format.format(entry.created_at),
+                    actor = entry.actor,
+                    method = entry.method,
+                    path = entry.path,
+                    status = entry.status,
+                )
+            );
+            last_id = Some(entry.id);
+        }
+        if !follow {
+            return Ok(());
+        }
+        tokio::time::sleep(AUDIT_POLL_INTERVAL).await;
+        entries = client.get_audit_log(lines.max(100), last_id).await?;
+    }
+}
@@ -1,6 +1,12 @@
 use anyhow::Result;
 use clap::Subcommand;
-use rutify_sdk::{CreateTokenRequest, LoginRequest, RegisterRequest, RutifyClient, TokenInfo};
+use rutify_client::{TimeFormat, t};
+use rutify_sdk::{
+    CreateTokenRequest, LoginRequest, RegisterRequest, RutifyClient, TokenDefaults, TokenId,
+    TokenInfo,
+};
+
+use crate::config::ProfileStore;
 
 #[derive(Subcommand)]
 pub enum AuthAction {
@@ -20,6 +26,8 @@ pub enum AuthAction {
         /// Password
         password: String,
     },
+    /// Forget the saved user token of the current profile
+    Logout,
     /// Get current user profile
     Profile,
     /// Create a new notification token
@@ -32,50 +40,109 @@ pub enum AuthAction {
         /// Token expiration in hours (default: 24)
         #[arg(long, default_value = "24")]
         expires: u64,
+        /// Title used when a notification sent with this token has none
+        #[arg(long)]
+        default_title: Option<String>,
+        /// Device used when a notification sent with this token has none
+        #[arg(long)]
+        default_device: Option<String>,
+        /// Channel used when a notification sent with this token has none
+        #[arg(long)]
+        default_channel: Option<String>,
+        /// Only accept notifications signed by this public key (see `signing-keygen`)
+        #[arg(long)]
+        signing_key: Option<String>,
     },
     /// List user tokens
     ListTokens,
     /// Delete a token
     DeleteToken {
         /// Token ID
-        id: i32,
+        id: TokenId,
     },
 }
 
-pub async fn handle_auth_command(server: &str, action: AuthAction) -> Result<()> {
-    let client = RutifyClient::new(server);
+/// 需要用户 JWT 的命令在未登录时直接退出
+pub fn require_user_token(client: &RutifyClient) {
+    if !client.has_user_token() {
+        eprintln!("{}", t!("not-logged-in"));
+        eprintln!("{}", t!("login-hint", bin = "rutify-cli"));
+        std::process::exit(1);
+    }
+}
+
+/// 逐个打印 Token 的编号、用途、类型与时间
+pub fn print_tokens(tokens: &[TokenInfo], time_format: &TimeFormat) {
+    for (i, token) in tokens.iter().enumerate() {
+        println!(
+            "  {}. 🆔 {} | 📝 {} | 🔐 {}",
+            i + 1,
+            token.id,
+            token.usage,
+            token.token_type
+        );
+        if let Some(device) = &token.device_info {
+            println!("     📱 {}", device);
+        }
+        println!(
+            "     📅 {} | ⏰ {}",
+            time_format.format(token.created_at),
+            time_format.format(token.expires_at)
+        );
+        if let Some(last_used) = token.last_used_at {
+            println!(
+                "     {}",
+                t!("label-last-used", value = time_format.format(last_used))
+            );
+        }
+        if i < tokens.len() - 1 {
+            println!();
+        }
+    }
+}
 
+pub async fn handle_auth_command(
+    client: &RutifyClient,
+    time_format: &TimeFormat,
+    store: &mut ProfileStore,
+    action: AuthAction,
+) -> Result<()> {
     match action {
         AuthAction::Register {
             username,
             password,
             email,
         } => {
-            println!("🔐 Registering new user...");
+            println!("{}", t!("registering"));
 
             let request = RegisterRequest {
                 username: username.clone(),
                 password,
                 email,
+                ..Default::default()
             };
 
             match client.register(&request).await {
                 Ok(_) => {
-                    println!("✅ User '{}' registered successfully!", username);
+                    println!("{}", t!("registered", username = username));
                     println!(
-                        "💡 You can now login with: rutify-cli auth login --username {} --password <password>",
-                        username
+                        "{}",
+                        t!(
+                            "register-login-hint",
+                            bin = "rutify-cli",
+                            username = username
+                        )
                     );
                 }
                 Err(e) => {
-                    eprintln!("❌ Registration failed: {}", e);
+                    eprintln!("{}", t!("register-failed", error = e));
                     std::process::exit(1);
                 }
             }
         }
 
         AuthAction::Login { username, password } => {
-            println!("🔑 Logging in...");
+            println!("{}", t!("logging-in"));
 
             let request = LoginRequest {
                 username: username.clone(),
@@ -84,52 +151,73 @@ pub async fn handle_auth_command(server: &str, action: AuthAction) -> Result<()>
 
             match client.login(&request).await {
                 Ok(response) => {
-                    println!("✅ Login successful!");
-                    println!("👤 User: {}", response.username);
-                    println!("📧 Email: {}", response.email);
-                    println!("🔐 Role: {}", response.role);
-                    println!("⏰ Expires at: {}", response.expires_at);
-                    println!("🎫 JWT Token: {}", response.jwt_token);
-                    println!();
-                    println!("💡 Save this token for future API calls:");
-                    println!("   export RUTIFY_USER_TOKEN=\"{}\"", response.jwt_token);
+                    println!("{}", t!("login-success"));
+                    println!("{}", t!("label-user", value = response.username));
+                    println!("{}", t!("label-email", value = response.email));
+                    println!("{}", t!("label-role", value = response.role));
+                    println!(
+                        "{}",
+                        t!(
+                            "label-expires-at",
+                            value = time_format.format(response.expires_at)
+                        )
+                    );
+                    store.update(|profile| {
+                        profile.server = Some(client.base_url.clone());
+                        profile.user_token = Some(response.jwt_token.clone());
+                    })?;
+                    println!("{}", t!("token-saved-to-profile", profile = store.name));
                 }
                 Err(e) => {
-                    eprintln!("❌ Login failed: {}", e);
+                    eprintln!("{}", t!("login-failed", error = e));
                     std::process::exit(1);
                 }
             }
         }
 
-        AuthAction::Profile => {
-            let user_token = std::env::var("RUTIFY_USER_TOKEN")
-                .unwrap_or_else(|_| {
-                    eprintln!("❌ RUTIFY_USER_TOKEN environment variable not set");
-                    eprintln!("💡 Please login first: rutify-cli auth login --username <user> --password <pass>");
-                    std::process::exit(1);
-                });
+        AuthAction::Logout => {
+            store.update(|profile| profile.user_token = None)?;
+            client.clear_user_token();
+            println!("{}", t!("logged-out", profile = store.name));
+        }
 
-            let client = client.with_user_token(&user_token);
+        AuthAction::Profile => {
+            require_user_token(client);
 
-            println!("👤 Getting user profile...");
+            println!("{}", t!("user-profile-loading"));
 
             match client.get_user_profile().await {
                 Ok(profile) => {
-                    println!("✅ User Profile:");
-                    println!("  🆔 ID: {}", profile.id);
-                    println!("  📝 Usage: {}", profile.usage);
-                    println!("  🔐 Type: {}", profile.token_type);
+                    println!("{}", t!("user-profile-header"));
+                    println!("  {}", t!("label-id", value = profile.id));
+                    println!("  {}", t!("label-usage", value = profile.usage));
+                    println!("  {}", t!("label-type", value = profile.token_type));
                     if let Some(device) = profile.device_info {
-                        println!("  📱 Device: {}", device);
+                        println!("  {}", t!("label-device", value = device));
                     }
-                    println!("  📅 Created: {}", profile.created_at);
-                    println!("  ⏰ Expires: {}", profile.expires_at);
+                    println!(
+                        "  {}",
+                        t!(
+                            "label-created",
+                            value = time_format.format(profile.created_at)
+                        )
+                    );
+                    println!(
+                        "  {}",
+                        t!(
+                            "label-expires",
+                            value = time_format.format(profile.expires_at)
+                        )
+                    );
                     if let Some(last_used) = profile.last_used_at {
-                        println!("  🔄 Last Used: {}", last_used);
+                        println!(
+                            "  {}",
+                            t!("label-last-used", value = time_format.format(last_used))
+                        );
                     }
                 }
                 Err(e) => {
-                    eprintln!("❌ Failed to get profile: {}", e);
+                    eprintln!("{}", t!("user-profile-failed", error = e));
                     std::process::exit(1);
                 }
             }
@@ -139,108 +227,83 @@ pub async fn handle_auth_command(server: &str, action: AuthAction) -> Result<()>
             usage,
             device,
             expires,
+            default_title,
+            default_device,
+            default_channel,
+            signing_key,
         } => {
-            let user_token = std::env::var("RUTIFY_USER_TOKEN")
-                .unwrap_or_else(|_| {
-                    eprintln!("❌ RUTIFY_USER_TOKEN environment variable not set");
-                    eprintln!("💡 Please login first: rutify-cli auth login --username <user> --password <pass>");
-                    std::process::exit(1);
-                });
+            require_user_token(client);
 
-            let client = client.with_user_token(&user_token);
-
-            println!("🎫 Creating notification token...");
+            println!("{}", t!("token-creating"));
 
             let request = CreateTokenRequest {
                 usage: usage.clone(),
                 expires_in_hours: Some(expires),
                 device_info: device,
+                defaults: TokenDefaults {
+                    title: default_title,
+                    device: default_device,
+                    channel: default_channel,
+                },
+                signing_key,
             };
 
             match client.create_notify_token(&request).await {
                 Ok(response) => {
-                    println!("✅ Token created successfully!");
-                    println!("🎫 Token: {}", response.token);
-                    println!("🆔 Token ID: {}", response.token_id);
-                    println!("📝 Usage: {}", response.usage);
-                    println!("🔐 Type: {}", response.token_type);
-                    println!("⏰ Expires at: {}", response.expires_at);
-                    println!();
-                    println!("💡 Use this token for notifications:");
-                    println!("   export RUTIFY_TOKEN=\"{}\"", response.token);
+                    println!("{}", t!("token-created"));
+                    println!("{}", t!("label-token", value = response.token));
+                    println!("{}", t!("label-token-id", value = response.token_id));
+                    println!("{}", t!("label-usage", value = response.usage));
+                    println!("{}", t!("label-type", value = response.token_type));
+                    println!(
+                        "{}",
+                        t!(
+                            "label-expires-at",
+                            value = time_format.format(response.expires_at)
+                        )
+                    );
+                    store.update(|profile| {
+                        profile.server = Some(client.base_url.clone());
+                        profile.token = Some(response.token.clone());
+                    })?;
+                    println!("{}", t!("notify-token-saved", profile = store.name));
                 }
                 Err(e) => {
-                    eprintln!("❌ Failed to create token: {}", e);
+                    eprintln!("{}", t!("token-create-failed", error = e));
                     std::process::exit(1);
                 }
             }
         }
 
         AuthAction::ListTokens => {
-            let user_token = std::env::var("RUTIFY_USER_TOKEN")
-                .unwrap_or_else(|_| {
-                    eprintln!("❌ RUTIFY_USER_TOKEN environment variable not set");
-                    eprintln!("💡 Please login first: rutify-cli auth login --username <user> --password <pass>");
-                    std::process::exit(1);
-                });
+            require_user_token(client);
 
-            let client = client.with_user_token(&user_token);
-
-            println!("📋 Listing user tokens...");
+            println!("{}", t!("tokens-listing"));
 
             match client.get_user_tokens().await {
+                Ok(tokens) if tokens.is_empty() => println!("{}", t!("tokens-empty")),
                 Ok(tokens) => {
-                    let tokens: Vec<TokenInfo> = tokens;
-                    if tokens.is_empty() {
-                        println!("📭 No tokens found.");
-                    } else {
-                        println!("🎫 User Tokens ({} total):", tokens.len());
-                        for (i, token) in tokens.iter().enumerate() {
-                            println!(
-                                "  {}. 🆔 {} | 📝 {} | 🔐 {}",
-                                i + 1,
-                                token.id,
-                                token.usage,
-                                token.token_type
-                            );
-                            if let Some(device) = &token.device_info {
-                                println!("     📱 {}", device);
-                            }
-                            println!("     📅 {} | ⏰ {}", token.created_at, token.expires_at);
-                            if let Some(last_used) = &token.last_used_at {
-                                println!("     🔄 Last Used: {}", last_used);
-                            }
-                            if i < tokens.len() - 1 {
-                                println!();
-                            }
-                        }
-                    }
+                    println!("{}", t!("tokens-header", count = tokens.len()));
+                    print_tokens(&tokens, time_format);
                 }
                 Err(e) => {
-                    eprintln!("❌ Failed to list tokens: {}", e);
+                    eprintln!("{}", t!("tokens-list-failed", error = e));
                     std::process::exit(1);
                 }
             }
         }
 
         AuthAction::DeleteToken { id } => {
-            let user_token = std::env::var("RUTIFY_USER_TOKEN")
-                .unwrap_or_else(|_| {
-                    eprintln!("❌ RUTIFY_USER_TOKEN environment variable not set");
-                    eprintln!("💡 Please login first: rutify-cli auth login --username <user> --password <pass>");
-                    std::process::exit(1);
-                });
-
-            let client = client.with_user_token(&user_token);
+            require_user_token(client);
 
-            println!("🗑️  Deleting token {}...", id);
+            println!("{}", t!("token-deleting", id = id));
 
             match client.delete_user_token(id).await {
                 Ok(_) => {
-                    println!("✅ Token {} deleted successfully!", id);
+                    println!("{}", t!("token-deleted", id = id));
                 }
                 Err(e) => {
-                    eprintln!("❌ Failed to delete token: {}", e);
+                    eprintln!("{}", t!("token-delete-failed", error = e));
                     std::process::exit(1);
                 }
             }
@@ -0,0 +1,166 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// 未指定 `--profile` 且没有设置默认配置档时使用的名称
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// 单个配置档：服务器地址与已保存的 Token
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Profile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server: Option<String>,
+    /// 通知 Token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// 用户 JWT
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_token: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CliConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default_profile: Option<String>,
+    /// 输出语言，例如 `zh-CN`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+    #[serde(default)]
+    profiles: BTreeMap<String, Profile>,
+}
+
+/// CLI 配置文件（JSON），保存各配置档的服务器与 Token
+///
+/// 路径依次取 `RUTIFY_CONFIG`、`$XDG_CONFIG_HOME/rutify/cli.json`、
+/// `~/.config/rutify/cli.json`（Windows 为 `%APPDATA%\rutify\cli.json`）。
+pub struct ProfileStore {
+    path: PathBuf,
+    config: CliConfig,
+    /// 当前命令使用的配置档
+    pub name: String,
+}
+
+impl ProfileStore {
+    /// 读取配置文件，文件不存在时视为空配置
+    pub fn load(profile: Option<String>) -> Result<Self> {
+        let path = config_path()?;
+        let config = match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text)
+                .with_context(|| format!("Invalid config file: {}", path.display()))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => CliConfig::default(),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+            }
+        };
+        let name = profile
+            .or_else(|| config.default_profile.clone())
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+
+        Ok(Self { path, config, name })
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// 当前配置档，未保存过时返回空配置档
+    pub fn profile(&self) -> Profile {
+        self.config
+            .profiles
+            .get(&self.name)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// 修改当前配置档并立即写回文件
+    pub fn update(&mut self, change: impl FnOnce(&mut Profile)) -> Result<()> {
+        change(self.config.profiles.entry(self.name.clone()).or_default());
+        self.save()
+    }
+
+    /// 全部配置档名称与内容
+    pub fn profiles(&self) -> impl Iterator<Item = (&String, &Profile)> {
+        self.config.profiles.iter()
+    }
+
+    pub fn default_profile(&self) -> &str {
+        self.config
+            .default_profile
+            .as_deref()
+            .unwrap_or(DEFAULT_PROFILE)
+    }
+
+    /// 配置文件中设置的输出语言
+    pub fn language(&self) -> Option<&str> {
+        self.config.language.as_deref()
+    }
+
+    /// 设置未指定 `--profile` 时使用的配置档
+    pub fn set_default_profile(&mut self, name: &str) -> Result<()> {
+        self.config.default_profile = Some(name.to_string());
+        self.save()
+    }
+
+    /// 删除配置档，返回是否存在
+    pub fn remove(&mut self, name: &str) -> Result<bool> {
+        let removed = self.config.profiles.remove(name).is_some();
+        if self.config.default_profile.as_deref() == Some(name) {
+            self.config.default_profile = None;
+        }
+        self.save()?;
+        Ok(removed)
+    }
+
+    fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let text = serde_json::to_string_pretty(&self.config)?;
+        rutify_client::settings::write_private(&self.path, text.as_bytes())
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+}
+
+fn config_path() -> Result<PathBuf> {
+    if let Ok(path) = std::env::var("RUTIFY_CONFIG") {
+        return Ok(PathBuf::from(path));
+    }
+    rutify_client::settings::config_dir()
+        .map(|dir| dir.join("cli.json"))
+        .context("Cannot determine config directory, set RUTIFY_CONFIG")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_roundtrip() {
+        let path = std::env::temp_dir().join(format!("rutify-cli-{}.json", std::process::id()));
+        let mut store = ProfileStore {
+            path: path.clone(),
+            config: CliConfig::default(),
+            name: "staging".to_string(),
+        };
+        assert!(store.profile().token.is_none());
+
+        store
+            .update(|profile| {
+                profile.server = Some("http://staging:3000".to_string());
+                profile.token = Some("notify".to_string());
+            })
+            .unwrap();
+        store.set_default_profile("staging").unwrap();
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        let config: CliConfig = serde_json::from_str(&text).unwrap();
+        assert_eq!(config.default_profile.as_deref(), Some("staging"));
+        assert_eq!(config.profiles["staging"].token.as_deref(), Some("notify"));
+
+        assert!(store.remove("staging").unwrap());
+        assert_eq!(store.default_profile(), DEFAULT_PROFILE);
+        std::fs::remove_file(path).unwrap();
+    }
+}
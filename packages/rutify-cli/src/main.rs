@@ -1,19 +1,55 @@
-use clap::{Parser, Subcommand};
+use clap::parser::ValueSource;
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use rutify_client::i18n::{self, Language};
 use rutify_client::{
-    ClientState, WebSocketNotification, format_notification, format_stats, health_check,
-    send_and_listen,
+    ClientState, DEFAULT_LISTEN_TIMEOUT, TimeFormat, TimeZoneSetting, WebSocketNotification,
+    format_notification, health_check, send_and_listen, t,
 };
+use rutify_sdk::{E2eKey, NotifySigner};
+use std::path::PathBuf;
+use std::time::Duration;
 
+mod admin_commands;
 mod auth_commands;
+mod config;
+mod monitor_commands;
+mod profile_commands;
+mod run_command;
+mod self_update_command;
+mod send_command;
+mod shell;
+mod silence_commands;
+mod stats_command;
+mod tail_command;
 mod token_commands;
+mod tui;
+
+use config::ProfileStore;
 
 #[derive(Parser)]
 #[command(name = "rutify-cli")]
 #[command(about = "Rutify CLI client")]
 struct Cli {
+    /// Server URL, defaults to the profile's saved server
     #[arg(short, long, default_value = "http://127.0.0.1:3000")]
     server: String,
 
+    /// Profile holding the server and saved tokens (env: RUTIFY_PROFILE)
+    #[arg(short, long, global = true)]
+    profile: Option<String>,
+
+    /// Timezone for timestamps: local, UTC or an IANA name such as Europe/Berlin (env: RUTIFY_TIMEZONE)
+    #[arg(long, global = true)]
+    timezone: Option<TimeZoneSetting>,
+
+    /// Show timestamps relative to now, e.g. "3 minutes ago"
+    #[arg(long, global = true)]
+    relative: bool,
+
+    /// Output language: en or zh-CN (env: RUTIFY_LANG, then LANG)
+    #[arg(long, global = true)]
+    lang: Option<Language>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -22,11 +58,16 @@ struct Cli {
 enum Commands {
     /// Get all notifications
     Notifies,
-    /// Get server statistics
-    Stats,
+    /// Get server statistics, or keep them on screen with --watch
+    Stats(stats_command::StatsArgs),
     /// Send a notification
     Send {
         /// Notification message
+        #[arg(
+            required_unless_present = "stdin",
+            default_value = "",
+            hide_default_value = true
+        )]
         message: String,
         /// Notification title
         #[arg(long)]
@@ -34,10 +75,46 @@ enum Commands {
         /// Target device
         #[arg(long)]
         device: Option<String>,
+        /// Expire the notification after a duration such as 30m, 2h or 1d
+        #[arg(long, value_parser = silence_commands::parse_duration)]
+        ttl: Option<u64>,
+        /// Read the message from stdin instead
+        #[arg(long, conflicts_with = "message")]
+        stdin: bool,
+        /// With --stdin, send every non-empty line as its own notification
+        #[arg(long, conflicts_with = "message")]
+        per_line: bool,
+        /// With --stdin, truncate each message after this many bytes
+        #[arg(long, conflicts_with = "message", default_value_t = send_command::DEFAULT_MAX_BYTES)]
+        max_bytes: usize,
+        /// Encrypt the title and message end-to-end with the shared key in this file
+        #[arg(long, value_name = "KEY_FILE")]
+        e2e_key: Option<PathBuf>,
+        /// Sign the title and message with the private key in this file (requires --title)
+        #[arg(
+            long,
+            value_name = "KEY_FILE",
+            conflicts_with = "e2e_key",
+            requires = "title"
+        )]
+        sign_key: Option<PathBuf>,
     },
-    /// Listen for WebSocket notifications
-    Listen,
-    /// Send a notification and listen for response
+    /// Generate a shared key file for --e2e-key
+    E2eKeygen {
+        /// Where to write the key; fails if the file already exists
+        out: PathBuf,
+    },
+    /// Generate a private key file for --sign-key and print its public key
+    SigningKeygen {
+        /// Where to write the private key; fails if the file already exists
+        out: PathBuf,
+    },
+    /// Run a command and send a success or failure notification when it exits
+    Run(run_command::RunArgs),
+    /// Print recent notifications, then follow new ones live
+    #[command(alias = "listen")]
+    Tail(tail_command::TailArgs),
+    /// Send a notification and wait until a subscriber confirms delivery
     SendAndListen {
         /// Notification message
         #[arg(long)]
@@ -48,8 +125,11 @@ enum Commands {
         /// Target device
         #[arg(long)]
         device: Option<String>,
+        /// Seconds to wait for delivery confirmation
+        #[arg(long, default_value_t = DEFAULT_LISTEN_TIMEOUT.as_secs())]
+        timeout: u64,
     },
-    /// List available devices
+    /// List devices with per-device notification counts
     Devices,
     /// Server health check
     Health,
@@ -63,154 +143,295 @@ enum Commands {
         #[command(subcommand)]
         action: auth_commands::AuthAction,
     },
+    /// Administrative maintenance (requires an admin login)
+    Admin {
+        #[command(subcommand)]
+        action: admin_commands::AdminAction,
+    },
+    /// Silence management
+    Silence {
+        #[command(subcommand)]
+        action: silence_commands::SilenceAction,
+    },
+    /// Heartbeat monitors that alert when a job stops reporting
+    Monitor {
+        #[command(subcommand)]
+        action: monitor_commands::MonitorAction,
+    },
+    /// Interactive shell sharing one client and WebSocket connection
+    Shell,
+    /// Terminal dashboard with a live notification list, search and details
+    Tui,
+    /// Saved profile management
+    Profile {
+        #[command(subcommand)]
+        action: profile_commands::ProfileAction,
+    },
+    /// Replace this binary with the latest GitHub release
+    SelfUpdate(self_update_command::SelfUpdateArgs),
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let cli = Cli::parse();
-    let mut state = ClientState::new(&cli.server);
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches)?;
+    let mut store =
+        ProfileStore::load(cli.profile.clone().or(std::env::var("RUTIFY_PROFILE").ok()))?;
+
+    // 语言：--lang > RUTIFY_LANG > 配置文件 language > 系统 LANG
+    i18n::init(cli.lang.unwrap_or_else(|| {
+        let configured = std::env::var("RUTIFY_LANG")
+            .ok()
+            .or(store.language().map(str::to_string));
+        Language::detect(configured.as_deref())
+    }));
+
+    // 显式的 --server 与环境变量中的 Token 优先于配置档
+    let profile = store.profile();
+    let server = match (matches.value_source("server"), profile.server) {
+        (Some(ValueSource::DefaultValue), Some(saved)) => saved,
+        _ => cli.server.clone(),
+    };
+    let timezone = match cli.timezone {
+        Some(timezone) => timezone,
+        None => std::env::var("RUTIFY_TIMEZONE")
+            .ok()
+            .map(|value| value.parse())
+            .transpose()
+            .map_err(anyhow::Error::msg)?
+            .unwrap_or_default(),
+    };
+    let state = ClientState::new(&server).with_time_format(TimeFormat::new(timezone, cli.relative));
+    if let Some(token) = std::env::var("RUTIFY_TOKEN").ok().or(profile.token) {
+        state.set_token(&token);
+    }
+    if let Some(user_token) = std::env::var("RUTIFY_USER_TOKEN")
+        .ok()
+        .or(profile.user_token)
+    {
+        state.client.set_user_token(&user_token);
+    }
 
     match cli.command {
         Commands::Notifies => match state.get_notifies().await {
             Ok(notifies) => {
-                println!("📬 Notifications ({} total):", notifies.len());
+                println!("{}", t!("notifies-header", count = notifies.len()));
                 for (i, notify) in notifies.iter().enumerate() {
-                    println!("  {}. {}", i + 1, format_notification(notify));
+                    println!(
+                        "  {}. {}",
+                        i + 1,
+                        format_notification(notify, &state.time_format)
+                    );
                     if i < notifies.len() - 1 {
                         println!();
                     }
                 }
             }
             Err(e) => {
-                eprintln!("❌ Failed to get notifies: {}", e);
+                eprintln!("{}", t!("notifies-failed", error = e));
                 std::process::exit(1);
             }
         },
-        Commands::Stats => match state.get_stats().await {
-            Ok(stats) => {
-                println!("📊 Server Statistics:");
-                println!("  {}", format_stats(&stats));
-            }
-            Err(e) => {
-                eprintln!("❌ Failed to get stats: {}", e);
-                std::process::exit(1);
+        Commands::Stats(args) => {
+            stats_command::handle_stats_command(&state, args).await?;
+        }
+        Commands::Send {
+            stdin: true,
+            title,
+            device,
+            ttl,
+            per_line,
+            max_bytes,
+            e2e_key,
+            sign_key,
+            ..
+        } => {
+            let protection = send_command::Protection::load(e2e_key, sign_key)?;
+            let sent = send_command::send_stdin(
+                &state,
+                title,
+                device,
+                ttl,
+                per_line,
+                max_bytes,
+                &protection,
+            )
+            .await;
+            match sent {
+                Ok(count) => println!("{}", t!("send-stdin-success", count = count)),
+                Err(e) => {
+                    eprintln!("{}", t!("send-failed", error = e));
+                    std::process::exit(1);
+                }
             }
-        },
+        }
         Commands::Send {
             message,
             title,
             device,
+            ttl,
+            e2e_key,
+            sign_key,
+            ..
         } => {
-            let input = rutify_sdk::NotificationInput {
+            let mut input = rutify_sdk::NotificationInput {
                 notify: message,
                 title,
                 device,
+                ttl_secs: ttl,
+                ..Default::default()
             };
+            send_command::Protection::load(e2e_key, sign_key)?.apply(&mut input)?;
 
             match state.send_notification(&input).await {
                 Ok(_) => {
-                    println!("✅ Notification sent successfully!");
+                    println!("{}", t!("send-success"));
                 }
                 Err(e) => {
-                    eprintln!("❌ Failed to send notification: {}", e);
+                    eprintln!("{}", t!("send-failed", error = e));
                     std::process::exit(1);
                 }
             }
         }
-        Commands::Listen => {
-            println!("🎧 Listening for WebSocket notifications...");
-            println!("   Press Ctrl+C to stop");
-
-            match state.listen_websocket_updates().await {
-                Ok(mut rx) => {
-                    while let Some(notification) = rx.recv().await {
-                        match notification {
-                            WebSocketNotification::Event(event) => {
-                                println!("🔔 New notification:");
-                                println!("   Title: {}", event.data.title);
-                                println!("   Message: {}", event.data.notify);
-                                println!("   Device: {}", event.data.device);
-                                println!(
-                                    "   Time: {}",
-                                    event.timestamp.format("%Y-%m-%d %H:%M:%S")
-                                );
-                            }
-                            WebSocketNotification::Text(text) => {
-                                println!("📝 Text message: {}", text);
-                            }
-                            WebSocketNotification::Error { message } => {
-                                eprintln!("❌ Error: {}", message);
-                            }
-                            WebSocketNotification::Close => {
-                                println!("🔌 Connection closed");
-                                break;
-                            }
-                        }
-                    }
-                }
-                Err(e) => {
-                    eprintln!("❌ Failed to connect WebSocket: {}", e);
-                    std::process::exit(1);
-                }
-            }
+        Commands::E2eKeygen { out } => {
+            E2eKey::generate().save(&out)?;
+            println!(
+                "{}",
+                t!("e2e-key-generated", path = out.display().to_string())
+            );
+        }
+        Commands::SigningKeygen { out } => {
+            let signer = NotifySigner::generate();
+            signer.save(&out)?;
+            println!(
+                "{}",
+                t!("signing-key-generated", path = out.display().to_string())
+            );
+            println!("   {}", signer.public_key());
+        }
+        Commands::Tail(args) => {
+            tail_command::handle_tail_command(&state, args).await?;
+        }
+        Commands::Run(args) => {
+            let code = run_command::handle_run_command(&state, args).await?;
+            std::process::exit(code);
         }
         Commands::SendAndListen {
             message,
             title,
             device,
+            timeout,
         } => {
-            println!("📤 Sending notification and listening for response...");
+            println!("{}", t!("send-listen-start"));
 
-            match send_and_listen(&state, message, title, device).await {
+            let timeout = Duration::from_secs(timeout);
+            match send_and_listen(&state, message, title, device, timeout).await {
                 Ok(Some(notification)) => match notification {
                     WebSocketNotification::Event(event) => {
-                        println!("🔔 Response received:");
-                        println!("   Title: {}", event.data.title);
-                        println!("   Message: {}", event.data.notify);
-                        println!("   Device: {}", event.data.device);
-                        println!("   Time: {}", event.timestamp.format("%Y-%m-%d %H:%M:%S"));
+                        println!("{}", t!("response-received"));
+                        println!("   {}", t!("event-title", value = event.data.title));
+                        println!("   {}", t!("event-message", value = event.data.notify));
+                        println!("   {}", t!("event-device", value = event.data.device));
+                        println!(
+                            "   {}",
+                            t!(
+                                "event-time",
+                                value = state.time_format.format(event.timestamp)
+                            )
+                        );
                     }
                     WebSocketNotification::Text(text) => {
-                        println!("📝 Response: {}", text);
+                        println!("{}", t!("response-text", text = text));
                     }
                     WebSocketNotification::Error { message } => {
-                        eprintln!("❌ Error: {}", message);
+                        eprintln!("{}", t!("error-message", message = message));
                     }
                     WebSocketNotification::Close => {
-                        println!("🔌 Connection closed");
+                        println!("{}", t!("connection-closed"));
                     }
                 },
                 Ok(None) => {
-                    println!("⏰ No response received");
+                    println!("{}", t!("no-response"));
                 }
                 Err(e) => {
-                    eprintln!("❌ Failed to send and listen: {}", e);
+                    eprintln!("{}", t!("send-listen-failed", error = e));
                     std::process::exit(1);
                 }
             }
         }
-        Commands::Devices => {
-            // This would be implemented when we have device management API
-            println!("📱 Device management not yet implemented");
-        }
+        Commands::Devices => match state.client.get_device_stats().await {
+            Ok(devices) if devices.is_empty() => println!("{}", t!("devices-empty")),
+            Ok(devices) => {
+                println!("{}", t!("devices-header", count = devices.len()));
+                for device in devices {
+                    let last = device
+                        .last_notified_at
+                        .map(|at| state.time_format.format(at))
+                        .unwrap_or_else(|| "-".to_string());
+                    println!(
+                        "  {}",
+                        t!(
+                            "devices-line",
+                            device = device.device,
+                            today = device.today_count,
+                            total = device.total_count,
+                            last = last,
+                        )
+                    );
+                }
+            }
+            Err(e) => {
+                eprintln!("{}", t!("devices-failed", error = e));
+                std::process::exit(1);
+            }
+        },
         Commands::Health => match health_check(&state).await {
             Ok(true) => {
-                println!("✅ Server is healthy and responsive");
+                println!("{}", t!("health-ok"));
             }
             Ok(false) => {
-                eprintln!("❌ Server health check failed");
+                eprintln!("{}", t!("health-failed"));
                 std::process::exit(1);
             }
             Err(e) => {
-                eprintln!("❌ Server health check failed: {}", e);
+                eprintln!("{}", t!("health-error", error = e));
                 std::process::exit(1);
             }
         },
         Commands::Token { action } => {
-            token_commands::handle_token_command(&mut state, action).await?;
+            token_commands::handle_token_command(&state, &mut store, action).await?;
         }
         Commands::Auth { action } => {
-            auth_commands::handle_auth_command(&cli.server, action).await?;
+            auth_commands::handle_auth_command(
+                &state.client,
+                &state.time_format,
+                &mut store,
+                action,
+            )
+            .await?;
+        }
+        Commands::Admin { action } => {
+            admin_commands::handle_admin_command(&state.client, &state.time_format, action).await?;
+        }
+        Commands::Silence { action } => {
+            silence_commands::handle_silence_command(&state.client, &state.time_format, action)
+                .await?;
+        }
+        Commands::Monitor { action } => {
+            monitor_commands::handle_monitor_command(&state.client, &state.time_format, action)
+                .await?;
+        }
+        Commands::Shell => {
+            shell::run_shell(&state).await?;
+        }
+        Commands::Tui => {
+            tui::run_tui(&state).await?;
+        }
+        Commands::Profile { action } => {
+            profile_commands::handle_profile_command(&mut store, action)?;
+        }
+        Commands::SelfUpdate(args) => {
+            self_update_command::handle_self_update_command(args).await?;
         }
     }
 
@@ -246,7 +467,7 @@ mod tests {
 
         assert_eq!(cli.server, "http://127.0.0.1:3000");
         match cli.command {
-            Commands::Stats => {} // Expected
+            Commands::Stats(args) => assert_eq!(args.watch, None),
             _ => panic!("Expected Stats command"),
         }
     }
@@ -269,6 +490,7 @@ mod tests {
                 message,
                 title,
                 device,
+                ..
             } => {
                 assert_eq!(message, "Hello World");
                 assert_eq!(title, Some("Test Title".to_string()));
@@ -288,6 +510,7 @@ mod tests {
                 message,
                 title,
                 device,
+                ..
             } => {
                 assert_eq!(message, "Hello World");
                 assert_eq!(title, None);
@@ -305,8 +528,18 @@ mod tests {
             vec!["rutify-cli", "send", "test"],
             vec!["rutify-cli", "listen"],
             vec!["rutify-cli", "send-and-listen", "--message", "test"],
+            vec![
+                "rutify-cli",
+                "send-and-listen",
+                "--message",
+                "test",
+                "--timeout",
+                "5",
+            ],
             vec!["rutify-cli", "devices"],
             vec!["rutify-cli", "health"],
+            vec!["rutify-cli", "shell"],
+            vec!["rutify-cli", "tail", "-n", "5", "--device", "nas", "--json"],
         ];
 
         for args in commands {
@@ -315,6 +548,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_silence_add_parsing() {
+        let args = vec![
+            "rutify-cli",
+            "silence",
+            "add",
+            "--device",
+            "web1",
+            "--for",
+            "2h",
+        ];
+        let cli = Cli::try_parse_from(args).unwrap();
+
+        match cli.command {
+            Commands::Silence {
+                action:
+                    silence_commands::SilenceAction::Add {
+                        device, duration, ..
+                    },
+            } => {
+                assert_eq!(device.as_deref(), Some("web1"));
+                assert_eq!(duration, "2h");
+            }
+            _ => panic!("Expected Silence Add command"),
+        }
+    }
+
+    #[test]
+    fn test_monitor_beat_parsing() {
+        let cli = Cli::try_parse_from(["rutify-cli", "monitor", "beat", "nightly-backup"]).unwrap();
+
+        match cli.command {
+            Commands::Monitor {
+                action: monitor_commands::MonitorAction::Beat { name },
+            } => assert_eq!(name, "nightly-backup"),
+            _ => panic!("Expected Monitor Beat command"),
+        }
+    }
+
     #[test]
     fn test_unicode_arguments() {
         let args = vec![
@@ -336,6 +608,7 @@ mod tests {
                     message,
                     title,
                     device,
+                    ..
                 } => {
                     assert_eq!(message, "🚀 Hello World 🌍");
                     assert_eq!(title.unwrap(), "测试标题");
@@ -345,4 +618,198 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_profile_flag_is_global() {
+        let args = vec!["rutify-cli", "token", "status", "--profile", "staging"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        assert_eq!(cli.profile.as_deref(), Some("staging"));
+
+        let args = vec!["rutify-cli", "profile", "use", "staging"];
+        let cli = Cli::try_parse_from(args).unwrap();
+        assert!(cli.profile.is_none());
+        match cli.command {
+            Commands::Profile {
+                action: profile_commands::ProfileAction::Use { name },
+            } => assert_eq!(name, "staging"),
+            _ => panic!("Expected Profile command"),
+        }
+    }
+
+    #[test]
+    fn test_send_stdin_parsing() {
+        let args = vec![
+            "rutify-cli",
+            "send",
+            "--stdin",
+            "--per-line",
+            "--title",
+            "job log",
+        ];
+        match Cli::try_parse_from(args).unwrap().command {
+            Commands::Send {
+                stdin,
+                per_line,
+                max_bytes,
+                title,
+                ..
+            } => {
+                assert!(stdin);
+                assert!(per_line);
+                assert_eq!(max_bytes, send_command::DEFAULT_MAX_BYTES);
+                assert_eq!(title.as_deref(), Some("job log"));
+            }
+            _ => panic!("Expected Send command"),
+        }
+
+        assert!(Cli::try_parse_from(["rutify-cli", "send"]).is_err());
+        assert!(Cli::try_parse_from(["rutify-cli", "send", "hi", "--stdin"]).is_err());
+        assert!(Cli::try_parse_from(["rutify-cli", "send", "hi", "--per-line"]).is_err());
+
+        match Cli::try_parse_from(["rutify-cli", "send", "hi", "--e2e-key", "team.key"])
+            .unwrap()
+            .command
+        {
+            Commands::Send { e2e_key, .. } => {
+                assert_eq!(e2e_key, Some(PathBuf::from("team.key")));
+            }
+            _ => panic!("Expected Send command"),
+        }
+        assert!(Cli::try_parse_from(["rutify-cli", "send", "hi", "--sign-key", "ci.key"]).is_err());
+        assert!(
+            Cli::try_parse_from([
+                "rutify-cli",
+                "send",
+                "hi",
+                "--title",
+                "CI",
+                "--sign-key",
+                "ci.key",
+                "--e2e-key",
+                "team.key",
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_run_parsing() {
+        let args = vec!["rutify-cli", "run", "--device", "ci", "--", "make", "-j4"];
+        match Cli::try_parse_from(args).unwrap().command {
+            Commands::Run(args) => {
+                assert_eq!(args.device.as_deref(), Some("ci"));
+                assert_eq!(args.command, vec!["make", "-j4"]);
+                assert_eq!(args.tail, 20);
+            }
+            _ => panic!("Expected Run command"),
+        }
+
+        assert!(Cli::try_parse_from(["rutify-cli", "run"]).is_err());
+    }
+
+    #[test]
+    fn test_admin_purge_parsing() {
+        let args = vec![
+            "rutify-cli",
+            "admin",
+            "purge",
+            "--older-than",
+            "30d",
+            "--read",
+            "--dry-run",
+        ];
+        match Cli::try_parse_from(args).unwrap().command {
+            Commands::Admin {
+                action:
+                    admin_commands::AdminAction::Purge {
+                        older_than,
+                        read,
+                        dry_run,
+                        ..
+                    },
+            } => {
+                assert_eq!(older_than.as_deref(), Some("30d"));
+                assert!(read && dry_run);
+            }
+            _ => panic!("Expected Admin Purge command"),
+        }
+
+        assert!(
+            Cli::try_parse_from(["rutify-cli", "admin", "purge", "--read", "--unread"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_admin_groups_parsing() {
+        match Cli::try_parse_from(["rutify-cli", "admin", "users", "disable", "mallory"])
+            .unwrap()
+            .command
+        {
+            Commands::Admin {
+                action:
+                    admin_commands::AdminAction::Users {
+                        action: admin_commands::UserAction::Disable { username },
+                    },
+            } => assert_eq!(username, "mallory"),
+            _ => panic!("Expected Admin Users Disable command"),
+        }
+
+        match Cli::try_parse_from(["rutify-cli", "admin", "audit", "tail", "-n", "5", "-f"])
+            .unwrap()
+            .command
+        {
+            Commands::Admin {
+                action:
+                    admin_commands::AdminAction::Audit {
+                        action: admin_commands::AuditAction::Tail { lines, follow },
+                    },
+            } => {
+                assert_eq!(lines, 5);
+                assert!(follow);
+            }
+            _ => panic!("Expected Admin Audit Tail command"),
+        }
+
+        let commands = vec![
+            vec!["rutify-cli", "admin", "users", "list"],
+            vec!["rutify-cli", "admin", "users", "promote", "alice"],
+            vec!["rutify-cli", "admin", "tokens", "list", "--all"],
+            vec!["rutify-cli", "admin", "settings", "get"],
+            vec![
+                "rutify-cli",
+                "admin",
+                "settings",
+                "set",
+                "rate_limit_per_sec",
+                "50",
+            ],
+        ];
+        for args in commands {
+            assert!(
+                Cli::try_parse_from(args.clone()).is_ok(),
+                "Failed to parse: {:?}",
+                args
+            );
+        }
+        assert!(
+            Cli::try_parse_from(["rutify-cli", "admin", "settings", "set", "purge_expired"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_self_update_parsing() {
+        match Cli::try_parse_from(["rutify-cli", "self-update", "--channel", "nightly", "-y"])
+            .unwrap()
+            .command
+        {
+            Commands::SelfUpdate(args) => {
+                assert_eq!(args.channel, self_update_command::Channel::Nightly);
+                assert!(args.yes);
+                assert!(!args.check);
+            }
+            _ => panic!("Expected SelfUpdate command"),
+        }
+        assert!(Cli::try_parse_from(["rutify-cli", "self-update", "--channel", "beta"]).is_err());
+    }
 }
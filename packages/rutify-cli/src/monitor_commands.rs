@@ -0,0 +1,136 @@
+use anyhow::Result;
+use clap::Subcommand;
+use rutify_client::{TimeFormat, t};
+use rutify_sdk::{CreateMonitorRequest, RutifyClient};
+
+use crate::auth_commands::require_user_token;
+use crate::silence_commands::parse_duration;
+
+#[derive(Subcommand)]
+pub enum MonitorAction {
+    /// Send a heartbeat (uses the notify token)
+    Beat {
+        /// Monitor name
+        name: String,
+    },
+    /// Create a monitor that alerts when heartbeats stop
+    Add {
+        /// Monitor name, used in the heartbeat URL
+        name: String,
+        /// Expected heartbeat interval such as 5m, 1h or 1d
+        #[arg(long)]
+        every: String,
+        /// Extra time allowed before alerting, defaults to a tenth of the interval (min 1m)
+        #[arg(long)]
+        grace: Option<String>,
+        /// Alert priority (1-5)
+        #[arg(long)]
+        priority: Option<u8>,
+        /// Channel for the alert notification
+        #[arg(long)]
+        channel: Option<String>,
+    },
+    /// List monitors
+    List,
+    /// Delete a monitor
+    Remove {
+        /// Monitor name
+        name: String,
+    },
+}
+
+pub async fn handle_monitor_command(
+    client: &RutifyClient,
+    time_format: &TimeFormat,
+    action: MonitorAction,
+) -> Result<()> {
+    match action {
+        MonitorAction::Beat { name } => match client.monitor_beat(&name).await {
+            Ok(monitor) => {
+                let due = monitor
+                    .due_at
+                    .map(|due| time_format.format(due))
+                    .unwrap_or_else(|| "-".to_string());
+                println!("{}", t!("monitor-beat-ok", name = monitor.name, due = due));
+            }
+            Err(e) => {
+                eprintln!("{}", t!("monitor-beat-failed", error = e));
+                std::process::exit(1);
+            }
+        },
+        MonitorAction::Add {
+            name,
+            every,
+            grace,
+            priority,
+            channel,
+        } => {
+            require_user_token(client);
+            let request = CreateMonitorRequest {
+                name,
+                interval_secs: parse_duration(&every)?,
+                grace_secs: grace.as_deref().map(parse_duration).transpose()?,
+                priority,
+                channel,
+            };
+
+            match client.create_monitor(&request).await {
+                Ok(monitor) => println!(
+                    "{}",
+                    t!(
+                        "monitor-created",
+                        name = monitor.name,
+                        interval = monitor.interval_secs
+                    )
+                ),
+                Err(e) => {
+                    eprintln!("{}", t!("monitor-create-failed", error = e));
+                    std::process::exit(1);
+                }
+            }
+        }
+        MonitorAction::List => {
+            require_user_token(client);
+            match client.get_monitors().await {
+                Ok(monitors) if monitors.is_empty() => {
+                    println!("{}", t!("monitors-empty"));
+                }
+                Ok(monitors) => {
+                    println!("{}", t!("monitors-header", count = monitors.len()));
+                    for monitor in monitors {
+                        let last = monitor
+                            .last_beat_at
+                            .map(|at| time_format.format(at))
+                            .unwrap_or_else(|| "-".to_string());
+                        println!(
+                            "  {}",
+                            t!(
+                                "monitor-line",
+                                name = monitor.name,
+                                status = monitor.status.as_str(),
+                                interval = monitor.interval_secs,
+                                grace = monitor.grace_secs,
+                                last = last,
+                            )
+                        );
+                    }
+                }
+                Err(e) => {
+                    eprintln!("{}", t!("monitors-list-failed", error = e));
+                    std::process::exit(1);
+                }
+            }
+        }
+        MonitorAction::Remove { name } => {
+            require_user_token(client);
+            match client.delete_monitor(&name).await {
+                Ok(_) => println!("{}", t!("monitor-removed", name = name)),
+                Err(e) => {
+                    eprintln!("{}", t!("monitor-remove-failed", error = e));
+                    std::process::exit(1);
+                }
+            }
+        }
+    }
+    Ok(())
+}
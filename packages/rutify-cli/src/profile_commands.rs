@@ -0,0 +1,67 @@
+use anyhow::Result;
+use clap::Subcommand;
+use rutify_client::t;
+
+use crate::config::ProfileStore;
+
+#[derive(Subcommand)]
+pub enum ProfileAction {
+    /// List saved profiles
+    List,
+    /// Use a profile by default when --profile is not given
+    Use {
+        /// Profile name
+        name: String,
+    },
+    /// Delete a saved profile and its tokens
+    Remove {
+        /// Profile name
+        name: String,
+    },
+}
+
+pub fn handle_profile_command(store: &mut ProfileStore, action: ProfileAction) -> Result<()> {
+    match action {
+        ProfileAction::List => {
+            let default_profile = store.default_profile().to_string();
+            let mut empty = true;
+            for (name, profile) in store.profiles() {
+                empty = false;
+                let marker = if *name == default_profile { "*" } else { " " };
+                let answer = |saved: bool| {
+                    if saved {
+                        t!("answer-yes")
+                    } else {
+                        t!("answer-no")
+                    }
+                };
+                println!(
+                    "{}",
+                    t!(
+                        "profile-line",
+                        marker = marker,
+                        name = name,
+                        server = profile.server.as_deref().unwrap_or("-"),
+                        token = answer(profile.token.is_some()),
+                        logged_in = answer(profile.user_token.is_some()),
+                    )
+                );
+            }
+            if empty {
+                println!("{}", t!("profiles-empty", path = store.path().display()));
+            }
+        }
+        ProfileAction::Use { name } => {
+            store.set_default_profile(&name)?;
+            println!("{}", t!("profile-default-set", name = name));
+        }
+        ProfileAction::Remove { name } => {
+            if store.remove(&name)? {
+                println!("{}", t!("profile-removed", name = name));
+            } else {
+                println!("{}", t!("profile-not-found", name = name));
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,199 @@
+use anyhow::{Context, Result};
+use clap::Args;
+use rutify_client::{ClientState, t};
+use rutify_sdk::{MAX_PRIORITY, NotificationInput};
+use std::collections::VecDeque;
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::process::Command;
+
+use crate::send_command::{DEFAULT_MAX_BYTES, truncate_body};
+
+#[derive(Args)]
+pub struct RunArgs {
+    /// Notification title, defaults to the command line
+    #[arg(long)]
+    pub title: Option<String>,
+    /// Target device
+    #[arg(long)]
+    pub device: Option<String>,
+    /// Number of trailing output lines to include in the notification
+    #[arg(long, default_value = "20")]
+    pub tail: usize,
+    /// Only notify when the command fails
+    #[arg(long)]
+    pub only_failure: bool,
+    /// Command to run, after `--`
+    #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+    pub command: Vec<String>,
+}
+
+/// 子进程结束后的摘要
+struct RunOutcome {
+    command: String,
+    exit_code: Option<i32>,
+    duration: Duration,
+    output: Vec<String>,
+}
+
+impl RunOutcome {
+    fn success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+
+    fn title(&self, custom: Option<&str>) -> String {
+        let name = custom.unwrap_or(&self.command);
+        match self.exit_code {
+            Some(0) => format!("✅ {} succeeded", name),
+            Some(code) => format!("❌ {} failed (exit {})", name, code),
+            None => format!("❌ {} was terminated by a signal", name),
+        }
+    }
+
+    fn body(&self) -> String {
+        let exit = self
+            .exit_code
+            .map_or_else(|| "signal".to_string(), |code| code.to_string());
+        let mut body = format!(
+            "Command: {}\nExit: {}\nDuration: {}",
+            self.command,
+            exit,
+            format_duration(self.duration)
+        );
+        if !self.output.is_empty() {
+            body.push_str("\n\nLast output:\n");
+            body.push_str(&self.output.join("\n"));
+        }
+        truncate_body(&body, DEFAULT_MAX_BYTES)
+    }
+}
+
+/// 以人类可读的形式输出耗时，例如 `1h 02m 03s`、`4.2s`
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    match secs {
+        0..60 => format!("{:.1}s", duration.as_secs_f64()),
+        60..3600 => format!("{}m {:02}s", secs / 60, secs % 60),
+        _ => format!(
+            "{}h {:02}m {:02}s",
+            secs / 3600,
+            secs % 3600 / 60,
+            secs % 60
+        ),
+    }
+}
+
+/// 命令行各参数用 shell 风格引号拼接，便于直接复制重跑
+fn display_command(command: &[String]) -> String {
+    shlex::try_join(command.iter().map(String::as_str)).unwrap_or_else(|_| command.join(" "))
+}
+
+/// 将子进程输出原样转发到终端，同时保留最后 `keep` 行
+async fn forward_output(
+    reader: impl AsyncRead + Unpin,
+    mut writer: impl AsyncWrite + Unpin,
+    lines: Arc<Mutex<VecDeque<String>>>,
+    keep: usize,
+) -> Result<()> {
+    let mut reader = BufReader::new(reader).lines();
+    while let Some(line) = reader.next_line().await? {
+        writer.write_all(line.as_bytes()).await?;
+        writer.write_all(b"\n").await?;
+        writer.flush().await?;
+
+        if keep > 0 {
+            let mut lines = lines.lock().unwrap();
+            if lines.len() >= keep {
+                lines.pop_front();
+            }
+            lines.push_back(line);
+        }
+    }
+    Ok(())
+}
+
+/// `rutify-cli run -- <command...>`：执行命令并按退出状态发送通知，返回子进程的退出码
+pub async fn handle_run_command(state: &ClientState, args: RunArgs) -> Result<i32> {
+    let command = display_command(&args.command);
+    let started = Instant::now();
+
+    let mut child = Command::new(&args.command[0])
+        .args(&args.command[1..])
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("failed to start '{}'", args.command[0]))?;
+
+    let lines = Arc::new(Mutex::new(VecDeque::new()));
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let stderr = child.stderr.take().expect("stderr is piped");
+    let (status, stdout_result, stderr_result) = tokio::join!(
+        child.wait(),
+        forward_output(stdout, tokio::io::stdout(), Arc::clone(&lines), args.tail),
+        forward_output(stderr, tokio::io::stderr(), Arc::clone(&lines), args.tail),
+    );
+    let status = status?;
+    stdout_result?;
+    stderr_result?;
+
+    let outcome = RunOutcome {
+        command,
+        exit_code: status.code(),
+        duration: started.elapsed(),
+        output: lines.lock().unwrap().drain(..).collect(),
+    };
+
+    if !(args.only_failure && outcome.success()) {
+        let input = NotificationInput {
+            notify: outcome.body(),
+            title: Some(outcome.title(args.title.as_deref())),
+            device: args.device,
+            priority: (!outcome.success()).then_some(MAX_PRIORITY),
+            tags: vec![
+                "run".to_string(),
+                if outcome.success() {
+                    "success"
+                } else {
+                    "failure"
+                }
+                .to_string(),
+            ],
+            ..Default::default()
+        };
+        if let Err(e) = state.send_notification(&input).await {
+            eprintln!("{}", t!("send-failed", error = e));
+        }
+    }
+
+    // 被信号终止时沿用 shell 的约定
+    Ok(outcome.exit_code.unwrap_or(128))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_outcome_summary() {
+        let outcome = RunOutcome {
+            command: display_command(&["make".to_string(), "build all".to_string()]),
+            exit_code: Some(2),
+            duration: Duration::from_secs(3723),
+            output: vec!["error: boom".to_string()],
+        };
+        assert_eq!(outcome.command, "make 'build all'");
+        assert!(!outcome.success());
+        assert_eq!(outcome.title(None), "❌ make 'build all' failed (exit 2)");
+        assert_eq!(outcome.title(Some("nightly")), "❌ nightly failed (exit 2)");
+        assert_eq!(
+            outcome.body(),
+            "Command: make 'build all'\nExit: 2\nDuration: 1h 02m 03s\n\nLast output:\nerror: boom"
+        );
+
+        assert_eq!(format_duration(Duration::from_millis(4200)), "4.2s");
+        assert_eq!(format_duration(Duration::from_secs(125)), "2m 05s");
+    }
+}
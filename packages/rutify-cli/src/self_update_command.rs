@@ -0,0 +1,148 @@
+use anyhow::{Context, Result};
+use clap::{Args, ValueEnum};
+use rutify_client::t;
+use self_update::backends::github::{ReleaseList, Update};
+use self_update::version::cmp_versions;
+use self_update::{Release, cargo_crate_version, get_target};
+
+const REPO_OWNER: &str = "alone-wolf";
+const REPO_NAME: &str = "rutify";
+const BIN_NAME: &str = "rutify-cli";
+/// 每个 Release 附带的 sha256 校验和文件
+const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+
+#[derive(Args)]
+pub struct SelfUpdateArgs {
+    /// Release channel to follow
+    #[arg(long, value_enum, default_value_t = Channel::Stable)]
+    pub channel: Channel,
+    /// Only check whether an update is available
+    #[arg(long)]
+    pub check: bool,
+    /// Install without asking for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Channel {
+    /// Tagged releases only
+    Stable,
+    /// Nightly builds as well as tagged releases
+    Nightly,
+}
+
+impl Channel {
+    fn as_str(self) -> &'static str {
+        match self {
+            Channel::Stable => "stable",
+            Channel::Nightly => "nightly",
+        }
+    }
+
+    /// stable 只接受正式版，nightly 额外接受 `-nightly.*` 预发布版本
+    fn accepts(self, version: &str) -> bool {
+        let version = version
+            .split_once('+')
+            .map_or(version, |(version, _)| version);
+        match version.split_once('-') {
+            None => true,
+            Some((_, pre)) => self == Channel::Nightly && pre.starts_with("nightly"),
+        }
+    }
+}
+
+/// 该渠道中版本最高的 Release
+fn newest_release(channel: Channel, releases: &[Release]) -> Option<&Release> {
+    releases
+        .iter()
+        .filter(|release| channel.accepts(release.version()))
+        .max_by(|a, b| cmp_versions(a.version(), b.version()).unwrap_or(std::cmp::Ordering::Equal))
+}
+
+pub async fn handle_self_update_command(args: SelfUpdateArgs) -> Result<()> {
+    // self_update 使用阻塞 IO，放到独立线程中执行
+    let result = tokio::task::spawn_blocking(move || self_update(args)).await?;
+    if let Err(e) = result {
+        eprintln!("{}", t!("update-failed", error = format!("{:#}", e)));
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn self_update(args: SelfUpdateArgs) -> Result<()> {
+    let current = cargo_crate_version!();
+    let channel = args.channel.as_str();
+    println!(
+        "{}",
+        t!("update-current", version = current, channel = channel)
+    );
+
+    let releases = ReleaseList::configure()
+        .repo_owner(REPO_OWNER)
+        .repo_name(REPO_NAME)
+        .filter_target(get_target())
+        .build()?
+        .fetch()
+        .context("failed to list releases")?
+        .into_vec();
+    let Some(release) = newest_release(args.channel, &releases) else {
+        println!(
+            "{}",
+            t!(
+                "update-no-release",
+                channel = channel,
+                target = get_target()
+            )
+        );
+        return Ok(());
+    };
+    if cmp_versions(release.version(), current)?.is_le() {
+        println!("{}", t!("update-up-to-date"));
+        return Ok(());
+    }
+    println!("{}", t!("update-available", version = release.version()));
+    if args.check {
+        return Ok(());
+    }
+
+    // 下载后先核对 SHA256SUMS 与 GitHub 记录的摘要，两者都通过才替换当前可执行文件
+    let status = Update::configure()
+        .repo_owner(REPO_OWNER)
+        .repo_name(REPO_NAME)
+        .bin_name(BIN_NAME)
+        .current_version(current)
+        .release_tag(format!("v{}", release.version()))
+        .checksum_from_asset(CHECKSUMS_ASSET)
+        .verify_release_digest(true)
+        .show_download_progress(true)
+        .no_confirm(args.yes)
+        .build()?
+        .update()?;
+    if status.is_updated() {
+        println!("{}", t!("update-installed", version = status.version()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn releases(versions: &[&str]) -> Vec<Release> {
+        versions
+            .iter()
+            .map(|version| Release::builder().version(*version).build().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_newest_release_per_channel() {
+        let releases = releases(&["0.2.0", "0.3.0-nightly.20261001", "0.2.1", "0.3.0-rc.1"]);
+        let version = |channel| newest_release(channel, &releases).map(|r| r.version());
+
+        assert_eq!(version(Channel::Stable), Some("0.2.1"));
+        assert_eq!(version(Channel::Nightly), Some("0.3.0-nightly.20261001"));
+        assert!(newest_release(Channel::Stable, &releases[1..2]).is_none());
+    }
+}
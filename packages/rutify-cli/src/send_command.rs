@@ -0,0 +1,112 @@
+use anyhow::{Result, bail};
+use rutify_client::ClientState;
+use rutify_sdk::{E2eKey, NotificationInput, NotifySigner};
+use std::path::PathBuf;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+
+/// `--stdin` 默认的正文长度上限（字节）
+pub const DEFAULT_MAX_BYTES: usize = 4096;
+
+/// 超出上限时在字符边界处截断，并注明截掉的字节数
+pub fn truncate_body(body: &str, max_bytes: usize) -> String {
+    if body.len() <= max_bytes {
+        return body.to_string();
+    }
+    let mut end = max_bytes;
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}\n… ({} bytes truncated)", &body[..end], body.len() - end)
+}
+
+/// 发送前对通知的保护：端到端加密或发送方签名
+#[derive(Default)]
+pub struct Protection {
+    e2e_key: Option<E2eKey>,
+    signer: Option<NotifySigner>,
+}
+
+impl Protection {
+    /// 读取 `--e2e-key` 与 `--sign-key` 指定的密钥文件
+    pub fn load(e2e_key: Option<PathBuf>, sign_key: Option<PathBuf>) -> Result<Self> {
+        Ok(Self {
+            e2e_key: e2e_key.map(E2eKey::load).transpose()?,
+            signer: sign_key.map(NotifySigner::load).transpose()?,
+        })
+    }
+
+    pub fn apply(&self, input: &mut NotificationInput) -> Result<()> {
+        if let Some(key) = &self.e2e_key {
+            key.encrypt(input)?;
+        }
+        if let Some(signer) = &self.signer {
+            signer.sign(input)?;
+        }
+        Ok(())
+    }
+}
+
+/// 从标准输入读取正文并发送，返回发送的通知数
+///
+/// `per_line` 时每读到一行非空内容就发送一条，适合跟随长时间运行的任务输出。
+/// 每条通知在截断后加密或签名。
+pub async fn send_stdin(
+    state: &ClientState,
+    title: Option<String>,
+    device: Option<String>,
+    ttl_secs: Option<u64>,
+    per_line: bool,
+    max_bytes: usize,
+    protection: &Protection,
+) -> Result<usize> {
+    let input = |body: &str| -> Result<NotificationInput> {
+        let mut input = NotificationInput {
+            notify: truncate_body(body, max_bytes),
+            title: title.clone(),
+            device: device.clone(),
+            ttl_secs,
+            ..Default::default()
+        };
+        protection.apply(&mut input)?;
+        Ok(input)
+    };
+
+    if per_line {
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+        let mut sent = 0;
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            state.send_notification(&input(&line)?).await?;
+            sent += 1;
+        }
+        return Ok(sent);
+    }
+
+    let mut bytes = Vec::new();
+    tokio::io::stdin().read_to_end(&mut bytes).await?;
+    let body = String::from_utf8_lossy(&bytes);
+    let body = body.trim_end();
+    if body.trim().is_empty() {
+        bail!("stdin is empty, nothing to send");
+    }
+    state.send_notification(&input(body)?).await?;
+    Ok(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_body() {
+        assert_eq!(truncate_body("short", 10), "short");
+        assert_eq!(
+            truncate_body("hello world", 5),
+            "hello\n… (6 bytes truncated)"
+        );
+        // 不在多字节字符中间截断
+        assert_eq!(truncate_body("日志内容", 4), "日\n… (9 bytes truncated)");
+    }
+}
@@ -0,0 +1,260 @@
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use rustyline::error::ReadlineError;
+use rustyline::{DefaultEditor, ExternalPrinter};
+use rutify_client::{
+    ClientState, TimeFormat, WebSocketNotification, format_notification, format_stats,
+    matches_search, t,
+};
+use rutify_sdk::{NotificationInput, NotifyEvent};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::tail_command::format_event;
+
+/// 交互模式中缓存的最近事件数
+const RECENT_EVENTS: usize = 100;
+
+/// 交互模式内可用的命令
+#[derive(Parser)]
+#[command(no_binary_name = true, disable_version_flag = true)]
+struct ShellLine {
+    #[command(subcommand)]
+    command: ShellCommand,
+}
+
+#[derive(Subcommand)]
+enum ShellCommand {
+    /// Send a notification
+    Send {
+        /// Notification message
+        message: String,
+        #[arg(long)]
+        title: Option<String>,
+        #[arg(long)]
+        device: Option<String>,
+        #[arg(long)]
+        priority: Option<u8>,
+        /// Tags, repeatable
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+    },
+    /// Show recent live events and keep printing new ones
+    Tail {
+        /// Number of recent events to show
+        #[arg(short = 'n', long, default_value = "10")]
+        lines: usize,
+        /// Stop printing live events
+        #[arg(long)]
+        off: bool,
+    },
+    /// Show server statistics
+    Stats,
+    /// Search stored notifications by title, message or device
+    Search {
+        /// Case-insensitive text to look for
+        text: String,
+        /// Maximum number of results
+        #[arg(short = 'n', long, default_value = "20")]
+        limit: usize,
+    },
+    /// Leave the shell
+    #[command(alias = "quit")]
+    Exit,
+}
+
+/// 交互模式共享的实时事件状态
+#[derive(Default)]
+struct LiveEvents {
+    recent: Mutex<VecDeque<NotifyEvent>>,
+    following: AtomicBool,
+}
+
+/// `rutify-cli shell`：复用同一个已认证客户端与一条 WebSocket 连接
+pub async fn run_shell(state: &ClientState) -> Result<()> {
+    let mut editor = DefaultEditor::new()?;
+    let printer = editor.create_external_printer()?;
+    let live = Arc::new(LiveEvents::default());
+
+    match state.listen_websocket_updates().await {
+        Ok(rx) => {
+            tokio::spawn(follow_events(
+                rx,
+                Arc::clone(&live),
+                printer,
+                state.time_format,
+            ));
+        }
+        Err(e) => eprintln!("{}", t!("live-events-unavailable", error = e)),
+    }
+
+    println!("{}", t!("shell-welcome", server = state.client.base_url));
+
+    // rustyline 是阻塞的，在独立线程中读取输入；每条命令执行完再显示下一个提示符
+    let (line_tx, mut line_rx) = tokio::sync::mpsc::unbounded_channel();
+    let (done_tx, done_rx) = std::sync::mpsc::channel::<()>();
+    std::thread::spawn(move || {
+        loop {
+            match editor.readline("rutify> ") {
+                Ok(line) => {
+                    let _ = editor.add_history_entry(line.as_str());
+                    if line_tx.send(line).is_err() || done_rx.recv().is_err() {
+                        break;
+                    }
+                }
+                Err(ReadlineError::Interrupted) => continue,
+                Err(_) => break,
+            }
+        }
+    });
+
+    while let Some(line) = line_rx.recv().await {
+        if !run_line(state, &live, &line).await {
+            break;
+        }
+        let _ = done_tx.send(());
+    }
+
+    live.following.store(false, Ordering::Relaxed);
+    Ok(())
+}
+
+/// 执行一行输入，返回是否继续
+async fn run_line(state: &ClientState, live: &LiveEvents, line: &str) -> bool {
+    let Some(words) = shlex::split(line) else {
+        eprintln!("{}", t!("shell-unbalanced-quotes"));
+        return true;
+    };
+    if words.is_empty() {
+        return true;
+    }
+
+    let command = match ShellLine::try_parse_from(words) {
+        Ok(parsed) => parsed.command,
+        Err(e) => {
+            let _ = e.print();
+            return true;
+        }
+    };
+
+    match command {
+        ShellCommand::Send {
+            message,
+            title,
+            device,
+            priority,
+            tags,
+        } => {
+            let input = NotificationInput {
+                notify: message,
+                title,
+                device,
+                priority,
+                tags,
+                ..Default::default()
+            };
+            match state.send_notification(&input).await {
+                Ok(_) => println!("{}", t!("shell-sent")),
+                Err(e) => eprintln!("{}", t!("send-failed", error = e)),
+            }
+        }
+        ShellCommand::Tail { lines, off } => {
+            if off {
+                live.following.store(false, Ordering::Relaxed);
+                println!("{}", t!("shell-tail-stopped"));
+            } else {
+                let recent = live.recent.lock().unwrap();
+                for event in recent.iter().skip(recent.len().saturating_sub(lines)) {
+                    println!("{}", format_event(event, &state.time_format));
+                }
+                live.following.store(true, Ordering::Relaxed);
+                println!("{}", t!("shell-tail-following"));
+            }
+        }
+        ShellCommand::Stats => match state.get_stats().await {
+            Ok(stats) => println!("{}", format_stats(&stats)),
+            Err(e) => eprintln!("{}", t!("stats-failed", error = e)),
+        },
+        ShellCommand::Search { text, limit } => match state.get_notifies().await {
+            Ok(notifies) => {
+                let matches: Vec<_> = notifies
+                    .iter()
+                    .filter(|notify| matches_search(notify, &text))
+                    .take(limit)
+                    .collect();
+                if matches.is_empty() {
+                    println!("{}", t!("search-no-match", text = text));
+                }
+                for notify in matches {
+                    println!("{}\n", format_notification(notify, &state.time_format));
+                }
+            }
+            Err(e) => eprintln!("{}", t!("notifies-failed", error = e)),
+        },
+        ShellCommand::Exit => return false,
+    }
+    true
+}
+
+/// 后台接收 WebSocket 事件：缓存最近事件，开启 tail 时打印到提示符上方
+async fn follow_events(
+    mut rx: tokio::sync::mpsc::UnboundedReceiver<WebSocketNotification>,
+    live: Arc<LiveEvents>,
+    mut printer: impl ExternalPrinter,
+    time_format: TimeFormat,
+) {
+    while let Some(notification) = rx.recv().await {
+        let message = match notification {
+            WebSocketNotification::Event(event) if event.event.is_notification() => {
+                let line = format_event(&event, &time_format);
+                let mut recent = live.recent.lock().unwrap();
+                if recent.len() >= RECENT_EVENTS {
+                    recent.pop_front();
+                }
+                recent.push_back(*event);
+                live.following.load(Ordering::Relaxed).then_some(line)
+            }
+            WebSocketNotification::Error { message } => {
+                Some(t!("error-message", message = message))
+            }
+            WebSocketNotification::Close => {
+                let _ = printer.print(t!("live-connection-closed"));
+                break;
+            }
+            WebSocketNotification::Event(_) | WebSocketNotification::Text(_) => None,
+        };
+        if let Some(message) = message {
+            let _ = printer.print(message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shell_line_parsing() {
+        let words = shlex::split(r#"send "disk full" --device nas --tag disk --tag ops"#).unwrap();
+        match ShellLine::try_parse_from(words).unwrap().command {
+            ShellCommand::Send {
+                message,
+                device,
+                tags,
+                ..
+            } => {
+                assert_eq!(message, "disk full");
+                assert_eq!(device.as_deref(), Some("nas"));
+                assert_eq!(tags, vec!["disk", "ops"]);
+            }
+            _ => panic!("Expected Send command"),
+        }
+
+        assert!(matches!(
+            ShellLine::try_parse_from(["quit"]).unwrap().command,
+            ShellCommand::Exit
+        ));
+        assert!(ShellLine::try_parse_from(["frobnicate"]).is_err());
+    }
+}
@@ -0,0 +1,150 @@
+use anyhow::{Result, bail};
+use clap::Subcommand;
+use rutify_client::{TimeFormat, t};
+use rutify_sdk::{CreateSilenceRequest, RutifyClient};
+
+use crate::auth_commands::require_user_token;
+
+#[derive(Subcommand)]
+pub enum SilenceAction {
+    /// Silence matching notifications (stored but not broadcast)
+    Add {
+        /// Device pattern, `*` matches anything
+        #[arg(long)]
+        device: Option<String>,
+        /// Channel pattern
+        #[arg(long)]
+        channel: Option<String>,
+        /// Tag pattern
+        #[arg(long)]
+        tag: Option<String>,
+        /// Duration such as 30m, 2h or 1d
+        #[arg(long = "for", default_value = "1h")]
+        duration: String,
+        /// Reason for the silence
+        #[arg(long)]
+        comment: Option<String>,
+    },
+    /// List silences
+    List {
+        /// Include expired silences
+        #[arg(long)]
+        all: bool,
+    },
+    /// Expire a silence immediately
+    Remove {
+        /// Silence ID
+        id: i32,
+    },
+}
+
+/// 解析 `90s`、`30m`、`2h`、`1d` 形式的时长，纯数字按秒计算
+pub fn parse_duration(text: &str) -> Result<u64> {
+    let text = text.trim();
+    let (number, unit) = match text.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => text.split_at(index),
+        None => (text, "s"),
+    };
+    let value: u64 = number.parse()?;
+    let multiplier = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => bail!("Unknown duration unit: {}", unit),
+    };
+    Ok(value * multiplier)
+}
+
+pub async fn handle_silence_command(
+    client: &RutifyClient,
+    time_format: &TimeFormat,
+    action: SilenceAction,
+) -> Result<()> {
+    require_user_token(client);
+
+    match action {
+        SilenceAction::Add {
+            device,
+            channel,
+            tag,
+            duration,
+            comment,
+        } => {
+            let request = CreateSilenceRequest {
+                device,
+                channel,
+                tag,
+                comment,
+                duration_secs: Some(parse_duration(&duration)?),
+                ..Default::default()
+            };
+
+            match client.create_silence(&request).await {
+                Ok(silence) => {
+                    println!("{}", t!("silence-created", id = silence.id));
+                    println!(
+                        "   {}",
+                        t!(
+                            "silence-expires-at",
+                            time = time_format.format(silence.expires_at)
+                        )
+                    );
+                }
+                Err(e) => {
+                    eprintln!("{}", t!("silence-create-failed", error = e));
+                    std::process::exit(1);
+                }
+            }
+        }
+        SilenceAction::List { all } => match client.get_silences(all).await {
+            Ok(silences) if silences.is_empty() => {
+                println!("{}", t!("silences-empty"));
+            }
+            Ok(silences) => {
+                println!("{}", t!("silences-header", count = silences.len()));
+                for silence in silences {
+                    println!(
+                        "  {}",
+                        t!(
+                            "silence-line",
+                            id = silence.id,
+                            device = silence.device.as_deref().unwrap_or("*"),
+                            channel = silence.channel.as_deref().unwrap_or("*"),
+                            tag = silence.tag.as_deref().unwrap_or("*"),
+                            until = time_format.format(silence.expires_at),
+                            comment = silence.comment.as_deref().unwrap_or("-"),
+                        )
+                    );
+                }
+            }
+            Err(e) => {
+                eprintln!("{}", t!("silences-list-failed", error = e));
+                std::process::exit(1);
+            }
+        },
+        SilenceAction::Remove { id } => match client.expire_silence(id).await {
+            Ok(_) => println!("{}", t!("silence-expired", id = id)),
+            Err(e) => {
+                eprintln!("{}", t!("silence-expire-failed", error = e));
+                std::process::exit(1);
+            }
+        },
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90").unwrap(), 90);
+        assert_eq!(parse_duration("30m").unwrap(), 1800);
+        assert_eq!(parse_duration("2h").unwrap(), 7200);
+        assert_eq!(parse_duration("1d").unwrap(), 86400);
+        assert!(parse_duration("2w").is_err());
+        assert!(parse_duration("h").is_err());
+    }
+}
@@ -0,0 +1,175 @@
+use anyhow::Result;
+use clap::Args;
+use futures_util::StreamExt;
+use rutify_client::{ClientState, TimeFormat, format_stats, t};
+use rutify_sdk::{DeviceStats, EventKind, NotifyEvent, Stats, Subscription};
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+/// `--watch` 未给出间隔时的刷新秒数，与 `watch` 命令一致
+const DEFAULT_WATCH_SECS: &str = "2";
+
+/// 监视模式下显示的设备数
+const TOP_DEVICES: usize = 5;
+
+#[derive(Args)]
+pub struct StatsArgs {
+    /// Keep redrawing the stats and top devices every SECS seconds (default 2) until Ctrl+C
+    #[arg(
+        long,
+        value_name = "SECS",
+        num_args = 0..=1,
+        default_missing_value = DEFAULT_WATCH_SECS,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub watch: Option<u64>,
+}
+
+/// `rutify-cli stats`：输出一次统计，`--watch` 时持续刷新
+pub async fn handle_stats_command(state: &ClientState, args: StatsArgs) -> Result<()> {
+    match args.watch {
+        Some(secs) => watch_stats(state, Duration::from_secs(secs)).await,
+        None => match state.get_stats().await {
+            Ok(stats) => {
+                println!("{}", t!("stats-header"));
+                println!("  {}", format_stats(&stats));
+                Ok(())
+            }
+            Err(e) => {
+                eprintln!("{}", t!("stats-failed", error = e));
+                std::process::exit(1);
+            }
+        },
+    }
+}
+
+/// 像 `watch -n` 一样定时清屏重绘；收到会改变统计的实时事件时立即重绘，
+/// WebSocket 不可用或断开后只按间隔轮询
+async fn watch_stats(state: &ClientState, interval: Duration) -> Result<()> {
+    let mut live = state.client.subscribe().await.ok();
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    loop {
+        tokio::select! {
+            _ = &mut ctrl_c => break,
+            _ = ticker.tick() => {}
+            event = next_event(&mut live) => match event {
+                Some(event) if changes_stats(&event) => {}
+                Some(_) => continue,
+                None => {
+                    live = None;
+                    continue;
+                }
+            },
+        }
+
+        let frame = match state.get_stats().await {
+            Ok(stats) => {
+                // 设备统计失败时仍显示总体统计
+                let devices = state.client.get_device_stats().await.unwrap_or_default();
+                render_frame(&stats, &devices, &state.time_format)
+            }
+            Err(e) => t!("stats-failed", error = e),
+        };
+        let now = state.time_format.format(chrono::Utc::now());
+        print!("\x1b[2J\x1b[H");
+        println!(
+            "{}\n",
+            t!(
+                "stats-watch-header",
+                interval = interval.as_secs(),
+                time = now
+            )
+        );
+        println!("{}", frame);
+    }
+
+    println!();
+    Ok(())
+}
+
+async fn next_event(live: &mut Option<Subscription>) -> Option<NotifyEvent> {
+    match live {
+        Some(live) => live.next().await,
+        None => std::future::pending().await,
+    }
+}
+
+fn changes_stats(event: &NotifyEvent) -> bool {
+    event.event.is_notification()
+        || matches!(event.event, EventKind::Deleted | EventKind::StatsChanged)
+}
+
+/// 一屏内容：总体统计与今日通知最多的设备
+fn render_frame(stats: &Stats, devices: &[DeviceStats], time_format: &TimeFormat) -> String {
+    let mut lines = vec![t!("stats-header"), format!("  {}", format_stats(stats))];
+    let mut top: Vec<&DeviceStats> = devices.iter().collect();
+    top.sort_by(|a, b| {
+        b.today_count
+            .cmp(&a.today_count)
+            .then(b.total_count.cmp(&a.total_count))
+    });
+    if !top.is_empty() {
+        lines.push(String::new());
+        lines.push(t!("stats-top-devices", count = top.len().min(TOP_DEVICES)));
+        for device in top.into_iter().take(TOP_DEVICES) {
+            let last = device
+                .last_notified_at
+                .map(|at| time_format.format(at))
+                .unwrap_or_else(|| "-".to_string());
+            lines.push(format!(
+                "  {}",
+                t!(
+                    "devices-line",
+                    device = device.device.clone(),
+                    today = device.today_count,
+                    total = device.total_count,
+                    last = last,
+                )
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(name: &str, today: u64, total: u64) -> DeviceStats {
+        DeviceStats {
+            device: name.into(),
+            total_count: total,
+            today_count: today,
+            last_notified_at: None,
+        }
+    }
+
+    #[test]
+    fn test_render_frame_top_devices() {
+        let stats = Stats {
+            today_count: 4,
+            total_count: 30,
+            device_count: 6,
+            is_running: true,
+            active_silences: 0,
+        };
+        let devices: Vec<DeviceStats> = (0..6)
+            .map(|i| device(&format!("dev{i}"), i % 3, i))
+            .collect();
+
+        let frame = render_frame(&stats, &devices, &TimeFormat::default());
+        let lines: Vec<&str> = frame.lines().filter(|line| line.contains('|')).collect();
+        assert_eq!(lines.len(), TOP_DEVICES);
+        // 今日数相同的按总数排序
+        assert!(lines[0].contains("dev5"));
+        assert!(lines[1].contains("dev2"));
+        assert!(!frame.contains("dev0"));
+
+        let frame = render_frame(&stats, &[], &TimeFormat::default());
+        assert!(!frame.contains('|'));
+    }
+}
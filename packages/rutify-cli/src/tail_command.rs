@@ -0,0 +1,218 @@
+use anyhow::Result;
+use clap::Args;
+use futures_util::StreamExt;
+use rutify_client::{ClientState, TimeFormat, format_progress, t};
+use rutify_sdk::{E2eKey, E2eKeyring, EventKind, NotificationData, NotifyEvent, NotifyItem};
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub struct TailArgs {
+    /// Number of recent notifications to print first
+    #[arg(short = 'n', long, default_value = "10")]
+    pub lines: usize,
+    /// Print recent notifications and exit without following
+    #[arg(long)]
+    pub no_follow: bool,
+    /// Only notifications from this device
+    #[arg(long)]
+    pub device: Option<String>,
+    /// Only notifications sent to this channel
+    #[arg(long)]
+    pub channel: Option<String>,
+    /// Only notifications whose title or message contains this text (case-insensitive)
+    #[arg(long)]
+    pub grep: Option<String>,
+    /// Print one JSON event per line
+    #[arg(long)]
+    pub json: bool,
+    /// Decrypt end-to-end encrypted notifications with the shared key in this file
+    #[arg(long, value_name = "KEY_FILE")]
+    pub e2e_key: Option<PathBuf>,
+}
+
+/// 历史通知与实时事件共用的过滤条件
+struct TailFilter {
+    device: Option<String>,
+    channel: Option<String>,
+    grep: Option<String>,
+}
+
+impl TailFilter {
+    fn new(args: &TailArgs) -> Self {
+        Self {
+            device: args.device.clone(),
+            channel: args.channel.clone(),
+            grep: args.grep.as_deref().map(str::to_lowercase),
+        }
+    }
+
+    fn matches(&self, data: &NotificationData) -> bool {
+        self.device
+            .as_ref()
+            .is_none_or(|device| *device == data.device)
+            && self
+                .channel
+                .as_ref()
+                .is_none_or(|channel| data.channel.as_ref() == Some(channel))
+            && self.grep.as_ref().is_none_or(|needle| {
+                data.title.to_lowercase().contains(needle)
+                    || data.notify.to_lowercase().contains(needle)
+            })
+    }
+}
+
+/// `rutify-cli tail`：先输出最近的通知，再持续输出实时事件
+pub async fn handle_tail_command(state: &ClientState, args: TailArgs) -> Result<()> {
+    let filter = TailFilter::new(&args);
+    let keyring = args
+        .e2e_key
+        .as_ref()
+        .map(E2eKey::load)
+        .transpose()?
+        .map(E2eKeyring::new);
+
+    // 先订阅再读取历史，避免两者之间的通知丢失；重复的由 ID 去重
+    let live = if args.no_follow {
+        None
+    } else {
+        let live = state.client.subscribe().await?;
+        Some(match &keyring {
+            Some(keyring) => live.decrypt(keyring.clone()),
+            None => live,
+        })
+    };
+
+    let mut items = state.client.get_notifies().await?;
+    let last_id = items.iter().map(|item| item.id).max().unwrap_or_default();
+    if let Some(keyring) = &keyring {
+        // 无法解密的通知保留密文输出
+        for item in &mut items {
+            let _ = keyring.decrypt_item(item);
+        }
+    }
+    let mut history: Vec<NotifyEvent> = items
+        .into_iter()
+        .map(history_event)
+        .filter(|event| filter.matches(&event.data))
+        .take(args.lines)
+        .collect();
+    history.reverse();
+    for event in &history {
+        print_event(event, args.json, &state.time_format)?;
+    }
+
+    let Some(mut live) = live else {
+        return Ok(());
+    };
+    if !args.json {
+        eprintln!("{}", t!("tail-following"));
+    }
+    while let Some(event) = live.next().await {
+        let seen =
+            event.event == EventKind::Created && event.data.id.is_some_and(|id| id <= last_id);
+        // 文本输出只显示通知，JSON 输出保留删除、确认等全部事件
+        let shown = args.json || event.event.is_notification();
+        if !seen && shown && filter.matches(&event.data) {
+            print_event(&event, args.json, &state.time_format)?;
+            let _ = live.ack(&event);
+        }
+    }
+    eprintln!("{}", t!("connection-closed"));
+    Ok(())
+}
+
+/// 将列表接口返回的通知转换为事件，使历史与实时输出格式一致
+fn history_event(item: NotifyItem) -> NotifyEvent {
+    NotifyEvent {
+        event: EventKind::History,
+        data: NotificationData {
+            id: Some(item.id),
+            notify: item.notify,
+            title: item.title,
+            device: item.device,
+            priority: item.priority,
+            tags: item.tags,
+            recipient: None,
+            channel: item.channel,
+            expires_at: item.expires_at,
+            progress: item.progress,
+            link_previews: item.link_previews,
+            signature: item.signature,
+            correlation_id: None,
+            token_id: None,
+        },
+        timestamp: item.received_at,
+    }
+}
+
+fn print_event(event: &NotifyEvent, json: bool, time_format: &TimeFormat) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string(event)?);
+    } else {
+        println!("{}", format_event(event, time_format));
+    }
+    Ok(())
+}
+
+/// 单行显示通知事件
+pub fn format_event(event: &NotifyEvent, time_format: &TimeFormat) -> String {
+    let mut line = format!(
+        "🔔 [{}] P{} {} | {}: {}",
+        time_format.format(event.timestamp),
+        event.data.priority,
+        event.data.device,
+        event.data.title,
+        event.data.notify
+    );
+    if let Some(channel) = &event.data.channel {
+        line.push_str(&format!(" #{}", channel));
+    }
+    if let Some(progress) = &event.data.progress {
+        line.push_str(&format!(" [{}]", format_progress(progress)));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(device: &str, title: &str, channel: Option<&str>) -> NotificationData {
+        NotificationData {
+            id: None,
+            notify: "Disk usage at 95%".to_string(),
+            title: title.to_string(),
+            device: device.to_string(),
+            priority: 3,
+            tags: Vec::new(),
+            recipient: None,
+            channel: channel.map(str::to_string),
+            expires_at: None,
+            progress: None,
+            link_previews: Vec::new(),
+            signature: None,
+            correlation_id: None,
+            token_id: None,
+        }
+    }
+
+    #[test]
+    fn test_tail_filter() {
+        let filter = TailFilter {
+            device: Some("nas".to_string()),
+            channel: None,
+            grep: Some("disk".to_string()),
+        };
+        assert!(filter.matches(&data("nas", "Alert", None)));
+        assert!(!filter.matches(&data("web1", "Alert", None)));
+
+        let filter = TailFilter {
+            device: None,
+            channel: Some("oncall".to_string()),
+            grep: Some("backup".to_string()),
+        };
+        assert!(!filter.matches(&data("nas", "Alert", Some("oncall"))));
+        assert!(filter.matches(&data("nas", "Backup failed", Some("oncall"))));
+        assert!(!filter.matches(&data("nas", "Backup failed", None)));
+    }
+}
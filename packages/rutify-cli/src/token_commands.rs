@@ -1,6 +1,8 @@
 use anyhow::Result;
 use clap::Subcommand;
-use rutify_client::ClientState;
+use rutify_client::{ClientState, t};
+
+use crate::config::ProfileStore;
 
 #[derive(Subcommand)]
 pub enum TokenAction {
@@ -12,55 +14,97 @@ pub enum TokenAction {
         #[arg(long, default_value = "24")]
         expires_in: u64,
     },
-    /// Set token for authentication
+    /// Save a notification token to the current profile
     Set {
         /// Bearer token
         token: String,
     },
-    /// Clear stored token
+    /// Remove the notification token from the current profile
     Clear,
     /// Show current token status
     Status,
 }
 
-pub async fn handle_token_command(state: &mut ClientState, action: TokenAction) -> Result<()> {
+pub async fn handle_token_command(
+    state: &ClientState,
+    store: &mut ProfileStore,
+    action: TokenAction,
+) -> Result<()> {
     match action {
         TokenAction::Create { usage, expires_in } => {
             println!(
-                "🔑 Creating new token for usage: '{}', expires in {} hours",
-                usage, expires_in
+                "{}",
+                t!("token-creating-for", usage = usage, hours = expires_in)
             );
             match state.create_token(&usage, expires_in).await {
                 Ok(token_response) => {
-                    println!("✅ Token created successfully!");
-                    println!("   Token ID: {}", token_response.token_id);
-                    println!("   Usage: {}", token_response.usage);
-                    println!("   Expires at: {}", token_response.expires_at);
-                    println!("   Token: {}", token_response.token);
-                    println!("   💡 Save this token securely!");
+                    println!("{}", t!("token-created"));
+                    println!(
+                        "   {}",
+                        t!("label-token-id", value = token_response.token_id)
+                    );
+                    println!("   {}", t!("label-usage", value = token_response.usage));
+                    println!(
+                        "   {}",
+                        t!(
+                            "label-expires-at",
+                            value = state.time_format.format(token_response.expires_at)
+                        )
+                    );
+                    println!("   {}", t!("label-token", value = token_response.token));
+                    store.update(|profile| {
+                        profile.server = Some(state.client.base_url.clone());
+                        profile.token = Some(token_response.token.clone());
+                    })?;
+                    println!("   {}", t!("saved-to-profile", profile = store.name));
                 }
-                Err(e) => eprintln!("❌ Failed to create token: {}", e),
+                Err(e) => eprintln!("{}", t!("token-create-failed", error = e)),
             }
         }
         TokenAction::Set { token } => {
-            println!("🔐 Setting authentication token...");
+            println!("{}", t!("token-setting"));
             state.set_token(&token);
+            store.update(|profile| {
+                profile.server = Some(state.client.base_url.clone());
+                profile.token = Some(token.clone());
+            })?;
             println!(
-                "   Token set: {}...",
-                &token[..std::cmp::min(20, token.len())]
+                "   {}",
+                t!(
+                    "token-set",
+                    preview = &token[..std::cmp::min(20, token.len())]
+                )
             );
-            println!("   💡 Use this token for subsequent requests");
+            println!("   {}", t!("saved-to-profile", profile = store.name));
         }
         TokenAction::Clear => {
-            println!("🗑️  Clearing stored token...");
+            println!("{}", t!("token-clearing"));
             state.clear_token();
-            println!("   Token cleared");
+            store.update(|profile| profile.token = None)?;
+            println!(
+                "   {}",
+                t!("token-cleared-from-profile", profile = store.name)
+            );
         }
         TokenAction::Status => {
+            println!(
+                "{}",
+                t!(
+                    "profile-status",
+                    profile = store.name,
+                    path = store.path().display()
+                )
+            );
+            println!("   {}", t!("label-server", value = state.client.base_url));
             if state.has_token() {
-                println!("✅ Token is configured");
+                println!("{}", t!("notify-token-configured"));
+            } else {
+                println!("{}", t!("notify-token-missing"));
+            }
+            if state.client.has_user_token() {
+                println!("{}", t!("user-token-configured"));
             } else {
-                println!("❌ No token configured");
+                println!("{}", t!("user-token-missing"));
             }
         }
     }
@@ -0,0 +1,510 @@
+use anyhow::Result;
+use crossterm::event::{Event, EventStream, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use futures_util::StreamExt;
+use ratatui::DefaultTerminal;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use rutify_client::{
+    ClientState, TimeFormat, format_link_preview, format_progress, matches_search, t,
+};
+use rutify_sdk::{EventKind, NotifyEvent, NotifyItem, Stats, Subscription};
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+/// 统计信息的刷新间隔
+const STATS_REFRESH: Duration = Duration::from_secs(10);
+
+/// 翻页时移动的行数
+const PAGE: usize = 10;
+
+/// 终端面板的全部界面状态
+#[derive(Default)]
+struct App {
+    /// 按接收时间从新到旧排列
+    items: Vec<NotifyItem>,
+    stats: Option<Stats>,
+    /// 在过滤后列表中的位置
+    selected: usize,
+    search: String,
+    searching: bool,
+    show_detail: bool,
+    live: bool,
+    status: String,
+    quit: bool,
+}
+
+impl App {
+    fn visible(&self) -> Vec<&NotifyItem> {
+        self.items
+            .iter()
+            .filter(|item| matches_search(item, &self.search))
+            .collect()
+    }
+
+    fn selected_item(&self) -> Option<&NotifyItem> {
+        self.visible().get(self.selected).copied()
+    }
+
+    fn clamp_selection(&mut self) {
+        let len = self.visible().len();
+        self.selected = self.selected.min(len.saturating_sub(1));
+    }
+
+    fn move_by(&mut self, delta: isize) {
+        self.selected = self.selected.saturating_add_signed(delta);
+        self.clamp_selection();
+    }
+
+    fn set_items(&mut self, items: Vec<NotifyItem>) {
+        self.items = items;
+        self.clamp_selection();
+    }
+
+    /// 与图形界面相同：更新与升级就地替换，删除移除，新通知插到最前；已有的 ID 不重复插入
+    fn apply_event(&mut self, event: &NotifyEvent) {
+        let cached = event
+            .data
+            .id
+            .and_then(|id| self.items.iter().position(|item| item.id == id));
+        match (event.event, cached) {
+            (EventKind::Updated | EventKind::Escalated, Some(index)) => {
+                self.items[index].apply_update(&event.data);
+            }
+            (EventKind::Deleted, Some(index)) => {
+                self.items.remove(index);
+            }
+            (kind, None) if kind.is_notification() => {
+                self.items.insert(0, notify_item(event));
+                // 保持选中的仍是同一条通知
+                if self.selected > 0 || self.show_detail {
+                    self.selected += usize::from(matches_search(&self.items[0], &self.search));
+                }
+            }
+            _ => {}
+        }
+        self.clamp_selection();
+    }
+
+    /// 处理一次按键，返回是否需要重新读取列表
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if key.kind != KeyEventKind::Press {
+            return false;
+        }
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.quit = true;
+            return false;
+        }
+        if self.searching {
+            match key.code {
+                KeyCode::Enter => self.searching = false,
+                KeyCode::Esc => {
+                    self.searching = false;
+                    self.search.clear();
+                }
+                KeyCode::Backspace => {
+                    self.search.pop();
+                }
+                KeyCode::Char(c) => {
+                    self.search.push(c);
+                    self.selected = 0;
+                }
+                _ => {}
+            }
+            self.clamp_selection();
+            return false;
+        }
+        match key.code {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Esc if self.show_detail => self.show_detail = false,
+            KeyCode::Esc if !self.search.is_empty() => {
+                self.search.clear();
+                self.clamp_selection();
+            }
+            KeyCode::Esc => self.quit = true,
+            KeyCode::Char('j') | KeyCode::Down => self.move_by(1),
+            KeyCode::Char('k') | KeyCode::Up => self.move_by(-1),
+            KeyCode::PageDown => self.move_by(PAGE as isize),
+            KeyCode::PageUp => self.move_by(-(PAGE as isize)),
+            KeyCode::Char('g') | KeyCode::Home => self.selected = 0,
+            KeyCode::Char('G') | KeyCode::End => self.move_by(isize::MAX),
+            KeyCode::Enter => self.show_detail = !self.show_detail,
+            KeyCode::Char('/') => self.searching = true,
+            KeyCode::Char('r') => return true,
+            _ => {}
+        }
+        false
+    }
+}
+
+/// `rutify-cli tui`：终端中的通知面板，列表随 WebSocket 实时更新
+pub async fn run_tui(state: &ClientState) -> Result<()> {
+    // 先订阅再读取列表，之间到达的通知按 ID 去重
+    let mut live = state.client.subscribe().await.ok();
+    let mut app = App {
+        live: live.is_some(),
+        ..Default::default()
+    };
+    reload(state, &mut app).await;
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, state, &mut app, &mut live).await;
+    ratatui::restore();
+    result
+}
+
+async fn event_loop(
+    terminal: &mut DefaultTerminal,
+    state: &ClientState,
+    app: &mut App,
+    live: &mut Option<Subscription>,
+) -> Result<()> {
+    let mut keys = EventStream::new();
+    let mut ticker = tokio::time::interval(STATS_REFRESH);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    while !app.quit {
+        terminal.draw(|frame| draw(frame, app, &state.time_format))?;
+        tokio::select! {
+            key = keys.next() => match key {
+                Some(Ok(Event::Key(key))) => {
+                    if app.handle_key(key) {
+                        reload(state, app).await;
+                    }
+                }
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(e.into()),
+                None => break,
+            },
+            event = next_event(live) => match event {
+                Some(event) => {
+                    if event.event == EventKind::StatsChanged {
+                        // 批量删除或清理后重新读取列表
+                        reload(state, app).await;
+                    } else {
+                        app.apply_event(&event);
+                        if event.event.is_notification() || event.event == EventKind::Deleted {
+                            app.stats = state.client.get_stats().await.ok().or(app.stats.take());
+                        }
+                    }
+                    if let Some(live) = live.as_ref() {
+                        let _ = live.ack(&event);
+                    }
+                }
+                None => {
+                    *live = None;
+                    app.live = false;
+                }
+            },
+            _ = ticker.tick() => {
+                if let Ok(stats) = state.client.get_stats().await {
+                    app.stats = Some(stats);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn next_event(live: &mut Option<Subscription>) -> Option<NotifyEvent> {
+    match live {
+        Some(live) => live.next().await,
+        None => std::future::pending().await,
+    }
+}
+
+async fn reload(state: &ClientState, app: &mut App) {
+    match state.client.get_notifies().await {
+        Ok(items) => {
+            app.status = t!("gui-loaded", count = items.len());
+            app.set_items(items);
+        }
+        Err(e) => app.status = t!("tui-load-failed", error = e),
+    }
+    if let Ok(stats) = state.client.get_stats().await {
+        app.stats = Some(stats);
+    }
+}
+
+fn draw(frame: &mut ratatui::Frame, app: &App, time_format: &TimeFormat) {
+    let [header, search, body, footer] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Length(1),
+        Constraint::Min(3),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    draw_header(frame, header, app);
+
+    let search_line = if app.searching || !app.search.is_empty() {
+        Line::from(vec![
+            Span::styled(format!("{}: ", t!("tui-search")), Style::new().bold()),
+            Span::raw(app.search.as_str()),
+            Span::raw(if app.searching { "▏" } else { "" }),
+        ])
+    } else {
+        Line::from(app.status.as_str()).dark_gray()
+    };
+    frame.render_widget(Paragraph::new(search_line), search);
+
+    let visible = app.visible();
+    let (list_area, detail_area) = if app.show_detail {
+        let [list, detail] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .areas(body);
+        (list, Some(detail))
+    } else {
+        (body, None)
+    };
+
+    let rows: Vec<ListItem> = visible
+        .iter()
+        .map(|item| ListItem::new(list_line(item, time_format)))
+        .collect();
+    let title = t!(
+        "tui-notifications",
+        shown = visible.len(),
+        total = app.items.len()
+    );
+    let list = List::new(rows)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("▶ ");
+    let mut list_state = ListState::default().with_selected(if visible.is_empty() {
+        None
+    } else {
+        Some(app.selected)
+    });
+    if visible.is_empty() {
+        frame.render_widget(
+            Paragraph::new(t!("tui-empty")).dark_gray().block(
+                Block::default().borders(Borders::ALL).title(t!(
+                    "tui-notifications",
+                    shown = 0,
+                    total = app.items.len()
+                )),
+            ),
+            list_area,
+        );
+    } else {
+        frame.render_stateful_widget(list, list_area, &mut list_state);
+    }
+
+    if let (Some(area), Some(item)) = (detail_area, app.selected_item()) {
+        let detail = Paragraph::new(detail_lines(item, time_format))
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(t!("tui-details")),
+            );
+        frame.render_widget(detail, area);
+    }
+
+    let help = if app.searching {
+        t!("tui-search-help")
+    } else {
+        t!("tui-help")
+    };
+    frame.render_widget(Paragraph::new(help).dark_gray(), footer);
+}
+
+fn draw_header(frame: &mut ratatui::Frame, area: Rect, app: &App) {
+    let stats = app.stats.as_ref().map_or_else(String::new, |stats| {
+        t!(
+            "tui-stats",
+            today = stats.today_count,
+            total = stats.total_count,
+            devices = stats.device_count,
+            silences = stats.active_silences
+        )
+    });
+    let connection = if app.live {
+        Span::styled(t!("tui-live"), Style::new().fg(Color::Green))
+    } else {
+        Span::styled(t!("tui-offline"), Style::new().fg(Color::Red))
+    };
+    let line = Line::from(vec![Span::raw(stats), Span::raw("  "), connection]);
+    frame.render_widget(
+        Paragraph::new(line).block(Block::default().borders(Borders::ALL).title("Rutify")),
+        area,
+    );
+}
+
+/// 列表中的一行：时间、设备、标题与正文，高优先级标红
+fn list_line<'a>(item: &'a NotifyItem, time_format: &TimeFormat) -> Line<'a> {
+    let marker = match (item.pinned, item.starred) {
+        (true, _) => "📌",
+        (false, true) => "★ ",
+        _ => "  ",
+    };
+    let title_style = if item.priority >= 4 {
+        Style::new().fg(Color::Red).bold()
+    } else {
+        Style::new().bold()
+    };
+    Line::from(vec![
+        Span::raw(marker),
+        Span::styled(
+            format!("{} ", time_format.format(item.received_at)),
+            Style::new().dark_gray(),
+        ),
+        Span::styled(format!("{} ", item.device), Style::new().cyan()),
+        Span::styled(item.title.as_str(), title_style),
+        Span::raw(": "),
+        Span::raw(item.notify.as_str()),
+    ])
+}
+
+fn detail_lines<'a>(item: &'a NotifyItem, time_format: &TimeFormat) -> Vec<Line<'a>> {
+    let mut lines = vec![
+        Line::from(item.title.as_str()).bold(),
+        Line::default(),
+        Line::from(item.notify.as_str()),
+        Line::default(),
+        Line::from(t!("event-device", value = item.device.clone())),
+        Line::from(t!(
+            "event-time",
+            value = time_format.format(item.received_at)
+        )),
+        Line::from(t!("tui-detail-priority", value = item.priority)),
+        Line::from(t!("tui-detail-id", value = item.id)),
+    ];
+    if !item.tags.is_empty() {
+        lines.push(Line::from(t!(
+            "tui-detail-tags",
+            value = item.tags.join(", ")
+        )));
+    }
+    if let Some(channel) = &item.channel {
+        lines.push(Line::from(t!(
+            "tui-detail-channel",
+            value = channel.clone()
+        )));
+    }
+    if let Some(expires_at) = item.expires_at {
+        lines.push(Line::from(t!(
+            "tui-detail-expires",
+            value = time_format.format(expires_at)
+        )));
+    }
+    if let Some(progress) = &item.progress {
+        lines.push(Line::from(t!(
+            "tui-detail-progress",
+            value = format_progress(progress)
+        )));
+    }
+    for preview in &item.link_previews {
+        lines.push(Line::from(format!("🔗 {}", format_link_preview(preview))));
+    }
+    lines
+}
+
+/// 实时事件转换为列表项
+fn notify_item(event: &NotifyEvent) -> NotifyItem {
+    NotifyItem {
+        id: event.data.id.unwrap_or_default(),
+        title: event.data.title.clone(),
+        notify: event.data.notify.clone(),
+        device: event.data.device.clone(),
+        priority: event.data.priority,
+        tags: event.data.tags.clone(),
+        channel: event.data.channel.clone(),
+        received_at: event.timestamp,
+        expires_at: event.data.expires_at,
+        pinned: false,
+        starred: false,
+        progress: event.data.progress,
+        device_source: None,
+        link_previews: event.data.link_previews.clone(),
+        signature: event.data.signature.clone(),
+        correlation_id: event.data.correlation_id.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rutify_sdk::{NotificationData, NotifyId};
+
+    fn event(kind: EventKind, id: i32, title: &str) -> NotifyEvent {
+        NotifyEvent {
+            event: kind,
+            data: NotificationData {
+                id: Some(NotifyId(id)),
+                notify: "Disk usage at 95%".to_string(),
+                title: title.to_string(),
+                device: "nas".to_string(),
+                priority: 3,
+                tags: Vec::new(),
+                recipient: None,
+                channel: None,
+                expires_at: None,
+                progress: None,
+                link_previews: Vec::new(),
+                signature: None,
+                correlation_id: None,
+                token_id: None,
+            },
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_apply_event() {
+        let mut app = App::default();
+        app.apply_event(&event(EventKind::Created, 1, "disk"));
+        app.apply_event(&event(EventKind::Created, 2, "backup"));
+        // 重复的创建事件不会插入第二次
+        app.apply_event(&event(EventKind::Created, 2, "backup"));
+        assert_eq!(
+            app.items
+                .iter()
+                .map(|item| item.id.get())
+                .collect::<Vec<_>>(),
+            [2, 1]
+        );
+
+        app.apply_event(&event(EventKind::Updated, 1, "disk ok"));
+        assert_eq!(app.items[1].title, "disk ok");
+
+        app.handle_key(key(KeyCode::Char('j')));
+        assert_eq!(app.selected_item().unwrap().id, NotifyId(1));
+        app.apply_event(&event(EventKind::Deleted, 1, ""));
+        assert_eq!(app.items.len(), 1);
+        assert_eq!(app.selected, 0);
+    }
+
+    #[test]
+    fn test_search_keys() {
+        let mut app = App::default();
+        app.set_items(vec![
+            notify_item(&event(EventKind::Created, 2, "backup")),
+            notify_item(&event(EventKind::Created, 1, "disk")),
+        ]);
+
+        app.handle_key(key(KeyCode::Char('/')));
+        for c in "BACK".chars() {
+            app.handle_key(key(KeyCode::Char(c)));
+        }
+        assert!(app.searching);
+        assert_eq!(app.visible().len(), 1);
+        // 搜索时 q 作为文字输入
+        app.handle_key(key(KeyCode::Char('q')));
+        assert!(!app.quit);
+        assert!(app.visible().is_empty());
+
+        app.handle_key(key(KeyCode::Esc));
+        assert!(!app.searching);
+        assert_eq!(app.visible().len(), 2);
+        assert!(app.handle_key(key(KeyCode::Char('r'))));
+        app.handle_key(key(KeyCode::Char('q')));
+        assert!(app.quit);
+    }
+}
@@ -0,0 +1,251 @@
+## Common
+
+error-message = ❌ Error: { $message }
+connection-closed = 🔌 Connection closed
+send-failed = ❌ Failed to send notification: { $error }
+notifies-failed = ❌ Failed to get notifies: { $error }
+stats-failed = ❌ Failed to get stats: { $error }
+answer-yes = yes
+answer-no = no
+
+## Notifications and statistics
+
+notifies-header = 📬 Notifications ({ $count } total):
+notification-received = Received: { $time }
+stats-header = 📊 Server Statistics:
+stats-today = Today's notifications: { $count }
+stats-total = Total notifications: { $count }
+stats-devices = Active devices: { $count }
+stats-silences = Active silences: { $count }
+stats-running = Server running: { $running }
+stats-running-yes = ✅ Yes
+stats-running-no = ❌ No
+stats-watch-header = Every { $interval }s: rutify-cli stats · { $time } · Ctrl+C to stop
+stats-top-devices = 📱 Top { $count } devices today:
+progress-done = done
+progress-failed = failed
+
+## Sending
+
+send-success = ✅ Notification sent successfully!
+send-stdin-success = ✅ { $count } notification(s) sent from stdin
+e2e-key-generated = 🔑 Shared key written to { $path }, share it with every client that sends or reads these notifications
+signing-key-generated = 🔑 Private key written to { $path }, register this public key with `auth create-token --signing-key`:
+send-listen-start = 📤 Sending notification and waiting for delivery confirmation...
+send-listen-failed = ❌ Failed to send and listen: { $error }
+response-received = 🔔 Delivery confirmed:
+response-text = 📝 Response: { $text }
+no-response = ⏰ No delivery confirmation before the timeout
+event-title = Title: { $value }
+event-message = Message: { $value }
+event-device = Device: { $value }
+event-time = Time: { $value }
+
+## Server
+
+devices-header = 📱 Devices ({ $count }):
+devices-empty = 📱 No device has sent a notification yet
+devices-line = { $device } | today { $today } | total { $total } | last { $last }
+devices-failed = ❌ Failed to get device stats: { $error }
+health-ok = ✅ Server is healthy and responsive
+health-failed = ❌ Server health check failed
+health-error = ❌ Server health check failed: { $error }
+
+## Authentication
+
+not-logged-in = ❌ Not logged in: no saved user token and RUTIFY_USER_TOKEN not set
+user-token-env-missing = ❌ RUTIFY_USER_TOKEN environment variable not set
+login-hint = 💡 Please login first: { $bin } auth login --username <user> --password <pass>
+registering = 🔐 Registering new user...
+registered = ✅ User '{ $username }' registered successfully!
+register-login-hint = 💡 You can now login with: { $bin } auth login --username { $username } --password <password>
+register-failed = ❌ Registration failed: { $error }
+logging-in = 🔑 Logging in...
+login-success = ✅ Login successful!
+login-failed = ❌ Login failed: { $error }
+logged-out = 👋 Logged out of profile '{ $profile }'
+token-saved-to-profile = 💾 Token saved to profile '{ $profile }'
+save-jwt-hint = 💡 Save this token for future API calls:
+user-profile-loading = 👤 Getting user profile...
+user-profile-header = ✅ User Profile:
+user-profile-failed = ❌ Failed to get profile: { $error }
+label-user = 👤 User: { $value }
+label-email = 📧 Email: { $value }
+label-role = 🔐 Role: { $value }
+label-id = 🆔 ID: { $value }
+label-usage = 📝 Usage: { $value }
+label-type = 🔐 Type: { $value }
+label-device = 📱 Device: { $value }
+label-created = 📅 Created: { $value }
+label-expires = ⏰ Expires: { $value }
+label-expires-at = ⏰ Expires at: { $value }
+label-last-used = 🔄 Last Used: { $value }
+label-token = 🎫 Token: { $value }
+label-token-id = 🆔 Token ID: { $value }
+label-jwt = 🎫 JWT Token: { $value }
+label-server = Server: { $value }
+
+## Tokens
+
+token-creating = 🎫 Creating notification token...
+token-creating-for = 🔑 Creating new token for usage: '{ $usage }', expires in { $hours } hours
+token-created = ✅ Token created successfully!
+token-create-failed = ❌ Failed to create token: { $error }
+notify-token-saved = 💾 Notification token saved to profile '{ $profile }'
+use-token-hint = 💡 Use this token for notifications:
+token-save-hint = 💡 Save this token securely!
+tokens-listing = 📋 Listing user tokens...
+tokens-empty = 📭 No tokens found.
+tokens-header = 🎫 User Tokens ({ $count } total):
+tokens-list-failed = ❌ Failed to list tokens: { $error }
+token-deleting = 🗑️  Deleting token { $id }...
+token-deleted = ✅ Token { $id } deleted successfully!
+token-delete-failed = ❌ Failed to delete token: { $error }
+token-setting = 🔐 Setting authentication token...
+token-set = Token set: { $preview }...
+token-set-hint = 💡 Use this token for subsequent requests
+token-clearing = 🗑️  Clearing stored token...
+token-cleared = Token cleared
+token-cleared-from-profile = Token cleared from profile '{ $profile }'
+saved-to-profile = 💾 Saved to profile '{ $profile }'
+token-configured = ✅ Token is configured
+token-missing = ❌ No token configured
+notify-token-configured = ✅ Notification token is configured
+notify-token-missing = ❌ No notification token configured
+user-token-configured = ✅ User token is configured
+user-token-missing = ❌ Not logged in
+
+## Profiles
+
+profile-status = 📁 Profile '{ $profile }' ({ $path })
+profile-line = { $marker } { $name } | { $server } | notify token: { $token } | logged in: { $logged_in }
+profiles-empty = 📭 No profiles saved in { $path }
+profile-default-set = ✅ Default profile set to '{ $name }'
+profile-removed = 🗑️  Profile '{ $name }' removed
+profile-not-found = ❌ Profile '{ $name }' not found
+
+## Silences
+
+silence-created = 🔕 Silence { $id } created
+silence-expires-at = Expires at: { $time }
+silence-create-failed = ❌ Failed to create silence: { $error }
+silences-empty = 📭 No silences found.
+silences-header = 🔕 Silences ({ $count } total):
+silence-line = { $id }. device={ $device } channel={ $channel } tag={ $tag } | until { $until } | { $comment }
+silences-list-failed = ❌ Failed to list silences: { $error }
+silence-expired = ✅ Silence { $id } expired
+silence-expire-failed = ❌ Failed to expire silence: { $error }
+
+## Monitors
+
+monitor-beat-ok = 💓 Heartbeat sent for '{ $name }' (next due { $due })
+monitor-beat-failed = ❌ Failed to send heartbeat: { $error }
+monitor-created = 💓 Monitor '{ $name }' created, expecting a heartbeat every { $interval }s
+monitor-create-failed = ❌ Failed to create monitor: { $error }
+monitors-empty = 📭 No monitors found.
+monitors-header = 💓 Monitors ({ $count } total):
+monitor-line = { $name } [{ $status }] every { $interval }s (+{ $grace }s grace) | last beat { $last }
+monitors-list-failed = ❌ Failed to list monitors: { $error }
+monitor-removed = 🗑️  Monitor '{ $name }' removed
+monitor-remove-failed = ❌ Failed to remove monitor: { $error }
+
+## Administration
+
+purge-dry-run = 🔍 { $count } notification(s) match, nothing deleted (dry run)
+purge-done = 🗑️  { $count } notification(s) purged
+purge-failed = ❌ Failed to purge notifications: { $error }
+admin-users-empty = 📭 No users found.
+admin-users-header = 👥 Users ({ $count } total):
+admin-user-line = { $username } <{ $email }> | { $role } | { $status } | joined { $created }
+admin-user-status-active = active
+admin-user-status-disabled = disabled
+admin-users-failed = ❌ Failed to list users: { $error }
+admin-user-disabled = 🚫 User '{ $username }' disabled
+admin-user-enabled = ✅ User '{ $username }' enabled
+admin-user-promoted = ⭐ User '{ $username }' is now an admin
+admin-user-update-failed = ❌ Failed to update user: { $error }
+admin-tokens-header = 🎫 All Tokens ({ $count } total):
+setting-line = ⚙️  { $key } = { $value }
+setting-unknown = ❌ Unknown setting '{ $key }'
+settings-failed = ❌ Failed to get settings: { $error }
+setting-updated = ✅ { $key } = { $value } (until the server reloads its configuration)
+setting-update-failed = ❌ Failed to update setting: { $error }
+audit-empty = 📭 No audit entries.
+audit-line = { $time } | { $actor } | { $method } { $path } → { $status }
+audit-failed = ❌ Failed to read audit log: { $error }
+
+## Self-update
+
+update-current = 📦 Current version: { $version } ({ $channel } channel)
+update-up-to-date = ✅ Already up to date
+update-available = ⬆️  Update available: { $version }
+update-no-release = 📭 No { $channel } release published for { $target }
+update-installed = ✅ Updated to { $version }
+update-failed = ❌ Self-update failed: { $error }
+
+## Live events
+
+live-events-unavailable = ⚠️  Live events unavailable: { $error }
+live-connection-closed = 🔌 Live event connection closed
+listen-start = 🎧 Listening for WebSocket notifications...
+listen-stop-hint = Press Ctrl+C to stop
+listen-failed = ❌ Failed to connect WebSocket: { $error }
+new-notification = 🔔 New notification:
+text-message = 📝 Text message: { $text }
+tail-following = 🎧 Following live notifications, press Ctrl+C to stop
+
+## Interactive shell
+
+shell-welcome = 🐚 Connected to { $server }. Commands: send, tail, stats, search, exit (help for details)
+shell-unbalanced-quotes = ❌ Unbalanced quotes
+shell-sent = ✅ Notification sent
+shell-tail-stopped = ⏸️  Stopped following live events
+shell-tail-following = 🎧 Following live events (tail --off to stop)
+search-no-match = 📭 No notifications match '{ $text }'
+
+## Terminal dashboard
+
+tui-stats = Today { $today } · Total { $total } · Devices { $devices } · Silences { $silences }
+tui-live = ● live
+tui-offline = ○ offline, press r to reload
+tui-notifications = Notifications ({ $shown }/{ $total })
+tui-empty = No notifications
+tui-search = Search
+tui-details = Details
+tui-help = ↑/↓ j/k move · PgUp/PgDn page · Enter details · / search · r reload · q quit
+tui-search-help = Type to filter · Enter keep · Esc clear
+tui-load-failed = Failed to load notifications: { $error }
+tui-detail-priority = Priority: { $value }
+tui-detail-id = ID: { $value }
+tui-detail-tags = Tags: { $value }
+tui-detail-channel = Channel: { $value }
+tui-detail-expires = Expires: { $value }
+tui-detail-progress = Progress: { $value }
+
+## Graphical apps
+
+gui-loaded = Loaded { $count } notifications
+gui-sent = Notification sent successfully!
+gui-test-sent = Test notification sent successfully!
+gui-send-failed = Failed to send: { $error }
+gui-refreshed = Data refreshed
+gui-delete-not-implemented = Delete notification not yet implemented
+gui-token-create-not-implemented = Token creation not yet implemented
+gui-admin-loaded = Loaded { $users } users and { $tokens } tokens
+gui-admin-load-failed = Failed to load users and tokens: { $error }
+gui-user-updated = User { $username } updated
+gui-user-action-failed = Failed to update user: { $error }
+gui-token-revoked = Token revoked
+gui-token-revoke-failed = Failed to revoke token: { $error }
+gui-unknown = Unknown
+gui-admin-summary = Tokens: { $tokens } · Database: { $size } · Auth failures: { $failures }
+gui-preferences-login-required = Set --user-token or RUTIFY_USER_TOKEN to edit preferences
+gui-preferences-loaded = Preferences loaded
+gui-preferences-load-failed = Failed to load preferences: { $error }
+gui-preferences-saved = Preferences saved
+gui-preferences-save-failed = Failed to save preferences: { $error }
+gui-star-failed = Failed to update star: { $error }
+gui-settings-saved = Settings saved
+gui-settings-save-failed = Failed to save settings: { $error }
+gui-server-incomplete = Enter a name and URL for the server
+gui-servers-unreachable = Could not reach: { $servers }
@@ -0,0 +1,251 @@
+## 通用
+
+error-message = ❌ 错误：{ $message }
+connection-closed = 🔌 连接已关闭
+send-failed = ❌ 发送通知失败：{ $error }
+notifies-failed = ❌ 获取通知失败：{ $error }
+stats-failed = ❌ 获取统计信息失败：{ $error }
+answer-yes = 是
+answer-no = 否
+
+## 通知与统计
+
+notifies-header = 📬 通知（共 { $count } 条）：
+notification-received = 接收时间：{ $time }
+stats-header = 📊 服务器统计：
+stats-today = 今日通知：{ $count }
+stats-total = 通知总数：{ $count }
+stats-devices = 活跃设备：{ $count }
+stats-silences = 生效的静默规则：{ $count }
+stats-running = 服务器运行中：{ $running }
+stats-running-yes = ✅ 是
+stats-running-no = ❌ 否
+stats-watch-header = 每 { $interval } 秒：rutify-cli stats · { $time } · 按 Ctrl+C 停止
+stats-top-devices = 📱 今日通知最多的 { $count } 个设备：
+progress-done = 已完成
+progress-failed = 已失败
+
+## 发送
+
+send-success = ✅ 通知发送成功！
+send-stdin-success = ✅ 已从标准输入发送 { $count } 条通知
+e2e-key-generated = 🔑 共享密钥已写入 { $path }，请分发给所有发送或读取这些通知的客户端
+signing-key-generated = 🔑 私钥已写入 { $path }，创建 Token 时通过 `auth create-token --signing-key` 登记以下公钥：
+send-listen-start = 📤 正在发送通知并等待送达确认……
+send-listen-failed = ❌ 发送并监听失败：{ $error }
+response-received = 🔔 已确认送达：
+response-text = 📝 响应：{ $text }
+no-response = ⏰ 超时前未收到送达确认
+event-title = 标题：{ $value }
+event-message = 内容：{ $value }
+event-device = 设备：{ $value }
+event-time = 时间：{ $value }
+
+## 服务器
+
+devices-header = 📱 设备（{ $count }）：
+devices-empty = 📱 尚无设备发送过通知
+devices-line = { $device } | 今日 { $today } | 总计 { $total } | 最近 { $last }
+devices-failed = ❌ 获取设备统计失败：{ $error }
+health-ok = ✅ 服务器运行正常
+health-failed = ❌ 服务器健康检查失败
+health-error = ❌ 服务器健康检查失败：{ $error }
+
+## 认证
+
+not-logged-in = ❌ 未登录：没有已保存的用户 Token，也未设置 RUTIFY_USER_TOKEN
+user-token-env-missing = ❌ 未设置 RUTIFY_USER_TOKEN 环境变量
+login-hint = 💡 请先登录：{ $bin } auth login --username <用户名> --password <密码>
+registering = 🔐 正在注册新用户……
+registered = ✅ 用户 '{ $username }' 注册成功！
+register-login-hint = 💡 现在可以登录：{ $bin } auth login --username { $username } --password <密码>
+register-failed = ❌ 注册失败：{ $error }
+logging-in = 🔑 正在登录……
+login-success = ✅ 登录成功！
+login-failed = ❌ 登录失败：{ $error }
+logged-out = 👋 已退出配置档 '{ $profile }'
+token-saved-to-profile = 💾 Token 已保存到配置档 '{ $profile }'
+save-jwt-hint = 💡 请保存此 Token 供后续 API 调用使用：
+user-profile-loading = 👤 正在获取用户信息……
+user-profile-header = ✅ 用户信息：
+user-profile-failed = ❌ 获取用户信息失败：{ $error }
+label-user = 👤 用户：{ $value }
+label-email = 📧 邮箱：{ $value }
+label-role = 🔐 角色：{ $value }
+label-id = 🆔 ID：{ $value }
+label-usage = 📝 用途：{ $value }
+label-type = 🔐 类型：{ $value }
+label-device = 📱 设备：{ $value }
+label-created = 📅 创建时间：{ $value }
+label-expires = ⏰ 过期时间：{ $value }
+label-expires-at = ⏰ 过期时间：{ $value }
+label-last-used = 🔄 最近使用：{ $value }
+label-token = 🎫 Token：{ $value }
+label-token-id = 🆔 Token ID：{ $value }
+label-jwt = 🎫 JWT Token：{ $value }
+label-server = 服务器：{ $value }
+
+## Token
+
+token-creating = 🎫 正在创建通知 Token……
+token-creating-for = 🔑 正在创建新 Token，用途：'{ $usage }'，{ $hours } 小时后过期
+token-created = ✅ Token 创建成功！
+token-create-failed = ❌ 创建 Token 失败：{ $error }
+notify-token-saved = 💾 通知 Token 已保存到配置档 '{ $profile }'
+use-token-hint = 💡 发送通知时使用此 Token：
+token-save-hint = 💡 请妥善保存此 Token！
+tokens-listing = 📋 正在列出用户 Token……
+tokens-empty = 📭 没有 Token。
+tokens-header = 🎫 用户 Token（共 { $count } 个）：
+tokens-list-failed = ❌ 列出 Token 失败：{ $error }
+token-deleting = 🗑️  正在删除 Token { $id }……
+token-deleted = ✅ Token { $id } 已删除
+token-delete-failed = ❌ 删除 Token 失败：{ $error }
+token-setting = 🔐 正在设置认证 Token……
+token-set = 已设置 Token：{ $preview }...
+token-set-hint = 💡 后续请求将使用此 Token
+token-clearing = 🗑️  正在清除已保存的 Token……
+token-cleared = Token 已清除
+token-cleared-from-profile = 已从配置档 '{ $profile }' 清除 Token
+saved-to-profile = 💾 已保存到配置档 '{ $profile }'
+token-configured = ✅ 已配置 Token
+token-missing = ❌ 未配置 Token
+notify-token-configured = ✅ 已配置通知 Token
+notify-token-missing = ❌ 未配置通知 Token
+user-token-configured = ✅ 已配置用户 Token
+user-token-missing = ❌ 未登录
+
+## 配置档
+
+profile-status = 📁 配置档 '{ $profile }'（{ $path }）
+profile-line = { $marker } { $name } | { $server } | 通知 Token：{ $token } | 已登录：{ $logged_in }
+profiles-empty = 📭 { $path } 中没有保存的配置档
+profile-default-set = ✅ 默认配置档已设为 '{ $name }'
+profile-removed = 🗑️  配置档 '{ $name }' 已删除
+profile-not-found = ❌ 找不到配置档 '{ $name }'
+
+## 静默规则
+
+silence-created = 🔕 已创建静默规则 { $id }
+silence-expires-at = 过期时间：{ $time }
+silence-create-failed = ❌ 创建静默规则失败：{ $error }
+silences-empty = 📭 没有静默规则。
+silences-header = 🔕 静默规则（共 { $count } 条）：
+silence-line = { $id }. 设备={ $device } 频道={ $channel } 标签={ $tag } | 截止 { $until } | { $comment }
+silences-list-failed = ❌ 列出静默规则失败：{ $error }
+silence-expired = ✅ 静默规则 { $id } 已失效
+silence-expire-failed = ❌ 使静默规则失效失败：{ $error }
+
+## 心跳监控
+
+monitor-beat-ok = 💓 已上报 '{ $name }' 的心跳（下次截止 { $due }）
+monitor-beat-failed = ❌ 上报心跳失败：{ $error }
+monitor-created = 💓 已创建心跳监控 '{ $name }'，每 { $interval } 秒需要一次心跳
+monitor-create-failed = ❌ 创建心跳监控失败：{ $error }
+monitors-empty = 📭 没有心跳监控。
+monitors-header = 💓 心跳监控（共 { $count } 个）：
+monitor-line = { $name } [{ $status }] 每 { $interval } 秒（宽限 { $grace } 秒）| 最近心跳 { $last }
+monitors-list-failed = ❌ 列出心跳监控失败：{ $error }
+monitor-removed = 🗑️  已删除心跳监控 '{ $name }'
+monitor-remove-failed = ❌ 删除心跳监控失败：{ $error }
+
+## 管理
+
+purge-dry-run = 🔍 匹配 { $count } 条通知，未删除（试运行）
+purge-done = 🗑️  已清理 { $count } 条通知
+purge-failed = ❌ 清理通知失败：{ $error }
+admin-users-empty = 📭 没有用户。
+admin-users-header = 👥 用户（共 { $count } 个）：
+admin-user-line = { $username } <{ $email }> | { $role } | { $status } | 注册于 { $created }
+admin-user-status-active = 正常
+admin-user-status-disabled = 已停用
+admin-users-failed = ❌ 列出用户失败：{ $error }
+admin-user-disabled = 🚫 已停用用户 '{ $username }'
+admin-user-enabled = ✅ 已启用用户 '{ $username }'
+admin-user-promoted = ⭐ 用户 '{ $username }' 已成为管理员
+admin-user-update-failed = ❌ 修改用户失败：{ $error }
+admin-tokens-header = 🎫 全部 Token（共 { $count } 个）：
+setting-line = ⚙️  { $key } = { $value }
+setting-unknown = ❌ 未知设置 '{ $key }'
+settings-failed = ❌ 获取设置失败：{ $error }
+setting-updated = ✅ { $key } = { $value }（服务端重新加载配置后恢复）
+setting-update-failed = ❌ 修改设置失败：{ $error }
+audit-empty = 📭 没有审计记录。
+audit-line = { $time } | { $actor } | { $method } { $path } → { $status }
+audit-failed = ❌ 读取审计日志失败：{ $error }
+
+## 自更新
+
+update-current = 📦 当前版本：{ $version }（{ $channel } 渠道）
+update-up-to-date = ✅ 已是最新版本
+update-available = ⬆️  有可用更新：{ $version }
+update-no-release = 📭 { $target } 没有已发布的 { $channel } 版本
+update-installed = ✅ 已更新到 { $version }
+update-failed = ❌ 自更新失败：{ $error }
+
+## 实时事件
+
+live-events-unavailable = ⚠️  实时事件不可用：{ $error }
+live-connection-closed = 🔌 实时事件连接已关闭
+listen-start = 🎧 正在监听 WebSocket 通知……
+listen-stop-hint = 按 Ctrl+C 停止
+listen-failed = ❌ 连接 WebSocket 失败：{ $error }
+new-notification = 🔔 新通知：
+text-message = 📝 文本消息：{ $text }
+tail-following = 🎧 正在跟随实时通知，按 Ctrl+C 停止
+
+## 交互模式
+
+shell-welcome = 🐚 已连接到 { $server }。可用命令：send、tail、stats、search、exit（输入 help 查看详情）
+shell-unbalanced-quotes = ❌ 引号不匹配
+shell-sent = ✅ 通知已发送
+shell-tail-stopped = ⏸️  已停止跟随实时事件
+shell-tail-following = 🎧 正在跟随实时事件（tail --off 停止）
+search-no-match = 📭 没有匹配 '{ $text }' 的通知
+
+## 终端面板
+
+tui-stats = 今日 { $today } · 总计 { $total } · 设备 { $devices } · 静默 { $silences }
+tui-live = ● 实时
+tui-offline = ○ 离线，按 r 重新加载
+tui-notifications = 通知（{ $shown }/{ $total }）
+tui-empty = 没有通知
+tui-search = 搜索
+tui-details = 详情
+tui-help = ↑/↓ j/k 移动 · PgUp/PgDn 翻页 · Enter 详情 · / 搜索 · r 重新加载 · q 退出
+tui-search-help = 输入以过滤 · Enter 保留 · Esc 清除
+tui-load-failed = 加载通知失败：{ $error }
+tui-detail-priority = 优先级：{ $value }
+tui-detail-id = ID：{ $value }
+tui-detail-tags = 标签：{ $value }
+tui-detail-channel = 频道：{ $value }
+tui-detail-expires = 过期时间：{ $value }
+tui-detail-progress = 进度：{ $value }
+
+## 图形界面
+
+gui-loaded = 已加载 { $count } 条通知
+gui-sent = 通知发送成功！
+gui-test-sent = 测试通知发送成功！
+gui-send-failed = 发送失败：{ $error }
+gui-refreshed = 数据已刷新
+gui-delete-not-implemented = 删除通知尚未实现
+gui-token-create-not-implemented = 创建 Token 尚未实现
+gui-admin-loaded = 已加载 { $users } 个用户和 { $tokens } 个 Token
+gui-admin-load-failed = 加载用户和 Token 失败：{ $error }
+gui-user-updated = 已更新用户 { $username }
+gui-user-action-failed = 更新用户失败：{ $error }
+gui-token-revoked = Token 已吊销
+gui-token-revoke-failed = 吊销 Token 失败：{ $error }
+gui-unknown = 未知
+gui-admin-summary = Token：{ $tokens } · 数据库：{ $size } · 认证失败：{ $failures }
+gui-preferences-login-required = 请通过 --user-token 或 RUTIFY_USER_TOKEN 设置用户 JWT 后编辑偏好
+gui-preferences-loaded = 偏好已加载
+gui-preferences-load-failed = 加载偏好失败：{ $error }
+gui-preferences-saved = 偏好已保存
+gui-preferences-save-failed = 保存偏好失败：{ $error }
+gui-star-failed = 更新星标失败：{ $error }
+gui-settings-saved = 设置已保存
+gui-settings-save-failed = 保存设置失败：{ $error }
+gui-server-incomplete = 请填写服务器名称与地址
+gui-servers-unreachable = 无法连接：{ $servers }
@@ -0,0 +1,190 @@
+use rutify_sdk::NotifyEvent;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::WebSocketNotification;
+
+type Handler<T> = Arc<dyn Fn(&T) + Send + Sync>;
+
+/// 处理器注册句柄，用于 [`EventBus::off`] 注销
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HandlerId(u64);
+
+#[derive(Default)]
+struct Handlers {
+    notification: Vec<(HandlerId, Handler<NotifyEvent>)>,
+    text: Vec<(HandlerId, Handler<String>)>,
+    error: Vec<(HandlerId, Handler<String>)>,
+    close: Vec<(HandlerId, Handler<()>)>,
+}
+
+/// WebSocket 事件总线
+///
+/// 多个组件各自注册处理器，共享同一条 WebSocket 连接（见
+/// [`ClientState::start_event_bus`](crate::ClientState::start_event_bus)）。
+/// 处理器在连接任务中同步调用，不应执行耗时操作。
+#[derive(Clone, Default)]
+pub struct EventBus {
+    handlers: Arc<Mutex<Handlers>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn next_id(&self) -> HandlerId {
+        HandlerId(self.next_id.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// 收到通知事件
+    pub fn on_notification(
+        &self,
+        handler: impl Fn(&NotifyEvent) + Send + Sync + 'static,
+    ) -> HandlerId {
+        let id = self.next_id();
+        let mut handlers = self.handlers.lock().unwrap();
+        handlers.notification.push((id, Arc::new(handler)));
+        id
+    }
+
+    /// 收到无法解析为事件的文本消息
+    pub fn on_text(&self, handler: impl Fn(&String) + Send + Sync + 'static) -> HandlerId {
+        let id = self.next_id();
+        let mut handlers = self.handlers.lock().unwrap();
+        handlers.text.push((id, Arc::new(handler)));
+        id
+    }
+
+    /// 连接出错（随后连接结束）
+    pub fn on_error(&self, handler: impl Fn(&String) + Send + Sync + 'static) -> HandlerId {
+        let id = self.next_id();
+        let mut handlers = self.handlers.lock().unwrap();
+        handlers.error.push((id, Arc::new(handler)));
+        id
+    }
+
+    /// 连接关闭
+    pub fn on_close(&self, handler: impl Fn() + Send + Sync + 'static) -> HandlerId {
+        let id = self.next_id();
+        let mut handlers = self.handlers.lock().unwrap();
+        handlers.close.push((id, Arc::new(move |_: &()| handler())));
+        id
+    }
+
+    /// 注销处理器，返回是否找到
+    pub fn off(&self, id: HandlerId) -> bool {
+        let mut handlers = self.handlers.lock().unwrap();
+        let before = handler_count(&handlers);
+        handlers
+            .notification
+            .retain(|(handler_id, _)| *handler_id != id);
+        handlers.text.retain(|(handler_id, _)| *handler_id != id);
+        handlers.error.retain(|(handler_id, _)| *handler_id != id);
+        handlers.close.retain(|(handler_id, _)| *handler_id != id);
+        handler_count(&handlers) != before
+    }
+
+    /// 分发消息给对应类型的全部处理器
+    ///
+    /// 先复制处理器列表再调用，处理器内部可以注册或注销处理器。
+    pub fn dispatch(&self, message: &WebSocketNotification) {
+        match message {
+            WebSocketNotification::Event(event) => {
+                let handlers = snapshot(&self.handlers.lock().unwrap().notification);
+                handlers.iter().for_each(|handler| handler(event));
+            }
+            WebSocketNotification::Text(text) => {
+                let handlers = snapshot(&self.handlers.lock().unwrap().text);
+                handlers.iter().for_each(|handler| handler(text));
+            }
+            WebSocketNotification::Error { message } => {
+                let handlers = snapshot(&self.handlers.lock().unwrap().error);
+                handlers.iter().for_each(|handler| handler(message));
+            }
+            WebSocketNotification::Close => {
+                let handlers = snapshot(&self.handlers.lock().unwrap().close);
+                handlers.iter().for_each(|handler| handler(&()));
+            }
+        }
+    }
+}
+
+fn handler_count(handlers: &Handlers) -> usize {
+    handlers.notification.len() + handlers.text.len() + handlers.error.len() + handlers.close.len()
+}
+
+fn snapshot<T>(handlers: &[(HandlerId, Handler<T>)]) -> Vec<Handler<T>> {
+    handlers
+        .iter()
+        .map(|(_, handler)| Arc::clone(handler))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rutify_sdk::{EventKind, NotificationData, NotifyId};
+    use std::sync::atomic::AtomicUsize;
+
+    fn event() -> WebSocketNotification {
+        WebSocketNotification::Event(Box::new(NotifyEvent {
+            event: EventKind::Created,
+            data: NotificationData {
+                id: Some(NotifyId(1)),
+                notify: "body".to_string(),
+                title: "title".to_string(),
+                device: "nas".to_string(),
+                priority: 3,
+                tags: Vec::new(),
+                recipient: None,
+                channel: None,
+                expires_at: None,
+                progress: None,
+                link_previews: Vec::new(),
+                signature: None,
+                correlation_id: None,
+                token_id: None,
+            },
+            timestamp: chrono::Utc::now(),
+        }))
+    }
+
+    #[test]
+    fn test_dispatch_to_all_handlers() {
+        let bus = EventBus::new();
+        let notified = Arc::new(AtomicUsize::new(0));
+        let closed = Arc::new(AtomicUsize::new(0));
+
+        let first = {
+            let notified = Arc::clone(&notified);
+            bus.on_notification(move |_| {
+                notified.fetch_add(1, Ordering::SeqCst);
+            })
+        };
+        {
+            let notified = Arc::clone(&notified);
+            bus.on_notification(move |event| {
+                assert_eq!(event.data.device, "nas");
+                notified.fetch_add(10, Ordering::SeqCst);
+            });
+        }
+        {
+            let closed = Arc::clone(&closed);
+            bus.on_close(move || {
+                closed.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+
+        bus.dispatch(&event());
+        assert_eq!(notified.load(Ordering::SeqCst), 11);
+
+        assert!(bus.off(first));
+        assert!(!bus.off(first));
+        bus.dispatch(&event());
+        bus.dispatch(&WebSocketNotification::Close);
+        assert_eq!(notified.load(Ordering::SeqCst), 21);
+        assert_eq!(closed.load(Ordering::SeqCst), 1);
+    }
+}
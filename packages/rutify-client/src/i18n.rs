@@ -0,0 +1,210 @@
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentResource, FluentValue};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+
+pub use fluent_bundle::FluentArgs;
+
+const EN_FTL: &str = include_str!("../locales/en/rutify.ftl");
+const ZH_CN_FTL: &str = include_str!("../locales/zh-CN/rutify.ftl");
+
+/// 界面语言
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Language {
+    #[default]
+    En,
+    ZhCn,
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    /// 接受 `zh-CN`、`zh_CN.UTF-8`、`en_US` 等写法
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tag = s
+            .split(['.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase()
+            .replace('_', "-");
+        match tag.split('-').next() {
+            Some("en") | Some("c") | Some("posix") => Ok(Self::En),
+            Some("zh") => Ok(Self::ZhCn),
+            _ => Err(format!(
+                "unsupported language '{}', expected en or zh-CN",
+                s
+            )),
+        }
+    }
+}
+
+impl Language {
+    /// 依次读取显式设置、`RUTIFY_LANG`、`LC_ALL`、`LC_MESSAGES`、`LANG`，都不可用时为英文
+    pub fn detect(explicit: Option<&str>) -> Self {
+        explicit
+            .map(str::to_string)
+            .into_iter()
+            .chain(
+                ["RUTIFY_LANG", "LC_ALL", "LC_MESSAGES", "LANG"]
+                    .into_iter()
+                    .filter_map(|key| std::env::var(key).ok()),
+            )
+            .filter(|value| !value.is_empty())
+            .find_map(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// BCP 47 语言标签
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::ZhCn => "zh-CN",
+        }
+    }
+
+    /// Slint 内置翻译使用的目录名，英文即源文本
+    pub fn slint_code(&self) -> &'static str {
+        match self {
+            Self::En => "",
+            Self::ZhCn => "zh_CN",
+        }
+    }
+
+    fn source(&self) -> &'static str {
+        match self {
+            Self::En => EN_FTL,
+            Self::ZhCn => ZH_CN_FTL,
+        }
+    }
+}
+
+/// 当前语言的消息包，缺失的消息回退到英文
+struct Localizer {
+    language: Language,
+    bundle: FluentBundle<FluentResource>,
+    fallback: FluentBundle<FluentResource>,
+}
+
+impl Localizer {
+    fn new(language: Language) -> Self {
+        Self {
+            language,
+            bundle: bundle(language),
+            fallback: bundle(Language::En),
+        }
+    }
+
+    fn translate(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        for bundle in [&self.bundle, &self.fallback] {
+            let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) else {
+                continue;
+            };
+            let mut errors = Vec::new();
+            return bundle
+                .format_pattern(pattern, args, &mut errors)
+                .into_owned();
+        }
+        id.to_string()
+    }
+}
+
+fn bundle(language: Language) -> FluentBundle<FluentResource> {
+    let langid: LanguageIdentifier = language.code().parse().expect("valid language tag");
+    let resource = FluentResource::try_new(language.source().to_string())
+        .expect("bundled FTL resources are valid");
+    let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+    // 终端输出不需要 Unicode 双向隔离符
+    bundle.set_use_isolating(false);
+    bundle
+        .add_resource(resource)
+        .expect("bundled FTL resources have no duplicate messages");
+    bundle
+}
+
+static LOCALIZER: OnceLock<Localizer> = OnceLock::new();
+
+/// 设置进程使用的语言，需在第一次翻译前调用；未调用时按环境变量自动检测
+pub fn init(language: Language) {
+    let _ = LOCALIZER.set(Localizer::new(language));
+}
+
+/// 当前使用的语言
+pub fn language() -> Language {
+    localizer().language
+}
+
+fn localizer() -> &'static Localizer {
+    LOCALIZER.get_or_init(|| Localizer::new(Language::detect(None)))
+}
+
+/// 按消息 ID 翻译，找不到时返回 ID 本身；一般通过 [`t!`](crate::t) 调用
+pub fn translate(id: &str, args: Option<&FluentArgs>) -> String {
+    localizer().translate(id, args)
+}
+
+/// 将任意可显示的值作为 Fluent 参数
+pub fn arg(value: impl std::fmt::Display) -> FluentValue<'static> {
+    FluentValue::from(value.to_string())
+}
+
+/// 翻译消息：`t!("sent")`、`t!("token-deleted", id = id)`
+///
+/// 参数统一按字符串传入，数字需要复数规则时直接传 `FluentValue`。
+#[macro_export]
+macro_rules! t {
+    ($id:expr) => {
+        $crate::i18n::translate($id, None)
+    };
+    ($id:expr, $($key:ident = $value:expr),+ $(,)?) => {{
+        let mut args = $crate::i18n::FluentArgs::new();
+        $(args.set(stringify!($key), $crate::i18n::arg(&$value));)+
+        $crate::i18n::translate($id, Some(&args))
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_parsing() {
+        assert_eq!("zh_CN.UTF-8".parse::<Language>(), Ok(Language::ZhCn));
+        assert_eq!("zh-Hans".parse::<Language>(), Ok(Language::ZhCn));
+        assert_eq!("en_US.UTF-8".parse::<Language>(), Ok(Language::En));
+        assert_eq!("C".parse::<Language>(), Ok(Language::En));
+        assert!("fr_FR".parse::<Language>().is_err());
+        assert_eq!(Language::detect(Some("zh-CN")), Language::ZhCn);
+    }
+
+    #[test]
+    fn test_bundles_translate_every_message() {
+        let en = Localizer::new(Language::En);
+        let zh = Localizer::new(Language::ZhCn);
+
+        let mut args = FluentArgs::new();
+        args.set("id", arg(7));
+        assert_eq!(
+            en.translate("token-deleted", Some(&args)),
+            "✅ Token 7 deleted successfully!"
+        );
+        assert_eq!(
+            zh.translate("token-deleted", Some(&args)),
+            "✅ Token 7 已删除"
+        );
+        assert_eq!(en.translate("no-such-message", None), "no-such-message");
+
+        // 两种语言的消息 ID 必须一致
+        let ids = |source: &str| {
+            let mut ids: Vec<String> = source
+                .lines()
+                .filter_map(|line| line.split_once(" ="))
+                .filter(|(id, _)| !id.starts_with([' ', '#']) && !id.is_empty())
+                .map(|(id, _)| id.to_string())
+                .collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(ids(EN_FTL), ids(ZH_CN_FTL));
+    }
+}
@@ -1,18 +1,33 @@
 use anyhow::Result;
-use rutify_sdk::client::TokenResponse;
 use rutify_sdk::{
-    NotificationInput, NotifyEvent, NotifyItem, RutifyClient, Stats, WebSocketMessage,
+    Acker, CreateTokenResponse, EventKind, LinkPreview, NotificationInput, NotifyEvent, NotifyId,
+    NotifyItem, Progress, ProgressStatus, RutifyClient, Stats, WebSocketMessage,
+    new_correlation_id,
 };
 use std::collections::VecDeque;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::sync::mpsc;
 
+pub mod events;
+pub mod i18n;
+pub mod settings;
+pub mod time_format;
+
+pub use events::{EventBus, HandlerId};
+pub use settings::{GuiSettings, SavedServer, ThemePreference};
+pub use time_format::{TimeFormat, TimeZoneSetting, format_relative};
+
 /// 共享的客户端状态管理
 #[derive(Clone)]
 pub struct ClientState {
     pub client: RutifyClient,
     pub notifications: Arc<Mutex<VecDeque<NotifyItem>>>,
     pub stats: Arc<Mutex<Option<Stats>>>,
+    /// 事件处理器注册表，由 [`ClientState::start_event_bus`] 分发
+    pub events: EventBus,
+    /// 输出时间戳时使用的时区与相对时间设置
+    pub time_format: TimeFormat,
 }
 
 impl ClientState {
@@ -21,9 +36,17 @@ impl ClientState {
             client: RutifyClient::new(server_url),
             notifications: Arc::new(Mutex::new(VecDeque::with_capacity(100))),
             stats: Arc::new(Mutex::new(None)),
+            events: EventBus::new(),
+            time_format: TimeFormat::default(),
         }
     }
 
+    /// 设置时间戳显示方式
+    pub fn with_time_format(mut self, time_format: TimeFormat) -> Self {
+        self.time_format = time_format;
+        self
+    }
+
     /// 获取所有通知
     pub async fn get_notifies(&self) -> Result<Vec<NotifyItem>> {
         let notifies = self.client.get_notifies().await?;
@@ -63,70 +86,88 @@ impl ClientState {
             .map_err(|e| anyhow::Error::new(e))
     }
 
-    /// 监听WebSocket消息并更新状态
+    /// 监听WebSocket消息并更新状态，通知交给接收方后自动确认送达
     pub async fn listen_websocket_updates(
         &self,
     ) -> Result<mpsc::UnboundedReceiver<WebSocketNotification>> {
+        let (ws_rx, acker) = self.client.connect_websocket_with_acker().await?;
+        Ok(self.forward_updates(ws_rx, Some(acker)))
+    }
+
+    /// 把连接上的消息写入本地缓存后转发；`acker` 为空时不确认送达
+    fn forward_updates(
+        &self,
+        mut ws_rx: mpsc::UnboundedReceiver<WebSocketMessage>,
+        acker: Option<Acker>,
+    ) -> mpsc::UnboundedReceiver<WebSocketNotification> {
         let (tx, rx) = mpsc::unbounded_channel();
         let notifications = Arc::clone(&self.notifications);
 
-        let mut ws_rx = self.connect_websocket().await?;
-
         tokio::spawn(async move {
             while let Some(msg) = ws_rx.recv().await {
-                match msg {
-                    WebSocketMessage::Event(event) => {
-                        // 更新本地通知缓存
-                        let mut guard = notifications.lock().unwrap();
-                        if guard.len() >= 100 {
-                            guard.pop_front();
-                        }
-                        guard.push_back(NotifyItem {
-                            id: 0, // Will be set by server
-                            title: event.data.title.clone(),
-                            notify: event.data.notify.clone(),
-                            device: event.data.device.clone(),
-                            received_at: event.timestamp,
-                        });
-
-                        // 发送通知
-                        let _ = tx.send(WebSocketNotification::Event(event));
-                    }
-                    WebSocketMessage::Text(text) => {
-                        let _ = tx.send(WebSocketNotification::Text(text));
-                    }
-                    WebSocketMessage::Error { message } => {
-                        let _ = tx.send(WebSocketNotification::Error { message });
-                    }
-                    WebSocketMessage::Close => {
-                        let _ = tx.send(WebSocketNotification::Close);
-                        break;
-                    }
-                    _ => {}
+                let Some(notification) = record_message(&notifications, msg) else {
+                    continue;
+                };
+                let closed = matches!(notification, WebSocketNotification::Close);
+                let ack_id = notification.ack_id();
+                if tx.send(notification).is_ok()
+                    && let Some(acker) = &acker
+                {
+                    ack(acker, ack_id);
+                }
+                if closed {
+                    break;
                 }
             }
         });
 
-        Ok(rx)
+        rx
+    }
+
+    /// 建立一条 WebSocket 连接，并把消息分发给 `events` 中注册的全部处理器
+    ///
+    /// 连接关闭后任务结束，重新连接需再次调用。
+    pub async fn start_event_bus(&self) -> Result<tokio::task::JoinHandle<()>> {
+        let notifications = Arc::clone(&self.notifications);
+        let events = self.events.clone();
+        let (mut ws_rx, acker) = self.client.connect_websocket_with_acker().await?;
+
+        Ok(tokio::spawn(async move {
+            while let Some(msg) = ws_rx.recv().await {
+                let Some(notification) = record_message(&notifications, msg) else {
+                    continue;
+                };
+                let closed = matches!(notification, WebSocketNotification::Close);
+                events.dispatch(&notification);
+                ack(&acker, notification.ack_id());
+                if closed {
+                    break;
+                }
+            }
+        }))
     }
 
-    /// 设置认证Token
-    pub fn set_token(&mut self, token: &str) {
+    /// 设置认证Token（所有克隆的状态同时生效）
+    pub fn set_token(&self, token: &str) {
         self.client.set_token(token);
     }
 
     /// 清除认证Token
-    pub fn clear_token(&mut self) {
+    pub fn clear_token(&self) {
         self.client.clear_token();
     }
 
     /// 检查是否有Token
     pub fn has_token(&self) -> bool {
-        self.client.token.is_some()
+        self.client.has_token()
     }
 
     /// 创建新的Token
-    pub async fn create_token(&self, usage: &str, expires_in_hours: u64) -> Result<TokenResponse> {
+    pub async fn create_token(
+        &self,
+        usage: &str,
+        expires_in_hours: u64,
+    ) -> Result<CreateTokenResponse> {
         self.client
             .create_token(usage, expires_in_hours)
             .await
@@ -140,44 +181,151 @@ impl ClientState {
             client,
             notifications: Arc::new(Mutex::new(VecDeque::with_capacity(100))),
             stats: Arc::new(Mutex::new(None)),
+            events: EventBus::new(),
+            time_format: TimeFormat::default(),
         }
     }
 }
 
+/// 将事件写入本地通知缓存，并转换为对外的通知类型
+fn record_message(
+    notifications: &Mutex<VecDeque<NotifyItem>>,
+    msg: WebSocketMessage,
+) -> Option<WebSocketNotification> {
+    match msg {
+        WebSocketMessage::Event(event) => {
+            // 更新本地通知缓存：更新与升级事件就地替换，删除事件移除已缓存的通知
+            let mut guard = notifications.lock().unwrap();
+            let cached = event
+                .data
+                .id
+                .and_then(|id| guard.iter().position(|item| item.id == id));
+            match (event.event, cached) {
+                (EventKind::Updated | EventKind::Escalated, Some(index)) => {
+                    guard[index].apply_update(&event.data);
+                }
+                (EventKind::Deleted, Some(index)) => {
+                    guard.remove(index);
+                }
+                (kind, _) if kind.is_notification() => {
+                    if guard.len() >= 100 {
+                        guard.pop_front();
+                    }
+                    guard.push_back(NotifyItem {
+                        id: event.data.id.unwrap_or_default(),
+                        title: event.data.title.clone(),
+                        notify: event.data.notify.clone(),
+                        device: event.data.device.clone(),
+                        priority: event.data.priority,
+                        tags: event.data.tags.clone(),
+                        channel: event.data.channel.clone(),
+                        received_at: event.timestamp,
+                        expires_at: event.data.expires_at,
+                        pinned: false,
+                        starred: false,
+                        progress: event.data.progress,
+                        device_source: None,
+                        link_previews: event.data.link_previews.clone(),
+                        signature: event.data.signature.clone(),
+                        correlation_id: event.data.correlation_id.clone(),
+                    });
+                }
+                _ => {}
+            }
+
+            Some(WebSocketNotification::Event(event))
+        }
+        WebSocketMessage::Text(text) => Some(WebSocketNotification::Text(text)),
+        WebSocketMessage::Error { message } => Some(WebSocketNotification::Error { message }),
+        WebSocketMessage::Close => Some(WebSocketNotification::Close),
+        _ => None,
+    }
+}
+
+/// 通知交给使用方处理后向服务端确认送达，连接已关闭时忽略
+fn ack(acker: &Acker, id: Option<NotifyId>) {
+    if let Some(id) = id {
+        let _ = acker.ack(id);
+    }
+}
+
 /// WebSocket通知类型
 #[derive(Debug, Clone)]
 pub enum WebSocketNotification {
-    Event(NotifyEvent),
+    Event(Box<NotifyEvent>),
     Text(String),
     Error { message: String },
     Close,
 }
 
-/// 发送通知并监听响应的便捷方法
+impl WebSocketNotification {
+    /// 需要向服务端确认送达的通知 ID，见 [`NotifyEvent::ack_id`]
+    pub fn ack_id(&self) -> Option<NotifyId> {
+        match self {
+            Self::Event(event) => event.ack_id(),
+            _ => None,
+        }
+    }
+
+    /// 是否为关联 ID 为 `correlation_id` 的通知已被订阅者确认送达的事件
+    fn confirms_delivery_of(&self, correlation_id: &str) -> bool {
+        matches!(self, Self::Event(event)
+            if event.event == EventKind::Delivered
+                && event.data.correlation_id.as_deref() == Some(correlation_id))
+    }
+}
+
+/// [`send_and_listen`] 默认等待送达确认的时间
+pub const DEFAULT_LISTEN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 发送通知并等待其他订阅者确认送达的便捷方法
+///
+/// 先建立 WebSocket 连接再发送，避免确认事件在订阅之前到达；确认事件按本次发送生成的
+/// 关联 ID 匹配。超时或通知被服务端插件丢弃时返回 `Ok(None)`，连接出错或关闭时返回对应消息。
 pub async fn send_and_listen(
     state: &ClientState,
     message: String,
     title: Option<String>,
     device: Option<String>,
+    timeout: Duration,
 ) -> Result<Option<WebSocketNotification>> {
+    let correlation_id = new_correlation_id();
     let input = NotificationInput {
         notify: message,
         title,
         device,
+        correlation_id: Some(correlation_id.clone()),
+        ..Default::default()
     };
 
-    // 发送通知
-    state.send_notification(&input).await?;
-
-    // 监听响应
-    let mut rx = state.listen_websocket_updates().await?;
+    // 这条连接只用于等待确认，不确认自己发送的通知
+    let ws_rx = state.connect_websocket().await?;
+    let mut rx = state.forward_updates(ws_rx, None);
 
-    // 等待第一个响应
-    if let Some(notification) = rx.recv().await {
-        Ok(Some(notification))
-    } else {
-        Ok(None)
+    if state
+        .client
+        .send_notification_with_id(&input)
+        .await?
+        .is_none()
+    {
+        return Ok(None);
     }
+
+    let confirmation = async {
+        while let Some(notification) = rx.recv().await {
+            let finished = matches!(
+                notification,
+                WebSocketNotification::Error { .. } | WebSocketNotification::Close
+            );
+            if finished || notification.confirms_delivery_of(&correlation_id) {
+                return Some(notification);
+            }
+        }
+        None
+    };
+    Ok(tokio::time::timeout(timeout, confirmation)
+        .await
+        .unwrap_or(None))
 }
 
 /// 健康检查
@@ -189,27 +337,196 @@ pub async fn health_check(state: &ClientState) -> Result<bool> {
 }
 
 /// 格式化通知显示
-pub fn format_notification(notify: &NotifyItem) -> String {
+pub fn format_notification(notify: &NotifyItem, time_format: &TimeFormat) -> String {
     format!(
-        "{} - {} ({})\nReceived: {}",
+        "{} - {} ({})\n{}",
         notify.title,
         notify.notify,
         notify.device,
-        notify.received_at.format("%Y-%m-%d %H:%M:%S")
+        t!(
+            "notification-received",
+            time = time_format.format(notify.received_at)
+        )
     )
 }
 
+/// 标题、正文或设备名是否包含 `text`（不区分大小写），空字符串匹配全部
+pub fn matches_search(notify: &NotifyItem, text: &str) -> bool {
+    let needle = text.to_lowercase();
+    [&notify.title, &notify.notify, &notify.device]
+        .iter()
+        .any(|field| field.to_lowercase().contains(&needle))
+}
+
+/// 合并多台服务器的通知，按接收时间从新到旧排列，每条附带来源名称
+pub fn merge_sources(sources: Vec<(String, Vec<NotifyItem>)>) -> Vec<(String, NotifyItem)> {
+    let mut merged: Vec<(String, NotifyItem)> = sources
+        .into_iter()
+        .flat_map(|(source, items)| items.into_iter().map(move |item| (source.clone(), item)))
+        .collect();
+    merged.sort_by_key(|(_, item)| std::cmp::Reverse(item.received_at));
+    merged
+}
+
+/// 进度的简短文字：运行中为百分比（总量未知时为当前值），结束后为状态
+pub fn format_progress(progress: &Progress) -> String {
+    match progress.status {
+        ProgressStatus::Running => progress.to_string(),
+        ProgressStatus::Done => t!("progress-done"),
+        ProgressStatus::Failed => t!("progress-failed"),
+    }
+}
+
+/// 链接预览的一行文字：有标题时为 `标题 · 域名`，否则为链接本身
+pub fn format_link_preview(preview: &LinkPreview) -> String {
+    let host = preview
+        .url
+        .split_once("://")
+        .and_then(|(_, rest)| rest.split(['/', '?', '#']).next())
+        .unwrap_or(&preview.url);
+    match &preview.title {
+        Some(title) => format!("{title} · {host}"),
+        None => preview.url.clone(),
+    }
+}
+
 /// 格式化统计信息显示
 pub fn format_stats(stats: &Stats) -> String {
-    format!(
-        "Today's notifications: {}\nTotal notifications: {}\nActive devices: {}\nServer running: {}",
-        stats.today_count,
-        stats.total_count,
-        stats.device_count,
-        if stats.is_running {
-            "✅ Yes"
-        } else {
-            "❌ No"
+    let running = if stats.is_running {
+        t!("stats-running-yes")
+    } else {
+        t!("stats-running-no")
+    };
+    [
+        t!("stats-today", count = stats.today_count),
+        t!("stats-total", count = stats.total_count),
+        t!("stats-devices", count = stats.device_count),
+        t!("stats-silences", count = stats.active_silences),
+        t!("stats-running", running = running),
+    ]
+    .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rutify_sdk::NotificationData;
+
+    fn message(event: EventKind, notify: &str) -> WebSocketMessage {
+        WebSocketMessage::Event(Box::new(NotifyEvent {
+            event,
+            data: NotificationData {
+                id: Some(NotifyId(7)),
+                notify: notify.to_string(),
+                title: "build".to_string(),
+                device: "ci".to_string(),
+                priority: 3,
+                tags: Vec::new(),
+                recipient: None,
+                channel: None,
+                expires_at: None,
+                progress: None,
+                link_previews: Vec::new(),
+                signature: None,
+                correlation_id: None,
+                token_id: None,
+            },
+            timestamp: chrono::Utc::now(),
+        }))
+    }
+
+    #[test]
+    fn test_update_replaces_cached_notification() {
+        let notifications = Mutex::new(VecDeque::new());
+        record_message(&notifications, message(EventKind::Created, "build 45%"));
+        record_message(&notifications, message(EventKind::Updated, "build 80%"));
+        record_message(&notifications, message(EventKind::Acked, "build 80%"));
+
+        {
+            let guard = notifications.lock().unwrap();
+            assert_eq!(guard.len(), 1);
+            assert_eq!(guard[0].id, NotifyId(7));
+            assert_eq!(guard[0].notify, "build 80%");
         }
-    )
+
+        record_message(&notifications, message(EventKind::Deleted, "build 80%"));
+        assert!(notifications.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_delivery_confirmation() {
+        let WebSocketMessage::Event(mut event) = message(EventKind::Created, "build") else {
+            unreachable!()
+        };
+        event.data.correlation_id = Some("c1".to_string());
+        let created = WebSocketNotification::Event(event.clone());
+        assert_eq!(created.ack_id(), Some(NotifyId(7)));
+        assert!(!created.confirms_delivery_of("c1"));
+
+        event.event = EventKind::Delivered;
+        let delivered = WebSocketNotification::Event(event);
+        assert_eq!(delivered.ack_id(), None);
+        assert!(delivered.confirms_delivery_of("c1"));
+        assert!(!delivered.confirms_delivery_of("c2"));
+    }
+
+    #[test]
+    fn test_matches_search() {
+        let item: NotifyItem = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "title": "Disk Full",
+            "notify": "/var is at 95%",
+            "device": "nas",
+            "received_at": "2026-01-01T00:00:00Z"
+        }))
+        .unwrap();
+        assert!(matches_search(&item, ""));
+        assert!(matches_search(&item, "disk"));
+        assert!(matches_search(&item, "NAS"));
+        assert!(!matches_search(&item, "backup"));
+    }
+
+    #[test]
+    fn test_merge_sources() {
+        let item = |id: i32, at: &str| -> NotifyItem {
+            serde_json::from_value(serde_json::json!({
+                "id": id,
+                "title": "t",
+                "notify": "n",
+                "device": "d",
+                "received_at": at
+            }))
+            .unwrap()
+        };
+        let merged = merge_sources(vec![
+            (
+                "home".to_string(),
+                vec![
+                    item(2, "2026-01-01T00:02:00Z"),
+                    item(1, "2026-01-01T00:00:00Z"),
+                ],
+            ),
+            ("work".to_string(), vec![item(1, "2026-01-01T00:01:00Z")]),
+        ]);
+        let order: Vec<(&str, i32)> = merged
+            .iter()
+            .map(|(source, item)| (source.as_str(), item.id.get()))
+            .collect();
+        assert_eq!(order, [("home", 2), ("work", 1), ("home", 1)]);
+    }
+
+    #[test]
+    fn test_format_link_preview() {
+        let mut preview = LinkPreview {
+            url: "https://ci.example.com/jobs/42?tab=log".to_string(),
+            title: Some("Job #42".to_string()),
+            favicon: None,
+        };
+        assert_eq!(format_link_preview(&preview), "Job #42 · ci.example.com");
+        preview.title = None;
+        assert_eq!(
+            format_link_preview(&preview),
+            "https://ci.example.com/jobs/42?tab=log"
+        );
+    }
 }
@@ -0,0 +1,188 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// 图形界面主题
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemePreference {
+    /// 跟随系统
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+/// rutify-application 保存的服务器及其 Token
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SavedServer {
+    /// 显示名称，合并列表中作为来源标签
+    pub name: String,
+    pub url: String,
+    /// 通知 Token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// 用户 JWT
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_token: Option<String>,
+}
+
+/// 图形界面（rutify-application、rutify-panel、服务端 --ui 窗口）共用的设置
+///
+/// 保存在 [`config_dir`] 下的 `gui.json`，可用 `RUTIFY_GUI_CONFIG` 指定路径。
+/// 文件包含 Token，Unix 下仅允许当前用户读写。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GuiSettings {
+    /// 上次使用的服务器地址，命令行未指定时使用
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server: Option<String>,
+    #[serde(default)]
+    pub theme: ThemePreference,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub servers: Vec<SavedServer>,
+    /// 合并显示所有已保存服务器的通知
+    #[serde(default)]
+    pub aggregate: bool,
+}
+
+impl GuiSettings {
+    /// 读取设置，文件不存在时返回默认值
+    pub fn load() -> Result<Self> {
+        match settings_path() {
+            Some(path) => Self::load_from(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => serde_json::from_str(&text)
+                .with_context(|| format!("Invalid settings file: {}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    /// 按地址查找已保存的服务器，忽略末尾的 `/`
+    pub fn find_server(&self, url: &str) -> Option<&SavedServer> {
+        let url = url.trim_end_matches('/');
+        self.servers
+            .iter()
+            .find(|server| server.url.trim_end_matches('/') == url)
+    }
+
+    /// 保存服务器，同名时替换
+    pub fn upsert_server(&mut self, server: SavedServer) {
+        match self
+            .servers
+            .iter_mut()
+            .find(|saved| saved.name == server.name)
+        {
+            Some(saved) => *saved = server,
+            None => self.servers.push(server),
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path =
+            settings_path().context("Cannot determine config directory, set RUTIFY_GUI_CONFIG")?;
+        self.save_to(&path)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let text = serde_json::to_string_pretty(self)?;
+        write_private(path, text.as_bytes())
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Rutify 配置目录：`$XDG_CONFIG_HOME/rutify`、`~/.config/rutify`，Windows 为 `%APPDATA%\rutify`
+pub fn config_dir() -> Option<PathBuf> {
+    let base = if cfg!(windows) {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+    base.map(|base| base.join("rutify"))
+}
+
+/// 写入包含 Token 的配置文件，Unix 下仅允许当前用户读写
+pub fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(contents)
+}
+
+fn settings_path() -> Option<PathBuf> {
+    match std::env::var_os("RUTIFY_GUI_CONFIG") {
+        Some(path) => Some(PathBuf::from(path)),
+        None => config_dir().map(|dir| dir.join("gui.json")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_roundtrip() {
+        let path = std::env::temp_dir()
+            .join(format!("rutify-gui-{}", std::process::id()))
+            .join("gui.json");
+        assert_eq!(
+            GuiSettings::load_from(&path).unwrap(),
+            GuiSettings::default()
+        );
+
+        let mut settings = GuiSettings {
+            server: Some("http://nas:3000".to_string()),
+            theme: ThemePreference::Dark,
+            ..Default::default()
+        };
+        settings.upsert_server(SavedServer {
+            name: "nas".to_string(),
+            url: "http://nas:3000/".to_string(),
+            ..Default::default()
+        });
+        settings.upsert_server(SavedServer {
+            name: "nas".to_string(),
+            url: "http://nas:3000".to_string(),
+            token: Some("notify".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(settings.servers.len(), 1);
+        assert_eq!(
+            settings
+                .find_server("http://nas:3000/")
+                .unwrap()
+                .token
+                .as_deref(),
+            Some("notify")
+        );
+        settings.save_to(&path).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.contains("\"dark\""));
+        assert_eq!(GuiSettings::load_from(&path).unwrap(), settings);
+
+        // 缺少的字段取默认值
+        std::fs::write(&path, "{}").unwrap();
+        assert_eq!(
+            GuiSettings::load_from(&path).unwrap(),
+            GuiSettings::default()
+        );
+        std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,140 @@
+use chrono::{DateTime, Local, Utc};
+use chrono_tz::Tz;
+use std::str::FromStr;
+
+const ABSOLUTE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// 显示时间戳使用的时区
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum TimeZoneSetting {
+    /// 协调世界时（默认，与服务端存储一致）
+    #[default]
+    Utc,
+    /// 本机时区
+    Local,
+    /// IANA 时区名，例如 `Asia/Shanghai`
+    Named(Tz),
+}
+
+impl FromStr for TimeZoneSetting {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "utc" | "z" => Ok(Self::Utc),
+            "local" => Ok(Self::Local),
+            _ => s.parse::<Tz>().map(Self::Named).map_err(|_| {
+                format!(
+                    "unknown timezone '{}', expected local, UTC or an IANA name",
+                    s
+                )
+            }),
+        }
+    }
+}
+
+/// 时间戳格式化选项，CLI 与图形界面共用
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TimeFormat {
+    pub timezone: TimeZoneSetting,
+    /// 显示为相对时间，例如 `3 minutes ago`
+    pub relative: bool,
+}
+
+impl TimeFormat {
+    pub fn new(timezone: TimeZoneSetting, relative: bool) -> Self {
+        Self { timezone, relative }
+    }
+
+    /// 按当前时间格式化
+    pub fn format(&self, time: DateTime<Utc>) -> String {
+        self.format_at(time, Utc::now())
+    }
+
+    /// 以给定的“现在”计算相对时间，便于测试
+    pub fn format_at(&self, time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+        if self.relative {
+            format_relative(time, now)
+        } else {
+            self.format_absolute(time)
+        }
+    }
+
+    /// 转换到所选时区后的绝对时间
+    pub fn format_absolute(&self, time: DateTime<Utc>) -> String {
+        match self.timezone {
+            TimeZoneSetting::Utc => time.format(ABSOLUTE_FORMAT).to_string(),
+            TimeZoneSetting::Local => time
+                .with_timezone(&Local)
+                .format(ABSOLUTE_FORMAT)
+                .to_string(),
+            TimeZoneSetting::Named(tz) => time
+                .with_timezone(&tz)
+                .format("%Y-%m-%d %H:%M:%S %Z")
+                .to_string(),
+        }
+    }
+}
+
+/// 相对时间，例如 `just now`、`5 minutes ago`、`in 2 hours`
+pub fn format_relative(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let delta = now.signed_duration_since(time);
+    let secs = delta.num_seconds().unsigned_abs();
+    if secs < 45 {
+        return "just now".to_string();
+    }
+
+    let (value, unit) = match secs {
+        0..3_600 => ((secs / 60).max(1), "minute"),
+        3_600..86_400 => (secs / 3_600, "hour"),
+        86_400..2_592_000 => (secs / 86_400, "day"),
+        2_592_000..31_536_000 => (secs / 2_592_000, "month"),
+        _ => (secs / 31_536_000, "year"),
+    };
+    let unit = if value == 1 {
+        unit.to_string()
+    } else {
+        format!("{}s", unit)
+    };
+
+    if delta.num_seconds() >= 0 {
+        format!("{} {} ago", value, unit)
+    } else {
+        format!("in {} {}", value, unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_time_format() {
+        let time = Utc.with_ymd_and_hms(2025, 1, 15, 8, 30, 0).unwrap();
+
+        assert_eq!(
+            TimeFormat::default().format_absolute(time),
+            "2025-01-15 08:30:00"
+        );
+        let shanghai: TimeZoneSetting = "Asia/Shanghai".parse().unwrap();
+        assert_eq!(
+            TimeFormat::new(shanghai, false).format_absolute(time),
+            "2025-01-15 16:30:00 CST"
+        );
+        assert_eq!("UTC".parse::<TimeZoneSetting>(), Ok(TimeZoneSetting::Utc));
+        assert_eq!(
+            "local".parse::<TimeZoneSetting>(),
+            Ok(TimeZoneSetting::Local)
+        );
+        assert!("Mars/Olympus".parse::<TimeZoneSetting>().is_err());
+
+        let relative = TimeFormat::new(TimeZoneSetting::Utc, true);
+        let after = |secs| time + chrono::Duration::seconds(secs);
+        assert_eq!(relative.format_at(time, after(10)), "just now");
+        assert_eq!(relative.format_at(time, after(180)), "3 minutes ago");
+        assert_eq!(relative.format_at(time, after(3_600)), "1 hour ago");
+        assert_eq!(relative.format_at(time, after(3 * 86_400)), "3 days ago");
+        assert_eq!(relative.format_at(after(7_200), time), "in 2 hours");
+    }
+}
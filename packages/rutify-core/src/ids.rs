@@ -0,0 +1,146 @@
+//! 各类实体的 ID 类型，避免把 Token ID 当作通知 ID 传入之类的错误
+//!
+//! 序列化形式与内部值相同，接口格式不变。
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+macro_rules! int_id {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(
+            Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+        )]
+        #[serde(transparent)]
+        pub struct $name(pub i32);
+
+        impl $name {
+            pub fn get(self) -> i32 {
+                self.0
+            }
+        }
+
+        impl From<i32> for $name {
+            fn from(id: i32) -> Self {
+                Self(id)
+            }
+        }
+
+        impl From<$name> for i32 {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = std::num::ParseIntError;
+
+            fn from_str(text: &str) -> Result<Self, Self::Err> {
+                text.trim().parse().map(Self)
+            }
+        }
+    };
+}
+
+int_id!(
+    /// 通知 ID
+    NotifyId
+);
+
+int_id!(
+    /// 通知 Token 或用户 JWT 在数据库中的 ID，不同于 JWT 中的 `sub`
+    TokenId
+);
+
+/// 用户 ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct UserId(pub Uuid);
+
+impl From<Uuid> for UserId {
+    fn from(id: Uuid) -> Self {
+        Self(id)
+    }
+}
+
+impl fmt::Display for UserId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for UserId {
+    type Err = uuid::Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        text.trim().parse().map(Self)
+    }
+}
+
+/// 设备标识，即发送方提供或服务端推断的设备名
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct DeviceId(pub String);
+
+impl DeviceId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for DeviceId {
+    fn from(name: String) -> Self {
+        Self(name)
+    }
+}
+
+impl From<&str> for DeviceId {
+    fn from(name: &str) -> Self {
+        Self(name.to_string())
+    }
+}
+
+impl fmt::Display for DeviceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for DeviceId {
+    type Err = std::convert::Infallible;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Ok(Self(text.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_id_serde() {
+        let id: NotifyId = serde_json::from_str("42").unwrap();
+        assert_eq!(id, NotifyId(42));
+        assert_eq!(serde_json::to_string(&TokenId(7)).unwrap(), "7");
+        assert_eq!("42".parse::<NotifyId>().unwrap().to_string(), "42");
+        assert!("abc".parse::<TokenId>().is_err());
+
+        let uuid = Uuid::new_v4();
+        let user: UserId = serde_json::from_str(&format!("\"{uuid}\"")).unwrap();
+        assert_eq!(user, UserId(uuid));
+        assert_eq!(user.to_string().parse::<UserId>().unwrap(), user);
+
+        let device: DeviceId = serde_json::from_str("\"nas\"").unwrap();
+        assert_eq!(device.as_str(), "nas");
+        assert_eq!(serde_json::to_string(&device).unwrap(), "\"nas\"");
+    }
+}
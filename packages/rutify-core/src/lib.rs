@@ -1,14 +1,132 @@
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
+
+pub mod ids;
+pub mod pow;
+pub mod timestamp;
+pub mod wire;
+
+pub use ids::{DeviceId, NotifyId, TokenId, UserId};
+pub use pow::{ProofOfWorkSolution, RegistrationChallenge};
+pub use wire::{WireFormat, WireFrame};
+
+/// 默认通知优先级（1 最低，5 最高）
+pub const DEFAULT_PRIORITY: u8 = 3;
+/// 最高通知优先级
+pub const MAX_PRIORITY: u8 = 5;
+
+/// 端到端加密正文的前缀，服务端只保存与转发这类正文，不解析其内容
+pub const E2E_PREFIX: &str = "e2e:v1:";
+
+/// 正文是否为端到端加密的密文
+pub fn is_e2e(notify: &str) -> bool {
+    notify.starts_with(E2E_PREFIX)
+}
+
+/// 关联 ID 的最大字节数
+pub const MAX_CORRELATION_ID_LEN: usize = 128;
+
+/// 生成随机的关联 ID
+pub fn new_correlation_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// 服务端实现的 API 级别，新增接口时递增；没有 `GET /api/version` 的旧服务端视为 1
+pub const API_LEVEL: u32 = 4;
+/// 通知支持 `channel` 字段的最低 API 级别
+pub const API_LEVEL_CHANNELS: u32 = 2;
+/// `GET /api/notifies` 支持 `limit`/`cursor` 分页的最低 API 级别
+pub const API_LEVEL_PAGINATION: u32 = 2;
+/// 提供 `GET /api/capabilities` 的最低 API 级别
+pub const API_LEVEL_CAPABILITIES: u32 = 3;
+/// 提供 `GET /api/notifies/counts` 的最低 API 级别
+pub const API_LEVEL_NOTIFY_COUNTS: u32 = 4;
+
+fn default_priority() -> u8 {
+    DEFAULT_PRIORITY
+}
 
 /// 通知项数据结构
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NotifyItem {
-    pub id: i32,
+    pub id: NotifyId,
     pub title: String,
     pub notify: String,
     pub device: String,
+    #[serde(default = "default_priority")]
+    pub priority: u8,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 发送时指定的投递频道
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
     pub received_at: DateTime<Utc>,
+    /// 过期时间，过期后不再出现在默认列表中
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// 置顶的通知在列表中排在最前，且不会被清理
+    #[serde(default)]
+    pub pinned: bool,
+    /// 星标的通知可通过 `GET /api/notifies?starred=true` 单独列出
+    #[serde(default)]
+    pub starred: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress: Option<Progress>,
+    /// 设备名的来源，便于排查：`payload`、`token_default`、`token_info`、`user_agent` 或 `default`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_source: Option<String>,
+    /// 正文中链接的预览
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub link_previews: Vec<LinkPreview>,
+    /// 发送方对标题与正文的签名，服务端已用 Token 登记的公钥验证
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<NotifySignature>,
+    /// 发送方提供的关联 ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+}
+
+/// 优先级缺省为 [`DEFAULT_PRIORITY`]，与反序列化一致
+impl Default for NotifyItem {
+    fn default() -> Self {
+        Self {
+            id: NotifyId::default(),
+            title: String::new(),
+            notify: String::new(),
+            device: String::new(),
+            priority: DEFAULT_PRIORITY,
+            tags: Vec::new(),
+            channel: None,
+            received_at: DateTime::default(),
+            expires_at: None,
+            pinned: false,
+            starred: false,
+            progress: None,
+            device_source: None,
+            link_previews: Vec::new(),
+            signature: None,
+            correlation_id: None,
+        }
+    }
+}
+
+impl NotifyItem {
+    /// 是否已过期
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// 就地应用 `update` 事件中的通知内容
+    pub fn apply_update(&mut self, data: &NotificationData) {
+        self.title = data.title.clone();
+        self.notify = data.notify.clone();
+        self.priority = data.priority;
+        self.tags = data.tags.clone();
+        self.progress = data.progress;
+        self.link_previews = data.link_previews.clone();
+        self.signature = data.signature.clone();
+    }
 }
 
 /// 服务器统计信息
@@ -18,14 +136,562 @@ pub struct Stats {
     pub total_count: i32,
     pub device_count: i32,
     pub is_running: bool,
+    /// 当前生效的静默规则数
+    #[serde(default)]
+    pub active_silences: i32,
 }
 
-/// 通知输入参数
+/// 静默规则：匹配的通知照常入库，但不广播
+///
+/// `device`、`channel`、`tag` 支持 `*` 通配符，未设置的条件不参与匹配。
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Silence {
+    pub id: i32,
+    pub device: Option<String>,
+    pub channel: Option<String>,
+    pub tag: Option<String>,
+    pub comment: Option<String>,
+    pub created_by: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 静默规则创建请求，`expires_at` 与 `duration_secs` 二选一
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateSilenceRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<u64>,
+}
+
+/// 心跳监控状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MonitorStatus {
+    /// 尚未收到过心跳，不会告警
+    New,
+    Up,
+    /// 超过 `interval_secs + grace_secs` 未收到心跳
+    Down,
+}
+
+impl MonitorStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::New => "new",
+            Self::Up => "up",
+            Self::Down => "down",
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        match text {
+            "new" => Some(Self::New),
+            "up" => Some(Self::Up),
+            "down" => Some(Self::Down),
+            _ => None,
+        }
+    }
+}
+
+/// 心跳监控（死信开关）：客户端定期上报心跳，逾期未上报时服务端自动告警
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Monitor {
+    pub id: i32,
+    pub name: String,
+    /// 预期的心跳间隔
+    pub interval_secs: u64,
+    /// 间隔之外额外容忍的时长
+    pub grace_secs: u64,
+    pub status: MonitorStatus,
+    /// 告警通知的优先级
+    pub priority: u8,
+    /// 告警通知的投递频道
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    pub last_beat_at: Option<DateTime<Utc>>,
+    /// 下一次心跳的截止时间，收到第一次心跳前为空
+    pub due_at: Option<DateTime<Utc>>,
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 心跳监控创建请求
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateMonitorRequest {
+    pub name: String,
+    pub interval_secs: u64,
+    /// 默认为 `interval_secs` 的十分之一，至少 60 秒
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grace_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+}
+
+/// 主动检查的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    /// 尚未执行过检查
+    Unknown,
+    Up,
+    Down,
+}
+
+impl CheckStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Unknown => "unknown",
+            Self::Up => "up",
+            Self::Down => "down",
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        match text {
+            "unknown" => Some(Self::Unknown),
+            "up" => Some(Self::Up),
+            "down" => Some(Self::Down),
+            _ => None,
+        }
+    }
+}
+
+/// 服务端定期请求的 HTTP 地址，状态变化时生成通知
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UptimeCheck {
+    pub id: i32,
+    pub name: String,
+    pub url: String,
+    pub interval_secs: u64,
+    pub timeout_secs: u64,
+    /// 期望的状态码，为空时任意 2xx 视为正常
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_status: Option<u16>,
+    pub status: CheckStatus,
+    /// 状态变化通知的优先级
+    pub priority: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    pub enabled: bool,
+    pub last_checked_at: Option<DateTime<Utc>>,
+    pub last_latency_ms: Option<u64>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 单次检查的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckResult {
+    pub checked_at: DateTime<Utc>,
+    pub up: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+    pub latency_ms: u64,
+    /// 连接失败、超时或状态码不符时的说明
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 主动检查创建请求
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateCheckRequest {
+    pub name: String,
+    pub url: String,
+    /// 默认 60 秒
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval_secs: Option<u64>,
+    /// 默认 10 秒
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_status: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+}
+
+/// 更新已发送通知的请求，未提供的字段保持不变
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateNotifyRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress: Option<Progress>,
+}
+
+impl UpdateNotifyRequest {
+    /// 没有任何需要更新的字段
+    pub fn is_empty(&self) -> bool {
+        self.notify.is_none()
+            && self.title.is_none()
+            && self.priority.is_none()
+            && self.tags.is_none()
+            && self.progress.is_none()
+    }
+}
+
+/// 按条件清理通知的请求，未设置的条件不参与匹配，至少需要一个条件
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PurgeNotifiesRequest {
+    /// 只清理早于该秒数之前收到的通知
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub older_than_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    /// `true` 只清理已确认的通知，`false` 只清理未确认的通知
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read: Option<bool>,
+    /// 只统计匹配数量，不删除
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// 清理结果，`dry_run` 时 `deleted` 为 0
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PurgeNotifiesResult {
+    pub matched: u64,
+    pub deleted: u64,
+    pub dry_run: bool,
+}
+
+/// 通知附件的元数据，内容通过 `GET /api/notifies/{id}/attachments/{attachment_id}` 获取
+///
+/// 目前只有超出大小限制被截断的通知会附带 `body.txt`，保存完整正文。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttachmentInfo {
+    pub id: i32,
+    pub notify_id: NotifyId,
+    pub name: String,
+    pub content_type: String,
+    /// 内容的字节数
+    pub size: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 送达回执的渠道
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryChannel {
+    /// 通过 WebSocket 推送给订阅者
+    Websocket,
+    Telegram,
+    Email,
+    Webhook,
+    /// 经 UnifiedPush 分发器推送到移动应用
+    #[serde(rename = "unifiedpush")]
+    UnifiedPush,
+    /// 经 Web Push 推送到浏览器
+    WebPush,
+}
+
+impl DeliveryChannel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Websocket => "websocket",
+            Self::Telegram => "telegram",
+            Self::Email => "email",
+            Self::Webhook => "webhook",
+            Self::UnifiedPush => "unifiedpush",
+            Self::WebPush => "web_push",
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        match text {
+            "websocket" => Some(Self::Websocket),
+            "telegram" => Some(Self::Telegram),
+            "email" => Some(Self::Email),
+            "webhook" => Some(Self::Webhook),
+            "unifiedpush" => Some(Self::UnifiedPush),
+            "web_push" => Some(Self::WebPush),
+            _ => None,
+        }
+    }
+}
+
+/// 通知的一次送达记录，由 `GET /api/notifies/{id}/deliveries` 返回
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryReceipt {
+    pub id: i32,
+    pub notify_id: NotifyId,
+    pub channel: DeliveryChannel,
+    /// WebSocket 为订阅者 Token 的用途，UnifiedPush 为实例名或推送地址的主机名，
+    /// Web Push 为订阅用户名，其他渠道为 chat id、收件地址或 Webhook 地址（不含查询参数）
+    pub target: String,
+    /// WebSocket 订阅者或 UnifiedPush 登记使用的通知 Token，匿名连接为空
+    pub token_id: Option<TokenId>,
+    /// 投递失败时的错误信息
+    pub error: Option<String>,
+    pub delivered_at: DateTime<Utc>,
+    /// 订阅者处理完通知后回复确认的时间，只有 WebSocket 渠道会有
+    #[serde(default)]
+    pub acked_at: Option<DateTime<Utc>>,
+}
+
+/// 移动应用登记的 UnifiedPush 推送地址
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnifiedPushRegistration {
+    pub id: i32,
+    /// 分发器分配的推送地址
+    pub endpoint: String,
+    /// 应用自定义的实例名，用于区分同一 Token 下的多个应用或设备
+    pub instance: Option<String>,
+    /// 登记时使用的通知 Token
+    pub token_id: TokenId,
+    pub created_at: DateTime<Utc>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    /// 连续推送失败次数，成功后清零
+    pub failures: u32,
+    pub last_error: Option<String>,
+}
+
+/// UnifiedPush 登记请求，同一推送地址重复登记时更新原记录
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RegisterUnifiedPushRequest {
+    pub endpoint: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+}
+
+/// 浏览器的 Web Push 订阅
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebPushSubscription {
+    pub id: i32,
+    /// 浏览器推送服务分配的推送地址
+    pub endpoint: String,
+    pub created_at: DateTime<Utc>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    /// 连续推送失败次数，成功后清零
+    pub failures: u32,
+    pub last_error: Option<String>,
+}
+
+/// 浏览器生成的订阅密钥，均为 base64url 编码
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PushSubscriptionKeys {
+    /// P-256 公钥（未压缩格式）
+    pub p256dh: String,
+    /// 16 字节的认证密钥
+    pub auth: String,
+}
+
+/// Web Push 订阅请求，与浏览器 `PushSubscription.toJSON()` 的格式一致，
+/// 同一推送地址重复订阅时更新原记录
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubscribeWebPushRequest {
+    pub endpoint: String,
+    pub keys: PushSubscriptionKeys,
+}
+
+/// 取消 Web Push 订阅的请求
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UnsubscribeWebPushRequest {
+    pub endpoint: String,
+}
+
+/// 服务端的 VAPID 公钥，浏览器订阅时作为 `applicationServerKey`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VapidPublicKey {
+    /// base64url 编码的未压缩 P-256 公钥
+    pub public_key: String,
+}
+
+/// 用户的通知偏好，由 `GET/PUT /api/preferences` 读写
+///
+/// 作用于推送给该用户的 UnifiedPush、Web Push 与值班转发；WebSocket 实时流不受影响。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationPreferences {
+    /// 订阅的频道，为空时接收所有频道；没有频道的通知总是接收
+    #[serde(default)]
+    pub channels: Vec<String>,
+    /// 免打扰时段，时段内只推送不低于指定优先级的通知
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quiet_hours: Option<QuietHours>,
+    /// 邮件摘要的频率，发送到用户的注册邮箱
+    #[serde(default)]
+    pub digest: DigestFrequency,
+    /// 各送达渠道的最低优先级，键为渠道名（如 `web_push`、`telegram`），未列出的渠道不限制
+    #[serde(default)]
+    pub min_priority: BTreeMap<String, u8>,
+}
+
+/// 每天的免打扰时段，结束时间早于开始时间时跨越午夜
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    /// 开始时间，`HH:MM`
+    pub start: String,
+    /// 结束时间，`HH:MM`
+    pub end: String,
+    /// IANA 时区名，默认 UTC
+    #[serde(default = "default_quiet_hours_timezone")]
+    pub timezone: String,
+    /// 不低于该优先级的通知在时段内照常推送，默认只放行最高优先级
+    #[serde(default = "default_quiet_hours_priority")]
+    pub min_priority: u8,
+}
+
+fn default_quiet_hours_timezone() -> String {
+    "UTC".to_string()
+}
+
+fn default_quiet_hours_priority() -> u8 {
+    MAX_PRIORITY
+}
+
+/// 邮件摘要频率
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestFrequency {
+    #[default]
+    Off,
+    Hourly,
+    Daily,
+}
+
+impl DigestFrequency {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Hourly => "hourly",
+            Self::Daily => "daily",
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        match text {
+            "off" => Some(Self::Off),
+            "hourly" => Some(Self::Hourly),
+            "daily" => Some(Self::Daily),
+            _ => None,
+        }
+    }
+}
+
+/// 通知输入参数
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct NotificationInput {
     pub notify: String,
     pub title: Option<String>,
     pub device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>,
+    /// 标签，查询参数中可写为逗号分隔的字符串
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        deserialize_with = "deserialize_tags"
+    )]
+    pub tags: Vec<String>,
+    /// 投递频道，`oncall` 表示只投递给当前值班人
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    /// 有效期（秒），过期的通知不再出现在默认列表中，并由定期清理删除
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_secs: Option<u64>,
+    /// 进度，之后通过 `PATCH /api/notifies/{id}` 更新
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress: Option<Progress>,
+    /// 发送方签名，签名时必须提供标题；查询参数中无法传递
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<NotifySignature>,
+    /// 发送方生成的关联 ID，随通知保存并出现在它的每个事件中，便于在广播流中认出自己发送的通知；
+    /// SDK 发送时未设置则自动生成
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+}
+
+impl NotificationInput {
+    /// 未设置关联 ID 时生成一个，返回最终使用的关联 ID
+    pub fn ensure_correlation_id(&mut self) -> &str {
+        self.correlation_id.get_or_insert_with(new_correlation_id)
+    }
+}
+
+/// 同时接受 `["a", "b"]` 与 `"a,b"` 两种标签写法
+fn deserialize_tags<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Tags {
+        List(Vec<String>),
+        Text(String),
+    }
+
+    let tags = match Tags::deserialize(deserializer)? {
+        Tags::List(list) => list,
+        Tags::Text(text) => text.split(',').map(str::to_string).collect(),
+    };
+    Ok(tags
+        .into_iter()
+        .map(|tag| tag.trim().to_string())
+        .filter(|tag| !tag.is_empty())
+        .collect())
+}
+
+/// 通知 Token 创建时设置的默认字段，发送方未提供对应字段时使用
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenDefaults {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+}
+
+impl TokenDefaults {
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.device.is_none() && self.channel.is_none()
+    }
+
+    /// 去掉首尾空白，空字符串视为未设置
+    pub fn normalized(self) -> Self {
+        fn clean(value: Option<String>) -> Option<String> {
+            value
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        }
+        Self {
+            title: clean(self.title),
+            device: clean(self.device),
+            channel: clean(self.channel),
+        }
+    }
+
+    /// 补全输入中缺失的标题、设备与频道，已提供的字段保持不变
+    pub fn apply(&self, input: &mut NotificationInput) {
+        if input.title.is_none() {
+            input.title = self.title.clone();
+        }
+        if input.device.is_none() {
+            input.device = self.device.clone();
+        }
+        if input.channel.is_none() {
+            input.channel = self.channel.clone();
+        }
+    }
 }
 
 /// API 响应结构
@@ -35,20 +701,415 @@ pub struct ApiResponse<T> {
     pub data: T,
 }
 
+/// 错误码，客户端据此区分错误类型，不必解析错误信息
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// 请求格式或参数错误
+    BadRequest,
+    /// 未登录或 Token 无效
+    Unauthorized,
+    /// 没有权限
+    Forbidden,
+    /// 资源不存在
+    NotFound,
+    /// 请求方法不被该接口支持
+    MethodNotAllowed,
+    /// 请求字段校验失败，`details.fields` 为逐字段的错误
+    Validation,
+    /// 请求体或通知超出大小限制
+    PayloadTooLarge,
+    /// 请求处理超时
+    RequestTimeout,
+    /// 超出请求速率
+    RateLimited,
+    /// 服务端繁忙、只读或依赖不可用
+    Unavailable,
+    /// 依赖的外部服务失败
+    Upstream,
+    /// 服务端内部错误
+    Internal,
+    /// 新版本服务端新增的错误码
+    #[serde(other)]
+    Unknown,
+}
+
+impl ErrorCode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::BadRequest => "bad_request",
+            Self::Unauthorized => "unauthorized",
+            Self::Forbidden => "forbidden",
+            Self::NotFound => "not_found",
+            Self::MethodNotAllowed => "method_not_allowed",
+            Self::Validation => "validation",
+            Self::PayloadTooLarge => "payload_too_large",
+            Self::RequestTimeout => "request_timeout",
+            Self::RateLimited => "rate_limited",
+            Self::Unavailable => "unavailable",
+            Self::Upstream => "upstream",
+            Self::Internal => "internal",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    /// 按 HTTP 状态码推断错误码，用于响应体不是 [`ApiError`] 的情况
+    pub fn from_status(status: u16) -> Self {
+        match status {
+            400 => Self::BadRequest,
+            401 => Self::Unauthorized,
+            403 => Self::Forbidden,
+            404 => Self::NotFound,
+            405 => Self::MethodNotAllowed,
+            408 => Self::RequestTimeout,
+            413 => Self::PayloadTooLarge,
+            422 => Self::Validation,
+            429 => Self::RateLimited,
+            502 | 504 => Self::Upstream,
+            503 => Self::Unavailable,
+            _ if (400..500).contains(&status) => Self::BadRequest,
+            _ => Self::Internal,
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 接口返回的错误
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiError {
+    pub code: ErrorCode,
+    pub message: String,
+    /// 与错误码相关的附加信息，如校验失败的字段
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+impl ApiError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.message, self.code)
+    }
+}
+
+/// 错误响应结构：`{"status":"error","error":{...},"request_id":"..."}`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiErrorResponse {
+    pub status: String,
+    pub error: ApiError,
+    /// 与服务端日志对应的请求 ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+/// WebSocket 事件类型
+///
+/// 已有的类型沿用原来的名称（如 `notify`、`update`），旧客户端按字符串比较仍然有效。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    /// 新通知
+    #[serde(rename = "notify")]
+    Created,
+    /// 通知内容被更新或恢复，按 `data.id` 就地替换
+    #[serde(rename = "update")]
+    Updated,
+    /// 通知被删除，`data.id` 为被删除的通知
+    Deleted,
+    /// 通知被确认
+    Acked,
+    /// 通知被批量删除或清理，客户端应重新读取列表与统计
+    StatsChanged,
+    /// Token 被撤销，`data.id` 为 Token ID；使用该 Token 的连接随后关闭
+    TokenRevoked,
+    /// 未确认的通知触发升级
+    #[serde(rename = "escalate")]
+    Escalated,
+    /// 重连后补发的通知
+    #[serde(rename = "replay")]
+    Replayed,
+    /// 断线期间错过通知数量的提示
+    Missed,
+    /// 客户端从列表接口读取的历史通知
+    History,
+    /// 订阅者确认已处理通知，`data.id` 为该通知
+    Delivered,
+    /// 新版本服务端新增的类型
+    #[serde(other)]
+    Unknown,
+}
+
+impl EventKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Created => "notify",
+            Self::Updated => "update",
+            Self::Deleted => "deleted",
+            Self::Acked => "acked",
+            Self::StatsChanged => "stats_changed",
+            Self::TokenRevoked => "token_revoked",
+            Self::Escalated => "escalate",
+            Self::Replayed => "replay",
+            Self::Missed => "missed",
+            Self::History => "history",
+            Self::Delivered => "delivered",
+            Self::Unknown => "unknown",
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        match text {
+            "notify" => Some(Self::Created),
+            "update" => Some(Self::Updated),
+            "deleted" => Some(Self::Deleted),
+            "acked" => Some(Self::Acked),
+            "stats_changed" => Some(Self::StatsChanged),
+            "token_revoked" => Some(Self::TokenRevoked),
+            "escalate" => Some(Self::Escalated),
+            "replay" => Some(Self::Replayed),
+            "missed" => Some(Self::Missed),
+            "history" => Some(Self::History),
+            "delivered" => Some(Self::Delivered),
+            _ => None,
+        }
+    }
+
+    /// 事件是否携带一条需要展示的通知，删除、确认等变更类事件返回 `false`
+    pub fn is_notification(self) -> bool {
+        matches!(
+            self,
+            Self::Created
+                | Self::Updated
+                | Self::Escalated
+                | Self::Replayed
+                | Self::Missed
+                | Self::History
+        )
+    }
+}
+
+impl std::fmt::Display for EventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// WebSocket 事件
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NotifyEvent {
-    pub event: String,
+    pub event: EventKind,
     pub data: NotificationData,
     pub timestamp: DateTime<Utc>,
 }
 
+impl NotifyEvent {
+    /// 客户端处理完后需要回复确认的通知 ID：新通知与重连补发的通知
+    pub fn ack_id(&self) -> Option<NotifyId> {
+        match self.event {
+            EventKind::Created | EventKind::Replayed => self.data.id,
+            _ => None,
+        }
+    }
+
+    /// `token_revoked` 事件中被撤销的 Token
+    pub fn revoked_token_id(&self) -> Option<TokenId> {
+        match self.event {
+            EventKind::TokenRevoked => self.data.token_id,
+            _ => None,
+        }
+    }
+}
+
 /// 通知数据
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NotificationData {
+    /// 入库后的通知 ID，客户端据此确认与去重
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<NotifyId>,
     pub notify: String,
     pub title: String,
     pub device: String,
+    #[serde(default = "default_priority")]
+    pub priority: u8,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 定向投递的用户名，为空时广播给所有连接
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<String>,
+    /// 发送时指定的投递频道，例如 `oncall`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    /// 过期时间，由发送时的 `ttl_secs` 计算
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// 进度通知的当前进度
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress: Option<Progress>,
+    /// 正文中链接的预览，服务端抓取完成后通过 `update` 事件补充
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub link_previews: Vec<LinkPreview>,
+    /// 发送方对标题与正文的签名，客户端可自行验证
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<NotifySignature>,
+    /// 发送时提供的关联 ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+    /// `token_revoked` 事件中被撤销的 Token，其他事件为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_id: Option<TokenId>,
+}
+
+/// 优先级缺省为 [`DEFAULT_PRIORITY`]，与反序列化一致
+impl Default for NotificationData {
+    fn default() -> Self {
+        Self {
+            id: None,
+            notify: String::new(),
+            title: String::new(),
+            device: String::new(),
+            priority: DEFAULT_PRIORITY,
+            tags: Vec::new(),
+            recipient: None,
+            channel: None,
+            expires_at: None,
+            progress: None,
+            link_previews: Vec::new(),
+            signature: None,
+            correlation_id: None,
+            token_id: None,
+        }
+    }
+}
+
+impl NotificationData {
+    /// 是否已过期
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+/// 发送方的 ed25519 签名，覆盖通知的标题与正文
+///
+/// 登记了签名公钥的通知 Token 只接受带有效签名的通知；通过 `PATCH` 修改标题或正文后签名随之清除。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotifySignature {
+    /// 签名者公钥（32 字节）的 base64 编码
+    pub public_key: String,
+    /// 签名（64 字节）的 base64 编码
+    pub signature: String,
+}
+
+impl NotifySignature {
+    /// 被签名的内容：版本前缀、标题长度、标题与正文，长度前缀避免标题与正文拼接时产生歧义
+    pub fn message(title: &str, notify: &str) -> Vec<u8> {
+        let mut message = b"rutify-signature:v1\n".to_vec();
+        message.extend_from_slice(&(title.len() as u64).to_be_bytes());
+        message.extend_from_slice(title.as_bytes());
+        message.extend_from_slice(notify.as_bytes());
+        message
+    }
+}
+
+/// 通知正文中链接的预览信息，由服务端抓取（需开启 `RUTIFY_LINK_PREVIEW`）
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkPreview {
+    pub url: String,
+    /// 页面标题，优先取 `og:title`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// 网站图标的绝对地址
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub favicon: Option<String>,
+}
+
+/// 进度通知的状态
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProgressStatus {
+    #[default]
+    Running,
+    Done,
+    Failed,
+}
+
+impl ProgressStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Done => "done",
+            Self::Failed => "failed",
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Self> {
+        match text {
+            "running" => Some(Self::Running),
+            "done" => Some(Self::Done),
+            "failed" => Some(Self::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// 长时间任务的进度，`total` 为 0 表示总量未知
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Progress {
+    pub current: u64,
+    pub total: u64,
+    #[serde(default)]
+    pub status: ProgressStatus,
+}
+
+impl Progress {
+    pub fn new(current: u64, total: u64) -> Self {
+        Self {
+            current,
+            total,
+            status: ProgressStatus::Running,
+        }
+    }
+
+    /// 完成比例（0.0–1.0），总量未知时返回 `None`
+    pub fn fraction(&self) -> Option<f64> {
+        if self.status == ProgressStatus::Done {
+            return Some(1.0);
+        }
+        (self.total > 0).then(|| (self.current.min(self.total) as f64) / (self.total as f64))
+    }
+}
+
+impl std::fmt::Display for Progress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.status, self.fraction()) {
+            (ProgressStatus::Running, Some(fraction)) => {
+                write!(f, "{:.0}%", fraction * 100.0)
+            }
+            (ProgressStatus::Running, None) => write!(f, "{}", self.current),
+            (status, _) => f.write_str(status.as_str()),
+        }
+    }
+}
+
+/// 当前值班信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnCallInfo {
+    pub schedule: String,
+    pub username: String,
+    /// 本轮值班结束时间
+    pub until: DateTime<Utc>,
 }
 
 /// WebSocket 消息类型
@@ -56,7 +1117,7 @@ pub struct NotificationData {
 #[serde(tag = "type", content = "data")]
 pub enum WebSocketMessage {
     /// 通知事件
-    Event(NotifyEvent),
+    Event(Box<NotifyEvent>),
     /// 纯文本消息
     Text(String),
     /// 关闭连接
@@ -69,6 +1130,16 @@ pub enum WebSocketMessage {
     Pong,
 }
 
+/// WebSocket 客户端发往服务端的消息
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WsClientMessage {
+    /// 连接建立后首帧鉴权，供无法设置请求头的客户端（如浏览器）使用
+    Auth { token: String },
+    /// 确认已处理通知，服务端记录到送达回执并广播 `delivered` 事件
+    Ack { id: NotifyId },
+}
+
 /// Token 管理相关结构
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenItem {
@@ -78,18 +1149,49 @@ pub struct TokenItem {
     pub created_at: DateTime<Utc>,
 }
 
-/// Token 创建请求
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// `POST /auth/tokens` 的请求，服务端与 SDK 共用
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CreateTokenRequest {
     pub usage: String,
-    pub expires_at: Option<DateTime<Utc>>,
+    /// 有效期，为空时服务端默认 24 小时
+    pub expires_in_hours: Option<u64>,
+    pub device_info: Option<String>,
+    /// 使用该 Token 发送时，未提供的标题、设备与频道取这里的值
+    #[serde(default, skip_serializing_if = "TokenDefaults::is_empty")]
+    pub defaults: TokenDefaults,
+    /// 发送方签名公钥（base64），设置后该 Token 只能发送此公钥签名的通知
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_key: Option<String>,
 }
 
-/// Token 创建响应
+/// `POST /auth/tokens` 的响应
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateTokenResponse {
     pub token: String,
-    pub token_item: TokenItem,
+    /// JWT 中的 `sub`，不同于数据库中的 [`TokenId`]
+    pub token_id: String,
+    pub usage: String,
+    pub token_type: String,
+    #[serde(deserialize_with = "timestamp::deserialize")]
+    pub expires_at: DateTime<Utc>,
+}
+
+/// `GET /auth/tokens` 与 `GET /api/admin/tokens` 返回的 Token 信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenInfo {
+    pub id: TokenId,
+    pub usage: String,
+    pub token_type: String,
+    pub device_info: Option<String>,
+    #[serde(deserialize_with = "timestamp::deserialize")]
+    pub created_at: DateTime<Utc>,
+    #[serde(deserialize_with = "timestamp::deserialize")]
+    pub expires_at: DateTime<Utc>,
+    #[serde(default, deserialize_with = "timestamp::option::deserialize")]
+    pub last_used_at: Option<DateTime<Utc>>,
+    /// 所有者用户名，仅管理员接口返回；未绑定用户的 Token 为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
 }
 
 /// 设备信息
@@ -101,6 +1203,204 @@ pub struct DeviceInfo {
     pub is_active: bool,
 }
 
+/// 单个设备的通知统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceStats {
+    pub device: DeviceId,
+    pub total_count: u64,
+    /// 今天（UTC）收到的通知数
+    pub today_count: u64,
+    pub last_notified_at: Option<DateTime<Utc>>,
+}
+
+/// `GET /api/notifies/counts` 的分组方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CountGroupBy {
+    Channel,
+    Device,
+    /// 按接收日期（UTC）
+    Day,
+}
+
+impl CountGroupBy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Channel => "channel",
+            Self::Device => "device",
+            Self::Day => "day",
+        }
+    }
+}
+
+/// 一组通知的数量
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotifyCount {
+    /// 频道、设备名或日期（`YYYY-MM-DD`）；未设置频道或设备的通知为 `None`
+    pub key: Option<String>,
+    pub count: u64,
+}
+
+/// 一次认证失败记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthFailure {
+    pub at: DateTime<Utc>,
+    /// 失败的认证方式：`login`、`user_token`、`notify_token` 或 `websocket`
+    pub kind: String,
+    /// 登录用户名或请求来源地址
+    pub subject: Option<String>,
+    pub reason: String,
+}
+
+/// 管理面板仪表盘的汇总数据，由 `GET /api/admin/overview` 返回
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminOverview {
+    pub version: String,
+    pub stats: Stats,
+    /// 当前 WebSocket 连接数
+    pub connections: u64,
+    /// 未过期的 Token 数，键为 Token 类型（`user_jwt`、`notify_bearer`）
+    pub tokens: BTreeMap<String, u64>,
+    /// 服务启动以来的认证失败总数
+    pub auth_failures_total: u64,
+    /// 最近的认证失败，新的在前
+    pub recent_auth_failures: Vec<AuthFailure>,
+    /// 数据库大小，无法获取时为空
+    pub db_size_bytes: Option<u64>,
+}
+
+/// 服务端版本信息，由 `GET /api/version` 返回
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerVersion {
+    pub version: String,
+    /// 服务端支持的 API 级别，见 [`API_LEVEL`]
+    pub api_level: u32,
+}
+
+impl ServerVersion {
+    /// 不提供 `GET /api/version` 的旧服务端
+    pub fn legacy() -> Self {
+        Self {
+            version: "unknown".to_string(),
+            api_level: 1,
+        }
+    }
+
+    /// 服务端是否支持 `api_level` 引入的接口
+    pub fn supports(&self, api_level: u32) -> bool {
+        self.api_level >= api_level
+    }
+
+    /// 主版本号是否与 `version` 相同；版本号无法解析（例如旧服务端）时视为兼容
+    pub fn is_compatible_with(&self, version: &str) -> bool {
+        fn major(version: &str) -> Option<u64> {
+            version
+                .trim_start_matches('v')
+                .split('.')
+                .next()?
+                .parse()
+                .ok()
+        }
+        match (major(&self.version), major(version)) {
+            (Some(server), Some(client)) => server == client,
+            _ => true,
+        }
+    }
+}
+
+/// 部署中已启用的可选子系统，由 `GET /api/capabilities` 返回，客户端据此隐藏不可用的功能
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities {
+    /// 通知附件
+    pub attachments: bool,
+    /// SMTP 邮件网关（`RUTIFY_SMTP_ADDR`）
+    pub email: bool,
+    /// Telegram 转发（`RUTIFY_TELEGRAM_BOT_TOKEN`）
+    pub telegram: bool,
+    /// MQTT 桥接
+    pub mqtt: bool,
+    /// Syslog 接入
+    pub syslog: bool,
+    /// gRPC 接口
+    pub grpc: bool,
+    /// 端到端加密通知的存储与转发
+    pub e2e: bool,
+    /// 静态加密
+    pub encryption_at_rest: bool,
+    /// 链接预览
+    pub link_preview: bool,
+    /// 多实例集群广播
+    pub cluster: bool,
+    /// 只读实例，不接受通知与修改
+    pub read_only: bool,
+    /// UnifiedPush 推送
+    #[serde(default)]
+    pub unifiedpush: bool,
+    /// 浏览器 Web Push 推送（`RUTIFY_VAPID_KEY_FILE`）
+    #[serde(default)]
+    pub web_push: bool,
+}
+
+impl Capabilities {
+    /// 已启用子系统的名称，按字段顺序排列
+    pub fn enabled(&self) -> Vec<&'static str> {
+        [
+            ("attachments", self.attachments),
+            ("email", self.email),
+            ("telegram", self.telegram),
+            ("mqtt", self.mqtt),
+            ("syslog", self.syslog),
+            ("grpc", self.grpc),
+            ("e2e", self.e2e),
+            ("encryption_at_rest", self.encryption_at_rest),
+            ("link_preview", self.link_preview),
+            ("cluster", self.cluster),
+            ("read_only", self.read_only),
+            ("unifiedpush", self.unifiedpush),
+            ("web_push", self.web_push),
+        ]
+        .into_iter()
+        .filter_map(|(name, enabled)| enabled.then_some(name))
+        .collect()
+    }
+}
+
+/// 管理员查看的用户信息，由 `GET /api/admin/users` 返回
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminUser {
+    pub id: UserId,
+    pub username: String,
+    pub email: String,
+    /// `admin` 或 `user`
+    pub role: String,
+    pub email_verified: bool,
+    /// 已停用的用户不能登录
+    pub disabled: bool,
+    #[serde(deserialize_with = "timestamp::deserialize")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// 审计日志中的一条记录：管理员对 `/api/admin` 发起的一次修改请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: i32,
+    /// 执行操作的管理员用户名
+    pub actor: String,
+    pub method: String,
+    pub path: String,
+    /// 响应状态码
+    pub status: u16,
+    pub created_at: DateTime<Utc>,
+}
+
+/// 可在运行时修改的服务端设置，由 `GET /api/admin/settings` 返回
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeSetting {
+    pub key: String,
+    pub value: String,
+    pub description: String,
+}
+
 /// 应用配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// 注册前需要完成的人机验证，由 `GET /auth/register/challenge` 返回
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RegistrationChallenge {
+    /// 无需验证
+    None,
+    /// 工作量证明：找到 `nonce`，使 `SHA-256("{challenge}:{nonce}")` 至少有 `difficulty` 个前导零比特
+    ProofOfWork { challenge: String, difficulty: u8 },
+    /// hCaptcha，前端用 `site_key` 渲染控件后提交 `captcha_token`
+    Hcaptcha { site_key: String },
+    /// Cloudflare Turnstile，用法同 hCaptcha
+    Turnstile { site_key: String },
+}
+
+/// 工作量证明的答案，随注册请求提交
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofOfWorkSolution {
+    pub challenge: String,
+    pub nonce: u64,
+}
+
+/// `SHA-256("{challenge}:{nonce}")` 的前导零比特数
+pub fn leading_zero_bits(challenge: &str, nonce: u64) -> u32 {
+    let digest = Sha256::digest(format!("{challenge}:{nonce}").as_bytes());
+    let mut bits = 0;
+    for byte in digest {
+        bits += byte.leading_zeros();
+        if byte != 0 {
+            break;
+        }
+    }
+    bits
+}
+
+/// 校验答案是否满足难度
+pub fn verify(challenge: &str, nonce: u64, difficulty: u8) -> bool {
+    leading_zero_bits(challenge, nonce) >= u32::from(difficulty)
+}
+
+/// 从 0 开始穷举 `nonce`，难度每加 1 平均耗时翻倍
+pub fn solve(challenge: &str, difficulty: u8) -> ProofOfWorkSolution {
+    let nonce = (0..)
+        .find(|nonce| verify(challenge, *nonce, difficulty))
+        .expect("a nonce exists for any difficulty up to 256");
+    ProofOfWorkSolution {
+        challenge: challenge.to_string(),
+        nonce,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_and_verify() {
+        let solution = solve("abc", 12);
+        assert!(verify("abc", solution.nonce, 12));
+        assert!(leading_zero_bits("abc", solution.nonce) >= 12);
+        assert!(verify("abc", 0, 0));
+
+        let json = serde_json::to_value(RegistrationChallenge::ProofOfWork {
+            challenge: "abc".to_string(),
+            difficulty: 12,
+        })
+        .unwrap();
+        assert_eq!(json["kind"], "proof_of_work");
+        assert_eq!(json["difficulty"], 12);
+    }
+}
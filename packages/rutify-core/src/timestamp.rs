@@ -0,0 +1,119 @@
+//! 时间戳的反序列化：接口统一使用 RFC 3339，同时兼容旧服务端的格式
+//!
+//! 旧版本的认证与 Token 接口用 `DateTime::to_string()` 输出时间，如
+//! `2026-01-01 08:00:00.123456 UTC`。字段声明为 `DateTime<Utc>` 后配合
+//! `#[serde(deserialize_with = "rutify_core::timestamp::deserialize")]` 使用，
+//! 序列化仍由 chrono 输出 RFC 3339。
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Deserializer};
+
+/// 解析 RFC 3339 或旧服务端的 `YYYY-MM-DD HH:MM:SS[.f] UTC` 格式
+pub fn parse(text: &str) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(text) {
+        return Some(at.with_timezone(&Utc));
+    }
+    let naive = text.strip_suffix("UTC").unwrap_or(text).trim_end();
+    NaiveDateTime::parse_from_str(naive, "%Y-%m-%d %H:%M:%S%.f")
+        .ok()
+        .map(|at| at.and_utc())
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let text = String::deserialize(deserializer)?;
+    parse(&text).ok_or_else(|| serde::de::Error::custom(format!("invalid timestamp: {text}")))
+}
+
+/// 可为空的时间戳，字段需同时标注 `#[serde(default)]`
+pub mod option {
+    use super::*;
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Option::<String>::deserialize(deserializer)? {
+            Some(text) => parse(&text)
+                .map(Some)
+                .ok_or_else(|| serde::de::Error::custom(format!("invalid timestamp: {text}"))),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde::Serialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Stamped {
+        #[serde(deserialize_with = "deserialize")]
+        at: DateTime<Utc>,
+        #[serde(default, deserialize_with = "option::deserialize")]
+        seen: Option<DateTime<Utc>>,
+    }
+
+    #[test]
+    fn test_parse_formats() {
+        let at = Utc.with_ymd_and_hms(2026, 1, 1, 8, 0, 0).unwrap();
+        assert_eq!(parse("2026-01-01T08:00:00Z"), Some(at));
+        assert_eq!(parse("2026-01-01T16:00:00+08:00"), Some(at));
+        assert_eq!(parse("2026-01-01 08:00:00 UTC"), Some(at));
+        assert_eq!(
+            parse("2026-01-01 08:00:00.250 UTC"),
+            Some(at + chrono::Duration::milliseconds(250))
+        );
+        assert_eq!(parse("yesterday"), None);
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        let now = Utc::now();
+        let stamped = Stamped {
+            at: now,
+            seen: Some(now),
+        };
+        let json = serde_json::to_string(&stamped).unwrap();
+        assert!(json.contains('T') && !json.contains("UTC"));
+        assert_eq!(serde_json::from_str::<Stamped>(&json).unwrap(), stamped);
+
+        // 旧服务端的格式
+        let legacy = format!(r#"{{"at":"{}","seen":null}}"#, now);
+        let parsed: Stamped = serde_json::from_str(&legacy).unwrap();
+        assert_eq!(parsed.at, now);
+        assert_eq!(parsed.seen, None);
+        let parsed: Stamped = serde_json::from_str(&format!(r#"{{"at":"{now}"}}"#)).unwrap();
+        assert_eq!(parsed.seen, None);
+
+        assert!(serde_json::from_str::<Stamped>(r#"{"at":"soon"}"#).is_err());
+    }
+
+    #[test]
+    fn test_token_info_timestamps() {
+        // 旧服务端的 `to_string()` 格式
+        let legacy: crate::TokenInfo = serde_json::from_str(
+            r#"{"id":1,"usage":"ci","token_type":"notify_bearer","device_info":null,
+                "created_at":"2026-01-01 08:00:00.5 UTC","expires_at":"2026-02-01 08:00:00 UTC",
+                "last_used_at":null}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            legacy.created_at.to_rfc3339(),
+            "2026-01-01T08:00:00.500+00:00"
+        );
+        assert_eq!(legacy.last_used_at, None);
+
+        let json = serde_json::to_string(&legacy).unwrap();
+        assert!(json.contains(r#""expires_at":"2026-02-01T08:00:00Z""#));
+        assert!(!json.contains("owner"));
+        let parsed: crate::TokenInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.created_at, legacy.created_at);
+        assert_eq!(parsed.expires_at, legacy.expires_at);
+    }
+}
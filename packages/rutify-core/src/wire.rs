@@ -0,0 +1,183 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::RutifyError;
+
+/// WebSocket 消息编码格式
+///
+/// 通过 `?format=` 参数或 `Sec-WebSocket-Protocol` 子协议协商，默认 JSON。
+/// MessagePack 需要启用 `msgpack` feature。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WireFormat {
+    #[default]
+    Json,
+    MessagePack,
+}
+
+/// 编码后的 WebSocket 帧
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WireFrame {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+impl WireFormat {
+    /// 解析 `format` 参数，未启用的格式返回 `None`
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "msgpack" | "messagepack" if Self::MessagePack.is_supported() => {
+                Some(Self::MessagePack)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::MessagePack => "msgpack",
+        }
+    }
+
+    /// 对应的 WebSocket 子协议名
+    pub fn subprotocol(self) -> &'static str {
+        match self {
+            Self::Json => "rutify.json",
+            Self::MessagePack => "rutify.msgpack",
+        }
+    }
+
+    /// 根据子协议名识别格式
+    pub fn from_subprotocol(protocol: &str) -> Option<Self> {
+        protocol
+            .strip_prefix("rutify.")
+            .and_then(Self::parse)
+            .filter(|format| format.subprotocol() == protocol)
+    }
+
+    /// 当前编译配置支持的格式（服务端按此顺序优先选择）
+    pub fn supported() -> Vec<Self> {
+        [Self::MessagePack, Self::Json]
+            .into_iter()
+            .filter(|format| format.is_supported())
+            .collect()
+    }
+
+    pub fn is_supported(self) -> bool {
+        match self {
+            Self::Json => true,
+            Self::MessagePack => cfg!(feature = "msgpack"),
+        }
+    }
+
+    /// 编码为 WebSocket 帧：JSON 为文本帧，MessagePack 为二进制帧
+    pub fn encode<T: Serialize>(self, value: &T) -> Result<WireFrame, RutifyError> {
+        match self {
+            Self::Json => serde_json::to_string(value)
+                .map(WireFrame::Text)
+                .map_err(parse_error),
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => rmp_serde::to_vec_named(value)
+                .map(WireFrame::Binary)
+                .map_err(parse_error),
+            #[cfg(not(feature = "msgpack"))]
+            Self::MessagePack => Err(unsupported()),
+        }
+    }
+
+    /// 解码二进制帧
+    pub fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T, RutifyError> {
+        match self {
+            Self::Json => serde_json::from_slice(bytes).map_err(parse_error),
+            #[cfg(feature = "msgpack")]
+            Self::MessagePack => rmp_serde::from_slice(bytes).map_err(parse_error),
+            #[cfg(not(feature = "msgpack"))]
+            Self::MessagePack => Err(unsupported()),
+        }
+    }
+}
+
+fn parse_error(err: impl std::fmt::Display) -> RutifyError {
+    RutifyError::Parse {
+        message: err.to_string(),
+    }
+}
+
+#[cfg(not(feature = "msgpack"))]
+fn unsupported() -> RutifyError {
+    RutifyError::Config {
+        message: "MessagePack support requires the `msgpack` feature".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EventKind, NotificationData, NotifyEvent, NotifyId};
+
+    fn event() -> NotifyEvent {
+        NotifyEvent {
+            event: EventKind::Created,
+            data: NotificationData {
+                id: Some(NotifyId(3)),
+                notify: "disk full".to_string(),
+                title: "alert".to_string(),
+                device: "web1".to_string(),
+                priority: 4,
+                tags: vec!["disk".to_string()],
+                recipient: None,
+                channel: None,
+                expires_at: None,
+                progress: None,
+                link_previews: Vec::new(),
+                signature: None,
+                correlation_id: None,
+                token_id: None,
+            },
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_json_frame() {
+        let WireFrame::Text(text) = WireFormat::Json.encode(&event()).unwrap() else {
+            panic!("JSON should be a text frame");
+        };
+        assert!(text.contains(r#""event":"notify""#));
+        let decoded: NotifyEvent = WireFormat::Json.decode(text.as_bytes()).unwrap();
+        assert_eq!(decoded.data.id, Some(NotifyId(3)));
+
+        // 不认识的事件类型不影响解析
+        let future = text.replace(r#""event":"notify""#, r#""event":"archived""#);
+        let decoded: NotifyEvent = WireFormat::Json.decode(future.as_bytes()).unwrap();
+        assert_eq!(decoded.event, EventKind::Unknown);
+    }
+
+    #[test]
+    fn test_subprotocol() {
+        assert_eq!(
+            WireFormat::from_subprotocol("rutify.json"),
+            Some(WireFormat::Json)
+        );
+        assert_eq!(WireFormat::from_subprotocol("json"), None);
+        assert_eq!(WireFormat::parse("xml"), None);
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn test_msgpack_roundtrip() {
+        let original = event();
+        let WireFrame::Binary(bytes) = WireFormat::MessagePack.encode(&original).unwrap() else {
+            panic!("MessagePack should be a binary frame");
+        };
+        let decoded: NotifyEvent = WireFormat::MessagePack.decode(&bytes).unwrap();
+        assert_eq!(decoded.data.tags, original.data.tags);
+        assert_eq!(decoded.data.recipient, None);
+        assert_eq!(decoded.timestamp, original.timestamp);
+        assert_eq!(
+            WireFormat::from_subprotocol("rutify.msgpack"),
+            Some(WireFormat::MessagePack)
+        );
+    }
+}
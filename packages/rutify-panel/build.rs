@@ -1,3 +1,5 @@
 fn main() {
-    slint_build::compile("ui/panel.slint").unwrap();
+    // 打包 lang/<语言>/LC_MESSAGES/*.po 中的翻译，运行时由 select_bundled_translation 切换
+    let config = slint_build::CompilerConfiguration::new().with_bundled_translations("lang");
+    slint_build::compile_with_config("ui/panel.slint", config).unwrap();
 }
@@ -1,5 +1,7 @@
 use clap::Parser;
-use rutify_sdk::RutifyClient;
+use rutify_client::i18n::{self, Language};
+use rutify_client::{GuiSettings, ThemePreference, TimeFormat, TimeZoneSetting, t};
+use rutify_sdk::{EventKind, NotifyId, RutifyClient, TokenId};
 use std::sync::{Arc, Mutex};
 
 mod tests;
@@ -10,6 +12,22 @@ mod tests;
 pub struct Cli {
     #[arg(short, long, default_value = "http://localhost:8080")]
     pub server: String,
+
+    /// Timezone for timestamps: local, UTC or an IANA name such as Europe/Berlin
+    #[arg(long, default_value = "local")]
+    pub timezone: TimeZoneSetting,
+
+    /// Show timestamps relative to now, e.g. "3 minutes ago"
+    #[arg(long)]
+    pub relative: bool,
+
+    /// Interface language: en or zh-CN (defaults to RUTIFY_LANG, then LANG)
+    #[arg(long)]
+    pub lang: Option<Language>,
+
+    /// Admin JWT used to load the server overview (defaults to RUTIFY_USER_TOKEN)
+    #[arg(long)]
+    pub user_token: Option<String>,
 }
 
 slint::include_modules!();
@@ -37,14 +55,49 @@ impl ManagementState {
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    i18n::init(cli.lang.unwrap_or_else(|| Language::detect(None)));
     let state = ManagementState::new(&cli.server);
+    if let Some(user_token) = cli
+        .user_token
+        .or_else(|| std::env::var("RUTIFY_USER_TOKEN").ok())
+    {
+        state.client.set_user_token(&user_token);
+    }
 
-    run_management_panel(state).await?;
+    run_management_panel(state, TimeFormat::new(cli.timezone, cli.relative)).await?;
     Ok(())
 }
 
-async fn run_management_panel(state: ManagementState) -> anyhow::Result<()> {
+async fn run_management_panel(
+    state: ManagementState,
+    time_format: TimeFormat,
+) -> anyhow::Result<()> {
     let ui = ManagementWindow::new()?;
+    select_translation();
+    ui.set_admin_available(state.client.user_token().is_some());
+    match GuiSettings::load() {
+        Ok(settings) => ui
+            .global::<Theme>()
+            .invoke_apply(theme_mode(settings.theme)),
+        Err(e) => eprintln!("Failed to load settings: {:#}", e),
+    }
+
+    // 主题与 rutify-application 共用 GUI 配置，只改写主题
+    let ui_weak = ui.as_weak();
+    ui.on_theme_changed(move |mode| {
+        let Some(ui) = ui_weak.upgrade() else {
+            return;
+        };
+        ui.global::<Theme>().invoke_apply(mode);
+        let result = GuiSettings::load().and_then(|mut settings| {
+            settings.theme = theme_preference(mode);
+            settings.save()
+        });
+        match result {
+            Ok(()) => ui.set_status(t!("gui-settings-saved").into()),
+            Err(e) => ui.set_status(t!("gui-settings-save-failed", error = e).into()),
+        }
+    });
 
     // Set up UI callbacks
     let notifications = Arc::clone(&state.notifications);
@@ -70,7 +123,16 @@ async fn run_management_panel(state: ManagementState) -> anyhow::Result<()> {
         let devices = Arc::clone(&devices_clone);
 
         tokio::spawn(async move {
-            refresh_all_data(ui_weak, &client, &notifications, &stats, &tokens, &devices).await;
+            refresh_all_data(
+                ui_weak,
+                &client,
+                &notifications,
+                &stats,
+                &tokens,
+                &devices,
+                &time_format,
+            )
+            .await;
         });
     });
 
@@ -87,11 +149,41 @@ async fn run_management_panel(state: ManagementState) -> anyhow::Result<()> {
         tokio::spawn(async move {
             // This would be implemented when we have delete API
             if let Some(ui) = ui_weak.upgrade() {
-                ui.set_status("Delete notification not yet implemented".into());
+                ui.set_status(t!("gui-delete-not-implemented").into());
             }
         });
     });
 
+    // Star notification
+    let ui_weak = ui.as_weak();
+    let client_clone = client.clone();
+    let notifications_clone = Arc::clone(&notifications);
+
+    ui.on_toggle_star(move |id, starred| {
+        let ui_weak = ui_weak.clone();
+        let client = client_clone.clone();
+        let notifications = Arc::clone(&notifications_clone);
+
+        tokio::spawn(async move {
+            let result = client.set_notification_starred(NotifyId(id), starred).await;
+            let _ = slint::invoke_from_event_loop(move || {
+                let Some(ui) = ui_weak.upgrade() else {
+                    return;
+                };
+                match result {
+                    Ok(()) => {
+                        let mut guard = notifications.lock().unwrap();
+                        if let Some(item) = guard.iter_mut().find(|item| item.id.get() == id) {
+                            item.starred = starred;
+                        }
+                        update_notifications_ui(&ui, &guard, &time_format);
+                    }
+                    Err(e) => ui.set_status(t!("gui-star-failed", error = e).into()),
+                }
+            });
+        });
+    });
+
     // Create token
     let ui_weak = ui.as_weak();
     let client_clone = client.clone();
@@ -105,26 +197,64 @@ async fn run_management_panel(state: ManagementState) -> anyhow::Result<()> {
         tokio::spawn(async move {
             // This would be implemented when we have token management API
             if let Some(ui) = ui_weak.upgrade() {
-                ui.set_status("Token creation not yet implemented".into());
+                ui.set_status(t!("gui-token-create-not-implemented").into());
             }
         });
     });
 
-    // Delete token
+    // Load users and tokens
     let ui_weak = ui.as_weak();
     let client_clone = client.clone();
-    let tokens_clone = Arc::clone(&tokens);
 
-    ui.on_delete_token(move |_id| {
+    ui.on_load_admin(move || {
         let ui_weak = ui_weak.clone();
-        let _client = client_clone.clone();
-        let _tokens = Arc::clone(&tokens_clone);
+        let client = client_clone.clone();
 
         tokio::spawn(async move {
-            // This would be implemented when we have token management API
-            if let Some(ui) = ui_weak.upgrade() {
-                ui.set_status("Token deletion not yet implemented".into());
-            }
+            load_admin_data(ui_weak, &client, &time_format).await;
+        });
+    });
+
+    // Disable, enable or promote a user
+    let ui_weak = ui.as_weak();
+    let client_clone = client.clone();
+
+    ui.on_user_action(move |username, action| {
+        let ui_weak = ui_weak.clone();
+        let client = client_clone.clone();
+        set_admin_busy(&ui_weak, true);
+
+        tokio::spawn(async move {
+            let result = match action.as_str() {
+                "disable" => client.disable_user(&username).await,
+                "enable" => client.enable_user(&username).await,
+                _ => client.promote_user(&username).await,
+            };
+            let status = match result {
+                Ok(user) => t!("gui-user-updated", username = user.username),
+                Err(e) => t!("gui-user-action-failed", error = e),
+            };
+            load_admin_data(ui_weak.clone(), &client, &time_format).await;
+            set_status(&ui_weak, status);
+        });
+    });
+
+    // Revoke token
+    let ui_weak = ui.as_weak();
+    let client_clone = client.clone();
+
+    ui.on_delete_token(move |id| {
+        let ui_weak = ui_weak.clone();
+        let client = client_clone.clone();
+        set_admin_busy(&ui_weak, true);
+
+        tokio::spawn(async move {
+            let status = match client.delete_user_token(TokenId(id)).await {
+                Ok(()) => t!("gui-token-revoked"),
+                Err(e) => t!("gui-token-revoke-failed", error = e),
+            };
+            load_admin_data(ui_weak.clone(), &client, &time_format).await;
+            set_status(&ui_weak, status);
         });
     });
 
@@ -148,18 +278,19 @@ async fn run_management_panel(state: ManagementState) -> anyhow::Result<()> {
             } else {
                 Some(device.to_string())
             },
+            ..Default::default()
         };
 
         tokio::spawn(async move {
             match client.send_notification(&input).await {
                 Ok(_) => {
                     if let Some(ui) = ui_weak.upgrade() {
-                        ui.set_status("Test notification sent successfully!".into());
+                        ui.set_status(t!("gui-test-sent").into());
                     }
                 }
                 Err(e) => {
                     if let Some(ui) = ui_weak.upgrade() {
-                        ui.set_status(format!("Failed to send: {}", e).into());
+                        ui.set_status(t!("gui-send-failed", error = e).into());
                     }
                 }
             }
@@ -173,7 +304,7 @@ async fn run_management_panel(state: ManagementState) -> anyhow::Result<()> {
     // let notifications_clone = Arc::clone(&notifications);
     //
     // tokio::spawn(async move {
-    //     if let Err(e) = start_websocket_listener(ui_weak, client_clone, notifications_clone).await {
+    //     if let Err(e) = start_websocket_listener(ui_weak, client_clone, notifications_clone, time_format).await {
     //         eprintln!("WebSocket listener errors: {}", e);
     //     }
     // });
@@ -194,6 +325,7 @@ async fn run_management_panel(state: ManagementState) -> anyhow::Result<()> {
             &stats_clone,
             &tokens_clone,
             &devices_clone,
+            &time_format,
         )
         .await;
     });
@@ -202,13 +334,41 @@ async fn run_management_panel(state: ManagementState) -> anyhow::Result<()> {
     Ok(())
 }
 
+fn theme_mode(theme: ThemePreference) -> ThemeMode {
+    match theme {
+        ThemePreference::System => ThemeMode::System,
+        ThemePreference::Light => ThemeMode::Light,
+        ThemePreference::Dark => ThemeMode::Dark,
+    }
+}
+
+fn theme_preference(mode: ThemeMode) -> ThemePreference {
+    match mode {
+        ThemeMode::Light => ThemePreference::Light,
+        ThemeMode::Dark => ThemePreference::Dark,
+        _ => ThemePreference::System,
+    }
+}
+
+/// 按当前语言切换 Slint 内置翻译，英文直接使用源文本
+fn select_translation() {
+    let code = i18n::language().slint_code();
+    if code.is_empty() {
+        return;
+    }
+    if let Err(e) = slint::select_bundled_translation(code) {
+        eprintln!("Failed to select translation '{}': {}", code, e);
+    }
+}
+
 async fn refresh_all_data(
     ui_weak: slint::Weak<ManagementWindow>,
     client: &RutifyClient,
     notifications: &Arc<Mutex<Vec<rutify_sdk::NotifyItem>>>,
     stats: &Arc<Mutex<Option<rutify_sdk::Stats>>>,
     _tokens: &Arc<Mutex<Vec<rutify_sdk::TokenItem>>>,
-    _devices: &Arc<Mutex<Vec<rutify_sdk::DeviceInfo>>>,
+    devices: &Arc<Mutex<Vec<rutify_sdk::DeviceInfo>>>,
+    time_format: &TimeFormat,
 ) {
     // Load notifications
     match client.get_notifies().await {
@@ -217,7 +377,7 @@ async fn refresh_all_data(
             *guard = items;
 
             if let Some(ui) = ui_weak.upgrade() {
-                update_notifications_ui(&ui, &guard);
+                update_notifications_ui(&ui, &guard, time_format);
             }
         }
         Err(e) => {
@@ -240,16 +400,283 @@ async fn refresh_all_data(
         }
     }
 
-    // Tokens and devices would be loaded here when APIs are available
+    // Load current on-call person
+    match client.get_oncall().await {
+        Ok(oncall) => {
+            if let Some(ui) = ui_weak.upgrade() {
+                ui.set_oncall(oncall_label(oncall.as_ref()).into());
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to load on-call: {}", e);
+        }
+    }
+
+    // Load uptime checks with their recent history
+    match client.get_checks().await {
+        Ok(checks) => {
+            let mut rows = Vec::with_capacity(checks.len());
+            for check in checks {
+                let history = client
+                    .get_check_history(check.id, CHECK_HISTORY_LEN)
+                    .await
+                    .unwrap_or_default();
+                rows.push(check_row(&check, &history));
+            }
+            if let Some(ui) = ui_weak.upgrade() {
+                ui.set_checks(slint::ModelRc::new(slint::VecModel::from(rows)));
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to load checks: {}", e);
+        }
+    }
+
+    // Load per-device activity
+    match client.get_device_stats().await {
+        Ok(stats) => {
+            *devices.lock().unwrap() = stats.iter().map(device_info).collect();
+            let rows: Vec<DeviceRow> = stats
+                .iter()
+                .map(|item| device_row(item, time_format))
+                .collect();
+            if let Some(ui) = ui_weak.upgrade() {
+                ui.set_devices(slint::ModelRc::new(slint::VecModel::from(rows)));
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to load device stats: {}", e);
+        }
+    }
+
+    // Load the admin overview, only available with an admin token
+    if client.user_token().is_some() {
+        match client.get_admin_overview().await {
+            Ok(overview) => {
+                if let Some(ui) = ui_weak.upgrade() {
+                    ui.set_version(overview.version.clone().into());
+                    ui.set_connections(overview.connections.to_string().into());
+                    ui.set_admin_summary(overview_summary(&overview).into());
+                }
+            }
+            Err(e) => {
+                eprintln!("Failed to load admin overview: {}", e);
+            }
+        }
+    }
+
+    // Load enabled optional subsystems, older servers don't report them
+    match client.get_capabilities().await {
+        Ok(capabilities) => {
+            if let Some(ui) = ui_weak.upgrade() {
+                let features = capabilities.map_or_else(String::new, |c| c.enabled().join(", "));
+                ui.set_features(features.into());
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to load capabilities: {}", e);
+        }
+    }
+
+    load_admin_data(ui_weak.clone(), client, time_format).await;
+
     if let Some(ui) = ui_weak.upgrade() {
-        ui.set_status("Data refreshed".into());
+        ui.set_status(t!("gui-refreshed").into());
+    }
+}
+
+/// 读取用户与所有 Token，需要管理员 JWT，加载期间显示忙碌状态
+async fn load_admin_data(
+    ui_weak: slint::Weak<ManagementWindow>,
+    client: &RutifyClient,
+    time_format: &TimeFormat,
+) {
+    if client.user_token().is_none() {
+        return;
+    }
+    set_admin_busy(&ui_weak, true);
+
+    let result = match client.get_admin_users().await {
+        Ok(users) => client.get_all_tokens().await.map(|all| (users, all)),
+        Err(e) => Err(e),
+    };
+    let time_format = *time_format;
+    let status = match result {
+        Ok((users, all)) => {
+            let user_rows: Vec<UserRow> = users
+                .iter()
+                .map(|user| user_row(user, &time_format))
+                .collect();
+            let token_rows: Vec<AdminTokenRow> = all
+                .iter()
+                .map(|token| admin_token_row(token, &time_format))
+                .collect();
+            let status = t!(
+                "gui-admin-loaded",
+                users = user_rows.len(),
+                tokens = token_rows.len()
+            );
+            let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                ui.set_users(slint::ModelRc::new(slint::VecModel::from(user_rows)));
+                ui.set_tokens(slint::ModelRc::new(slint::VecModel::from(token_rows)));
+            });
+            status
+        }
+        Err(e) => t!("gui-admin-load-failed", error = e),
+    };
+    set_status(&ui_weak, status);
+    set_admin_busy(&ui_weak, false);
+}
+
+fn set_admin_busy(ui_weak: &slint::Weak<ManagementWindow>, busy: bool) {
+    let _ = ui_weak.upgrade_in_event_loop(move |ui| ui.set_admin_busy(busy));
+}
+
+fn set_status(ui_weak: &slint::Weak<ManagementWindow>, status: String) {
+    let _ = ui_weak.upgrade_in_event_loop(move |ui| ui.set_status(status.into()));
+}
+
+fn user_row(user: &rutify_sdk::AdminUser, time_format: &TimeFormat) -> UserRow {
+    UserRow {
+        username: user.username.clone().into(),
+        email: user.email.clone().into(),
+        role: user.role.clone().into(),
+        verified: user.email_verified,
+        disabled: user.disabled,
+        created: time_format.format(user.created_at).into(),
+    }
+}
+
+fn admin_token_row(token: &rutify_sdk::TokenInfo, time_format: &TimeFormat) -> AdminTokenRow {
+    AdminTokenRow {
+        id: token.id.get(),
+        usage: token.usage.clone().into(),
+        owner: token
+            .owner
+            .clone()
+            .unwrap_or_else(|| "-".to_string())
+            .into(),
+        kind: token.token_type.clone().into(),
+        last_used: token
+            .last_used_at
+            .map(|at| time_format.format(at))
+            .unwrap_or_default()
+            .into(),
+        expires: time_format.format(token.expires_at).into(),
     }
 }
 
-fn update_notifications_ui(ui: &ManagementWindow, notifications: &Vec<rutify_sdk::NotifyItem>) {
-    // 简化版本，暂时不设置通知列表
-    // TODO: 实现通知列表显示
-    ui.set_status(format!("Loaded {} notifications", notifications.len()).into());
+fn update_notifications_ui(
+    ui: &ManagementWindow,
+    notifications: &Vec<rutify_sdk::NotifyItem>,
+    time_format: &TimeFormat,
+) {
+    let rows: Vec<NotificationRow> = notifications
+        .iter()
+        .map(|notify| NotificationRow {
+            id: notify.id.get(),
+            title: notify.title.clone().into(),
+            message: notify.notify.clone().into(),
+            device: notify.device.clone().into(),
+            timestamp: time_format.format(notify.received_at).into(),
+            starred: notify.starred,
+        })
+        .collect();
+    ui.set_notifications(slint::ModelRc::new(slint::VecModel::from(rows)));
+    ui.set_status(t!("gui-loaded", count = notifications.len()).into());
+}
+
+/// 检查列表中显示的最近结果数
+const CHECK_HISTORY_LEN: u64 = 20;
+
+/// 检查行，历史按时间从左到右排列，`●` 为正常、`○` 为异常
+fn check_row(check: &rutify_sdk::UptimeCheck, history: &[rutify_sdk::CheckResult]) -> CheckRow {
+    CheckRow {
+        name: check.name.clone().into(),
+        url: check.url.clone().into(),
+        up: check.status == rutify_sdk::CheckStatus::Up,
+        status: check.status.as_str().into(),
+        latency: check
+            .last_latency_ms
+            .map(|ms| format!("{} ms", ms))
+            .unwrap_or_default()
+            .into(),
+        history: history
+            .iter()
+            .rev()
+            .map(|result| if result.up { '●' } else { '○' })
+            .collect::<String>()
+            .into(),
+    }
+}
+
+/// 今天有通知的设备视为活跃
+fn device_info(stats: &rutify_sdk::DeviceStats) -> rutify_sdk::DeviceInfo {
+    rutify_sdk::DeviceInfo {
+        id: None,
+        name: stats.device.to_string(),
+        last_seen: stats.last_notified_at,
+        is_active: stats.today_count > 0,
+    }
+}
+
+fn device_row(stats: &rutify_sdk::DeviceStats, time_format: &TimeFormat) -> DeviceRow {
+    DeviceRow {
+        name: stats.device.as_str().into(),
+        total: i32::try_from(stats.total_count).unwrap_or(i32::MAX),
+        today: i32::try_from(stats.today_count).unwrap_or(i32::MAX),
+        last: stats
+            .last_notified_at
+            .map(|at| time_format.format(at))
+            .unwrap_or_default()
+            .into(),
+    }
+}
+
+/// 仪表盘摘要：各类型的有效 Token 数、数据库大小与认证失败次数
+fn overview_summary(overview: &rutify_sdk::AdminOverview) -> String {
+    let tokens = overview
+        .tokens
+        .iter()
+        .map(|(kind, count)| format!("{kind} {count}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let size = overview
+        .db_size_bytes
+        .map(format_size)
+        .unwrap_or_else(|| t!("gui-unknown"));
+    t!(
+        "gui-admin-summary",
+        tokens = tokens,
+        size = size,
+        failures = overview.auth_failures_total
+    )
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+fn oncall_label(oncall: Option<&rutify_sdk::OnCallInfo>) -> String {
+    match oncall {
+        Some(info) => format!(
+            "{} (until {})",
+            info.username,
+            info.until.format("%Y-%m-%d %H:%M UTC")
+        ),
+        None => "Nobody".to_string(),
+    }
 }
 
 fn update_stats_ui(
@@ -268,7 +695,7 @@ fn update_stats_ui(
             }
             .into(),
         );
-        ui.set_uptime("Unknown".into()); // Would be calculated from server start time
+        ui.set_uptime(t!("gui-unknown").into()); // Would be calculated from server start time
     }
 }
 
@@ -276,28 +703,60 @@ async fn start_websocket_listener(
     ui_weak: slint::Weak<ManagementWindow>,
     client: RutifyClient,
     notifications: Arc<Mutex<Vec<rutify_sdk::NotifyItem>>>,
+    time_format: TimeFormat,
 ) -> anyhow::Result<()> {
     match client.connect_websocket().await {
         Ok(mut rx) => {
             while let Some(msg) = rx.recv().await {
                 match msg {
                     rutify_sdk::WebSocketMessage::Event(event) => {
-                        // Add new notification to the list
+                        // 批量删除或清理后重新读取列表
+                        if event.event == EventKind::StatsChanged
+                            && let Ok(items) = client.get_notifies().await
+                        {
+                            *notifications.lock().unwrap() = items;
+                        }
+
                         let mut guard = notifications.lock().unwrap();
-                        guard.insert(
-                            0,
-                            rutify_sdk::NotifyItem {
-                                id: 0, // Will be set by server
-                                title: event.data.title,
-                                notify: event.data.notify,
-                                device: event.data.device,
-                                received_at: event.timestamp,
-                            },
-                        );
+                        // 更新与升级事件就地替换已有通知，删除事件移除，新通知插入到列表最前
+                        let cached = event
+                            .data
+                            .id
+                            .and_then(|id| guard.iter().position(|item| item.id == id));
+                        match (event.event, cached) {
+                            (EventKind::Updated | EventKind::Escalated, Some(index)) => {
+                                guard[index].apply_update(&event.data);
+                            }
+                            (EventKind::Deleted, Some(index)) => {
+                                guard.remove(index);
+                            }
+                            (kind, _) if kind.is_notification() => guard.insert(
+                                0,
+                                rutify_sdk::NotifyItem {
+                                    id: event.data.id.unwrap_or_default(),
+                                    title: event.data.title,
+                                    notify: event.data.notify,
+                                    device: event.data.device,
+                                    priority: event.data.priority,
+                                    tags: event.data.tags,
+                                    channel: event.data.channel,
+                                    received_at: event.timestamp,
+                                    expires_at: event.data.expires_at,
+                                    pinned: false,
+                                    starred: false,
+                                    progress: event.data.progress,
+                                    device_source: None,
+                                    link_previews: event.data.link_previews,
+                                    signature: event.data.signature,
+                                    correlation_id: event.data.correlation_id,
+                                },
+                            ),
+                            _ => {}
+                        }
 
                         // Update UI
                         if let Some(ui) = ui_weak.upgrade() {
-                            update_notifications_ui(&ui, &guard);
+                            update_notifications_ui(&ui, &guard, &time_format);
 
                             // Update stats
                             if let Ok(stats) = client.get_stats().await {
@@ -52,16 +52,27 @@ mod tests {
         let mut guard = state.notifications.lock().unwrap();
 
         let item = rutify_sdk::NotifyItem {
-            id: 1,
+            id: rutify_sdk::NotifyId(1),
             title: "Test".to_string(),
             notify: "Message".to_string(),
             device: "Device".to_string(),
+            priority: rutify_sdk::DEFAULT_PRIORITY,
+            tags: Vec::new(),
+            channel: None,
             received_at: chrono::Utc::now(),
+            expires_at: None,
+            pinned: false,
+            starred: false,
+            progress: None,
+            device_source: None,
+            link_previews: Vec::new(),
+            signature: None,
+            correlation_id: None,
         };
 
         guard.push(item);
         assert_eq!(guard.len(), 1);
-        assert_eq!(guard[0].id, 1);
+        assert_eq!(guard[0].id, rutify_sdk::NotifyId(1));
     }
 
     #[test]
@@ -149,4 +160,98 @@ mod tests {
         assert_eq!(cloned_state.notifications.lock().unwrap().len(), 0);
         assert!(cloned_state.stats.lock().unwrap().is_none());
     }
+
+    #[test]
+    fn test_oncall_label() {
+        use chrono::TimeZone;
+
+        let info = rutify_sdk::OnCallInfo {
+            schedule: "default".to_string(),
+            username: "alice".to_string(),
+            until: chrono::Utc.with_ymd_and_hms(2026, 3, 2, 9, 0, 0).unwrap(),
+        };
+
+        assert_eq!(
+            crate::oncall_label(Some(&info)),
+            "alice (until 2026-03-02 09:00 UTC)"
+        );
+        assert_eq!(crate::oncall_label(None), "Nobody");
+    }
+
+    #[test]
+    fn test_check_row() {
+        let now = chrono::Utc::now();
+        let check = rutify_sdk::UptimeCheck {
+            id: 1,
+            name: "website".to_string(),
+            url: "https://example.com".to_string(),
+            interval_secs: 60,
+            timeout_secs: 10,
+            expected_status: None,
+            status: rutify_sdk::CheckStatus::Down,
+            priority: 4,
+            channel: None,
+            enabled: true,
+            last_checked_at: Some(now),
+            last_latency_ms: Some(120),
+            created_at: now,
+        };
+        let result = |up| rutify_sdk::CheckResult {
+            checked_at: now,
+            up,
+            status_code: None,
+            latency_ms: 0,
+            error: None,
+        };
+
+        // 接口返回新的在前，显示时最新的在最右
+        let row = crate::check_row(&check, &[result(false), result(true), result(true)]);
+        assert_eq!(row.history, "●●○");
+        assert_eq!(row.status, "down");
+        assert_eq!(row.latency, "120 ms");
+        assert!(!row.up);
+    }
+
+    #[test]
+    fn test_device_row() {
+        let stats = rutify_sdk::DeviceStats {
+            device: "nas".into(),
+            total_count: 12,
+            today_count: 0,
+            last_notified_at: None,
+        };
+
+        let row = crate::device_row(&stats, &rutify_client::TimeFormat::default());
+        assert_eq!(row.name, "nas");
+        assert_eq!((row.total, row.today), (12, 0));
+        assert_eq!(row.last, "");
+        assert!(!crate::device_info(&stats).is_active);
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(crate::format_size(512), "512 B");
+        assert_eq!(crate::format_size(1536), "1.5 KB");
+        assert_eq!(crate::format_size(12 * 1024 * 1024), "12.0 MB");
+    }
+
+    #[test]
+    fn test_admin_token_row() {
+        let token = rutify_sdk::TokenInfo {
+            id: rutify_sdk::TokenId(7),
+            usage: "ci".to_string(),
+            token_type: "notify_bearer".to_string(),
+            device_info: None,
+            created_at: rutify_sdk::timestamp::parse("2024-01-01T08:00:00Z").unwrap(),
+            expires_at: rutify_sdk::timestamp::parse("2024-02-01T08:00:00Z").unwrap(),
+            last_used_at: None,
+            owner: Some("alice".to_string()),
+        };
+
+        let row = crate::admin_token_row(&token, &rutify_client::TimeFormat::default());
+        assert_eq!(row.id, 7);
+        assert_eq!(row.owner, "alice");
+        assert_eq!(row.last_used, "");
+        assert_eq!(row.expires, "2024-02-01 08:00:00");
+    }
 }
@@ -0,0 +1,22 @@
+fn main() {
+    #[cfg(feature = "grpc")]
+    {
+        use std::path::PathBuf;
+
+        println!("cargo:rerun-if-changed=../../proto/rutify.proto");
+
+        // 使用内置的 protoc 与标准 proto 文件，无需在系统中安装
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc not found");
+        let well_known =
+            protoc_bin_vendored::include_path().expect("vendored protoc includes not found");
+        unsafe { std::env::set_var("PROTOC", protoc) };
+
+        tonic_prost_build::configure()
+            .build_server(false)
+            .compile_protos(
+                &[PathBuf::from("../../proto/rutify.proto")],
+                &[PathBuf::from("../../proto"), well_known],
+            )
+            .expect("failed to compile rutify.proto");
+    }
+}
@@ -1,10 +1,18 @@
+use chrono::{DateTime, Utc};
+use rutify_core::{ProofOfWorkSolution, UserId, timestamp};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RegisterRequest {
     pub username: String,
     pub password: String,
     pub email: String,
+    /// 服务端要求 hCaptcha / Turnstile 时，填入控件返回的 Token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub captcha_token: Option<String>,
+    /// 工作量证明答案，为空时 [`RutifyClient::register`](crate::RutifyClient::register) 自动求解
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof_of_work: Option<ProofOfWorkSolution>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -15,37 +23,11 @@ pub struct LoginRequest {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LoginResponse {
-    pub user_id: String,
+    pub user_id: UserId,
     pub username: String,
     pub email: String,
     pub role: String,
     pub jwt_token: String,
-    pub expires_at: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct CreateTokenRequest {
-    pub usage: String,
-    pub expires_in_hours: Option<u64>,
-    pub device_info: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct CreateTokenResponse {
-    pub token: String,
-    pub token_id: String,
-    pub usage: String,
-    pub token_type: String,
-    pub expires_at: String,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct TokenInfo {
-    pub id: i32,
-    pub usage: String,
-    pub token_type: String,
-    pub device_info: Option<String>,
-    pub created_at: String,
-    pub expires_at: String,
-    pub last_used_at: Option<String>,
+    #[serde(deserialize_with = "timestamp::deserialize")]
+    pub expires_at: DateTime<Utc>,
 }
@@ -0,0 +1,105 @@
+//! 响应缓存（stale-while-revalidate）：有缓存时立即返回，过期后在后台刷新
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// 按接口与参数缓存的响应
+pub(crate) struct ResponseCache {
+    /// 超过该时长的缓存在下次读取时触发后台刷新
+    max_age: Duration,
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+struct Entry {
+    value: Value,
+    fetched_at: Instant,
+    refreshing: bool,
+}
+
+/// 读取缓存的结果
+pub(crate) enum Lookup {
+    /// 缓存未过期
+    Fresh(Value),
+    /// 缓存已过期，调用方负责刷新；同一时刻只有一个调用方拿到该结果
+    Stale(Value),
+    /// 缓存已过期且正在刷新
+    Refreshing(Value),
+    Missing,
+}
+
+impl ResponseCache {
+    pub(crate) fn new(max_age: Duration) -> Self {
+        Self {
+            max_age,
+            entries: Mutex::default(),
+        }
+    }
+
+    pub(crate) fn lookup(&self, key: &str) -> Lookup {
+        let mut entries = self.entries.lock().unwrap();
+        let Some(entry) = entries.get_mut(key) else {
+            return Lookup::Missing;
+        };
+        if entry.fetched_at.elapsed() < self.max_age {
+            Lookup::Fresh(entry.value.clone())
+        } else if entry.refreshing {
+            Lookup::Refreshing(entry.value.clone())
+        } else {
+            entry.refreshing = true;
+            Lookup::Stale(entry.value.clone())
+        }
+    }
+
+    /// 保存最新响应，返回内容是否与之前的缓存不同
+    pub(crate) fn store(&self, key: &str, value: Value) -> bool {
+        let mut entries = self.entries.lock().unwrap();
+        let changed = entries.get(key).is_none_or(|entry| entry.value != value);
+        entries.insert(
+            key.to_string(),
+            Entry {
+                value,
+                fetched_at: Instant::now(),
+                refreshing: false,
+            },
+        );
+        changed
+    }
+
+    /// 后台刷新失败，保留旧数据，下次读取时重试
+    pub(crate) fn refresh_failed(&self, key: &str) {
+        if let Some(entry) = self.entries.lock().unwrap().get_mut(key) {
+            entry.refreshing = false;
+        }
+    }
+
+    pub(crate) fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_stale_entry_refreshed_once() {
+        let cache = ResponseCache::new(Duration::ZERO);
+        assert!(matches!(cache.lookup("stats"), Lookup::Missing));
+
+        assert!(cache.store("stats", json!({ "total_count": 1 })));
+        assert!(matches!(cache.lookup("stats"), Lookup::Stale(_)));
+        assert!(matches!(cache.lookup("stats"), Lookup::Refreshing(_)));
+
+        cache.refresh_failed("stats");
+        assert!(matches!(cache.lookup("stats"), Lookup::Stale(_)));
+        assert!(!cache.store("stats", json!({ "total_count": 1 })));
+        assert!(cache.store("stats", json!({ "total_count": 2 })));
+
+        let cache = ResponseCache::new(Duration::from_secs(60));
+        cache.store("stats", json!({ "total_count": 1 }));
+        assert!(matches!(cache.lookup("stats"), Lookup::Fresh(_)));
+    }
+}
@@ -0,0 +1,195 @@
+//! WebSocket 连接：与 HTTP 客户端共用代理与自定义根证书
+
+use crate::SdkResult;
+use crate::error::SdkError;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use percent_encoding::percent_decode_str;
+use rustls::pki_types::CertificateDer;
+use rustls::pki_types::pem::PemObject;
+use rustls::{ClientConfig, RootCertStore};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::handshake::client::Request as ClientRequest;
+use tokio_tungstenite::tungstenite::http::Uri;
+use tokio_tungstenite::{
+    Connector, MaybeTlsStream, WebSocketStream, client_async_tls_with_config,
+    connect_async_tls_with_config,
+};
+use url::Url;
+
+/// 代理 CONNECT 响应头的长度上限
+const MAX_PROXY_RESPONSE: usize = 8 * 1024;
+
+pub(crate) type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// WebSocket 连接设置
+#[derive(Clone, Default)]
+pub(crate) struct WsConnector {
+    /// HTTP 代理，通过 CONNECT 建立隧道
+    proxy: Option<Url>,
+    /// 在系统根证书之外信任的证书
+    root_certificates: Vec<CertificateDer<'static>>,
+}
+
+impl WsConnector {
+    /// 设置代理，仅支持 `http://` 代理
+    pub(crate) fn set_proxy(&mut self, proxy: Url) -> SdkResult<()> {
+        if proxy.scheme() != "http" || proxy.host_str().is_none() {
+            return Err(SdkError::ConfigError(format!(
+                "unsupported proxy {proxy}, expected http://host:port"
+            )));
+        }
+        self.proxy = Some(proxy);
+        Ok(())
+    }
+
+    /// 添加 PEM 格式的根证书，可包含多张
+    pub(crate) fn add_root_certificates(&mut self, pem: &[u8]) -> SdkResult<()> {
+        let certs = CertificateDer::pem_slice_iter(pem)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| SdkError::ConfigError(format!("invalid root certificate: {e}")))?;
+        if certs.is_empty() {
+            return Err(SdkError::ConfigError(
+                "no certificate found in PEM".to_string(),
+            ));
+        }
+        self.root_certificates.extend(certs);
+        Ok(())
+    }
+
+    pub(crate) async fn connect(&self, request: ClientRequest) -> SdkResult<WsStream> {
+        let connector = self.tls_connector();
+        let result = match &self.proxy {
+            Some(proxy) => {
+                let stream = tunnel(proxy, request.uri()).await?;
+                client_async_tls_with_config(request, stream, None, connector).await
+            }
+            None => connect_async_tls_with_config(request, None, false, connector).await,
+        };
+        result
+            .map(|(stream, _)| stream)
+            .map_err(|e| SdkError::NetworkError(e.to_string()))
+    }
+
+    /// 没有自定义根证书时返回 `None`，由 tokio-tungstenite 使用系统根证书
+    fn tls_connector(&self) -> Option<Connector> {
+        if self.root_certificates.is_empty() {
+            return None;
+        }
+        let mut roots = RootCertStore::empty();
+        roots.add_parsable_certificates(rustls_native_certs::load_native_certs().certs);
+        roots.add_parsable_certificates(self.root_certificates.iter().cloned());
+        let config = ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        Some(Connector::Rustls(Arc::new(config)))
+    }
+}
+
+/// 通过 HTTP 代理的 CONNECT 方法建立到目标主机的隧道
+async fn tunnel(proxy: &Url, target: &Uri) -> SdkResult<TcpStream> {
+    let host = target
+        .host()
+        .ok_or_else(|| SdkError::NetworkError(format!("missing host in {target}")))?;
+    let port = target
+        .port_u16()
+        .unwrap_or(if target.scheme_str() == Some("wss") {
+            443
+        } else {
+            80
+        });
+    let proxy_host = proxy.host_str().unwrap_or_default();
+    let proxy_port = proxy.port_or_known_default().unwrap_or(80);
+
+    let mut stream = TcpStream::connect((proxy_host, proxy_port))
+        .await
+        .map_err(|e| SdkError::NetworkError(format!("failed to connect to proxy: {e}")))?;
+
+    let mut head = format!("CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n");
+    if !proxy.username().is_empty() {
+        let credentials = format!(
+            "{}:{}",
+            percent_decode_str(proxy.username()).decode_utf8_lossy(),
+            percent_decode_str(proxy.password().unwrap_or_default()).decode_utf8_lossy()
+        );
+        head.push_str(&format!(
+            "Proxy-Authorization: Basic {}\r\n",
+            STANDARD.encode(credentials)
+        ));
+    }
+    head.push_str("\r\n");
+    stream
+        .write_all(head.as_bytes())
+        .await
+        .map_err(|e| SdkError::NetworkError(e.to_string()))?;
+
+    // 逐字节读取，避免把隧道建立后的数据读进缓冲区
+    let mut response = Vec::new();
+    while !response.ends_with(b"\r\n\r\n") {
+        if response.len() >= MAX_PROXY_RESPONSE {
+            return Err(SdkError::NetworkError(
+                "proxy response too large".to_string(),
+            ));
+        }
+        let byte = stream
+            .read_u8()
+            .await
+            .map_err(|e| SdkError::NetworkError(format!("proxy closed the connection: {e}")))?;
+        response.push(byte);
+    }
+
+    match connect_status(&response) {
+        Some(200) => Ok(stream),
+        Some(status) => Err(SdkError::NetworkError(format!(
+            "proxy CONNECT failed with status {status}"
+        ))),
+        None => Err(SdkError::NetworkError("invalid proxy response".to_string())),
+    }
+}
+
+/// 解析 CONNECT 响应的状态码
+fn connect_status(response: &[u8]) -> Option<u16> {
+    let line = response.split(|&b| b == b'\r').next()?;
+    let line = std::str::from_utf8(line).ok()?;
+    let mut parts = line.split_whitespace();
+    if !parts.next()?.starts_with("HTTP/1.") {
+        return None;
+    }
+    parts.next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connect_status() {
+        assert_eq!(
+            connect_status(b"HTTP/1.1 200 Connection established\r\n\r\n"),
+            Some(200)
+        );
+        assert_eq!(
+            connect_status(b"HTTP/1.0 407 Proxy Authentication Required\r\n\r\n"),
+            Some(407)
+        );
+        assert_eq!(connect_status(b"SSH-2.0-OpenSSH\r\n\r\n"), None);
+    }
+
+    #[test]
+    fn test_proxy_and_certificate_validation() {
+        let mut connector = WsConnector::default();
+        assert!(
+            connector
+                .set_proxy(Url::parse("socks5://127.0.0.1:1080").unwrap())
+                .is_err()
+        );
+        assert!(
+            connector
+                .set_proxy(Url::parse("http://127.0.0.1:3128").unwrap())
+                .is_ok()
+        );
+        assert!(connector.add_root_certificates(b"not a pem").is_err());
+    }
+}
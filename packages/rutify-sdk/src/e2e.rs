@@ -0,0 +1,298 @@
+//! 端到端加密：发送方在本地用共享密钥加密标题与正文，服务端只保存与转发密文，
+//! 持有同一密钥的订阅方在本地解密
+//!
+//! 设备、频道、优先级与标签仍为明文，服务端依赖它们投递与过滤；
+//! 加密后服务端无法搜索正文、生成链接预览，也不会截断超长的密文。
+//!
+//! ```no_run
+//! # async fn demo(client: &rutify_sdk::RutifyClient) -> rutify_sdk::SdkResult<()> {
+//! use futures_util::StreamExt;
+//! use rutify_sdk::{E2eKey, E2eKeyring, Notification};
+//!
+//! let key = E2eKey::load("oncall.key")?;
+//! let mut input = Notification::builder().body("db1 is down").channel("oncall").build()?;
+//! key.encrypt(&mut input)?;
+//! client.send_notification(&input).await?;
+//!
+//! let keyring = E2eKeyring::default().channel("oncall", key);
+//! let mut events = client.subscribe().await?.decrypt(keyring);
+//! while let Some(event) = events.next().await {
+//!     println!("{}", event.data.notify);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::SdkError;
+use crate::{E2E_PREFIX, NotificationData, NotificationInput, NotifyItem, SdkResult, is_e2e};
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+
+const NONCE_LEN: usize = 12;
+
+/// 密文中加密的字段
+#[derive(Serialize, Deserialize)]
+struct Sealed {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    notify: String,
+}
+
+/// 通知的共享密钥（AES-256-GCM）
+#[derive(Clone)]
+pub struct E2eKey {
+    key: Key<Aes256Gcm>,
+    cipher: Aes256Gcm,
+}
+
+impl fmt::Debug for E2eKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("E2eKey(..)")
+    }
+}
+
+impl E2eKey {
+    /// 生成新的随机密钥
+    pub fn generate() -> Self {
+        Self::from_key(Aes256Gcm::generate_key(OsRng))
+    }
+
+    fn from_key(key: Key<Aes256Gcm>) -> Self {
+        Self {
+            cipher: Aes256Gcm::new(&key),
+            key,
+        }
+    }
+
+    /// 从 32 字节密钥的 base64 编码创建
+    pub fn from_base64(text: &str) -> SdkResult<Self> {
+        let bytes = STANDARD
+            .decode(text.trim())
+            .map_err(|e| e2e_error(format!("key is not valid base64: {e}")))?;
+        if bytes.len() != 32 {
+            return Err(e2e_error(format!(
+                "key must be 32 bytes, got {} bytes",
+                bytes.len()
+            )));
+        }
+        Ok(Self::from_key(*Key::<Aes256Gcm>::from_slice(&bytes)))
+    }
+
+    /// 密钥的 base64 编码，用于分发给其他客户端
+    pub fn to_base64(&self) -> String {
+        STANDARD.encode(self.key)
+    }
+
+    /// 读取密钥文件，文件内容为 [`to_base64`](Self::to_base64) 的结果
+    pub fn load(path: impl AsRef<Path>) -> SdkResult<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| e2e_error(format!("failed to read key file {}: {e}", path.display())))?;
+        Self::from_base64(&text)
+    }
+
+    /// 写入密钥文件，文件已存在时报错；Unix 下权限为 0600
+    pub fn save(&self, path: impl AsRef<Path>) -> SdkResult<()> {
+        let path = path.as_ref();
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        options
+            .open(path)
+            .and_then(|mut file| writeln!(file, "{}", self.to_base64()))
+            .map_err(|e| e2e_error(format!("failed to write key file {}: {e}", path.display())))
+    }
+
+    /// 加密待发送通知的标题与正文：正文替换为密文，标题清空
+    pub fn encrypt(&self, input: &mut NotificationInput) -> SdkResult<()> {
+        if is_e2e(&input.notify) {
+            return Err(e2e_error("notification is already encrypted"));
+        }
+        let sealed = serde_json::to_vec(&Sealed {
+            title: input.title.take(),
+            notify: std::mem::take(&mut input.notify),
+        })?;
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, sealed.as_slice())
+            .map_err(|_| e2e_error("encryption failed"))?;
+        let mut bytes = nonce.to_vec();
+        bytes.extend_from_slice(&ciphertext);
+        input.notify = format!("{E2E_PREFIX}{}", STANDARD.encode(bytes));
+        Ok(())
+    }
+
+    /// 解密通知事件，未加密的通知原样保留；返回是否解密
+    pub fn decrypt(&self, data: &mut NotificationData) -> SdkResult<bool> {
+        self.decrypt_fields(&mut data.title, &mut data.notify)
+    }
+
+    /// 解密列表接口返回的通知，规则同 [`decrypt`](Self::decrypt)
+    pub fn decrypt_item(&self, item: &mut NotifyItem) -> SdkResult<bool> {
+        self.decrypt_fields(&mut item.title, &mut item.notify)
+    }
+
+    fn decrypt_fields(&self, title: &mut String, notify: &mut String) -> SdkResult<bool> {
+        let Some(encoded) = notify.strip_prefix(E2E_PREFIX) else {
+            return Ok(false);
+        };
+        let bytes = STANDARD
+            .decode(encoded)
+            .map_err(|_| e2e_error("invalid ciphertext encoding"))?;
+        if bytes.len() < NONCE_LEN {
+            return Err(e2e_error("ciphertext is too short"));
+        }
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+        let plain = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| e2e_error("decryption failed, wrong key?"))?;
+        let sealed: Sealed = serde_json::from_slice(&plain)?;
+        // 发送方未设置标题时保留服务端补全的标题
+        if let Some(sealed_title) = sealed.title {
+            *title = sealed_title;
+        }
+        *notify = sealed.notify;
+        Ok(true)
+    }
+}
+
+/// 按频道选择密钥，没有频道专用密钥时使用默认密钥
+#[derive(Debug, Clone, Default)]
+pub struct E2eKeyring {
+    default: Option<E2eKey>,
+    channels: HashMap<String, E2eKey>,
+}
+
+impl E2eKeyring {
+    /// 所有频道共用 `key`
+    pub fn new(key: E2eKey) -> Self {
+        Self {
+            default: Some(key),
+            channels: HashMap::new(),
+        }
+    }
+
+    /// 为频道设置专用密钥
+    pub fn channel(mut self, channel: impl Into<String>, key: E2eKey) -> Self {
+        self.channels.insert(channel.into(), key);
+        self
+    }
+
+    /// 频道对应的密钥
+    pub fn key_for(&self, channel: Option<&str>) -> Option<&E2eKey> {
+        channel
+            .and_then(|channel| self.channels.get(channel))
+            .or(self.default.as_ref())
+    }
+
+    /// 用通知频道对应的密钥加密，没有可用密钥时报错
+    pub fn encrypt(&self, input: &mut NotificationInput) -> SdkResult<()> {
+        self.key_for(input.channel.as_deref())
+            .ok_or_else(|| e2e_error("no key for this channel"))?
+            .encrypt(input)
+    }
+
+    /// 用通知频道对应的密钥解密；未加密或没有可用密钥时原样保留，返回是否解密
+    pub fn decrypt(&self, data: &mut NotificationData) -> SdkResult<bool> {
+        match self.key_for(data.channel.as_deref()) {
+            Some(key) => key.decrypt(data),
+            None => Ok(false),
+        }
+    }
+
+    /// 解密列表接口返回的通知，规则同 [`decrypt`](Self::decrypt)
+    pub fn decrypt_item(&self, item: &mut NotifyItem) -> SdkResult<bool> {
+        match self.key_for(item.channel.as_deref()) {
+            Some(key) => key.decrypt_item(item),
+            None => Ok(false),
+        }
+    }
+}
+
+fn e2e_error(message: impl Into<String>) -> SdkError {
+    SdkError::E2eError(message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NotifyId;
+
+    fn data_from(input: &NotificationInput) -> NotificationData {
+        NotificationData {
+            id: Some(NotifyId(1)),
+            notify: input.notify.clone(),
+            title: input.title.clone().unwrap_or_else(|| "Notification".into()),
+            device: "nas".to_string(),
+            priority: 3,
+            tags: Vec::new(),
+            recipient: None,
+            channel: input.channel.clone(),
+            expires_at: None,
+            progress: None,
+            link_previews: Vec::new(),
+            signature: None,
+            correlation_id: None,
+            token_id: None,
+        }
+    }
+
+    #[test]
+    fn test_encrypt_and_decrypt() {
+        let key = E2eKey::generate();
+        let restored = E2eKey::from_base64(&key.to_base64()).unwrap();
+        let mut input = NotificationInput {
+            notify: "db1 is down".to_string(),
+            title: Some("Alert".to_string()),
+            channel: Some("oncall".to_string()),
+            ..Default::default()
+        };
+        key.encrypt(&mut input).unwrap();
+        assert!(is_e2e(&input.notify));
+        assert!(!input.notify.contains("db1"));
+        assert_eq!(input.title, None);
+        assert!(key.encrypt(&mut input).is_err());
+
+        let mut data = data_from(&input);
+        assert!(E2eKey::generate().decrypt(&mut data.clone()).is_err());
+        assert!(restored.decrypt(&mut data).unwrap());
+        assert_eq!(data.title, "Alert");
+        assert_eq!(data.notify, "db1 is down");
+        assert!(!restored.decrypt(&mut data).unwrap());
+    }
+
+    #[test]
+    fn test_keyring_selects_channel_key() {
+        let oncall = E2eKey::generate();
+        let keyring = E2eKeyring::default().channel("oncall", oncall.clone());
+
+        let mut input = NotificationInput {
+            notify: "secret".to_string(),
+            ..Default::default()
+        };
+        assert!(keyring.encrypt(&mut input).is_err());
+
+        input.channel = Some("oncall".to_string());
+        keyring.encrypt(&mut input).unwrap();
+        let mut data = data_from(&input);
+        assert!(keyring.decrypt(&mut data).unwrap());
+        assert_eq!(data.notify, "secret");
+        assert_eq!(data.title, "Notification");
+
+        // 没有密钥的频道原样保留
+        let mut other = data_from(&input);
+        other.channel = None;
+        assert!(!keyring.decrypt(&mut other).unwrap());
+        assert!(is_e2e(&other.notify));
+    }
+}
@@ -1,4 +1,5 @@
-use rutify_core::RutifyError;
+use reqwest::Response;
+use rutify_core::{ApiError, ApiErrorResponse, ErrorCode, RutifyError};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -12,11 +13,111 @@ pub enum SdkError {
     #[error("API returned errors status: {status}")]
     ApiError { status: String },
 
+    /// 服务端返回的错误响应，`request_id` 可用于在服务端日志中定位
+    #[error("Server errors ({status}): {error}")]
+    ServerError {
+        status: u16,
+        error: ApiError,
+        request_id: Option<String>,
+    },
+
+    #[cfg(feature = "ws")]
     #[error("Invalid URL: {0}")]
     InvalidUrl(#[from] url::ParseError),
 
     #[error("Network errors: {0}")]
     NetworkError(String),
+
+    #[error("gRPC errors ({code}): {message}")]
+    GrpcError { code: String, message: String },
+
+    #[error("Invalid notification: {0}")]
+    InvalidNotification(String),
+
+    #[error("End-to-end encryption failed: {0}")]
+    E2eError(String),
+
+    #[error("Notification signing failed: {0}")]
+    SigningError(String),
+
+    #[error("Invalid client configuration: {0}")]
+    ConfigError(String),
+}
+
+impl SdkError {
+    /// 服务端返回的结构化错误
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            SdkError::ServerError { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+
+    /// 服务端返回的错误码
+    pub fn error_code(&self) -> Option<ErrorCode> {
+        self.api_error().map(|error| error.code)
+    }
+
+    /// 按状态码与响应体构造错误；旧版服务端的 `{"errors": "..."}` 与非 JSON 响应按状态码推断错误码
+    fn from_response(status: u16, body: &str) -> Self {
+        if let Ok(response) = serde_json::from_str::<ApiErrorResponse>(body) {
+            return SdkError::ServerError {
+                status,
+                error: response.error,
+                request_id: response.request_id,
+            };
+        }
+        let legacy: Option<serde_json::Value> = serde_json::from_str(body).ok();
+        let message = legacy
+            .as_ref()
+            .and_then(|value| value.get("errors"))
+            .and_then(|errors| errors.as_str())
+            .map(str::to_string)
+            .or_else(|| Some(body.trim().to_string()).filter(|body| !body.is_empty()))
+            .unwrap_or_else(|| format!("HTTP {status}"));
+        SdkError::ServerError {
+            status,
+            error: ApiError::new(ErrorCode::from_status(status), message),
+            request_id: legacy
+                .as_ref()
+                .and_then(|value| value.get("request_id"))
+                .and_then(|id| id.as_str())
+                .map(str::to_string),
+        }
+    }
+
+    /// 复制错误，供共享同一请求结果的调用方使用；HTTP 错误转为同样描述的网络错误
+    pub(crate) fn duplicate(&self) -> Self {
+        match self {
+            SdkError::HttpError(e) => SdkError::NetworkError(e.to_string()),
+            SdkError::JsonError(e) => SdkError::JsonError(serde::de::Error::custom(e)),
+            SdkError::ApiError { status } => SdkError::ApiError {
+                status: status.clone(),
+            },
+            SdkError::ServerError {
+                status,
+                error,
+                request_id,
+            } => SdkError::ServerError {
+                status: *status,
+                error: error.clone(),
+                request_id: request_id.clone(),
+            },
+            #[cfg(feature = "ws")]
+            SdkError::InvalidUrl(e) => SdkError::InvalidUrl(*e),
+            SdkError::NetworkError(message) => SdkError::NetworkError(message.clone()),
+            SdkError::GrpcError { code, message } => SdkError::GrpcError {
+                code: code.clone(),
+                message: message.clone(),
+            },
+            SdkError::InvalidNotification(message) => {
+                SdkError::InvalidNotification(message.clone())
+            }
+            SdkError::E2eError(message) => SdkError::E2eError(message.clone()),
+            SdkError::SigningError(message) => SdkError::SigningError(message.clone()),
+            SdkError::ConfigError(message) => SdkError::ConfigError(message.clone()),
+        }
+    }
 }
 
 impl From<SdkError> for RutifyError {
@@ -32,14 +133,42 @@ impl From<SdkError> for RutifyError {
                 status,
                 message: "API errors".to_string(),
             },
+            SdkError::ServerError { error, .. } if error.code == ErrorCode::Unauthorized => {
+                RutifyError::Auth {
+                    message: error.message,
+                }
+            }
+            SdkError::ServerError { error, .. } => RutifyError::Api {
+                status: error.code.to_string(),
+                message: error.message,
+            },
+            #[cfg(feature = "ws")]
             SdkError::InvalidUrl(e) => RutifyError::Config {
                 message: e.to_string(),
             },
             SdkError::NetworkError(msg) => RutifyError::Network { message: msg },
+            SdkError::GrpcError { code, message } => RutifyError::Api {
+                status: code,
+                message,
+            },
+            SdkError::InvalidNotification(message) => RutifyError::Config { message },
+            SdkError::E2eError(message) => RutifyError::Config { message },
+            SdkError::SigningError(message) => RutifyError::Config { message },
+            SdkError::ConfigError(message) => RutifyError::Config { message },
         }
     }
 }
 
+/// 非 2xx 的响应转为 [`SdkError::ServerError`]
+pub(crate) async fn check_status(response: Response) -> Result<Response, SdkError> {
+    let status = response.status();
+    if !(status.is_client_error() || status.is_server_error()) {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(SdkError::from_response(status.as_u16(), &body))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,6 +229,7 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "ws")]
     #[test]
     fn test_sdk_error_url_to_rutify_error() {
         let sdk_error = SdkError::InvalidUrl(url::ParseError::EmptyHost);
@@ -113,4 +243,34 @@ mod tests {
             _ => panic!("Expected Config errors"),
         }
     }
+
+    #[test]
+    fn test_server_error_from_response() {
+        let error = SdkError::from_response(
+            422,
+            r#"{"status":"error","error":{"code":"validation","message":"validation failed","details":{"fields":[]}},"request_id":"r1"}"#,
+        );
+        assert_eq!(error.error_code(), Some(ErrorCode::Validation));
+        assert!(matches!(
+            &error,
+            SdkError::ServerError { status: 422, request_id: Some(id), .. } if id == "r1"
+        ));
+
+        // 旧版服务端与代理返回的错误
+        let legacy = SdkError::from_response(404, r#"{"errors":"notify not found"}"#);
+        assert_eq!(legacy.error_code(), Some(ErrorCode::NotFound));
+        assert_eq!(legacy.api_error().unwrap().message, "notify not found");
+        let proxy = SdkError::from_response(502, "");
+        assert_eq!(proxy.error_code(), Some(ErrorCode::Upstream));
+        assert_eq!(proxy.api_error().unwrap().message, "HTTP 502");
+
+        let unauthorized = SdkError::from_response(
+            401,
+            r#"{"status":"error","error":{"code":"unauthorized","message":"Invalid token"}}"#,
+        );
+        assert!(matches!(
+            RutifyError::from(unauthorized),
+            RutifyError::Auth { message } if message == "Invalid token"
+        ));
+    }
 }
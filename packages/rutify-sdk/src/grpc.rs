@@ -0,0 +1,337 @@
+use chrono::{DateTime, Utc};
+use futures_util::{Stream, StreamExt};
+use tonic::metadata::MetadataValue;
+use tonic::service::Interceptor;
+use tonic::service::interceptor::InterceptedService;
+use tonic::transport::{Channel, Endpoint};
+use tonic::{Request, Status};
+
+use crate::error::SdkError;
+use crate::{
+    EventKind, NotificationData, NotificationInput, NotifyEvent, NotifyId, NotifyItem, Progress,
+    ProgressStatus, SdkResult, TokenId,
+};
+
+/// 由 `proto/rutify.proto` 生成的消息与客户端
+pub mod proto {
+    tonic::include_proto!("rutify.v1");
+}
+
+use proto::rutify_client::RutifyClient as ProtoClient;
+
+/// 为每个请求附加通知 Token
+#[derive(Clone)]
+pub struct BearerInterceptor {
+    authorization: Option<MetadataValue<tonic::metadata::Ascii>>,
+}
+
+impl Interceptor for BearerInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        if let Some(authorization) = &self.authorization {
+            request
+                .metadata_mut()
+                .insert("authorization", authorization.clone());
+        }
+        Ok(request)
+    }
+}
+
+/// Rutify gRPC 客户端，认证方式与 WebSocket 相同（通知 Token）
+#[derive(Clone)]
+pub struct RutifyGrpcClient {
+    inner: ProtoClient<InterceptedService<Channel, BearerInterceptor>>,
+}
+
+impl RutifyGrpcClient {
+    /// 连接 gRPC 服务，例如 `http://127.0.0.1:50051`
+    pub async fn connect(endpoint: &str, token: Option<&str>) -> SdkResult<Self> {
+        let authorization = token
+            .map(|token| format!("Bearer {token}").parse())
+            .transpose()
+            .map_err(|_| SdkError::NetworkError("Invalid token".to_string()))?;
+        let channel = Endpoint::from_shared(endpoint.to_string())
+            .map_err(|e| SdkError::NetworkError(e.to_string()))?
+            .connect()
+            .await
+            .map_err(|e| SdkError::NetworkError(e.to_string()))?;
+
+        Ok(Self {
+            inner: ProtoClient::with_interceptor(channel, BearerInterceptor { authorization }),
+        })
+    }
+
+    /// 发送通知，返回入库后的通知 ID；被服务端丢弃时返回 `None`
+    ///
+    /// 未设置关联 ID 时自动生成。
+    pub async fn send_notification(&mut self, input: &NotificationInput) -> SdkResult<Option<i32>> {
+        let mut input = input.clone();
+        input.ensure_correlation_id();
+        let response = self
+            .inner
+            .send_notification(proto::SendNotificationRequest::from(input))
+            .await?
+            .into_inner();
+        Ok(if response.dropped { None } else { response.id })
+    }
+
+    /// 列出通知，`limit` 为 0 时不限制条数
+    pub async fn list_notifications(&mut self, limit: u32) -> SdkResult<Vec<NotifyItem>> {
+        let response = self
+            .inner
+            .list_notifications(proto::ListNotificationsRequest {
+                limit,
+                include_expired: false,
+            })
+            .await?
+            .into_inner();
+        Ok(response.items.into_iter().map(NotifyItem::from).collect())
+    }
+
+    /// 订阅通知事件流
+    pub async fn stream_events(
+        &mut self,
+    ) -> SdkResult<impl Stream<Item = SdkResult<NotifyEvent>> + use<>> {
+        let stream = self
+            .inner
+            .stream_events(proto::StreamEventsRequest {})
+            .await?
+            .into_inner();
+        Ok(stream.map(|event| event.map(NotifyEvent::from).map_err(SdkError::from)))
+    }
+}
+
+impl From<Status> for SdkError {
+    fn from(status: Status) -> Self {
+        SdkError::GrpcError {
+            code: format!("{:?}", status.code()),
+            message: status.message().to_string(),
+        }
+    }
+}
+
+fn to_timestamp(time: DateTime<Utc>) -> prost_types::Timestamp {
+    prost_types::Timestamp {
+        seconds: time.timestamp(),
+        nanos: time.timestamp_subsec_nanos() as i32,
+    }
+}
+
+fn from_timestamp(timestamp: Option<prost_types::Timestamp>) -> DateTime<Utc> {
+    timestamp
+        .and_then(|timestamp| {
+            DateTime::from_timestamp(timestamp.seconds, timestamp.nanos.max(0) as u32)
+        })
+        .unwrap_or_default()
+}
+
+fn to_priority(priority: u32) -> u8 {
+    priority.clamp(1, crate::MAX_PRIORITY as u32) as u8
+}
+
+impl From<Progress> for proto::Progress {
+    fn from(progress: Progress) -> Self {
+        Self {
+            current: progress.current,
+            total: progress.total,
+            status: progress.status.as_str().to_string(),
+        }
+    }
+}
+
+impl From<proto::Progress> for Progress {
+    fn from(progress: proto::Progress) -> Self {
+        Self {
+            current: progress.current,
+            total: progress.total,
+            status: ProgressStatus::parse(&progress.status).unwrap_or_default(),
+        }
+    }
+}
+
+impl From<NotificationInput> for proto::SendNotificationRequest {
+    fn from(input: NotificationInput) -> Self {
+        Self {
+            notify: input.notify,
+            title: input.title,
+            device: input.device,
+            priority: input.priority.map(u32::from),
+            tags: input.tags,
+            channel: input.channel,
+            ttl_secs: input.ttl_secs,
+            progress: input.progress.map(Into::into),
+            correlation_id: input.correlation_id,
+        }
+    }
+}
+
+impl From<proto::SendNotificationRequest> for NotificationInput {
+    fn from(request: proto::SendNotificationRequest) -> Self {
+        Self {
+            notify: request.notify,
+            title: request.title,
+            device: request.device,
+            priority: request.priority.map(to_priority),
+            tags: request.tags,
+            channel: request.channel,
+            ttl_secs: request.ttl_secs,
+            progress: request.progress.map(Into::into),
+            signature: None,
+            correlation_id: request.correlation_id,
+        }
+    }
+}
+
+impl From<NotifyItem> for proto::NotifyItem {
+    fn from(item: NotifyItem) -> Self {
+        Self {
+            id: item.id.get(),
+            title: item.title,
+            notify: item.notify,
+            device: item.device,
+            priority: u32::from(item.priority),
+            tags: item.tags,
+            channel: item.channel,
+            received_at: Some(to_timestamp(item.received_at)),
+            expires_at: item.expires_at.map(to_timestamp),
+            pinned: item.pinned,
+            progress: item.progress.map(Into::into),
+            correlation_id: item.correlation_id,
+        }
+    }
+}
+
+impl From<proto::NotifyItem> for NotifyItem {
+    fn from(item: proto::NotifyItem) -> Self {
+        Self {
+            id: NotifyId(item.id),
+            title: item.title,
+            notify: item.notify,
+            device: item.device,
+            priority: to_priority(item.priority),
+            tags: item.tags,
+            channel: item.channel,
+            received_at: from_timestamp(item.received_at),
+            expires_at: item
+                .expires_at
+                .map(|timestamp| from_timestamp(Some(timestamp))),
+            pinned: item.pinned,
+            starred: false,
+            progress: item.progress.map(Into::into),
+            device_source: None,
+            link_previews: Vec::new(),
+            signature: None,
+            correlation_id: item.correlation_id,
+        }
+    }
+}
+
+impl From<NotificationData> for proto::NotificationData {
+    fn from(data: NotificationData) -> Self {
+        Self {
+            id: data.id.map(NotifyId::get),
+            notify: data.notify,
+            title: data.title,
+            device: data.device,
+            priority: u32::from(data.priority),
+            tags: data.tags,
+            recipient: data.recipient,
+            channel: data.channel,
+            expires_at: data.expires_at.map(to_timestamp),
+            progress: data.progress.map(Into::into),
+            correlation_id: data.correlation_id,
+            token_id: data.token_id.map(TokenId::get),
+        }
+    }
+}
+
+impl From<proto::NotificationData> for NotificationData {
+    fn from(data: proto::NotificationData) -> Self {
+        Self {
+            id: data.id.map(NotifyId),
+            notify: data.notify,
+            title: data.title,
+            device: data.device,
+            priority: to_priority(data.priority),
+            tags: data.tags,
+            recipient: data.recipient,
+            channel: data.channel,
+            expires_at: data
+                .expires_at
+                .map(|timestamp| from_timestamp(Some(timestamp))),
+            progress: data.progress.map(Into::into),
+            link_previews: Vec::new(),
+            signature: None,
+            correlation_id: data.correlation_id,
+            token_id: data.token_id.map(TokenId),
+        }
+    }
+}
+
+impl From<NotifyEvent> for proto::NotifyEvent {
+    fn from(event: NotifyEvent) -> Self {
+        Self {
+            event: event.event.as_str().to_string(),
+            data: Some(event.data.into()),
+            timestamp: Some(to_timestamp(event.timestamp)),
+        }
+    }
+}
+
+impl From<proto::NotifyEvent> for NotifyEvent {
+    fn from(event: proto::NotifyEvent) -> Self {
+        Self {
+            event: EventKind::parse(&event.event).unwrap_or(EventKind::Unknown),
+            data: event.data.unwrap_or_default().into(),
+            timestamp: from_timestamp(event.timestamp),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notify_event_roundtrip() {
+        let event = NotifyEvent {
+            event: EventKind::Created,
+            data: NotificationData {
+                id: Some(NotifyId(7)),
+                notify: "disk full".to_string(),
+                title: "alert".to_string(),
+                device: "web1".to_string(),
+                priority: 5,
+                tags: vec!["disk".to_string()],
+                recipient: None,
+                channel: None,
+                expires_at: None,
+                progress: None,
+                link_previews: Vec::new(),
+                signature: None,
+                correlation_id: Some("c1".to_string()),
+                token_id: None,
+            },
+            timestamp: Utc::now(),
+        };
+
+        let decoded = NotifyEvent::from(proto::NotifyEvent::from(event.clone()));
+        assert_eq!(decoded.event, event.event);
+        assert_eq!(decoded.data.id, Some(NotifyId(7)));
+        assert_eq!(decoded.data.tags, event.data.tags);
+        assert_eq!(decoded.data.correlation_id.as_deref(), Some("c1"));
+        assert_eq!(decoded.timestamp, event.timestamp);
+    }
+
+    #[test]
+    fn test_priority_is_clamped() {
+        let request = proto::SendNotificationRequest {
+            notify: "x".to_string(),
+            priority: Some(42),
+            ..Default::default()
+        };
+        assert_eq!(NotificationInput::from(request).priority, Some(5));
+
+        let item = NotifyItem::from(proto::NotifyItem::default());
+        assert_eq!(item.priority, 1);
+    }
+}
@@ -1,13 +1,41 @@
 pub mod auth;
+mod cache;
 pub mod client;
+#[cfg(feature = "ws")]
+mod connector;
+#[cfg(feature = "e2e")]
+pub mod e2e;
 pub mod error;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod middleware;
+mod negotiation;
+pub mod notification;
+pub mod pagination;
+pub mod progress;
+#[cfg(feature = "signing")]
+pub mod signing;
+mod single_flight;
+#[cfg(feature = "ws")]
+pub mod subscription;
 
-pub use auth::{
-    CreateTokenRequest, CreateTokenResponse, LoginRequest, LoginResponse, RegisterRequest,
-    TokenInfo,
-};
+pub use auth::{LoginRequest, LoginResponse, RegisterRequest};
 pub use client::RutifyClient;
+#[cfg(feature = "e2e")]
+pub use e2e::{E2eKey, E2eKeyring};
 pub use error::SdkError;
+#[cfg(feature = "grpc")]
+pub use grpc::RutifyGrpcClient;
+pub use middleware::{Middleware, RequestRecord};
+pub use notification::{Notification, NotificationBuilder};
+pub use pagination::NotifyPage;
+#[cfg(feature = "stream")]
+pub use pagination::NotifyPages;
+pub use progress::ProgressNotification;
 pub use rutify_core::*;
+#[cfg(feature = "signing")]
+pub use signing::NotifySigner;
+#[cfg(feature = "ws")]
+pub use subscription::{Acker, Subscription};
 
 pub type SdkResult<T> = Result<T, SdkError>;
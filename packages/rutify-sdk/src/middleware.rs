@@ -0,0 +1,39 @@
+//! 请求拦截器：为 SDK 发出的每个 HTTP 请求附加请求头、记录日志或统计耗时
+//!
+//! 拦截器按注册顺序执行，只作用于 HTTP 接口；WebSocket 连接不经过拦截器。
+
+pub use reqwest::RequestBuilder;
+use reqwest::{Method, StatusCode, Url};
+use std::time::Duration;
+
+/// 请求拦截器
+///
+/// 只需修改请求时可直接传入闭包 `Fn(RequestBuilder) -> RequestBuilder`。
+pub trait Middleware: Send + Sync {
+    /// 发送前调整请求，例如附加追踪 ID
+    fn on_request(&self, request: RequestBuilder) -> RequestBuilder {
+        request
+    }
+
+    /// 收到响应或请求失败后调用
+    fn on_response(&self, _record: &RequestRecord) {}
+}
+
+impl<F> Middleware for F
+where
+    F: Fn(RequestBuilder) -> RequestBuilder + Send + Sync,
+{
+    fn on_request(&self, request: RequestBuilder) -> RequestBuilder {
+        self(request)
+    }
+}
+
+/// 一次请求的结果摘要
+#[derive(Debug, Clone)]
+pub struct RequestRecord {
+    pub method: Method,
+    pub url: Url,
+    /// 响应状态码，连接失败或超时时为 `None`
+    pub status: Option<StatusCode>,
+    pub elapsed: Duration,
+}
@@ -0,0 +1,101 @@
+//! 与服务端协商版本：首次需要时获取 `GET /api/version` 并缓存，据此决定是否使用较新的接口
+
+use crate::SdkResult;
+use rutify_core::ServerVersion;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tokio::sync::OnceCell;
+use tracing::warn;
+
+/// 本 SDK 的版本，与服务端主版本号比较
+const SDK_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[derive(Default)]
+pub(crate) struct Negotiation {
+    version: OnceCell<ServerVersion>,
+    channels_warned: AtomicBool,
+}
+
+impl Negotiation {
+    /// 已缓存时直接返回，否则执行 `fetch`；获取失败时不缓存，下次重试
+    pub(crate) async fn get_or_fetch<F, Fut>(&self, fetch: F) -> SdkResult<&ServerVersion>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = SdkResult<ServerVersion>>,
+    {
+        self.version
+            .get_or_try_init(|| async {
+                let version = fetch().await?;
+                if let Some(warning) = incompatibility(&version) {
+                    warn!("{}", warning);
+                }
+                Ok(version)
+            })
+            .await
+    }
+
+    /// 旧服务端不识别 `channel` 字段，只提示一次
+    pub(crate) fn warn_channels_unsupported(&self) {
+        if !self.channels_warned.swap(true, Ordering::Relaxed) {
+            warn!(
+                "The server does not support notification channels; \
+                 notifications are delivered without channel routing"
+            );
+        }
+    }
+}
+
+/// 服务端主版本号与 SDK 不同时返回警告信息
+fn incompatibility(server: &ServerVersion) -> Option<String> {
+    (!server.is_compatible_with(SDK_VERSION)).then(|| {
+        format!(
+            "Server version {} may be incompatible with rutify-sdk {}",
+            server.version, SDK_VERSION
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::SdkError;
+    use rutify_core::{API_LEVEL_PAGINATION, ServerVersion};
+
+    fn server(version: &str, api_level: u32) -> ServerVersion {
+        ServerVersion {
+            version: version.to_string(),
+            api_level,
+        }
+    }
+
+    #[test]
+    fn test_incompatible_major_version() {
+        assert!(incompatibility(&server(SDK_VERSION, 2)).is_none());
+        assert!(incompatibility(&ServerVersion::legacy()).is_none());
+        assert!(incompatibility(&server("99.0.0", 2)).is_some());
+
+        assert!(server("0.1.0", 2).supports(API_LEVEL_PAGINATION));
+        assert!(!ServerVersion::legacy().supports(API_LEVEL_PAGINATION));
+    }
+
+    #[tokio::test]
+    async fn test_version_is_cached_after_success() {
+        let negotiation = Negotiation::default();
+        let failed = negotiation
+            .get_or_fetch(|| async { Err(SdkError::NetworkError("down".to_string())) })
+            .await;
+        assert!(failed.is_err());
+
+        let version = negotiation
+            .get_or_fetch(|| async { Ok(server("0.1.0", 2)) })
+            .await
+            .unwrap();
+        assert_eq!(version.api_level, 2);
+
+        let cached = negotiation
+            .get_or_fetch(|| async { Ok(ServerVersion::legacy()) })
+            .await
+            .unwrap();
+        assert_eq!(cached.api_level, 2);
+    }
+}
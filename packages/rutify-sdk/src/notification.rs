@@ -0,0 +1,213 @@
+use crate::error::SdkError;
+use crate::{MAX_CORRELATION_ID_LEN, MAX_PRIORITY, NotificationInput, RutifyClient, SdkResult};
+use std::time::Duration;
+
+/// 通知构建入口
+///
+/// ```no_run
+/// # async fn demo(client: &rutify_sdk::RutifyClient) -> rutify_sdk::SdkResult<()> {
+/// use rutify_sdk::Notification;
+///
+/// Notification::builder()
+///     .title("Backup")
+///     .body("nightly backup finished")
+///     .device("nas")
+///     .priority(4)
+///     .tag("backup")
+///     .send(client)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Notification;
+
+impl Notification {
+    pub fn builder() -> NotificationBuilder {
+        NotificationBuilder::default()
+    }
+}
+
+/// 通知构建器，发送前在本地校验字段
+#[derive(Debug, Clone, Default)]
+pub struct NotificationBuilder {
+    input: NotificationInput,
+}
+
+impl NotificationBuilder {
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.input.title = Some(title.into());
+        self
+    }
+
+    /// 通知正文（对应 `notify` 字段）
+    pub fn body(mut self, body: impl Into<String>) -> Self {
+        self.input.notify = body.into();
+        self
+    }
+
+    pub fn device(mut self, device: impl Into<String>) -> Self {
+        self.input.device = Some(device.into());
+        self
+    }
+
+    /// 优先级，取值 1-5
+    pub fn priority(mut self, priority: u8) -> Self {
+        self.input.priority = Some(priority);
+        self
+    }
+
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.input.tags.push(tag.into());
+        self
+    }
+
+    pub fn tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.input.tags.extend(tags.into_iter().map(Into::into));
+        self
+    }
+
+    /// 投递频道，例如 `oncall`
+    pub fn channel(mut self, channel: impl Into<String>) -> Self {
+        self.input.channel = Some(channel.into());
+        self
+    }
+
+    /// 有效期，过期后服务端不再在默认列表中返回该通知
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.input.ttl_secs = Some(ttl.as_secs());
+        self
+    }
+
+    /// 关联 ID，出现在该通知的事件中；不设置时发送时自动生成
+    pub fn correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.input.correlation_id = Some(correlation_id.into());
+        self
+    }
+
+    /// 校验并生成 [`NotificationInput`]
+    pub fn build(self) -> SdkResult<NotificationInput> {
+        let input = self.input;
+
+        if input.notify.trim().is_empty() {
+            return Err(invalid("body must not be empty"));
+        }
+        if input.title.as_deref().is_some_and(|t| t.trim().is_empty()) {
+            return Err(invalid("title must not be empty"));
+        }
+        if input.device.as_deref().is_some_and(|d| d.trim().is_empty()) {
+            return Err(invalid("device must not be empty"));
+        }
+        if let Some(priority) = input.priority
+            && !(1..=MAX_PRIORITY).contains(&priority)
+        {
+            return Err(invalid(&format!(
+                "priority must be between 1 and {MAX_PRIORITY}, got {priority}"
+            )));
+        }
+        // 查询参数中标签以逗号分隔，标签本身不能包含逗号
+        if let Some(tag) = input
+            .tags
+            .iter()
+            .find(|tag| tag.trim().is_empty() || tag.contains(','))
+        {
+            return Err(invalid(&format!("invalid tag: {tag:?}")));
+        }
+        if input.ttl_secs == Some(0) {
+            return Err(invalid("ttl must be at least one second"));
+        }
+        if let Some(id) = &input.correlation_id
+            && (id.is_empty() || id.len() > MAX_CORRELATION_ID_LEN)
+        {
+            return Err(invalid(&format!(
+                "correlation id must be 1 to {MAX_CORRELATION_ID_LEN} bytes"
+            )));
+        }
+
+        Ok(input)
+    }
+
+    /// 校验通过后通过 HTTP 发送
+    pub async fn send(self, client: &RutifyClient) -> SdkResult<()> {
+        let input = self.build()?;
+        client.send_notification(&input).await
+    }
+}
+
+fn invalid(message: &str) -> SdkError {
+    SdkError::InvalidNotification(message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_fields() {
+        let input = Notification::builder()
+            .title("Backup")
+            .body("done")
+            .device("nas")
+            .priority(4)
+            .tag("backup")
+            .tags(["nightly"])
+            .channel("oncall")
+            .ttl(Duration::from_secs(3600))
+            .correlation_id("job-42")
+            .build()
+            .unwrap();
+
+        assert_eq!(input.notify, "done");
+        assert_eq!(input.title.as_deref(), Some("Backup"));
+        assert_eq!(input.device.as_deref(), Some("nas"));
+        assert_eq!(input.priority, Some(4));
+        assert_eq!(input.tags, vec!["backup", "nightly"]);
+        assert_eq!(input.channel.as_deref(), Some("oncall"));
+        assert_eq!(input.ttl_secs, Some(3600));
+        assert_eq!(input.correlation_id.as_deref(), Some("job-42"));
+    }
+
+    #[test]
+    fn test_builder_validation() {
+        assert!(Notification::builder().build().is_err());
+        assert!(
+            Notification::builder()
+                .body("x")
+                .priority(0)
+                .build()
+                .is_err()
+        );
+        assert!(
+            Notification::builder()
+                .body("x")
+                .priority(6)
+                .build()
+                .is_err()
+        );
+        assert!(
+            Notification::builder()
+                .body("x")
+                .tag("a,b")
+                .build()
+                .is_err()
+        );
+        assert!(
+            Notification::builder()
+                .body("x")
+                .title(" ")
+                .build()
+                .is_err()
+        );
+        assert!(
+            Notification::builder()
+                .body("x")
+                .ttl(Duration::from_millis(500))
+                .build()
+                .is_err()
+        );
+        assert!(Notification::builder().body("x").build().is_ok());
+    }
+}
@@ -0,0 +1,177 @@
+#[cfg(feature = "stream")]
+use crate::SdkResult;
+#[cfg(feature = "stream")]
+use futures_util::future::BoxFuture;
+#[cfg(feature = "stream")]
+use futures_util::{Stream, StreamExt};
+use rutify_core::NotifyItem;
+#[cfg(feature = "stream")]
+use std::pin::Pin;
+#[cfg(feature = "stream")]
+use std::task::{Context, Poll};
+
+/// 每页默认请求的通知数
+pub const DEFAULT_PAGE_SIZE: u64 = 100;
+
+/// 通知列表的一页
+#[derive(Debug, Clone)]
+pub struct NotifyPage {
+    pub items: Vec<NotifyItem>,
+    /// 符合条件的通知总数
+    pub total: u64,
+    /// 下一页的游标，为空表示已到最后一页
+    pub next_cursor: Option<String>,
+}
+
+#[cfg(feature = "stream")]
+type FetchPage =
+    Box<dyn FnMut(Option<String>, u64) -> BoxFuture<'static, SdkResult<NotifyPage>> + Send>;
+
+/// 按页遍历通知列表，由 [`RutifyClient::notifies_pages`](crate::RutifyClient::notifies_pages) 创建
+///
+/// 自动跟随服务端返回的游标，最后一页或请求出错后流结束：
+///
+/// ```no_run
+/// # async fn demo(client: &rutify_sdk::RutifyClient) -> rutify_sdk::SdkResult<()> {
+/// use futures_util::StreamExt;
+///
+/// let mut pages = client.notifies_pages().page_size(50);
+/// while let Some(page) = pages.next().await {
+///     for item in page?.items {
+///         println!("{}: {}", item.title, item.notify);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "stream")]
+pub struct NotifyPages {
+    fetch: FetchPage,
+    page_size: u64,
+    cursor: Option<String>,
+    pending: Option<BoxFuture<'static, SdkResult<NotifyPage>>>,
+    done: bool,
+}
+
+#[cfg(feature = "stream")]
+impl NotifyPages {
+    pub(crate) fn new(fetch: FetchPage) -> Self {
+        Self {
+            fetch,
+            page_size: DEFAULT_PAGE_SIZE,
+            cursor: None,
+            pending: None,
+            done: false,
+        }
+    }
+
+    /// 每页请求的通知数，服务端最多返回 500 条
+    pub fn page_size(mut self, page_size: u64) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// 依次读取各页，凑满 `limit` 条或没有更多通知时返回
+    pub async fn collect_all(mut self, limit: usize) -> SdkResult<Vec<NotifyItem>> {
+        let mut items = Vec::new();
+        while items.len() < limit {
+            let Some(page) = self.next().await else {
+                break;
+            };
+            items.extend(page?.items);
+        }
+        items.truncate(limit);
+        Ok(items)
+    }
+}
+
+#[cfg(feature = "stream")]
+impl Stream for NotifyPages {
+    type Item = SdkResult<NotifyPage>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if this.done {
+            return Poll::Ready(None);
+        }
+        let pending = match &mut this.pending {
+            Some(pending) => pending,
+            None => this
+                .pending
+                .insert((this.fetch)(this.cursor.take(), this.page_size)),
+        };
+        let result = match pending.as_mut().poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+        this.pending = None;
+        match &result {
+            Ok(page) => {
+                this.cursor = page.next_cursor.clone();
+                this.done = this.cursor.is_none();
+            }
+            Err(_) => this.done = true,
+        }
+        Poll::Ready(Some(result))
+    }
+}
+
+#[cfg(all(test, feature = "stream"))]
+mod tests {
+    use super::*;
+    use crate::NotifyId;
+    use crate::error::SdkError;
+    use futures_util::FutureExt;
+
+    fn item(id: i32) -> NotifyItem {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "title": "title",
+            "notify": "body",
+            "device": "nas",
+            "received_at": "2026-01-01T00:00:00Z"
+        }))
+        .unwrap()
+    }
+
+    /// 共 `total` 条通知，游标为下一页第一条的下标
+    fn pages(total: i32) -> NotifyPages {
+        NotifyPages::new(Box::new(move |cursor, page_size| {
+            let start: i32 = cursor.map_or(0, |cursor| cursor.parse().unwrap());
+            let end = (start + page_size as i32).min(total);
+            let page = NotifyPage {
+                items: (start..end).map(item).collect(),
+                total: total as u64,
+                next_cursor: (end < total).then(|| end.to_string()),
+            };
+            async move { Ok(page) }.boxed()
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_pages_follow_cursor() {
+        let all: Vec<_> = pages(7).page_size(3).collect().await;
+        let sizes: Vec<_> = all
+            .into_iter()
+            .map(|page| page.unwrap().items.len())
+            .collect();
+        assert_eq!(sizes, [3, 3, 1]);
+
+        let items = pages(7).page_size(3).collect_all(5).await.unwrap();
+        assert_eq!(
+            items.iter().map(|item| item.id).collect::<Vec<_>>(),
+            [0, 1, 2, 3, 4].map(NotifyId)
+        );
+        assert_eq!(pages(7).collect_all(100).await.unwrap().len(), 7);
+
+        let failing = NotifyPages::new(Box::new(|_, _| {
+            async {
+                Err(SdkError::ApiError {
+                    status: "error".to_string(),
+                })
+            }
+            .boxed()
+        }));
+        assert!(failing.collect_all(10).await.is_err());
+    }
+}
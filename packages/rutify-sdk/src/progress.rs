@@ -0,0 +1,111 @@
+use crate::{NotifyId, Progress, ProgressStatus, RutifyClient, SdkResult, UpdateNotifyRequest};
+
+/// 进度通知句柄，由 [`RutifyClient::start_progress`] 创建
+///
+/// ```no_run
+/// # async fn demo(client: &rutify_sdk::RutifyClient) -> rutify_sdk::SdkResult<()> {
+/// use rutify_sdk::Notification;
+///
+/// let input = Notification::builder()
+///     .title("Backup")
+///     .body("backing up /srv")
+///     .build()?;
+/// let mut progress = client.start_progress(input, 100).await?;
+/// progress.update(45).await?;
+/// progress.update(80).await?;
+/// progress.finish().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ProgressNotification {
+    client: RutifyClient,
+    id: NotifyId,
+    progress: Progress,
+}
+
+impl ProgressNotification {
+    pub(crate) fn new(client: RutifyClient, id: NotifyId, progress: Progress) -> Self {
+        Self {
+            client,
+            id,
+            progress,
+        }
+    }
+
+    /// 通知 ID
+    pub fn id(&self) -> NotifyId {
+        self.id
+    }
+
+    /// 最近一次发送的进度
+    pub fn progress(&self) -> Progress {
+        self.progress
+    }
+
+    /// 更新当前进度
+    pub async fn update(&mut self, current: u64) -> SdkResult<()> {
+        self.progress.current = current;
+        self.send(None).await
+    }
+
+    /// 更新当前进度并替换正文
+    pub async fn update_with_message(
+        &mut self,
+        current: u64,
+        message: impl Into<String>,
+    ) -> SdkResult<()> {
+        self.progress.current = current;
+        self.send(Some(message.into())).await
+    }
+
+    /// 标记完成，总量已知时进度补满
+    pub async fn finish(mut self) -> SdkResult<()> {
+        self.progress.status = ProgressStatus::Done;
+        self.progress.current = self.progress.current.max(self.progress.total);
+        self.send(None).await
+    }
+
+    /// 标记失败并以失败原因替换正文
+    pub async fn fail(mut self, message: impl Into<String>) -> SdkResult<()> {
+        self.progress.status = ProgressStatus::Failed;
+        self.send(Some(message.into())).await
+    }
+
+    fn request(&self, notify: Option<String>) -> UpdateNotifyRequest {
+        UpdateNotifyRequest {
+            notify,
+            progress: Some(self.progress),
+            ..Default::default()
+        }
+    }
+
+    async fn send(&self, notify: Option<String>) -> SdkResult<()> {
+        self.client
+            .update_notification(self.id, &self.request(notify))
+            .await
+            .map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_request() {
+        let client = RutifyClient::new("http://localhost:3000");
+        let mut handle = ProgressNotification::new(client, NotifyId(7), Progress::new(0, 200));
+        handle.progress.current = 90;
+
+        let request = handle.request(Some("transcoding".to_string()));
+        assert_eq!(request.notify.as_deref(), Some("transcoding"));
+        assert_eq!(request.progress, Some(Progress::new(90, 200)));
+        assert!(request.title.is_none());
+        assert_eq!(handle.progress().to_string(), "45%");
+
+        handle.progress.status = ProgressStatus::Done;
+        assert_eq!(handle.progress().fraction(), Some(1.0));
+        assert_eq!(handle.progress().to_string(), "done");
+    }
+}
@@ -0,0 +1,184 @@
+//! 发送方签名：用 ed25519 私钥对标题与正文签名，接收方验证后确认通知来自持有私钥的发送方
+//!
+//! 将 [`NotifySigner::public_key`] 登记到通知 Token（创建时的 `signing_key`）后，服务端只接受
+//! 该公钥签名的通知。接收方可用 [`verified_signer`] 自行验证，并与预先保存的公钥比对，
+//! 即使中转的服务端被攻破也无法伪造告警。
+//!
+//! ```no_run
+//! # async fn demo(client: &rutify_sdk::RutifyClient) -> rutify_sdk::SdkResult<()> {
+//! use rutify_sdk::{Notification, NotifySigner};
+//!
+//! let signer = NotifySigner::load("ci.signing-key")?;
+//! let mut input = Notification::builder().title("Deploy").body("v2 is live").build()?;
+//! signer.sign(&mut input)?;
+//! client.send_notification(&input).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::SdkError;
+use crate::{NotificationData, NotificationInput, NotifyItem, NotifySignature, SdkResult};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use rand_core::OsRng;
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+
+/// 发送方的 ed25519 私钥
+#[derive(Clone)]
+pub struct NotifySigner {
+    key: SigningKey,
+}
+
+impl fmt::Debug for NotifySigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("NotifySigner")
+            .field(&self.public_key())
+            .finish()
+    }
+}
+
+impl NotifySigner {
+    /// 生成新的随机私钥
+    pub fn generate() -> Self {
+        Self {
+            key: SigningKey::generate(&mut OsRng),
+        }
+    }
+
+    /// 从 32 字节私钥的 base64 编码创建
+    pub fn from_base64(text: &str) -> SdkResult<Self> {
+        let bytes: [u8; 32] = STANDARD
+            .decode(text.trim())
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| signing_error("signing key must be 32 bytes of base64"))?;
+        Ok(Self {
+            key: SigningKey::from_bytes(&bytes),
+        })
+    }
+
+    /// 私钥的 base64 编码
+    pub fn to_base64(&self) -> String {
+        STANDARD.encode(self.key.to_bytes())
+    }
+
+    /// 公钥的 base64 编码，创建通知 Token 时登记
+    pub fn public_key(&self) -> String {
+        STANDARD.encode(self.key.verifying_key().as_bytes())
+    }
+
+    /// 读取私钥文件，文件内容为 [`to_base64`](Self::to_base64) 的结果
+    pub fn load(path: impl AsRef<Path>) -> SdkResult<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            signing_error(format!("failed to read key file {}: {e}", path.display()))
+        })?;
+        Self::from_base64(&text)
+    }
+
+    /// 写入私钥文件，文件已存在时报错；Unix 下权限为 0600
+    pub fn save(&self, path: impl AsRef<Path>) -> SdkResult<()> {
+        let path = path.as_ref();
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        options
+            .open(path)
+            .and_then(|mut file| writeln!(file, "{}", self.to_base64()))
+            .map_err(|e| signing_error(format!("failed to write key file {}: {e}", path.display())))
+    }
+
+    /// 对标题与正文签名；签名的通知必须带标题，之后不能再修改标题与正文
+    ///
+    /// 端到端加密的通知在接收方解密后无法再验证签名，两者不宜同时使用。
+    pub fn sign(&self, input: &mut NotificationInput) -> SdkResult<()> {
+        let title = input
+            .title
+            .as_deref()
+            .ok_or_else(|| signing_error("signed notifications must include a title"))?;
+        let signature = self
+            .key
+            .sign(&NotifySignature::message(title, &input.notify));
+        input.signature = Some(NotifySignature {
+            public_key: self.public_key(),
+            signature: STANDARD.encode(signature.to_bytes()),
+        });
+        Ok(())
+    }
+}
+
+/// 验证签名是否由 `signature.public_key` 对应的私钥对标题与正文生成
+pub fn verify(title: &str, notify: &str, signature: &NotifySignature) -> bool {
+    let Some(key) = STANDARD
+        .decode(&signature.public_key)
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+    else {
+        return false;
+    };
+    let Some(bytes) = STANDARD
+        .decode(&signature.signature)
+        .ok()
+        .and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+    else {
+        return false;
+    };
+    key.verify_strict(
+        &NotifySignature::message(title, notify),
+        &Signature::from_bytes(&bytes),
+    )
+    .is_ok()
+}
+
+/// 签名有效时返回签名者公钥，调用方可与信任的公钥比对；未签名或签名无效时返回 `None`
+pub fn verified_signer(data: &NotificationData) -> Option<&str> {
+    let signature = data.signature.as_ref()?;
+    verify(&data.title, &data.notify, signature).then_some(signature.public_key.as_str())
+}
+
+/// 列表接口返回的通知，规则同 [`verified_signer`]
+pub fn verified_item_signer(item: &NotifyItem) -> Option<&str> {
+    let signature = item.signature.as_ref()?;
+    verify(&item.title, &item.notify, signature).then_some(signature.public_key.as_str())
+}
+
+fn signing_error(message: impl Into<String>) -> SdkError {
+    SdkError::SigningError(message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let signer = NotifySigner::generate();
+        let restored = NotifySigner::from_base64(&signer.to_base64()).unwrap();
+        assert_eq!(restored.public_key(), signer.public_key());
+
+        let mut input = NotificationInput {
+            notify: "v2 is live".to_string(),
+            ..Default::default()
+        };
+        assert!(signer.sign(&mut input).is_err());
+
+        input.title = Some("Deploy".to_string());
+        signer.sign(&mut input).unwrap();
+        let signature = input.signature.clone().unwrap();
+        assert!(verify("Deploy", "v2 is live", &signature));
+        assert!(!verify("Deploy", "v2 rolled back", &signature));
+        assert!(!verify("Deplo", "yv2 is live", &signature));
+
+        // 换成其他公钥后验证失败
+        let forged = NotifySignature {
+            public_key: NotifySigner::generate().public_key(),
+            ..signature
+        };
+        assert!(!verify("Deploy", "v2 is live", &forged));
+    }
+}
@@ -0,0 +1,125 @@
+//! 合并并发的相同请求：同一时刻只有一个请求发往服务端，其余调用等待并共享它的结果
+
+use crate::SdkResult;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+
+type Shared = Arc<SdkResult<Value>>;
+
+#[derive(Default)]
+pub(crate) struct SingleFlight {
+    inflight: Mutex<HashMap<String, broadcast::Sender<Shared>>>,
+}
+
+impl SingleFlight {
+    /// 相同 `key` 的请求正在进行时等待它的结果，否则执行 `request`
+    pub(crate) async fn run<F, Fut>(&self, key: String, request: F) -> SdkResult<Value>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = SdkResult<Value>>,
+    {
+        let waiting = {
+            let mut inflight = self.inflight.lock().unwrap();
+            match inflight.get(&key) {
+                Some(sender) => Some(sender.subscribe()),
+                None => {
+                    inflight.insert(key.clone(), broadcast::channel(1).0);
+                    None
+                }
+            }
+        };
+        if let Some(mut receiver) = waiting {
+            return match receiver.recv().await {
+                Ok(shared) => duplicate(&shared),
+                // 发起请求的调用被取消，自行请求
+                Err(_) => request().await,
+            };
+        }
+
+        let mut leader = Leader {
+            flights: self,
+            key: Some(key),
+        };
+        let result = request().await;
+        if let Some(sender) = leader.finish() {
+            let _ = sender.send(Arc::new(duplicate(&result)));
+        }
+        result
+    }
+}
+
+fn duplicate(result: &SdkResult<Value>) -> SdkResult<Value> {
+    match result {
+        Ok(value) => Ok(value.clone()),
+        Err(e) => Err(e.duplicate()),
+    }
+}
+
+/// 发起请求的调用；结束或被取消时移除进行中的记录
+struct Leader<'a> {
+    flights: &'a SingleFlight,
+    key: Option<String>,
+}
+
+impl Leader<'_> {
+    fn finish(&mut self) -> Option<broadcast::Sender<Shared>> {
+        let key = self.key.take()?;
+        let sender = self.flights.inflight.lock().unwrap().remove(&key)?;
+        (sender.receiver_count() > 0).then_some(sender)
+    }
+}
+
+impl Drop for Leader<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.flights.inflight.lock().unwrap().remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::SdkError;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_concurrent_requests_share_one_call() {
+        let flights = SingleFlight::default();
+        let calls = AtomicUsize::new(0);
+        let request = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Ok(serde_json::json!({ "total_count": 3 }))
+        };
+
+        let (a, b) = tokio::join!(
+            flights.run("stats".to_string(), request),
+            flights.run("stats".to_string(), request)
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(a.unwrap(), b.unwrap());
+
+        let failed = || async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            Err(SdkError::ApiError {
+                status: "error".to_string(),
+            })
+        };
+        let (a, b) = tokio::join!(
+            flights.run("stats".to_string(), failed),
+            flights.run("stats".to_string(), failed)
+        );
+        assert!(matches!(a, Err(SdkError::ApiError { .. })));
+        assert!(matches!(b, Err(SdkError::ApiError { .. })));
+
+        // 结束后不再合并
+        flights.run("stats".to_string(), request).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(flights.inflight.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,241 @@
+use crate::SdkResult;
+#[cfg(feature = "e2e")]
+use crate::e2e::E2eKeyring;
+use crate::error::SdkError;
+use futures_util::Stream;
+use rutify_core::{NotificationData, NotifyEvent, NotifyId, WebSocketMessage, WsClientMessage};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+
+/// 通知事件流，由 [`RutifyClient::subscribe`](crate::RutifyClient::subscribe) 创建
+///
+/// 连接关闭或出错时流结束；可叠加设备、频道与优先级过滤，
+/// 之后照常使用 `StreamExt` 的各类适配器：
+///
+/// ```no_run
+/// # async fn demo(client: &rutify_sdk::RutifyClient) -> rutify_sdk::SdkResult<()> {
+/// use futures_util::StreamExt;
+///
+/// let mut events = client.subscribe().await?.device("nas").min_priority(4);
+/// while let Some(event) = events.next().await {
+///     println!("{}: {}", event.data.title, event.data.notify);
+///     events.ack(&event)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Subscription {
+    receiver: UnboundedReceiver<WebSocketMessage>,
+    filter: EventFilter,
+    #[cfg(feature = "e2e")]
+    keyring: Option<E2eKeyring>,
+    acker: Option<Acker>,
+    closed: bool,
+}
+
+/// 向服务端确认已处理通知，可克隆后移入其他任务
+#[derive(Debug, Clone)]
+pub struct Acker {
+    sender: UnboundedSender<WsClientMessage>,
+}
+
+impl Acker {
+    pub(crate) fn new(sender: UnboundedSender<WsClientMessage>) -> Self {
+        Self { sender }
+    }
+
+    /// 确认已处理通知 `id`，连接已关闭时返回错误
+    pub fn ack(&self, id: NotifyId) -> SdkResult<()> {
+        self.sender
+            .send(WsClientMessage::Ack { id })
+            .map_err(|_| SdkError::NetworkError("websocket connection closed".to_string()))
+    }
+}
+
+/// 事件过滤条件，同一类条件多次设置时满足其一即可
+#[derive(Debug, Clone, Default)]
+struct EventFilter {
+    devices: Vec<String>,
+    channels: Vec<String>,
+    min_priority: Option<u8>,
+}
+
+impl EventFilter {
+    fn matches(&self, data: &NotificationData) -> bool {
+        (self.devices.is_empty() || self.devices.contains(&data.device))
+            && (self.channels.is_empty()
+                || data
+                    .channel
+                    .as_ref()
+                    .is_some_and(|channel| self.channels.contains(channel)))
+            && self
+                .min_priority
+                .is_none_or(|min_priority| data.priority >= min_priority)
+    }
+}
+
+impl Subscription {
+    pub(crate) fn new(receiver: UnboundedReceiver<WebSocketMessage>) -> Self {
+        Self {
+            receiver,
+            filter: EventFilter::default(),
+            #[cfg(feature = "e2e")]
+            keyring: None,
+            acker: None,
+            closed: false,
+        }
+    }
+
+    pub(crate) fn with_acker(mut self, acker: Acker) -> Self {
+        self.acker = Some(acker);
+        self
+    }
+
+    /// 确认已处理事件中的通知；不需要确认的事件（如更新、删除）直接忽略
+    pub fn ack(&self, event: &NotifyEvent) -> SdkResult<()> {
+        match (&self.acker, event.ack_id()) {
+            (Some(acker), Some(id)) => acker.ack(id),
+            _ => Ok(()),
+        }
+    }
+
+    /// 只保留指定设备的事件
+    pub fn device(mut self, device: impl Into<String>) -> Self {
+        self.filter.devices.push(device.into());
+        self
+    }
+
+    /// 只保留指定频道（如 `oncall`）的事件
+    pub fn channel(mut self, channel: impl Into<String>) -> Self {
+        self.filter.channels.push(channel.into());
+        self
+    }
+
+    /// 只保留优先级不低于 `priority` 的事件
+    pub fn min_priority(mut self, priority: u8) -> Self {
+        self.filter.min_priority = Some(priority);
+        self
+    }
+
+    /// 用 `keyring` 解密端到端加密的事件；无法解密的事件保留密文
+    #[cfg(feature = "e2e")]
+    pub fn decrypt(mut self, keyring: E2eKeyring) -> Self {
+        self.keyring = Some(keyring);
+        self
+    }
+
+    #[cfg(feature = "e2e")]
+    fn decrypted(&self, mut event: Box<NotifyEvent>) -> Box<NotifyEvent> {
+        if let Some(keyring) = &self.keyring {
+            let _ = keyring.decrypt(&mut event.data);
+        }
+        event
+    }
+}
+
+impl Stream for Subscription {
+    type Item = NotifyEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.closed {
+            return Poll::Ready(None);
+        }
+        loop {
+            match self.receiver.poll_recv(cx) {
+                Poll::Ready(Some(WebSocketMessage::Event(event))) => {
+                    #[cfg(feature = "e2e")]
+                    let event = self.decrypted(event);
+                    if self.filter.matches(&event.data) {
+                        return Poll::Ready(Some(*event));
+                    }
+                }
+                Poll::Ready(Some(WebSocketMessage::Close | WebSocketMessage::Error { .. }))
+                | Poll::Ready(None) => {
+                    self.closed = true;
+                    return Poll::Ready(None);
+                }
+                Poll::Ready(Some(_)) => {}
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+    use rutify_core::EventKind;
+
+    fn message(device: &str, priority: u8, channel: Option<&str>) -> WebSocketMessage {
+        WebSocketMessage::Event(Box::new(NotifyEvent {
+            event: EventKind::Created,
+            data: NotificationData {
+                id: None,
+                notify: "body".to_string(),
+                title: "title".to_string(),
+                device: device.to_string(),
+                priority,
+                tags: Vec::new(),
+                recipient: None,
+                channel: channel.map(str::to_string),
+                expires_at: None,
+                progress: None,
+                link_previews: Vec::new(),
+                signature: None,
+                correlation_id: None,
+                token_id: None,
+            },
+            timestamp: Default::default(),
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_subscription_filters_and_ends_on_close() {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        tx.send(message("nas", 5, None)).unwrap();
+        tx.send(message("web1", 5, Some("oncall"))).unwrap();
+        tx.send(WebSocketMessage::Text("hello".to_string()))
+            .unwrap();
+        tx.send(message("nas", 2, Some("oncall"))).unwrap();
+        tx.send(message("nas", 4, Some("oncall"))).unwrap();
+        tx.send(WebSocketMessage::Close).unwrap();
+        tx.send(message("nas", 5, Some("oncall"))).unwrap();
+
+        let events: Vec<_> = Subscription::new(rx)
+            .device("nas")
+            .channel("oncall")
+            .min_priority(3)
+            .collect()
+            .await;
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].data.priority, 4);
+    }
+
+    #[test]
+    fn test_ack_only_new_notifications() {
+        let (ack_tx, mut ack_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (_tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let subscription = Subscription::new(rx).with_acker(Acker::new(ack_tx));
+
+        let WebSocketMessage::Event(mut event) = message("nas", 3, None) else {
+            unreachable!()
+        };
+        event.data.id = Some(NotifyId(7));
+        subscription.ack(&event).unwrap();
+        event.event = EventKind::Updated;
+        subscription.ack(&event).unwrap();
+
+        assert_eq!(
+            ack_rx.try_recv().unwrap(),
+            WsClientMessage::Ack { id: NotifyId(7) }
+        );
+        assert!(ack_rx.try_recv().is_err());
+
+        drop(ack_rx);
+        event.event = EventKind::Created;
+        assert!(subscription.ack(&event).is_err());
+    }
+}
@@ -0,0 +1,238 @@
+//! 同步发送 Rutify 通知的最小客户端
+//!
+//! 只依赖 `rutify-core` 的类型与 ureq，不需要 tokio 等异步运行时，适合资源受限的采集代理与构建容器。
+//! 订阅、查询与管理接口请使用 `rutify-sdk`。
+//!
+//! ```no_run
+//! use rutify_send::{NotificationInput, Sender};
+//!
+//! let sender = Sender::new("https://rutify.example.com").with_token("notify-token");
+//! sender.send_text("backup finished")?;
+//!
+//! let input = NotificationInput {
+//!     notify: "disk usage at 95%".to_string(),
+//!     title: Some("nas".to_string()),
+//!     priority: Some(5),
+//!     ..Default::default()
+//! };
+//! let id = sender.send(&input)?;
+//! println!("sent {id:?}");
+//! # Ok::<(), rutify_send::SendError>(())
+//! ```
+
+pub use rutify_core::{
+    ApiError, ErrorCode, NotificationInput, NotifyId, NotifySignature, Progress, ProgressStatus,
+};
+
+use rutify_core::ApiErrorResponse;
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SendError {
+    #[error("HTTP request failed: {0}")]
+    Http(#[from] ureq::Error),
+
+    #[error("JSON serialization/deserialization failed: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// 服务端返回的错误响应
+    #[error("Server error ({status}): {error}")]
+    Server {
+        status: u16,
+        error: ApiError,
+        request_id: Option<String>,
+    },
+}
+
+impl SendError {
+    /// 按状态码与响应体构造错误；非 JSON 响应按状态码推断错误码
+    fn from_response(status: u16, body: &str) -> Self {
+        if let Ok(response) = serde_json::from_str::<ApiErrorResponse>(body) {
+            return SendError::Server {
+                status,
+                error: response.error,
+                request_id: response.request_id,
+            };
+        }
+        let message = Some(body.trim().to_string())
+            .filter(|body| !body.is_empty())
+            .unwrap_or_else(|| format!("HTTP {status}"));
+        SendError::Server {
+            status,
+            error: ApiError::new(ErrorCode::from_status(status), message),
+            request_id: None,
+        }
+    }
+
+    /// 服务端返回的错误码
+    pub fn error_code(&self) -> Option<ErrorCode> {
+        match self {
+            SendError::Server { error, .. } => Some(error.code),
+            _ => None,
+        }
+    }
+}
+
+pub type SendResult<T> = Result<T, SendError>;
+
+/// `POST /notify` 的返回内容
+#[derive(serde::Deserialize)]
+struct SendResponse {
+    #[serde(default)]
+    id: Option<NotifyId>,
+}
+
+/// 同步的通知发送方，克隆后共用连接池
+#[derive(Clone)]
+pub struct Sender {
+    agent: ureq::Agent,
+    base_url: String,
+    token: Option<String>,
+}
+
+impl Sender {
+    /// 默认超时 30 秒
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            agent: agent(Duration::from_secs(30)),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token: None,
+        }
+    }
+
+    /// 通知 Token，通过 `Authorization: Bearer` 头发送
+    pub fn with_token(mut self, token: &str) -> Self {
+        self.token = Some(token.to_string());
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.agent = agent(timeout);
+        self
+    }
+
+    /// 发送通知，返回服务端分配的通知 ID
+    ///
+    /// 命中静默规则的通知照常入库并返回 ID；被插件丢弃或服务端入库失败时为 `None`。
+    /// 超出大小限制且策略为 `reject` 时返回 413 错误，而不是 `None`。
+    pub fn send(&self, input: &NotificationInput) -> SendResult<Option<NotifyId>> {
+        let mut input = input.clone();
+        input.ensure_correlation_id();
+        let body = serde_json::to_vec(&input)?;
+
+        let mut request = self
+            .agent
+            .post(format!("{}/notify", self.base_url))
+            .content_type("application/json");
+        if let Some(token) = &self.token {
+            request = request.header("Authorization", format!("Bearer {token}"));
+        }
+
+        let mut response = request.send(&body[..])?;
+        let status = response.status().as_u16();
+        let text = response.body_mut().read_to_string()?;
+        if !(200..300).contains(&status) {
+            return Err(SendError::from_response(status, &text));
+        }
+        Ok(serde_json::from_str::<SendResponse>(&text)?.id)
+    }
+
+    /// 只发送正文
+    pub fn send_text(&self, notify: &str) -> SendResult<Option<NotifyId>> {
+        self.send(&NotificationInput {
+            notify: notify.to_string(),
+            ..Default::default()
+        })
+    }
+}
+
+/// 4xx 与 5xx 响应交给 [`SendError::from_response`] 解析
+fn agent(timeout: Duration) -> ureq::Agent {
+    ureq::Agent::config_builder()
+        .timeout_global(Some(timeout))
+        .http_status_as_error(false)
+        .build()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// 只处理一个请求的 HTTP 服务端，返回收到的请求头与请求体
+    fn serve_once(status: &str, body: &str) -> (String, thread::JoinHandle<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {status}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                if line == "\r\n" {
+                    break;
+                }
+                head.push_str(&line);
+            }
+            let mut payload = vec![0; length];
+            reader.read_exact(&mut payload).unwrap();
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            (head, String::from_utf8(payload).unwrap())
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn test_send() {
+        let (url, server) = serve_once("200 OK", r#"{"status":"ok","id":42}"#);
+        let sender = Sender::new(&url).with_token("abc");
+        let input = NotificationInput {
+            notify: "backup finished".to_string(),
+            tags: vec!["backup".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(sender.send(&input).unwrap(), Some(NotifyId(42)));
+
+        let (head, payload) = server.join().unwrap();
+        assert!(head.starts_with("POST /notify HTTP/1.1"));
+        assert!(head.to_lowercase().contains("authorization: bearer abc"));
+        let sent: NotificationInput = serde_json::from_str(&payload).unwrap();
+        assert_eq!(sent.notify, "backup finished");
+        assert_eq!(sent.tags, ["backup"]);
+        assert!(sent.correlation_id.is_some());
+    }
+
+    #[test]
+    fn test_send_dropped_and_errors() {
+        let (url, server) = serve_once("200 OK", r#"{"status":"ok","dropped":true}"#);
+        assert_eq!(Sender::new(&url).send_text("quiet").unwrap(), None);
+        server.join().unwrap();
+
+        let (url, server) = serve_once(
+            "401 Unauthorized",
+            r#"{"status":"error","error":{"code":"unauthorized","message":"invalid token"}}"#,
+        );
+        let err = Sender::new(&url).send_text("hello").unwrap_err();
+        assert_eq!(err.error_code(), Some(ErrorCode::Unauthorized));
+        server.join().unwrap();
+
+        let (url, server) = serve_once("503 Service Unavailable", "maintenance");
+        let err = Sender::new(&url).send_text("hello").unwrap_err();
+        assert_eq!(err.error_code(), Some(ErrorCode::Unavailable));
+        assert!(err.to_string().contains("maintenance"));
+        server.join().unwrap();
+    }
+}
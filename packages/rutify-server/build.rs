@@ -3,4 +3,26 @@ fn main() {
 
     // println!("cargo:rerun-if-changed=build.rs");
     slint_build::compile("slint/app.slint").expect("Slint UI build failed");
+
+    #[cfg(feature = "grpc")]
+    {
+        use std::path::PathBuf;
+
+        println!("cargo:rerun-if-changed=../../proto/rutify.proto");
+
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc not found");
+        let well_known =
+            protoc_bin_vendored::include_path().expect("vendored protoc includes not found");
+        unsafe { std::env::set_var("PROTOC", protoc) };
+
+        // 只生成服务端桩代码，消息类型复用 rutify-sdk
+        tonic_prost_build::configure()
+            .build_client(false)
+            .extern_path(".rutify.v1", "::rutify_sdk::grpc::proto")
+            .compile_protos(
+                &[PathBuf::from("../../proto/rutify.proto")],
+                &[PathBuf::from("../../proto"), well_known],
+            )
+            .expect("failed to compile rutify.proto");
+    }
 }
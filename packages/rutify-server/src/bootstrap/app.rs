@@ -1,51 +1,53 @@
+use crate::bootstrap::config::{AppConfig, CorsOrigins};
+use crate::bootstrap::protection::{self, Protection};
+use crate::bootstrap::request_id::{self, REQUEST_ID_HEADER};
+use crate::bootstrap::{monitoring, read_only, request_limits};
+use crate::error;
 use crate::routes;
+use crate::services::auth::user::user_auth_middleware;
+use crate::services::dedup::DUPLICATE_HEADER;
 use crate::state::AppState;
-use anyhow::Result;
+use axum::Router;
+use axum::extract::DefaultBodyLimit;
+use axum::http::HeaderName;
+use axum::middleware;
 use axum::routing::get;
-use common_http_server_rs::{
-    AppBuilder, AppConfig, GlobalMonitoringConfig, MiddlewareOrchestrator,
-    PerformanceMonitoringConfig, ProtectionStackBuilder, ddos_presets, rate_limit_presets,
-    size_limit_presets,
-};
 use std::sync::Arc;
+use tokio::sync::watch;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+use tower_http::trace::{DefaultOnResponse, TraceLayer};
+use tracing::Level;
 
-pub(crate) fn app_builder(state: Arc<AppState>, app_config: AppConfig) -> Result<AppBuilder> {
-    let ddos_config = ddos_presets::lenient();
-    let rate_limit_config = rate_limit_presets::lenient();
-    let size_limit_config = size_limit_presets::api();
+pub(crate) fn app(state: Arc<AppState>, app_config: AppConfig) -> Router {
+    let mut monitor_router = routes::monitor::router(state.monitoring.clone());
+    if state.auth_policy.requires_user_auth() {
+        monitor_router = monitor_router.layer(middleware::from_fn_with_state(
+            Arc::clone(&state),
+            user_auth_middleware,
+        ));
+    }
+    let protection = Arc::new(Protection::new(
+        state.config_reloader.protection(),
+        state.monitoring.clone(),
+    ));
 
-    let protection_stack = ProtectionStackBuilder::new()
-        .with_ddos(ddos_config.clone())
-        .with_rate_limit(rate_limit_config.clone())
-        .with_size_limit_content_length_only(size_limit_config.clone())
-        .build()?;
-
-    let monitor_router = routes::monitor::router(state.monitoring.clone());
-
-    let orchestrator = MiddlewareOrchestrator::new()
-        .with_app_runtime_layers(true)
-        .with_monitoring_config(
-            state.monitoring.clone(),
-            GlobalMonitoringConfig::new().with_performance_config(
-                PerformanceMonitoringConfig::new()
-                    .exclude_request_count_path_prefix("/monitor")
-                    .exclude_request_count_path_prefix("/health"),
-            ),
-        )
-        .with_protection_stack(protection_stack);
-
-    Ok(AppBuilder::new(app_config)
-        .validate_ddos_config(ddos_config)
-        .validate_rate_limit_config(rate_limit_config)
-        .validate_size_limit_config(size_limit_config)
+    let mut router = Router::new()
         .route("/", get(routes::index::handler))
+        .route(
+            "/health",
+            get(routes::health::handler).with_state(Arc::clone(&state)),
+        )
         .route(
             "/ws",
             get(routes::notify::ws_handler).with_state(Arc::clone(&state)),
         )
         .nest(
             "/notify",
-            routes::notify::router().with_state(Arc::clone(&state)),
+            routes::notify::router(Arc::clone(&state)).with_state(Arc::clone(&state)),
+        )
+        .nest(
+            "/hooks",
+            routes::hooks::router().with_state(Arc::clone(&state)),
         )
         .nest(
             "/api",
@@ -55,6 +57,47 @@ pub(crate) fn app_builder(state: Arc<AppState>, app_config: AppConfig) -> Result
             "/auth",
             routes::auth::router(Arc::clone(&state)).with_state(Arc::clone(&state)),
         )
-        .nest("/monitor", monitor_router)
-        .with_orchestrator(orchestrator))
+        .nest("/monitor", monitor_router);
+    if state.read_only {
+        router = router.layer(middleware::from_fn(read_only::middleware));
+    }
+
+    // 先添加的层在内层：请求 ID 包住限流、请求限制与错误改写，错误响应都能带上它；
+    // 统计与跨域在最外层，被拒绝的请求同样计数，预检请求不消耗限流令牌
+    router
+        .layer(DefaultBodyLimit::max(
+            app_config.request_limits.max_body_bytes,
+        ))
+        .layer(middleware::from_fn_with_state(
+            app_config.request_limits,
+            request_limits::middleware,
+        ))
+        .layer(middleware::from_fn(error::middleware))
+        .layer(TraceLayer::new_for_http().on_response(DefaultOnResponse::new().level(Level::INFO)))
+        .layer(middleware::from_fn_with_state(
+            protection,
+            protection::middleware,
+        ))
+        .layer(middleware::from_fn(request_id::middleware))
+        .layer(middleware::from_fn_with_state(
+            state.monitoring.clone(),
+            monitoring::middleware,
+        ))
+        .layer(cors_layer(state.config_reloader.cors_origins()))
+}
+
+/// 每个请求读取当前允许的来源，重新加载后立即生效
+fn cors_layer(origins: watch::Receiver<CorsOrigins>) -> CorsLayer {
+    let allow_origin = AllowOrigin::predicate(move |origin, _| match &*origins.borrow() {
+        CorsOrigins::Any => true,
+        CorsOrigins::List(origins) => origins.contains(origin),
+    });
+    CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods(Any)
+        .allow_headers(Any)
+        .expose_headers([
+            HeaderName::from_static(REQUEST_ID_HEADER),
+            HeaderName::from_static(DUPLICATE_HEADER),
+        ])
 }
@@ -0,0 +1,72 @@
+use crate::bootstrap::source::ConfigSource;
+use crate::db::encryption;
+use rutify_core::Capabilities;
+
+/// 启动时根据配置得出已启用的可选子系统；各项配置已在启动时校验，这里只看是否设置
+pub(crate) fn from_source(
+    source: &ConfigSource,
+    read_only: bool,
+    cluster: bool,
+    link_preview: bool,
+) -> Capabilities {
+    let mut capabilities = detect(|key| source.var(key).ok(), read_only);
+    capabilities.encryption_at_rest = encryption::enabled();
+    capabilities.cluster = cluster;
+    capabilities.link_preview = link_preview;
+    capabilities
+}
+
+fn detect(env: impl Fn(&str) -> Option<String>, read_only: bool) -> Capabilities {
+    let set = |key: &str| env(key).is_some_and(|value| !value.trim().is_empty());
+    // 只读实例不启动入库桥接
+    let bridge = |key: &str| !read_only && set(key);
+
+    Capabilities {
+        attachments: true,
+        email: bridge("RUTIFY_SMTP_ADDR"),
+        telegram: set("RUTIFY_TELEGRAM_BOT_TOKEN"),
+        mqtt: cfg!(feature = "mqtt") && bridge("RUTIFY_MQTT_URL"),
+        syslog: bridge("RUTIFY_SYSLOG_ADDR"),
+        grpc: cfg!(feature = "grpc") && set("RUTIFY_GRPC_ADDR"),
+        e2e: true,
+        read_only,
+        unifiedpush: !read_only,
+        web_push: bridge("RUTIFY_VAPID_KEY_FILE"),
+        ..Capabilities::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_capabilities() {
+        let env = |key: &str| match key {
+            "RUTIFY_SMTP_ADDR" => Some("0.0.0.0:2525".to_string()),
+            "RUTIFY_TELEGRAM_BOT_TOKEN" => Some("123:abc".to_string()),
+            "RUTIFY_SYSLOG_ADDR" => Some(" ".to_string()),
+            "RUTIFY_VAPID_KEY_FILE" => Some("vapid.key".to_string()),
+            _ => None,
+        };
+
+        let capabilities = detect(env, false);
+        assert!(capabilities.email && capabilities.telegram);
+        assert!(!capabilities.syslog && !capabilities.mqtt);
+        assert_eq!(
+            capabilities.enabled(),
+            [
+                "attachments",
+                "email",
+                "telegram",
+                "e2e",
+                "unifiedpush",
+                "web_push"
+            ]
+        );
+
+        let read_only = detect(env, true);
+        assert!(!read_only.email && read_only.telegram && read_only.read_only);
+        assert!(!read_only.unifiedpush && !read_only.web_push);
+    }
+}
@@ -1,27 +1,309 @@
-use anyhow::{Context, Result};
-use common_http_server_rs::{AppConfig, CorsConfig, LogFormat, LoggingConfig, ServerConfig};
-use std::net::SocketAddr;
+use crate::bootstrap::diagnostics::redact_url;
+use crate::bootstrap::request_limits::RequestLimits;
+use crate::bootstrap::source::ConfigSource;
+use crate::services::auth::user::DEFAULT_JWT_SECRET;
+use crate::services::hooks::Provider;
+use anyhow::{Context, Result, bail};
+use axum::http::HeaderValue;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::PathBuf;
 
 const DEFAULT_ADDR: &str = "0.0.0.0:3000";
+const DEFAULT_DB_URL: &str = "sqlite://rutify.db?mode=rwc";
+const DEFAULT_PUBLIC_URL: &str = "http://localhost:3000";
+const DEFAULT_SMTP_RELAY: &str = "127.0.0.1:25";
+const DEFAULT_SMTP_FROM: &str = "rutify@localhost";
 
-pub(crate) fn server_config_from_env() -> Result<ServerConfig> {
-    let addr_text = std::env::var("RUTIFY_ADDR").unwrap_or_else(|_| DEFAULT_ADDR.to_string());
-    let addr: SocketAddr = addr_text
-        .parse()
-        .with_context(|| format!("invalid RUTIFY_ADDR: {addr_text}"))?;
+/// 单个监听地址
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ListenAddr {
+    Tcp(SocketAddr),
+    /// Unix 域套接字，写作 `unix:/run/rutify.sock`
+    Unix(PathBuf),
+}
+
+impl fmt::Display for ListenAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "{addr}"),
+            Self::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+/// 服务端的核心配置，启动时加载一次并保存在 [`AppState`](crate::state::AppState) 中
+#[derive(Clone)]
+pub(crate) struct ServerConfig {
+    /// 同时监听的全部地址，至少一个
+    pub(crate) addrs: Vec<ListenAddr>,
+    /// 数据库地址，来自 `RUTIFY_DB_URL`
+    pub(crate) database_url: String,
+    /// JWT 与注册题目的签名密钥，来自 `RUTIFY_JWT_SECRET`
+    pub(crate) jwt_secret: Option<String>,
+    /// 验证链接等使用的外部地址，来自 `RUTIFY_PUBLIC_URL`
+    pub(crate) public_url: String,
+    /// 登录是否要求邮箱已验证，来自 `RUTIFY_REQUIRE_EMAIL_VERIFICATION`（默认关闭）
+    pub(crate) require_email_verification: bool,
+    /// 邮件转发使用的 SMTP 中继，来自 `RUTIFY_SMTP_RELAY`
+    pub(crate) smtp_relay: String,
+    /// 转发邮件的发件地址，来自 `RUTIFY_SMTP_FROM`
+    pub(crate) smtp_from: String,
+    /// Telegram 转发使用的 Bot Token，来自 `RUTIFY_TELEGRAM_BOT_TOKEN`
+    pub(crate) telegram_bot_token: Option<String>,
+    /// 各 Webhook 来源的共享密钥，来自 `RUTIFY_HOOK_SECRET_<PROVIDER>`
+    pub(crate) hook_secrets: Vec<(Provider, String)>,
+}
+
+impl fmt::Debug for ServerConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secret = |value: &Option<String>| if value.is_some() { "set" } else { "unset" };
+        f.debug_struct("ServerConfig")
+            .field("addrs", &self.addrs)
+            .field("database_url", &redact_url(&self.database_url))
+            .field("jwt_secret", &secret(&self.jwt_secret))
+            .field("public_url", &self.public_url)
+            .field(
+                "require_email_verification",
+                &self.require_email_verification,
+            )
+            .field("smtp_relay", &self.smtp_relay)
+            .field("smtp_from", &self.smtp_from)
+            .field("telegram_bot_token", &secret(&self.telegram_bot_token))
+            .field(
+                "hook_secrets",
+                &self
+                    .hook_secrets
+                    .iter()
+                    .map(|(provider, _)| provider.name())
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl ServerConfig {
+    pub(crate) fn from_source(source: &ConfigSource) -> Result<Self> {
+        Self::from_lookup(|key| source.var(key).ok())
+    }
 
-    Ok(ServerConfig::new(addr.port()).with_host(addr.ip().to_string()))
+    fn from_lookup(var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let addr_text = var("RUTIFY_ADDR").unwrap_or_else(|| DEFAULT_ADDR.to_string());
+        let addrs = parse_listen_addrs(&addr_text)
+            .with_context(|| format!("invalid RUTIFY_ADDR: {addr_text}"))?;
+        let set = |key: &str| var(key).filter(|value| !value.trim().is_empty());
+
+        Ok(Self {
+            addrs,
+            database_url: var("RUTIFY_DB_URL").unwrap_or_else(|| DEFAULT_DB_URL.to_string()),
+            jwt_secret: var("RUTIFY_JWT_SECRET"),
+            public_url: var("RUTIFY_PUBLIC_URL").unwrap_or_else(|| DEFAULT_PUBLIC_URL.to_string()),
+            require_email_verification: var("RUTIFY_REQUIRE_EMAIL_VERIFICATION").is_some_and(
+                |value| {
+                    matches!(
+                        value.trim().to_ascii_lowercase().as_str(),
+                        "1" | "true" | "yes"
+                    )
+                },
+            ),
+            smtp_relay: var("RUTIFY_SMTP_RELAY").unwrap_or_else(|| DEFAULT_SMTP_RELAY.to_string()),
+            smtp_from: var("RUTIFY_SMTP_FROM").unwrap_or_else(|| DEFAULT_SMTP_FROM.to_string()),
+            telegram_bot_token: set("RUTIFY_TELEGRAM_BOT_TOKEN"),
+            hook_secrets: Provider::ALL
+                .into_iter()
+                .filter_map(|provider| {
+                    var(&provider.secret_key())
+                        .filter(|secret| !secret.is_empty())
+                        .map(|secret| (provider, secret))
+                })
+                .collect(),
+        })
+    }
+
+    /// 全部使用默认值的配置
+    #[cfg(test)]
+    pub(crate) fn for_tests() -> Self {
+        Self::from_lookup(|_| None).expect("default configuration")
+    }
+
+    /// JWT 签名密钥，未设置时使用内置密钥（启动时会给出警告）
+    pub(crate) fn jwt_key(&self) -> &[u8] {
+        self.jwt_secret
+            .as_deref()
+            .unwrap_or(DEFAULT_JWT_SECRET)
+            .as_bytes()
+    }
+
+    /// Webhook 来源配置的共享密钥，未配置时改为要求通知 Token
+    pub(crate) fn hook_secret(&self, provider: Provider) -> Option<&str> {
+        self.hook_secrets
+            .iter()
+            .find(|(configured, _)| *configured == provider)
+            .map(|(_, secret)| secret.as_str())
+    }
+
+    /// 本机访问服务端时使用的 TCP 地址：取第一个 TCP 监听地址，监听所有地址时改为回环地址
+    pub(crate) fn local_tcp_addr(&self) -> Option<SocketAddr> {
+        self.addrs.iter().find_map(|addr| match addr {
+            ListenAddr::Tcp(addr) if addr.ip().is_unspecified() => {
+                let loopback = match addr.ip() {
+                    IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::LOCALHOST),
+                    IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::LOCALHOST),
+                };
+                Some(SocketAddr::new(loopback, addr.port()))
+            }
+            ListenAddr::Tcp(addr) => Some(*addr),
+            ListenAddr::Unix(_) => None,
+        })
+    }
+}
+
+/// `RUTIFY_ADDR` 为逗号分隔的监听地址列表，如 `0.0.0.0:3000,[::]:3000,unix:/run/rutify.sock`
+fn parse_listen_addrs(text: &str) -> Result<Vec<ListenAddr>> {
+    let mut addrs = Vec::new();
+    for item in text
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+    {
+        let addr = match item.strip_prefix("unix:") {
+            Some("") => bail!("empty unix socket path"),
+            Some(path) => ListenAddr::Unix(PathBuf::from(path)),
+            None => ListenAddr::Tcp(
+                item.parse()
+                    .with_context(|| format!("invalid address: {item}"))?,
+            ),
+        };
+        if addrs.contains(&addr) {
+            bail!("duplicate address: {item}");
+        }
+        addrs.push(addr);
+    }
+    if addrs.is_empty() {
+        bail!("no listen address");
+    }
+    Ok(addrs)
 }
 
-pub(crate) fn app_config_from_env() -> AppConfig {
-    let cors_config = CorsConfig::from_env();
-    let logging_config = LoggingConfig::default()
-        .with_format(LogFormat::Pretty)
-        .with_json_backend(false);
+/// 允许跨域访问的来源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum CorsOrigins {
+    Any,
+    List(Vec<HeaderValue>),
+}
+
+/// HTTP 应用层中需要重启才能生效的配置；跨域与限流见 [`RuntimeConfig`](super::reload::RuntimeConfig)
+#[derive(Debug, Clone)]
+pub(crate) struct AppConfig {
+    pub(crate) request_limits: RequestLimits,
+}
+
+pub(crate) fn app_config_from_source(source: &ConfigSource) -> Result<AppConfig> {
+    Ok(AppConfig {
+        request_limits: RequestLimits::from_source(source)?,
+    })
+}
+
+/// `RUTIFY_CORS_ORIGINS` 为逗号分隔的来源列表，未设置或为 `*` 时允许任意来源
+pub(crate) fn cors_origins_from_source(source: &ConfigSource) -> Result<CorsOrigins> {
+    match source.var("RUTIFY_CORS_ORIGINS") {
+        Ok(text) => parse_cors_origins(&text),
+        Err(_) => Ok(CorsOrigins::Any),
+    }
+}
+
+fn parse_cors_origins(text: &str) -> Result<CorsOrigins> {
+    let text = text.trim();
+    if text.is_empty() || text == "*" {
+        return Ok(CorsOrigins::Any);
+    }
+    let origins = text
+        .split(',')
+        .map(str::trim)
+        .filter(|origin| !origin.is_empty())
+        .map(|origin| {
+            HeaderValue::from_str(origin.trim_end_matches('/'))
+                .with_context(|| format!("invalid origin in RUTIFY_CORS_ORIGINS: {origin}"))
+        })
+        .collect::<Result<_>>()?;
+    Ok(CorsOrigins::List(origins))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_listen_addrs() {
+        let addrs = parse_listen_addrs("0.0.0.0:3000, [::]:3000,unix:/run/rutify.sock").unwrap();
+        assert_eq!(
+            addrs,
+            [
+                ListenAddr::Tcp("0.0.0.0:3000".parse().unwrap()),
+                ListenAddr::Tcp("[::]:3000".parse().unwrap()),
+                ListenAddr::Unix(PathBuf::from("/run/rutify.sock")),
+            ]
+        );
+        assert_eq!(addrs[2].to_string(), "unix:/run/rutify.sock");
+
+        let config = ServerConfig {
+            addrs,
+            ..ServerConfig::for_tests()
+        };
+        assert_eq!(
+            config.local_tcp_addr(),
+            Some("127.0.0.1:3000".parse().unwrap())
+        );
+
+        assert!(parse_listen_addrs(" , ").is_err());
+        assert!(parse_listen_addrs("unix:").is_err());
+        assert!(parse_listen_addrs("localhost:3000").is_err());
+        assert!(parse_listen_addrs("0.0.0.0:3000,0.0.0.0:3000").is_err());
+    }
+
+    #[test]
+    fn test_server_config_from_lookup() {
+        let config = ServerConfig::for_tests();
+        assert_eq!(config.database_url, DEFAULT_DB_URL);
+        assert_eq!(config.jwt_key(), DEFAULT_JWT_SECRET.as_bytes());
+        assert!(!config.require_email_verification);
+        assert!(config.telegram_bot_token.is_none());
+
+        let config = ServerConfig::from_lookup(|key| match key {
+            "RUTIFY_DB_URL" => Some("postgres://rutify:hunter2@db/rutify".to_string()),
+            "RUTIFY_JWT_SECRET" => Some("Kq3v9XzT1pLw8sYb2nRf6hJd0mUc4eGa".to_string()),
+            "RUTIFY_REQUIRE_EMAIL_VERIFICATION" => Some("Yes".to_string()),
+            "RUTIFY_TELEGRAM_BOT_TOKEN" => Some(" ".to_string()),
+            "RUTIFY_HOOK_SECRET_UPTIME_KUMA" => Some("s3cr3t-kuma".to_string()),
+            "RUTIFY_HOOK_SECRET_GITHUB" => Some(String::new()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(config.jwt_key(), b"Kq3v9XzT1pLw8sYb2nRf6hJd0mUc4eGa");
+        assert!(config.require_email_verification);
+        assert!(config.telegram_bot_token.is_none());
+        assert_eq!(
+            config.hook_secret(Provider::UptimeKuma),
+            Some("s3cr3t-kuma")
+        );
+        assert_eq!(config.hook_secret(Provider::Github), None);
+        let debug = format!("{config:?}");
+        assert!(!debug.contains("hunter2") && !debug.contains("Kq3v9X"));
+        assert!(debug.contains("uptime-kuma") && !debug.contains("s3cr3t"));
+
+        assert!(ServerConfig::from_lookup(|_| Some(String::new())).is_err());
+    }
 
-    AppConfig::new()
-        .with_cors_config(cors_config)
-        .with_logging(true)
-        .with_logging_config(logging_config)
-        .with_tracing(true)
+    #[test]
+    fn test_parse_cors_origins() {
+        assert_eq!(parse_cors_origins(" * ").unwrap(), CorsOrigins::Any);
+        assert_eq!(
+            parse_cors_origins("https://panel.example.com/, http://localhost:5173").unwrap(),
+            CorsOrigins::List(vec![
+                HeaderValue::from_static("https://panel.example.com"),
+                HeaderValue::from_static("http://localhost:5173"),
+            ])
+        );
+        assert!(parse_cors_origins("https://bad\norigin").is_err());
+    }
 }
@@ -0,0 +1,151 @@
+//! `rutify-server healthcheck` 与 `rutify-server print-config` 子命令
+
+use crate::bootstrap::config::{self, ServerConfig};
+use crate::bootstrap::logging::LogConfig;
+use crate::bootstrap::reload::RuntimeConfig;
+use crate::bootstrap::source::{ConfigSource, config_file};
+use crate::services::auth::mode::AuthPolicy;
+use crate::services::auth::validation::PasswordPolicy;
+use crate::services::cluster::ClusterConfig;
+use crate::services::limits::SizeLimits;
+use crate::services::link_preview::LinkPreviewConfig;
+use anyhow::{Context, Result, bail};
+use std::fmt::{Debug, Write};
+use std::time::Duration;
+
+/// 请求本机服务端的 `/health`：优先使用第一个 TCP 监听地址，只监听 Unix 域套接字时经套接字请求
+pub(crate) async fn check_local(config: &ServerConfig, timeout: Duration) -> Result<()> {
+    if let Some(addr) = config.local_tcp_addr() {
+        return check_url(&format!("http://{addr}/health"), timeout).await;
+    }
+    match config.addrs.first() {
+        #[cfg(unix)]
+        Some(config::ListenAddr::Unix(path)) => tokio::time::timeout(timeout, check_unix(path))
+            .await
+            .with_context(|| format!("unix:{} did not respond in time", path.display()))?,
+        Some(addr) => bail!("cannot check {addr} on this platform"),
+        None => bail!("no listen address"),
+    }
+}
+
+/// 请求指定的健康检查地址，返回非 2xx 或无法连接时报错
+pub(crate) async fn check_url(url: &str, timeout: Duration) -> Result<()> {
+    let client = reqwest::Client::builder()
+        .timeout(timeout)
+        .build()
+        .context("failed to build HTTP client")?;
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .with_context(|| format!("failed to reach {url}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        bail!("{url} returned {status}");
+    }
+    Ok(())
+}
+
+/// 经 Unix 域套接字发送最简单的 HTTP/1.1 请求
+#[cfg(unix)]
+async fn check_unix(path: &std::path::Path) -> Result<()> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut stream = tokio::net::UnixStream::connect(path)
+        .await
+        .with_context(|| format!("failed to reach unix:{}", path.display()))?;
+    stream
+        .write_all(b"GET /health HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+        .await?;
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).await?;
+    match parse_status(&response) {
+        Some(status) if (200..300).contains(&status) => Ok(()),
+        Some(status) => bail!("unix:{} returned {status}", path.display()),
+        None => bail!("unix:{} returned an invalid response", path.display()),
+    }
+}
+
+/// 从 `HTTP/1.1 200 OK` 这样的状态行中取出状态码
+fn parse_status(response: &[u8]) -> Option<u16> {
+    let line = response.split(|byte| *byte == b'\n').next()?;
+    let line = std::str::from_utf8(line).ok()?;
+    let mut parts = line.split_whitespace();
+    parts
+        .next()
+        .filter(|version| version.starts_with("HTTP/"))?;
+    parts.next()?.parse().ok()
+}
+
+/// 按配置文件、`.env` 与环境变量解析出的生效配置，密钥与密码不会输出
+pub(crate) fn render_config(source: &ConfigSource) -> Result<String> {
+    let mut out = String::new();
+    section(&mut out, "config_file", &config_file()?);
+    section(&mut out, "server", &ServerConfig::from_source(source)?);
+    section(&mut out, "app", &config::app_config_from_source(source)?);
+    section(&mut out, "runtime", &RuntimeConfig::from_source(source)?);
+    section(&mut out, "log", &LogConfig::from_source(source)?);
+    section(&mut out, "auth", &AuthPolicy::from_source(source)?);
+    section(
+        &mut out,
+        "password_policy",
+        &PasswordPolicy::from_source(source)?,
+    );
+    section(
+        &mut out,
+        "encryption_key_file",
+        &source.var("RUTIFY_ENCRYPTION_KEY_FILE").ok(),
+    );
+    section(&mut out, "size_limits", &SizeLimits::from_source(source)?);
+    section(
+        &mut out,
+        "link_preview",
+        &LinkPreviewConfig::from_source(source)?,
+    );
+    let cluster = ClusterConfig::from_source(source)?.map(|cluster| ClusterConfig {
+        url: redact_url(&cluster.url),
+        ..cluster
+    });
+    section(&mut out, "cluster", &cluster);
+    Ok(out)
+}
+
+fn section(out: &mut String, name: &str, value: &dyn Debug) {
+    let _ = writeln!(out, "{name} = {value:#?}");
+}
+
+/// 隐藏数据库地址中的密码
+pub(crate) fn redact_url(text: &str) -> String {
+    match reqwest::Url::parse(text) {
+        Ok(mut url) if url.password().is_some() => {
+            let _ = url.set_password(Some("***"));
+            url.to_string()
+        }
+        _ => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status_and_redaction() {
+        assert_eq!(
+            parse_status(b"HTTP/1.1 503 Service Unavailable\r\ncontent-length: 0\r\n\r\n"),
+            Some(503)
+        );
+        assert_eq!(parse_status(b"HTTP/1.1 200 OK\r\n"), Some(200));
+        assert_eq!(parse_status(b"garbage"), None);
+        assert_eq!(parse_status(b""), None);
+
+        assert_eq!(
+            redact_url("postgres://rutify:hunter2@db:5432/rutify"),
+            "postgres://rutify:***@db:5432/rutify"
+        );
+        assert_eq!(
+            redact_url("sqlite://rutify.db?mode=rwc"),
+            "sqlite://rutify.db?mode=rwc"
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+use std::sync::OnceLock;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Registry, fmt, reload};
+
+use crate::bootstrap::source::ConfigSource;
+
+/// 未设置 `RUST_LOG` 时的日志级别
+const DEFAULT_FILTER: &str = "info";
+
+/// 运行时替换日志过滤规则的句柄
+static FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// 日志输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LogFormat {
+    Pretty,
+    /// 每行一个 JSON 对象，便于 Loki/ELK 采集
+    Json,
+}
+
+/// 日志配置，来自 `RUST_LOG`、`RUTIFY_LOG_FORMAT`、`RUTIFY_LOG_FILE`、`RUTIFY_LOG_ROTATION`
+#[derive(Debug, Clone)]
+pub(crate) struct LogConfig {
+    /// 启动时的日志过滤规则
+    pub(crate) filter: String,
+    pub(crate) format: LogFormat,
+    /// 除标准输出外额外写入的日志文件
+    pub(crate) file: Option<String>,
+    pub(crate) rotation: Rotation,
+}
+
+impl LogConfig {
+    pub(crate) fn from_source(source: &ConfigSource) -> Result<Self> {
+        let format = match source
+            .var("RUTIFY_LOG_FORMAT")
+            .unwrap_or_default()
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "" | "pretty" | "text" => LogFormat::Pretty,
+            "json" => LogFormat::Json,
+            other => bail!("invalid RUTIFY_LOG_FORMAT: {other}, expected pretty or json"),
+        };
+        let rotation = match source
+            .var("RUTIFY_LOG_ROTATION")
+            .unwrap_or_default()
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "" | "daily" => Rotation::DAILY,
+            "hourly" => Rotation::HOURLY,
+            "minutely" => Rotation::MINUTELY,
+            "never" => Rotation::NEVER,
+            other => bail!(
+                "invalid RUTIFY_LOG_ROTATION: {other}, expected daily, hourly, minutely or never"
+            ),
+        };
+        let file = source
+            .var("RUTIFY_LOG_FILE")
+            .ok()
+            .filter(|path| !path.is_empty());
+
+        Ok(Self {
+            filter: filter_from_source(source),
+            format,
+            file,
+            rotation,
+        })
+    }
+}
+
+/// 日志过滤规则，来自 `RUST_LOG`，未设置时为 [`DEFAULT_FILTER`]
+pub(crate) fn filter_from_source(source: &ConfigSource) -> String {
+    source
+        .var("RUST_LOG")
+        .ok()
+        .filter(|filter| !filter.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_FILTER.to_string())
+}
+
+/// 安装全局 tracing 订阅者；返回的 guard 需持有到进程退出，否则文件日志会丢失尾部
+pub(crate) fn init(config: &LogConfig) -> Result<Option<WorkerGuard>> {
+    let filter =
+        EnvFilter::try_new(&config.filter).unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    let (filter, handle) = reload::Layer::new(filter);
+
+    let (file_writer, guard) = match &config.file {
+        Some(path) => {
+            let (writer, guard) = tracing_appender::non_blocking(file_appender(path, config)?);
+            (Some(writer), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    let registry = tracing_subscriber::registry().with(filter);
+    match config.format {
+        LogFormat::Pretty => registry
+            .with(fmt::layer())
+            .with(file_writer.map(|writer| fmt::layer().with_ansi(false).with_writer(writer)))
+            .try_init(),
+        LogFormat::Json => registry
+            .with(fmt::layer().json().with_current_span(true))
+            .with(file_writer.map(|writer| {
+                fmt::layer()
+                    .json()
+                    .with_current_span(true)
+                    .with_writer(writer)
+            }))
+            .try_init(),
+    }
+    .context("failed to install tracing subscriber")?;
+    let _ = FILTER_HANDLE.set(handle);
+
+    Ok(guard)
+}
+
+/// 当前生效的日志过滤规则，日志尚未初始化时为 `None`
+pub(crate) fn current_filter() -> Option<String> {
+    FILTER_HANDLE
+        .get()?
+        .with_current(|filter| filter.to_string())
+        .ok()
+}
+
+/// 替换日志过滤规则，语法同 `RUST_LOG`；日志尚未初始化时不做任何事
+pub(crate) fn set_filter(directives: &str) -> Result<()> {
+    let filter = EnvFilter::try_new(directives)
+        .with_context(|| format!("invalid log filter: {directives}"))?;
+    if let Some(handle) = FILTER_HANDLE.get() {
+        handle
+            .reload(filter)
+            .context("failed to replace log filter")?;
+    }
+    Ok(())
+}
+
+fn file_appender(path: &str, config: &LogConfig) -> Result<RollingFileAppender> {
+    let path = Path::new(path);
+    let directory = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let file_name = path
+        .file_name()
+        .with_context(|| format!("invalid RUTIFY_LOG_FILE: {}", path.display()))?;
+
+    RollingFileAppender::builder()
+        .rotation(config.rotation.clone())
+        .filename_prefix(file_name.to_string_lossy())
+        .build(directory)
+        .with_context(|| format!("failed to open log file in {}", directory.display()))
+}
@@ -1,4 +1,19 @@
 pub(crate) mod app;
+pub(crate) mod capabilities;
 pub(crate) mod config;
-mod shutdown;
+pub(crate) mod diagnostics;
+pub(crate) mod logging;
+pub(crate) mod monitoring;
+pub(crate) mod protection;
+pub(crate) mod read_only;
+pub(crate) mod reload;
+pub(crate) mod request_id;
+pub(crate) mod request_limits;
+pub(crate) mod server;
+pub(crate) mod service;
+pub(crate) mod settings;
+pub(crate) mod shutdown;
+pub(crate) mod source;
+pub(crate) mod startup;
 mod state;
+mod systemd;
@@ -0,0 +1,207 @@
+use axum::extract::{Request, State};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::Response;
+use serde::Serialize;
+use std::fmt::Write;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::time::Instant;
+
+/// 不计入请求统计的路径前缀，避免抓取指标本身干扰数据
+const EXCLUDED_PREFIXES: &[&str] = &["/monitor", "/health"];
+
+/// 进程内的 HTTP 请求统计，由 `/monitor/metrics` 与 `/monitor/monitoring` 读取
+#[derive(Clone)]
+pub(crate) struct MonitoringState {
+    inner: Arc<Counters>,
+}
+
+struct Counters {
+    started_at: Instant,
+    in_flight: AtomicI64,
+    /// 按状态码类别（1xx 到 5xx）计数
+    by_class: [AtomicU64; 5],
+    duration_micros: AtomicU64,
+    rate_limited: AtomicU64,
+    overloaded: AtomicU64,
+}
+
+/// 拒绝请求的原因
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Rejection {
+    RateLimited,
+    Overloaded,
+}
+
+/// `/monitor/monitoring` 返回的快照
+#[derive(Debug, Serialize)]
+pub(crate) struct MonitoringSnapshot {
+    pub(crate) uptime_secs: u64,
+    pub(crate) requests_total: u64,
+    pub(crate) requests_in_flight: i64,
+    pub(crate) responses_4xx: u64,
+    pub(crate) responses_5xx: u64,
+    pub(crate) avg_duration_ms: f64,
+    pub(crate) rate_limited: u64,
+    pub(crate) overloaded: u64,
+}
+
+impl MonitoringState {
+    pub(crate) fn new() -> Self {
+        Self {
+            inner: Arc::new(Counters {
+                started_at: Instant::now(),
+                in_flight: AtomicI64::new(0),
+                by_class: Default::default(),
+                duration_micros: AtomicU64::new(0),
+                rate_limited: AtomicU64::new(0),
+                overloaded: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// 记录被保护层拒绝的请求
+    pub(crate) fn record_rejection(&self, rejection: Rejection) {
+        let counter = match rejection {
+            Rejection::RateLimited => &self.inner.rate_limited,
+            Rejection::Overloaded => &self.inner.overloaded,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn record(&self, status: StatusCode, started: Instant) {
+        let class = (status.as_u16() / 100).clamp(1, 5) as usize - 1;
+        self.inner.by_class[class].fetch_add(1, Ordering::Relaxed);
+        self.inner
+            .duration_micros
+            .fetch_add(started.elapsed().as_micros() as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> MonitoringSnapshot {
+        let counters = &self.inner;
+        let by_class: Vec<u64> = counters
+            .by_class
+            .iter()
+            .map(|count| count.load(Ordering::Relaxed))
+            .collect();
+        let requests_total = by_class.iter().sum();
+        let duration_micros = counters.duration_micros.load(Ordering::Relaxed);
+        MonitoringSnapshot {
+            uptime_secs: counters.started_at.elapsed().as_secs(),
+            requests_total,
+            requests_in_flight: counters.in_flight.load(Ordering::Relaxed),
+            responses_4xx: by_class[3],
+            responses_5xx: by_class[4],
+            avg_duration_ms: match requests_total {
+                0 => 0.0,
+                total => duration_micros as f64 / total as f64 / 1000.0,
+            },
+            rate_limited: counters.rate_limited.load(Ordering::Relaxed),
+            overloaded: counters.overloaded.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Prometheus 文本格式的指标
+    pub(crate) fn render_metrics(&self) -> String {
+        let counters = &self.inner;
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "# TYPE rutify_uptime_seconds gauge\nrutify_uptime_seconds {}",
+            counters.started_at.elapsed().as_secs()
+        );
+        out.push_str("# TYPE rutify_http_requests_total counter\n");
+        for (index, count) in counters.by_class.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "rutify_http_requests_total{{status=\"{}xx\"}} {}",
+                index + 1,
+                count.load(Ordering::Relaxed)
+            );
+        }
+        let _ = writeln!(
+            out,
+            "# TYPE rutify_http_requests_in_flight gauge\nrutify_http_requests_in_flight {}",
+            counters.in_flight.load(Ordering::Relaxed)
+        );
+        let _ = writeln!(
+            out,
+            "# TYPE rutify_http_request_duration_seconds_sum counter\n\
+             rutify_http_request_duration_seconds_sum {}",
+            counters.duration_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0
+        );
+        out.push_str("# TYPE rutify_http_rejected_total counter\n");
+        let _ = writeln!(
+            out,
+            "rutify_http_rejected_total{{reason=\"rate_limit\"}} {}\n\
+             rutify_http_rejected_total{{reason=\"overload\"}} {}",
+            counters.rate_limited.load(Ordering::Relaxed),
+            counters.overloaded.load(Ordering::Relaxed)
+        );
+        out
+    }
+}
+
+/// 统计请求数、状态码与耗时；需放在保护层外层，被拒绝的请求也会计入
+pub(crate) async fn middleware(
+    State(monitoring): State<MonitoringState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path();
+    if EXCLUDED_PREFIXES
+        .iter()
+        .any(|prefix| path.starts_with(prefix))
+    {
+        return next.run(request).await;
+    }
+
+    let started = Instant::now();
+    let in_flight = InFlight::enter(&monitoring.inner.in_flight);
+    let response = next.run(request).await;
+    drop(in_flight);
+    monitoring.record(response.status(), started);
+    response
+}
+
+/// 进行中的请求计数，客户端中途断开、请求被取消时同样会减回
+struct InFlight<'a>(&'a AtomicI64);
+
+impl<'a> InFlight<'a> {
+    fn enter(counter: &'a AtomicI64) -> Self {
+        counter.fetch_add(1, Ordering::Relaxed);
+        Self(counter)
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_metrics() {
+        let monitoring = MonitoringState::new();
+        let started = Instant::now();
+        monitoring.record(StatusCode::OK, started);
+        monitoring.record(StatusCode::NOT_FOUND, started);
+        monitoring.record(StatusCode::TOO_MANY_REQUESTS, started);
+        monitoring.record_rejection(Rejection::RateLimited);
+
+        let snapshot = monitoring.snapshot();
+        assert_eq!(snapshot.requests_total, 3);
+        assert_eq!(snapshot.responses_4xx, 2);
+        assert_eq!(snapshot.rate_limited, 1);
+
+        let metrics = monitoring.render_metrics();
+        assert!(metrics.contains("rutify_http_requests_total{status=\"2xx\"} 1\n"));
+        assert!(metrics.contains("rutify_http_requests_total{status=\"4xx\"} 2\n"));
+        assert!(metrics.contains("rutify_http_rejected_total{reason=\"rate_limit\"} 1\n"));
+    }
+}
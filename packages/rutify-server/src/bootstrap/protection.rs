@@ -0,0 +1,297 @@
+use crate::bootstrap::monitoring::{MonitoringState, Rejection};
+use crate::bootstrap::source::ConfigSource;
+use crate::error::AppError;
+use anyhow::{Context, Result};
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::{HeaderValue, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use lru::LruCache;
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+/// 同时跟踪的客户端上限，超出时淘汰最久未访问的客户端
+const MAX_TRACKED_CLIENTS: NonZeroUsize = NonZeroUsize::new(10_000).unwrap();
+
+/// 限流与过载保护配置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ProtectionConfig {
+    /// 每个客户端 IP 每秒补充的请求数，`0` 表示不限流
+    pub(crate) rate_per_sec: u32,
+    /// 每个客户端 IP 允许的突发请求数
+    pub(crate) burst: u32,
+    /// 同时处理的请求上限，超出时直接返回 503，`0` 表示不限制
+    pub(crate) max_concurrent: usize,
+}
+
+impl Default for ProtectionConfig {
+    fn default() -> Self {
+        Self {
+            rate_per_sec: 20,
+            burst: 100,
+            max_concurrent: 1024,
+        }
+    }
+}
+
+impl ProtectionConfig {
+    /// 读取 `RUTIFY_RATE_LIMIT_PER_SEC`（默认 20）、`RUTIFY_RATE_LIMIT_BURST`（默认 100）
+    /// 与 `RUTIFY_MAX_CONCURRENT_REQUESTS`（默认 1024）
+    pub(crate) fn from_source(source: &ConfigSource) -> Result<Self> {
+        let defaults = Self::default();
+        let rate_per_sec = match source.var("RUTIFY_RATE_LIMIT_PER_SEC") {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid RUTIFY_RATE_LIMIT_PER_SEC: {text}"))?,
+            Err(_) => defaults.rate_per_sec,
+        };
+        let burst = match source.var("RUTIFY_RATE_LIMIT_BURST") {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid RUTIFY_RATE_LIMIT_BURST: {text}"))?,
+            Err(_) => defaults.burst,
+        };
+        let max_concurrent = match source.var("RUTIFY_MAX_CONCURRENT_REQUESTS") {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid RUTIFY_MAX_CONCURRENT_REQUESTS: {text}"))?,
+            Err(_) => defaults.max_concurrent,
+        };
+        Ok(Self {
+            rate_per_sec,
+            // 突发数小于每秒速率时，每秒实际只能放行 burst 个请求
+            burst: burst.max(rate_per_sec),
+            max_concurrent,
+        })
+    }
+}
+
+/// 单个客户端的令牌桶
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// 按客户端 IP 限流，并限制同时处理的请求数；配置可在运行时重新加载
+pub(crate) struct Protection {
+    config: watch::Receiver<ProtectionConfig>,
+    /// 按 [`client_key`] 归并的令牌桶，被淘汰的客户端下次访问时重新获得满桶
+    buckets: Mutex<LruCache<IpAddr, Bucket>>,
+    in_flight: AtomicUsize,
+    monitoring: MonitoringState,
+}
+
+impl Protection {
+    pub(crate) fn new(
+        config: watch::Receiver<ProtectionConfig>,
+        monitoring: MonitoringState,
+    ) -> Self {
+        Self::with_capacity(config, monitoring, MAX_TRACKED_CLIENTS)
+    }
+
+    fn with_capacity(
+        config: watch::Receiver<ProtectionConfig>,
+        monitoring: MonitoringState,
+        capacity: NonZeroUsize,
+    ) -> Self {
+        Self {
+            config,
+            buckets: Mutex::new(LruCache::new(capacity)),
+            in_flight: AtomicUsize::new(0),
+            monitoring,
+        }
+    }
+
+    /// 消耗一个令牌；令牌不足时返回需要等待的时间
+    fn acquire(&self, ip: IpAddr, now: Instant) -> Result<(), Duration> {
+        let config = *self.config.borrow();
+        if config.rate_per_sec == 0 {
+            return Ok(());
+        }
+        let rate = f64::from(config.rate_per_sec);
+        let burst = f64::from(config.burst);
+        let mut buckets = self.buckets.lock().unwrap();
+        let bucket = buckets.get_or_insert_mut(client_key(ip), || Bucket {
+            tokens: burst,
+            updated_at: now,
+        });
+        if refill(bucket, now, rate, burst) >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
+        }
+    }
+}
+
+/// 限流使用的客户端标识：IPv6 地址按 /64 前缀归并，避免单个客户端轮换地址绕过限流
+fn client_key(ip: IpAddr) -> IpAddr {
+    match ip.to_canonical() {
+        IpAddr::V6(ip) => IpAddr::V6(Ipv6Addr::from(ip.to_bits() & !u128::from(u64::MAX))),
+        ip => ip,
+    }
+}
+
+/// 按经过的时间补充令牌，返回当前令牌数
+fn refill(bucket: &mut Bucket, now: Instant, rate: f64, burst: f64) -> f64 {
+    let elapsed = now
+        .saturating_duration_since(bucket.updated_at)
+        .as_secs_f64();
+    bucket.tokens = (bucket.tokens + elapsed * rate).min(burst);
+    bucket.updated_at = now;
+    bucket.tokens
+}
+
+/// 超出单 IP 速率时返回 429 与 `Retry-After`，同时处理的请求过多时返回 503
+///
+/// 客户端地址取自 TCP 连接；部署在反向代理之后时所有请求共用代理的地址，应调大速率或关闭限流，
+/// 在代理层按真实地址限流。
+pub(crate) async fn middleware(
+    State(protection): State<Arc<Protection>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    if let Some(ip) = peer
+        && let Err(wait) = protection.acquire(ip, Instant::now())
+    {
+        protection
+            .monitoring
+            .record_rejection(Rejection::RateLimited);
+        let retry_after = wait.as_secs().max(1);
+        let mut response =
+            AppError::TooManyRequests("rate limit exceeded, slow down".to_string()).into_response();
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+        return response;
+    }
+
+    let max_concurrent = protection.config.borrow().max_concurrent;
+    let in_flight = InFlight::enter(&protection.in_flight);
+    if max_concurrent > 0 && in_flight.count > max_concurrent {
+        protection
+            .monitoring
+            .record_rejection(Rejection::Overloaded);
+        return AppError::ServiceUnavailable("server is busy, try again later".to_string())
+            .into_response();
+    }
+    next.run(request).await
+}
+
+/// 同时处理的请求计数，请求结束或被取消时减回
+struct InFlight<'a> {
+    counter: &'a AtomicUsize,
+    /// 计入本请求后的数量
+    count: usize,
+}
+
+impl<'a> InFlight<'a> {
+    fn enter(counter: &'a AtomicUsize) -> Self {
+        let count = counter.fetch_add(1, Ordering::Relaxed) + 1;
+        Self { counter, count }
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.counter.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit_per_client() {
+        let (config, receiver) = watch::channel(ProtectionConfig {
+            rate_per_sec: 2,
+            burst: 3,
+            max_concurrent: 0,
+        });
+        let protection = Protection::new(receiver, MonitoringState::new());
+        let client: IpAddr = "203.0.113.7".parse().unwrap();
+        let other: IpAddr = "203.0.113.8".parse().unwrap();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert!(protection.acquire(client, now).is_ok());
+        }
+        let wait = protection.acquire(client, now).unwrap_err();
+        assert_eq!(wait, Duration::from_millis(500));
+        assert!(protection.acquire(other, now).is_ok());
+
+        // 半秒后补充一个令牌
+        let later = now + Duration::from_millis(500);
+        assert!(protection.acquire(client, later).is_ok());
+        assert!(protection.acquire(client, later).is_err());
+
+        // 重新加载后立即按新配置限流
+        config.send_modify(|config| config.rate_per_sec = 0);
+        assert!(protection.acquire(client, later).is_ok());
+    }
+
+    #[test]
+    fn test_ipv6_clients_share_prefix() {
+        let (_config, receiver) = watch::channel(ProtectionConfig {
+            rate_per_sec: 1,
+            burst: 1,
+            max_concurrent: 0,
+        });
+        let protection = Protection::new(receiver, MonitoringState::new());
+        let now = Instant::now();
+
+        let first: IpAddr = "2001:db8:1:2::1".parse().unwrap();
+        let rotated: IpAddr = "2001:db8:1:2:abcd::9".parse().unwrap();
+        let other: IpAddr = "2001:db8:1:3::1".parse().unwrap();
+        assert!(protection.acquire(first, now).is_ok());
+        assert!(protection.acquire(rotated, now).is_err());
+        assert!(protection.acquire(other, now).is_ok());
+
+        // IPv4 映射地址按 IPv4 处理
+        let mapped: IpAddr = "::ffff:203.0.113.7".parse().unwrap();
+        assert_eq!(client_key(mapped), "203.0.113.7".parse::<IpAddr>().unwrap());
+    }
+
+    #[test]
+    fn test_tracked_clients_are_capped() {
+        let (_config, receiver) = watch::channel(ProtectionConfig {
+            rate_per_sec: 1,
+            burst: 1,
+            max_concurrent: 0,
+        });
+        let capacity = NonZeroUsize::new(4).unwrap();
+        let protection = Protection::with_capacity(receiver, MonitoringState::new(), capacity);
+        let now = Instant::now();
+
+        for host in 0..100u8 {
+            let ip = IpAddr::from([198, 51, 100, host]);
+            assert!(protection.acquire(ip, now).is_ok());
+            assert!(protection.buckets.lock().unwrap().len() <= capacity.get());
+        }
+
+        // 最近访问的客户端仍在跟踪，最早的已被淘汰
+        assert!(
+            protection
+                .acquire(IpAddr::from([198, 51, 100, 99]), now)
+                .is_err()
+        );
+        assert!(
+            protection
+                .acquire(IpAddr::from([198, 51, 100, 0]), now)
+                .is_ok()
+        );
+    }
+}
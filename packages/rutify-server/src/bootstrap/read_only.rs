@@ -0,0 +1,65 @@
+use crate::bootstrap::source::ConfigSource;
+use crate::error::AppError;
+use anyhow::{Context, Result};
+use axum::extract::Request;
+use axum::http::Method;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+/// 拒绝写请求时返回的说明，gRPC 接口同样使用
+pub(crate) const MESSAGE: &str = "this instance is read-only";
+
+/// 只读模式下仍允许的写方法请求：登录、密码强度检查与只改内存状态的运维接口
+const ALLOWED_WRITES: &[&str] = &[
+    "/auth/login",
+    "/auth/password/strength",
+    "/api/admin/reload",
+    "/api/admin/log-level",
+];
+
+/// 只读模式下拒绝的读方法请求：`GET /notify` 会入库通知，邮箱验证链接会修改用户
+const BLOCKED_READS: &[&str] = &["/notify", "/notify/", "/auth/verify-email"];
+
+/// 读取 `RUTIFY_READ_ONLY`（默认 `false`）
+pub(crate) fn from_source(source: &ConfigSource) -> Result<bool> {
+    match source.var("RUTIFY_READ_ONLY") {
+        Ok(text) => text
+            .trim()
+            .parse()
+            .with_context(|| format!("invalid RUTIFY_READ_ONLY: {text}")),
+        Err(_) => Ok(false),
+    }
+}
+
+fn is_allowed(method: &Method, path: &str) -> bool {
+    if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        !BLOCKED_READS.contains(&path)
+    } else {
+        ALLOWED_WRITES.contains(&path)
+    }
+}
+
+/// 只读实例上拒绝入库与修改数据的请求，返回 503
+pub(crate) async fn middleware(request: Request, next: Next) -> Response {
+    if !is_allowed(request.method(), request.uri().path()) {
+        return AppError::ServiceUnavailable(MESSAGE.to_string()).into_response();
+    }
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_only_rules() {
+        assert!(is_allowed(&Method::GET, "/api/notifies"));
+        assert!(is_allowed(&Method::GET, "/notify/ws"));
+        assert!(is_allowed(&Method::POST, "/auth/login"));
+        assert!(!is_allowed(&Method::GET, "/notify"));
+        assert!(!is_allowed(&Method::POST, "/notify"));
+        assert!(!is_allowed(&Method::PATCH, "/api/notifies/1"));
+        assert!(!is_allowed(&Method::POST, "/hooks/github"));
+        assert!(!is_allowed(&Method::GET, "/auth/verify-email"));
+    }
+}
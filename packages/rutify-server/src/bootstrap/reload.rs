@@ -0,0 +1,195 @@
+use crate::bootstrap::config::{self, CorsOrigins};
+use crate::bootstrap::protection::ProtectionConfig;
+use crate::bootstrap::source::ConfigSource;
+use crate::bootstrap::{logging, systemd};
+use crate::services::db_maintenance::{DbMaintenance, DbMaintenanceConfig};
+use crate::state::AppState;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+/// 无需重启即可生效的配置
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RuntimeConfig {
+    /// 日志过滤规则，来自 `RUST_LOG`
+    pub(crate) log_filter: String,
+    pub(crate) protection: ProtectionConfig,
+    pub(crate) cors_origins: CorsOrigins,
+    /// 过期通知清理与数据库维护
+    pub(crate) db_maintenance: DbMaintenanceConfig,
+}
+
+impl RuntimeConfig {
+    pub(crate) fn from_source(source: &ConfigSource) -> Result<Self> {
+        Ok(Self {
+            log_filter: logging::filter_from_source(source),
+            protection: ProtectionConfig::from_source(source)?,
+            cors_origins: config::cors_origins_from_source(source)?,
+            db_maintenance: DbMaintenanceConfig::from_source(source)?,
+        })
+    }
+}
+
+/// 一次重新加载的结果
+#[derive(Debug, Serialize)]
+pub(crate) struct ReloadOutcome {
+    /// 发生变化的配置项
+    pub(crate) changed: Vec<&'static str>,
+}
+
+/// 保存当前的运行时配置，并通过 watch 通道把变化推送给限流与跨域层
+pub(crate) struct ConfigReloader {
+    current: Mutex<RuntimeConfig>,
+    protection: watch::Sender<ProtectionConfig>,
+    cors_origins: watch::Sender<CorsOrigins>,
+}
+
+impl ConfigReloader {
+    pub(crate) fn new(config: RuntimeConfig) -> Self {
+        Self {
+            protection: watch::Sender::new(config.protection),
+            cors_origins: watch::Sender::new(config.cors_origins.clone()),
+            current: Mutex::new(config),
+        }
+    }
+
+    pub(crate) fn protection(&self) -> watch::Receiver<ProtectionConfig> {
+        self.protection.subscribe()
+    }
+
+    pub(crate) fn cors_origins(&self) -> watch::Receiver<CorsOrigins> {
+        self.cors_origins.subscribe()
+    }
+
+    pub(crate) fn current(&self) -> RuntimeConfig {
+        self.current.lock().unwrap().clone()
+    }
+
+    /// 应用新配置，返回发生变化的配置项；日志过滤规则无效时不应用任何变化
+    pub(crate) fn apply(
+        &self,
+        config: RuntimeConfig,
+        db_maintenance: &DbMaintenance,
+    ) -> Result<ReloadOutcome> {
+        let mut current = self.current.lock().unwrap();
+        let mut changed = Vec::new();
+        if config.log_filter != current.log_filter {
+            logging::set_filter(&config.log_filter)?;
+            changed.push("log_filter");
+        }
+        if config.protection != current.protection {
+            self.protection.send_replace(config.protection);
+            changed.push("rate_limits");
+        }
+        if config.cors_origins != current.cors_origins {
+            self.cors_origins.send_replace(config.cors_origins.clone());
+            changed.push("cors");
+        }
+        if config.db_maintenance != current.db_maintenance {
+            db_maintenance.set_config(config.db_maintenance.clone());
+            changed.push("retention");
+        }
+        *current = config;
+        Ok(ReloadOutcome { changed })
+    }
+}
+
+/// 重新读取 `.env`、环境变量与配置文件并应用；`.env` 中的值覆盖进程启动时的同名变量
+///
+/// 监听地址、数据库、加密密钥与请求大小限制等仍需重启才能生效。
+pub(crate) fn reload(state: &AppState) -> Result<ReloadOutcome> {
+    systemd::notify_reloading();
+    let result = reload_from_env(state);
+    systemd::notify_reloaded();
+    result
+}
+
+fn reload_from_env(state: &AppState) -> Result<ReloadOutcome> {
+    if let Err(e) = dotenvy::dotenv_override()
+        && !e.not_found()
+    {
+        return Err(e).context("failed to read .env");
+    }
+    let config = RuntimeConfig::from_source(&ConfigSource::load()?)?;
+    let outcome = state.config_reloader.apply(config, &state.db_maintenance)?;
+    info!(changed = ?outcome.changed, "Configuration reloaded");
+    Ok(outcome)
+}
+
+/// 收到 SIGHUP 时重新加载配置，已建立的 WebSocket 连接不受影响
+#[cfg(unix)]
+pub(crate) fn spawn_sighup(state: Arc<AppState>) -> Result<()> {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let mut hangup = signal(SignalKind::hangup()).context("failed to listen for SIGHUP")?;
+    tokio::spawn(async move {
+        while hangup.recv().await.is_some() {
+            if let Err(e) = reload(&state) {
+                warn!("Failed to reload configuration: {:#}", e);
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub(crate) fn spawn_sighup(_state: Arc<AppState>) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_filter_from_source() {
+        let config = RuntimeConfig::from_source(&ConfigSource::from_pairs([(
+            "RUST_LOG",
+            "rutify_server=debug",
+        )]))
+        .unwrap();
+        assert_eq!(config.log_filter, "rutify_server=debug");
+
+        let config =
+            RuntimeConfig::from_source(&ConfigSource::from_pairs([("RUST_LOG", " ")])).unwrap();
+        assert_eq!(config.log_filter, "info");
+    }
+
+    #[test]
+    fn test_apply_reports_changes() {
+        let config = RuntimeConfig {
+            log_filter: "info".to_string(),
+            protection: ProtectionConfig::default(),
+            cors_origins: CorsOrigins::Any,
+            db_maintenance: DbMaintenanceConfig {
+                interval: None,
+                notifies_thresholds: Vec::new(),
+                purge_expired: true,
+            },
+        };
+        let db_maintenance = DbMaintenance::new(config.db_maintenance.clone());
+        let reloader = ConfigReloader::new(config.clone());
+        let mut protection = reloader.protection();
+
+        let outcome = reloader.apply(config.clone(), &db_maintenance).unwrap();
+        assert!(outcome.changed.is_empty());
+        assert!(!protection.has_changed().unwrap());
+
+        let mut updated = config.clone();
+        updated.protection.rate_per_sec = 5;
+        updated.db_maintenance.purge_expired = false;
+        let outcome = reloader.apply(updated, &db_maintenance).unwrap();
+        assert_eq!(outcome.changed, ["rate_limits", "retention"]);
+        assert_eq!(protection.borrow_and_update().rate_per_sec, 5);
+        assert!(!db_maintenance.config().purge_expired);
+
+        // 无效的日志过滤规则不应用任何变化
+        let mut invalid = config;
+        invalid.log_filter = "info,=[".to_string();
+        invalid.protection.rate_per_sec = 7;
+        assert!(reloader.apply(invalid, &db_maintenance).is_err());
+        assert_eq!(reloader.protection().borrow().rate_per_sec, 5);
+    }
+}
@@ -0,0 +1,88 @@
+use axum::extract::Request;
+use axum::http::{HeaderMap, HeaderValue};
+use axum::middleware::Next;
+use axum::response::Response;
+use tracing::Instrument;
+
+/// 请求 ID 所在的请求头与响应头
+pub(crate) const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// 客户端自带请求 ID 的最大长度，超出或含非法字符时重新生成
+const MAX_CLIENT_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// 为每个请求分配请求 ID：写入 tracing span、任务上下文与响应头
+pub(crate) async fn middleware(request: Request, next: Next) -> Response {
+    let request_id = resolve(request.headers());
+    let span = tracing::info_span!(
+        "request",
+        request_id = %request_id,
+        method = %request.method(),
+        path = %request.uri().path(),
+    );
+
+    let mut response = REQUEST_ID
+        .scope(request_id.clone(), next.run(request))
+        .instrument(span)
+        .await;
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// 当前请求的 ID，不在请求上下文中（后台任务等）时为 `None`
+pub(crate) fn current() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
+/// 沿用上游代理传入的请求 ID，便于跨服务关联日志
+fn resolve(headers: &HeaderMap) -> String {
+    headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| {
+            !id.is_empty()
+                && id.len() <= MAX_CLIENT_ID_LEN
+                && id
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        })
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_request_id() {
+        let mut headers = HeaderMap::new();
+        let generated = resolve(&headers);
+        assert!(uuid::Uuid::parse_str(&generated).is_ok());
+
+        headers.insert(REQUEST_ID_HEADER, HeaderValue::from_static("edge-42.a_b"));
+        assert_eq!(resolve(&headers), "edge-42.a_b");
+
+        headers.insert(REQUEST_ID_HEADER, HeaderValue::from_static("bad id"));
+        assert_ne!(resolve(&headers), "bad id");
+        headers.insert(
+            REQUEST_ID_HEADER,
+            HeaderValue::from_str(&"x".repeat(MAX_CLIENT_ID_LEN + 1)).unwrap(),
+        );
+        assert_eq!(resolve(&headers).len(), 36);
+    }
+
+    #[tokio::test]
+    async fn test_current_request_id_scope() {
+        assert_eq!(current(), None);
+        let inside = REQUEST_ID
+            .scope("req-1".to_string(), async { current() })
+            .await;
+        assert_eq!(inside.as_deref(), Some("req-1"));
+    }
+}
@@ -0,0 +1,184 @@
+use crate::bootstrap::source::ConfigSource;
+use crate::error::AppError;
+use anyhow::{Context, Result};
+use axum::extract::{OriginalUri, Request, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::time::Duration;
+
+/// 不受请求超时限制的路径：备份需要先打包整个数据库才能开始响应
+const UNTIMED_PATHS: &[&str] = &["/api/admin/backup"];
+
+/// 请求体大小与处理时间的上限
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RequestLimits {
+    pub(crate) max_body_bytes: usize,
+    pub(crate) timeout: Duration,
+}
+
+impl Default for RequestLimits {
+    fn default() -> Self {
+        Self {
+            max_body_bytes: 512 * 1024,
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl RequestLimits {
+    /// 读取 `RUTIFY_MAX_REQUEST_BYTES`（默认 524288）与 `RUTIFY_REQUEST_TIMEOUT_SECS`（默认 30）
+    pub(crate) fn from_source(source: &ConfigSource) -> Result<Self> {
+        let defaults = Self::default();
+        let max_body_bytes = match source.var("RUTIFY_MAX_REQUEST_BYTES") {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid RUTIFY_MAX_REQUEST_BYTES: {text}"))?,
+            Err(_) => defaults.max_body_bytes,
+        };
+        let timeout = match source.var("RUTIFY_REQUEST_TIMEOUT_SECS") {
+            Ok(text) => Duration::from_secs(
+                text.trim()
+                    .parse()
+                    .with_context(|| format!("invalid RUTIFY_REQUEST_TIMEOUT_SECS: {text}"))?,
+            ),
+            Err(_) => defaults.timeout,
+        };
+        Ok(Self {
+            max_body_bytes,
+            timeout,
+        })
+    }
+
+    /// `Content-Length` 超出上限时直接拒绝，不读取请求体
+    fn check_content_length(&self, headers: &HeaderMap) -> Result<(), AppError> {
+        let length = headers
+            .get(header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok());
+        match length {
+            Some(length) if length > self.max_body_bytes as u64 => Err(self.too_large()),
+            _ => Ok(()),
+        }
+    }
+
+    fn too_large(&self) -> AppError {
+        AppError::PayloadTooLarge(format!(
+            "request body exceeds {} bytes",
+            self.max_body_bytes
+        ))
+    }
+}
+
+/// 限制请求体大小与处理时间，超出时返回 JSON 格式的 413 与 408
+///
+/// 没有 `Content-Length` 的请求体由 [`axum::extract::DefaultBodyLimit`] 在读取时截停，
+/// 这里把提取器返回的纯文本 413 改写为 JSON。需放在请求 ID 中间件内层，错误响应才能带上请求 ID。
+pub(crate) async fn middleware(
+    State(limits): State<RequestLimits>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if let Err(err) = limits.check_content_length(request.headers()) {
+        return err.into_response();
+    }
+    let path = request
+        .extensions()
+        .get::<OriginalUri>()
+        .map_or_else(|| request.uri().path(), |uri| uri.path())
+        .to_string();
+
+    let response = if UNTIMED_PATHS.contains(&path.as_str()) {
+        next.run(request).await
+    } else {
+        match tokio::time::timeout(limits.timeout, next.run(request)).await {
+            Ok(response) => response,
+            Err(_) => {
+                return AppError::RequestTimeout(format!(
+                    "request did not complete within {} seconds",
+                    limits.timeout.as_secs()
+                ))
+                .into_response();
+            }
+        }
+    };
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|value| value.as_bytes().starts_with(b"application/json"));
+    if response.status() == StatusCode::PAYLOAD_TOO_LARGE && !is_json {
+        return limits.too_large().into_response();
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::body::Body;
+    use axum::extract::DefaultBodyLimit;
+    use axum::routing::{get, post};
+    use tower::ServiceExt;
+
+    fn app(limits: RequestLimits) -> Router {
+        Router::new()
+            .route("/echo", post(|body: String| async move { body }))
+            .route(
+                "/slow",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    "done"
+                }),
+            )
+            .layer(DefaultBodyLimit::max(limits.max_body_bytes))
+            .layer(axum::middleware::from_fn_with_state(limits, middleware))
+    }
+
+    async fn status_and_body(app: Router, request: Request) -> (StatusCode, String) {
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    #[tokio::test]
+    async fn test_request_limits() {
+        let limits = RequestLimits {
+            max_body_bytes: 16,
+            timeout: Duration::from_millis(50),
+        };
+
+        let request = Request::post("/echo").body(Body::from("short")).unwrap();
+        let (status, body) = status_and_body(app(limits), request).await;
+        assert_eq!((status, body.as_str()), (StatusCode::OK, "short"));
+
+        let request = Request::post("/echo")
+            .header(header::CONTENT_LENGTH, "64")
+            .body(Body::from("x".repeat(64)))
+            .unwrap();
+        let (status, body) = status_and_body(app(limits), request).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(body.contains("exceeds 16 bytes"));
+
+        // 没有 Content-Length 的流式请求体在读取时被截停
+        let chunks = futures_util::stream::iter(
+            ["x".repeat(10), "x".repeat(10)].map(Ok::<_, std::io::Error>),
+        );
+        let request = Request::post("/echo")
+            .body(Body::from_stream(chunks))
+            .unwrap();
+        let (status, body) = status_and_body(app(limits), request).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(body.contains("exceeds 16 bytes"));
+
+        let request = Request::get("/slow").body(Body::empty()).unwrap();
+        let (status, body) = status_and_body(app(limits), request).await;
+        assert_eq!(status, StatusCode::REQUEST_TIMEOUT);
+        assert!(body.contains("\"code\":\"request_timeout\""));
+    }
+}
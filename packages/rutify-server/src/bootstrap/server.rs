@@ -0,0 +1,119 @@
+use crate::bootstrap::config::{ListenAddr, ServerConfig};
+use crate::bootstrap::{shutdown, systemd};
+use anyhow::{Context, Result};
+use axum::Router;
+use futures_util::future::try_join_all;
+use std::net::SocketAddr;
+use tokio::sync::watch;
+use tracing::info;
+
+/// 已绑定的监听套接字
+enum Listener {
+    Tcp(tokio::net::TcpListener),
+    #[cfg(unix)]
+    Unix(tokio::net::UnixListener, std::path::PathBuf),
+}
+
+/// 监听全部地址并运行到收到停止信号；TCP 连接信息会写入请求扩展，供限流与审计读取客户端地址
+///
+/// 由 systemd socket activation 启动时使用传入的套接字，忽略 `RUTIFY_ADDR`。
+pub(crate) async fn serve(config: &ServerConfig, app: Router) -> Result<()> {
+    let listeners = match systemd::take_listener()? {
+        Some(listener) => vec![Listener::Tcp(tokio::net::TcpListener::from_std(listener)?)],
+        None => {
+            let mut listeners = Vec::with_capacity(config.addrs.len());
+            for addr in &config.addrs {
+                listeners.push(bind(addr).await?);
+            }
+            listeners
+        }
+    };
+    let local_addrs = listeners
+        .iter()
+        .map(|listener| match listener {
+            Listener::Tcp(listener) => Ok(listener.local_addr()?.to_string()),
+            #[cfg(unix)]
+            Listener::Unix(_, path) => Ok(format!("unix:{}", path.display())),
+        })
+        .collect::<Result<Vec<_>>>()?
+        .join(", ");
+    info!("Listening on {}", local_addrs);
+    systemd::notify_ready(&format!("Listening on {local_addrs}"));
+
+    // 一个停止信号同时通知所有监听器
+    let (stop, stopped) = watch::channel(false);
+    tokio::spawn(async move {
+        shutdown::signal().await;
+        stop.send_replace(true);
+    });
+    try_join_all(
+        listeners
+            .into_iter()
+            .map(|listener| run(listener, app.clone(), stopped.clone())),
+    )
+    .await?;
+    Ok(())
+}
+
+async fn bind(addr: &ListenAddr) -> Result<Listener> {
+    match addr {
+        ListenAddr::Tcp(addr) => tokio::net::TcpListener::bind(addr)
+            .await
+            .map(Listener::Tcp)
+            .with_context(|| format!("failed to bind {addr}")),
+        #[cfg(unix)]
+        ListenAddr::Unix(path) => {
+            bind_unix(path).map(|listener| Listener::Unix(listener, path.clone()))
+        }
+        #[cfg(not(unix))]
+        ListenAddr::Unix(path) => {
+            anyhow::bail!(
+                "cannot bind unix:{}: unix sockets are not supported on this platform",
+                path.display()
+            )
+        }
+    }
+}
+
+/// 绑定 Unix 域套接字；上次运行遗留的套接字文件会先删除，仍有进程在监听时报错
+#[cfg(unix)]
+fn bind_unix(path: &std::path::Path) -> Result<tokio::net::UnixListener> {
+    use std::os::unix::fs::FileTypeExt;
+
+    if let Ok(metadata) = std::fs::symlink_metadata(path)
+        && metadata.file_type().is_socket()
+    {
+        if std::os::unix::net::UnixStream::connect(path).is_ok() {
+            anyhow::bail!("unix:{} is already in use", path.display());
+        }
+        std::fs::remove_file(path)
+            .with_context(|| format!("failed to remove stale socket {}", path.display()))?;
+    }
+    tokio::net::UnixListener::bind(path)
+        .with_context(|| format!("failed to bind unix:{}", path.display()))
+}
+
+async fn run(listener: Listener, app: Router, mut stopped: watch::Receiver<bool>) -> Result<()> {
+    let shutdown = async move {
+        let _ = stopped.wait_for(|stopped| *stopped).await;
+    };
+    match listener {
+        Listener::Tcp(listener) => axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(shutdown)
+        .await
+        .context("server errors"),
+        // Unix 域套接字没有客户端 IP，这些请求不受单 IP 限流，open 模式下按公网来源处理
+        #[cfg(unix)]
+        Listener::Unix(listener, path) => {
+            let result = axum::serve(listener, app.into_make_service())
+                .with_graceful_shutdown(shutdown)
+                .await
+                .context("server errors");
+            let _ = std::fs::remove_file(&path);
+            result
+        }
+    }
+}
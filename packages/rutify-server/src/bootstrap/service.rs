@@ -0,0 +1,387 @@
+//! 作为系统服务运行：Windows 服务与 macOS launchd
+//!
+//! `install` 记录工作目录，服务启动时切换到该目录，照常读取其中的 `.env` 与 `rutify.toml`。
+//! Linux 使用 systemd 单元部署，见 README。
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+pub(crate) use imp::{install, uninstall};
+
+/// Windows 服务名
+#[cfg(windows)]
+const SERVICE_NAME: &str = "rutify";
+/// launchd 任务标签
+#[cfg(any(target_os = "macos", test))]
+const LAUNCHD_LABEL: &str = "com.rutify.server";
+/// 连续崩溃时两次重启之间的最短间隔
+#[cfg(any(windows, target_os = "macos", test))]
+const RESTART_DELAY_SECS: u64 = 10;
+
+/// 安装服务时的选项
+#[derive(Debug, Clone)]
+#[cfg_attr(not(any(windows, target_os = "macos")), allow(dead_code))]
+pub(crate) struct ServiceOptions {
+    /// 服务的工作目录
+    pub(crate) working_dir: PathBuf,
+    /// 服务的日志文件
+    pub(crate) log_file: PathBuf,
+    /// macOS：安装为当前用户的 LaunchAgent（登录后启动），而不是系统级 LaunchDaemon
+    pub(crate) user: bool,
+}
+
+impl ServiceOptions {
+    /// 工作目录默认为当前目录，日志文件默认为工作目录下的 `rutify-server.log`；都转为绝对路径
+    pub(crate) fn resolve(
+        working_dir: Option<PathBuf>,
+        log_file: Option<PathBuf>,
+        user: bool,
+    ) -> Result<Self> {
+        let current_dir = std::env::current_dir()?;
+        let working_dir = current_dir.join(working_dir.unwrap_or_default());
+        let working_dir = working_dir
+            .canonicalize()
+            .with_context(|| format!("working directory {} not found", working_dir.display()))?;
+        let log_file = match log_file {
+            Some(path) => current_dir.join(path),
+            None => working_dir.join("rutify-server.log"),
+        };
+        Ok(Self {
+            working_dir,
+            log_file,
+            user,
+        })
+    }
+}
+
+/// 由服务管理器启动：切换到工作目录后运行服务器，Windows 下同时接入服务控制
+pub(crate) fn run(working_dir: Option<&Path>, log_file: Option<PathBuf>) -> Result<()> {
+    if let Some(dir) = working_dir {
+        std::env::set_current_dir(dir)
+            .with_context(|| format!("failed to enter {}", dir.display()))?;
+    }
+    imp::run(log_file)
+}
+
+/// 服务管理器启动服务器时使用的命令行参数
+#[cfg(any(windows, target_os = "macos", test))]
+fn run_arguments(working_dir: &Path, log_file: Option<&Path>) -> Vec<String> {
+    let mut arguments = vec![
+        "service".to_string(),
+        "run".to_string(),
+        "--working-dir".to_string(),
+        working_dir.display().to_string(),
+    ];
+    if let Some(log_file) = log_file {
+        arguments.push("--log-file".to_string());
+        arguments.push(log_file.display().to_string());
+    }
+    arguments
+}
+
+/// launchd 任务定义：开机（LaunchAgent 为登录后）启动，异常退出后重启，标准输出与错误写入日志文件
+#[cfg(any(target_os = "macos", test))]
+fn launchd_plist(executable: &Path, options: &ServiceOptions) -> String {
+    fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    }
+    let program_arguments: String = std::iter::once(executable.display().to_string())
+        .chain(run_arguments(&options.working_dir, None))
+        .map(|argument| format!("        <string>{}</string>\n", escape(&argument)))
+        .collect();
+    let log_file = escape(&options.log_file.display().to_string());
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{LAUNCHD_LABEL}</string>
+    <key>ProgramArguments</key>
+    <array>
+{program_arguments}    </array>
+    <key>WorkingDirectory</key>
+    <string>{working_dir}</string>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>KeepAlive</key>
+    <dict>
+        <key>SuccessfulExit</key>
+        <false/>
+    </dict>
+    <key>ThrottleInterval</key>
+    <integer>{RESTART_DELAY_SECS}</integer>
+    <key>StandardOutPath</key>
+    <string>{log_file}</string>
+    <key>StandardErrorPath</key>
+    <string>{log_file}</string>
+</dict>
+</plist>
+"#,
+        working_dir = escape(&options.working_dir.display().to_string()),
+    )
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use super::{LAUNCHD_LABEL, ServiceOptions, launchd_plist};
+    use anyhow::{Context, Result, bail};
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    /// 系统级任务需要 root，用户级任务放在当前用户的 LaunchAgents 下
+    fn plist_path(user: bool) -> Result<PathBuf> {
+        let file = format!("{LAUNCHD_LABEL}.plist");
+        if !user {
+            return Ok(Path::new("/Library/LaunchDaemons").join(file));
+        }
+        let home = std::env::var_os("HOME").context("HOME is not set")?;
+        Ok(Path::new(&home).join("Library/LaunchAgents").join(file))
+    }
+
+    fn launchctl(arguments: &[&str], plist: &Path) -> Result<()> {
+        let status = Command::new("launchctl")
+            .args(arguments)
+            .arg(plist)
+            .status()
+            .context("failed to run launchctl")?;
+        if !status.success() {
+            bail!("launchctl {} failed with {status}", arguments.join(" "));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn install(options: &ServiceOptions) -> Result<()> {
+        let plist = plist_path(options.user)?;
+        if plist.exists() {
+            bail!(
+                "{} already exists; run `rutify-server service uninstall` first",
+                plist.display()
+            );
+        }
+        if let Some(parent) = plist.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let executable = std::env::current_exe()?;
+        std::fs::write(&plist, launchd_plist(&executable, options))
+            .with_context(|| format!("failed to write {}", plist.display()))?;
+        launchctl(&["load", "-w"], &plist)?;
+        println!(
+            "Installed launchd job {LAUNCHD_LABEL} ({})",
+            plist.display()
+        );
+        println!("Logs: {}", options.log_file.display());
+        Ok(())
+    }
+
+    pub(crate) fn uninstall(user: bool) -> Result<()> {
+        let plist = plist_path(user)?;
+        if !plist.exists() {
+            bail!("{} does not exist", plist.display());
+        }
+        launchctl(&["unload", "-w"], &plist)?;
+        std::fs::remove_file(&plist)?;
+        println!("Removed launchd job {LAUNCHD_LABEL}");
+        Ok(())
+    }
+
+    /// launchd 直接管理进程，按普通方式运行，日志由 launchd 重定向
+    pub(crate) fn run(log_file: Option<PathBuf>) -> Result<()> {
+        crate::run_server(log_file)
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use super::{RESTART_DELAY_SECS, SERVICE_NAME, ServiceOptions, run_arguments};
+    use crate::bootstrap::shutdown;
+    use anyhow::{Context, Result};
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+    use std::sync::OnceLock;
+    use std::time::Duration;
+    use windows_service::service::{
+        ServiceAccess, ServiceAction, ServiceActionType, ServiceControl, ServiceControlAccept,
+        ServiceErrorControl, ServiceExitCode, ServiceFailureActions, ServiceFailureResetPeriod,
+        ServiceInfo, ServiceStartType, ServiceState, ServiceStatus, ServiceType,
+    };
+    use windows_service::service_control_handler::{self, ServiceControlHandlerResult};
+    use windows_service::service_manager::{ServiceManager, ServiceManagerAccess};
+    use windows_service::{define_windows_service, service_dispatcher};
+
+    /// 传给服务入口的日志文件，服务入口由系统在另一个线程中调用
+    static LOG_FILE: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+    define_windows_service!(ffi_service_main, service_main);
+
+    pub(crate) fn install(options: &ServiceOptions) -> Result<()> {
+        let manager = ServiceManager::local_computer(
+            None::<&str>,
+            ServiceManagerAccess::CONNECT | ServiceManagerAccess::CREATE_SERVICE,
+        )
+        .context("failed to connect to the service manager; run as administrator")?;
+        let info = ServiceInfo {
+            name: OsString::from(SERVICE_NAME),
+            display_name: OsString::from("Rutify notification server"),
+            service_type: ServiceType::OWN_PROCESS,
+            start_type: ServiceStartType::AutoStart,
+            error_control: ServiceErrorControl::Normal,
+            executable_path: std::env::current_exe()?,
+            launch_arguments: run_arguments(&options.working_dir, Some(&options.log_file))
+                .into_iter()
+                .map(OsString::from)
+                .collect(),
+            dependencies: vec![],
+            account_name: None,
+            account_password: None,
+        };
+        let service = manager
+            .create_service(&info, ServiceAccess::CHANGE_CONFIG | ServiceAccess::START)
+            .context("failed to create the service")?;
+        service.set_description("Receives notifications and pushes them to subscribers")?;
+
+        // 崩溃或异常退出后重启，一天内没有再失败则重新计数
+        let restart = |secs| ServiceAction {
+            action_type: ServiceActionType::Restart,
+            delay: Duration::from_secs(secs),
+        };
+        service.update_failure_actions(ServiceFailureActions {
+            reset_period: ServiceFailureResetPeriod::After(Duration::from_secs(24 * 60 * 60)),
+            reboot_msg: None,
+            command: None,
+            actions: Some(vec![
+                restart(RESTART_DELAY_SECS),
+                restart(RESTART_DELAY_SECS * 3),
+                restart(RESTART_DELAY_SECS * 6),
+            ]),
+        })?;
+        service.set_failure_actions_on_non_crash_failures(true)?;
+        service.start(&[] as &[&str])?;
+        println!("Installed and started Windows service {SERVICE_NAME}");
+        println!("Logs: {}", options.log_file.display());
+        Ok(())
+    }
+
+    pub(crate) fn uninstall(_user: bool) -> Result<()> {
+        let manager = ServiceManager::local_computer(None::<&str>, ServiceManagerAccess::CONNECT)
+            .context("failed to connect to the service manager; run as administrator")?;
+        let service = manager
+            .open_service(
+                SERVICE_NAME,
+                ServiceAccess::QUERY_STATUS | ServiceAccess::STOP | ServiceAccess::DELETE,
+            )
+            .context("service is not installed")?;
+        if service.query_status()?.current_state != ServiceState::Stopped {
+            service.stop()?;
+        }
+        service.delete()?;
+        println!("Removed Windows service {SERVICE_NAME}");
+        Ok(())
+    }
+
+    /// 交给服务控制管理器调度，阻塞到服务停止
+    pub(crate) fn run(log_file: Option<PathBuf>) -> Result<()> {
+        let _ = LOG_FILE.set(log_file);
+        service_dispatcher::start(SERVICE_NAME, ffi_service_main)
+            .context("failed to start the service dispatcher; `service run` is started by Windows")
+    }
+
+    fn service_main(_arguments: Vec<OsString>) {
+        if let Err(e) = run_service() {
+            tracing::error!("Service failed: {:#}", e);
+        }
+    }
+
+    fn run_service() -> Result<()> {
+        let handle = service_control_handler::register(SERVICE_NAME, |control| match control {
+            ServiceControl::Stop | ServiceControl::Shutdown => {
+                shutdown::request();
+                ServiceControlHandlerResult::NoError
+            }
+            ServiceControl::Interrogate => ServiceControlHandlerResult::NoError,
+            _ => ServiceControlHandlerResult::NotImplemented,
+        })?;
+        let status = |state, controls_accepted, exit_code| ServiceStatus {
+            service_type: ServiceType::OWN_PROCESS,
+            current_state: state,
+            controls_accepted,
+            exit_code,
+            checkpoint: 0,
+            wait_hint: Duration::default(),
+            process_id: None,
+        };
+        handle.set_service_status(status(
+            ServiceState::Running,
+            ServiceControlAccept::STOP | ServiceControlAccept::SHUTDOWN,
+            ServiceExitCode::NO_ERROR,
+        ))?;
+
+        let log_file = LOG_FILE.get().cloned().flatten();
+        let result = crate::run_server(log_file);
+        // 非零退出码让服务控制管理器按失败处理并重启
+        let exit_code = match &result {
+            Ok(()) => ServiceExitCode::NO_ERROR,
+            Err(_) => ServiceExitCode::ServiceSpecific(1),
+        };
+        handle.set_service_status(status(
+            ServiceState::Stopped,
+            ServiceControlAccept::empty(),
+            exit_code,
+        ))?;
+        result
+    }
+}
+
+#[cfg(not(any(windows, target_os = "macos")))]
+mod imp {
+    use super::ServiceOptions;
+    use anyhow::{Result, bail};
+    use std::path::PathBuf;
+
+    pub(crate) fn install(_options: &ServiceOptions) -> Result<()> {
+        bail!(
+            "service install supports Windows and macOS; on Linux use the systemd units (see README)"
+        )
+    }
+
+    pub(crate) fn uninstall(_user: bool) -> Result<()> {
+        bail!(
+            "service uninstall supports Windows and macOS; on Linux use the systemd units (see README)"
+        )
+    }
+
+    pub(crate) fn run(log_file: Option<PathBuf>) -> Result<()> {
+        crate::run_server(log_file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launchd_plist() {
+        let options = ServiceOptions {
+            working_dir: PathBuf::from("/srv/R&D"),
+            log_file: PathBuf::from("/var/log/rutify.log"),
+            user: false,
+        };
+        let plist = launchd_plist(Path::new("/usr/local/bin/rutify-server"), &options);
+
+        assert!(plist.contains("<string>com.rutify.server</string>"));
+        assert!(plist.contains(
+            "<string>/usr/local/bin/rutify-server</string>\n        <string>service</string>\n        <string>run</string>\n        <string>--working-dir</string>\n        <string>/srv/R&amp;D</string>\n    </array>"
+        ));
+        assert!(plist.contains("<key>WorkingDirectory</key>\n    <string>/srv/R&amp;D</string>"));
+        assert!(
+            plist
+                .contains("<key>StandardErrorPath</key>\n    <string>/var/log/rutify.log</string>")
+        );
+        assert_eq!(
+            run_arguments(&options.working_dir, Some(&options.log_file))[4..],
+            ["--log-file", "/var/log/rutify.log"]
+        );
+    }
+}
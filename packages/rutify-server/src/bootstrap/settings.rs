@@ -0,0 +1,153 @@
+use crate::bootstrap::logging;
+use crate::bootstrap::reload::RuntimeConfig;
+use crate::error::AppError;
+use crate::state::AppState;
+use rutify_core::RuntimeSetting;
+use tracing::info;
+
+/// 可通过 `/api/admin/settings` 修改的运行时配置项
+///
+/// 修改立即生效但不写回环境变量，下一次重新加载配置（SIGHUP 或 `/api/admin/reload`）后恢复。
+const SETTINGS: [(&str, &str); 5] = [
+    ("log_filter", "Log filter, same syntax as RUST_LOG"),
+    (
+        "rate_limit_per_sec",
+        "Requests per second allowed per client IP, 0 disables rate limiting",
+    ),
+    ("rate_limit_burst", "Burst requests allowed per client IP"),
+    (
+        "max_concurrent_requests",
+        "Requests handled at once before returning 503, 0 means unlimited",
+    ),
+    (
+        "purge_expired",
+        "Whether expired notifications are deleted periodically",
+    ),
+];
+
+pub(crate) fn list(state: &AppState) -> Vec<RuntimeSetting> {
+    let config = current(state);
+    SETTINGS
+        .iter()
+        .map(|(key, description)| RuntimeSetting {
+            key: key.to_string(),
+            value: value(&config, key),
+            description: description.to_string(),
+        })
+        .collect()
+}
+
+/// 修改一项设置并立即应用
+pub(crate) fn set(state: &AppState, key: &str, value: &str) -> Result<RuntimeSetting, AppError> {
+    let config = with_setting(current(state), key, value)?;
+    state
+        .config_reloader
+        .apply(config, &state.db_maintenance)
+        .map_err(|e| AppError::BadRequest(format!("{e:#}")))?;
+    info!(key, value, "Runtime setting changed");
+    list(state)
+        .into_iter()
+        .find(|setting| setting.key == key)
+        .ok_or_else(|| unknown(key))
+}
+
+/// 当前配置；日志过滤规则可能已被 `/api/admin/log-level` 单独修改，以实际生效的为准
+fn current(state: &AppState) -> RuntimeConfig {
+    let mut config = state.config_reloader.current();
+    if let Some(filter) = logging::current_filter() {
+        config.log_filter = filter;
+    }
+    config
+}
+
+fn value(config: &RuntimeConfig, key: &str) -> String {
+    match key {
+        "log_filter" => config.log_filter.clone(),
+        "rate_limit_per_sec" => config.protection.rate_per_sec.to_string(),
+        "rate_limit_burst" => config.protection.burst.to_string(),
+        "max_concurrent_requests" => config.protection.max_concurrent.to_string(),
+        "purge_expired" => config.db_maintenance.purge_expired.to_string(),
+        _ => String::new(),
+    }
+}
+
+fn with_setting(
+    mut config: RuntimeConfig,
+    key: &str,
+    value: &str,
+) -> Result<RuntimeConfig, AppError> {
+    fn parse<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, AppError> {
+        value
+            .trim()
+            .parse()
+            .map_err(|_| AppError::BadRequest(format!("invalid value for {key}: {value}")))
+    }
+
+    match key {
+        "log_filter" => {
+            if value.trim().is_empty() {
+                return Err(AppError::BadRequest(
+                    "log_filter must not be empty".to_string(),
+                ));
+            }
+            config.log_filter = value.trim().to_string();
+        }
+        "rate_limit_per_sec" => config.protection.rate_per_sec = parse(key, value)?,
+        "rate_limit_burst" => config.protection.burst = parse(key, value)?,
+        "max_concurrent_requests" => config.protection.max_concurrent = parse(key, value)?,
+        "purge_expired" => config.db_maintenance.purge_expired = parse(key, value)?,
+        _ => return Err(unknown(key)),
+    }
+    Ok(config)
+}
+
+fn unknown(key: &str) -> AppError {
+    AppError::NotFound(format!("Unknown setting: {key}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::config::CorsOrigins;
+    use crate::bootstrap::protection::ProtectionConfig;
+    use crate::services::db_maintenance::DbMaintenanceConfig;
+
+    #[test]
+    fn test_with_setting() {
+        let config = RuntimeConfig {
+            log_filter: "info".to_string(),
+            protection: ProtectionConfig::default(),
+            cors_origins: CorsOrigins::Any,
+            db_maintenance: DbMaintenanceConfig {
+                interval: None,
+                notifies_thresholds: Vec::new(),
+                purge_expired: true,
+            },
+        };
+
+        let updated = with_setting(config.clone(), "rate_limit_per_sec", " 5").unwrap();
+        assert_eq!(updated.protection.rate_per_sec, 5);
+        let updated = with_setting(updated, "purge_expired", "false").unwrap();
+        assert_eq!(value(&updated, "purge_expired"), "false");
+
+        for key in SETTINGS.map(|(key, _)| key) {
+            assert_eq!(
+                with_setting(config.clone(), key, &value(&config, key)).unwrap(),
+                config
+            );
+        }
+
+        assert!(matches!(
+            with_setting(config.clone(), "rate_limit_burst", "many"),
+            Err(AppError::BadRequest(_))
+        ));
+        assert!(matches!(
+            with_setting(config.clone(), "log_filter", " "),
+            Err(AppError::BadRequest(_))
+        ));
+        assert!(matches!(
+            with_setting(config, "listen_addr", "0.0.0.0"),
+            Err(AppError::NotFound(_))
+        ));
+    }
+}
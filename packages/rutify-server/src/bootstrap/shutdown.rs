@@ -1 +1,45 @@
+use crate::bootstrap::systemd;
+use tokio::sync::Notify;
+use tracing::info;
 
+/// 服务管理器（如 Windows 服务控制）发出的停止请求
+static STOP_REQUESTED: Notify = Notify::const_new();
+
+/// 请求停止服务器，效果与收到 SIGTERM 相同
+#[cfg(windows)]
+pub(crate) fn request() {
+    STOP_REQUESTED.notify_one();
+}
+
+/// 等待 Ctrl+C、SIGTERM 或服务管理器的停止请求，收到后服务器停止接受新连接并等待进行中的请求完成
+pub(crate) async fn signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+        _ = STOP_REQUESTED.notified() => {}
+    }
+    info!("Shutdown signal received, draining in-flight requests");
+    systemd::notify_stopping();
+}
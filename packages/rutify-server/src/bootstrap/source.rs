@@ -0,0 +1,147 @@
+//! 配置来源：环境变量（含 `.env`）优先，其次为可选的 TOML 配置文件
+//!
+//! 配置文件的键去掉 `RUTIFY_` 前缀并小写，表名作为前缀拼接，如 `db_url` 对应 `RUTIFY_DB_URL`，
+//! `[smtp] relay` 对应 `RUTIFY_SMTP_RELAY`；数组按逗号拼接。
+
+use anyhow::{Context, Result, bail};
+use std::collections::HashMap;
+use std::env::VarError;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+/// 未设置 `RUTIFY_CONFIG` 时，当前目录下存在该文件则读取
+const DEFAULT_CONFIG_FILE: &str = "rutify.toml";
+
+/// 启动时（以及重新加载配置时）合并得到的全部配置项，由各模块解析为类型化的配置
+pub(crate) struct ConfigSource {
+    values: HashMap<String, String>,
+}
+
+impl ConfigSource {
+    /// 读取配置文件并与当前环境变量合并；重新加载配置时再次调用
+    pub(crate) fn load() -> Result<Self> {
+        let mut values = match config_file()? {
+            Some(path) => read_file(&path)?,
+            None => HashMap::new(),
+        };
+        values.extend(std::env::vars());
+        Ok(Self { values })
+    }
+
+    /// 与 `std::env::var` 相同的用法
+    pub(crate) fn var(&self, key: &str) -> Result<String, VarError> {
+        self.values.get(key).cloned().ok_or(VarError::NotPresent)
+    }
+
+    /// 只包含给定配置项的来源
+    #[cfg(test)]
+    pub(crate) fn from_pairs<'a>(pairs: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        Self {
+            values: pairs
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        }
+    }
+}
+
+/// 使用的配置文件，来自 `RUTIFY_CONFIG`
+pub(crate) fn config_file() -> Result<Option<PathBuf>> {
+    match std::env::var("RUTIFY_CONFIG") {
+        Ok(path) if path.trim().is_empty() => Ok(None),
+        Ok(path) => {
+            let path = PathBuf::from(path);
+            if !path.is_file() {
+                bail!("RUTIFY_CONFIG: {} does not exist", path.display());
+            }
+            Ok(Some(path))
+        }
+        Err(_) => Ok(Some(PathBuf::from(DEFAULT_CONFIG_FILE)).filter(|path| path.is_file())),
+    }
+}
+
+fn read_file(path: &Path) -> Result<HashMap<String, String>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    parse(&text).with_context(|| format!("invalid config file {}", path.display()))
+}
+
+fn parse(text: &str) -> Result<HashMap<String, String>> {
+    let table: Table = text.parse()?;
+    let mut values = HashMap::new();
+    flatten("RUTIFY", table, &mut values)?;
+    Ok(values)
+}
+
+fn flatten(prefix: &str, table: Table, values: &mut HashMap<String, String>) -> Result<()> {
+    for (key, value) in table {
+        let key = format!("{prefix}_{}", key.replace('-', "_").to_ascii_uppercase());
+        let text = match value {
+            Value::Table(table) => {
+                flatten(&key, table, values)?;
+                continue;
+            }
+            Value::Array(items) => items
+                .into_iter()
+                .map(scalar)
+                .collect::<Option<Vec<_>>>()
+                .map(|items| items.join(","))
+                .with_context(|| format!("{key}: arrays may only contain plain values"))?,
+            value => scalar(value).with_context(|| format!("{key}: unsupported value"))?,
+        };
+        values.insert(key, text);
+    }
+    Ok(())
+}
+
+fn scalar(value: Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text),
+        Value::Integer(number) => Some(number.to_string()),
+        Value::Float(number) => Some(number.to_string()),
+        Value::Boolean(flag) => Some(flag.to_string()),
+        Value::Datetime(datetime) => Some(datetime.to_string()),
+        Value::Array(_) | Value::Table(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config_file() {
+        let values = parse(
+            r#"
+            addr = "127.0.0.1:3000"
+            jwt-secret = "Kq3v9XzT1pLw8sYb2nRf6hJd0mUc4eGa"
+            read_only = true
+            cors_origins = ["https://a.example.com", "https://b.example.com"]
+
+            [smtp]
+            relay = "mail.example.com:25"
+
+            [token_cache]
+            ttl_secs = 30
+            "#,
+        )
+        .unwrap();
+        let value = |key: &str| values.get(key).map(String::as_str);
+
+        assert_eq!(value("RUTIFY_ADDR"), Some("127.0.0.1:3000"));
+        assert_eq!(
+            value("RUTIFY_JWT_SECRET"),
+            Some("Kq3v9XzT1pLw8sYb2nRf6hJd0mUc4eGa")
+        );
+        assert_eq!(value("RUTIFY_READ_ONLY"), Some("true"));
+        assert_eq!(
+            value("RUTIFY_CORS_ORIGINS"),
+            Some("https://a.example.com,https://b.example.com")
+        );
+        assert_eq!(value("RUTIFY_SMTP_RELAY"), Some("mail.example.com:25"));
+        assert_eq!(value("RUTIFY_TOKEN_CACHE_TTL_SECS"), Some("30"));
+
+        assert!(parse("topics = [[\"a\"]]").is_err());
+        assert!(parse("addr = ").is_err());
+    }
+}
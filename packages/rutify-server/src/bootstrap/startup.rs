@@ -0,0 +1,245 @@
+//! 启动前的配置校验与启动摘要
+
+use crate::bootstrap::config::{self, ServerConfig};
+use crate::bootstrap::diagnostics::redact_url;
+use crate::bootstrap::read_only;
+use crate::bootstrap::reload::RuntimeConfig;
+use crate::bootstrap::source::ConfigSource;
+use crate::bridges;
+use crate::db;
+use crate::services::auth::mode::{AuthMode, AuthPolicy};
+use crate::services::auth::registration_gate::RegistrationGate;
+use crate::services::auth::token_cache::TokenCache;
+use crate::services::auth::user::{DEFAULT_JWT_SECRET, MIN_JWT_SECRET_LEN};
+use crate::services::auth::validation::PasswordPolicy;
+use crate::services::cluster::ClusterConfig;
+use crate::services::limits::SizeLimits;
+use crate::services::link_preview::LinkPreviewConfig;
+use crate::services::unifiedpush::UnifiedPushConfig;
+use crate::services::web_push::WebPushConfig;
+use crate::state::AppState;
+use anyhow::{Result, bail};
+use std::path::Path;
+use tracing::{info, warn};
+
+/// 校验全部配置，存在错误时一次列出并返回错误；通过时返回需要提示的警告
+pub(crate) fn validate(source: &ConfigSource) -> Result<Vec<String>> {
+    let mut report = Report::default();
+
+    let server = ServerConfig::from_source(source);
+    if let Ok(server) = &server {
+        check_jwt_secret(server.jwt_secret.as_deref(), &mut report);
+        check_database_url(&server.database_url, &mut report);
+        check_smtp_relay(&server.smtp_relay, &server.smtp_from, &mut report);
+        report.parse(
+            "database pool",
+            db::connect_options(source, &server.database_url),
+        );
+    }
+    report.parse("server", server);
+    report.parse("request limits", config::app_config_from_source(source));
+    report.parse("runtime", RuntimeConfig::from_source(source));
+    report.parse("RUTIFY_READ_ONLY", read_only::from_source(source));
+    report.parse("RUTIFY_AUTH_MODE", AuthPolicy::from_source(source));
+    report.parse("password policy", PasswordPolicy::from_source(source));
+    report.parse("registration", RegistrationGate::from_source(source));
+    report.parse("token cache", TokenCache::from_source(source));
+    report.parse("size limits", SizeLimits::from_source(source));
+    report.parse("link preview", LinkPreviewConfig::from_source(source));
+    report.parse("UnifiedPush", UnifiedPushConfig::from_source(source));
+    report.parse("Web Push", WebPushConfig::from_source(source));
+    report.parse("cluster", ClusterConfig::from_source(source));
+    report.parse(
+        "RUTIFY_ENCRYPTION_KEY_FILE",
+        db::encryption::key_from_source(source),
+    );
+    report.parse(
+        "SMTP gateway",
+        bridges::smtp::SmtpConfig::from_source(source),
+    );
+    report.parse("syslog", bridges::syslog::SyslogConfig::from_source(source));
+    #[cfg(feature = "mqtt")]
+    report.parse("MQTT", bridges::mqtt::MqttConfig::from_source(source));
+    #[cfg(feature = "grpc")]
+    report.parse("gRPC", crate::grpc::GrpcConfig::from_source(source));
+
+    if let Ok(policy) = AuthPolicy::from_source(source)
+        && policy.mode == AuthMode::Open
+    {
+        report.warn(if policy.open_private_only {
+            "RUTIFY_AUTH_MODE=open: any host on a private network can send notifications without a token"
+        } else {
+            "RUTIFY_AUTH_MODE=open: anyone who can reach the server can send notifications without a token"
+        });
+    }
+
+    if !report.errors.is_empty() {
+        bail!(
+            "invalid configuration:\n  - {}",
+            report.errors.join("\n  - ")
+        );
+    }
+    Ok(report.warnings)
+}
+
+/// 启动完成后输出的配置摘要
+pub(crate) fn log_banner(config: &ServerConfig, state: &AppState, warnings: &[String]) {
+    let addrs: Vec<String> = config.addrs.iter().map(ToString::to_string).collect();
+
+    info!("Rutify server {}", env!("CARGO_PKG_VERSION"));
+    info!("  listen:       {}", addrs.join(", "));
+    info!("  database:     {}", redact_url(&config.database_url));
+    info!("  auth mode:    {:?}", state.auth_policy.mode);
+    info!("  read-only:    {}", state.read_only);
+    info!(
+        "  capabilities: {}",
+        state.capabilities.enabled().join(", ")
+    );
+    for warning in warnings {
+        warn!("  {}", warning);
+    }
+}
+
+#[derive(Default)]
+struct Report {
+    errors: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl Report {
+    fn parse<T>(&mut self, name: &str, result: Result<T>) {
+        if let Err(e) = result {
+            self.errors.push(format!("{name}: {e:#}"));
+        }
+    }
+
+    fn error(&mut self, message: impl Into<String>) {
+        self.errors.push(message.into());
+    }
+
+    fn warn(&mut self, message: impl Into<String>) {
+        self.warnings.push(message.into());
+    }
+}
+
+fn check_jwt_secret(secret: Option<&str>, report: &mut Report) {
+    const HINT: &str = "generate one with `openssl rand -base64 48`";
+    match secret {
+        None => report.warn(format!(
+            "RUTIFY_JWT_SECRET is not set, using a built-in secret that anyone can forge tokens with; {HINT}"
+        )),
+        Some(secret) if secret.len() < MIN_JWT_SECRET_LEN => report.error(format!(
+            "RUTIFY_JWT_SECRET must be at least {MIN_JWT_SECRET_LEN} characters, got {}; {HINT}",
+            secret.len()
+        )),
+        Some(DEFAULT_JWT_SECRET) => {
+            report.warn(format!("RUTIFY_JWT_SECRET is the public default; {HINT}"))
+        }
+        Some(secret) => {
+            let mut distinct: Vec<char> = secret.chars().collect();
+            distinct.sort_unstable();
+            distinct.dedup();
+            if distinct.len() < 8 {
+                report.warn(format!(
+                    "RUTIFY_JWT_SECRET uses only {} distinct characters; {HINT}",
+                    distinct.len()
+                ));
+            }
+        }
+    }
+}
+
+/// SQLite 文件所在目录必须存在；没有 `mode=rwc` 时文件本身也必须存在
+fn check_database_url(url: &str, report: &mut Report) {
+    let Some((scheme, rest)) = url.split_once(':') else {
+        report.error(format!("RUTIFY_DB_URL is not a URL: {}", redact_url(url)));
+        return;
+    };
+    match scheme {
+        "postgres" | "postgresql" | "mysql" => {}
+        "sqlite" => {
+            let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+            let path = path.trim_start_matches("//");
+            if path.is_empty() || path == ":memory:" {
+                return;
+            }
+            let path = Path::new(path);
+            let parent = path
+                .parent()
+                .filter(|parent| !parent.as_os_str().is_empty());
+            if let Some(parent) = parent
+                && !parent.is_dir()
+            {
+                report.error(format!(
+                    "RUTIFY_DB_URL: directory {} does not exist",
+                    parent.display()
+                ));
+            } else if !query.contains("mode=rwc") && !path.exists() {
+                report.error(format!(
+                    "RUTIFY_DB_URL: {} does not exist; append ?mode=rwc to create it",
+                    path.display()
+                ));
+            }
+        }
+        other => report.error(format!(
+            "RUTIFY_DB_URL: unsupported database `{other}`, expected sqlite, postgres or mysql"
+        )),
+    }
+}
+
+/// 邮件转发使用的 `RUTIFY_SMTP_RELAY` 与 `RUTIFY_SMTP_FROM`
+fn check_smtp_relay(relay: &str, from: &str, report: &mut Report) {
+    let valid = relay
+        .rsplit_once(':')
+        .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok());
+    if !valid {
+        report.error(format!(
+            "RUTIFY_SMTP_RELAY must be host:port, got `{relay}`"
+        ));
+    }
+    if !from.contains('@') {
+        report.error(format!(
+            "RUTIFY_SMTP_FROM must be an email address, got `{from}`"
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jwt_secret_checks() {
+        fn check(secret: Option<&str>) -> (usize, usize) {
+            let mut report = Report::default();
+            check_jwt_secret(secret, &mut report);
+            (report.errors.len(), report.warnings.len())
+        }
+        assert_eq!(check(None), (0, 1));
+        assert_eq!(check(Some("short")), (1, 0));
+        assert_eq!(check(Some(DEFAULT_JWT_SECRET)), (0, 1));
+        assert_eq!(check(Some(&"ab".repeat(20))), (0, 1));
+        assert_eq!(check(Some("Kq3v9XzT1pLw8sYb2nRf6hJd0mUc4eGa")), (0, 0));
+    }
+
+    #[test]
+    fn test_database_url_and_smtp_checks() {
+        let errors = |url: &str| {
+            let mut report = Report::default();
+            check_database_url(url, &mut report);
+            report.errors.len()
+        };
+        assert_eq!(errors("sqlite://rutify.db?mode=rwc"), 0);
+        assert_eq!(errors("sqlite::memory:"), 0);
+        assert_eq!(errors("postgres://rutify:secret@db/rutify"), 0);
+        assert_eq!(errors("sqlite:///no/such/dir/rutify.db?mode=rwc"), 1);
+        assert_eq!(errors("sqlite://missing-rutify.db"), 1);
+        assert_eq!(errors("redis://localhost"), 1);
+
+        let mut report = Report::default();
+        check_smtp_relay("127.0.0.1:25", "rutify@localhost", &mut report);
+        assert!(report.errors.is_empty());
+        check_smtp_relay("mail.example.com", "rutify", &mut report);
+        assert_eq!(report.errors.len(), 2);
+    }
+}
@@ -0,0 +1,94 @@
+//! systemd 集成：socket activation 与 `Type=notify` 就绪、看门狗通知
+//!
+//! 不在 systemd 下运行（没有 `LISTEN_FDS`、`NOTIFY_SOCKET`）时这些函数不做任何事。
+
+pub(crate) use imp::{
+    notify_ready, notify_reloaded, notify_reloading, notify_stopping, take_listener,
+};
+
+#[cfg(unix)]
+mod imp {
+    use anyhow::{Context, Result, bail};
+    use sd_notify::NotifyState;
+    use std::os::fd::FromRawFd;
+    use std::time::Duration;
+    use tracing::{info, warn};
+
+    /// 取出 systemd 通过 socket activation 传入的监听套接字，没有时返回 `None`
+    pub(crate) fn take_listener() -> Result<Option<std::net::TcpListener>> {
+        let mut fds = sd_notify::listen_fds().context("invalid LISTEN_FDS")?;
+        let Some(fd) = fds.next() else {
+            return Ok(None);
+        };
+        if fds.next().is_some() {
+            warn!("systemd passed more than one socket, only the first one is used");
+        }
+        // systemd 保证 LISTEN_FDS 中的描述符归本进程所有且只在这里取用一次
+        let listener = unsafe { std::net::TcpListener::from_raw_fd(fd) };
+        if listener.local_addr().is_err() {
+            bail!("socket passed by systemd is not a TCP listener");
+        }
+        listener.set_nonblocking(true)?;
+        info!("Using socket passed by systemd");
+        Ok(Some(listener))
+    }
+
+    /// 数据库迁移完成、开始监听后发送 `READY=1`，并在启用看门狗时定期发送 `WATCHDOG=1`
+    pub(crate) fn notify_ready(status: &str) {
+        notify(&[NotifyState::Ready, NotifyState::Status(status)]);
+        let mut usec = 0;
+        if sd_notify::watchdog_enabled(false, &mut usec) {
+            // 以超时的一半发送心跳；运行时卡死时心跳停止，由 systemd 重启
+            let period = Duration::from_micros(usec / 2);
+            info!("systemd watchdog enabled, pinging every {:?}", period);
+            tokio::spawn(async move {
+                let mut ticker = tokio::time::interval(period);
+                loop {
+                    ticker.tick().await;
+                    notify(&[NotifyState::Watchdog]);
+                }
+            });
+        }
+    }
+
+    /// 开始重新加载配置时发送 `RELOADING=1`，systemd 要求同时附带当前单调时钟
+    pub(crate) fn notify_reloading() {
+        match NotifyState::monotonic_usec_now() {
+            Ok(now) => notify(&[NotifyState::Reloading, now]),
+            Err(e) => warn!("Failed to read monotonic clock: {}", e),
+        }
+    }
+
+    /// 重新加载结束后再次发送 `READY=1`
+    pub(crate) fn notify_reloaded() {
+        notify(&[NotifyState::Ready]);
+    }
+
+    /// 收到停止信号后发送 `STOPPING=1`
+    pub(crate) fn notify_stopping() {
+        notify(&[NotifyState::Stopping]);
+    }
+
+    fn notify(states: &[NotifyState]) {
+        if let Err(e) = sd_notify::notify(false, states) {
+            warn!("Failed to notify systemd: {}", e);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod imp {
+    use anyhow::Result;
+
+    pub(crate) fn take_listener() -> Result<Option<std::net::TcpListener>> {
+        Ok(None)
+    }
+
+    pub(crate) fn notify_ready(_status: &str) {}
+
+    pub(crate) fn notify_reloading() {}
+
+    pub(crate) fn notify_reloaded() {}
+
+    pub(crate) fn notify_stopping() {}
+}
@@ -0,0 +1,4 @@
+#[cfg(feature = "mqtt")]
+pub(crate) mod mqtt;
+pub(crate) mod smtp;
+pub(crate) mod syslog;
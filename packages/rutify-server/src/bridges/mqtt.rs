@@ -0,0 +1,228 @@
+use anyhow::{Context, Result};
+use rumqttc::{AsyncClient, Event, MqttOptions, Packet, QoS};
+use rutify_core::{NotificationInput, NotifyEvent};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{error, info, warn};
+
+use crate::bootstrap::source::ConfigSource;
+use crate::services::notify;
+use crate::state::AppState;
+
+const DEFAULT_CLIENT_ID: &str = "rutify-server";
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// MQTT 桥接配置
+#[derive(Clone)]
+pub(crate) struct MqttConfig {
+    pub url: String,
+    pub topics: Vec<String>,
+    pub publish_topic: Option<String>,
+    /// 来自 `RUTIFY_MQTT_USERNAME` 与 `RUTIFY_MQTT_PASSWORD`，两者都设置时才使用
+    pub credentials: Option<(String, String)>,
+}
+
+impl fmt::Debug for MqttConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MqttConfig")
+            .field("url", &self.url)
+            .field("topics", &self.topics)
+            .field("publish_topic", &self.publish_topic)
+            .field(
+                "credentials",
+                &self.credentials.as_ref().map(|(username, _)| username),
+            )
+            .finish()
+    }
+}
+
+impl MqttConfig {
+    /// 读取 `RUTIFY_MQTT_*` 环境变量，未设置 `RUTIFY_MQTT_URL` 时返回 `None`
+    pub(crate) fn from_source(source: &ConfigSource) -> Result<Option<Self>> {
+        let Ok(url) = source.var("RUTIFY_MQTT_URL") else {
+            return Ok(None);
+        };
+
+        let topics: Vec<String> = source
+            .var("RUTIFY_MQTT_TOPICS")
+            .unwrap_or_else(|_| "rutify/#".to_string())
+            .split(',')
+            .map(|topic| topic.trim().to_string())
+            .filter(|topic| !topic.is_empty())
+            .collect();
+        if topics.is_empty() {
+            anyhow::bail!("RUTIFY_MQTT_TOPICS must contain at least one topic");
+        }
+
+        let publish_topic = source
+            .var("RUTIFY_MQTT_PUBLISH_TOPIC")
+            .ok()
+            .filter(|topic| !topic.trim().is_empty());
+
+        let credentials = match (
+            source.var("RUTIFY_MQTT_USERNAME"),
+            source.var("RUTIFY_MQTT_PASSWORD"),
+        ) {
+            (Ok(username), Ok(password)) => Some((username, password)),
+            _ => None,
+        };
+
+        Ok(Some(Self {
+            url,
+            topics,
+            publish_topic,
+            credentials,
+        }))
+    }
+
+    fn options(&self) -> Result<MqttOptions> {
+        let url = if self.url.contains("client_id=") {
+            self.url.clone()
+        } else if self.url.contains('?') {
+            format!("{}&client_id={}", self.url, DEFAULT_CLIENT_ID)
+        } else {
+            format!("{}?client_id={}", self.url, DEFAULT_CLIENT_ID)
+        };
+
+        let mut options = MqttOptions::parse_url(url)
+            .with_context(|| format!("invalid RUTIFY_MQTT_URL: {}", self.url))?;
+        options.set_keep_alive(Duration::from_secs(30));
+        if let Some((username, password)) = &self.credentials {
+            options.set_credentials(username, password);
+        }
+        Ok(options)
+    }
+}
+
+/// 启动 MQTT 桥接：订阅配置的主题入库通知，并可选地将 NotifyEvent 转发到出站主题
+pub(crate) fn spawn(config: MqttConfig, state: Arc<AppState>) -> Result<()> {
+    let options = config.options()?;
+    let (client, mut eventloop) = AsyncClient::new(options, 64);
+
+    if let Some(publish_topic) = config.publish_topic.clone() {
+        tokio::spawn(republish_events(
+            client.clone(),
+            publish_topic,
+            state.tx.subscribe(),
+        ));
+    }
+
+    tokio::spawn(async move {
+        info!("MQTT bridge connecting to {}", config.url);
+        loop {
+            match eventloop.poll().await {
+                Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                    // 每次（重新）连接后都需要重新订阅
+                    for topic in &config.topics {
+                        if let Err(e) = client.subscribe(topic.as_str(), QoS::AtLeastOnce).await {
+                            error!("MQTT subscribe to '{}' failed: {}", topic, e);
+                        }
+                    }
+                    info!("MQTT bridge subscribed to {:?}", config.topics);
+                }
+                Ok(Event::Incoming(Packet::Publish(publish))) => {
+                    if config.publish_topic.as_deref() == Some(publish.topic.as_str()) {
+                        continue;
+                    }
+                    match message_to_input(&publish.topic, &publish.payload) {
+                        Some(input) => {
+                            notify::ingest(&state, input).await;
+                        }
+                        None => warn!("Ignoring empty MQTT message on '{}'", publish.topic),
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    warn!("MQTT connection errors: {}, retrying", e);
+                    tokio::time::sleep(RECONNECT_DELAY).await;
+                }
+            }
+        }
+    });
+
+    Ok(())
+}
+
+async fn republish_events(
+    client: AsyncClient,
+    topic: String,
+    mut rx: broadcast::Receiver<NotifyEvent>,
+) {
+    loop {
+        match rx.recv().await {
+            Ok(event) => match serde_json::to_vec(&event) {
+                Ok(payload) => {
+                    if let Err(e) = client
+                        .publish(topic.as_str(), QoS::AtLeastOnce, false, payload)
+                        .await
+                    {
+                        warn!("MQTT publish to '{}' failed: {}", topic, e);
+                    }
+                }
+                Err(e) => error!("Failed to serialize MQTT event: {}", e),
+            },
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                warn!("MQTT republisher lagged, skipped {} events", skipped);
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+/// 将 MQTT 消息映射为通知
+///
+/// 负载为 JSON 形式的 NotificationInput 时直接使用；否则整个负载作为通知内容，
+/// 主题最后一级作为设备名、完整主题作为标题。
+fn message_to_input(topic: &str, payload: &[u8]) -> Option<NotificationInput> {
+    let device = topic.rsplit('/').next().unwrap_or(topic).to_string();
+
+    if let Ok(mut input) = serde_json::from_slice::<NotificationInput>(payload) {
+        input.title.get_or_insert_with(|| topic.to_string());
+        input.device.get_or_insert(device);
+        return Some(input);
+    }
+
+    let text = String::from_utf8_lossy(payload).trim().to_string();
+    if text.is_empty() {
+        return None;
+    }
+
+    Some(NotificationInput {
+        notify: text,
+        title: Some(topic.to_string()),
+        device: Some(device),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_payload_uses_topic() {
+        let input = message_to_input("rutify/garage-door", b"opened").unwrap();
+        assert_eq!(input.notify, "opened");
+        assert_eq!(input.title.as_deref(), Some("rutify/garage-door"));
+        assert_eq!(input.device.as_deref(), Some("garage-door"));
+    }
+
+    #[test]
+    fn test_json_payload_keeps_fields() {
+        let input = message_to_input(
+            "tasmota/plug1",
+            br#"{"notify":"power high","title":"Plug alert"}"#,
+        )
+        .unwrap();
+        assert_eq!(input.notify, "power high");
+        assert_eq!(input.title.as_deref(), Some("Plug alert"));
+        assert_eq!(input.device.as_deref(), Some("plug1"));
+    }
+
+    #[test]
+    fn test_empty_payload_is_ignored() {
+        assert!(message_to_input("rutify/x", b"  ").is_none());
+    }
+}
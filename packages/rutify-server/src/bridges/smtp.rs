@@ -0,0 +1,566 @@
+use anyhow::{Context, Result};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use rutify_core::NotificationInput;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::io::{
+    AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader,
+};
+use tokio::net::TcpListener;
+use tracing::{debug, info, warn};
+
+use crate::bootstrap::source::ConfigSource;
+use crate::services::auth::auth::authorize_notify_token;
+use crate::services::notify;
+use crate::state::AppState;
+
+/// 单封邮件的最大字节数
+const MAX_MESSAGE_SIZE: usize = 1024 * 1024;
+/// 单行命令的最大长度
+const MAX_LINE_LEN: u64 = 4096;
+/// 通知正文的最大字符数，超出部分截断
+const MAX_NOTIFY_CHARS: usize = 4000;
+
+/// SMTP 网关配置
+#[derive(Debug, Clone)]
+pub(crate) struct SmtpConfig {
+    pub addr: SocketAddr,
+    pub hostname: String,
+    /// 收件地址的本地部分，支持 `<mailbox>+<notify_token>@任意域名`
+    pub mailbox: String,
+    /// 允许的发件人，可写完整地址或 `@domain`
+    pub allowed_senders: Vec<String>,
+}
+
+impl SmtpConfig {
+    /// 读取 `RUTIFY_SMTP_*` 环境变量，未设置 `RUTIFY_SMTP_ADDR` 时返回 `None`
+    pub(crate) fn from_source(source: &ConfigSource) -> Result<Option<Self>> {
+        let Ok(addr_text) = source.var("RUTIFY_SMTP_ADDR") else {
+            return Ok(None);
+        };
+        let addr: SocketAddr = addr_text
+            .parse()
+            .with_context(|| format!("invalid RUTIFY_SMTP_ADDR: {addr_text}"))?;
+
+        let allowed_senders = source
+            .var("RUTIFY_SMTP_ALLOWED_SENDERS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|sender| sender.trim().to_ascii_lowercase())
+            .filter(|sender| !sender.is_empty())
+            .collect();
+
+        Ok(Some(Self {
+            addr,
+            hostname: source
+                .var("RUTIFY_SMTP_HOSTNAME")
+                .unwrap_or_else(|_| "rutify".to_string()),
+            mailbox: source
+                .var("RUTIFY_SMTP_MAILBOX")
+                .unwrap_or_else(|_| "notify".to_string())
+                .to_ascii_lowercase(),
+            allowed_senders,
+        }))
+    }
+
+    fn sender_allowed(&self, sender: &str) -> bool {
+        let sender = sender.to_ascii_lowercase();
+        self.allowed_senders.iter().any(|allowed| {
+            if allowed.starts_with('@') {
+                sender.ends_with(allowed.as_str())
+            } else {
+                *allowed == sender
+            }
+        })
+    }
+}
+
+/// 启动 SMTP 监听
+pub(crate) async fn spawn(config: SmtpConfig, state: Arc<AppState>) -> Result<()> {
+    let listener = TcpListener::bind(config.addr)
+        .await
+        .with_context(|| format!("failed to bind SMTP {}", config.addr))?;
+    info!("SMTP gateway listening on {}", config.addr);
+
+    let config = Arc::new(config);
+    tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, peer)) => {
+                    let config = config.clone();
+                    let state = state.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = handle_session(stream, &config, &state).await {
+                            debug!("SMTP session with {} ended: {}", peer, e);
+                        }
+                    });
+                }
+                Err(e) => warn!("SMTP accept errors: {}", e),
+            }
+        }
+    });
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct Envelope {
+    sender: Option<String>,
+    authorized: bool,
+    recipients: usize,
+}
+
+async fn handle_session<S>(stream: S, config: &SmtpConfig, state: &AppState) -> Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut reader = BufReader::new(reader);
+    let mut envelope = Envelope::default();
+
+    reply(
+        &mut writer,
+        &format!("220 {} ESMTP rutify", config.hostname),
+    )
+    .await?;
+
+    loop {
+        let Some(line) = read_line(&mut reader).await? else {
+            return Ok(());
+        };
+        let (verb, arg) = line.split_once(' ').unwrap_or((line.as_str(), ""));
+
+        match verb.to_ascii_uppercase().as_str() {
+            "HELO" => reply(&mut writer, &format!("250 {}", config.hostname)).await?,
+            "EHLO" => {
+                let text = format!(
+                    "250-{}\r\n250-8BITMIME\r\n250 SIZE {}",
+                    config.hostname, MAX_MESSAGE_SIZE
+                );
+                reply(&mut writer, &text).await?;
+            }
+            "MAIL" => match parse_path(arg, "FROM:") {
+                Some(sender) => {
+                    envelope = Envelope {
+                        sender: Some(sender),
+                        ..Envelope::default()
+                    };
+                    reply(&mut writer, "250 OK").await?;
+                }
+                None => reply(&mut writer, "501 Syntax: MAIL FROM:<address>").await?,
+            },
+            "RCPT" => {
+                let Some(sender) = envelope.sender.clone() else {
+                    reply(&mut writer, "503 Need MAIL command").await?;
+                    continue;
+                };
+                let Some(recipient) = parse_path(arg, "TO:") else {
+                    reply(&mut writer, "501 Syntax: RCPT TO:<address>").await?;
+                    continue;
+                };
+                let Some(token) = match_mailbox(&recipient, &config.mailbox) else {
+                    reply(&mut writer, "550 No such mailbox").await?;
+                    continue;
+                };
+
+                let authorized = match token {
+                    Some(token) => authorize_notify_token(state, &token).await.is_ok(),
+                    None => config.sender_allowed(&sender),
+                };
+                if authorized {
+                    envelope.authorized = true;
+                    envelope.recipients += 1;
+                    reply(&mut writer, "250 OK").await?;
+                } else {
+                    warn!("SMTP rejected mail from {} to {}", sender, recipient);
+                    reply(&mut writer, "550 Not authorized").await?;
+                }
+            }
+            "DATA" => {
+                if !envelope.authorized || envelope.recipients == 0 {
+                    reply(&mut writer, "503 Need RCPT command").await?;
+                    continue;
+                }
+                reply(&mut writer, "354 End data with <CR><LF>.<CR><LF>").await?;
+
+                let Some(raw) = read_data(&mut reader).await? else {
+                    reply(&mut writer, "552 Message too large").await?;
+                    envelope = Envelope::default();
+                    continue;
+                };
+                let sender = envelope.sender.take().unwrap_or_default();
+                notify::ingest(state, message_to_input(&raw, &sender)).await;
+                envelope = Envelope::default();
+                reply(&mut writer, "250 OK: queued").await?;
+            }
+            "RSET" => {
+                envelope = Envelope::default();
+                reply(&mut writer, "250 OK").await?;
+            }
+            "NOOP" => reply(&mut writer, "250 OK").await?,
+            "QUIT" => {
+                reply(&mut writer, "221 Bye").await?;
+                return Ok(());
+            }
+            _ => reply(&mut writer, "502 Command not implemented").await?,
+        }
+    }
+}
+
+async fn reply<W: AsyncWrite + Unpin>(writer: &mut W, text: &str) -> std::io::Result<()> {
+    writer.write_all(text.as_bytes()).await?;
+    writer.write_all(b"\r\n").await?;
+    writer.flush().await
+}
+
+async fn read_line<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<Option<String>> {
+    let mut buf = Vec::new();
+    let len = (&mut *reader)
+        .take(MAX_LINE_LEN)
+        .read_until(b'\n', &mut buf)
+        .await?;
+    if len == 0 {
+        return Ok(None);
+    }
+    let line = String::from_utf8_lossy(&buf);
+    Ok(Some(line.trim_end_matches(['\r', '\n']).to_string()))
+}
+
+/// 读取 DATA 段直到单独的 `.` 行，超出大小限制时返回 `None`（仍会读完整个 DATA 段）
+async fn read_data<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<Option<String>> {
+    let mut data = String::new();
+    let mut too_large = false;
+    loop {
+        let Some(line) = read_line(reader).await? else {
+            anyhow::bail!("connection closed during DATA");
+        };
+        if line == "." {
+            break;
+        }
+        if too_large {
+            continue;
+        }
+        let line = line.strip_prefix('.').unwrap_or(&line);
+        data.push_str(line);
+        data.push_str("\r\n");
+        too_large = data.len() > MAX_MESSAGE_SIZE;
+    }
+    Ok((!too_large).then_some(data))
+}
+
+/// 从 `FROM:<a@b>` / `TO:<a@b> SIZE=123` 中取出地址
+fn parse_path(arg: &str, prefix: &str) -> Option<String> {
+    let arg = arg.trim();
+    if arg.len() < prefix.len() || !arg[..prefix.len()].eq_ignore_ascii_case(prefix) {
+        return None;
+    }
+    let rest = arg[prefix.len()..].trim_start();
+    let address = match rest.strip_prefix('<') {
+        Some(rest) => rest.split_once('>')?.0,
+        None => rest.split_whitespace().next().unwrap_or(""),
+    };
+    Some(address.to_string())
+}
+
+/// 校验收件地址，返回可选的加号地址 Token
+///
+/// `None` 表示地址不匹配，`Some(None)` 为普通地址，`Some(Some(token))` 为 `mailbox+token@...`
+fn match_mailbox(recipient: &str, mailbox: &str) -> Option<Option<String>> {
+    let (local, _domain) = recipient.rsplit_once('@')?;
+    match local.split_once('+') {
+        Some((name, token)) if name.eq_ignore_ascii_case(mailbox) && !token.is_empty() => {
+            Some(Some(token.to_string()))
+        }
+        None if local.eq_ignore_ascii_case(mailbox) => Some(None),
+        _ => None,
+    }
+}
+
+/// 邮件主题作为标题、正文作为通知内容、发件人作为设备名
+fn message_to_input(raw: &str, sender: &str) -> NotificationInput {
+    let (headers, body) = split_message(raw);
+    let subject = header_value(&headers, "subject")
+        .map(|subject| decode_encoded_words(&subject))
+        .filter(|subject| !subject.trim().is_empty());
+
+    let mut text = extract_text(&headers, body).trim().to_string();
+    if text.is_empty() {
+        text = subject
+            .clone()
+            .unwrap_or_else(|| "(empty email)".to_string());
+    }
+    if text.chars().count() > MAX_NOTIFY_CHARS {
+        text = text.chars().take(MAX_NOTIFY_CHARS).collect();
+    }
+
+    NotificationInput {
+        notify: text,
+        title: subject.map(|subject| subject.trim().to_string()),
+        device: (!sender.is_empty()).then(|| sender.to_string()),
+        ..Default::default()
+    }
+}
+
+/// 拆分邮件头与正文，并展开折行的头部
+fn split_message(raw: &str) -> (Vec<(String, String)>, &str) {
+    let (head, body) = raw
+        .split_once("\r\n\r\n")
+        .or_else(|| raw.split_once("\n\n"))
+        .unwrap_or((raw, ""));
+
+    let mut headers: Vec<(String, String)> = Vec::new();
+    for line in head.lines() {
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+    (headers, body)
+}
+
+fn header_value(headers: &[(String, String)], name: &str) -> Option<String> {
+    headers
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.clone())
+}
+
+/// 取出 `Content-Type` 的参数，如 boundary、charset
+fn content_type_param(content_type: &str, param: &str) -> Option<String> {
+    content_type.split(';').skip(1).find_map(|item| {
+        let (key, value) = item.split_once('=')?;
+        key.trim()
+            .eq_ignore_ascii_case(param)
+            .then(|| value.trim().trim_matches('"').to_string())
+    })
+}
+
+/// 提取纯文本正文；multipart 邮件优先取第一个 text/plain 部分
+fn extract_text(headers: &[(String, String)], body: &str) -> String {
+    let content_type = header_value(headers, "content-type").unwrap_or_default();
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+
+    if mime.starts_with("multipart/") {
+        let Some(boundary) = content_type_param(&content_type, "boundary") else {
+            return body.to_string();
+        };
+        let delimiter = format!("--{boundary}");
+        let parts: Vec<(Vec<(String, String)>, &str)> = body
+            .split(delimiter.as_str())
+            .skip(1)
+            .take_while(|part| !part.starts_with("--"))
+            .map(|part| split_message(part.trim_start_matches(['\r', '\n'])))
+            .collect();
+
+        let preferred = parts.iter().find(|(part_headers, _)| {
+            header_value(part_headers, "content-type")
+                .map(|value| value.to_ascii_lowercase().starts_with("text/plain"))
+                .unwrap_or(true)
+        });
+        return match preferred.or(parts.first()) {
+            Some((part_headers, part_body)) => extract_text(part_headers, part_body),
+            None => String::new(),
+        };
+    }
+
+    let encoding = header_value(headers, "content-transfer-encoding")
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let bytes = match encoding.as_str() {
+        "base64" => {
+            let compact: String = body.chars().filter(|c| !c.is_whitespace()).collect();
+            STANDARD
+                .decode(compact)
+                .unwrap_or_else(|_| body.as_bytes().to_vec())
+        }
+        "quoted-printable" => decode_quoted_printable(body, false),
+        _ => body.as_bytes().to_vec(),
+    };
+    let text = String::from_utf8_lossy(&bytes).replace("\r\n", "\n");
+
+    if mime == "text/html" {
+        strip_html(&text)
+    } else {
+        text
+    }
+}
+
+/// 解码 quoted-printable；`header` 为 true 时按 RFC 2047 Q 编码把 `_` 视为空格
+fn decode_quoted_printable(input: &str, header: bool) -> Vec<u8> {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'=' if bytes[index + 1..].starts_with(b"\r\n") => index += 3,
+            b'=' if bytes[index + 1..].starts_with(b"\n") => index += 2,
+            b'=' if index + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[index + 1..index + 3]).unwrap_or("");
+                match u8::from_str_radix(hex, 16) {
+                    Ok(value) => {
+                        out.push(value);
+                        index += 3;
+                    }
+                    Err(_) => {
+                        out.push(b'=');
+                        index += 1;
+                    }
+                }
+            }
+            b'_' if header => {
+                out.push(b' ');
+                index += 1;
+            }
+            byte => {
+                out.push(byte);
+                index += 1;
+            }
+        }
+    }
+    out
+}
+
+/// 解码 RFC 2047 编码词，如 `=?UTF-8?B?5rWL6K+V?=`
+fn decode_encoded_words(value: &str) -> String {
+    let mut out = String::new();
+    let mut rest = value;
+    let mut last_was_word = false;
+
+    while let Some(start) = rest.find("=?") {
+        let decoded = rest[start + 2..].split_once("?=").and_then(|(word, tail)| {
+            let mut fields = word.splitn(3, '?');
+            let _charset = fields.next()?;
+            let encoding = fields.next()?;
+            let text = fields.next()?;
+            let bytes = match encoding {
+                "B" | "b" => STANDARD.decode(text).ok()?,
+                "Q" | "q" => decode_quoted_printable(text, true),
+                _ => return None,
+            };
+            Some((String::from_utf8_lossy(&bytes).into_owned(), tail))
+        });
+
+        let Some((text, tail)) = decoded else {
+            out.push_str(&rest[..start + 2]);
+            rest = &rest[start + 2..];
+            last_was_word = false;
+            continue;
+        };
+
+        // 相邻编码词之间的空白需要忽略
+        let between = &rest[..start];
+        if !(last_was_word && between.trim().is_empty()) {
+            out.push_str(between);
+        }
+        out.push_str(&text);
+        rest = tail;
+        last_was_word = true;
+    }
+
+    out.push_str(rest);
+    out
+}
+
+fn strip_html(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_mailbox() {
+        assert_eq!(match_mailbox("notify@example.com", "notify"), Some(None));
+        assert_eq!(
+            match_mailbox("Notify+abc.def@example.com", "notify"),
+            Some(Some("abc.def".to_string()))
+        );
+        assert_eq!(match_mailbox("other@example.com", "notify"), None);
+        assert_eq!(match_mailbox("notify+@example.com", "notify"), None);
+    }
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(
+            parse_path("FROM:<cam@home.lan> SIZE=100", "FROM:").as_deref(),
+            Some("cam@home.lan")
+        );
+        assert_eq!(
+            parse_path("to: notify@x", "TO:").as_deref(),
+            Some("notify@x")
+        );
+        assert_eq!(parse_path("<a@b>", "FROM:"), None);
+    }
+
+    #[test]
+    fn test_sender_allowlist() {
+        let config = SmtpConfig {
+            addr: "127.0.0.1:2525".parse().unwrap(),
+            hostname: "rutify".to_string(),
+            mailbox: "notify".to_string(),
+            allowed_senders: vec!["nas@home.lan".to_string(), "@cams.lan".to_string()],
+        };
+
+        assert!(config.sender_allowed("NAS@home.lan"));
+        assert!(config.sender_allowed("door@cams.lan"));
+        assert!(!config.sender_allowed("evil@example.com"));
+    }
+
+    #[test]
+    fn test_plain_message_to_input() {
+        let raw =
+            "From: NAS <nas@home.lan>\r\nSubject: Disk\r\n  warning\r\n\r\nDisk 2 is failing\r\n";
+        let input = message_to_input(raw, "nas@home.lan");
+
+        assert_eq!(input.title.as_deref(), Some("Disk warning"));
+        assert_eq!(input.notify, "Disk 2 is failing");
+        assert_eq!(input.device.as_deref(), Some("nas@home.lan"));
+    }
+
+    #[test]
+    fn test_multipart_message_prefers_plain_text() {
+        let raw = concat!(
+            "Subject: =?UTF-8?B?5rWL6K+V?= =?UTF-8?Q?_alert?=\r\n",
+            "Content-Type: multipart/alternative; boundary=\"xyz\"\r\n",
+            "\r\n",
+            "--xyz\r\n",
+            "Content-Type: text/html\r\n",
+            "\r\n",
+            "<p>html body</p>\r\n",
+            "--xyz\r\n",
+            "Content-Type: text/plain; charset=utf-8\r\n",
+            "Content-Transfer-Encoding: quoted-printable\r\n",
+            "\r\n",
+            "Motion =E2=9C=93 detected=\r\n at door\r\n",
+            "--xyz--\r\n",
+        );
+        let input = message_to_input(raw, "cam@cams.lan");
+
+        assert_eq!(input.title.as_deref(), Some("测试 alert"));
+        assert_eq!(input.notify, "Motion ✓ detected at door");
+    }
+}
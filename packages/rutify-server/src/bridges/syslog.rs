@@ -0,0 +1,421 @@
+use anyhow::{Context, Result};
+use rutify_core::NotificationInput;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::net::{TcpListener, TcpStream, UdpSocket};
+use tracing::{debug, info, warn};
+
+use crate::bootstrap::source::ConfigSource;
+use crate::services::notify;
+use crate::state::AppState;
+
+/// TCP 单条消息的最大长度，防止异常客户端占满内存
+const MAX_MESSAGE_LEN: usize = 64 * 1024;
+
+const SEVERITY_NAMES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
+const FACILITY_NAMES: [&str; 24] = [
+    "kern",
+    "user",
+    "mail",
+    "daemon",
+    "auth",
+    "syslog",
+    "lpr",
+    "news",
+    "uucp",
+    "cron",
+    "authpriv",
+    "ftp",
+    "ntp",
+    "security",
+    "console",
+    "solaris-cron",
+    "local0",
+    "local1",
+    "local2",
+    "local3",
+    "local4",
+    "local5",
+    "local6",
+    "local7",
+];
+
+/// Syslog 监听配置
+#[derive(Debug, Clone)]
+pub(crate) struct SyslogConfig {
+    pub addr: SocketAddr,
+    /// 严重级别阈值，数值小于等于该值的消息才会转为通知
+    pub max_severity: u8,
+    /// 允许的 facility，空表示全部接受
+    pub facilities: Vec<u8>,
+}
+
+impl SyslogConfig {
+    /// 读取 `RUTIFY_SYSLOG_*` 环境变量，未设置 `RUTIFY_SYSLOG_ADDR` 时返回 `None`
+    pub(crate) fn from_source(source: &ConfigSource) -> Result<Option<Self>> {
+        let Ok(addr_text) = source.var("RUTIFY_SYSLOG_ADDR") else {
+            return Ok(None);
+        };
+        let addr: SocketAddr = addr_text
+            .parse()
+            .with_context(|| format!("invalid RUTIFY_SYSLOG_ADDR: {addr_text}"))?;
+
+        let max_severity = match source.var("RUTIFY_SYSLOG_SEVERITY") {
+            Ok(text) => parse_severity(&text)
+                .with_context(|| format!("invalid RUTIFY_SYSLOG_SEVERITY: {text}"))?,
+            Err(_) => 4,
+        };
+
+        let facilities = match source.var("RUTIFY_SYSLOG_FACILITIES") {
+            Ok(text) => text
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| {
+                    parse_facility(item)
+                        .with_context(|| format!("invalid RUTIFY_SYSLOG_FACILITIES entry: {item}"))
+                })
+                .collect::<Result<Vec<_>>>()?,
+            Err(_) => Vec::new(),
+        };
+
+        Ok(Some(Self {
+            addr,
+            max_severity,
+            facilities,
+        }))
+    }
+
+    fn accepts(&self, message: &SyslogMessage) -> bool {
+        message.severity <= self.max_severity
+            && (self.facilities.is_empty() || self.facilities.contains(&message.facility))
+    }
+}
+
+/// 在同一地址上同时启动 UDP 与 TCP syslog 监听
+pub(crate) async fn spawn(config: SyslogConfig, state: Arc<AppState>) -> Result<()> {
+    let config = Arc::new(config);
+    let socket = UdpSocket::bind(config.addr)
+        .await
+        .with_context(|| format!("failed to bind syslog UDP {}", config.addr))?;
+    let listener = TcpListener::bind(config.addr)
+        .await
+        .with_context(|| format!("failed to bind syslog TCP {}", config.addr))?;
+    info!("Syslog listener on {} (udp/tcp)", config.addr);
+
+    tokio::spawn(run_udp(socket, config.clone(), state.clone()));
+    tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, peer)) => {
+                    debug!("Syslog TCP connection from {}", peer);
+                    tokio::spawn(run_tcp(stream, config.clone(), state.clone()));
+                }
+                Err(e) => warn!("Syslog TCP accept errors: {}", e),
+            }
+        }
+    });
+
+    Ok(())
+}
+
+async fn run_udp(socket: UdpSocket, config: Arc<SyslogConfig>, state: Arc<AppState>) {
+    let mut buf = vec![0u8; MAX_MESSAGE_LEN];
+    loop {
+        match socket.recv_from(&mut buf).await {
+            Ok((len, _)) => handle_line(&buf[..len], &config, &state).await,
+            Err(e) => warn!("Syslog UDP receive errors: {}", e),
+        }
+    }
+}
+
+/// 支持 RFC 6587 的两种分帧：八位组计数（`<len> <msg>`）与换行分隔
+async fn run_tcp(stream: TcpStream, config: Arc<SyslogConfig>, state: Arc<AppState>) {
+    let mut reader = BufReader::new(stream);
+    loop {
+        let starts_with_digit = match reader.fill_buf().await {
+            Ok([]) => break,
+            Ok(buf) => buf[0].is_ascii_digit(),
+            Err(e) => {
+                warn!("Syslog TCP read errors: {}", e);
+                break;
+            }
+        };
+
+        let mut frame = Vec::new();
+        let result = if starts_with_digit {
+            read_octet_counted(&mut reader, &mut frame).await
+        } else {
+            (&mut reader)
+                .take(MAX_MESSAGE_LEN as u64)
+                .read_until(b'\n', &mut frame)
+                .await
+                .map(|_| ())
+        };
+
+        if let Err(e) = result {
+            warn!("Syslog TCP framing errors: {}", e);
+            break;
+        }
+        handle_line(&frame, &config, &state).await;
+    }
+}
+
+async fn read_octet_counted(
+    reader: &mut BufReader<TcpStream>,
+    frame: &mut Vec<u8>,
+) -> std::io::Result<()> {
+    let mut len_text = Vec::new();
+    (&mut *reader)
+        .take(8)
+        .read_until(b' ', &mut len_text)
+        .await?;
+    let len = std::str::from_utf8(&len_text)
+        .ok()
+        .and_then(|text| text.trim().parse::<usize>().ok())
+        .filter(|len| *len <= MAX_MESSAGE_LEN)
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "bad frame length"))?;
+
+    frame.resize(len, 0);
+    reader.read_exact(frame).await?;
+    Ok(())
+}
+
+async fn handle_line(raw: &[u8], config: &SyslogConfig, state: &AppState) {
+    let line = String::from_utf8_lossy(raw);
+    let line = line.trim_end_matches(['\r', '\n', '\0']);
+    if line.is_empty() {
+        return;
+    }
+
+    let Some(message) = parse_message(line) else {
+        debug!("Ignoring unparsable syslog line: {}", line);
+        return;
+    };
+    if config.accepts(&message) {
+        notify::ingest(state, message.into_input()).await;
+    }
+}
+
+/// 解析后的 syslog 消息
+#[derive(Debug, PartialEq)]
+struct SyslogMessage {
+    facility: u8,
+    severity: u8,
+    hostname: Option<String>,
+    app_name: Option<String>,
+    message: String,
+}
+
+impl SyslogMessage {
+    fn into_input(self) -> NotificationInput {
+        let severity = SEVERITY_NAMES[self.severity as usize];
+        let title = match self.app_name {
+            Some(app) => format!("{app} [{severity}]"),
+            None => format!("syslog [{severity}]"),
+        };
+        NotificationInput {
+            notify: self.message,
+            title: Some(title),
+            device: self.hostname,
+            ..Default::default()
+        }
+    }
+}
+
+/// 解析 RFC 5424 消息，兼容常见的 RFC 3164（BSD）格式
+fn parse_message(line: &str) -> Option<SyslogMessage> {
+    let rest = line.strip_prefix('<')?;
+    let (pri_text, rest) = rest.split_once('>')?;
+    if pri_text.is_empty() || pri_text.len() > 3 {
+        return None;
+    }
+    let pri: u8 = pri_text.parse().ok().filter(|pri| *pri <= 191)?;
+    let facility = pri / 8;
+    let severity = pri % 8;
+
+    let (hostname, app_name, message) = match rest.strip_prefix("1 ") {
+        Some(rest) => parse_rfc5424_body(rest)?,
+        None => parse_rfc3164_body(rest),
+    };
+
+    Some(SyslogMessage {
+        facility,
+        severity,
+        hostname,
+        app_name,
+        message,
+    })
+}
+
+fn nil_value(field: &str) -> Option<String> {
+    (field != "-" && !field.is_empty()).then(|| field.to_string())
+}
+
+/// `TIMESTAMP HOSTNAME APP-NAME PROCID MSGID STRUCTURED-DATA [MSG]`
+fn parse_rfc5424_body(rest: &str) -> Option<(Option<String>, Option<String>, String)> {
+    let mut parts = rest.splitn(6, ' ');
+    let _timestamp = parts.next()?;
+    let hostname = nil_value(parts.next()?);
+    let app_name = nil_value(parts.next()?);
+    let _procid = parts.next()?;
+    let _msgid = parts.next()?;
+    let rest = parts.next().unwrap_or("");
+
+    let message = skip_structured_data(rest)?;
+    let message = message.strip_prefix('\u{feff}').unwrap_or(message);
+    Some((hostname, app_name, message.trim().to_string()))
+}
+
+/// 跳过 STRUCTURED-DATA 段，返回其后的 MSG
+fn skip_structured_data(rest: &str) -> Option<&str> {
+    if let Some(message) = rest.strip_prefix('-') {
+        return Some(message);
+    }
+
+    let bytes = rest.as_bytes();
+    let mut index = 0;
+    while bytes.get(index) == Some(&b'[') {
+        let mut in_quotes = false;
+        loop {
+            index += 1;
+            match *bytes.get(index)? {
+                b'\\' if in_quotes => index += 1,
+                b'"' => in_quotes = !in_quotes,
+                b']' if !in_quotes => break,
+                _ => {}
+            }
+        }
+        index += 1;
+    }
+
+    (index > 0).then(|| &rest[index..])
+}
+
+/// `Mmm dd hh:mm:ss HOSTNAME TAG[PID]: MSG`，时间戳缺失时退化为整行作为消息
+fn parse_rfc3164_body(rest: &str) -> (Option<String>, Option<String>, String) {
+    let has_timestamp = rest.len() > 16
+        && rest.is_char_boundary(16)
+        && rest.as_bytes()[3] == b' '
+        && rest.as_bytes()[9] == b':'
+        && rest.as_bytes()[12] == b':'
+        && rest.as_bytes()[15] == b' ';
+    if !has_timestamp {
+        return (None, None, rest.trim().to_string());
+    }
+
+    let rest = &rest[16..];
+    let (hostname, rest) = rest.split_once(' ').unwrap_or((rest, ""));
+    let (app_name, message) = match rest.split_once(": ") {
+        Some((tag, message)) if !tag.contains(' ') => {
+            let app = tag.split('[').next().unwrap_or(tag);
+            (nil_value(app), message)
+        }
+        _ => (None, rest),
+    };
+
+    (nil_value(hostname), app_name, message.trim().to_string())
+}
+
+fn parse_severity(text: &str) -> Result<u8> {
+    let text = text.trim().to_ascii_lowercase();
+    if let Ok(value) = text.parse::<u8>() {
+        anyhow::ensure!(value < 8, "severity must be 0-7");
+        return Ok(value);
+    }
+    let alias = match text.as_str() {
+        "emergency" | "panic" => "emerg",
+        "critical" => "crit",
+        "error" => "err",
+        "warn" => "warning",
+        other => other,
+    };
+    SEVERITY_NAMES
+        .iter()
+        .position(|name| *name == alias)
+        .map(|index| index as u8)
+        .context("unknown severity")
+}
+
+fn parse_facility(text: &str) -> Result<u8> {
+    let text = text.trim().to_ascii_lowercase();
+    if let Ok(value) = text.parse::<u8>() {
+        anyhow::ensure!(value < 24, "facility must be 0-23");
+        return Ok(value);
+    }
+    FACILITY_NAMES
+        .iter()
+        .position(|name| *name == text)
+        .map(|index| index as u8)
+        .context("unknown facility")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rfc5424_message() {
+        let message = parse_message(
+            r#"<165>1 2003-10-11T22:14:15.003Z router1 evntslog - ID47 [exampleSDID@32473 iut="3" eventSource="App\]"] An application event log entry"#,
+        )
+        .unwrap();
+
+        assert_eq!(message.facility, 20);
+        assert_eq!(message.severity, 5);
+        assert_eq!(message.hostname.as_deref(), Some("router1"));
+        assert_eq!(message.app_name.as_deref(), Some("evntslog"));
+        assert_eq!(message.message, "An application event log entry");
+    }
+
+    #[test]
+    fn test_parse_rfc5424_without_structured_data() {
+        let message = parse_message("<11>1 - nas01 - - - - disk failure").unwrap();
+
+        assert_eq!(message.severity, 3);
+        assert_eq!(message.hostname.as_deref(), Some("nas01"));
+        assert_eq!(message.app_name, None);
+        assert_eq!(message.message, "disk failure");
+    }
+
+    #[test]
+    fn test_parse_rfc3164_message() {
+        let message =
+            parse_message("<34>Oct 11 22:14:15 mymachine su[123]: 'su root' failed on /dev/pts/8")
+                .unwrap();
+
+        assert_eq!(message.facility, 4);
+        assert_eq!(message.severity, 2);
+        assert_eq!(message.hostname.as_deref(), Some("mymachine"));
+        assert_eq!(message.app_name.as_deref(), Some("su"));
+        assert_eq!(message.message, "'su root' failed on /dev/pts/8");
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_priority() {
+        assert!(parse_message("no priority").is_none());
+        assert!(parse_message("<999>1 - - - - - - x").is_none());
+    }
+
+    #[test]
+    fn test_filter_by_severity_and_facility() {
+        let config = SyslogConfig {
+            addr: "127.0.0.1:5514".parse().unwrap(),
+            max_severity: parse_severity("warning").unwrap(),
+            facilities: vec![parse_facility("local4").unwrap()],
+        };
+
+        let error = parse_message("<163>1 - host app - - - boom").unwrap();
+        let info = parse_message("<166>1 - host app - - - fine").unwrap();
+        let kern = parse_message("<3>1 - host app - - - other facility").unwrap();
+
+        assert!(config.accepts(&error));
+        assert!(!config.accepts(&info));
+        assert!(!config.accepts(&kern));
+        assert_eq!(error.into_input().title.as_deref(), Some("app [err]"));
+    }
+}
@@ -0,0 +1,110 @@
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::Query;
+use sea_orm::{QueryOrder, Set};
+
+use super::encryption;
+
+/// 截断通知的完整正文附件名
+pub(crate) const FULL_BODY_NAME: &str = "body.txt";
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "attachments")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment_flag)]
+    pub id: i32,
+    pub notify_id: i32,
+    pub name: String,
+    pub content_type: String,
+    /// 明文内容的字节数
+    pub size: i64,
+    /// 开启静态加密时为密文，读取时经 [`encryption::open`] 解密
+    #[sea_orm(column_type = "Text")]
+    pub content: String,
+    pub created_at: chrono::DateTime<Utc>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub(crate) fn into_info(self) -> rutify_core::AttachmentInfo {
+        rutify_core::AttachmentInfo {
+            id: self.id,
+            notify_id: rutify_core::NotifyId(self.notify_id),
+            name: self.name,
+            content_type: self.content_type,
+            size: self.size.max(0) as u64,
+            created_at: self.created_at,
+        }
+    }
+}
+
+/// 保存被截断通知的完整正文
+pub(crate) async fn insert_full_body(
+    db: &DatabaseConnection,
+    notify_id: i32,
+    body: String,
+) -> Result<Model, DbErr> {
+    ActiveModel {
+        notify_id: Set(notify_id),
+        name: Set(FULL_BODY_NAME.to_string()),
+        content_type: Set("text/plain; charset=utf-8".to_string()),
+        size: Set(body.len() as i64),
+        content: Set(encryption::seal(body)),
+        created_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+}
+
+/// 通知的附件，按创建顺序排列
+pub(crate) async fn list_for_notify(
+    db: &DatabaseConnection,
+    notify_id: i32,
+) -> Result<Vec<Model>, DbErr> {
+    Entity::find()
+        .filter(Column::NotifyId.eq(notify_id))
+        .order_by_asc(Column::Id)
+        .all(db)
+        .await
+}
+
+pub(crate) async fn find_for_notify(
+    db: &DatabaseConnection,
+    notify_id: i32,
+    id: i32,
+) -> Result<Option<Model>, DbErr> {
+    Entity::find_by_id(id)
+        .filter(Column::NotifyId.eq(notify_id))
+        .one(db)
+        .await
+}
+
+pub(crate) async fn delete_for_notify(
+    db: &DatabaseConnection,
+    notify_id: i32,
+) -> Result<u64, DbErr> {
+    let deleted = Entity::delete_many()
+        .filter(Column::NotifyId.eq(notify_id))
+        .exec(db)
+        .await?;
+    Ok(deleted.rows_affected)
+}
+
+/// 删除所属通知已不存在的附件（批量清理、过期清理后遗留），返回删除数
+pub(crate) async fn delete_orphaned(db: &DatabaseConnection) -> Result<u64, DbErr> {
+    let deleted = Entity::delete_many()
+        .filter(
+            Column::NotifyId.not_in_subquery(
+                Query::select()
+                    .column(super::notifies::Column::Id)
+                    .from(super::Notifies)
+                    .to_owned(),
+            ),
+        )
+        .exec(db)
+        .await?;
+    Ok(deleted.rows_affected)
+}
@@ -0,0 +1,69 @@
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+use sea_orm::{QueryOrder, QuerySelect, Set};
+
+/// 管理员的一次修改操作，由 `/api/admin` 下的非 GET 请求记录
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "audit_log")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment_flag)]
+    pub id: i32,
+    /// 执行操作的管理员用户名
+    pub actor: String,
+    pub method: String,
+    pub path: String,
+    /// 响应状态码
+    pub status: i32,
+    pub created_at: chrono::DateTime<Utc>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub(crate) fn into_entry(self) -> rutify_core::AuditEntry {
+        rutify_core::AuditEntry {
+            id: self.id,
+            actor: self.actor,
+            method: self.method,
+            path: self.path,
+            status: self.status.clamp(0, u16::MAX as i32) as u16,
+            created_at: self.created_at,
+        }
+    }
+}
+
+pub(crate) async fn record(
+    db: &DatabaseConnection,
+    actor: &str,
+    method: &str,
+    path: &str,
+    status: u16,
+) -> Result<(), DbErr> {
+    ActiveModel {
+        actor: Set(actor.to_string()),
+        method: Set(method.to_string()),
+        path: Set(path.to_string()),
+        status: Set(i32::from(status)),
+        created_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    Ok(())
+}
+
+/// 最近的 `limit` 条记录，按时间顺序排列；设置 `after_id` 时只返回更新的记录
+pub(crate) async fn recent(
+    db: &DatabaseConnection,
+    limit: u64,
+    after_id: Option<i32>,
+) -> Result<Vec<Model>, DbErr> {
+    let mut query = Entity::find();
+    if let Some(after_id) = after_id {
+        query = query.filter(Column::Id.gt(after_id));
+    }
+    let mut entries = query.order_by_desc(Column::Id).limit(limit).all(db).await?;
+    entries.reverse();
+    Ok(entries)
+}
@@ -0,0 +1,30 @@
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "check_results")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment_flag)]
+    pub id: i32,
+    pub check_id: i32,
+    pub checked_at: chrono::DateTime<Utc>,
+    pub up: bool,
+    pub status_code: Option<i32>,
+    pub latency_ms: i64,
+    pub error: Option<String>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub(crate) fn into_result(self) -> rutify_core::CheckResult {
+        rutify_core::CheckResult {
+            checked_at: self.checked_at,
+            up: self.up,
+            status_code: self.status_code.and_then(|code| u16::try_from(code).ok()),
+            latency_ms: self.latency_ms.max(0) as u64,
+            error: self.error,
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use chrono::Utc;
+use rutify_core::CheckStatus;
+use sea_orm::entity::prelude::*;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "checks")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment_flag)]
+    pub id: i32,
+    pub name: String,
+    pub url: String,
+    pub interval_secs: i64,
+    pub timeout_secs: i64,
+    /// 期望的状态码，为空时任意 2xx 视为正常
+    pub expected_status: Option<i32>,
+    /// `unknown`、`up` 或 `down`
+    pub status: String,
+    pub priority: i32,
+    pub channel: Option<String>,
+    pub enabled: bool,
+    pub last_checked_at: Option<chrono::DateTime<Utc>>,
+    pub last_latency_ms: Option<i64>,
+    /// 创建者用户名
+    pub created_by: Option<String>,
+    pub created_at: chrono::DateTime<Utc>,
+    pub updated_at: chrono::DateTime<Utc>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub(crate) fn check_status(&self) -> CheckStatus {
+        CheckStatus::parse(&self.status).unwrap_or(CheckStatus::Unknown)
+    }
+
+    pub(crate) fn into_check(self) -> rutify_core::UptimeCheck {
+        rutify_core::UptimeCheck {
+            id: self.id,
+            status: self.check_status(),
+            name: self.name,
+            url: self.url,
+            interval_secs: self.interval_secs.max(0) as u64,
+            timeout_secs: self.timeout_secs.max(0) as u64,
+            expected_status: self
+                .expected_status
+                .and_then(|code| u16::try_from(code).ok()),
+            priority: self.priority.clamp(1, rutify_core::MAX_PRIORITY as i32) as u8,
+            channel: self.channel,
+            enabled: self.enabled,
+            last_checked_at: self.last_checked_at,
+            last_latency_ms: self.last_latency_ms.map(|ms| ms.max(0) as u64),
+            created_at: self.created_at,
+        }
+    }
+}
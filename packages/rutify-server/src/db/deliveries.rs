@@ -0,0 +1,144 @@
+use chrono::Utc;
+use rutify_core::{DeliveryChannel, DeliveryReceipt, NotifyId, TokenId};
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::Query;
+use sea_orm::{Condition, QueryOrder, Set};
+
+/// 通知经某个渠道送达订阅者或外部目标的记录
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "deliveries")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment_flag)]
+    pub id: i32,
+    pub notify_id: i32,
+    /// [`DeliveryChannel`] 的名称
+    pub channel: String,
+    pub target: String,
+    pub token_id: Option<i32>,
+    /// 投递失败时的错误信息，成功时为空
+    pub error: Option<String>,
+    pub delivered_at: chrono::DateTime<Utc>,
+    /// 订阅者回复确认的时间
+    pub acked_at: Option<chrono::DateTime<Utc>>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    /// 无法识别的渠道名按 Webhook 处理
+    pub(crate) fn into_receipt(self) -> DeliveryReceipt {
+        DeliveryReceipt {
+            id: self.id,
+            notify_id: NotifyId(self.notify_id),
+            channel: DeliveryChannel::parse(&self.channel).unwrap_or(DeliveryChannel::Webhook),
+            target: self.target,
+            token_id: self.token_id.map(TokenId),
+            error: self.error,
+            delivered_at: self.delivered_at,
+            acked_at: self.acked_at,
+        }
+    }
+}
+
+/// 待写入的送达记录
+#[derive(Debug, Clone)]
+pub(crate) struct NewDelivery {
+    pub(crate) notify_id: i32,
+    pub(crate) channel: DeliveryChannel,
+    pub(crate) target: String,
+    pub(crate) token_id: Option<i32>,
+    pub(crate) error: Option<String>,
+}
+
+pub(crate) async fn record(db: &DatabaseConnection, delivery: NewDelivery) -> Result<(), DbErr> {
+    ActiveModel {
+        notify_id: Set(delivery.notify_id),
+        channel: Set(delivery.channel.as_str().to_string()),
+        target: Set(delivery.target),
+        token_id: Set(delivery.token_id),
+        error: Set(delivery.error),
+        delivered_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    Ok(())
+}
+
+/// 记录订阅者对通知的确认，返回是否为首次确认
+///
+/// 优先更新同一 Token（匿名连接按用途）的送达记录；找不到时补写一条，
+/// 例如送达记录还在写入时确认已经到达。
+pub(crate) async fn acknowledge(
+    db: &DatabaseConnection,
+    delivery: NewDelivery,
+) -> Result<bool, DbErr> {
+    let subscriber = match delivery.token_id {
+        Some(token_id) => Condition::all().add(Column::TokenId.eq(token_id)),
+        None => Condition::all()
+            .add(Column::TokenId.is_null())
+            .add(Column::Target.eq(delivery.target.as_str())),
+    };
+    let existing = Entity::find()
+        .filter(Column::NotifyId.eq(delivery.notify_id))
+        .filter(Column::Channel.eq(delivery.channel.as_str()))
+        .filter(subscriber)
+        .order_by_asc(Column::Id)
+        .one(db)
+        .await?;
+
+    let now = Utc::now();
+    match existing {
+        Some(model) if model.acked_at.is_some() => Ok(false),
+        Some(model) => {
+            let mut active: ActiveModel = model.into();
+            active.acked_at = Set(Some(now));
+            active.update(db).await?;
+            Ok(true)
+        }
+        None => {
+            ActiveModel {
+                notify_id: Set(delivery.notify_id),
+                channel: Set(delivery.channel.as_str().to_string()),
+                target: Set(delivery.target),
+                token_id: Set(delivery.token_id),
+                error: Set(delivery.error),
+                delivered_at: Set(now),
+                acked_at: Set(Some(now)),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?;
+            Ok(true)
+        }
+    }
+}
+
+/// 通知的全部送达记录，按时间顺序排列
+pub(crate) async fn list_for_notify(
+    db: &DatabaseConnection,
+    notify_id: i32,
+) -> Result<Vec<Model>, DbErr> {
+    Entity::find()
+        .filter(Column::NotifyId.eq(notify_id))
+        .order_by_asc(Column::Id)
+        .all(db)
+        .await
+}
+
+/// 删除所属通知已不存在的记录
+pub(crate) async fn delete_orphaned(db: &DatabaseConnection) -> Result<u64, DbErr> {
+    let deleted = Entity::delete_many()
+        .filter(
+            Column::NotifyId.not_in_subquery(
+                Query::select()
+                    .column(super::notifies::Column::Id)
+                    .from(super::Notifies)
+                    .to_owned(),
+            ),
+        )
+        .exec(db)
+        .await?;
+    Ok(deleted.rows_affected)
+}
@@ -0,0 +1,258 @@
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use anyhow::{Context, anyhow, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set, TransactionTrait,
+};
+use std::io::Write;
+use std::path::Path;
+use std::sync::OnceLock;
+use tracing::warn;
+
+use crate::bootstrap::source::ConfigSource;
+use crate::db::{attachments, notifies};
+
+/// 加密值的前缀；没有前缀的值按明文读取（开启加密前写入的旧数据）
+const PREFIX: &str = "enc:v1:";
+
+const NONCE_LEN: usize = 12;
+
+/// 无法解密时返回给客户端的占位内容
+const UNREADABLE: &str = "[encrypted]";
+
+/// 重新加密时每批读取的行数
+const REKEY_BATCH: u64 = 500;
+
+static CIPHER: OnceLock<Aes256Gcm> = OnceLock::new();
+
+/// 读取 `RUTIFY_ENCRYPTION_KEY_FILE` 指定的密钥，未设置时不加密
+pub(crate) fn key_from_source(source: &ConfigSource) -> anyhow::Result<Option<Aes256Gcm>> {
+    match source.var("RUTIFY_ENCRYPTION_KEY_FILE") {
+        Ok(path) => load_key(Path::new(path.trim())).map(Some),
+        Err(_) => Ok(None),
+    }
+}
+
+/// 密钥文件内容为 32 字节密钥的 base64 编码
+pub(crate) fn load_key(path: &Path) -> anyhow::Result<Aes256Gcm> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read key file {}", path.display()))?;
+    let bytes = STANDARD
+        .decode(text.trim())
+        .with_context(|| format!("key file {} is not valid base64", path.display()))?;
+    if bytes.len() != 32 {
+        bail!(
+            "key file {} must contain a 32-byte key, got {} bytes",
+            path.display(),
+            bytes.len()
+        );
+    }
+    Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&bytes)))
+}
+
+/// 生成新的随机密钥并写入文件，文件已存在时报错；Unix 下权限为 0600
+pub(crate) fn generate_key_file(path: &Path) -> anyhow::Result<()> {
+    let key = Aes256Gcm::generate_key(OsRng);
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("failed to create key file {}", path.display()))?;
+    writeln!(file, "{}", STANDARD.encode(key))?;
+    Ok(())
+}
+
+/// 设置进程内使用的密钥，启动时调用一次
+pub(crate) fn init(cipher: Aes256Gcm) {
+    if CIPHER.set(cipher).is_err() {
+        warn!("Encryption key already initialized");
+    }
+}
+
+/// 是否已配置静态加密密钥
+pub(crate) fn enabled() -> bool {
+    CIPHER.get().is_some()
+}
+
+/// 写入数据库前加密，未配置密钥时原样返回
+pub(crate) fn seal(value: String) -> String {
+    match CIPHER.get() {
+        Some(cipher) => seal_with(cipher, &value),
+        None => value,
+    }
+}
+
+/// 读取后解密；未配置密钥或没有前缀时原样返回，无法解密时返回占位内容
+pub(crate) fn open(value: String) -> String {
+    if !value.starts_with(PREFIX) {
+        return value;
+    }
+    match open_with(CIPHER.get(), &value) {
+        Ok(plain) => plain,
+        Err(e) => {
+            warn!("Failed to decrypt stored value: {:#}", e);
+            UNREADABLE.to_string()
+        }
+    }
+}
+
+fn seal_with(cipher: &Aes256Gcm, plain: &str) -> String {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    // 只有明文超过 AES-GCM 的长度上限（约 64 GiB）时才会失败
+    let ciphertext = cipher
+        .encrypt(&nonce, plain.as_bytes())
+        .expect("AES-GCM encryption failed");
+    let mut bytes = nonce.to_vec();
+    bytes.extend_from_slice(&ciphertext);
+    format!("{PREFIX}{}", STANDARD.encode(bytes))
+}
+
+/// `cipher` 为空时数据从未加密，所有值（包括恰好以前缀开头的明文）按明文返回
+fn open_with(cipher: Option<&Aes256Gcm>, value: &str) -> anyhow::Result<String> {
+    let (Some(cipher), Some(encoded)) = (cipher, value.strip_prefix(PREFIX)) else {
+        return Ok(value.to_string());
+    };
+    let bytes = STANDARD
+        .decode(encoded)
+        .context("invalid ciphertext encoding")?;
+    if bytes.len() < NONCE_LEN {
+        bail!("ciphertext is too short");
+    }
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+    let plain = cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow!("decryption failed, wrong key?"))?;
+    String::from_utf8(plain).context("decrypted value is not UTF-8")
+}
+
+/// 用旧密钥解密、新密钥重新加密
+fn convert(
+    old: Option<&Aes256Gcm>,
+    new: Option<&Aes256Gcm>,
+    value: &str,
+) -> anyhow::Result<String> {
+    let plain = open_with(old, value)?;
+    Ok(match new {
+        Some(cipher) => seal_with(cipher, &plain),
+        None => plain,
+    })
+}
+
+/// 重新加密所有通知的标题、正文与附件，返回（通知数，附件数）
+///
+/// `old` 为当前密钥（从未加密过时为空），`new` 为空时解密为明文。在单个事务中执行，
+/// 任一行无法用旧密钥解密时整体回滚。
+pub(crate) async fn rekey(
+    db: &DatabaseConnection,
+    old: Option<&Aes256Gcm>,
+    new: Option<&Aes256Gcm>,
+) -> anyhow::Result<(u64, u64)> {
+    let txn = db.begin().await?;
+
+    let mut notify_count = 0;
+    let mut last_id = 0;
+    loop {
+        let batch = notifies::Entity::find()
+            .filter(notifies::Column::Id.gt(last_id))
+            .order_by_asc(notifies::Column::Id)
+            .limit(REKEY_BATCH)
+            .all(&txn)
+            .await?;
+        let Some(last) = batch.last() else {
+            break;
+        };
+        last_id = last.id;
+        for notify in batch {
+            let id = notify.id;
+            let body = convert(old, new, &notify.notify)
+                .with_context(|| format!("failed to re-encrypt notify {id}"))?;
+            let title = notify
+                .title
+                .as_deref()
+                .map(|title| convert(old, new, title))
+                .transpose()
+                .with_context(|| format!("failed to re-encrypt notify {id}"))?;
+            let mut active_model: notifies::ActiveModel = notify.into();
+            active_model.notify = Set(body);
+            active_model.title = Set(title);
+            active_model.update(&txn).await?;
+            notify_count += 1;
+        }
+    }
+
+    let mut attachment_count = 0;
+    let mut last_id = 0;
+    loop {
+        let batch = attachments::Entity::find()
+            .filter(attachments::Column::Id.gt(last_id))
+            .order_by_asc(attachments::Column::Id)
+            .limit(REKEY_BATCH)
+            .all(&txn)
+            .await?;
+        let Some(last) = batch.last() else {
+            break;
+        };
+        last_id = last.id;
+        for attachment in batch {
+            let id = attachment.id;
+            let content = convert(old, new, &attachment.content)
+                .with_context(|| format!("failed to re-encrypt attachment {id}"))?;
+            let mut active_model: attachments::ActiveModel = attachment.into();
+            active_model.content = Set(content);
+            active_model.update(&txn).await?;
+            attachment_count += 1;
+        }
+    }
+
+    txn.commit().await?;
+    Ok((notify_count, attachment_count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open() {
+        let key = Aes256Gcm::new(&Aes256Gcm::generate_key(OsRng));
+        let other = Aes256Gcm::new(&Aes256Gcm::generate_key(OsRng));
+
+        let sealed = seal_with(&key, "disk full on web1");
+        assert!(sealed.starts_with(PREFIX));
+        assert!(!sealed.contains("disk full"));
+        assert_ne!(sealed, seal_with(&key, "disk full on web1"));
+        assert_eq!(open_with(Some(&key), &sealed).unwrap(), "disk full on web1");
+
+        assert!(open_with(Some(&other), &sealed).is_err());
+        assert_eq!(
+            open_with(None, "legacy plaintext").unwrap(),
+            "legacy plaintext"
+        );
+
+        let rotated = convert(Some(&key), Some(&other), &sealed).unwrap();
+        assert_eq!(
+            open_with(Some(&other), &rotated).unwrap(),
+            "disk full on web1"
+        );
+        assert_eq!(
+            convert(Some(&other), None, &rotated).unwrap(),
+            "disk full on web1"
+        );
+    }
+
+    #[test]
+    fn test_plaintext_with_prefix_without_key() {
+        let plain = format!("{PREFIX}not actually encrypted");
+        assert_eq!(open(plain.clone()), plain);
+        assert_eq!(open_with(None, &plain).unwrap(), plain);
+
+        let key = Aes256Gcm::new(&Aes256Gcm::generate_key(OsRng));
+        let sealed = convert(None, Some(&key), &plain).unwrap();
+        assert_eq!(open_with(Some(&key), &sealed).unwrap(), plain);
+    }
+}
@@ -0,0 +1,26 @@
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "escalations")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment_flag)]
+    pub id: i32,
+    pub name: String,
+    /// 优先级不低于该值的通知才会触发
+    pub min_priority: i32,
+    /// 通知未确认多久后触发
+    pub after_minutes: i32,
+    /// `rebroadcast`、`bump` 或 `forward`
+    pub action: String,
+    /// `forward` 的目标渠道：`telegram`、`email` 或 `webhook`
+    pub sink: Option<String>,
+    /// 渠道目标：Telegram chat id、邮箱地址或 Webhook URL
+    pub target: Option<String>,
+    pub enabled: bool,
+    pub created_at: chrono::DateTime<Utc>,
+    pub updated_at: chrono::DateTime<Utc>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,4 +1,16 @@
-use crate::db::migration::m00001_create_all_tables;
+use crate::db::migration::{
+    m00001_create_all_tables, m00002_create_plugins_table, m00003_add_notify_metadata,
+    m00004_create_escalations, m00005_create_oncall, m00006_create_silences,
+    m00007_add_delivery_tracking, m00008_add_notify_channel, m00009_add_query_indices,
+    m00010_add_user_email_verified, m00011_create_monitors, m00012_create_checks,
+    m00013_add_notify_expires_at, m00014_add_notify_pinned, m00015_add_notify_progress,
+    m00016_add_notify_device_source, m00017_create_attachments, m00018_add_notify_link_previews,
+    m00019_add_notify_signature, m00020_create_leases, m00021_add_user_disabled,
+    m00022_create_audit_log, m00023_create_deliveries, m00024_add_delivery_acked_at,
+    m00025_add_notify_correlation_id, m00026_create_unifiedpush_registrations,
+    m00027_create_web_push_subscriptions, m00028_create_user_preferences,
+    m00029_add_notify_starred, m00030_add_notify_token_hash,
+};
 use sea_orm::DbConn;
 use sea_orm_migration::{MigrationTrait, MigratorTrait};
 
@@ -11,6 +23,37 @@ pub struct Migrator;
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m00001_create_all_tables::Migration)]
+        vec![
+            Box::new(m00001_create_all_tables::Migration),
+            Box::new(m00002_create_plugins_table::Migration),
+            Box::new(m00003_add_notify_metadata::Migration),
+            Box::new(m00004_create_escalations::Migration),
+            Box::new(m00005_create_oncall::Migration),
+            Box::new(m00006_create_silences::Migration),
+            Box::new(m00007_add_delivery_tracking::Migration),
+            Box::new(m00008_add_notify_channel::Migration),
+            Box::new(m00009_add_query_indices::Migration),
+            Box::new(m00010_add_user_email_verified::Migration),
+            Box::new(m00011_create_monitors::Migration),
+            Box::new(m00012_create_checks::Migration),
+            Box::new(m00013_add_notify_expires_at::Migration),
+            Box::new(m00014_add_notify_pinned::Migration),
+            Box::new(m00015_add_notify_progress::Migration),
+            Box::new(m00016_add_notify_device_source::Migration),
+            Box::new(m00017_create_attachments::Migration),
+            Box::new(m00018_add_notify_link_previews::Migration),
+            Box::new(m00019_add_notify_signature::Migration),
+            Box::new(m00020_create_leases::Migration),
+            Box::new(m00021_add_user_disabled::Migration),
+            Box::new(m00022_create_audit_log::Migration),
+            Box::new(m00023_create_deliveries::Migration),
+            Box::new(m00024_add_delivery_acked_at::Migration),
+            Box::new(m00025_add_notify_correlation_id::Migration),
+            Box::new(m00026_create_unifiedpush_registrations::Migration),
+            Box::new(m00027_create_web_push_subscriptions::Migration),
+            Box::new(m00028_create_user_preferences::Migration),
+            Box::new(m00029_add_notify_starred::Migration),
+            Box::new(m00030_add_notify_token_hash::Migration),
+        ]
     }
 }
@@ -0,0 +1,69 @@
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{Condition, Set};
+
+/// 多实例之间的租约，持有者在到期前续约，到期后其他节点可以接管
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "leases")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub name: String,
+    /// 持有租约的节点 ID
+    pub holder: String,
+    pub expires_at: chrono::DateTime<Utc>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// 取得或续约租约：租约不存在、已过期或已由 `holder` 持有时成功
+pub(crate) async fn try_acquire(
+    db: &DatabaseConnection,
+    name: &str,
+    holder: &str,
+    ttl: chrono::Duration,
+) -> Result<bool, DbErr> {
+    let now = Utc::now();
+    let expires_at = now + ttl;
+    // 条件更新保证同一时刻只有一个节点能接管
+    let result = Entity::update_many()
+        .col_expr(Column::Holder, Expr::value(holder))
+        .col_expr(Column::ExpiresAt, Expr::value(expires_at))
+        .filter(Column::Name.eq(name))
+        .filter(
+            Condition::any()
+                .add(Column::Holder.eq(holder))
+                .add(Column::ExpiresAt.lt(now)),
+        )
+        .exec(db)
+        .await?;
+    if result.rows_affected > 0 {
+        return Ok(true);
+    }
+
+    let lease = ActiveModel {
+        name: Set(name.to_string()),
+        holder: Set(holder.to_string()),
+        expires_at: Set(expires_at),
+    };
+    let inserted = Entity::insert(lease)
+        .on_conflict(OnConflict::column(Column::Name).do_nothing().to_owned())
+        .exec_without_returning(db)
+        .await?;
+    Ok(inserted > 0)
+}
+
+/// 主动放弃租约，其他节点无需等待到期即可接管
+pub(crate) async fn release(
+    db: &DatabaseConnection,
+    name: &str,
+    holder: &str,
+) -> Result<(), DbErr> {
+    Entity::delete_many()
+        .filter(Column::Name.eq(name))
+        .filter(Column::Holder.eq(holder))
+        .exec(db)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use crate::db;
+use sea_orm::sea_query::Table;
+use sea_orm::{DbErr, DeriveMigrationName};
+use sea_orm_migration::{MigrationTrait, SchemaManager, schema};
+
+#[derive(DeriveMigrationName)]
+pub(crate) struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 创建 plugins 表（通知过滤/转换脚本）
+        let plugins_table = Table::create()
+            .table(db::Plugins)
+            .if_not_exists()
+            .col(schema::pk_auto(db::Plugins::COLUMN.id))
+            .col(schema::string_uniq(db::Plugins::COLUMN.name))
+            .col(schema::text(db::Plugins::COLUMN.script))
+            .col(schema::boolean(db::Plugins::COLUMN.enabled).default(true))
+            .col(schema::integer(db::Plugins::COLUMN.sort_order).default(0))
+            .col(schema::date(db::Plugins::COLUMN.created_at))
+            .col(schema::date(db::Plugins::COLUMN.updated_at))
+            .to_owned();
+
+        manager.create_table(plugins_table).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
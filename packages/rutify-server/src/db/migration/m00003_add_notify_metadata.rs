@@ -0,0 +1,39 @@
+use crate::db;
+use sea_orm::sea_query::Table;
+use sea_orm::{DbErr, DeriveMigrationName};
+use sea_orm_migration::{MigrationTrait, SchemaManager, schema};
+
+#[derive(DeriveMigrationName)]
+pub(crate) struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // sqlite 每条 ALTER TABLE 只能新增一列，逐列添加
+        let columns = [
+            schema::integer(db::Notifies::COLUMN.priority)
+                .default(3)
+                .take(),
+            schema::string_null(db::Notifies::COLUMN.tags),
+            schema::string_null(db::Notifies::COLUMN.group_key),
+            schema::date_null(db::Notifies::COLUMN.resolved_at),
+        ];
+
+        for column in columns {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(db::Notifies)
+                        .add_column_if_not_exists(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use crate::db;
+use sea_orm::sea_query::Table;
+use sea_orm::{DbErr, DeriveMigrationName};
+use sea_orm_migration::{MigrationTrait, SchemaManager, schema};
+
+#[derive(DeriveMigrationName)]
+pub(crate) struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 通知确认时间，未确认的高优先级通知会按升级规则处理
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(db::Notifies)
+                    .add_column_if_not_exists(schema::date_null(db::Notifies::COLUMN.acked_at))
+                    .to_owned(),
+            )
+            .await?;
+
+        // 创建 escalations 表（未确认通知的升级规则）
+        let escalations_table = Table::create()
+            .table(db::Escalations)
+            .if_not_exists()
+            .col(schema::pk_auto(db::Escalations::COLUMN.id))
+            .col(schema::string(db::Escalations::COLUMN.name))
+            .col(schema::integer(db::Escalations::COLUMN.min_priority).default(4))
+            .col(schema::integer(db::Escalations::COLUMN.after_minutes))
+            .col(schema::string(db::Escalations::COLUMN.action))
+            .col(schema::string_null(db::Escalations::COLUMN.sink))
+            .col(schema::string_null(db::Escalations::COLUMN.target))
+            .col(schema::boolean(db::Escalations::COLUMN.enabled).default(true))
+            .col(schema::date(db::Escalations::COLUMN.created_at))
+            .col(schema::date(db::Escalations::COLUMN.updated_at))
+            .to_owned();
+
+        manager.create_table(escalations_table).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
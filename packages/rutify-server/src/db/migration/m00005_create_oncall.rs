@@ -0,0 +1,56 @@
+use crate::db;
+use sea_orm::sea_query::Table;
+use sea_orm::{DbErr, DeriveMigrationName};
+use sea_orm_migration::{MigrationTrait, SchemaManager, schema};
+
+#[derive(DeriveMigrationName)]
+pub(crate) struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 定向投递的用户名（值班通知）
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(db::Notifies)
+                    .add_column_if_not_exists(schema::string_null(db::Notifies::COLUMN.recipient))
+                    .to_owned(),
+            )
+            .await?;
+
+        // 创建 oncall_schedules 表（值班排班）
+        let schedules_table = Table::create()
+            .table(db::OncallSchedules)
+            .if_not_exists()
+            .col(schema::pk_auto(db::OncallSchedules::COLUMN.id))
+            .col(schema::string_uniq(db::OncallSchedules::COLUMN.name))
+            .col(schema::integer(db::OncallSchedules::COLUMN.rotation_hours).default(168))
+            .col(schema::date(db::OncallSchedules::COLUMN.starts_at))
+            .col(schema::date(db::OncallSchedules::COLUMN.created_at))
+            .col(schema::date(db::OncallSchedules::COLUMN.updated_at))
+            .to_owned();
+
+        manager.create_table(schedules_table).await?;
+
+        // 创建 oncall_members 表（排班成员及轮值顺序）
+        let members_table = Table::create()
+            .table(db::OncallMembers)
+            .if_not_exists()
+            .col(schema::pk_auto(db::OncallMembers::COLUMN.id))
+            .col(schema::integer(db::OncallMembers::COLUMN.schedule_id))
+            .col(schema::uuid(db::OncallMembers::COLUMN.user_id))
+            .col(schema::integer(db::OncallMembers::COLUMN.position))
+            .col(schema::string_null(db::OncallMembers::COLUMN.sink))
+            .col(schema::string_null(db::OncallMembers::COLUMN.target))
+            .to_owned();
+
+        manager.create_table(members_table).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
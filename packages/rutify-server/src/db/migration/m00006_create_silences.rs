@@ -0,0 +1,34 @@
+use crate::db;
+use sea_orm::sea_query::Table;
+use sea_orm::{DbErr, DeriveMigrationName};
+use sea_orm_migration::{MigrationTrait, SchemaManager, schema};
+
+#[derive(DeriveMigrationName)]
+pub(crate) struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 创建 silences 表（带过期时间的静默规则）
+        let silences_table = Table::create()
+            .table(db::Silences)
+            .if_not_exists()
+            .col(schema::pk_auto(db::Silences::COLUMN.id))
+            .col(schema::string_null(db::Silences::COLUMN.device))
+            .col(schema::string_null(db::Silences::COLUMN.channel))
+            .col(schema::string_null(db::Silences::COLUMN.tag))
+            .col(schema::string_null(db::Silences::COLUMN.comment))
+            .col(schema::string_null(db::Silences::COLUMN.created_by))
+            .col(schema::date(db::Silences::COLUMN.expires_at))
+            .col(schema::date(db::Silences::COLUMN.created_at))
+            .to_owned();
+
+        manager.create_table(silences_table).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use crate::db;
+use sea_orm::sea_query::{Table, TableAlterStatement};
+use sea_orm::{DbErr, DeriveMigrationName};
+use sea_orm_migration::{MigrationTrait, SchemaManager, schema};
+
+#[derive(DeriveMigrationName)]
+pub(crate) struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // sqlite 每条 ALTER TABLE 只能新增一列，逐列添加
+        let statements: [TableAlterStatement; 3] = [
+            Table::alter()
+                .table(db::Notifies)
+                .add_column_if_not_exists(
+                    schema::boolean(db::Notifies::COLUMN.silenced)
+                        .default(false)
+                        .take(),
+                )
+                .to_owned(),
+            Table::alter()
+                .table(db::Tokens)
+                .add_column_if_not_exists(schema::integer_null(
+                    db::Tokens::COLUMN.last_delivered_id,
+                ))
+                .to_owned(),
+            Table::alter()
+                .table(db::Tokens)
+                .add_column_if_not_exists(schema::date_null(db::Tokens::COLUMN.last_delivered_at))
+                .to_owned(),
+        ];
+
+        for statement in statements {
+            manager.alter_table(statement).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use crate::db;
+use sea_orm::sea_query::{Index, IndexCreateStatement};
+use sea_orm::{DbErr, DeriveMigrationName};
+use sea_orm_migration::{MigrationTrait, SchemaManager};
+
+#[derive(DeriveMigrationName)]
+pub(crate) struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 列表、统计按接收时间与设备过滤；每次鉴权都按哈希查 Token，过期清理按过期时间扫描
+        let indices: [IndexCreateStatement; 4] = [
+            Index::create()
+                .name("idx_notifies_received_at")
+                .table(db::Notifies)
+                .col(db::Notifies::COLUMN.received_at)
+                .if_not_exists()
+                .to_owned(),
+            Index::create()
+                .name("idx_notifies_device")
+                .table(db::Notifies)
+                .col(db::Notifies::COLUMN.device)
+                .if_not_exists()
+                .to_owned(),
+            Index::create()
+                .name("idx_tokens_token_hash")
+                .table(db::Tokens)
+                .col(db::Tokens::COLUMN.token_hash)
+                .if_not_exists()
+                .to_owned(),
+            Index::create()
+                .name("idx_tokens_expires_at")
+                .table(db::Tokens)
+                .col(db::Tokens::COLUMN.expires_at)
+                .if_not_exists()
+                .to_owned(),
+        ];
+
+        for index in indices {
+            manager.create_index(index).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
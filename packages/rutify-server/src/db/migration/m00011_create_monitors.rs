@@ -0,0 +1,38 @@
+use crate::db;
+use sea_orm::sea_query::Table;
+use sea_orm::{DbErr, DeriveMigrationName};
+use sea_orm_migration::{MigrationTrait, SchemaManager, schema};
+
+#[derive(DeriveMigrationName)]
+pub(crate) struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 创建 monitors 表（心跳监控）
+        let monitors_table = Table::create()
+            .table(db::Monitors)
+            .if_not_exists()
+            .col(schema::pk_auto(db::Monitors::COLUMN.id))
+            .col(schema::string_uniq(db::Monitors::COLUMN.name))
+            .col(schema::big_integer(db::Monitors::COLUMN.interval_secs))
+            .col(schema::big_integer(db::Monitors::COLUMN.grace_secs))
+            .col(schema::string(db::Monitors::COLUMN.status).default("new"))
+            .col(schema::integer(db::Monitors::COLUMN.priority).default(4))
+            .col(schema::string_null(db::Monitors::COLUMN.channel))
+            .col(schema::date_null(db::Monitors::COLUMN.last_beat_at))
+            .col(schema::date_null(db::Monitors::COLUMN.due_at))
+            .col(schema::string_null(db::Monitors::COLUMN.created_by))
+            .col(schema::date(db::Monitors::COLUMN.created_at))
+            .col(schema::date(db::Monitors::COLUMN.updated_at))
+            .to_owned();
+
+        manager.create_table(monitors_table).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use crate::db;
+use sea_orm::sea_query::{Index, Table};
+use sea_orm::{DbErr, DeriveMigrationName};
+use sea_orm_migration::{MigrationTrait, SchemaManager, schema};
+
+#[derive(DeriveMigrationName)]
+pub(crate) struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 创建 checks 表（服务端主动执行的 HTTP 检查）
+        let checks_table = Table::create()
+            .table(db::Checks)
+            .if_not_exists()
+            .col(schema::pk_auto(db::Checks::COLUMN.id))
+            .col(schema::string(db::Checks::COLUMN.name))
+            .col(schema::string(db::Checks::COLUMN.url))
+            .col(schema::big_integer(db::Checks::COLUMN.interval_secs).default(60))
+            .col(schema::big_integer(db::Checks::COLUMN.timeout_secs).default(10))
+            .col(schema::integer_null(db::Checks::COLUMN.expected_status))
+            .col(schema::string(db::Checks::COLUMN.status).default("unknown"))
+            .col(schema::integer(db::Checks::COLUMN.priority).default(4))
+            .col(schema::string_null(db::Checks::COLUMN.channel))
+            .col(schema::boolean(db::Checks::COLUMN.enabled).default(true))
+            .col(schema::date_null(db::Checks::COLUMN.last_checked_at))
+            .col(schema::big_integer_null(db::Checks::COLUMN.last_latency_ms))
+            .col(schema::string_null(db::Checks::COLUMN.created_by))
+            .col(schema::date(db::Checks::COLUMN.created_at))
+            .col(schema::date(db::Checks::COLUMN.updated_at))
+            .to_owned();
+        manager.create_table(checks_table).await?;
+
+        // 创建 check_results 表（检查历史）
+        let results_table = Table::create()
+            .table(db::CheckResults)
+            .if_not_exists()
+            .col(schema::pk_auto(db::CheckResults::COLUMN.id))
+            .col(schema::integer(db::CheckResults::COLUMN.check_id))
+            .col(schema::date(db::CheckResults::COLUMN.checked_at))
+            .col(schema::boolean(db::CheckResults::COLUMN.up))
+            .col(schema::integer_null(db::CheckResults::COLUMN.status_code))
+            .col(schema::big_integer(db::CheckResults::COLUMN.latency_ms))
+            .col(schema::string_null(db::CheckResults::COLUMN.error))
+            .to_owned();
+        manager.create_table(results_table).await?;
+
+        // 历史按检查倒序读取，过期清理按时间扫描
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_check_results_check_id_checked_at")
+                    .table(db::CheckResults)
+                    .col(db::CheckResults::COLUMN.check_id)
+                    .col(db::CheckResults::COLUMN.checked_at)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
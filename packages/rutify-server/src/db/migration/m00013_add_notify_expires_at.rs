@@ -0,0 +1,37 @@
+use crate::db;
+use sea_orm::sea_query::{Index, Table};
+use sea_orm::{DbErr, DeriveMigrationName};
+use sea_orm_migration::{MigrationTrait, SchemaManager, schema};
+
+#[derive(DeriveMigrationName)]
+pub(crate) struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(db::Notifies)
+                    .add_column_if_not_exists(schema::date_null(db::Notifies::COLUMN.expires_at))
+                    .to_owned(),
+            )
+            .await?;
+
+        // 定期清理按过期时间扫描
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_notifies_expires_at")
+                    .table(db::Notifies)
+                    .col(db::Notifies::COLUMN.expires_at)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
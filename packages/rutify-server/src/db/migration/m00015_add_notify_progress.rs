@@ -0,0 +1,42 @@
+use crate::db;
+use sea_orm::sea_query::{Table, TableAlterStatement};
+use sea_orm::{DbErr, DeriveMigrationName};
+use sea_orm_migration::{MigrationTrait, SchemaManager, schema};
+
+#[derive(DeriveMigrationName)]
+pub(crate) struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // sqlite 每条 ALTER TABLE 只能新增一列，逐列添加
+        let statements: [TableAlterStatement; 3] = [
+            Table::alter()
+                .table(db::Notifies)
+                .add_column_if_not_exists(schema::big_integer_null(
+                    db::Notifies::COLUMN.progress_current,
+                ))
+                .to_owned(),
+            Table::alter()
+                .table(db::Notifies)
+                .add_column_if_not_exists(schema::big_integer_null(
+                    db::Notifies::COLUMN.progress_total,
+                ))
+                .to_owned(),
+            Table::alter()
+                .table(db::Notifies)
+                .add_column_if_not_exists(schema::string_null(db::Notifies::COLUMN.progress_status))
+                .to_owned(),
+        ];
+
+        for statement in statements {
+            manager.alter_table(statement).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use crate::db;
+use sea_orm::sea_query::{Index, Table};
+use sea_orm::{DbErr, DeriveMigrationName};
+use sea_orm_migration::{MigrationTrait, SchemaManager, schema};
+
+#[derive(DeriveMigrationName)]
+pub(crate) struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 创建 attachments 表（被截断通知的完整正文）
+        let attachments_table = Table::create()
+            .table(db::Attachments)
+            .if_not_exists()
+            .col(schema::pk_auto(db::Attachments::COLUMN.id))
+            .col(schema::integer(db::Attachments::COLUMN.notify_id))
+            .col(schema::string(db::Attachments::COLUMN.name))
+            .col(schema::string(db::Attachments::COLUMN.content_type))
+            .col(schema::big_integer(db::Attachments::COLUMN.size))
+            .col(schema::text(db::Attachments::COLUMN.content))
+            .col(schema::date(db::Attachments::COLUMN.created_at))
+            .to_owned();
+        manager.create_table(attachments_table).await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_attachments_notify_id")
+                    .table(db::Attachments)
+                    .col(db::Attachments::COLUMN.notify_id)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
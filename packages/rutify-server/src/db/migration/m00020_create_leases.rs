@@ -0,0 +1,31 @@
+use crate::db;
+use sea_orm::sea_query::Table;
+use sea_orm::{DbErr, DeriveMigrationName};
+use sea_orm_migration::{MigrationTrait, SchemaManager, schema};
+
+#[derive(DeriveMigrationName)]
+pub(crate) struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 创建 leases 表（多实例选主），到期时间需要精确到秒
+        let leases_table = Table::create()
+            .table(db::Leases)
+            .if_not_exists()
+            .col(schema::string(db::Leases::COLUMN.name).primary_key())
+            .col(schema::string(db::Leases::COLUMN.holder))
+            .col(schema::timestamp_with_time_zone(
+                db::Leases::COLUMN.expires_at,
+            ))
+            .to_owned();
+
+        manager.create_table(leases_table).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
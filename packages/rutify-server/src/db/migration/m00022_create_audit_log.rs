@@ -0,0 +1,34 @@
+use crate::db;
+use sea_orm::sea_query::Table;
+use sea_orm::{DbErr, DeriveMigrationName};
+use sea_orm_migration::{MigrationTrait, SchemaManager, schema};
+
+#[derive(DeriveMigrationName)]
+pub(crate) struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 创建 audit_log 表（管理员的修改操作）
+        let audit_log_table = Table::create()
+            .table(db::AuditLog)
+            .if_not_exists()
+            .col(schema::pk_auto(db::AuditLog::COLUMN.id))
+            .col(schema::string(db::AuditLog::COLUMN.actor))
+            .col(schema::string(db::AuditLog::COLUMN.method))
+            .col(schema::string(db::AuditLog::COLUMN.path))
+            .col(schema::integer(db::AuditLog::COLUMN.status))
+            .col(schema::timestamp_with_time_zone(
+                db::AuditLog::COLUMN.created_at,
+            ))
+            .to_owned();
+
+        manager.create_table(audit_log_table).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
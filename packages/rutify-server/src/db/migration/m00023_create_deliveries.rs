@@ -0,0 +1,45 @@
+use crate::db;
+use sea_orm::sea_query::{Index, Table};
+use sea_orm::{DbErr, DeriveMigrationName};
+use sea_orm_migration::{MigrationTrait, SchemaManager, schema};
+
+#[derive(DeriveMigrationName)]
+pub(crate) struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 创建 deliveries 表（通知的送达回执）
+        let deliveries_table = Table::create()
+            .table(db::Deliveries)
+            .if_not_exists()
+            .col(schema::pk_auto(db::Deliveries::COLUMN.id))
+            .col(schema::integer(db::Deliveries::COLUMN.notify_id))
+            .col(schema::string(db::Deliveries::COLUMN.channel))
+            .col(schema::string(db::Deliveries::COLUMN.target))
+            .col(schema::integer_null(db::Deliveries::COLUMN.token_id))
+            .col(schema::text_null(db::Deliveries::COLUMN.error))
+            .col(schema::timestamp_with_time_zone(
+                db::Deliveries::COLUMN.delivered_at,
+            ))
+            .to_owned();
+        manager.create_table(deliveries_table).await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_deliveries_notify_id")
+                    .table(db::Deliveries)
+                    .col(db::Deliveries::COLUMN.notify_id)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use crate::db;
+use sea_orm::sea_query::{Index, Table};
+use sea_orm::{DbErr, DeriveMigrationName};
+use sea_orm_migration::{MigrationTrait, SchemaManager, schema};
+
+#[derive(DeriveMigrationName)]
+pub(crate) struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 创建 unifiedpush_registrations 表（移动应用登记的推送地址）
+        let registrations_table = Table::create()
+            .table(db::UnifiedPushRegistrations)
+            .if_not_exists()
+            .col(schema::pk_auto(db::UnifiedPushRegistrations::COLUMN.id))
+            .col(schema::string_uniq(
+                db::UnifiedPushRegistrations::COLUMN.endpoint,
+            ))
+            .col(schema::string_null(
+                db::UnifiedPushRegistrations::COLUMN.instance,
+            ))
+            .col(schema::integer(
+                db::UnifiedPushRegistrations::COLUMN.token_id,
+            ))
+            .col(schema::timestamp_with_time_zone(
+                db::UnifiedPushRegistrations::COLUMN.created_at,
+            ))
+            .col(schema::timestamp_with_time_zone_null(
+                db::UnifiedPushRegistrations::COLUMN.last_success_at,
+            ))
+            .col(schema::integer(db::UnifiedPushRegistrations::COLUMN.failures).default(0))
+            .col(schema::text_null(
+                db::UnifiedPushRegistrations::COLUMN.last_error,
+            ))
+            .to_owned();
+        manager.create_table(registrations_table).await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_unifiedpush_registrations_token_id")
+                    .table(db::UnifiedPushRegistrations)
+                    .col(db::UnifiedPushRegistrations::COLUMN.token_id)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use crate::db;
+use sea_orm::sea_query::{Index, Table};
+use sea_orm::{DbErr, DeriveMigrationName};
+use sea_orm_migration::{MigrationTrait, SchemaManager, schema};
+
+#[derive(DeriveMigrationName)]
+pub(crate) struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 创建 web_push_subscriptions 表（浏览器的 Web Push 订阅）
+        let subscriptions_table = Table::create()
+            .table(db::WebPushSubscriptions)
+            .if_not_exists()
+            .col(schema::pk_auto(db::WebPushSubscriptions::COLUMN.id))
+            .col(schema::string_uniq(
+                db::WebPushSubscriptions::COLUMN.endpoint,
+            ))
+            .col(schema::string(db::WebPushSubscriptions::COLUMN.p256dh))
+            .col(schema::string(db::WebPushSubscriptions::COLUMN.auth))
+            .col(schema::uuid(db::WebPushSubscriptions::COLUMN.user_id))
+            .col(schema::timestamp_with_time_zone(
+                db::WebPushSubscriptions::COLUMN.created_at,
+            ))
+            .col(schema::timestamp_with_time_zone_null(
+                db::WebPushSubscriptions::COLUMN.last_success_at,
+            ))
+            .col(schema::integer(db::WebPushSubscriptions::COLUMN.failures).default(0))
+            .col(schema::text_null(
+                db::WebPushSubscriptions::COLUMN.last_error,
+            ))
+            .to_owned();
+        manager.create_table(subscriptions_table).await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_web_push_subscriptions_user_id")
+                    .table(db::WebPushSubscriptions)
+                    .col(db::WebPushSubscriptions::COLUMN.user_id)
+                    .if_not_exists()
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use crate::db;
+use sea_orm::sea_query::Table;
+use sea_orm::{DbErr, DeriveMigrationName};
+use sea_orm_migration::{MigrationTrait, SchemaManager, schema};
+
+#[derive(DeriveMigrationName)]
+pub(crate) struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // 创建 user_preferences 表（用户的通知偏好）
+        let preferences_table = Table::create()
+            .table(db::UserPreferences)
+            .if_not_exists()
+            .col(schema::uuid(db::UserPreferences::COLUMN.user_id).primary_key())
+            .col(schema::text(db::UserPreferences::COLUMN.settings))
+            .col(schema::string(db::UserPreferences::COLUMN.digest).default("off"))
+            .col(schema::timestamp_with_time_zone_null(
+                db::UserPreferences::COLUMN.last_digest_at,
+            ))
+            .col(schema::timestamp_with_time_zone(
+                db::UserPreferences::COLUMN.updated_at,
+            ))
+            .to_owned();
+        manager.create_table(preferences_table).await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        Ok(())
+    }
+}
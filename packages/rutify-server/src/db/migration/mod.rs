@@ -1 +1,30 @@
 pub mod m00001_create_all_tables;
+pub mod m00002_create_plugins_table;
+pub mod m00003_add_notify_metadata;
+pub mod m00004_create_escalations;
+pub mod m00005_create_oncall;
+pub mod m00006_create_silences;
+pub mod m00007_add_delivery_tracking;
+pub mod m00008_add_notify_channel;
+pub mod m00009_add_query_indices;
+pub mod m00010_add_user_email_verified;
+pub mod m00011_create_monitors;
+pub mod m00012_create_checks;
+pub mod m00013_add_notify_expires_at;
+pub mod m00014_add_notify_pinned;
+pub mod m00015_add_notify_progress;
+pub mod m00016_add_notify_device_source;
+pub mod m00017_create_attachments;
+pub mod m00018_add_notify_link_previews;
+pub mod m00019_add_notify_signature;
+pub mod m00020_create_leases;
+pub mod m00021_add_user_disabled;
+pub mod m00022_create_audit_log;
+pub mod m00023_create_deliveries;
+pub mod m00024_add_delivery_acked_at;
+pub mod m00025_add_notify_correlation_id;
+pub mod m00026_create_unifiedpush_registrations;
+pub mod m00027_create_web_push_subscriptions;
+pub mod m00028_create_user_preferences;
+pub mod m00029_add_notify_starred;
+pub mod m00030_add_notify_token_hash;
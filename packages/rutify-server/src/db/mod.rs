@@ -1,10 +1,78 @@
+pub(crate) mod attachments;
+pub(crate) mod audit_log;
+pub(crate) mod check_results;
+pub(crate) mod checks;
+pub(crate) mod deliveries;
+pub(crate) mod encryption;
+pub(crate) mod escalations;
 pub mod initialize;
+pub(crate) mod leases;
 mod migration;
+pub(crate) mod monitors;
 pub(crate) mod notifies;
+pub(crate) mod oncall_members;
+pub(crate) mod oncall_schedules;
+pub(crate) mod plugins;
+pub(crate) mod silences;
 pub mod token_ops;
 pub(crate) mod tokens;
+pub(crate) mod unifiedpush_registrations;
+pub(crate) mod user_preferences;
 pub(crate) mod users;
+pub(crate) mod web_push_subscriptions;
 
+use crate::bootstrap::source::ConfigSource;
+use sea_orm::ConnectOptions;
+use std::time::Duration;
+
+/// 连接池配置，读取 `RUTIFY_DB_MAX_CONNECTIONS`、`RUTIFY_DB_MIN_CONNECTIONS`、
+/// `RUTIFY_DB_ACQUIRE_TIMEOUT_SECS` 与 `RUTIFY_DB_SQLX_LOGGING`，未设置的项沿用 SeaORM 默认值
+pub(crate) fn connect_options(
+    source: &ConfigSource,
+    db_url: &str,
+) -> anyhow::Result<ConnectOptions> {
+    fn env<T: std::str::FromStr>(source: &ConfigSource, key: &str) -> anyhow::Result<Option<T>> {
+        match source.var(key) {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .map(Some)
+                .map_err(|_| anyhow::anyhow!("invalid {key}: {text}")),
+            Err(_) => Ok(None),
+        }
+    }
+
+    let mut options = ConnectOptions::new(db_url);
+    if let Some(max) = env::<u32>(source, "RUTIFY_DB_MAX_CONNECTIONS")? {
+        options.max_connections(max);
+    }
+    if let Some(min) = env::<u32>(source, "RUTIFY_DB_MIN_CONNECTIONS")? {
+        options.min_connections(min);
+    }
+    if let Some(secs) = env::<u64>(source, "RUTIFY_DB_ACQUIRE_TIMEOUT_SECS")? {
+        options.acquire_timeout(Duration::from_secs(secs));
+    }
+    if let Some(logging) = env::<bool>(source, "RUTIFY_DB_SQLX_LOGGING")? {
+        options.sqlx_logging(logging);
+    }
+    Ok(options)
+}
+
+pub use attachments::Entity as Attachments;
+pub use audit_log::Entity as AuditLog;
+pub use check_results::Entity as CheckResults;
+pub use checks::Entity as Checks;
+pub use deliveries::Entity as Deliveries;
+pub use escalations::Entity as Escalations;
+pub use leases::Entity as Leases;
+pub use monitors::Entity as Monitors;
 pub use notifies::Entity as Notifies;
+pub use oncall_members::Entity as OncallMembers;
+pub use oncall_schedules::Entity as OncallSchedules;
+pub use plugins::Entity as Plugins;
+pub use silences::Entity as Silences;
 pub use tokens::Entity as Tokens;
+pub use unifiedpush_registrations::Entity as UnifiedPushRegistrations;
+pub use user_preferences::Entity as UserPreferences;
 pub use users::Entity as Users;
+pub use web_push_subscriptions::Entity as WebPushSubscriptions;
@@ -0,0 +1,47 @@
+use chrono::Utc;
+use rutify_core::MonitorStatus;
+use sea_orm::entity::prelude::*;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "monitors")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment_flag)]
+    pub id: i32,
+    /// 心跳地址 `/api/monitors/{name}/beat` 中的名称
+    #[sea_orm(unique)]
+    pub name: String,
+    pub interval_secs: i64,
+    pub grace_secs: i64,
+    /// `new`、`up` 或 `down`
+    pub status: String,
+    pub priority: i32,
+    pub channel: Option<String>,
+    pub last_beat_at: Option<chrono::DateTime<Utc>>,
+    /// 最近一次心跳时间 + 间隔 + 宽限期
+    pub due_at: Option<chrono::DateTime<Utc>>,
+    /// 创建者用户名
+    pub created_by: Option<String>,
+    pub created_at: chrono::DateTime<Utc>,
+    pub updated_at: chrono::DateTime<Utc>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub(crate) fn into_monitor(self) -> rutify_core::Monitor {
+        rutify_core::Monitor {
+            id: self.id,
+            status: MonitorStatus::parse(&self.status).unwrap_or(MonitorStatus::New),
+            name: self.name,
+            interval_secs: self.interval_secs.max(0) as u64,
+            grace_secs: self.grace_secs.max(0) as u64,
+            priority: self.priority.clamp(1, rutify_core::MAX_PRIORITY as i32) as u8,
+            channel: self.channel,
+            last_beat_at: self.last_beat_at,
+            due_at: self.due_at,
+            created_by: self.created_by,
+            created_at: self.created_at,
+        }
+    }
+}
@@ -1,7 +1,16 @@
 use chrono::Utc;
-use rutify_core::NotificationData;
+use rutify_core::{
+    CountGroupBy, DeviceId, DeviceStats, LinkPreview, NotificationData, NotifyCount, NotifyId,
+    NotifyItem, NotifySignature, Progress, ProgressStatus, PurgeNotifiesRequest,
+    UpdateNotifyRequest,
+};
 use sea_orm::ActiveValue;
 use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::Expr;
+use sea_orm::{Condition, DbBackend, PaginatorTrait, QueryOrder, QuerySelect, Select};
+use std::collections::HashMap;
+
+use super::encryption;
 
 #[sea_orm::model]
 #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
@@ -9,25 +18,723 @@ use sea_orm::entity::prelude::*;
 pub struct Model {
     #[sea_orm(primary_key, auto_increment_flag)]
     pub id: i32,
+    /// 正文与标题在开启静态加密时为密文，见 [`encryption`]
     pub notify: String,
     pub title: Option<String>,
     pub device: Option<String>,
     pub received_at: chrono::DateTime<Utc>,
+    pub priority: i32,
+    /// 逗号分隔的标签
+    pub tags: Option<String>,
+    /// 外部告警的分组键，用于在恢复时定位原通知
+    pub group_key: Option<String>,
+    pub resolved_at: Option<chrono::DateTime<Utc>>,
+    pub acked_at: Option<chrono::DateTime<Utc>>,
+    /// 定向投递的用户名
+    pub recipient: Option<String>,
+    /// 命中静默规则，未广播，也不参与断线补发
+    pub silenced: bool,
+    /// 发送时指定的投递频道
+    pub channel: Option<String>,
+    /// 过期时间，过期后不再出现在默认列表中
+    pub expires_at: Option<chrono::DateTime<Utc>>,
+    /// 置顶，列表中排在最前且不参与清理
+    pub pinned: bool,
+    /// 进度通知的当前值、总量与状态，非进度通知均为空
+    pub progress_current: Option<i64>,
+    pub progress_total: Option<i64>,
+    pub progress_status: Option<String>,
+    /// 设备名的来源，见 `services::device::DeviceSource`，旧通知为空
+    pub device_source: Option<String>,
+    /// 正文中链接的预览，JSON 数组；未开启链接预览或没有链接时为空
+    #[sea_orm(column_type = "Text", nullable)]
+    pub link_previews: Option<String>,
+    /// 发送方签名的公钥与签名（均为 base64），标题或正文被修改后清空
+    pub signature_key: Option<String>,
+    pub signature: Option<String>,
+    /// 发送方提供的关联 ID
+    pub correlation_id: Option<String>,
+    /// 星标，可通过 `starred=true` 单独列出
+    pub starred: bool,
+    /// 发送时使用的通知 Token 的哈希，只有该 Token 能修改这条通知；匿名或内部产生的通知为空
+    pub token_hash: Option<String>,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
 
-pub(crate) async fn insert_new_notify(db: &DatabaseConnection, data: NotificationData) {
+impl Model {
+    pub(crate) fn into_item(self) -> NotifyItem {
+        let progress = self.progress();
+        let link_previews = self.link_previews();
+        let signature = self.signature();
+        NotifyItem {
+            id: NotifyId(self.id),
+            title: self
+                .title
+                .map(encryption::open)
+                .unwrap_or_else(|| "default title".to_string()),
+            notify: encryption::open(self.notify),
+            device: self.device.unwrap_or_else(|| "default device".to_string()),
+            priority: self.priority.clamp(1, rutify_core::MAX_PRIORITY as i32) as u8,
+            tags: split_tags(self.tags.as_deref()),
+            channel: self.channel,
+            received_at: self.received_at,
+            expires_at: self.expires_at,
+            pinned: self.pinned,
+            starred: self.starred,
+            progress,
+            device_source: self.device_source,
+            link_previews,
+            signature,
+            correlation_id: self.correlation_id,
+        }
+    }
+
+    pub(crate) fn to_data(&self) -> NotificationData {
+        NotificationData {
+            id: Some(NotifyId(self.id)),
+            notify: encryption::open(self.notify.clone()),
+            title: self
+                .title
+                .clone()
+                .map(encryption::open)
+                .unwrap_or_else(|| "default title".to_string()),
+            device: self
+                .device
+                .clone()
+                .unwrap_or_else(|| "default device".to_string()),
+            priority: self.priority.clamp(1, rutify_core::MAX_PRIORITY as i32) as u8,
+            tags: split_tags(self.tags.as_deref()),
+            recipient: self.recipient.clone(),
+            channel: self.channel.clone(),
+            expires_at: self.expires_at,
+            progress: self.progress(),
+            link_previews: self.link_previews(),
+            signature: self.signature(),
+            correlation_id: self.correlation_id.clone(),
+            token_id: None,
+        }
+    }
+
+    fn signature(&self) -> Option<NotifySignature> {
+        Some(NotifySignature {
+            public_key: self.signature_key.clone()?,
+            signature: self.signature.clone()?,
+        })
+    }
+
+    fn link_previews(&self) -> Vec<LinkPreview> {
+        self.link_previews
+            .as_deref()
+            .and_then(|json| serde_json::from_str(json).ok())
+            .unwrap_or_default()
+    }
+
+    fn progress(&self) -> Option<Progress> {
+        let status = ProgressStatus::parse(self.progress_status.as_deref()?)?;
+        Some(Progress {
+            current: self.progress_current.unwrap_or(0).max(0) as u64,
+            total: self.progress_total.unwrap_or(0).max(0) as u64,
+            status,
+        })
+    }
+}
+
+/// 签名拆分为公钥与签名两列存储
+fn signature_columns(signature: Option<NotifySignature>) -> (Option<String>, Option<String>) {
+    match signature {
+        Some(signature) => (Some(signature.public_key), Some(signature.signature)),
+        None => (None, None),
+    }
+}
+
+/// 进度拆分为三列存储
+fn progress_columns(progress: Option<Progress>) -> (Option<i64>, Option<i64>, Option<String>) {
+    match progress {
+        Some(progress) => (
+            Some(i64::try_from(progress.current).unwrap_or(i64::MAX)),
+            Some(i64::try_from(progress.total).unwrap_or(i64::MAX)),
+            Some(progress.status.as_str().to_string()),
+        ),
+        None => (None, None, None),
+    }
+}
+
+fn join_tags(tags: &[String]) -> Option<String> {
+    (!tags.is_empty()).then(|| tags.join(","))
+}
+
+fn split_tags(tags: Option<&str>) -> Vec<String> {
+    tags.unwrap_or("")
+        .split(',')
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+pub(crate) async fn insert_new_notify(
+    db: &DatabaseConnection,
+    data: NotificationData,
+    group_key: Option<String>,
+    silenced: bool,
+    device_source: &str,
+    token_hash: Option<String>,
+) -> Result<Model, DbErr> {
     let received_at = Utc::now();
+    let (progress_current, progress_total, progress_status) = progress_columns(data.progress);
+    let (signature_key, signature) = signature_columns(data.signature);
 
     ActiveModel {
         id: ActiveValue::NotSet,
-        notify: ActiveValue::Set(data.notify),
-        title: ActiveValue::Set(Some(data.title)),
+        notify: ActiveValue::Set(encryption::seal(data.notify)),
+        title: ActiveValue::Set(Some(encryption::seal(data.title))),
         device: ActiveValue::Set(Some(data.device)),
         received_at: ActiveValue::Set(received_at),
+        priority: ActiveValue::Set(data.priority as i32),
+        tags: ActiveValue::Set(join_tags(&data.tags)),
+        group_key: ActiveValue::Set(group_key),
+        resolved_at: ActiveValue::Set(None),
+        acked_at: ActiveValue::Set(None),
+        recipient: ActiveValue::Set(data.recipient),
+        channel: ActiveValue::Set(data.channel),
+        silenced: ActiveValue::Set(silenced),
+        expires_at: ActiveValue::Set(data.expires_at),
+        pinned: ActiveValue::Set(false),
+        starred: ActiveValue::Set(false),
+        progress_current: ActiveValue::Set(progress_current),
+        progress_total: ActiveValue::Set(progress_total),
+        progress_status: ActiveValue::Set(progress_status),
+        device_source: ActiveValue::Set(Some(device_source.to_string())),
+        link_previews: ActiveValue::Set(None),
+        signature_key: ActiveValue::Set(signature_key),
+        signature: ActiveValue::Set(signature),
+        correlation_id: ActiveValue::Set(data.correlation_id),
+        token_hash: ActiveValue::Set(token_hash),
     }
     .insert(db)
     .await
-    .unwrap();
+}
+
+/// 将同一分组键下最近一条未恢复的通知标记为已恢复，并用新内容覆盖
+///
+/// 没有可恢复的通知时返回 `None`。
+pub(crate) async fn resolve_open_notify(
+    db: &DatabaseConnection,
+    group_key: &str,
+    data: NotificationData,
+) -> Result<Option<Model>, DbErr> {
+    let open = Entity::find()
+        .filter(Column::GroupKey.eq(group_key))
+        .filter(Column::ResolvedAt.is_null())
+        .order_by_desc(Column::Id)
+        .one(db)
+        .await?;
+
+    let Some(open) = open else {
+        return Ok(None);
+    };
+
+    let (signature_key, signature) = signature_columns(data.signature);
+    let mut active_model: ActiveModel = open.into();
+    active_model.notify = ActiveValue::Set(encryption::seal(data.notify));
+    active_model.title = ActiveValue::Set(Some(encryption::seal(data.title)));
+    active_model.priority = ActiveValue::Set(data.priority as i32);
+    active_model.tags = ActiveValue::Set(join_tags(&data.tags));
+    active_model.link_previews = ActiveValue::Set(None);
+    active_model.signature_key = ActiveValue::Set(signature_key);
+    active_model.signature = ActiveValue::Set(signature);
+    active_model.resolved_at = ActiveValue::Set(Some(Utc::now()));
+    active_model.update(db).await.map(Some)
+}
+
+/// 最新一条通知的 ID
+pub(crate) async fn latest_id(db: &DatabaseConnection) -> Result<Option<i32>, DbErr> {
+    Ok(Entity::find()
+        .order_by_desc(Column::Id)
+        .one(db)
+        .await?
+        .map(|notify| notify.id))
+}
+
+/// 统计并读取 `after_id` 之后投递给该用户的已广播且未过期的通知，最多返回最新的 `limit` 条（按 ID 升序）
+pub(crate) async fn missed_since(
+    db: &DatabaseConnection,
+    after_id: i32,
+    username: Option<&str>,
+    limit: u64,
+) -> Result<(u64, Vec<Model>), DbErr> {
+    let mut audience = Condition::any().add(Column::Recipient.is_null());
+    if let Some(username) = username {
+        audience = audience.add(Column::Recipient.eq(username));
+    }
+    let query = Entity::find()
+        .filter(Column::Id.gt(after_id))
+        .filter(Column::Silenced.eq(false))
+        .filter(not_expired(Utc::now()))
+        .filter(audience);
+    let total = query.clone().count(db).await?;
+    let mut items = query.order_by_desc(Column::Id).limit(limit).all(db).await?;
+    items.reverse();
+    Ok((total, items))
+}
+
+/// 统计并读取 `[since, until)` 内投递给该用户的已广播通知，用于邮件摘要，最多返回最早的 `limit` 条
+///
+/// `channels` 非空时只包含未指定频道或频道在其中的通知。
+pub(crate) async fn digest_between(
+    db: &DatabaseConnection,
+    since: chrono::DateTime<Utc>,
+    until: chrono::DateTime<Utc>,
+    username: &str,
+    channels: &[String],
+    limit: u64,
+) -> Result<(u64, Vec<Model>), DbErr> {
+    let mut query = Entity::find()
+        .filter(Column::ReceivedAt.gte(since))
+        .filter(Column::ReceivedAt.lt(until))
+        .filter(Column::Silenced.eq(false))
+        .filter(
+            Condition::any()
+                .add(Column::Recipient.is_null())
+                .add(Column::Recipient.eq(username)),
+        );
+    if !channels.is_empty() {
+        query = query.filter(
+            Condition::any()
+                .add(Column::Channel.is_null())
+                .add(Column::Channel.is_in(channels.iter().cloned())),
+        );
+    }
+    let total = query.clone().count(db).await?;
+    let items = query.order_by_asc(Column::Id).limit(limit).all(db).await?;
+    Ok((total, items))
+}
+
+/// 按设备分组统计通知总数、`since` 之后的数量与最近一次通知时间，最近活跃的设备在前
+///
+/// 未设置设备的通知不参与统计。
+pub(crate) async fn device_stats(
+    db: &DatabaseConnection,
+    since: chrono::DateTime<Utc>,
+) -> Result<Vec<DeviceStats>, DbErr> {
+    let totals: Vec<(String, i64, Option<chrono::DateTime<Utc>>)> = Entity::find()
+        .select_only()
+        .column(Column::Device)
+        .column_as(Column::Id.count(), "total_count")
+        .column_as(Column::ReceivedAt.max(), "last_notified_at")
+        .filter(Column::Device.is_not_null())
+        .group_by(Column::Device)
+        .into_tuple()
+        .all(db)
+        .await?;
+    let recent: HashMap<String, i64> = Entity::find()
+        .select_only()
+        .column(Column::Device)
+        .column_as(Column::Id.count(), "today_count")
+        .filter(Column::Device.is_not_null())
+        .filter(Column::ReceivedAt.gte(since))
+        .group_by(Column::Device)
+        .into_tuple::<(String, i64)>()
+        .all(db)
+        .await?
+        .into_iter()
+        .collect();
+
+    let mut stats: Vec<DeviceStats> = totals
+        .into_iter()
+        .map(|(device, total, last_notified_at)| DeviceStats {
+            today_count: recent.get(&device).copied().unwrap_or(0).max(0) as u64,
+            device: DeviceId(device),
+            total_count: total.max(0) as u64,
+            last_notified_at,
+        })
+        .collect();
+    stats.sort_by_key(|stats| std::cmp::Reverse(stats.last_notified_at));
+    Ok(stats)
+}
+
+/// 按频道、设备或接收日期（UTC）分组统计满足条件的通知数
+///
+/// 按日期分组时日期新的在前，其余按数量从多到少排列。
+pub(crate) async fn grouped_counts(
+    db: &DatabaseConnection,
+    group_by: CountGroupBy,
+    condition: Condition,
+) -> Result<Vec<NotifyCount>, DbErr> {
+    let key = match group_by {
+        CountGroupBy::Channel => Expr::col(Column::Channel),
+        CountGroupBy::Device => Expr::col(Column::Device),
+        CountGroupBy::Day => match db.get_database_backend() {
+            DbBackend::Postgres => {
+                Expr::cust("to_char(received_at AT TIME ZONE 'UTC', 'YYYY-MM-DD')")
+            }
+            _ => Expr::cust("strftime('%Y-%m-%d', received_at)"),
+        },
+    };
+    let rows: Vec<(Option<String>, i64)> = Entity::find()
+        .select_only()
+        .column_as(key.clone(), "key")
+        .column_as(Column::Id.count(), "count")
+        .filter(condition)
+        .group_by(key)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let mut counts: Vec<NotifyCount> = rows
+        .into_iter()
+        .map(|(key, count)| NotifyCount {
+            key,
+            count: count.max(0) as u64,
+        })
+        .collect();
+    match group_by {
+        CountGroupBy::Day => counts.sort_by(|a, b| b.key.cmp(&a.key)),
+        _ => counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key))),
+    }
+    Ok(counts)
+}
+
+/// 未设置过期时间或尚未过期
+pub(crate) fn not_expired(now: chrono::DateTime<Utc>) -> Condition {
+    Condition::any()
+        .add(Column::ExpiresAt.is_null())
+        .add(Column::ExpiresAt.gt(now))
+}
+
+/// 删除已过期且未置顶的通知，返回删除数
+pub(crate) async fn purge_expired(db: &DatabaseConnection) -> Result<u64, DbErr> {
+    let deleted = Entity::delete_many()
+        .filter(Column::ExpiresAt.lte(Utc::now()))
+        .filter(Column::Pinned.eq(false))
+        .exec(db)
+        .await?;
+    Ok(deleted.rows_affected)
+}
+
+/// 清理条件对应的查询条件，置顶的通知不会被清理
+pub(crate) fn purge_condition(request: &PurgeNotifiesRequest) -> Condition {
+    let mut condition = Condition::all().add(Column::Pinned.eq(false));
+    if let Some(secs) = request.older_than_secs {
+        let cutoff = i64::try_from(secs)
+            .ok()
+            .and_then(chrono::Duration::try_seconds)
+            .and_then(|age| Utc::now().checked_sub_signed(age))
+            .unwrap_or(chrono::DateTime::<Utc>::MIN_UTC);
+        condition = condition.add(Column::ReceivedAt.lt(cutoff));
+    }
+    if let Some(device) = &request.device {
+        condition = condition.add(Column::Device.eq(device.as_str()));
+    }
+    if let Some(channel) = &request.channel {
+        condition = condition.add(Column::Channel.eq(channel.as_str()));
+    }
+    match request.read {
+        Some(true) => condition = condition.add(Column::AckedAt.is_not_null()),
+        Some(false) => condition = condition.add(Column::AckedAt.is_null()),
+        None => {}
+    }
+    condition
+}
+
+/// 按条件删除通知，`dry_run` 时只统计数量；返回（匹配数，删除数）
+pub(crate) async fn purge(
+    db: &DatabaseConnection,
+    request: &PurgeNotifiesRequest,
+) -> Result<(u64, u64), DbErr> {
+    let condition = purge_condition(request);
+    let matched = Entity::find().filter(condition.clone()).count(db).await?;
+    if request.dry_run || matched == 0 {
+        return Ok((matched, 0));
+    }
+    let deleted = Entity::delete_many().filter(condition).exec(db).await?;
+    Ok((matched, deleted.rows_affected))
+}
+
+/// 更新通知内容，未提供的字段保持不变；标题或正文变化时清除发送方签名
+///
+/// 通知不存在时返回 `None`。
+pub(crate) async fn update_notify(
+    db: &DatabaseConnection,
+    id: i32,
+    request: &UpdateNotifyRequest,
+) -> Result<Option<Model>, DbErr> {
+    let Some(notify) = Entity::find_by_id(id).one(db).await? else {
+        return Ok(None);
+    };
+
+    let mut active_model: ActiveModel = notify.into();
+    if request.notify.is_some() || request.title.is_some() {
+        active_model.signature_key = ActiveValue::Set(None);
+        active_model.signature = ActiveValue::Set(None);
+    }
+    if let Some(body) = &request.notify {
+        active_model.notify = ActiveValue::Set(encryption::seal(body.clone()));
+        active_model.link_previews = ActiveValue::Set(None);
+    }
+    if let Some(title) = &request.title {
+        active_model.title = ActiveValue::Set(Some(encryption::seal(title.clone())));
+    }
+    if let Some(priority) = request.priority {
+        active_model.priority = ActiveValue::Set(priority as i32);
+    }
+    if let Some(tags) = &request.tags {
+        active_model.tags = ActiveValue::Set(join_tags(tags));
+    }
+    if let Some(progress) = request.progress {
+        let (current, total, status) = progress_columns(Some(progress));
+        active_model.progress_current = ActiveValue::Set(current);
+        active_model.progress_total = ActiveValue::Set(total);
+        active_model.progress_status = ActiveValue::Set(status);
+    }
+    active_model.update(db).await.map(Some)
+}
+
+/// 列表排序：置顶在前，其余按接收时间倒序，时间相同时按 ID 倒序
+pub(crate) fn list_order(query: Select<Entity>) -> Select<Entity> {
+    query
+        .order_by_desc(Column::Pinned)
+        .order_by_desc(Column::ReceivedAt)
+        .order_by_desc(Column::Id)
+}
+
+/// 分页游标：上一页最后一条通知在 [`list_order`] 中的排序键
+///
+/// 按排序键而非偏移量翻页，翻页期间收到新通知不会导致重复或遗漏。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ListCursor {
+    pinned: bool,
+    received_at: chrono::DateTime<Utc>,
+    id: i32,
+}
+
+impl ListCursor {
+    pub(crate) fn after(model: &Model) -> Self {
+        Self {
+            pinned: model.pinned,
+            received_at: model.received_at,
+            id: model.id,
+        }
+    }
+
+    /// 解析 [`encode`](Self::encode) 生成的游标，格式不正确时返回 `None`
+    pub(crate) fn parse(cursor: &str) -> Option<Self> {
+        let mut parts = cursor.splitn(3, '_');
+        let pinned = match parts.next()? {
+            "0" => false,
+            "1" => true,
+            _ => return None,
+        };
+        let id = parts.next()?.parse().ok()?;
+        let received_at = chrono::DateTime::parse_from_rfc3339(parts.next()?)
+            .ok()?
+            .with_timezone(&Utc);
+        Some(Self {
+            pinned,
+            received_at,
+            id,
+        })
+    }
+
+    pub(crate) fn encode(&self) -> String {
+        format!(
+            "{}_{}_{}",
+            u8::from(self.pinned),
+            self.id,
+            self.received_at
+                .to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true)
+        )
+    }
+
+    /// 排在游标之后的通知
+    pub(crate) fn condition(&self) -> Condition {
+        Condition::any().add(Column::Pinned.lt(self.pinned)).add(
+            Condition::all().add(Column::Pinned.eq(self.pinned)).add(
+                Condition::any()
+                    .add(Column::ReceivedAt.lt(self.received_at))
+                    .add(
+                        Condition::all()
+                            .add(Column::ReceivedAt.eq(self.received_at))
+                            .add(Column::Id.lt(self.id)),
+                    ),
+            ),
+        )
+    }
+}
+
+/// 设置通知的置顶状态
+///
+/// 通知不存在时返回 `None`。
+pub(crate) async fn set_pinned(
+    db: &DatabaseConnection,
+    id: i32,
+    pinned: bool,
+) -> Result<Option<Model>, DbErr> {
+    let Some(notify) = Entity::find_by_id(id).one(db).await? else {
+        return Ok(None);
+    };
+    if notify.pinned == pinned {
+        return Ok(Some(notify));
+    }
+
+    let mut active_model: ActiveModel = notify.into();
+    active_model.pinned = ActiveValue::Set(pinned);
+    active_model.update(db).await.map(Some)
+}
+
+/// 设置通知的星标状态
+///
+/// 通知不存在时返回 `None`。
+pub(crate) async fn set_starred(
+    db: &DatabaseConnection,
+    id: i32,
+    starred: bool,
+) -> Result<Option<Model>, DbErr> {
+    let Some(notify) = Entity::find_by_id(id).one(db).await? else {
+        return Ok(None);
+    };
+    if notify.starred == starred {
+        return Ok(Some(notify));
+    }
+
+    let mut active_model: ActiveModel = notify.into();
+    active_model.starred = ActiveValue::Set(starred);
+    active_model.update(db).await.map(Some)
+}
+
+/// 保存链接预览
+///
+/// 通知不存在，或抓取期间正文已被更新（预览已过时）时返回 `None`。
+pub(crate) async fn set_link_previews(
+    db: &DatabaseConnection,
+    id: i32,
+    body: &str,
+    previews: &[LinkPreview],
+) -> Result<Option<Model>, DbErr> {
+    let Some(notify) = Entity::find_by_id(id).one(db).await? else {
+        return Ok(None);
+    };
+    if encryption::open(notify.notify.clone()) != body {
+        return Ok(None);
+    }
+
+    let json = serde_json::to_string(previews).map_err(|e| DbErr::Custom(e.to_string()))?;
+    let mut active_model: ActiveModel = notify.into();
+    active_model.link_previews = ActiveValue::Set(Some(json));
+    active_model.update(db).await.map(Some)
+}
+
+/// 确认通知，已确认的通知保持原确认时间
+///
+/// 通知不存在时返回 `None`。
+pub(crate) async fn ack_notify(db: &DatabaseConnection, id: i32) -> Result<Option<Model>, DbErr> {
+    let Some(notify) = Entity::find_by_id(id).one(db).await? else {
+        return Ok(None);
+    };
+    if notify.acked_at.is_some() {
+        return Ok(Some(notify));
+    }
+
+    let mut active_model: ActiveModel = notify.into();
+    active_model.acked_at = ActiveValue::Set(Some(Utc::now()));
+    active_model.update(db).await.map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_cursor_roundtrip() {
+        let cursor = ListCursor {
+            pinned: true,
+            received_at: Utc::now(),
+            id: 42,
+        };
+        let encoded = cursor.encode();
+        assert_eq!(ListCursor::parse(&encoded), Some(cursor));
+
+        assert_eq!(ListCursor::parse(""), None);
+        assert_eq!(ListCursor::parse("2_1_2026-01-01T00:00:00Z"), None);
+        assert_eq!(ListCursor::parse("0_x_2026-01-01T00:00:00Z"), None);
+        assert_eq!(ListCursor::parse("0_1_yesterday"), None);
+    }
+
+    #[tokio::test]
+    async fn test_set_starred() {
+        use crate::services::auth::mode::AuthPolicy;
+        use crate::state::AppState;
+
+        let state = AppState::for_tests(AuthPolicy::default()).await;
+        let data = crate::services::notify::system_data("Disk", "full".to_string());
+        let first = insert_new_notify(&state.db, data.clone(), None, false, "payload", None)
+            .await
+            .unwrap();
+        insert_new_notify(&state.db, data, None, false, "payload", None)
+            .await
+            .unwrap();
+        assert!(!first.starred);
+
+        let starred = set_starred(&state.db, first.id, true)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(starred.into_item().starred);
+        let items = Entity::find()
+            .filter(Column::Starred.eq(true))
+            .all(&state.db)
+            .await
+            .unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].id, first.id);
+
+        assert!(
+            !set_starred(&state.db, first.id, false)
+                .await
+                .unwrap()
+                .unwrap()
+                .starred
+        );
+        assert!(set_starred(&state.db, 9999, true).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_grouped_counts() {
+        use crate::services::auth::mode::AuthPolicy;
+        use crate::state::AppState;
+
+        let state = AppState::for_tests(AuthPolicy::default()).await;
+        let mut ids = Vec::new();
+        for (device, channel) in [("nas", Some("ops")), ("nas", None), ("ci", Some("ops"))] {
+            let mut data = crate::services::notify::system_data("Disk", "full".to_string());
+            data.device = device.to_string();
+            data.channel = channel.map(str::to_string);
+            let model = insert_new_notify(&state.db, data, None, false, "payload", None)
+                .await
+                .unwrap();
+            ids.push(model.id);
+        }
+        ack_notify(&state.db, ids[0]).await.unwrap();
+
+        let count = |key: Option<&str>, count: u64| NotifyCount {
+            key: key.map(str::to_string),
+            count,
+        };
+        let devices = grouped_counts(&state.db, CountGroupBy::Device, Condition::all())
+            .await
+            .unwrap();
+        assert_eq!(devices, vec![count(Some("nas"), 2), count(Some("ci"), 1)]);
+
+        let unread = Condition::all().add(Column::AckedAt.is_null());
+        let channels = grouped_counts(&state.db, CountGroupBy::Channel, unread)
+            .await
+            .unwrap();
+        assert_eq!(channels, vec![count(None, 1), count(Some("ops"), 1)]);
+
+        let days = grouped_counts(&state.db, CountGroupBy::Day, Condition::all())
+            .await
+            .unwrap();
+        let today = Utc::now().format("%Y-%m-%d").to_string();
+        assert_eq!(days, vec![count(Some(&today), 3)]);
+    }
 }
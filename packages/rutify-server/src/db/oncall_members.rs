@@ -0,0 +1,18 @@
+use sea_orm::entity::prelude::*;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "oncall_members")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment_flag)]
+    pub id: i32,
+    pub schedule_id: i32,
+    pub user_id: Uuid,
+    /// 轮值顺序，从 0 开始
+    pub position: i32,
+    /// 值班期间额外转发的渠道：`telegram`、`email` 或 `webhook`
+    pub sink: Option<String>,
+    pub target: Option<String>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,21 @@
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "oncall_schedules")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment_flag)]
+    pub id: i32,
+    /// 频道 `oncall:<name>` 对应的排班名，`default` 对应频道 `oncall`
+    #[sea_orm(unique)]
+    pub name: String,
+    /// 每人连续值班的小时数
+    pub rotation_hours: i32,
+    /// 第一轮值班开始时间
+    pub starts_at: chrono::DateTime<Utc>,
+    pub created_at: chrono::DateTime<Utc>,
+    pub updated_at: chrono::DateTime<Utc>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
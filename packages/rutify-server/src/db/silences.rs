@@ -0,0 +1,35 @@
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "silences")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment_flag)]
+    pub id: i32,
+    pub device: Option<String>,
+    pub channel: Option<String>,
+    pub tag: Option<String>,
+    pub comment: Option<String>,
+    /// 创建者用户名
+    pub created_by: Option<String>,
+    pub expires_at: chrono::DateTime<Utc>,
+    pub created_at: chrono::DateTime<Utc>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub(crate) fn into_silence(self) -> rutify_core::Silence {
+        rutify_core::Silence {
+            id: self.id,
+            device: self.device,
+            channel: self.channel,
+            tag: self.tag,
+            comment: self.comment,
+            created_by: self.created_by,
+            expires_at: self.expires_at,
+            created_at: self.created_at,
+        }
+    }
+}
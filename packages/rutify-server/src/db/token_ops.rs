@@ -1,15 +1,19 @@
 use crate::db::tokens::{self, Entity as Tokens, Model as TokenModel, TokenType};
+use crate::db::users::Entity as Users;
 use crate::error::AppError;
 use chrono::Utc;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+    ActiveEnum, ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, Iterable,
+    PaginatorTrait, QueryFilter, QueryOrder, Set,
 };
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
 pub async fn create_notify_token(
     db: &DatabaseConnection,
     token_hash: &str,
     usage: &str,
+    user_id: Option<Uuid>,
     expires_at: chrono::DateTime<Utc>,
     device_info: Option<String>,
 ) -> Result<TokenModel, AppError> {
@@ -17,7 +21,7 @@ pub async fn create_notify_token(
         token_hash: Set(token_hash.to_string()),
         usage: Set(usage.to_string()),
         token_type: Set(TokenType::NotifyBearer),
-        user_id: Set(None),
+        user_id: Set(user_id),
         device_info: Set(device_info),
         created_at: Set(Utc::now()),
         expires_at: Set(expires_at),
@@ -69,6 +73,60 @@ pub async fn verify_token_exists(
     Ok(token.is_some())
 }
 
+/// 按哈希查询未过期的 Token
+pub async fn find_token(
+    db: &DatabaseConnection,
+    token_hash: &str,
+) -> Result<Option<TokenModel>, AppError> {
+    Tokens::find()
+        .filter(tokens::Column::TokenHash.eq(token_hash))
+        .filter(tokens::Column::ExpiresAt.gt(Utc::now()))
+        .one(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find token: {e}")))
+}
+
+/// 记录 Token 连接最近一次收到的通知 ID
+pub async fn update_delivery_cursor(
+    db: &DatabaseConnection,
+    token_id: i32,
+    last_delivered_id: i32,
+) -> Result<(), AppError> {
+    tokens::ActiveModel {
+        id: Set(token_id),
+        last_delivered_id: Set(Some(last_delivered_id)),
+        last_delivered_at: Set(Some(Utc::now())),
+        ..Default::default()
+    }
+    .update(db)
+    .await
+    .map_err(|e| AppError::DatabaseError(format!("Failed to update delivery cursor: {e}")))?;
+
+    Ok(())
+}
+
+/// 查询 Token 所属用户的用户名，未绑定用户时返回 `None`
+pub async fn find_token_username(
+    db: &DatabaseConnection,
+    token_hash: &str,
+) -> Result<Option<String>, AppError> {
+    let token = Tokens::find()
+        .filter(tokens::Column::TokenHash.eq(token_hash))
+        .one(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find token: {e}")))?;
+
+    let Some(user_id) = token.and_then(|token| token.user_id) else {
+        return Ok(None);
+    };
+    let user = Users::find_by_id(user_id)
+        .one(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find user: {e}")))?;
+
+    Ok(user.map(|user| user.username))
+}
+
 pub async fn update_token_last_used(
     db: &DatabaseConnection,
     token_hash: &str,
@@ -112,6 +170,17 @@ pub async fn get_user_tokens(
         .map_err(|e| AppError::DatabaseError(format!("Failed to get user tokens: {e}")))
 }
 
+/// 按 ID 查询 Token（含已过期的）
+pub async fn find_token_by_id(
+    db: &DatabaseConnection,
+    token_id: i32,
+) -> Result<Option<TokenModel>, AppError> {
+    Tokens::find_by_id(token_id)
+        .one(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find token: {e}")))
+}
+
 pub async fn delete_token_by_id(db: &DatabaseConnection, token_id: i32) -> Result<bool, AppError> {
     let result = Tokens::delete_by_id(token_id)
         .exec(db)
@@ -152,6 +221,18 @@ pub async fn list_all_tokens(db: &DatabaseConnection) -> Result<Vec<TokenModel>,
         .map_err(|e| AppError::DatabaseError(format!("Failed to list all tokens: {e}")))
 }
 
+/// 全部用户 ID 到用户名的映射，用于列出 Token 的所有者
+pub async fn usernames_by_id(db: &DatabaseConnection) -> Result<BTreeMap<Uuid, String>, AppError> {
+    let users = Users::find()
+        .all(db)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to list users: {e}")))?;
+    Ok(users
+        .into_iter()
+        .map(|user| (user.id, user.username))
+        .collect())
+}
+
 pub async fn list_tokens_by_usage(
     db: &DatabaseConnection,
     usage: &str,
@@ -175,3 +256,20 @@ pub async fn list_tokens_by_type(
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to list tokens by type: {e}")))
 }
+
+/// 按类型统计未过期的 Token 数，键为类型的存储值
+pub async fn count_active_tokens_by_type(
+    db: &DatabaseConnection,
+) -> Result<BTreeMap<String, u64>, AppError> {
+    let mut counts = BTreeMap::new();
+    for token_type in TokenType::iter() {
+        let count = Tokens::find()
+            .filter(tokens::Column::TokenType.eq(token_type.clone()))
+            .filter(tokens::Column::ExpiresAt.gt(Utc::now()))
+            .count(db)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to count tokens: {e}")))?;
+        counts.insert(token_type.to_value(), count);
+    }
+    Ok(counts)
+}
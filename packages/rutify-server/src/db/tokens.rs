@@ -25,6 +25,9 @@ pub struct Model {
     pub created_at: chrono::DateTime<Utc>,
     pub expires_at: chrono::DateTime<Utc>,
     pub last_used_at: Option<chrono::DateTime<Utc>>,
+    /// 最近一次投递给该 Token 连接的通知 ID
+    pub last_delivered_id: Option<i32>,
+    pub last_delivered_at: Option<chrono::DateTime<Utc>>,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,190 @@
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::{Expr, Query};
+use sea_orm::{QueryOrder, Set};
+use std::collections::HashMap;
+
+use super::{tokens, users};
+
+/// 移动应用经 UnifiedPush 分发器接收通知的推送地址
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "unifiedpush_registrations")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment_flag)]
+    pub id: i32,
+    #[sea_orm(unique)]
+    pub endpoint: String,
+    pub instance: Option<String>,
+    /// 登记时使用的通知 Token，Token 删除或过期后不再推送
+    pub token_id: i32,
+    pub created_at: chrono::DateTime<Utc>,
+    pub last_success_at: Option<chrono::DateTime<Utc>>,
+    /// 连续推送失败次数
+    pub failures: i32,
+    pub last_error: Option<String>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub(crate) fn into_registration(self) -> rutify_core::UnifiedPushRegistration {
+        rutify_core::UnifiedPushRegistration {
+            id: self.id,
+            endpoint: self.endpoint,
+            instance: self.instance,
+            token_id: rutify_core::TokenId(self.token_id),
+            created_at: self.created_at,
+            last_success_at: self.last_success_at,
+            failures: self.failures.max(0) as u32,
+            last_error: self.last_error,
+        }
+    }
+}
+
+/// 登记推送地址；地址已登记时改为归属当前 Token 并清除失败记录
+pub(crate) async fn register(
+    db: &DatabaseConnection,
+    token_id: i32,
+    endpoint: &str,
+    instance: Option<String>,
+) -> Result<Model, DbErr> {
+    let existing = Entity::find()
+        .filter(Column::Endpoint.eq(endpoint))
+        .one(db)
+        .await?;
+    match existing {
+        Some(model) => {
+            let mut active: ActiveModel = model.into();
+            active.token_id = Set(token_id);
+            active.instance = Set(instance);
+            active.failures = Set(0);
+            active.last_error = Set(None);
+            active.update(db).await
+        }
+        None => {
+            ActiveModel {
+                endpoint: Set(endpoint.to_string()),
+                instance: Set(instance),
+                token_id: Set(token_id),
+                created_at: Set(Utc::now()),
+                failures: Set(0),
+                ..Default::default()
+            }
+            .insert(db)
+            .await
+        }
+    }
+}
+
+pub(crate) async fn list_for_token(
+    db: &DatabaseConnection,
+    token_id: i32,
+) -> Result<Vec<Model>, DbErr> {
+    Entity::find()
+        .filter(Column::TokenId.eq(token_id))
+        .order_by_asc(Column::Id)
+        .all(db)
+        .await
+}
+
+/// 删除 Token 自己登记的地址，返回是否存在
+pub(crate) async fn delete_for_token(
+    db: &DatabaseConnection,
+    token_id: i32,
+    id: i32,
+) -> Result<bool, DbErr> {
+    let deleted = Entity::delete_many()
+        .filter(Column::Id.eq(id))
+        .filter(Column::TokenId.eq(token_id))
+        .exec(db)
+        .await?;
+    Ok(deleted.rows_affected > 0)
+}
+
+pub(crate) async fn delete(db: &DatabaseConnection, id: i32) -> Result<(), DbErr> {
+    Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+/// Token 仍然有效的登记，附带 Token 绑定的用户名，用于判断定向通知是否可见
+pub(crate) async fn active(db: &DatabaseConnection) -> Result<Vec<(Model, Option<String>)>, DbErr> {
+    let registrations = Entity::find().order_by_asc(Column::Id).all(db).await?;
+    if registrations.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let token_ids: Vec<i32> = registrations.iter().map(|item| item.token_id).collect();
+    let tokens: HashMap<i32, Option<Uuid>> = tokens::Entity::find()
+        .filter(tokens::Column::Id.is_in(token_ids))
+        .filter(tokens::Column::ExpiresAt.gt(Utc::now()))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|token| (token.id, token.user_id))
+        .collect();
+    let user_ids: Vec<Uuid> = tokens.values().flatten().copied().collect();
+    let usernames: HashMap<Uuid, String> = if user_ids.is_empty() {
+        HashMap::new()
+    } else {
+        users::Entity::find()
+            .filter(users::Column::Id.is_in(user_ids))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|user| (user.id, user.username))
+            .collect()
+    };
+
+    Ok(registrations
+        .into_iter()
+        .filter_map(|item| {
+            let user_id = *tokens.get(&item.token_id)?;
+            let username = user_id.and_then(|id| usernames.get(&id).cloned());
+            Some((item, username))
+        })
+        .collect())
+}
+
+pub(crate) async fn record_success(db: &DatabaseConnection, id: i32) -> Result<(), DbErr> {
+    Entity::update_many()
+        .col_expr(Column::LastSuccessAt, Expr::value(Utc::now()))
+        .col_expr(Column::Failures, Expr::value(0))
+        .col_expr(Column::LastError, Expr::value(Option::<String>::None))
+        .filter(Column::Id.eq(id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+pub(crate) async fn record_failure(
+    db: &DatabaseConnection,
+    id: i32,
+    error: &str,
+) -> Result<(), DbErr> {
+    use sea_orm::sea_query::ExprTrait;
+
+    Entity::update_many()
+        .col_expr(Column::Failures, Expr::col(Column::Failures).add(1))
+        .col_expr(Column::LastError, Expr::value(error))
+        .filter(Column::Id.eq(id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// 删除所属 Token 已不存在的登记
+pub(crate) async fn delete_orphaned(db: &DatabaseConnection) -> Result<u64, DbErr> {
+    let deleted = Entity::delete_many()
+        .filter(
+            Column::TokenId.not_in_subquery(
+                Query::select()
+                    .column(tokens::Column::Id)
+                    .from(super::Tokens)
+                    .to_owned(),
+            ),
+        )
+        .exec(db)
+        .await?;
+    Ok(deleted.rows_affected)
+}
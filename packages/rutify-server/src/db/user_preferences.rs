@@ -0,0 +1,162 @@
+use chrono::Utc;
+use rutify_core::{DigestFrequency, NotificationPreferences};
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::{Expr, Query};
+use sea_orm::{QueryOrder, Set};
+use std::collections::HashMap;
+use tracing::warn;
+
+use super::users;
+
+/// 用户的通知偏好，没有记录的用户使用默认偏好
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "user_preferences")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub user_id: Uuid,
+    /// [`NotificationPreferences`] 的 JSON
+    pub settings: String,
+    /// 冗余保存摘要频率，摘要任务按此筛选
+    pub digest: String,
+    /// 上次发送邮件摘要的时间，摘要只包含此后收到的通知
+    pub last_digest_at: Option<chrono::DateTime<Utc>>,
+    pub updated_at: chrono::DateTime<Utc>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    /// 无法解析的记录按默认偏好处理
+    pub(crate) fn preferences(&self) -> NotificationPreferences {
+        serde_json::from_str(&self.settings).unwrap_or_else(|e| {
+            warn!("Invalid preferences of user {}: {}", self.user_id, e);
+            NotificationPreferences::default()
+        })
+    }
+}
+
+pub(crate) async fn get(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+) -> Result<NotificationPreferences, DbErr> {
+    Ok(Entity::find_by_id(user_id)
+        .one(db)
+        .await?
+        .map(|model| model.preferences())
+        .unwrap_or_default())
+}
+
+/// 整体替换用户的偏好；开启摘要或更改频率时从现在开始计算下一份摘要
+pub(crate) async fn save(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    preferences: &NotificationPreferences,
+) -> Result<(), DbErr> {
+    let settings = serde_json::to_string(preferences).map_err(|e| DbErr::Custom(e.to_string()))?;
+    let digest = preferences.digest.as_str().to_string();
+    let now = Utc::now();
+    match Entity::find_by_id(user_id).one(db).await? {
+        Some(model) => {
+            let digest_changed = model.digest != digest;
+            let mut active: ActiveModel = model.into();
+            active.settings = Set(settings);
+            if digest_changed {
+                active.digest = Set(digest);
+                active.last_digest_at = Set(Some(now));
+            }
+            active.updated_at = Set(now);
+            active.update(db).await?;
+        }
+        None => {
+            ActiveModel {
+                user_id: Set(user_id),
+                settings: Set(settings),
+                digest: Set(digest),
+                last_digest_at: Set(Some(now)),
+                updated_at: Set(now),
+            }
+            .insert(db)
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// 按用户名索引的偏好，推送任务据此过滤；没有记录的用户不在其中
+pub(crate) async fn by_username(
+    db: &DatabaseConnection,
+) -> Result<HashMap<String, NotificationPreferences>, DbErr> {
+    let rows = Entity::find().all(db).await?;
+    if rows.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let user_ids: Vec<Uuid> = rows.iter().map(|model| model.user_id).collect();
+    let usernames: HashMap<Uuid, String> = users::Entity::find()
+        .filter(users::Column::Id.is_in(user_ids))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|user| (user.id, user.username))
+        .collect();
+    Ok(rows
+        .into_iter()
+        .filter_map(|model| {
+            let username = usernames.get(&model.user_id)?.clone();
+            Some((username, model.preferences()))
+        })
+        .collect())
+}
+
+pub(crate) async fn for_username(
+    db: &DatabaseConnection,
+    username: &str,
+) -> Result<NotificationPreferences, DbErr> {
+    let user = users::Entity::find()
+        .filter(users::Column::Username.eq(username))
+        .one(db)
+        .await?;
+    match user {
+        Some(user) => get(db, user.id).await,
+        None => Ok(NotificationPreferences::default()),
+    }
+}
+
+/// 开启了邮件摘要的用户
+pub(crate) async fn with_digest(db: &DatabaseConnection) -> Result<Vec<Model>, DbErr> {
+    Entity::find()
+        .filter(Column::Digest.ne(DigestFrequency::Off.as_str()))
+        .order_by_asc(Column::UserId)
+        .all(db)
+        .await
+}
+
+pub(crate) async fn record_digest(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    at: chrono::DateTime<Utc>,
+) -> Result<(), DbErr> {
+    Entity::update_many()
+        .col_expr(Column::LastDigestAt, Expr::value(at))
+        .filter(Column::UserId.eq(user_id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// 删除所属用户已不存在的偏好
+pub(crate) async fn delete_orphaned(db: &DatabaseConnection) -> Result<u64, DbErr> {
+    let deleted = Entity::delete_many()
+        .filter(
+            Column::UserId.not_in_subquery(
+                Query::select()
+                    .column(users::Column::Id)
+                    .from(super::Users)
+                    .to_owned(),
+            ),
+        )
+        .exec(db)
+        .await?;
+    Ok(deleted.rows_affected)
+}
@@ -1,4 +1,6 @@
+use chrono::Utc;
 use sea_orm::entity::prelude::*;
+use sea_orm::{ActiveValue, QueryOrder};
 use serde::{Deserialize, Serialize};
 
 #[sea_orm::model]
@@ -12,6 +14,10 @@ pub struct Model {
     pub password_hash: String,
     pub email: String,
     pub role: UserRole,
+    /// 邮箱是否已验证，开启 `RUTIFY_REQUIRE_EMAIL_VERIFICATION` 时未验证的用户不能登录
+    pub email_verified: bool,
+    /// 被管理员停用的用户不能登录，已签发的用户 JWT 也随之失效
+    pub disabled: bool,
     pub created_at: DateTimeWithTimeZone,
     pub updated_at: DateTimeWithTimeZone,
 }
@@ -26,3 +32,56 @@ pub enum UserRole {
 }
 
 impl ActiveModelBehavior for ActiveModel {}
+
+impl UserRole {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            UserRole::Admin => "admin",
+            UserRole::User => "user",
+        }
+    }
+}
+
+impl Model {
+    pub(crate) fn into_admin_user(self) -> rutify_core::AdminUser {
+        rutify_core::AdminUser {
+            id: rutify_core::UserId(self.id),
+            username: self.username,
+            email: self.email,
+            role: self.role.as_str().to_string(),
+            email_verified: self.email_verified,
+            disabled: self.disabled,
+            created_at: self.created_at.with_timezone(&chrono::Utc),
+        }
+    }
+}
+
+/// 全部用户，按注册时间排序
+pub(crate) async fn list(db: &DatabaseConnection) -> Result<Vec<Model>, DbErr> {
+    Entity::find().order_by_asc(Column::CreatedAt).all(db).await
+}
+
+/// 修改用户的停用状态或角色，用户不存在时返回 `None`
+pub(crate) async fn update_by_username(
+    db: &DatabaseConnection,
+    username: &str,
+    disabled: Option<bool>,
+    role: Option<UserRole>,
+) -> Result<Option<Model>, DbErr> {
+    let Some(user) = Entity::find()
+        .filter(Column::Username.eq(username))
+        .one(db)
+        .await?
+    else {
+        return Ok(None);
+    };
+    let mut active_model: ActiveModel = user.into();
+    if let Some(disabled) = disabled {
+        active_model.disabled = ActiveValue::Set(disabled);
+    }
+    if let Some(role) = role {
+        active_model.role = ActiveValue::Set(role);
+    }
+    active_model.updated_at = ActiveValue::Set(Utc::now().into());
+    active_model.update(db).await.map(Some)
+}
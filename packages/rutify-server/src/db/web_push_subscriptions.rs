@@ -0,0 +1,180 @@
+use chrono::Utc;
+use sea_orm::entity::prelude::*;
+use sea_orm::sea_query::{Expr, Query};
+use sea_orm::{QueryOrder, Set};
+use std::collections::HashMap;
+
+use super::users;
+
+/// 浏览器的 Web Push 订阅，归属订阅时登录的用户
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "web_push_subscriptions")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment_flag)]
+    pub id: i32,
+    #[sea_orm(unique)]
+    pub endpoint: String,
+    /// 浏览器的 P-256 公钥（base64url）
+    pub p256dh: String,
+    /// 浏览器的认证密钥（base64url）
+    pub auth: String,
+    pub user_id: Uuid,
+    pub created_at: chrono::DateTime<Utc>,
+    pub last_success_at: Option<chrono::DateTime<Utc>>,
+    /// 连续推送失败次数
+    pub failures: i32,
+    pub last_error: Option<String>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub(crate) fn into_subscription(self) -> rutify_core::WebPushSubscription {
+        rutify_core::WebPushSubscription {
+            id: self.id,
+            endpoint: self.endpoint,
+            created_at: self.created_at,
+            last_success_at: self.last_success_at,
+            failures: self.failures.max(0) as u32,
+            last_error: self.last_error,
+        }
+    }
+}
+
+/// 保存订阅；地址已订阅时更新密钥、改为归属当前用户并清除失败记录
+pub(crate) async fn subscribe(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    endpoint: &str,
+    p256dh: &str,
+    auth: &str,
+) -> Result<Model, DbErr> {
+    let existing = Entity::find()
+        .filter(Column::Endpoint.eq(endpoint))
+        .one(db)
+        .await?;
+    match existing {
+        Some(model) => {
+            let mut active: ActiveModel = model.into();
+            active.p256dh = Set(p256dh.to_string());
+            active.auth = Set(auth.to_string());
+            active.user_id = Set(user_id);
+            active.failures = Set(0);
+            active.last_error = Set(None);
+            active.update(db).await
+        }
+        None => {
+            ActiveModel {
+                endpoint: Set(endpoint.to_string()),
+                p256dh: Set(p256dh.to_string()),
+                auth: Set(auth.to_string()),
+                user_id: Set(user_id),
+                created_at: Set(Utc::now()),
+                failures: Set(0),
+                ..Default::default()
+            }
+            .insert(db)
+            .await
+        }
+    }
+}
+
+pub(crate) async fn list_for_user(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+) -> Result<Vec<Model>, DbErr> {
+    Entity::find()
+        .filter(Column::UserId.eq(user_id))
+        .order_by_asc(Column::Id)
+        .all(db)
+        .await
+}
+
+/// 删除用户自己的订阅，返回是否存在
+pub(crate) async fn delete_for_user(
+    db: &DatabaseConnection,
+    user_id: Uuid,
+    endpoint: &str,
+) -> Result<bool, DbErr> {
+    let deleted = Entity::delete_many()
+        .filter(Column::Endpoint.eq(endpoint))
+        .filter(Column::UserId.eq(user_id))
+        .exec(db)
+        .await?;
+    Ok(deleted.rows_affected > 0)
+}
+
+pub(crate) async fn delete(db: &DatabaseConnection, id: i32) -> Result<(), DbErr> {
+    Entity::delete_by_id(id).exec(db).await?;
+    Ok(())
+}
+
+/// 所属用户未停用的订阅，附带用户名，用于判断定向通知是否可见
+pub(crate) async fn active(db: &DatabaseConnection) -> Result<Vec<(Model, String)>, DbErr> {
+    let subscriptions = Entity::find().order_by_asc(Column::Id).all(db).await?;
+    if subscriptions.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let user_ids: Vec<Uuid> = subscriptions.iter().map(|item| item.user_id).collect();
+    let usernames: HashMap<Uuid, String> = users::Entity::find()
+        .filter(users::Column::Id.is_in(user_ids))
+        .filter(users::Column::Disabled.eq(false))
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|user| (user.id, user.username))
+        .collect();
+
+    Ok(subscriptions
+        .into_iter()
+        .filter_map(|item| {
+            let username = usernames.get(&item.user_id)?.clone();
+            Some((item, username))
+        })
+        .collect())
+}
+
+pub(crate) async fn record_success(db: &DatabaseConnection, id: i32) -> Result<(), DbErr> {
+    Entity::update_many()
+        .col_expr(Column::LastSuccessAt, Expr::value(Utc::now()))
+        .col_expr(Column::Failures, Expr::value(0))
+        .col_expr(Column::LastError, Expr::value(Option::<String>::None))
+        .filter(Column::Id.eq(id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+pub(crate) async fn record_failure(
+    db: &DatabaseConnection,
+    id: i32,
+    error: &str,
+) -> Result<(), DbErr> {
+    use sea_orm::sea_query::ExprTrait;
+
+    Entity::update_many()
+        .col_expr(Column::Failures, Expr::col(Column::Failures).add(1))
+        .col_expr(Column::LastError, Expr::value(error))
+        .filter(Column::Id.eq(id))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// 删除所属用户已不存在的订阅
+pub(crate) async fn delete_orphaned(db: &DatabaseConnection) -> Result<u64, DbErr> {
+    let deleted = Entity::delete_many()
+        .filter(
+            Column::UserId.not_in_subquery(
+                Query::select()
+                    .column(users::Column::Id)
+                    .from(super::Users)
+                    .to_owned(),
+            ),
+        )
+        .exec(db)
+        .await?;
+    Ok(deleted.rows_affected)
+}
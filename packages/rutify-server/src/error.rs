@@ -1,16 +1,54 @@
 use axum::Json;
-use axum::http::StatusCode;
-use axum::response::IntoResponse;
+use axum::extract::Request;
+use axum::http::{StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use rutify_core::{ApiError, ApiErrorResponse, ErrorCode};
 use sea_orm::DbErr;
+use serde::Serialize;
 use std::fmt;
 use tracing::error;
 
+/// 改写框架错误时读取的最大响应体
+const MAX_REWRITTEN_BODY: usize = 4096;
+
 #[derive(Debug)]
 pub(crate) enum AppError {
     Db(DbErr),
     Json(serde_json::Error),
     AuthError(String),
     DatabaseError(String),
+    BadRequest(String),
+    Forbidden(String),
+    NotFound(String),
+    /// 通知超出大小限制，返回 413
+    PayloadTooLarge(String),
+    /// 请求处理超时，返回 408
+    RequestTimeout(String),
+    /// 超出客户端请求速率，返回 429
+    TooManyRequests(String),
+    /// 服务端繁忙，返回 503
+    ServiceUnavailable(String),
+    /// 依赖的外部服务（如 SMTP 中继）失败
+    Upstream(String),
+    /// 请求字段校验失败，返回 422 与逐字段的错误
+    Validation(Vec<FieldError>),
+}
+
+/// 单个字段的校验错误
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct FieldError {
+    pub(crate) field: &'static str,
+    pub(crate) message: String,
+}
+
+impl FieldError {
+    pub(crate) fn new(field: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            field,
+            message: message.into(),
+        }
+    }
 }
 
 impl From<DbErr> for AppError {
@@ -32,33 +70,227 @@ impl fmt::Display for AppError {
             AppError::Json(err) => write!(f, "JSON errors: {}", err),
             AppError::AuthError(msg) => write!(f, "Authentication errors: {}", msg),
             AppError::DatabaseError(msg) => write!(f, "Database operation errors: {}", msg),
+            AppError::BadRequest(msg) => write!(f, "Bad request: {}", msg),
+            AppError::Forbidden(msg) => write!(f, "Forbidden: {}", msg),
+            AppError::NotFound(msg) => write!(f, "Not found: {}", msg),
+            AppError::PayloadTooLarge(msg) => write!(f, "Payload too large: {}", msg),
+            AppError::RequestTimeout(msg) => write!(f, "Request timeout: {}", msg),
+            AppError::TooManyRequests(msg) => write!(f, "Too many requests: {}", msg),
+            AppError::ServiceUnavailable(msg) => write!(f, "Service unavailable: {}", msg),
+            AppError::Upstream(msg) => write!(f, "Upstream errors: {}", msg),
+            AppError::Validation(errors) => {
+                let fields: Vec<String> = errors
+                    .iter()
+                    .map(|error| format!("{} {}", error.field, error.message))
+                    .collect();
+                write!(f, "Validation errors: {}", fields.join("; "))
+            }
         }
     }
 }
 
+impl std::error::Error for AppError {}
+
 impl IntoResponse for AppError {
-    fn into_response(self) -> axum::response::Response {
-        let (status, message) = match self {
+    fn into_response(self) -> Response {
+        let (status, error) = match self {
             AppError::Db(err) => {
                 error!(error = %err, "database errors");
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
-                    "database errors".to_string(),
+                    ApiError::new(ErrorCode::Internal, "database errors"),
                 )
             }
             AppError::Json(err) => {
                 error!(error = %err, "json errors");
-                (StatusCode::BAD_REQUEST, "json errors".to_string())
+                (
+                    StatusCode::BAD_REQUEST,
+                    ApiError::new(ErrorCode::BadRequest, "json errors"),
+                )
             }
             AppError::AuthError(msg) => {
                 error!(error = %msg, "authentication errors");
-                (StatusCode::UNAUTHORIZED, msg.clone())
+                (
+                    StatusCode::UNAUTHORIZED,
+                    ApiError::new(ErrorCode::Unauthorized, msg),
+                )
             }
             AppError::DatabaseError(msg) => {
                 error!(error = %msg, "database operation errors");
-                (StatusCode::INTERNAL_SERVER_ERROR, msg.clone())
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    ApiError::new(ErrorCode::Internal, msg),
+                )
+            }
+            AppError::BadRequest(msg) => (
+                StatusCode::BAD_REQUEST,
+                ApiError::new(ErrorCode::BadRequest, msg),
+            ),
+            AppError::Forbidden(msg) => {
+                error!(error = %msg, "forbidden");
+                (
+                    StatusCode::FORBIDDEN,
+                    ApiError::new(ErrorCode::Forbidden, msg),
+                )
+            }
+            AppError::NotFound(msg) => (
+                StatusCode::NOT_FOUND,
+                ApiError::new(ErrorCode::NotFound, msg),
+            ),
+            AppError::PayloadTooLarge(msg) => (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                ApiError::new(ErrorCode::PayloadTooLarge, msg),
+            ),
+            AppError::RequestTimeout(msg) => {
+                error!(error = %msg, "request timeout");
+                (
+                    StatusCode::REQUEST_TIMEOUT,
+                    ApiError::new(ErrorCode::RequestTimeout, msg),
+                )
+            }
+            AppError::TooManyRequests(msg) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                ApiError::new(ErrorCode::RateLimited, msg),
+            ),
+            AppError::ServiceUnavailable(msg) => {
+                error!(error = %msg, "service unavailable");
+                (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    ApiError::new(ErrorCode::Unavailable, msg),
+                )
+            }
+            AppError::Upstream(msg) => {
+                error!(error = %msg, "upstream errors");
+                (
+                    StatusCode::BAD_GATEWAY,
+                    ApiError::new(ErrorCode::Upstream, msg),
+                )
             }
+            AppError::Validation(errors) => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                ApiError {
+                    code: ErrorCode::Validation,
+                    message: "validation failed".to_string(),
+                    details: Some(serde_json::json!({ "fields": errors })),
+                },
+            ),
         };
-        (status, Json(serde_json::json!({ "errors": message }))).into_response()
+        error_response(status, error)
+    }
+}
+
+/// 错误响应，附带请求 ID 便于与服务端日志对应
+pub(crate) fn error_response(status: StatusCode, error: ApiError) -> Response {
+    let body = ApiErrorResponse {
+        status: "error".to_string(),
+        error,
+        request_id: crate::bootstrap::request_id::current(),
+    };
+    (status, Json(body)).into_response()
+}
+
+/// 把提取器拒绝、未匹配的路由等由框架生成的非 JSON 错误改写为 [`ApiError`]
+///
+/// 需放在请求限制中间件外层，413 保留其更明确的提示。
+pub(crate) async fn middleware(request: Request, next: Next) -> Response {
+    let response = next.run(request).await;
+    let status = response.status();
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|value| value.as_bytes().starts_with(b"application/json"));
+    if !(status.is_client_error() || status.is_server_error()) || is_json {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let text = axum::body::to_bytes(body, MAX_REWRITTEN_BODY)
+        .await
+        .map(|bytes| String::from_utf8_lossy(&bytes).trim().to_string())
+        .unwrap_or_default();
+    let message = if text.is_empty() {
+        status.canonical_reason().unwrap_or("error").to_string()
+    } else {
+        text
+    };
+    let mut rewritten = error_response(
+        status,
+        ApiError::new(ErrorCode::from_status(status.as_u16()), message),
+    );
+    // 保留 `Allow`、`Retry-After` 等响应头
+    for (name, value) in &parts.headers {
+        if name != header::CONTENT_TYPE && name != header::CONTENT_LENGTH {
+            rewritten.headers_mut().append(name, value.clone());
+        }
+    }
+    rewritten
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::body::Body;
+    use axum::routing::post;
+    use tower::ServiceExt;
+
+    async fn error_of(response: Response) -> (StatusCode, ApiErrorResponse) {
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_app_error_body() {
+        let (status, body) =
+            error_of(AppError::NotFound("no such notify".into()).into_response()).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body.status, "error");
+        assert_eq!(
+            body.error,
+            ApiError::new(ErrorCode::NotFound, "no such notify")
+        );
+
+        let errors = vec![FieldError::new("username", "is too short")];
+        let (status, body) = error_of(AppError::Validation(errors).into_response()).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body.error.code, ErrorCode::Validation);
+        assert_eq!(
+            body.error.details.unwrap()["fields"][0]["field"],
+            "username"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_middleware_rewrites_framework_errors() {
+        let app = Router::new()
+            .route(
+                "/json",
+                post(|Json(value): Json<serde_json::Value>| async move { Json(value) }),
+            )
+            .layer(axum::middleware::from_fn(middleware));
+
+        let request = Request::post("/json")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from("{"))
+            .unwrap();
+        let (status, body) = error_of(app.clone().oneshot(request).await.unwrap()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body.error.code, ErrorCode::BadRequest);
+        assert!(body.error.message.contains("JSON"));
+
+        let request = Request::get("/missing").body(Body::empty()).unwrap();
+        let (status, body) = error_of(app.clone().oneshot(request).await.unwrap()).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body.error.message, "Not Found");
+
+        let request = Request::get("/json").body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert!(response.headers().contains_key(header::ALLOW));
+        let (status, body) = error_of(response).await;
+        assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+        assert_eq!(body.error.code, ErrorCode::MethodNotAllowed);
     }
 }
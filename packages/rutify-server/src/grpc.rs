@@ -0,0 +1,227 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use rutify_core::{NotificationInput, NotifyId};
+use rutify_sdk::grpc::proto;
+use sea_orm::{EntityTrait, PaginatorTrait, QueryFilter, QuerySelect};
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::wrappers::{BroadcastStream, TcpListenerStream};
+use tokio_stream::{Stream, StreamExt};
+use tonic::metadata::MetadataMap;
+use tonic::{Request, Response, Status};
+use tracing::{error, info, warn};
+
+use crate::bootstrap::config::ServerConfig;
+use crate::bootstrap::read_only;
+use crate::bootstrap::source::ConfigSource;
+use crate::db::{notifies, token_ops};
+use crate::error::AppError;
+use crate::services::auth::auth::{
+    TokenClaims, authorize_notify_token, generate_token_hash, verify_notify_token,
+};
+use crate::services::delivery::is_visible_to;
+use crate::services::device::{self, SenderContext};
+use crate::services::notify;
+use crate::services::signing;
+use crate::state::AppState;
+
+/// 服务端桩代码，消息类型复用 SDK 中生成的 `proto` 模块
+mod pb {
+    tonic::include_proto!("rutify.v1");
+}
+
+use pb::rutify_server::{Rutify, RutifyServer};
+
+/// gRPC 监听配置
+#[derive(Debug, Clone)]
+pub(crate) struct GrpcConfig {
+    pub addr: SocketAddr,
+}
+
+impl GrpcConfig {
+    /// 读取 `RUTIFY_GRPC_ADDR`，未设置时返回 `None`
+    pub(crate) fn from_source(source: &ConfigSource) -> Result<Option<Self>> {
+        let Ok(addr_text) = source.var("RUTIFY_GRPC_ADDR") else {
+            return Ok(None);
+        };
+        let addr = addr_text
+            .parse()
+            .with_context(|| format!("invalid RUTIFY_GRPC_ADDR: {addr_text}"))?;
+        Ok(Some(Self { addr }))
+    }
+}
+
+/// 启动 gRPC 服务，与 HTTP 服务共享同一个 `AppState`
+pub(crate) async fn spawn(config: GrpcConfig, state: Arc<AppState>) -> Result<()> {
+    let listener = TcpListener::bind(config.addr)
+        .await
+        .with_context(|| format!("failed to bind gRPC {}", config.addr))?;
+    info!("gRPC listener on {}", config.addr);
+
+    let server_config = state.config.clone();
+    let service = RutifyServer::with_interceptor(RutifyService { state }, move |request| {
+        check_token(&server_config, request)
+    });
+    tokio::spawn(async move {
+        if let Err(e) = tonic::transport::Server::builder()
+            .add_service(service)
+            .serve_with_incoming(TcpListenerStream::new(listener))
+            .await
+        {
+            error!("gRPC server errors: {}", e);
+        }
+    });
+
+    Ok(())
+}
+
+/// 拦截器：校验通知 Token 的 JWT，数据库记录在各方法中确认
+fn check_token(config: &ServerConfig, request: Request<()>) -> Result<Request<()>, Status> {
+    let token = bearer_token(request.metadata())?;
+    verify_notify_token(config, token)?;
+    Ok(request)
+}
+
+fn bearer_token(metadata: &MetadataMap) -> Result<&str, Status> {
+    metadata
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or_else(|| Status::unauthenticated("Missing bearer token"))
+}
+
+impl From<AppError> for Status {
+    fn from(err: AppError) -> Self {
+        match err {
+            AppError::AuthError(msg) => Status::unauthenticated(msg),
+            AppError::Forbidden(msg) => Status::permission_denied(msg),
+            AppError::BadRequest(msg) => Status::invalid_argument(msg),
+            AppError::NotFound(msg) => Status::not_found(msg),
+            AppError::PayloadTooLarge(msg) => Status::resource_exhausted(msg),
+            err => {
+                error!("gRPC request errors: {}", err);
+                Status::internal("internal errors")
+            }
+        }
+    }
+}
+
+struct RutifyService {
+    state: Arc<AppState>,
+}
+
+impl RutifyService {
+    async fn authorize<T>(&self, request: &Request<T>) -> Result<TokenClaims, Status> {
+        let token = bearer_token(request.metadata())?;
+        Ok(authorize_notify_token(&self.state, token).await?)
+    }
+}
+
+type EventStream = Pin<Box<dyn Stream<Item = Result<proto::NotifyEvent, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl Rutify for RutifyService {
+    async fn send_notification(
+        &self,
+        request: Request<proto::SendNotificationRequest>,
+    ) -> Result<Response<proto::SendNotificationResponse>, Status> {
+        if self.state.read_only {
+            return Err(Status::unavailable(read_only::MESSAGE));
+        }
+        let claims = self.authorize(&request).await?;
+        let token = bearer_token(request.metadata())?.to_string();
+        let user_agent = request
+            .metadata()
+            .get("user-agent")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+
+        let mut input: NotificationInput = request.into_inner().into();
+        self.state.size_limits.check(&input)?;
+        // gRPC 请求不携带签名，登记了签名公钥的 Token 只能通过 HTTP 发送
+        signing::verify_sender(claims.signing_key.as_deref(), &mut input)?;
+        let sender = SenderContext {
+            defaults: Some(&claims.defaults),
+            token: Some(&token),
+            user_agent: user_agent.as_deref(),
+        };
+        let device_source = device::derive(&self.state, &mut input, sender).await;
+        let token_hash = Some(generate_token_hash(&token));
+        let event =
+            notify::ingest_derived(&self.state, input, None, device_source, token_hash).await;
+        Ok(Response::new(proto::SendNotificationResponse {
+            dropped: event.is_none(),
+            id: event.and_then(|event| event.data.id).map(NotifyId::get),
+        }))
+    }
+
+    async fn list_notifications(
+        &self,
+        request: Request<proto::ListNotificationsRequest>,
+    ) -> Result<Response<proto::ListNotificationsResponse>, Status> {
+        self.authorize(&request).await?;
+
+        let request = request.into_inner();
+        let limit = request.limit;
+        let mut query = notifies::Entity::find();
+        if !request.include_expired {
+            query = query.filter(notifies::not_expired(Utc::now()));
+        }
+        let total = query
+            .clone()
+            .count(&self.state.db)
+            .await
+            .map_err(AppError::from)?;
+        let mut query = notifies::list_order(query);
+        if limit > 0 {
+            query = query.limit(u64::from(limit));
+        }
+        let items = query.all(&self.state.db).await.map_err(AppError::from)?;
+
+        Ok(Response::new(proto::ListNotificationsResponse {
+            items: items
+                .into_iter()
+                .map(|item| item.into_item().into())
+                .collect(),
+            total,
+        }))
+    }
+
+    type StreamEventsStream = EventStream;
+
+    async fn stream_events(
+        &self,
+        request: Request<proto::StreamEventsRequest>,
+    ) -> Result<Response<Self::StreamEventsStream>, Status> {
+        let claims = self.authorize(&request).await?;
+        let token = bearer_token(request.metadata())?;
+
+        // 与 WebSocket 相同，绑定用户的 Token 才能收到定向通知
+        let username = token_ops::find_token_username(&self.state.db, &generate_token_hash(token))
+            .await
+            .unwrap_or_else(|e| {
+                warn!("Failed to resolve gRPC token owner: {}", e);
+                None
+            });
+        info!("gRPC event stream opened for usage: {}", claims.usage);
+
+        let stream =
+            BroadcastStream::new(self.state.tx.subscribe()).filter_map(move |event| match event {
+                Ok(event) if is_visible_to(&event.data, username.as_deref()) => {
+                    Some(Ok(event.into()))
+                }
+                Ok(_) => None,
+                Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                    warn!(
+                        "gRPC stream lagged for usage {}, {} events skipped",
+                        claims.usage, skipped
+                    );
+                    None
+                }
+            });
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
@@ -1,32 +1,162 @@
 mod bootstrap;
+mod bridges;
 mod db;
 mod error;
+#[cfg(feature = "grpc")]
+mod grpc;
+mod response;
 mod routes;
 mod services;
 mod state;
 
 slint::include_modules!();
 
+use crate::bootstrap::config::ServerConfig;
+use crate::bootstrap::logging::LogConfig;
+use crate::bootstrap::monitoring::MonitoringState;
+use crate::bootstrap::reload::{ConfigReloader, RuntimeConfig};
+use crate::bootstrap::service::ServiceOptions;
+use crate::bootstrap::source::ConfigSource;
+use crate::services::auth::failures::AuthFailureLog;
+use crate::services::auth::mode::AuthPolicy;
+use crate::services::auth::registration_gate::RegistrationGate;
+use crate::services::auth::token_cache::TokenCache;
+use crate::services::auth::validation::PasswordPolicy;
+use crate::services::cluster::ClusterConfig;
+use crate::services::connections::ConnectionTracker;
+use crate::services::db_maintenance::DbMaintenance;
+use crate::services::dedup::GetDedup;
+use crate::services::escalation::EscalationRegistry;
+use crate::services::leader::Leadership;
+use crate::services::limits::SizeLimits;
+use crate::services::link_preview::{LinkPreviewConfig, LinkPreviewer};
+use crate::services::maintenance::Maintenance;
+use crate::services::plugins::PluginRegistry;
+use crate::services::silences::SilenceRegistry;
+use crate::services::unifiedpush::UnifiedPushConfig;
+use crate::services::web_push::{WebPush, WebPushConfig};
 use crate::state::AppState;
 use clap::Parser;
-use common_http_server_rs::{MonitoringState, Server, setup_metrics_recorder};
 use dotenvy::dotenv;
-use rutify_core::NotifyItem as CoreNotifyItem;
-use rutify_sdk::RutifyClient;
+use rutify_client::{GuiSettings, ThemePreference};
+use rutify_core::{
+    CountGroupBy, NotificationInput, NotifyCount, NotifyItem as CoreNotifyItem, TokenDefaults,
+    TokenId,
+};
+use rutify_sdk::{CreateTokenRequest, RutifyClient, TokenInfo};
 use sea_orm::Database;
 use slint::{ModelRc, VecModel};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::sync::broadcast;
-use tracing::warn;
+use tracing::{info, warn};
 
 #[derive(clap::Parser)]
 struct CliArgs {
     #[clap(long)]
     ui: bool,
+
+    #[command(subcommand)]
+    command: Option<ServerCommand>,
+}
+
+#[derive(clap::Subcommand)]
+enum ServerCommand {
+    /// Write a .tar.zst backup of the database (sqlite snapshot or pg_dump)
+    Backup {
+        /// Output archive path
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Restore the database from a backup; stop the server first
+    Restore {
+        /// Backup archive created by `backup`
+        #[arg(long)]
+        from: PathBuf,
+    },
+    /// Generate a key file for RUTIFY_ENCRYPTION_KEY_FILE
+    GenKey {
+        /// Path of the new key file; must not exist yet
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Generate a VAPID key file for RUTIFY_VAPID_KEY_FILE (Web Push)
+    GenVapidKey {
+        /// Path of the new key file; must not exist yet
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Re-encrypt stored notifications with a new key; stop the server first
+    ///
+    /// Existing rows are decrypted with the key in RUTIFY_ENCRYPTION_KEY_FILE (or read as
+    /// plaintext when it is unset).
+    Rekey {
+        /// Key file for the new key, generated if it does not exist; omit to store plaintext
+        #[arg(long)]
+        new_key_file: Option<PathBuf>,
+    },
+    /// Exit 0 if the running server answers GET /health, 1 otherwise (for container HEALTHCHECK)
+    Healthcheck {
+        /// Health endpoint to request; defaults to /health on the first TCP address in RUTIFY_ADDR
+        /// (loopback for 0.0.0.0), or the unix socket when no TCP address is configured
+        #[arg(long)]
+        url: Option<String>,
+        /// Seconds to wait for the response
+        #[arg(long, default_value_t = 5)]
+        timeout: u64,
+    },
+    /// Print the effective configuration resolved from the config file, .env and the environment;
+    /// secrets are hidden
+    PrintConfig,
+    /// Run the server as a Windows service or macOS launchd job
+    Service {
+        #[command(subcommand)]
+        command: ServiceCommand,
+    },
+}
+
+#[derive(clap::Subcommand)]
+enum ServiceCommand {
+    /// Register the server to start at boot and restart after crashes, then start it
+    ///
+    /// Creates a Windows service or a macOS launchd job; on Linux use the systemd units instead.
+    Install {
+        /// Directory with .env and rutify.toml that the service runs in; defaults to the current
+        /// directory
+        #[arg(long)]
+        working_dir: Option<PathBuf>,
+        /// Log file; defaults to rutify-server.log in the working directory
+        #[arg(long)]
+        log_file: Option<PathBuf>,
+        /// macOS: install a LaunchAgent that starts at login instead of a system LaunchDaemon
+        #[arg(long)]
+        user: bool,
+    },
+    /// Stop and remove the installed service
+    Uninstall {
+        /// macOS: remove the LaunchAgent installed with --user
+        #[arg(long)]
+        user: bool,
+    },
+    /// Run the server under the service manager; used by the installed service
+    Run {
+        #[arg(long)]
+        working_dir: Option<PathBuf>,
+        /// Write logs to this file in addition to stdout
+        #[arg(long)]
+        log_file: Option<PathBuf>,
+    },
 }
 
 fn main() -> anyhow::Result<()> {
     let args = CliArgs::parse();
+    if let Some(command) = args.command {
+        return match command {
+            ServerCommand::Service { command } => run_service_command(command),
+            command => run_command(command),
+        };
+    }
     println!("ui:{}", args.ui);
     match args.ui {
         true => run_with_ui()?,
@@ -36,30 +166,154 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+fn run_command(command: ServerCommand) -> anyhow::Result<()> {
+    dotenv().ok();
+    let source = ConfigSource::load()?;
+    let _log_guard = bootstrap::logging::init(&LogConfig::from_source(&source)?)?;
+    let server_config = ServerConfig::from_source(&source)?;
+    let db_url = server_config.database_url.clone();
+
+    match command {
+        ServerCommand::Backup { out } => {
+            let rt = tokio::runtime::Runtime::new()?;
+            rt.block_on(async {
+                let db_cnn = Database::connect(db::connect_options(&source, &db_url)?).await?;
+                services::backup::create_backup(&db_cnn, &db_url, &out).await
+            })?;
+            println!("Backup written to {}", out.display());
+        }
+        ServerCommand::Restore { from } => {
+            services::backup::restore_backup(&from, &db_url)?;
+            println!("Database restored from {}", from.display());
+        }
+        ServerCommand::GenKey { out } => {
+            db::encryption::generate_key_file(&out)?;
+            println!("Key written to {}", out.display());
+        }
+        ServerCommand::GenVapidKey { out } => {
+            services::web_push::generate_key_file(&out)?;
+            let key = services::web_push::load_key(&out)?;
+            println!("VAPID key written to {}", out.display());
+            println!("Public key: {}", services::web_push::public_key(&key));
+        }
+        ServerCommand::Rekey { new_key_file } => {
+            let old = db::encryption::key_from_source(&source)?;
+            let new = match &new_key_file {
+                Some(path) => {
+                    if !path.exists() {
+                        db::encryption::generate_key_file(path)?;
+                        println!("Generated new key in {}", path.display());
+                    }
+                    Some(db::encryption::load_key(path)?)
+                }
+                None => None,
+            };
+            let rt = tokio::runtime::Runtime::new()?;
+            let (notifies, attachments) = rt.block_on(async {
+                let db_cnn = Database::connect(db::connect_options(&source, &db_url)?).await?;
+                db::initialize::initial(&db_cnn).await;
+                db::encryption::rekey(&db_cnn, old.as_ref(), new.as_ref()).await
+            })?;
+            println!("Re-encrypted {notifies} notifications and {attachments} attachments");
+            match new_key_file {
+                Some(path) => println!("Set RUTIFY_ENCRYPTION_KEY_FILE={}", path.display()),
+                None => {
+                    println!("Stored content is now plaintext; unset RUTIFY_ENCRYPTION_KEY_FILE")
+                }
+            }
+        }
+        ServerCommand::Healthcheck { url, timeout } => {
+            let timeout = Duration::from_secs(timeout);
+            let rt = tokio::runtime::Runtime::new()?;
+            rt.block_on(async {
+                match url {
+                    Some(url) => bootstrap::diagnostics::check_url(&url, timeout).await,
+                    None => bootstrap::diagnostics::check_local(&server_config, timeout).await,
+                }
+            })?;
+            println!("ok");
+        }
+        ServerCommand::PrintConfig => {
+            print!("{}", bootstrap::diagnostics::render_config(&source)?);
+        }
+        ServerCommand::Service { .. } => unreachable!("handled in main"),
+    }
+    Ok(())
+}
+
+/// 服务管理相关命令不读取配置，`run` 先切换到安装时记录的工作目录
+fn run_service_command(command: ServiceCommand) -> anyhow::Result<()> {
+    match command {
+        ServiceCommand::Install {
+            working_dir,
+            log_file,
+            user,
+        } => bootstrap::service::install(&ServiceOptions::resolve(working_dir, log_file, user)?),
+        ServiceCommand::Uninstall { user } => bootstrap::service::uninstall(user),
+        ServiceCommand::Run {
+            working_dir,
+            log_file,
+        } => bootstrap::service::run(working_dir.as_deref(), log_file),
+    }
+}
+
 fn run_cli_only() -> anyhow::Result<()> {
+    run_server(None)
+}
+
+/// 加载配置并运行服务器直到收到停止信号，`log_file` 优先于 `RUTIFY_LOG_FILE`
+fn run_server(log_file: Option<PathBuf>) -> anyhow::Result<()> {
     dotenv().ok();
+    let source = ConfigSource::load()?;
+    let mut log_config = LogConfig::from_source(&source)?;
+    if let Some(log_file) = log_file {
+        log_config.file = Some(log_file.display().to_string());
+    }
+    let _log_guard = bootstrap::logging::init(&log_config)?;
 
     let rt = tokio::runtime::Runtime::new()?;
-    rt.block_on(async { rutify_service().await })?;
+    rt.block_on(rutify_service(source))?;
 
     Ok(())
 }
 
 fn run_with_ui() -> anyhow::Result<()> {
     dotenv().ok();
+    let source = ConfigSource::load()?;
+    let _log_guard = bootstrap::logging::init(&LogConfig::from_source(&source)?)?;
 
     let ui = AppWindow::new()?;
     let rt = tokio::runtime::Runtime::new()?;
     let rt_handle = rt.handle().clone();
     let weak_ui = ui.as_weak();
-    let service_addr = resolve_service_addr();
+    let service_addr = resolve_service_addr(&source);
     let sdk_client = RutifyClient::new(&service_addr);
     let cached_notifies: Arc<Mutex<Vec<CoreNotifyItem>>> = Arc::new(Mutex::new(Vec::new()));
     ui.set_service_addr(service_addr.clone().into());
+    match GuiSettings::load() {
+        Ok(settings) => ui
+            .global::<Theme>()
+            .invoke_apply(theme_mode(settings.theme)),
+        Err(e) => warn!("Failed to load GUI settings: {:#}", e),
+    }
+    // 主题与其他图形界面共用 GUI 配置，只改写主题
+    let theme_ui = ui.as_weak();
+    ui.on_theme_changed(move |mode| {
+        if let Some(ui) = theme_ui.upgrade() {
+            ui.global::<Theme>().invoke_apply(mode);
+        }
+        let result = GuiSettings::load().and_then(|mut settings| {
+            settings.theme = theme_preference(mode);
+            settings.save()
+        });
+        if let Err(e) = result {
+            warn!("Failed to save GUI settings: {:#}", e);
+        }
+    });
 
     // 启动服务器
     let _server_handle = rt_handle.spawn(async move {
-        if let Err(e) = rutify_service().await {
+        if let Err(e) = rutify_service(source).await {
             tracing::error!("Server failed to start: {}", e);
         }
         slint::invoke_from_event_loop(move || if let Some(_ui) = weak_ui.upgrade() {}).ok();
@@ -103,9 +357,128 @@ fn run_with_ui() -> anyhow::Result<()> {
         let refresh_cache = Arc::clone(&refresh_cache);
         refresh_handle.spawn(async move {
             match sdk_client.get_notifies().await {
-                Ok(items) => apply_notifies_to_ui(refresh_ui, refresh_cache, items),
+                Ok(items) => apply_notifies_to_ui(refresh_ui.clone(), refresh_cache, items),
                 Err(err) => warn!("failed to refresh notifies: {err}"),
             }
+            reload_unread_counts(&sdk_client, refresh_ui).await;
+        });
+    });
+
+    let test_handle = rt_handle.clone();
+    let test_sdk_client = sdk_client.clone();
+    let test_ui = ui.as_weak();
+    ui.on_send_test(move |title, message, device| {
+        let sdk_client = test_sdk_client.clone();
+        let test_ui = test_ui.clone();
+        let input = NotificationInput {
+            notify: message.to_string(),
+            title: non_empty(&title),
+            device: non_empty(&device),
+            ..Default::default()
+        };
+        test_handle.spawn(async move {
+            let result = sdk_client.send_notification(&input).await;
+            let _ = test_ui.upgrade_in_event_loop(move |ui| match result {
+                Ok(()) => {
+                    ui.set_test_status("Test notification sent".into());
+                    ui.invoke_refresh_notifies();
+                }
+                Err(err) => ui.set_test_status(format!("Failed to send: {err}").into()),
+            });
+        });
+    });
+
+    // Token 管理需要先登录本机账户，登录后的用户 JWT 保存在本地 SDK 客户端中
+    let sign_in_handle = rt_handle.clone();
+    let sign_in_sdk_client = sdk_client.clone();
+    let sign_in_ui = ui.as_weak();
+    ui.on_sign_in(move |username, password| {
+        let sdk_client = sign_in_sdk_client.clone();
+        let sign_in_ui = sign_in_ui.clone();
+        if let Some(ui) = sign_in_ui.upgrade() {
+            ui.set_tokens_busy(true);
+        }
+        sign_in_handle.spawn(async move {
+            match sdk_client.login_and_set_token(&username, &password).await {
+                Ok(login) => {
+                    let _ = sign_in_ui.upgrade_in_event_loop(move |ui| {
+                        ui.set_signed_in(true);
+                        ui.set_username(login.username.into());
+                    });
+                    reload_tokens(&sdk_client, sign_in_ui, None).await;
+                }
+                Err(err) => {
+                    let _ = sign_in_ui.upgrade_in_event_loop(move |ui| {
+                        ui.set_tokens_status(format!("Sign-in failed: {err}").into());
+                        ui.set_tokens_busy(false);
+                    });
+                }
+            }
+        });
+    });
+
+    let tokens_handle = rt_handle.clone();
+    let tokens_sdk_client = sdk_client.clone();
+    let tokens_ui = ui.as_weak();
+    ui.on_refresh_tokens(move || {
+        let sdk_client = tokens_sdk_client.clone();
+        let tokens_ui = tokens_ui.clone();
+        if let Some(ui) = tokens_ui.upgrade() {
+            ui.set_tokens_busy(true);
+        }
+        tokens_handle.spawn(async move {
+            reload_tokens(&sdk_client, tokens_ui, None).await;
+        });
+    });
+
+    let create_handle = rt_handle.clone();
+    let create_sdk_client = sdk_client.clone();
+    let create_ui = ui.as_weak();
+    ui.on_create_token(move |usage| {
+        let sdk_client = create_sdk_client.clone();
+        let create_ui = create_ui.clone();
+        if let Some(ui) = create_ui.upgrade() {
+            ui.set_tokens_busy(true);
+        }
+        let request = CreateTokenRequest {
+            usage: usage.to_string(),
+            expires_in_hours: None,
+            device_info: None,
+            defaults: TokenDefaults::default(),
+            signing_key: None,
+        };
+        create_handle.spawn(async move {
+            let status = match sdk_client.create_notify_token(&request).await {
+                Ok(created) => {
+                    // 新 Token 同时用于发送测试通知
+                    sdk_client.set_token(&created.token);
+                    let _ = create_ui.upgrade_in_event_loop(move |ui| {
+                        ui.set_new_token(created.token.into());
+                        ui.set_new_token_usage("".into());
+                    });
+                    format!("Token '{}' created", created.usage)
+                }
+                Err(err) => format!("Failed to create token: {err}"),
+            };
+            reload_tokens(&sdk_client, create_ui, Some(status)).await;
+        });
+    });
+
+    let delete_handle = rt_handle.clone();
+    let delete_sdk_client = sdk_client.clone();
+    let delete_ui = ui.as_weak();
+    ui.on_delete_token(move |id| {
+        let sdk_client = delete_sdk_client.clone();
+        let delete_ui = delete_ui.clone();
+        if let Some(ui) = delete_ui.upgrade() {
+            ui.set_tokens_busy(true);
+        }
+        delete_handle.spawn(async move {
+            let status = match sdk_client.delete_user_token(TokenId(id)).await {
+                Ok(()) => "Token deleted".to_string(),
+                Err(err) => format!("Failed to delete token: {err}"),
+            };
+            reload_tokens(&sdk_client, delete_ui, Some(status)).await;
         });
     });
 
@@ -114,9 +487,10 @@ fn run_with_ui() -> anyhow::Result<()> {
     let initial_cache = Arc::clone(&cached_notifies);
     rt_handle.spawn(async move {
         match initial_sdk_client.get_notifies().await {
-            Ok(items) => apply_notifies_to_ui(initial_ui, initial_cache, items),
+            Ok(items) => apply_notifies_to_ui(initial_ui.clone(), initial_cache, items),
             Err(err) => warn!("failed to load notifies: {err}"),
         }
+        reload_unread_counts(&initial_sdk_client, initial_ui).await;
     });
 
     let stats_sdk_client = sdk_client.clone();
@@ -143,29 +517,118 @@ fn run_with_ui() -> anyhow::Result<()> {
     Ok(())
 }
 
-fn resolve_service_addr() -> String {
-    let addr = std::env::var("RUTIFY_ADDR").unwrap_or_else(|_| "0.0.0.0:3000".to_string());
-    format!("http://{}", addr.replace("0.0.0.0", "127.0.0.1"))
+fn resolve_service_addr(source: &ConfigSource) -> String {
+    let addr = ServerConfig::from_source(source)
+        .ok()
+        .and_then(|config| config.local_tcp_addr())
+        .map(|addr| addr.to_string())
+        .unwrap_or_else(|| "127.0.0.1:3000".to_string());
+    format!("http://{addr}")
 }
 
 fn notify_model(items: &[CoreNotifyItem]) -> ModelRc<NotifyItem> {
     let converted: Vec<NotifyItem> = items
         .iter()
         .map(|item| NotifyItem {
-            id: item.id,
+            id: item.id.get(),
             title: item.title.clone().into(),
             notify: item.notify.clone().into(),
             device: item.device.clone().into(),
-            received_at: item
-                .received_at
-                .format("%Y-%m-%d %H:%M:%S")
-                .to_string()
+            received_at: format_time(item.received_at).into(),
+        })
+        .collect();
+    ModelRc::new(VecModel::from(converted))
+}
+
+fn format_time(at: chrono::DateTime<chrono::Utc>) -> String {
+    at.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+fn token_model(tokens: &[TokenInfo]) -> ModelRc<TokenItem> {
+    let converted: Vec<TokenItem> = tokens
+        .iter()
+        .map(|token| TokenItem {
+            id: token.id.get(),
+            usage: token.usage.clone().into(),
+            token_type: token.token_type.clone().into(),
+            created_at: format_time(token.created_at).into(),
+            expires_at: format_time(token.expires_at).into(),
+            last_used_at: token
+                .last_used_at
+                .map(format_time)
+                .unwrap_or_default()
                 .into(),
         })
         .collect();
     ModelRc::new(VecModel::from(converted))
 }
 
+/// 重新读取当前用户的 Token，`status` 为刚完成的操作结果
+async fn reload_tokens(client: &RutifyClient, ui: slint::Weak<AppWindow>, status: Option<String>) {
+    let result = client.get_user_tokens().await;
+    let _ = ui.upgrade_in_event_loop(move |ui| {
+        match result {
+            Ok(tokens) => {
+                ui.set_tokens(token_model(&tokens));
+                ui.set_tokens_status(status.unwrap_or_default().into());
+            }
+            Err(err) => ui.set_tokens_status(format!("Failed to load tokens: {err}").into()),
+        }
+        ui.set_tokens_busy(false);
+    });
+}
+
+/// 按设备统计的未确认通知数，用于侧边栏角标
+async fn reload_unread_counts(client: &RutifyClient, ui: slint::Weak<AppWindow>) {
+    match client.get_notify_counts(CountGroupBy::Device, true).await {
+        Ok(Some(counts)) => {
+            let _ = ui.upgrade_in_event_loop(move |ui| {
+                let (total, model) = unread_model(&counts);
+                ui.set_unread_count(total);
+                ui.set_unread_devices(model);
+            });
+        }
+        Ok(None) => {}
+        Err(err) => warn!("failed to load unread counts: {err}"),
+    }
+}
+
+fn unread_model(counts: &[NotifyCount]) -> (i32, ModelRc<CountItem>) {
+    let total = counts.iter().map(|count| count.count).sum::<u64>();
+    let converted: Vec<CountItem> = counts
+        .iter()
+        .map(|count| CountItem {
+            key: count.key.clone().unwrap_or_else(|| "-".to_string()).into(),
+            count: count.count.try_into().unwrap_or(i32::MAX),
+        })
+        .collect();
+    (
+        total.try_into().unwrap_or(i32::MAX),
+        ModelRc::new(VecModel::from(converted)),
+    )
+}
+
+fn non_empty(text: &str) -> Option<String> {
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn theme_mode(theme: ThemePreference) -> ThemeMode {
+    match theme {
+        ThemePreference::System => ThemeMode::System,
+        ThemePreference::Light => ThemeMode::Light,
+        ThemePreference::Dark => ThemeMode::Dark,
+    }
+}
+
+fn theme_preference(mode: ThemeMode) -> ThemePreference {
+    match mode {
+        ThemeMode::Light => ThemePreference::Light,
+        ThemeMode::Dark => ThemePreference::Dark,
+        _ => ThemePreference::System,
+    }
+}
+
 fn apply_notifies_to_ui(
     ui: slint::Weak<AppWindow>,
     cache: Arc<Mutex<Vec<CoreNotifyItem>>>,
@@ -188,37 +651,132 @@ fn apply_notifies_to_ui(
     });
 }
 
-async fn rutify_service() -> anyhow::Result<()> {
-    let db_url = std::env::var("RUTIFY_DB_URL")
-        .unwrap_or_else(|_| "sqlite://rutify.db?mode=rwc".to_string());
-    let db_cnn = Database::connect(&db_url).await?;
-    db::initialize::initial(&db_cnn).await;
+async fn rutify_service(source: ConfigSource) -> anyhow::Result<()> {
+    let warnings = bootstrap::startup::validate(&source)?;
+    let server_config = Arc::new(ServerConfig::from_source(&source)?);
+    let app_config = bootstrap::config::app_config_from_source(&source)?;
+    let runtime_config = RuntimeConfig::from_source(&source)?;
+    let read_only = bootstrap::read_only::from_source(&source)?;
+    if let Some(cipher) = db::encryption::key_from_source(&source)? {
+        db::encryption::init(cipher);
+    }
+    let db_cnn =
+        Database::connect(db::connect_options(&source, &server_config.database_url)?).await?;
+    // 只读实例连接的副本库无法执行迁移，表结构由主库迁移后同步过来
+    if !read_only {
+        db::initialize::initial(&db_cnn).await;
+    }
 
     let monitoring = MonitoringState::new();
-    setup_metrics_recorder(monitoring.clone());
+
+    let plugins = Arc::new(PluginRegistry::new());
+    plugins.reload(&db_cnn).await?;
+
+    let (escalations, escalation_jobs) = EscalationRegistry::new();
+    let escalations = Arc::new(escalations);
+    escalations.reload(&db_cnn).await?;
+
+    let silences = Arc::new(SilenceRegistry::new());
+    silences.reload(&db_cnn).await?;
 
     let (tx, _) = broadcast::channel(200);
+    let cluster = match ClusterConfig::from_source(&source)? {
+        Some(cluster_config) => Some(Arc::new(
+            services::cluster::spawn(cluster_config, tx.clone()).await?,
+        )),
+        None => None,
+    };
+    let link_previewer = LinkPreviewConfig::from_source(&source)?
+        .map(LinkPreviewer::new)
+        .transpose()?
+        .map(Arc::new);
+    // 只读实例不推送，也不接受订阅
+    let web_push = if read_only {
+        None
+    } else {
+        WebPushConfig::from_source(&source)?
+            .map(WebPush::new)
+            .transpose()?
+            .map(Arc::new)
+    };
+    let capabilities = bootstrap::capabilities::from_source(
+        &source,
+        read_only,
+        cluster.is_some(),
+        link_previewer.is_some(),
+    );
     let state = Arc::new(AppState {
+        config: server_config.clone(),
         db: db_cnn,
         tx,
         monitoring,
+        plugins,
+        escalations,
+        silences,
+        maintenance: Arc::new(Maintenance::new()),
+        db_maintenance: Arc::new(DbMaintenance::new(runtime_config.db_maintenance.clone())),
+        token_cache: Arc::new(TokenCache::from_source(&source)?),
+        auth_policy: AuthPolicy::from_source(&source)?,
+        password_policy: PasswordPolicy::from_source(&source)?,
+        registration_gate: Arc::new(RegistrationGate::from_source(&source)?),
+        auth_failures: Arc::new(AuthFailureLog::new()),
+        connections: Arc::new(ConnectionTracker::new()),
+        size_limits: SizeLimits::from_source(&source)?,
+        get_dedup: Arc::new(GetDedup::from_source(&source)?),
+        link_previewer,
+        web_push,
+        config_reloader: Arc::new(ConfigReloader::new(runtime_config)),
+        cluster,
+        leadership: Arc::new(Leadership::new()),
+        read_only,
+        capabilities,
     });
+    bootstrap::startup::log_banner(&server_config, &state, &warnings);
+    bootstrap::reload::spawn_sighup(state.clone())?;
+    services::escalation::spawn(state.clone(), escalation_jobs);
+    services::db_maintenance::spawn(state.clone());
+    services::monitors::spawn(state.clone());
+    services::checks::spawn(state.clone())?;
+    if read_only {
+        // 不参与选主，后台任务与入库桥接都不会运行
+        info!("Read-only mode: ingestion, mutations and background jobs are disabled");
+    } else {
+        services::leader::spawn(state.clone());
+        services::unifiedpush::spawn(state.clone(), UnifiedPushConfig::from_source(&source)?)?;
+        services::web_push::spawn(state.clone());
+        services::digest::spawn(state.clone());
+        spawn_bridges(&source, &state).await?;
+    }
+    #[cfg(feature = "grpc")]
+    if let Some(grpc_config) = grpc::GrpcConfig::from_source(&source)? {
+        grpc::spawn(grpc_config, state.clone()).await?;
+    }
 
-    let app_config = bootstrap::config::app_config_from_env();
-    let app_builder = bootstrap::app::app_builder(state, app_config)?;
-    let server_config = bootstrap::config::server_config_from_env()?;
-    let server = Server::new(server_config, app_builder);
+    let app = bootstrap::app::app(state.clone(), app_config);
+    bootstrap::server::serve(&server_config, app).await?;
+    services::leader::release(&state).await;
+    Ok(())
+}
 
-    server
-        .start()
-        .await
-        .map_err(|e| anyhow::anyhow!("failed to start server: {e}"))?;
+/// 启动 MQTT、Syslog 与邮件网关等入库桥接
+async fn spawn_bridges(source: &ConfigSource, state: &Arc<AppState>) -> anyhow::Result<()> {
+    #[cfg(feature = "mqtt")]
+    if let Some(mqtt_config) = bridges::mqtt::MqttConfig::from_source(source)? {
+        bridges::mqtt::spawn(mqtt_config, state.clone())?;
+    }
+    if let Some(syslog_config) = bridges::syslog::SyslogConfig::from_source(source)? {
+        bridges::syslog::spawn(syslog_config, state.clone()).await?;
+    }
+    if let Some(smtp_config) = bridges::smtp::SmtpConfig::from_source(source)? {
+        bridges::smtp::spawn(smtp_config, state.clone()).await?;
+    }
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rutify_core::NotifyId;
     use sea_orm::Database;
     use slint::Model;
 
@@ -237,7 +795,7 @@ mod tests {
     #[test]
     fn test_resolve_service_addr_default() {
         // Test the function with default behavior
-        let addr = resolve_service_addr();
+        let addr = resolve_service_addr(&ConfigSource::from_pairs([]));
         // Should return default address when RUTIFY_ADDR is not set
         assert!(addr.contains("127.0.0.1"));
         assert!(addr.contains("3000"));
@@ -245,12 +803,9 @@ mod tests {
 
     #[test]
     fn test_resolve_service_addr_custom() {
-        unsafe {
-            std::env::set_var("RUTIFY_ADDR", "0.0.0.0:8080");
-            let addr = resolve_service_addr();
-            assert_eq!(addr, "http://127.0.0.1:8080");
-            std::env::remove_var("RUTIFY_ADDR");
-        }
+        let addr =
+            resolve_service_addr(&ConfigSource::from_pairs([("RUTIFY_ADDR", "0.0.0.0:8080")]));
+        assert_eq!(addr, "http://127.0.0.1:8080");
     }
 
     #[test]
@@ -263,33 +818,79 @@ mod tests {
     #[test]
     fn test_notify_model_single_item() {
         let items = vec![CoreNotifyItem {
-            id: 1,
+            id: NotifyId(1),
             title: "Test".to_string(),
             notify: "Message".to_string(),
             device: "Device".to_string(),
             received_at: chrono::Utc::now(),
+            ..Default::default()
         }];
 
         let model = notify_model(&items);
         assert_eq!(model.row_count(), 1);
     }
 
+    #[test]
+    fn test_token_model() {
+        let tokens = vec![TokenInfo {
+            id: TokenId(3),
+            usage: "backup".to_string(),
+            token_type: "notify_bearer".to_string(),
+            device_info: None,
+            created_at: rutify_core::timestamp::parse("2024-01-01T00:00:00Z").unwrap(),
+            expires_at: rutify_core::timestamp::parse("2024-02-01T00:00:00Z").unwrap(),
+            last_used_at: None,
+            owner: None,
+        }];
+
+        let model = token_model(&tokens);
+        let row = model.row_data(0).unwrap();
+        assert_eq!(row.id, 3);
+        assert_eq!(row.usage, "backup");
+        assert_eq!(row.expires_at, "2024-02-01 00:00:00");
+        assert_eq!(row.last_used_at, "");
+        assert_eq!(non_empty("  "), None);
+        assert_eq!(non_empty(" nas ").as_deref(), Some("nas"));
+    }
+
+    #[test]
+    fn test_unread_model() {
+        let counts = vec![
+            NotifyCount {
+                key: Some("nas".to_string()),
+                count: 3,
+            },
+            NotifyCount {
+                key: None,
+                count: 1,
+            },
+        ];
+        let (total, model) = unread_model(&counts);
+        assert_eq!(total, 4);
+        assert_eq!(model.row_count(), 2);
+        let missing = model.row_data(1).unwrap();
+        assert_eq!(missing.key.as_str(), "-");
+        assert_eq!(missing.count, 1);
+    }
+
     #[test]
     fn test_notify_model_multiple_items() {
         let items = vec![
             CoreNotifyItem {
-                id: 1,
+                id: NotifyId(1),
                 title: "Test 1".to_string(),
                 notify: "Message 1".to_string(),
                 device: "Device 1".to_string(),
                 received_at: chrono::Utc::now(),
+                ..Default::default()
             },
             CoreNotifyItem {
-                id: 2,
+                id: NotifyId(2),
                 title: "Test 2".to_string(),
                 notify: "Message 2".to_string(),
                 device: "Device 2".to_string(),
                 received_at: chrono::Utc::now(),
+                ..Default::default()
             },
         ];
 
@@ -314,11 +915,12 @@ mod tests {
     fn test_apply_notifies_to_ui_with_items() {
         let cache = Arc::new(std::sync::Mutex::new(Vec::<CoreNotifyItem>::new()));
         let items = vec![CoreNotifyItem {
-            id: 1,
+            id: NotifyId(1),
             title: "Test".to_string(),
             notify: "Message".to_string(),
             device: "Device".to_string(),
             received_at: chrono::Utc::now(),
+            ..Default::default()
         }];
 
         // This should not panic
@@ -331,6 +933,6 @@ mod tests {
         // Verify the cache was updated
         let guard = cache.lock().unwrap();
         assert_eq!(guard.len(), 1);
-        assert_eq!(guard[0].id, 1);
+        assert_eq!(guard[0].id, NotifyId(1));
     }
 }
@@ -0,0 +1,37 @@
+//! 成功响应的统一结构 `{"status":"ok","data":...}`
+
+use crate::error::AppError;
+use axum::Json;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// 包装为统一结构，对象数据的字段同时保留在顶层
+///
+/// 用于原先直接返回数据的接口：过渡期内按旧格式读取顶层字段的客户端不受影响。
+pub(crate) fn with_legacy_fields<T: Serialize>(data: T) -> Result<Json<Value>, AppError> {
+    let data = serde_json::to_value(data)?;
+    let mut body = match &data {
+        Value::Object(fields) => fields.clone(),
+        _ => Map::new(),
+    };
+    body.insert("status".to_string(), Value::from("ok"));
+    body.insert("data".to_string(), data);
+    Ok(Json(Value::Object(body)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_legacy_fields() {
+        let Json(body) = with_legacy_fields(serde_json::json!({ "id": 7 })).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({ "status": "ok", "data": { "id": 7 }, "id": 7 })
+        );
+
+        let Json(body) = with_legacy_fields(vec![1, 2]).unwrap();
+        assert_eq!(body, serde_json::json!({ "status": "ok", "data": [1, 2] }));
+    }
+}
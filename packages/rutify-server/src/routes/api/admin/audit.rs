@@ -0,0 +1,96 @@
+use crate::db::audit_log;
+use crate::db::users::Model as UserModel;
+use crate::error::AppError;
+use crate::state::AppState;
+use axum::extract::{OriginalUri, Query, Request, State};
+use axum::http::{Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use rutify_core::AuditEntry;
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::warn;
+
+/// 单次最多返回的审计记录数
+const MAX_LIMIT: u64 = 500;
+
+pub(crate) fn router() -> Router<Arc<AppState>> {
+    Router::new().route("/audit", get(list_audit_handler))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct AuditQuery {
+    limit: u64,
+    /// 只返回 ID 大于该值的记录，用于持续跟踪
+    after_id: Option<i32>,
+}
+
+impl Default for AuditQuery {
+    fn default() -> Self {
+        Self {
+            limit: 50,
+            after_id: None,
+        }
+    }
+}
+
+/// 最近的审计记录，按时间顺序排列
+async fn list_audit_handler(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<AuditQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let data: Vec<AuditEntry> =
+        audit_log::recent(&state.db, query.limit.clamp(1, MAX_LIMIT), query.after_id)
+            .await?
+            .into_iter()
+            .map(audit_log::Model::into_entry)
+            .collect();
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": data })),
+    ))
+}
+
+/// 记录管理员发起的修改请求（非 GET），需位于管理员认证之后
+///
+/// 写入失败只记录日志，不影响请求本身。
+pub(super) async fn audit_middleware(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().clone();
+    if method == Method::GET || method == Method::HEAD {
+        return next.run(request).await;
+    }
+    let path = request
+        .extensions()
+        .get::<OriginalUri>()
+        .map(|uri| uri.path().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string());
+    let actor = request
+        .extensions()
+        .get::<UserModel>()
+        .map(|user| user.username.clone())
+        .unwrap_or_default();
+
+    let response = next.run(request).await;
+    if let Err(e) = audit_log::record(
+        &state.db,
+        &actor,
+        method.as_str(),
+        &path,
+        response.status().as_u16(),
+    )
+    .await
+    {
+        warn!(
+            "Failed to record audit entry for {} {}: {}",
+            method, path, e
+        );
+    }
+    response
+}
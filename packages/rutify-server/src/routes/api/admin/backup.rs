@@ -0,0 +1,54 @@
+use crate::error::AppError;
+use crate::services::backup;
+use crate::state::AppState;
+use axum::Router;
+use axum::body::Body;
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use chrono::Utc;
+use futures_util::StreamExt;
+use std::sync::Arc;
+use tokio_util::io::ReaderStream;
+use tracing::error;
+
+pub(crate) fn router() -> Router<Arc<AppState>> {
+    Router::new().route("/backup", get(backup_handler))
+}
+
+/// 生成备份并以流的形式下载，格式与 `rutify-server backup` 相同
+async fn backup_handler(State(state): State<Arc<AppState>>) -> Result<impl IntoResponse, AppError> {
+    let workdir = tempfile::tempdir().map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    let file_name = format!(
+        "rutify-backup-{}.tar.zst",
+        Utc::now().format("%Y%m%d-%H%M%S")
+    );
+    let path = workdir.path().join(&file_name);
+
+    backup::create_backup(&state.db, &state.config.database_url, &path)
+        .await
+        .map_err(|e| {
+            error!(error = %e, "backup failed");
+            AppError::DatabaseError("backup failed".to_string())
+        })?;
+    let file = tokio::fs::File::open(&path)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    // 临时目录随响应流一起释放，下载结束后自动删除
+    let body = Body::from_stream(ReaderStream::new(file).map(move |chunk| {
+        let _workdir = &workdir;
+        chunk
+    }));
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zstd".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{file_name}\""),
+            ),
+        ],
+        body,
+    ))
+}
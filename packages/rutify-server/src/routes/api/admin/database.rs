@@ -0,0 +1,38 @@
+use crate::error::AppError;
+use crate::state::AppState;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use std::sync::Arc;
+
+pub(crate) fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/status", get(db_status_handler))
+        .route("/maintenance", post(run_maintenance_handler))
+}
+
+async fn db_status_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let status = state
+        .db_maintenance
+        .status(&state.db, &state.config.database_url)
+        .await?;
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": status })),
+    ))
+}
+
+/// 立即执行一次数据库维护，SQLite 的 VACUUM 期间写入会被阻塞
+async fn run_maintenance_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let run = state.db_maintenance.run(&state.db).await;
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": run })),
+    ))
+}
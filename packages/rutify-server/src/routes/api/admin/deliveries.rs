@@ -0,0 +1,125 @@
+use crate::db::tokens::TokenType;
+use crate::db::{notifies, token_ops, tokens, users};
+use crate::error::AppError;
+use crate::services::delivery::{self, DeliveryStatus};
+use crate::state::AppState;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use rutify_core::NotifyId;
+use sea_orm::{DatabaseConnection, EntityTrait};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub(crate) fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/subscribers", get(list_subscribers_handler))
+        .route("/notifies/{id}/deliveries", get(notify_deliveries_handler))
+}
+
+/// 订阅者（通知 Token）的投递进度
+#[derive(Debug, Serialize)]
+struct SubscriberResponse {
+    token_id: i32,
+    usage: String,
+    device_info: Option<String>,
+    username: Option<String>,
+    last_delivered_id: Option<i32>,
+    last_delivered_at: Option<DateTime<Utc>>,
+    last_used_at: Option<DateTime<Utc>>,
+}
+
+/// 单条通知对某个订阅者的投递状态
+#[derive(Debug, Serialize)]
+struct DeliveryResponse {
+    #[serde(flatten)]
+    subscriber: SubscriberResponse,
+    status: DeliveryStatus,
+}
+
+/// 未过期的通知 Token 及其所属用户名
+async fn load_subscribers(
+    db: &DatabaseConnection,
+) -> Result<Vec<(tokens::Model, Option<String>)>, AppError> {
+    let usernames: HashMap<Uuid, String> = users::Entity::find()
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|user| (user.id, user.username))
+        .collect();
+    let now = Utc::now();
+
+    Ok(token_ops::list_tokens_by_type(db, TokenType::NotifyBearer)
+        .await?
+        .into_iter()
+        .filter(|token| token.expires_at > now)
+        .map(|token| {
+            let username = token
+                .user_id
+                .and_then(|user_id| usernames.get(&user_id).cloned());
+            (token, username)
+        })
+        .collect())
+}
+
+fn subscriber_response(token: &tokens::Model, username: Option<String>) -> SubscriberResponse {
+    SubscriberResponse {
+        token_id: token.id,
+        usage: token.usage.clone(),
+        device_info: token.device_info.clone(),
+        username,
+        last_delivered_id: token.last_delivered_id,
+        last_delivered_at: token.last_delivered_at,
+        last_used_at: token.last_used_at,
+    }
+}
+
+async fn list_subscribers_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let data: Vec<SubscriberResponse> = load_subscribers(&state.db)
+        .await?
+        .into_iter()
+        .map(|(token, username)| subscriber_response(&token, username))
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": data })),
+    ))
+}
+
+async fn notify_deliveries_handler(
+    State(state): State<Arc<AppState>>,
+    Path(NotifyId(id)): Path<NotifyId>,
+) -> Result<impl IntoResponse, AppError> {
+    let notify = notifies::Entity::find_by_id(id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Notify not found".to_string()))?;
+
+    let data: Vec<DeliveryResponse> = load_subscribers(&state.db)
+        .await?
+        .into_iter()
+        .map(|(token, username)| DeliveryResponse {
+            status: delivery::delivery_status(&notify, &token, username.as_deref()),
+            subscriber: subscriber_response(&token, username),
+        })
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "status": "ok",
+            "data": {
+                "notify_id": notify.id,
+                "deliveries": data,
+            }
+        })),
+    ))
+}
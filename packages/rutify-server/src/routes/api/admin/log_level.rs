@@ -0,0 +1,50 @@
+use crate::bootstrap::logging;
+use crate::error::AppError;
+use crate::state::AppState;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::info;
+
+pub(crate) fn router() -> Router<Arc<AppState>> {
+    Router::new().route(
+        "/log-level",
+        get(get_log_level_handler).put(set_log_level_handler),
+    )
+}
+
+/// 日志过滤规则，语法同 `RUST_LOG`，如 `debug` 或 `info,rutify_server=trace`
+#[derive(Debug, Deserialize)]
+struct SetLogLevelRequest {
+    filter: String,
+}
+
+async fn get_log_level_handler() -> Result<impl IntoResponse, AppError> {
+    Ok((
+        StatusCode::OK,
+        Json(
+            serde_json::json!({ "status": "ok", "data": { "filter": logging::current_filter() } }),
+        ),
+    ))
+}
+
+/// 立即替换日志过滤规则，进程重启或重新加载到不同的 `RUST_LOG` 后恢复
+async fn set_log_level_handler(
+    Json(payload): Json<SetLogLevelRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let filter = payload.filter.trim();
+    if filter.is_empty() {
+        return Err(AppError::BadRequest("filter must not be empty".to_string()));
+    }
+    logging::set_filter(filter).map_err(|e| AppError::BadRequest(format!("{e:#}")))?;
+    info!(filter, "Log filter changed");
+    Ok((
+        StatusCode::OK,
+        Json(
+            serde_json::json!({ "status": "ok", "data": { "filter": logging::current_filter() } }),
+        ),
+    ))
+}
@@ -0,0 +1,101 @@
+use crate::error::AppError;
+use crate::services::maintenance::ReleaseMode;
+use crate::services::notify;
+use crate::state::AppState;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::info;
+
+pub(crate) fn router() -> Router<Arc<AppState>> {
+    Router::new().route(
+        "/",
+        get(get_maintenance_handler)
+            .post(start_maintenance_handler)
+            .delete(end_maintenance_handler),
+    )
+}
+
+/// 进入维护模式请求
+#[derive(Debug, Default, Deserialize)]
+struct StartMaintenanceRequest {
+    reason: Option<String>,
+}
+
+/// 结束维护模式参数，`mode` 为 `flush`（默认）或 `summary`
+#[derive(Debug, Deserialize)]
+struct EndMaintenanceQuery {
+    mode: Option<String>,
+}
+
+async fn get_maintenance_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": state.maintenance.status() })),
+    ))
+}
+
+async fn start_maintenance_handler(
+    State(state): State<Arc<AppState>>,
+    payload: Option<Json<StartMaintenanceRequest>>,
+) -> Result<impl IntoResponse, AppError> {
+    let payload = payload.map(|Json(payload)| payload).unwrap_or_default();
+    let reason = payload
+        .reason
+        .map(|reason| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty());
+
+    let status = state.maintenance.start(reason);
+    info!("Maintenance mode enabled: {:?}", status.reason);
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": status })),
+    ))
+}
+
+async fn end_maintenance_handler(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<EndMaintenanceQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let mode = match query.mode.as_deref() {
+        Some(mode) => ReleaseMode::parse(mode).ok_or_else(|| {
+            AppError::BadRequest("mode must be one of: flush, summary".to_string())
+        })?,
+        None => ReleaseMode::Flush,
+    };
+    let window = state
+        .maintenance
+        .finish()
+        .ok_or_else(|| AppError::BadRequest("Maintenance mode is not enabled".to_string()))?;
+
+    let queued = window.deliveries.len();
+    let dropped = window.dropped;
+    let deliveries = window.release(mode);
+    let released = deliveries.len();
+    for delivery in deliveries {
+        notify::dispatch(&state, delivery);
+    }
+    info!(
+        "Maintenance mode disabled, released {} of {} queued deliveries",
+        released, queued
+    );
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "status": "ok",
+            "data": {
+                "queued": queued,
+                "dropped": dropped,
+                "released": released,
+            }
+        })),
+    ))
+}
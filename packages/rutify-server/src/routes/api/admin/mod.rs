@@ -0,0 +1,41 @@
+use crate::services::auth::user::admin_auth_middleware;
+use crate::state::AppState;
+use axum::{Router, middleware};
+use std::sync::Arc;
+
+mod audit;
+mod backup;
+mod database;
+mod deliveries;
+mod log_level;
+mod maintenance;
+mod overview;
+mod plugins;
+mod purge;
+mod reload;
+mod settings;
+mod tokens;
+mod users;
+
+pub(crate) fn router(state: Arc<AppState>) -> Router<Arc<AppState>> {
+    Router::new()
+        .nest("/plugins", plugins::router())
+        .nest("/maintenance", maintenance::router())
+        .merge(backup::router())
+        .nest("/db", database::router())
+        .merge(deliveries::router())
+        .merge(log_level::router())
+        .merge(purge::router())
+        .merge(reload::router())
+        .merge(tokens::router())
+        .merge(overview::router())
+        .merge(settings::router())
+        .merge(users::router())
+        .merge(audit::router())
+        // 后添加的层先执行：先认证管理员，再记录审计日志
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            audit::audit_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(state, admin_auth_middleware))
+}
@@ -0,0 +1,36 @@
+use crate::db::token_ops;
+use crate::error::AppError;
+use crate::routes::api::stats::current_stats;
+use crate::services::db_maintenance::database_size;
+use crate::state::AppState;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use rutify_core::AdminOverview;
+use std::sync::Arc;
+
+pub(crate) fn router() -> Router<Arc<AppState>> {
+    Router::new().route("/overview", get(overview_handler))
+}
+
+/// 仪表盘所需的全部数据，一次请求返回
+async fn overview_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let data = AdminOverview {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        stats: current_stats(&state).await?,
+        connections: state.connections.active() as u64,
+        tokens: token_ops::count_active_tokens_by_type(&state.db).await?,
+        auth_failures_total: state.auth_failures.total(),
+        recent_auth_failures: state.auth_failures.recent(),
+        db_size_bytes: database_size(&state.db, &state.config.database_url).await?,
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": data })),
+    ))
+}
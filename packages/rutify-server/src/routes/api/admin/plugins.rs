@@ -0,0 +1,183 @@
+use crate::db::plugins;
+use crate::error::AppError;
+use crate::services::plugins::PluginMetrics;
+use crate::state::AppState;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{DateTime, Utc};
+use sea_orm::{ActiveModelTrait, EntityTrait, QueryOrder, Set};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+pub(crate) fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(list_plugins_handler).post(create_plugin_handler))
+        .route(
+            "/{id}",
+            get(get_plugin_handler)
+                .put(update_plugin_handler)
+                .delete(delete_plugin_handler),
+        )
+}
+
+/// 插件创建请求
+#[derive(Debug, Deserialize)]
+struct CreatePluginRequest {
+    name: String,
+    script: String,
+    enabled: Option<bool>,
+    sort_order: Option<i32>,
+}
+
+/// 插件更新请求（未提供的字段保持不变）
+#[derive(Debug, Deserialize)]
+struct UpdatePluginRequest {
+    name: Option<String>,
+    script: Option<String>,
+    enabled: Option<bool>,
+    sort_order: Option<i32>,
+}
+
+/// 插件信息响应
+#[derive(Debug, Serialize)]
+struct PluginResponse {
+    id: i32,
+    name: String,
+    script: String,
+    enabled: bool,
+    sort_order: i32,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    metrics: PluginMetrics,
+}
+
+fn plugin_response(state: &AppState, item: plugins::Model) -> PluginResponse {
+    PluginResponse {
+        metrics: state.plugins.metrics(item.id),
+        id: item.id,
+        name: item.name,
+        script: item.script,
+        enabled: item.enabled,
+        sort_order: item.sort_order,
+        created_at: item.created_at,
+        updated_at: item.updated_at,
+    }
+}
+
+async fn find_plugin(state: &AppState, id: i32) -> Result<plugins::Model, AppError> {
+    plugins::Entity::find_by_id(id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Plugin not found".to_string()))
+}
+
+async fn list_plugins_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let items = plugins::Entity::find()
+        .order_by_asc(plugins::Column::SortOrder)
+        .order_by_asc(plugins::Column::Id)
+        .all(&state.db)
+        .await?;
+
+    let data: Vec<PluginResponse> = items
+        .into_iter()
+        .map(|item| plugin_response(&state, item))
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": data })),
+    ))
+}
+
+async fn get_plugin_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let item = find_plugin(&state, id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": plugin_response(&state, item) })),
+    ))
+}
+
+async fn create_plugin_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<CreatePluginRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if request.name.trim().is_empty() {
+        return Err(AppError::BadRequest("Plugin name is required".to_string()));
+    }
+    state.plugins.compile(&request.script)?;
+
+    let now = Utc::now();
+    let item = plugins::ActiveModel {
+        name: Set(request.name),
+        script: Set(request.script),
+        enabled: Set(request.enabled.unwrap_or(true)),
+        sort_order: Set(request.sort_order.unwrap_or(0)),
+        created_at: Set(now),
+        updated_at: Set(now),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    state.plugins.reload(&state.db).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({ "status": "ok", "data": plugin_response(&state, item) })),
+    ))
+}
+
+async fn update_plugin_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Json(request): Json<UpdatePluginRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let mut item: plugins::ActiveModel = find_plugin(&state, id).await?.into();
+
+    if let Some(name) = request.name {
+        item.name = Set(name);
+    }
+    if let Some(script) = request.script {
+        state.plugins.compile(&script)?;
+        item.script = Set(script);
+    }
+    if let Some(enabled) = request.enabled {
+        item.enabled = Set(enabled);
+    }
+    if let Some(sort_order) = request.sort_order {
+        item.sort_order = Set(sort_order);
+    }
+    item.updated_at = Set(Utc::now());
+
+    let item = item.update(&state.db).await?;
+    state.plugins.reload(&state.db).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": plugin_response(&state, item) })),
+    ))
+}
+
+async fn delete_plugin_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let deleted = plugins::Entity::delete_by_id(id).exec(&state.db).await?;
+    if deleted.rows_affected == 0 {
+        return Err(AppError::NotFound("Plugin not found".to_string()));
+    }
+
+    state.plugins.forget(id);
+    state.plugins.reload(&state.db).await?;
+
+    Ok((StatusCode::OK, Json(serde_json::json!({ "status": "ok" }))))
+}
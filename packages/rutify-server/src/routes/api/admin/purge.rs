@@ -0,0 +1,65 @@
+use crate::db::notifies;
+use crate::error::AppError;
+use crate::services::notify;
+use crate::state::AppState;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::post;
+use axum::{Json, Router};
+use rutify_core::{EventKind, PurgeNotifiesRequest, PurgeNotifiesResult};
+use std::sync::Arc;
+use tracing::info;
+
+pub(crate) fn router() -> Router<Arc<AppState>> {
+    Router::new().route("/notifies/purge", post(purge_notifies_handler))
+}
+
+async fn purge_notifies_handler(
+    State(state): State<Arc<AppState>>,
+    Json(mut payload): Json<PurgeNotifiesRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    payload.device = normalize(payload.device);
+    payload.channel = normalize(payload.channel);
+    // 清空全部通知仍使用 DELETE /api/notifies，这里要求至少一个条件防止误删
+    if payload.older_than_secs.is_none()
+        && payload.device.is_none()
+        && payload.channel.is_none()
+        && payload.read.is_none()
+    {
+        return Err(AppError::BadRequest(
+            "at least one of older_than_secs, device, channel or read is required".to_string(),
+        ));
+    }
+
+    let (matched, deleted) = notifies::purge(&state.db, &payload).await?;
+    if !payload.dry_run {
+        info!(matched, deleted, criteria = ?payload, "Purged notifies");
+    }
+    if deleted > 0 {
+        notify::broadcast(
+            &state,
+            EventKind::StatsChanged,
+            notify::system_data(
+                "Notifications purged",
+                format!("{deleted} notifications were purged"),
+            ),
+        );
+    }
+
+    let data = PurgeNotifiesResult {
+        matched,
+        deleted,
+        dry_run: payload.dry_run,
+    };
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": data })),
+    ))
+}
+
+fn normalize(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
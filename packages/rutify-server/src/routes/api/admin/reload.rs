@@ -0,0 +1,22 @@
+use crate::bootstrap::reload;
+use crate::error::AppError;
+use crate::state::AppState;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::post;
+use axum::{Json, Router};
+use std::sync::Arc;
+
+pub(crate) fn router() -> Router<Arc<AppState>> {
+    Router::new().route("/reload", post(reload_handler))
+}
+
+/// 重新加载配置，与向进程发送 SIGHUP 效果相同
+async fn reload_handler(State(state): State<Arc<AppState>>) -> Result<impl IntoResponse, AppError> {
+    let outcome = reload::reload(&state).map_err(|e| AppError::BadRequest(format!("{e:#}")))?;
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": outcome })),
+    ))
+}
@@ -0,0 +1,43 @@
+use crate::bootstrap::settings;
+use crate::error::AppError;
+use crate::state::AppState;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{get, put};
+use axum::{Json, Router};
+use serde::Deserialize;
+use std::sync::Arc;
+
+pub(crate) fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/settings", get(list_settings_handler))
+        .route("/settings/{key}", put(set_setting_handler))
+}
+
+#[derive(Debug, Deserialize)]
+struct SetSettingRequest {
+    value: String,
+}
+
+async fn list_settings_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": settings::list(&state) })),
+    ))
+}
+
+/// 修改运行时设置，下一次重新加载配置后恢复为环境变量中的值
+async fn set_setting_handler(
+    State(state): State<Arc<AppState>>,
+    Path(key): Path<String>,
+    Json(payload): Json<SetSettingRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let setting = settings::set(&state, &key, &payload.value)?;
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": setting })),
+    ))
+}
@@ -0,0 +1,9 @@
+use crate::services::auth::auth::get_all_tokens;
+use crate::state::AppState;
+use axum::Router;
+use axum::routing::get;
+use std::sync::Arc;
+
+pub(crate) fn router() -> Router<Arc<AppState>> {
+    Router::new().route("/tokens", get(get_all_tokens))
+}
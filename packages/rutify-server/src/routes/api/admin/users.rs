@@ -0,0 +1,88 @@
+use crate::db::users::{self, Model as UserModel, UserRole};
+use crate::error::AppError;
+use crate::state::AppState;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{get, post};
+use axum::{Extension, Json, Router};
+use rutify_core::AdminUser;
+use std::sync::Arc;
+use tracing::info;
+
+pub(crate) fn router() -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/users", get(list_users_handler))
+        .route("/users/{username}/disable", post(disable_user_handler))
+        .route("/users/{username}/enable", post(enable_user_handler))
+        .route("/users/{username}/promote", post(promote_user_handler))
+}
+
+async fn list_users_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let data: Vec<AdminUser> = users::list(&state.db)
+        .await?
+        .into_iter()
+        .map(UserModel::into_admin_user)
+        .collect();
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": data })),
+    ))
+}
+
+/// 停用用户，该用户无法再登录，已签发的用户 JWT 立即失效；不能停用自己
+async fn disable_user_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(admin): Extension<UserModel>,
+    Path(username): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    if username == admin.username {
+        return Err(AppError::BadRequest(
+            "You cannot disable your own account".to_string(),
+        ));
+    }
+    let user = update_user(&state, &username, Some(true), None).await?;
+    info!(username, by = admin.username, "User disabled");
+    Ok(user_response(user))
+}
+
+async fn enable_user_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(admin): Extension<UserModel>,
+    Path(username): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = update_user(&state, &username, Some(false), None).await?;
+    info!(username, by = admin.username, "User enabled");
+    Ok(user_response(user))
+}
+
+/// 将用户提升为管理员
+async fn promote_user_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(admin): Extension<UserModel>,
+    Path(username): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let user = update_user(&state, &username, None, Some(UserRole::Admin)).await?;
+    info!(username, by = admin.username, "User promoted to admin");
+    Ok(user_response(user))
+}
+
+async fn update_user(
+    state: &AppState,
+    username: &str,
+    disabled: Option<bool>,
+    role: Option<UserRole>,
+) -> Result<UserModel, AppError> {
+    users::update_by_username(&state.db, username, disabled, role)
+        .await?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))
+}
+
+fn user_response(user: UserModel) -> impl IntoResponse {
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": user.into_admin_user() })),
+    )
+}
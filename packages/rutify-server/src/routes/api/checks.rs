@@ -0,0 +1,258 @@
+use crate::db::{check_results, checks};
+use crate::error::AppError;
+use crate::services::auth::user::{UserClaims, user_auth_middleware};
+use crate::services::checks::{MAX_TIMEOUT_SECS, MIN_INTERVAL_SECS, validate_url};
+use crate::state::AppState;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{get, post, put};
+use axum::{Extension, Json, Router, middleware};
+use chrono::Utc;
+use rutify_core::{CheckResult, CheckStatus, CreateCheckRequest, MAX_PRIORITY, UptimeCheck};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+const DEFAULT_INTERVAL_SECS: u64 = 60;
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+const DEFAULT_ALERT_PRIORITY: u8 = 4;
+const DEFAULT_HISTORY_LIMIT: u64 = 100;
+const MAX_HISTORY_LIMIT: u64 = 1000;
+
+pub(crate) fn router(state: Arc<AppState>) -> Router<Arc<AppState>> {
+    let write_router = Router::new()
+        .route("/", post(create_check_handler))
+        .route(
+            "/{id}",
+            put(update_check_handler).delete(delete_check_handler),
+        )
+        .layer(middleware::from_fn_with_state(state, user_auth_middleware));
+
+    Router::new()
+        .route("/", get(list_checks_handler))
+        .route("/{id}", get(get_check_handler))
+        .route("/{id}/history", get(check_history_handler))
+        .merge(write_router)
+}
+
+/// 主动检查更新请求（未提供的字段保持不变）
+#[derive(Debug, Deserialize)]
+struct UpdateCheckRequest {
+    name: Option<String>,
+    url: Option<String>,
+    interval_secs: Option<u64>,
+    timeout_secs: Option<u64>,
+    /// 传 0 表示恢复为任意 2xx
+    expected_status: Option<u16>,
+    priority: Option<u8>,
+    channel: Option<String>,
+    enabled: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HistoryQuery {
+    limit: Option<u64>,
+}
+
+fn validate_settings(
+    name: &str,
+    interval_secs: u64,
+    timeout_secs: u64,
+    priority: u8,
+) -> Result<(), AppError> {
+    if name.trim().is_empty() {
+        return Err(AppError::BadRequest("name must not be empty".to_string()));
+    }
+    if !(MIN_INTERVAL_SECS..=i64::MAX as u64).contains(&interval_secs) {
+        return Err(AppError::BadRequest(format!(
+            "interval_secs must be at least {MIN_INTERVAL_SECS}"
+        )));
+    }
+    if !(1..=MAX_TIMEOUT_SECS).contains(&timeout_secs) {
+        return Err(AppError::BadRequest(format!(
+            "timeout_secs must be between 1 and {MAX_TIMEOUT_SECS}"
+        )));
+    }
+    if !(1..=MAX_PRIORITY).contains(&priority) {
+        return Err(AppError::BadRequest(format!(
+            "priority must be between 1 and {MAX_PRIORITY}"
+        )));
+    }
+    Ok(())
+}
+
+/// 空字符串视为不指定频道
+fn channel(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+async fn find_check(state: &AppState, id: i32) -> Result<checks::Model, AppError> {
+    checks::Entity::find_by_id(id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Check not found".to_string()))
+}
+
+async fn list_checks_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let data: Vec<UptimeCheck> = checks::Entity::find()
+        .order_by_asc(checks::Column::Name)
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|item| item.into_check())
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": data })),
+    ))
+}
+
+async fn get_check_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let item = find_check(&state, id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": item.into_check() })),
+    ))
+}
+
+/// 最近的检查结果，新的在前
+async fn check_history_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Query(query): Query<HistoryQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    find_check(&state, id).await?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_HISTORY_LIMIT)
+        .clamp(1, MAX_HISTORY_LIMIT);
+    let data: Vec<CheckResult> = check_results::Entity::find()
+        .filter(check_results::Column::CheckId.eq(id))
+        .order_by_desc(check_results::Column::CheckedAt)
+        .limit(limit)
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|item| item.into_result())
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": data })),
+    ))
+}
+
+async fn create_check_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<UserClaims>,
+    Json(request): Json<CreateCheckRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let interval_secs = request.interval_secs.unwrap_or(DEFAULT_INTERVAL_SECS);
+    let timeout_secs = request.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS);
+    let priority = request.priority.unwrap_or(DEFAULT_ALERT_PRIORITY);
+    validate_settings(&request.name, interval_secs, timeout_secs, priority)?;
+    validate_url(&request.url)?;
+
+    let now = Utc::now();
+    let item = checks::ActiveModel {
+        name: Set(request.name.trim().to_string()),
+        url: Set(request.url),
+        interval_secs: Set(interval_secs as i64),
+        timeout_secs: Set(timeout_secs as i64),
+        expected_status: Set(request.expected_status.map(i32::from)),
+        status: Set(CheckStatus::Unknown.as_str().to_string()),
+        priority: Set(priority.into()),
+        channel: Set(channel(request.channel)),
+        enabled: Set(true),
+        last_checked_at: Set(None),
+        last_latency_ms: Set(None),
+        created_by: Set(Some(claims.username)),
+        created_at: Set(now),
+        updated_at: Set(now),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({ "status": "ok", "data": item.into_check() })),
+    ))
+}
+
+async fn update_check_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Json(request): Json<UpdateCheckRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let current = find_check(&state, id).await?;
+    let name = request.name.unwrap_or_else(|| current.name.clone());
+    let interval_secs = request
+        .interval_secs
+        .unwrap_or(current.interval_secs.max(0) as u64);
+    let timeout_secs = request
+        .timeout_secs
+        .unwrap_or(current.timeout_secs.max(0) as u64);
+    let priority = request.priority.unwrap_or(current.priority as u8);
+    validate_settings(&name, interval_secs, timeout_secs, priority)?;
+    if let Some(url) = &request.url {
+        validate_url(url)?;
+    }
+    let channel = match request.channel {
+        Some(value) => channel(Some(value)),
+        None => current.channel.clone(),
+    };
+
+    let mut check: checks::ActiveModel = current.into();
+    check.name = Set(name.trim().to_string());
+    if let Some(url) = request.url {
+        check.url = Set(url);
+        // 地址变化后重新判断状态
+        check.status = Set(CheckStatus::Unknown.as_str().to_string());
+    }
+    check.interval_secs = Set(interval_secs as i64);
+    check.timeout_secs = Set(timeout_secs as i64);
+    if let Some(expected) = request.expected_status {
+        check.expected_status = Set((expected != 0).then_some(i32::from(expected)));
+    }
+    check.priority = Set(priority.into());
+    check.channel = Set(channel);
+    if let Some(enabled) = request.enabled {
+        check.enabled = Set(enabled);
+    }
+    check.updated_at = Set(Utc::now());
+    let item = check.update(&state.db).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": item.into_check() })),
+    ))
+}
+
+async fn delete_check_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let item = find_check(&state, id).await?;
+    check_results::Entity::delete_many()
+        .filter(check_results::Column::CheckId.eq(item.id))
+        .exec(&state.db)
+        .await?;
+    checks::Entity::delete_by_id(item.id)
+        .exec(&state.db)
+        .await?;
+
+    Ok((StatusCode::OK, Json(serde_json::json!({ "status": "ok" }))))
+}
@@ -0,0 +1,246 @@
+use crate::db::escalations;
+use crate::error::AppError;
+use crate::services::auth::user::admin_auth_middleware;
+use crate::services::escalation::EscalationAction;
+use crate::services::sinks::Sink;
+use crate::state::AppState;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router, middleware};
+use chrono::Utc;
+use rutify_core::MAX_PRIORITY;
+use sea_orm::{ActiveModelTrait, EntityTrait, QueryOrder, Set};
+use serde::Deserialize;
+use std::sync::Arc;
+
+pub(crate) fn router(state: Arc<AppState>) -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/",
+            get(list_escalations_handler).post(create_escalation_handler),
+        )
+        .route(
+            "/{id}",
+            get(get_escalation_handler)
+                .put(update_escalation_handler)
+                .delete(delete_escalation_handler),
+        )
+        .layer(middleware::from_fn_with_state(state, admin_auth_middleware))
+}
+
+/// 升级规则创建请求
+#[derive(Debug, Deserialize)]
+struct CreateEscalationRequest {
+    name: String,
+    min_priority: Option<i32>,
+    after_minutes: i32,
+    action: String,
+    sink: Option<String>,
+    target: Option<String>,
+    enabled: Option<bool>,
+}
+
+/// 升级规则更新请求（未提供的字段保持不变）
+#[derive(Debug, Deserialize)]
+struct UpdateEscalationRequest {
+    name: Option<String>,
+    min_priority: Option<i32>,
+    after_minutes: Option<i32>,
+    action: Option<String>,
+    sink: Option<String>,
+    target: Option<String>,
+    enabled: Option<bool>,
+}
+
+fn escalation_json(item: &escalations::Model) -> serde_json::Value {
+    serde_json::json!({
+        "id": item.id,
+        "name": item.name,
+        "min_priority": item.min_priority,
+        "after_minutes": item.after_minutes,
+        "action": item.action,
+        "sink": item.sink,
+        "target": item.target,
+        "enabled": item.enabled,
+        "created_at": item.created_at,
+        "updated_at": item.updated_at,
+    })
+}
+
+/// 校验规则字段组合
+fn validate_rule(
+    min_priority: i32,
+    after_minutes: i32,
+    action: &str,
+    sink: Option<&str>,
+    target: Option<&str>,
+) -> Result<(), AppError> {
+    if !(1..=MAX_PRIORITY as i32).contains(&min_priority) {
+        return Err(AppError::BadRequest(format!(
+            "min_priority must be between 1 and {MAX_PRIORITY}"
+        )));
+    }
+    if after_minutes < 1 {
+        return Err(AppError::BadRequest(
+            "after_minutes must be at least 1".to_string(),
+        ));
+    }
+    let action = EscalationAction::parse(action)
+        .ok_or_else(|| AppError::BadRequest(format!("Unknown escalation action: {action}")))?;
+    if action == EscalationAction::Forward {
+        if sink.and_then(Sink::parse).is_none() {
+            return Err(AppError::BadRequest(
+                "forward requires sink: telegram, email or webhook".to_string(),
+            ));
+        }
+        if target.is_none_or(|target| target.trim().is_empty()) {
+            return Err(AppError::BadRequest("forward requires target".to_string()));
+        }
+    }
+    Ok(())
+}
+
+async fn find_escalation(state: &AppState, id: i32) -> Result<escalations::Model, AppError> {
+    escalations::Entity::find_by_id(id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Escalation not found".to_string()))
+}
+
+async fn list_escalations_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let items = escalations::Entity::find()
+        .order_by_asc(escalations::Column::AfterMinutes)
+        .order_by_asc(escalations::Column::Id)
+        .all(&state.db)
+        .await?;
+
+    let data: Vec<serde_json::Value> = items.iter().map(escalation_json).collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": data })),
+    ))
+}
+
+async fn get_escalation_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let item = find_escalation(&state, id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": escalation_json(&item) })),
+    ))
+}
+
+async fn create_escalation_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<CreateEscalationRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if request.name.trim().is_empty() {
+        return Err(AppError::BadRequest(
+            "Escalation name is required".to_string(),
+        ));
+    }
+    let min_priority = request.min_priority.unwrap_or(4);
+    validate_rule(
+        min_priority,
+        request.after_minutes,
+        &request.action,
+        request.sink.as_deref(),
+        request.target.as_deref(),
+    )?;
+
+    let now = Utc::now();
+    let item = escalations::ActiveModel {
+        name: Set(request.name),
+        min_priority: Set(min_priority),
+        after_minutes: Set(request.after_minutes),
+        action: Set(request.action.to_ascii_lowercase()),
+        sink: Set(request.sink.map(|sink| sink.to_ascii_lowercase())),
+        target: Set(request.target),
+        enabled: Set(request.enabled.unwrap_or(true)),
+        created_at: Set(now),
+        updated_at: Set(now),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    state.escalations.reload(&state.db).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({ "status": "ok", "data": escalation_json(&item) })),
+    ))
+}
+
+async fn update_escalation_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Json(request): Json<UpdateEscalationRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let current = find_escalation(&state, id).await?;
+
+    let min_priority = request.min_priority.unwrap_or(current.min_priority);
+    let after_minutes = request.after_minutes.unwrap_or(current.after_minutes);
+    let action = request
+        .action
+        .map(|action| action.to_ascii_lowercase())
+        .unwrap_or_else(|| current.action.clone());
+    let sink = request
+        .sink
+        .map(|sink| sink.to_ascii_lowercase())
+        .or_else(|| current.sink.clone());
+    let target = request.target.or_else(|| current.target.clone());
+    validate_rule(
+        min_priority,
+        after_minutes,
+        &action,
+        sink.as_deref(),
+        target.as_deref(),
+    )?;
+
+    let mut item: escalations::ActiveModel = current.into();
+    if let Some(name) = request.name {
+        item.name = Set(name);
+    }
+    if let Some(enabled) = request.enabled {
+        item.enabled = Set(enabled);
+    }
+    item.min_priority = Set(min_priority);
+    item.after_minutes = Set(after_minutes);
+    item.action = Set(action);
+    item.sink = Set(sink);
+    item.target = Set(target);
+    item.updated_at = Set(Utc::now());
+
+    let item = item.update(&state.db).await?;
+    state.escalations.reload(&state.db).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": escalation_json(&item) })),
+    ))
+}
+
+async fn delete_escalation_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let deleted = escalations::Entity::delete_by_id(id)
+        .exec(&state.db)
+        .await?;
+    if deleted.rows_affected == 0 {
+        return Err(AppError::NotFound("Escalation not found".to_string()));
+    }
+
+    state.escalations.reload(&state.db).await?;
+
+    Ok((StatusCode::OK, Json(serde_json::json!({ "status": "ok" }))))
+}
@@ -1,14 +1,54 @@
+use crate::services::auth::user::user_auth_middleware;
 use crate::state::AppState;
-use axum::Router;
+use axum::{Router, middleware};
 use std::sync::Arc;
 
+mod admin;
+mod capabilities;
+mod checks;
+mod devices;
+mod escalations;
+mod monitors;
 mod notifies;
+mod oncall;
+mod preferences;
+mod push;
+mod silences;
 mod stats;
+mod unifiedpush;
+mod version;
 
-pub(crate) fn router(_state: Arc<AppState>) -> Router<Arc<AppState>> {
-    Router::new()
-        .nest("/notifies", notifies::router())
+pub(crate) fn router(state: Arc<AppState>) -> Router<Arc<AppState>> {
+    let router = Router::new()
+        .nest("/notifies", notifies::router(Arc::clone(&state)))
         .nest("/stats", stats::router())
         // Backward-compatible alias.
         .nest("/states", stats::router())
+        .nest("/devices", devices::router())
+        .nest("/capabilities", capabilities::router())
+        .nest("/escalations", escalations::router(Arc::clone(&state)))
+        .nest("/oncall", oncall::router(Arc::clone(&state)))
+        .nest("/silences", silences::router(Arc::clone(&state)))
+        .nest("/monitors", monitors::router(Arc::clone(&state)))
+        .nest("/checks", checks::router(Arc::clone(&state)))
+        .nest("/admin", admin::router(Arc::clone(&state)));
+
+    // strict 模式下只读接口同样需要登录
+    let router = if state.auth_policy.requires_user_auth() {
+        router.layer(middleware::from_fn_with_state(
+            Arc::clone(&state),
+            user_auth_middleware,
+        ))
+    } else {
+        router
+    };
+
+    // 心跳与 UnifiedPush 登记使用通知 Token 鉴权，Web Push 订阅与通知偏好始终需要登录，
+    // 版本信息与 VAPID 公钥无需登录，均不受 strict 模式影响
+    router
+        .nest("/unifiedpush", unifiedpush::router(Arc::clone(&state)))
+        .nest("/push", push::router(Arc::clone(&state)))
+        .nest("/preferences", preferences::router(Arc::clone(&state)))
+        .merge(monitors::beat_router(state))
+        .nest("/version", version::router())
 }
@@ -0,0 +1,216 @@
+use crate::db::monitors;
+use crate::error::AppError;
+use crate::services::auth::auth::notify_token_middleware;
+use crate::services::auth::user::{UserClaims, user_auth_middleware};
+use crate::services::monitors::{self as monitor_service, default_grace_secs, validate_name};
+use crate::state::AppState;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{get, post};
+use axum::{Extension, Json, Router, middleware};
+use chrono::Utc;
+use rutify_core::{CreateMonitorRequest, MAX_PRIORITY, Monitor, MonitorStatus};
+use sea_orm::{ActiveModelTrait, EntityTrait, QueryOrder, Set};
+use serde::Deserialize;
+use std::sync::Arc;
+
+/// 告警通知的默认优先级
+const DEFAULT_ALERT_PRIORITY: u8 = 4;
+
+pub(crate) fn router(state: Arc<AppState>) -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(list_monitors_handler).post(create_monitor_handler))
+        .route(
+            "/{name}",
+            get(get_monitor_handler)
+                .put(update_monitor_handler)
+                .delete(delete_monitor_handler),
+        )
+        .layer(middleware::from_fn_with_state(state, user_auth_middleware))
+}
+
+/// 心跳接口使用通知 Token，便于 cron 任务等客户端直接上报
+pub(crate) fn beat_router(state: Arc<AppState>) -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/monitors/{name}/beat", post(beat_handler))
+        .route_layer(middleware::from_fn_with_state(
+            state,
+            notify_token_middleware,
+        ))
+}
+
+/// 心跳监控更新请求（未提供的字段保持不变）
+#[derive(Debug, Deserialize)]
+struct UpdateMonitorRequest {
+    interval_secs: Option<u64>,
+    grace_secs: Option<u64>,
+    priority: Option<u8>,
+    channel: Option<String>,
+}
+
+fn validate_settings(interval_secs: u64, priority: u8) -> Result<(i64, i32), AppError> {
+    if interval_secs == 0 {
+        return Err(AppError::BadRequest(
+            "interval_secs must be greater than 0".to_string(),
+        ));
+    }
+    if !(1..=MAX_PRIORITY).contains(&priority) {
+        return Err(AppError::BadRequest(format!(
+            "priority must be between 1 and {MAX_PRIORITY}"
+        )));
+    }
+    let interval_secs = i64::try_from(interval_secs)
+        .map_err(|_| AppError::BadRequest("interval_secs is too large".to_string()))?;
+    Ok((interval_secs, priority.into()))
+}
+
+fn grace_secs(secs: u64) -> Result<i64, AppError> {
+    i64::try_from(secs).map_err(|_| AppError::BadRequest("grace_secs is too large".to_string()))
+}
+
+/// 空字符串视为不指定频道
+fn channel(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+async fn list_monitors_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let data: Vec<Monitor> = monitors::Entity::find()
+        .order_by_asc(monitors::Column::Name)
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|item| item.into_monitor())
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": data })),
+    ))
+}
+
+async fn get_monitor_handler(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let item = monitor_service::find_by_name(&state, &name).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": item.into_monitor() })),
+    ))
+}
+
+async fn create_monitor_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<UserClaims>,
+    Json(request): Json<CreateMonitorRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let name = request.name.trim().to_string();
+    validate_name(&name)?;
+    let (interval_secs, priority) = validate_settings(
+        request.interval_secs,
+        request.priority.unwrap_or(DEFAULT_ALERT_PRIORITY),
+    )?;
+    let grace = grace_secs(
+        request
+            .grace_secs
+            .unwrap_or_else(|| default_grace_secs(request.interval_secs)),
+    )?;
+    if monitor_service::find_by_name(&state, &name).await.is_ok() {
+        return Err(AppError::BadRequest(format!(
+            "Monitor '{name}' already exists"
+        )));
+    }
+
+    let now = Utc::now();
+    let item = monitors::ActiveModel {
+        name: Set(name),
+        interval_secs: Set(interval_secs),
+        grace_secs: Set(grace),
+        status: Set(MonitorStatus::New.as_str().to_string()),
+        priority: Set(priority),
+        channel: Set(channel(request.channel)),
+        last_beat_at: Set(None),
+        due_at: Set(None),
+        created_by: Set(Some(claims.username)),
+        created_at: Set(now),
+        updated_at: Set(now),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({ "status": "ok", "data": item.into_monitor() })),
+    ))
+}
+
+async fn update_monitor_handler(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+    Json(request): Json<UpdateMonitorRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let current = monitor_service::find_by_name(&state, &name).await?;
+    let (interval_secs, priority) = validate_settings(
+        request
+            .interval_secs
+            .unwrap_or(current.interval_secs.max(0) as u64),
+        request.priority.unwrap_or(current.priority as u8),
+    )?;
+    let grace = match request.grace_secs {
+        Some(secs) => grace_secs(secs)?,
+        None => current.grace_secs,
+    };
+    let channel = match request.channel {
+        Some(value) => channel(Some(value)),
+        None => current.channel.clone(),
+    };
+    // 新的间隔从最近一次心跳起算
+    let due_at = current
+        .last_beat_at
+        .map(|at| at + chrono::Duration::seconds(interval_secs + grace));
+
+    let mut monitor: monitors::ActiveModel = current.into();
+    monitor.interval_secs = Set(interval_secs);
+    monitor.grace_secs = Set(grace);
+    monitor.priority = Set(priority);
+    monitor.channel = Set(channel);
+    monitor.due_at = Set(due_at);
+    monitor.updated_at = Set(Utc::now());
+    let item = monitor.update(&state.db).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": item.into_monitor() })),
+    ))
+}
+
+async fn delete_monitor_handler(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let item = monitor_service::find_by_name(&state, &name).await?;
+    monitors::Entity::delete_by_id(item.id)
+        .exec(&state.db)
+        .await?;
+
+    Ok((StatusCode::OK, Json(serde_json::json!({ "status": "ok" }))))
+}
+
+async fn beat_handler(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let item = monitor_service::beat(&state, &name).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": item })),
+    ))
+}
@@ -1,27 +1,313 @@
+use crate::db::notifies::ListCursor;
+use crate::db::{attachments, deliveries, encryption};
 use crate::error::AppError;
+use crate::services::auth::editor::{NotifyEditor, notify_editor_middleware};
+use crate::services::{link_preview, notify};
 use crate::state::AppState;
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
+use axum::http::header::CONTENT_TYPE;
 use axum::response::IntoResponse;
-use axum::routing::{delete, get};
-use axum::{Json, Router};
-use rutify_core::NotifyItem;
-use sea_orm::{EntityTrait, PaginatorTrait, QueryOrder};
+use axum::routing::{delete, get, post};
+use axum::{Extension, Json, Router, middleware};
+use chrono::Utc;
+use rutify_core::{
+    AttachmentInfo, CountGroupBy, DeliveryReceipt, EventKind, MAX_PRIORITY, NotifyId, NotifyItem,
+    UpdateNotifyRequest,
+};
+use sea_orm::{ColumnTrait, Condition, EntityTrait, PaginatorTrait, QueryFilter, QuerySelect};
+use serde::Deserialize;
 use std::sync::Arc;
 
-pub(crate) fn router() -> Router<Arc<AppState>> {
+pub(crate) fn router(state: Arc<AppState>) -> Router<Arc<AppState>> {
     Router::new()
-        .route("/", get(list_notifies_handler))
         .route("/", delete(delete_all_notifies_handler))
-        .route("/{id}", delete(delete_notify_by_id_handler))
+        .route(
+            "/{id}",
+            delete(delete_notify_by_id_handler).patch(update_notify_handler),
+        )
+        .route(
+            "/{id}/pin",
+            post(pin_notify_handler).delete(unpin_notify_handler),
+        )
+        .route(
+            "/{id}/star",
+            post(star_notify_handler).delete(unstar_notify_handler),
+        )
+        .route("/{id}/ack", post(ack_notify_handler))
+        // 修改与删除通知不论鉴权模式都需要用户 JWT 或发送该通知的 Token
+        .route_layer(middleware::from_fn_with_state(
+            state,
+            notify_editor_middleware,
+        ))
+        .route("/", get(list_notifies_handler))
+        .route("/counts", get(count_notifies_handler))
+        .route("/{id}/attachments", get(list_attachments_handler))
+        .route("/{id}/deliveries", get(list_deliveries_handler))
+        .route(
+            "/{id}/attachments/{attachment_id}",
+            get(get_attachment_handler),
+        )
+}
+
+/// 确认通知，已确认的通知不再触发升级规则
+async fn ack_notify_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(editor): Extension<NotifyEditor>,
+    Path(NotifyId(id)): Path<NotifyId>,
+) -> Result<impl IntoResponse, AppError> {
+    find_editable(&state, id, &editor).await?;
+    let notify = crate::db::notifies::ack_notify(&state.db, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Notify not found".to_string()))?;
+    if !notify.silenced {
+        notify::broadcast(&state, EventKind::Acked, notify.to_data());
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "status": "ok",
+            "data": {
+                "id": notify.id,
+                "acked_at": notify.acked_at
+            }
+        })),
+    ))
+}
+
+/// 通知的附件列表（不含内容）
+async fn list_attachments_handler(
+    State(state): State<Arc<AppState>>,
+    Path(NotifyId(id)): Path<NotifyId>,
+) -> Result<impl IntoResponse, AppError> {
+    let data: Vec<AttachmentInfo> = attachments::list_for_notify(&state.db, id)
+        .await?
+        .into_iter()
+        .map(|attachment| attachment.into_info())
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": data })),
+    ))
+}
+
+/// 通知的送达记录：推送到了哪些 WebSocket 订阅者、转发到了哪些外部渠道，以及时间
+async fn list_deliveries_handler(
+    State(state): State<Arc<AppState>>,
+    Path(NotifyId(id)): Path<NotifyId>,
+) -> Result<impl IntoResponse, AppError> {
+    crate::db::notifies::Entity::find_by_id(id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Notify not found".to_string()))?;
+    let data: Vec<DeliveryReceipt> = deliveries::list_for_notify(&state.db, id)
+        .await?
+        .into_iter()
+        .map(|delivery| delivery.into_receipt())
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": data })),
+    ))
+}
+
+/// 附件的原始内容，例如被截断通知的完整正文
+async fn get_attachment_handler(
+    State(state): State<Arc<AppState>>,
+    Path((NotifyId(id), attachment_id)): Path<(NotifyId, i32)>,
+) -> Result<impl IntoResponse, AppError> {
+    let attachment = attachments::find_for_notify(&state.db, id, attachment_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Attachment not found".to_string()))?;
+
+    Ok((
+        StatusCode::OK,
+        [(CONTENT_TYPE, attachment.content_type)],
+        encryption::open(attachment.content),
+    ))
+}
+
+fn validate_update(request: &UpdateNotifyRequest) -> Result<(), AppError> {
+    if request.is_empty() {
+        return Err(AppError::BadRequest("Nothing to update".to_string()));
+    }
+    if request
+        .notify
+        .as_deref()
+        .is_some_and(|body| body.trim().is_empty())
+    {
+        return Err(AppError::BadRequest("notify must not be empty".to_string()));
+    }
+    if request
+        .title
+        .as_deref()
+        .is_some_and(|title| title.trim().is_empty())
+    {
+        return Err(AppError::BadRequest("title must not be empty".to_string()));
+    }
+    if let Some(priority) = request.priority
+        && !(1..=MAX_PRIORITY).contains(&priority)
+    {
+        return Err(AppError::BadRequest(format!(
+            "priority must be between 1 and {MAX_PRIORITY}"
+        )));
+    }
+    Ok(())
+}
+
+/// 查找调用方可以修改的通知，通知 Token 只能修改自己发送的通知
+async fn find_editable(
+    state: &AppState,
+    id: i32,
+    editor: &NotifyEditor,
+) -> Result<crate::db::notifies::Model, AppError> {
+    let notify = crate::db::notifies::Entity::find_by_id(id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Notify not found".to_string()))?;
+    editor.check(&notify)?;
+    Ok(notify)
+}
+
+/// 更新已发送通知的内容并广播 `update` 事件，客户端据 ID 就地替换
+async fn update_notify_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(editor): Extension<NotifyEditor>,
+    Path(NotifyId(id)): Path<NotifyId>,
+    Json(mut request): Json<UpdateNotifyRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    validate_update(&request)?;
+    find_editable(&state, id, &editor).await?;
+    let full_body = state.size_limits.apply_to_update(&mut request)?;
+    let notify = crate::db::notifies::update_notify(&state.db, id, &request)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Notify not found".to_string()))?;
+    if let Some(body) = full_body {
+        notify::save_full_body(&state, notify.id, body).await;
+    }
+
+    // 静默的通知从未广播，更新同样不广播
+    let data = notify.to_data();
+    if request.notify.is_some() {
+        link_preview::enrich(&state, &data, notify.silenced);
+    }
+    if !notify.silenced {
+        notify::broadcast(&state, EventKind::Updated, data);
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": notify.into_item() })),
+    ))
+}
+
+/// 置顶通知，置顶的通知在列表中排在最前且不会被清理
+async fn pin_notify_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(editor): Extension<NotifyEditor>,
+    Path(NotifyId(id)): Path<NotifyId>,
+) -> Result<impl IntoResponse, AppError> {
+    update_pinned(&state, &editor, id, true).await
+}
+
+/// 取消置顶
+async fn unpin_notify_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(editor): Extension<NotifyEditor>,
+    Path(NotifyId(id)): Path<NotifyId>,
+) -> Result<impl IntoResponse, AppError> {
+    update_pinned(&state, &editor, id, false).await
+}
+
+async fn update_pinned(
+    state: &AppState,
+    editor: &NotifyEditor,
+    id: i32,
+    pinned: bool,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    find_editable(state, id, editor).await?;
+    let notify = crate::db::notifies::set_pinned(&state.db, id, pinned)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Notify not found".to_string()))?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "status": "ok",
+            "data": {
+                "id": notify.id,
+                "pinned": notify.pinned
+            }
+        })),
+    ))
 }
 
+/// 为通知加星标
+async fn star_notify_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(editor): Extension<NotifyEditor>,
+    Path(NotifyId(id)): Path<NotifyId>,
+) -> Result<impl IntoResponse, AppError> {
+    update_starred(&state, &editor, id, true).await
+}
+
+/// 取消星标
+async fn unstar_notify_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(editor): Extension<NotifyEditor>,
+    Path(NotifyId(id)): Path<NotifyId>,
+) -> Result<impl IntoResponse, AppError> {
+    update_starred(&state, &editor, id, false).await
+}
+
+async fn update_starred(
+    state: &AppState,
+    editor: &NotifyEditor,
+    id: i32,
+    starred: bool,
+) -> Result<(StatusCode, Json<serde_json::Value>), AppError> {
+    find_editable(state, id, editor).await?;
+    let notify = crate::db::notifies::set_starred(&state.db, id, starred)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Notify not found".to_string()))?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "status": "ok",
+            "data": {
+                "id": notify.id,
+                "starred": notify.starred
+            }
+        })),
+    ))
+}
+
+/// 删除全部通知，只允许用户执行
 async fn delete_all_notifies_handler(
     State(state): State<Arc<AppState>>,
+    Extension(editor): Extension<NotifyEditor>,
 ) -> Result<impl IntoResponse, AppError> {
+    editor.require_user()?;
     let deleted = crate::db::notifies::Entity::delete_many()
         .exec(&state.db)
         .await?;
+    crate::db::Attachments::delete_many()
+        .exec(&state.db)
+        .await?;
+    if deleted.rows_affected > 0 {
+        notify::broadcast(
+            &state,
+            EventKind::StatsChanged,
+            notify::system_data(
+                "Notifications deleted",
+                format!("{} notifications were deleted", deleted.rows_affected),
+            ),
+        );
+    }
 
     Ok((
         StatusCode::OK,
@@ -36,43 +322,104 @@ async fn delete_all_notifies_handler(
 
 async fn delete_notify_by_id_handler(
     State(state): State<Arc<AppState>>,
-    Path(id): Path<i32>,
+    Extension(editor): Extension<NotifyEditor>,
+    Path(NotifyId(id)): Path<NotifyId>,
 ) -> Result<impl IntoResponse, AppError> {
+    let notify = find_editable(&state, id, &editor).await?;
     let deleted = crate::db::notifies::Entity::delete_by_id(id)
         .exec(&state.db)
         .await?;
 
     if deleted.rows_affected == 0 {
-        return Ok((
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({
-                "errors": "Notify not found"
-            })),
-        ));
+        return Err(AppError::NotFound("Notify not found".to_string()));
+    }
+    attachments::delete_for_notify(&state.db, id).await?;
+    if !notify.silenced {
+        notify::broadcast(&state, EventKind::Deleted, notify.to_data());
     }
 
     Ok((StatusCode::OK, Json(serde_json::json!({ "status": "ok" }))))
 }
 
+/// 单页最多返回的通知数
+const MAX_PAGE_SIZE: u64 = 500;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ListNotifiesQuery {
+    /// 是否包含已过期的通知
+    include_expired: bool,
+    /// 每页数量，不设置时返回全部通知
+    limit: Option<u64>,
+    /// 上一页返回的 `next_cursor`
+    cursor: Option<String>,
+    /// 设置时只返回星标（`true`）或未加星标（`false`）的通知
+    starred: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CountNotifiesQuery {
+    group_by: CountGroupBy,
+    /// 只统计未确认的通知
+    #[serde(default)]
+    unread: bool,
+    /// 是否包含已过期的通知
+    #[serde(default)]
+    include_expired: bool,
+}
+
+/// 按频道、设备或日期分组的通知数，供客户端显示角标而无需读取列表
+async fn count_notifies_handler(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<CountNotifiesQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let mut condition = Condition::all();
+    if !query.include_expired {
+        condition = condition.add(crate::db::notifies::not_expired(Utc::now()));
+    }
+    if query.unread {
+        condition = condition.add(crate::db::notifies::Column::AckedAt.is_null());
+    }
+    let data = crate::db::notifies::grouped_counts(&state.db, query.group_by, condition).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": data })),
+    ))
+}
+
+/// 通知列表；设置 `limit` 时分页返回，`meta.next_cursor` 为空表示已到最后一页
 async fn list_notifies_handler(
     State(state): State<Arc<AppState>>,
+    Query(query): Query<ListNotifiesQuery>,
 ) -> Result<impl IntoResponse, AppError> {
-    let total = crate::db::notifies::Entity::find().count(&state.db).await?;
-    let notifies = crate::db::notifies::Entity::find()
-        .order_by_desc(crate::db::notifies::Column::ReceivedAt)
-        .all(&state.db)
-        .await?;
+    let mut select = crate::db::notifies::Entity::find();
+    if !query.include_expired {
+        select = select.filter(crate::db::notifies::not_expired(Utc::now()));
+    }
+    if let Some(starred) = query.starred {
+        select = select.filter(crate::db::notifies::Column::Starred.eq(starred));
+    }
+    let total = select.clone().count(&state.db).await?;
+    if let Some(cursor) = &query.cursor {
+        let cursor = ListCursor::parse(cursor)
+            .ok_or_else(|| AppError::BadRequest("Invalid cursor".to_string()))?;
+        select = select.filter(cursor.condition());
+    }
+    let mut select = crate::db::notifies::list_order(select);
+    let limit = query.limit.map(|limit| limit.clamp(1, MAX_PAGE_SIZE));
+    if let Some(limit) = limit {
+        select = select.limit(limit);
+    }
+    let notifies = select.all(&state.db).await?;
 
-    let data: Vec<NotifyItem> = notifies
-        .into_iter()
-        .map(|item| NotifyItem {
-            id: item.id,
-            title: item.title.unwrap_or_else(|| "default title".to_string()),
-            notify: item.notify,
-            device: item.device.unwrap_or_else(|| "default device".to_string()),
-            received_at: item.received_at,
-        })
-        .collect();
+    let next_cursor = match (limit, notifies.last()) {
+        (Some(limit), Some(last)) if notifies.len() as u64 == limit => {
+            Some(ListCursor::after(last).encode())
+        }
+        _ => None,
+    };
+    let data: Vec<NotifyItem> = notifies.into_iter().map(|item| item.into_item()).collect();
 
     Ok((
         StatusCode::OK,
@@ -80,8 +427,96 @@ async fn list_notifies_handler(
             "status": "ok",
             "data": data,
             "meta": {
-                "total": total
+                "total": total,
+                "next_cursor": next_cursor
             }
         })),
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::auth::mode::{AuthMode, AuthPolicy};
+    use axum::body::Body;
+    use axum::http::Request;
+    use axum::http::header::AUTHORIZATION;
+    use tower::ServiceExt;
+
+    async fn send(
+        policy: AuthPolicy,
+        request: axum::http::request::Builder,
+        authorization: Option<&str>,
+    ) -> StatusCode {
+        let state = AppState::for_tests(policy).await;
+        let app = router(Arc::clone(&state)).with_state(state);
+
+        let mut request = request.header(CONTENT_TYPE, "application/json");
+        if let Some(authorization) = authorization {
+            request = request.header(AUTHORIZATION, authorization);
+        }
+        let request = request.body(Body::from(r#"{"notify":"edited"}"#)).unwrap();
+        app.oneshot(request).await.unwrap().status()
+    }
+
+    fn policies() -> [AuthPolicy; 3] {
+        [AuthMode::Open, AuthMode::Token, AuthMode::Strict].map(|mode| AuthPolicy {
+            mode,
+            open_private_only: false,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_update_requires_auth() {
+        for policy in policies() {
+            assert_eq!(
+                send(policy, Request::patch("/1"), None).await,
+                StatusCode::UNAUTHORIZED
+            );
+            assert_eq!(
+                send(policy, Request::patch("/1"), Some("Bearer not-a-jwt")).await,
+                StatusCode::UNAUTHORIZED
+            );
+        }
+
+        // 只读列表不受影响
+        let open = policies()[0];
+        assert_eq!(send(open, Request::get("/"), None).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_pin_requires_auth() {
+        for policy in policies() {
+            assert_eq!(
+                send(policy, Request::post("/1/pin"), None).await,
+                StatusCode::UNAUTHORIZED
+            );
+            assert_eq!(
+                send(policy, Request::delete("/1/pin"), None).await,
+                StatusCode::UNAUTHORIZED
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_star_and_ack_require_auth() {
+        for policy in policies() {
+            for request in [
+                Request::post("/1/star"),
+                Request::delete("/1/star"),
+                Request::post("/1/ack"),
+            ] {
+                assert_eq!(send(policy, request, None).await, StatusCode::UNAUTHORIZED);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete_requires_auth() {
+        for policy in policies() {
+            for request in [Request::delete("/"), Request::delete("/1")] {
+                assert_eq!(send(policy, request, None).await, StatusCode::UNAUTHORIZED);
+            }
+        }
+    }
+}
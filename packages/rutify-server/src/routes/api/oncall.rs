@@ -0,0 +1,311 @@
+use crate::db::{oncall_members, oncall_schedules, users};
+use crate::error::AppError;
+use crate::services::auth::user::admin_auth_middleware;
+use crate::services::oncall::{self, DEFAULT_SCHEDULE};
+use crate::services::sinks::Sink;
+use crate::state::AppState;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router, middleware};
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+pub(crate) fn router(state: Arc<AppState>) -> Router<Arc<AppState>> {
+    let admin_router = Router::new()
+        .route(
+            "/",
+            get(list_schedules_handler).post(create_schedule_handler),
+        )
+        .route(
+            "/{id}",
+            get(get_schedule_handler)
+                .put(update_schedule_handler)
+                .delete(delete_schedule_handler),
+        )
+        .layer(middleware::from_fn_with_state(state, admin_auth_middleware));
+
+    Router::new()
+        .route("/current", get(current_oncall_handler))
+        .merge(admin_router)
+}
+
+#[derive(Debug, Deserialize)]
+struct CurrentQuery {
+    schedule: Option<String>,
+}
+
+/// 排班成员（按数组顺序轮值）
+#[derive(Debug, Deserialize)]
+struct MemberRequest {
+    username: String,
+    sink: Option<String>,
+    target: Option<String>,
+}
+
+/// 排班创建请求
+#[derive(Debug, Deserialize)]
+struct CreateScheduleRequest {
+    name: Option<String>,
+    rotation_hours: Option<i32>,
+    starts_at: Option<DateTime<Utc>>,
+    members: Vec<MemberRequest>,
+}
+
+/// 排班更新请求（未提供的字段保持不变，提供 members 时整体替换）
+#[derive(Debug, Deserialize)]
+struct UpdateScheduleRequest {
+    name: Option<String>,
+    rotation_hours: Option<i32>,
+    starts_at: Option<DateTime<Utc>>,
+    members: Option<Vec<MemberRequest>>,
+}
+
+async fn schedule_json(
+    db: &DatabaseConnection,
+    schedule: &oncall_schedules::Model,
+) -> Result<serde_json::Value, AppError> {
+    let members = oncall_members::Entity::find()
+        .filter(oncall_members::Column::ScheduleId.eq(schedule.id))
+        .order_by_asc(oncall_members::Column::Position)
+        .all(db)
+        .await?;
+
+    let mut member_items = Vec::with_capacity(members.len());
+    for member in members {
+        let username = users::Entity::find_by_id(member.user_id)
+            .one(db)
+            .await?
+            .map(|user| user.username);
+        member_items.push(serde_json::json!({
+            "user_id": member.user_id,
+            "username": username,
+            "position": member.position,
+            "sink": member.sink,
+            "target": member.target,
+        }));
+    }
+
+    let current = oncall::current(db, schedule)
+        .await?
+        .map(|oncall| oncall.info);
+
+    Ok(serde_json::json!({
+        "id": schedule.id,
+        "name": schedule.name,
+        "rotation_hours": schedule.rotation_hours,
+        "starts_at": schedule.starts_at,
+        "members": member_items,
+        "current": current,
+        "created_at": schedule.created_at,
+        "updated_at": schedule.updated_at,
+    }))
+}
+
+/// 校验成员并替换排班的全部成员
+async fn replace_members(
+    db: &DatabaseConnection,
+    schedule_id: i32,
+    members: Vec<MemberRequest>,
+) -> Result<(), AppError> {
+    let mut rows = Vec::with_capacity(members.len());
+    for (position, member) in members.into_iter().enumerate() {
+        let user = users::Entity::find()
+            .filter(users::Column::Username.eq(member.username.as_str()))
+            .one(db)
+            .await?
+            .ok_or_else(|| AppError::BadRequest(format!("Unknown user: {}", member.username)))?;
+
+        let sink = match member.sink.as_deref() {
+            Some(sink) if Sink::parse(sink).is_none() => {
+                return Err(AppError::BadRequest(format!("Unknown sink: {sink}")));
+            }
+            Some(_) if member.target.is_none() => {
+                return Err(AppError::BadRequest(format!(
+                    "Sink for {} requires target",
+                    member.username
+                )));
+            }
+            sink => sink.map(str::to_ascii_lowercase),
+        };
+
+        rows.push(oncall_members::ActiveModel {
+            schedule_id: Set(schedule_id),
+            user_id: Set(user.id),
+            position: Set(position as i32),
+            sink: Set(sink),
+            target: Set(member.target),
+            ..Default::default()
+        });
+    }
+
+    oncall_members::Entity::delete_many()
+        .filter(oncall_members::Column::ScheduleId.eq(schedule_id))
+        .exec(db)
+        .await?;
+    for row in rows {
+        row.insert(db).await?;
+    }
+    Ok(())
+}
+
+fn validate_rotation(rotation_hours: i32) -> Result<(), AppError> {
+    if rotation_hours < 1 {
+        return Err(AppError::BadRequest(
+            "rotation_hours must be at least 1".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+async fn find_schedule(state: &AppState, id: i32) -> Result<oncall_schedules::Model, AppError> {
+    oncall_schedules::Entity::find_by_id(id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("On-call schedule not found".to_string()))
+}
+
+async fn current_oncall_handler(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<CurrentQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let name = query.schedule.as_deref().unwrap_or(DEFAULT_SCHEDULE);
+    let current = oncall::current_by_name(&state.db, name)
+        .await?
+        .map(|oncall| oncall.info);
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": current })),
+    ))
+}
+
+async fn list_schedules_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let schedules = oncall_schedules::Entity::find()
+        .order_by_asc(oncall_schedules::Column::Id)
+        .all(&state.db)
+        .await?;
+
+    let mut data = Vec::with_capacity(schedules.len());
+    for schedule in &schedules {
+        data.push(schedule_json(&state.db, schedule).await?);
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": data })),
+    ))
+}
+
+async fn get_schedule_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let schedule = find_schedule(&state, id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "status": "ok",
+            "data": schedule_json(&state.db, &schedule).await?
+        })),
+    ))
+}
+
+async fn create_schedule_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<CreateScheduleRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let name = request
+        .name
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_SCHEDULE.to_string());
+    let rotation_hours = request.rotation_hours.unwrap_or(168);
+    validate_rotation(rotation_hours)?;
+
+    let now = Utc::now();
+    let schedule = oncall_schedules::ActiveModel {
+        name: Set(name),
+        rotation_hours: Set(rotation_hours),
+        starts_at: Set(request.starts_at.unwrap_or(now)),
+        created_at: Set(now),
+        updated_at: Set(now),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+
+    if let Err(e) = replace_members(&state.db, schedule.id, request.members).await {
+        oncall_schedules::Entity::delete_by_id(schedule.id)
+            .exec(&state.db)
+            .await?;
+        return Err(e);
+    }
+
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({
+            "status": "ok",
+            "data": schedule_json(&state.db, &schedule).await?
+        })),
+    ))
+}
+
+async fn update_schedule_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Json(request): Json<UpdateScheduleRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let mut schedule: oncall_schedules::ActiveModel = find_schedule(&state, id).await?.into();
+
+    if let Some(name) = request.name {
+        schedule.name = Set(name);
+    }
+    if let Some(rotation_hours) = request.rotation_hours {
+        validate_rotation(rotation_hours)?;
+        schedule.rotation_hours = Set(rotation_hours);
+    }
+    if let Some(starts_at) = request.starts_at {
+        schedule.starts_at = Set(starts_at);
+    }
+    if let Some(members) = request.members {
+        replace_members(&state.db, id, members).await?;
+    }
+    schedule.updated_at = Set(Utc::now());
+
+    let schedule = schedule.update(&state.db).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "status": "ok",
+            "data": schedule_json(&state.db, &schedule).await?
+        })),
+    ))
+}
+
+async fn delete_schedule_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let deleted = oncall_schedules::Entity::delete_by_id(id)
+        .exec(&state.db)
+        .await?;
+    if deleted.rows_affected == 0 {
+        return Err(AppError::NotFound("On-call schedule not found".to_string()));
+    }
+
+    oncall_members::Entity::delete_many()
+        .filter(oncall_members::Column::ScheduleId.eq(id))
+        .exec(&state.db)
+        .await?;
+
+    Ok((StatusCode::OK, Json(serde_json::json!({ "status": "ok" }))))
+}
@@ -0,0 +1,122 @@
+use crate::db::{user_preferences, users};
+use crate::error::AppError;
+use crate::services::auth::user::user_auth_middleware;
+use crate::services::preferences::validate;
+use crate::state::AppState;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Extension, Json, Router, middleware};
+use rutify_core::NotificationPreferences;
+use std::sync::Arc;
+
+/// 偏好归属登录用户，始终需要登录
+pub(crate) fn router(state: Arc<AppState>) -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/",
+            get(get_preferences_handler).put(update_preferences_handler),
+        )
+        .layer(middleware::from_fn_with_state(state, user_auth_middleware))
+}
+
+async fn get_preferences_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<users::Model>,
+) -> Result<impl IntoResponse, AppError> {
+    let data = user_preferences::get(&state.db, user.id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": data })),
+    ))
+}
+
+/// 整体替换偏好，返回规范化后的结果
+async fn update_preferences_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<users::Model>,
+    Json(request): Json<NotificationPreferences>,
+) -> Result<impl IntoResponse, AppError> {
+    let data = validate(request)?;
+    user_preferences::save(&state.db, user.id, &data).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": data })),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::auth::mode::AuthPolicy;
+    use chrono::Utc;
+    use rutify_core::DigestFrequency;
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_preferences_follow_users() {
+        let state = AppState::for_tests(AuthPolicy::default()).await;
+        let alice = users::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            username: Set("alice".to_string()),
+            password_hash: Set(String::new()),
+            email: Set("alice@example.com".to_string()),
+            role: Set(users::UserRole::User),
+            email_verified: Set(true),
+            disabled: Set(false),
+            created_at: Set(Utc::now().into()),
+            updated_at: Set(Utc::now().into()),
+        }
+        .insert(&state.db)
+        .await
+        .unwrap();
+        assert_eq!(
+            user_preferences::get(&state.db, alice.id).await.unwrap(),
+            NotificationPreferences::default()
+        );
+
+        let mut preferences = NotificationPreferences {
+            channels: vec!["ops".to_string()],
+            digest: DigestFrequency::Daily,
+            ..Default::default()
+        };
+        user_preferences::save(&state.db, alice.id, &preferences)
+            .await
+            .unwrap();
+        let first = user_preferences::Entity::find_by_id(alice.id)
+            .one(&state.db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.digest, "daily");
+
+        // 频率不变时保留上次摘要时间
+        preferences.channels.push("ci".to_string());
+        user_preferences::save(&state.db, alice.id, &preferences)
+            .await
+            .unwrap();
+        let again = user_preferences::Entity::find_by_id(alice.id)
+            .one(&state.db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(again.last_digest_at, first.last_digest_at);
+        assert_eq!(
+            user_preferences::by_username(&state.db).await.unwrap()["alice"].channels,
+            ["ops", "ci"]
+        );
+
+        users::Entity::delete_by_id(alice.id)
+            .exec(&state.db)
+            .await
+            .unwrap();
+        assert_eq!(
+            user_preferences::delete_orphaned(&state.db).await.unwrap(),
+            1
+        );
+    }
+}
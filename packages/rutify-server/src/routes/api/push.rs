@@ -0,0 +1,177 @@
+use crate::db::users;
+use crate::db::web_push_subscriptions as subscriptions;
+use crate::error::AppError;
+use crate::services::auth::user::user_auth_middleware;
+use crate::services::push::validate_endpoint;
+use crate::services::web_push::{WebPush, validate_keys};
+use crate::state::AppState;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::{get, post};
+use axum::{Extension, Json, Router, middleware};
+use rutify_core::{
+    SubscribeWebPushRequest, UnsubscribeWebPushRequest, VapidPublicKey, WebPushSubscription,
+};
+use std::sync::Arc;
+
+/// 订阅归属登录用户，公钥无需登录即可获取
+pub(crate) fn router(state: Arc<AppState>) -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/subscribe",
+            post(subscribe_handler).delete(unsubscribe_handler),
+        )
+        .route("/subscriptions", get(list_subscriptions_handler))
+        .layer(middleware::from_fn_with_state(state, user_auth_middleware))
+        .route("/vapid-public-key", get(vapid_public_key_handler))
+}
+
+fn web_push(state: &AppState) -> Result<&WebPush, AppError> {
+    state
+        .web_push
+        .as_deref()
+        .ok_or_else(|| AppError::NotFound("Web Push is not configured".to_string()))
+}
+
+async fn vapid_public_key_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    let data = VapidPublicKey {
+        public_key: web_push(&state)?.public_key().to_string(),
+    };
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": data })),
+    ))
+}
+
+async fn subscribe_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<users::Model>,
+    Json(request): Json<SubscribeWebPushRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    web_push(&state)?;
+    let endpoint = validate_endpoint(&request.endpoint)?;
+    validate_keys(&request.keys)?;
+    let item = subscriptions::subscribe(
+        &state.db,
+        user.id,
+        &endpoint,
+        request.keys.p256dh.trim(),
+        request.keys.auth.trim(),
+    )
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({ "status": "ok", "data": item.into_subscription() })),
+    ))
+}
+
+async fn list_subscriptions_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<users::Model>,
+) -> Result<impl IntoResponse, AppError> {
+    let data: Vec<WebPushSubscription> = subscriptions::list_for_user(&state.db, user.id)
+        .await?
+        .into_iter()
+        .map(|item| item.into_subscription())
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": data })),
+    ))
+}
+
+async fn unsubscribe_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<users::Model>,
+    Json(request): Json<UnsubscribeWebPushRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    if !subscriptions::delete_for_user(&state.db, user.id, request.endpoint.trim()).await? {
+        return Err(AppError::NotFound(
+            "Web Push subscription not found".to_string(),
+        ));
+    }
+
+    Ok((StatusCode::OK, Json(serde_json::json!({ "status": "ok" }))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::auth::mode::AuthPolicy;
+    use chrono::Utc;
+    use sea_orm::{ActiveModelTrait, Set};
+    use uuid::Uuid;
+
+    async fn user(state: &AppState, username: &str, disabled: bool) -> users::Model {
+        users::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            username: Set(username.to_string()),
+            password_hash: Set(String::new()),
+            email: Set(format!("{username}@example.com")),
+            role: Set(users::UserRole::User),
+            email_verified: Set(true),
+            disabled: Set(disabled),
+            created_at: Set(Utc::now().into()),
+            updated_at: Set(Utc::now().into()),
+        }
+        .insert(&state.db)
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_subscriptions_follow_users() {
+        let state = AppState::for_tests(AuthPolicy::default()).await;
+        let alice = user(&state, "alice", false).await;
+        let bob = user(&state, "bob", true).await;
+
+        let first =
+            subscriptions::subscribe(&state.db, alice.id, "https://push.example/a", "k", "a")
+                .await
+                .unwrap();
+        subscriptions::record_failure(&state.db, first.id, "HTTP 500")
+            .await
+            .unwrap();
+        // 浏览器重新订阅同一地址时更新密钥
+        let again =
+            subscriptions::subscribe(&state.db, alice.id, "https://push.example/a", "k2", "a2")
+                .await
+                .unwrap();
+        assert_eq!(again.id, first.id);
+        assert_eq!((again.failures, again.p256dh.as_str()), (0, "k2"));
+        subscriptions::subscribe(&state.db, bob.id, "https://push.example/b", "k", "a")
+            .await
+            .unwrap();
+
+        // 停用用户的订阅不推送
+        let active = subscriptions::active(&state.db).await.unwrap();
+        assert_eq!(active.len(), 1);
+        assert_eq!(
+            (active[0].0.endpoint.as_str(), active[0].1.as_str()),
+            ("https://push.example/a", "alice")
+        );
+
+        assert!(
+            !subscriptions::delete_for_user(&state.db, bob.id, "https://push.example/a")
+                .await
+                .unwrap()
+        );
+        assert!(
+            subscriptions::delete_for_user(&state.db, alice.id, "https://push.example/a")
+                .await
+                .unwrap()
+        );
+        assert!(
+            subscriptions::list_for_user(&state.db, alice.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
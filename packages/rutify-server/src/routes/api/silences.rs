@@ -0,0 +1,206 @@
+use crate::db::silences;
+use crate::error::AppError;
+use crate::services::auth::user::{UserClaims, user_auth_middleware};
+use crate::state::AppState;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Extension, Json, Router, middleware};
+use chrono::{DateTime, Utc};
+use rutify_core::{CreateSilenceRequest, Silence};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set};
+use serde::Deserialize;
+use std::sync::Arc;
+
+pub(crate) fn router(state: Arc<AppState>) -> Router<Arc<AppState>> {
+    Router::new()
+        .route("/", get(list_silences_handler).post(create_silence_handler))
+        .route(
+            "/{id}",
+            get(get_silence_handler)
+                .put(update_silence_handler)
+                .delete(expire_silence_handler),
+        )
+        .layer(middleware::from_fn_with_state(state, user_auth_middleware))
+}
+
+#[derive(Debug, Deserialize)]
+struct ListSilencesQuery {
+    /// 为 true 时包含已过期的规则
+    #[serde(default)]
+    all: bool,
+}
+
+/// 静默规则更新请求（未提供的字段保持不变）
+#[derive(Debug, Deserialize)]
+struct UpdateSilenceRequest {
+    device: Option<String>,
+    channel: Option<String>,
+    tag: Option<String>,
+    comment: Option<String>,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+/// 空字符串视为未设置该条件
+fn pattern(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn validate_conditions(
+    device: &Option<String>,
+    channel: &Option<String>,
+    tag: &Option<String>,
+) -> Result<(), AppError> {
+    if device.is_none() && channel.is_none() && tag.is_none() {
+        return Err(AppError::BadRequest(
+            "Silence requires at least one of device, channel or tag".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+async fn find_silence(state: &AppState, id: i32) -> Result<silences::Model, AppError> {
+    silences::Entity::find_by_id(id)
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Silence not found".to_string()))
+}
+
+async fn list_silences_handler(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ListSilencesQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let mut select = silences::Entity::find().order_by_desc(silences::Column::Id);
+    if !query.all {
+        select = select.filter(silences::Column::ExpiresAt.gt(Utc::now()));
+    }
+    let data: Vec<Silence> = select
+        .all(&state.db)
+        .await?
+        .into_iter()
+        .map(|item| item.into_silence())
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": data })),
+    ))
+}
+
+async fn get_silence_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let item = find_silence(&state, id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": item.into_silence() })),
+    ))
+}
+
+async fn create_silence_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(claims): Extension<UserClaims>,
+    Json(request): Json<CreateSilenceRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let now = Utc::now();
+    let expires_at = match (request.expires_at, request.duration_secs) {
+        (Some(expires_at), _) => expires_at,
+        (None, Some(secs)) => {
+            let secs = i64::try_from(secs)
+                .map_err(|_| AppError::BadRequest("duration_secs is too large".to_string()))?;
+            now + chrono::Duration::seconds(secs)
+        }
+        (None, None) => {
+            return Err(AppError::BadRequest(
+                "Silence requires expires_at or duration_secs".to_string(),
+            ));
+        }
+    };
+    if expires_at <= now {
+        return Err(AppError::BadRequest(
+            "Silence expiry must be in the future".to_string(),
+        ));
+    }
+
+    let device = pattern(request.device);
+    let channel = pattern(request.channel);
+    let tag = pattern(request.tag);
+    validate_conditions(&device, &channel, &tag)?;
+
+    let item = silences::ActiveModel {
+        device: Set(device),
+        channel: Set(channel),
+        tag: Set(tag),
+        comment: Set(request.comment),
+        created_by: Set(Some(claims.username)),
+        expires_at: Set(expires_at),
+        created_at: Set(now),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+    state.silences.reload(&state.db).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({ "status": "ok", "data": item.into_silence() })),
+    ))
+}
+
+async fn update_silence_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Json(request): Json<UpdateSilenceRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let current = find_silence(&state, id).await?;
+    let device = match request.device {
+        Some(device) => pattern(Some(device)),
+        None => current.device.clone(),
+    };
+    let channel = match request.channel {
+        Some(channel) => pattern(Some(channel)),
+        None => current.channel.clone(),
+    };
+    let tag = match request.tag {
+        Some(tag) => pattern(Some(tag)),
+        None => current.tag.clone(),
+    };
+    validate_conditions(&device, &channel, &tag)?;
+
+    let mut silence: silences::ActiveModel = current.into();
+    silence.device = Set(device);
+    silence.channel = Set(channel);
+    silence.tag = Set(tag);
+    if let Some(comment) = request.comment {
+        silence.comment = Set(Some(comment));
+    }
+    if let Some(expires_at) = request.expires_at {
+        silence.expires_at = Set(expires_at);
+    }
+
+    let item = silence.update(&state.db).await?;
+    state.silences.reload(&state.db).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": item.into_silence() })),
+    ))
+}
+
+/// 立即过期静默规则（保留记录便于追溯）
+async fn expire_silence_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    let mut silence: silences::ActiveModel = find_silence(&state, id).await?.into();
+    silence.expires_at = Set(Utc::now());
+    silence.update(&state.db).await?;
+    state.silences.reload(&state.db).await?;
+
+    Ok((StatusCode::OK, Json(serde_json::json!({ "status": "ok" }))))
+}
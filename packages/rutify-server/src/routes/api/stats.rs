@@ -15,6 +15,18 @@ pub(crate) fn router() -> Router<Arc<AppState>> {
 }
 
 async fn stats_handler(State(state): State<Arc<AppState>>) -> Result<impl IntoResponse, AppError> {
+    let data = current_stats(&state).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "status": "ok",
+            "data": data
+        })),
+    ))
+}
+
+pub(crate) async fn current_stats(state: &AppState) -> Result<Stats, AppError> {
     let notifies = crate::db::notifies::Entity::find().all(&state.db).await?;
     let today = chrono::Utc::now().date_naive();
 
@@ -29,18 +41,11 @@ async fn stats_handler(State(state): State<Arc<AppState>>) -> Result<impl IntoRe
         .collect::<HashSet<String>>()
         .len() as i32;
 
-    let data = Stats {
+    Ok(Stats {
         today_count,
         total_count: notifies.len() as i32,
         device_count,
         is_running: true,
-    };
-
-    Ok((
-        StatusCode::OK,
-        Json(serde_json::json!({
-            "status": "ok",
-            "data": data
-        })),
-    ))
+        active_silences: state.silences.active_count() as i32,
+    })
 }
@@ -0,0 +1,174 @@
+use crate::db::token_ops;
+use crate::db::tokens;
+use crate::db::unifiedpush_registrations as registrations;
+use crate::error::AppError;
+use crate::services::auth::auth::{
+    authorize_notify_token, extract_bearer_token, generate_token_hash,
+};
+use crate::services::auth::failures;
+use crate::services::push::validate_endpoint;
+use crate::services::unifiedpush::validate_instance;
+use crate::state::AppState;
+use axum::extract::{Path, Request, State};
+use axum::http::StatusCode;
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get};
+use axum::{Extension, Json, Router};
+use rutify_core::{RegisterUnifiedPushRequest, UnifiedPushRegistration};
+use std::sync::Arc;
+
+/// 登记使用订阅方的通知 Token，`open` 模式下同样需要，推送地址随 Token 删除或过期失效
+pub(crate) fn router(state: Arc<AppState>) -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/registrations",
+            get(list_registrations_handler).post(register_handler),
+        )
+        .route("/registrations/{id}", delete(unregister_handler))
+        .layer(middleware::from_fn_with_state(state, subscriber_middleware))
+}
+
+/// 校验通知 Token 并将 Token 记录写入请求扩展
+async fn subscriber_middleware(
+    State(state): State<Arc<AppState>>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let token = match extract_bearer_token(&request) {
+        Ok(token) => authorize_notify_token(&state, &token.0)
+            .await
+            .map(|_| token.0),
+        Err(e) => Err(e),
+    }
+    .inspect_err(|e| {
+        state
+            .auth_failures
+            .record_error("unifiedpush", failures::peer(&request), e)
+    })?;
+    let token = token_ops::find_token(&state.db, &generate_token_hash(&token))
+        .await?
+        .ok_or_else(|| AppError::AuthError("Token not found or expired".to_string()))?;
+
+    request.extensions_mut().insert(token);
+    Ok(next.run(request).await)
+}
+
+async fn list_registrations_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(token): Extension<tokens::Model>,
+) -> Result<impl IntoResponse, AppError> {
+    let data: Vec<UnifiedPushRegistration> = registrations::list_for_token(&state.db, token.id)
+        .await?
+        .into_iter()
+        .map(|item| item.into_registration())
+        .collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": data })),
+    ))
+}
+
+async fn register_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(token): Extension<tokens::Model>,
+    Json(request): Json<RegisterUnifiedPushRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let endpoint = validate_endpoint(&request.endpoint)?;
+    let instance = validate_instance(request.instance)?;
+    let item = registrations::register(&state.db, token.id, &endpoint, instance).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(serde_json::json!({ "status": "ok", "data": item.into_registration() })),
+    ))
+}
+
+async fn unregister_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(token): Extension<tokens::Model>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, AppError> {
+    if !registrations::delete_for_token(&state.db, token.id, id).await? {
+        return Err(AppError::NotFound(format!(
+            "UnifiedPush registration {id} not found"
+        )));
+    }
+
+    Ok((StatusCode::OK, Json(serde_json::json!({ "status": "ok" }))))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::auth::mode::AuthPolicy;
+    use chrono::Utc;
+    use sea_orm::{ActiveModelTrait, Set};
+
+    async fn token(state: &AppState, hash: &str, expires_in: chrono::Duration) -> tokens::Model {
+        tokens::ActiveModel {
+            token_hash: Set(hash.to_string()),
+            usage: Set("phone".to_string()),
+            token_type: Set(tokens::TokenType::NotifyBearer),
+            created_at: Set(Utc::now()),
+            expires_at: Set(Utc::now() + expires_in),
+            ..Default::default()
+        }
+        .insert(&state.db)
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_registrations_follow_tokens() {
+        let state = AppState::for_tests(AuthPolicy::default()).await;
+        let phone = token(&state, "phone", chrono::Duration::hours(1)).await;
+        let expired = token(&state, "expired", chrono::Duration::hours(-1)).await;
+
+        let first = registrations::register(&state.db, phone.id, "https://up.example/a", None)
+            .await
+            .unwrap();
+        registrations::record_failure(&state.db, first.id, "HTTP 500")
+            .await
+            .unwrap();
+        // 重复登记同一地址时更新原记录
+        let again = registrations::register(
+            &state.db,
+            phone.id,
+            "https://up.example/a",
+            Some("pixel".to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(again.id, first.id);
+        assert_eq!(
+            (again.failures, again.instance.as_deref()),
+            (0, Some("pixel"))
+        );
+        registrations::register(&state.db, expired.id, "https://up.example/b", None)
+            .await
+            .unwrap();
+
+        let active = registrations::active(&state.db).await.unwrap();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].0.endpoint, "https://up.example/a");
+
+        assert!(
+            !registrations::delete_for_token(&state.db, expired.id, first.id)
+                .await
+                .unwrap()
+        );
+        assert!(
+            registrations::delete_for_token(&state.db, phone.id, first.id)
+                .await
+                .unwrap()
+        );
+        assert!(
+            registrations::list_for_token(&state.db, phone.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,24 @@
+use crate::state::AppState;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::{Json, Router};
+use rutify_core::{API_LEVEL, ServerVersion};
+use std::sync::Arc;
+
+pub(crate) fn router() -> Router<Arc<AppState>> {
+    Router::new().route("/", get(version_handler))
+}
+
+/// 客户端据此判断服务端支持哪些接口，不需要登录
+async fn version_handler() -> impl IntoResponse {
+    let data = ServerVersion {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        api_level: API_LEVEL,
+    };
+
+    (
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": data })),
+    )
+}
@@ -5,9 +5,12 @@ use axum::{
 use std::sync::Arc;
 
 use crate::services::auth::auth::{create_token, delete_token, get_tokens};
+use crate::services::auth::email_verification::{resend_verification, verify_email};
+use crate::services::auth::registration_gate::registration_challenge;
 use crate::services::auth::user::{
     get_user_profile, login_user, register_user, user_auth_middleware,
 };
+use crate::services::auth::validation::password_strength;
 use crate::state::AppState;
 
 pub(crate) fn router(state: Arc<AppState>) -> Router<Arc<AppState>> {
@@ -20,18 +23,10 @@ pub(crate) fn router(state: Arc<AppState>) -> Router<Arc<AppState>> {
 
     Router::new()
         .route("/register", post(register_user))
+        .route("/register/challenge", get(registration_challenge))
         .route("/login", post(login_user))
+        .route("/verify-email", get(verify_email))
+        .route("/resend-verification", post(resend_verification))
+        .route("/password/strength", post(password_strength))
         .merge(protected_router)
 }
-
-// /// Token信息响应
-// #[derive(serde::Serialize)]
-// pub struct TokenInfoResponse {
-//     pub id: i32,
-//     pub usage: String,
-//     pub token_type: String,
-//     pub device_info: Option<String>,
-//     pub created_at: String,
-//     pub expires_at: String,
-//     pub last_used_at: Option<String>,
-// }
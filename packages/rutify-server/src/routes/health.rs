@@ -0,0 +1,21 @@
+use crate::error::AppError;
+use crate::state::AppState;
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use std::sync::Arc;
+use tracing::warn;
+
+/// 存活检查：数据库可用时返回 200，否则返回 503；不需要鉴权，供容器与负载均衡探测
+pub(crate) async fn handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
+    if let Err(e) = state.db.ping().await {
+        warn!("Health check failed: {}", e);
+        return Err(AppError::ServiceUnavailable(
+            "database unavailable".to_string(),
+        ));
+    }
+    Ok((StatusCode::OK, Json(serde_json::json!({ "status": "ok" }))))
+}
@@ -0,0 +1,97 @@
+use crate::error::AppError;
+use crate::services::auth::auth::authorize_notify_token;
+use crate::services::hooks::Provider;
+use crate::services::notify;
+use crate::state::AppState;
+use axum::body::Bytes;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode, header::AUTHORIZATION};
+use axum::response::IntoResponse;
+use axum::routing::post;
+use axum::{Json, Router};
+use rutify_core::TokenDefaults;
+use serde::Deserialize;
+use std::sync::Arc;
+
+pub(crate) fn router() -> Router<Arc<AppState>> {
+    Router::new().route("/{provider}", post(receive_hook_handler))
+}
+
+#[derive(Debug, Deserialize)]
+struct HookQuery {
+    secret: Option<String>,
+    token: Option<String>,
+}
+
+async fn receive_hook_handler(
+    State(state): State<Arc<AppState>>,
+    Path(provider): Path<String>,
+    Query(query): Query<HookQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<impl IntoResponse, AppError> {
+    let provider = Provider::parse(&provider)
+        .ok_or_else(|| AppError::NotFound(format!("Unknown webhook provider: {provider}")))?;
+
+    // 配置了来源密钥时校验密钥，否则要求通知 Token，并使用 Token 的默认字段
+    let defaults = match state.config.hook_secret(provider) {
+        Some(secret) => {
+            provider.verify_secret(secret, &headers, query.secret.as_deref(), &body)?;
+            TokenDefaults::default()
+        }
+        None => {
+            let token = query
+                .token
+                .as_deref()
+                .or_else(|| {
+                    headers
+                        .get(AUTHORIZATION)
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(|value| value.strip_prefix("Bearer "))
+                })
+                .ok_or_else(|| AppError::AuthError("Missing webhook token".to_string()))?;
+            let claims = authorize_notify_token(&state, token).await?;
+            // 第三方 Webhook 无法签名，登记了签名公钥的 Token 不能用于接收 Webhook
+            if claims.signing_key.is_some() {
+                return Err(AppError::AuthError(
+                    "This token requires signed notifications".to_string(),
+                ));
+            }
+            claims.defaults
+        }
+    };
+
+    let payload: serde_json::Value = serde_json::from_slice(&body)?;
+    let notifications = provider.transform(&headers, &payload)?;
+    for notification in &notifications {
+        state.size_limits.check(&notification.input)?;
+    }
+
+    let mut accepted = 0;
+    let mut resolved = 0;
+    for mut notification in notifications {
+        defaults.apply(&mut notification.input);
+        let event = match (notification.resolved, notification.group_key) {
+            (true, Some(group_key)) => {
+                resolved += 1;
+                notify::resolve(&state, &group_key, notification.input).await
+            }
+            (_, group_key) => notify::ingest_grouped(&state, notification.input, group_key).await,
+        };
+        if event.is_some() {
+            accepted += 1;
+        }
+    }
+
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({
+            "status": "ok",
+            "data": {
+                "provider": provider.name(),
+                "accepted": accepted,
+                "resolved": resolved,
+            },
+        })),
+    ))
+}
@@ -1,5 +1,7 @@
 pub(crate) mod api;
 pub mod auth;
+pub(crate) mod health;
+pub(crate) mod hooks;
 pub(crate) mod index;
 pub(crate) mod monitor;
 pub(crate) mod notify;
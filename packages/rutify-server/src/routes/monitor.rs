@@ -1,27 +1,25 @@
-use axum::Router;
+use crate::bootstrap::monitoring::MonitoringState;
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
 use axum::routing::get;
-use common_http_server_rs::{MonitoringState, metrics_endpoint, monitoring_info_endpoint};
+use axum::{Json, Router};
 
 pub(crate) fn router(monitoring: MonitoringState) -> Router {
     Router::new()
-        .route(
-            "/metrics",
-            get({
-                let monitoring = monitoring.clone();
-                move || {
-                    let monitoring = monitoring.clone();
-                    async move { metrics_endpoint(axum::extract::State(monitoring)).await }
-                }
-            }),
-        )
-        .route(
-            "/monitoring",
-            get({
-                let monitoring = monitoring.clone();
-                move || {
-                    let monitoring = monitoring.clone();
-                    async move { monitoring_info_endpoint(axum::extract::State(monitoring)).await }
-                }
-            }),
-        )
+        .route("/metrics", get(metrics_handler))
+        .route("/monitoring", get(monitoring_handler))
+        .with_state(monitoring)
+}
+
+/// Prometheus 文本格式的请求指标
+async fn metrics_handler(State(monitoring): State<MonitoringState>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        monitoring.render_metrics(),
+    )
+}
+
+async fn monitoring_handler(State(monitoring): State<MonitoringState>) -> impl IntoResponse {
+    Json(serde_json::json!({ "status": "ok", "data": monitoring.snapshot() }))
 }
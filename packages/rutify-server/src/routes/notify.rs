@@ -1,138 +1,396 @@
+use crate::db::token_ops;
 use crate::error::AppError;
-use crate::services::auth::auth::{check_token_exists, verify_ws_token};
+use crate::response::with_legacy_fields;
+use crate::services::auth::auth::{
+    TokenClaims, check_token_exists, generate_token_hash, notify_token_middleware, verify_ws_token,
+};
+use crate::services::dedup::{DUPLICATE_HEADER, GetDedup};
+use crate::services::delivery::{self, DeliveryCursor, is_visible_to};
+use crate::services::device::{self, SenderContext};
+use crate::services::notify;
+use crate::services::signing;
 use crate::state::AppState;
-use axum::extract::ws::{Message, WebSocket};
-use axum::extract::{Query, State, WebSocketUpgrade};
-use axum::http::StatusCode;
-use axum::response::IntoResponse;
+use axum::extract::ws::{CloseFrame, Message, WebSocket};
+use axum::extract::{ConnectInfo, Query, State, WebSocketUpgrade};
+use axum::http::header::{AUTHORIZATION, USER_AGENT};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
 use axum::routing::{get, post};
-use axum::{Json, Router};
-use rutify_core::{NotificationData, NotificationInput, NotifyEvent};
+use axum::{Extension, Json, Router, middleware};
+use rutify_core::{
+    EventKind, NotificationInput, NotifyEvent, NotifyId, WireFormat, WireFrame, WsClientMessage,
+};
 use serde::Deserialize;
+use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::broadcast;
 use tracing::{error, info, warn};
 
-const DEFAULT_TITLE: &str = "default title";
-const DEFAULT_DEVICE: &str = "default device";
+/// 投递游标的持久化间隔
+const CURSOR_SAVE_INTERVAL: Duration = Duration::from_secs(5);
 
-pub(crate) fn router() -> Router<Arc<AppState>> {
+/// 首帧鉴权的等待时间
+const AUTH_FRAME_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 首帧鉴权失败时的关闭码
+const CLOSE_UNAUTHORIZED: u16 = 4401;
+
+pub(crate) fn router(state: Arc<AppState>) -> Router<Arc<AppState>> {
     Router::new()
         .route("/", get(receive_notify_get_handler))
         .route("/", post(receive_notify_post_handler))
+        .route_layer(middleware::from_fn_with_state(
+            state,
+            notify_token_middleware,
+        ))
+        // WebSocket 自行鉴权，支持首帧鉴权
         .route("/ws", get(ws_handler))
 }
 
+/// 窗口内重复的请求不再入库，直接返回首次的通知 ID，响应头 `X-Rutify-Duplicate` 标明是否重复
 async fn receive_notify_get_handler(
     State(state): State<Arc<AppState>>,
+    claims: Option<Extension<TokenClaims>>,
+    headers: HeaderMap,
     Query(payload): Query<NotificationInput>,
-) -> Result<impl IntoResponse, AppError> {
-    receive_notify_logic(state, payload).await;
-    Ok((StatusCode::OK, Json(serde_json::json!({ "status": "ok" }))))
+) -> Result<Response, AppError> {
+    if !state.get_dedup.is_enabled() {
+        let event = ingest_request(&state, payload, claims, &headers).await?;
+        return Ok(notify_response(event)?.into_response());
+    }
+
+    let token_id = claims.as_ref().map(|Extension(claims)| claims.jti.as_str());
+    let key = GetDedup::key(token_id, &payload)?;
+    if let Some(id) = state.get_dedup.claim(&key).await {
+        let data = serde_json::json!({ "id": id });
+        return Ok(([(DUPLICATE_HEADER, "true")], with_legacy_fields(data)?).into_response());
+    }
+    match ingest_request(&state, payload, claims, &headers).await {
+        Ok(event) => {
+            let id = event.as_ref().and_then(|event| event.data.id);
+            state.get_dedup.record(key, id.map(NotifyId::get)).await;
+            Ok(([(DUPLICATE_HEADER, "false")], notify_response(event)?).into_response())
+        }
+        Err(e) => {
+            state.get_dedup.release(&key).await;
+            Err(e)
+        }
+    }
 }
 
 async fn receive_notify_post_handler(
     State(state): State<Arc<AppState>>,
+    claims: Option<Extension<TokenClaims>>,
+    headers: HeaderMap,
     Json(payload): Json<NotificationInput>,
 ) -> Result<impl IntoResponse, AppError> {
-    receive_notify_logic(state, payload).await;
-    Ok((StatusCode::OK, Json(serde_json::json!({ "status": "ok" }))))
-}
-
-async fn receive_notify_logic(state: Arc<AppState>, payload: NotificationInput) {
-    let db = &state.db;
-    let tx = &state.tx;
-    let data = normalize_notification(payload);
-    crate::db::notifies::insert_new_notify(db, data.clone()).await;
-    let event = NotifyEvent {
-        event: "notify".to_string(),
-        data,
-        timestamp: chrono::Utc::now(),
+    let event = ingest_request(&state, payload, claims, &headers).await?;
+    notify_response(event)
+}
+
+/// 补全设备名与 Token 默认字段后入库；open 模式的匿名请求没有 Token，只按 User-Agent 推断
+///
+/// 拒绝策略下超长的通知返回 413；Token 登记了签名公钥时，缺少或无效的签名返回 401。
+async fn ingest_request(
+    state: &AppState,
+    mut payload: NotificationInput,
+    claims: Option<Extension<TokenClaims>>,
+    headers: &HeaderMap,
+) -> Result<Option<NotifyEvent>, AppError> {
+    state.size_limits.check(&payload)?;
+    let claims = claims.map(|Extension(claims)| claims);
+    signing::verify_sender(
+        claims
+            .as_ref()
+            .and_then(|claims| claims.signing_key.as_deref()),
+        &mut payload,
+    )?;
+    let token = claims.as_ref().and_then(|_| bearer_token(headers));
+    let sender = SenderContext {
+        defaults: claims.as_ref().map(|claims| &claims.defaults),
+        token,
+        user_agent: headers
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok()),
     };
-    let _ = tx.send(event);
+    let device_source = device::derive(state, &mut payload, sender).await;
+    let token_hash = token.map(generate_token_hash);
+    Ok(notify::ingest_derived(state, payload, None, device_source, token_hash).await)
 }
 
-fn normalize_notification(payload: NotificationInput) -> NotificationData {
-    NotificationData {
-        notify: payload.notify,
-        title: payload.title.unwrap_or_else(|| DEFAULT_TITLE.to_string()),
-        device: payload.device.unwrap_or_else(|| DEFAULT_DEVICE.to_string()),
-    }
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+}
+
+fn notify_response(event: Option<NotifyEvent>) -> Result<impl IntoResponse, AppError> {
+    let data = match event {
+        Some(event) => serde_json::json!({ "id": event.data.id }),
+        None => serde_json::json!({ "id": null, "dropped": true }),
+    };
+    Ok((StatusCode::OK, with_legacy_fields(data)?))
 }
 
 #[derive(Debug, Deserialize)]
 pub(crate) struct WsQuery {
-    token: String,
+    /// 已弃用：查询参数中的 Token 会进入代理日志，请改用 `Authorization` 头或首帧鉴权
+    token: Option<String>,
+    /// 消息编码：`json`（默认）或 `msgpack`，也可通过子协议协商
+    format: Option<String>,
 }
 
+/// 已授权 WebSocket 连接的上下文
+struct WsSession {
+    /// Token 用途，open 模式的匿名连接为 `anonymous`
+    usage: String,
+    username: Option<String>,
+    cursor: Option<DeliveryCursor>,
+}
+
+/// WebSocket 入口
+///
+/// Token 依次取自 `Authorization: Bearer` 头与 `token` 查询参数；两者都没有时，open 模式
+/// 直接作为匿名连接，其他模式先完成升级，再等待客户端首帧发送 `{"type":"auth","token":"..."}`。
 pub(crate) async fn ws_handler(
     ws: WebSocketUpgrade,
     State(state): State<Arc<AppState>>,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
+    headers: HeaderMap,
     Query(query): Query<WsQuery>,
 ) -> impl IntoResponse {
-    match verify_ws_token(&query.token, &state).await {
-        Ok(claims) => {
-            info!(
-                "WebSocket connection authorized for token usage: {}",
-                claims.usage
-            );
-
-            // 异步验证 token 是否在数据库中存在
-            let state_clone = Arc::clone(&state);
-            let token_clone = query.token.clone();
-            let _claims_clone = claims.clone();
-
-            tokio::spawn(async move {
-                match check_token_exists(&token_clone, &state_clone).await {
-                    Ok(true) => {
-                        info!("Token verified in database for WebSocket connection");
-                    }
-                    Ok(false) => {
-                        warn!("Token not found in database for WebSocket connection");
-                    }
-                    Err(e) => {
-                        error!("Database errors during WebSocket token verification: {}", e);
-                    }
-                }
-            });
+    let ws = ws.protocols(
+        WireFormat::supported()
+            .into_iter()
+            .map(WireFormat::subprotocol),
+    );
+    let format = match query.format.as_deref() {
+        Some(name) => match WireFormat::parse(name) {
+            Some(format) => format,
+            None => {
+                return AppError::BadRequest(format!("Unsupported format: {name}")).into_response();
+            }
+        },
+        None => ws
+            .selected_protocol()
+            .and_then(|protocol| protocol.to_str().ok())
+            .and_then(WireFormat::from_subprotocol)
+            .unwrap_or_default(),
+    };
 
-            ws.on_upgrade(move |socket| handle_socket(socket, state, claims))
+    let header_token = bearer_token(&headers).map(str::to_string);
+    let token = match (header_token, query.token) {
+        (Some(token), _) => token,
+        (None, Some(token)) => {
+            warn!("WebSocket token passed in query string, which is deprecated");
+            token
+        }
+        (None, None) => {
+            let peer = connect_info.map(|Extension(ConnectInfo(addr))| addr.ip());
+            if state.auth_policy.allows_anonymous(peer) {
+                let session = WsSession {
+                    usage: "anonymous".to_string(),
+                    username: None,
+                    cursor: None,
+                };
+                return ws
+                    .on_upgrade(move |socket| handle_socket(socket, state, session, format))
+                    .into_response();
+            }
+            return ws
+                .on_upgrade(move |socket| authenticate_first_frame(socket, state, format))
+                .into_response();
         }
+    };
+
+    match authorize_socket(&state, &token).await {
+        Ok(session) => ws
+            .on_upgrade(move |socket| handle_socket(socket, state, session, format))
+            .into_response(),
         Err(e) => {
             error!("WebSocket authorization failed: {}", e);
             // 返回错误响应而不是升级连接
-            axum::response::Response::builder()
-                .status(axum::http::StatusCode::UNAUTHORIZED)
-                .body(axum::body::Body::from("Unauthorized"))
-                .unwrap()
-                .into_response()
+            AppError::AuthError("Unauthorized".to_string()).into_response()
+        }
+    }
+}
+
+/// 等待首帧鉴权消息，超时或鉴权失败时以 4401 关闭连接
+async fn authenticate_first_frame(mut socket: WebSocket, state: Arc<AppState>, format: WireFormat) {
+    let token = match tokio::time::timeout(AUTH_FRAME_TIMEOUT, socket.recv()).await {
+        Ok(Some(Ok(Message::Text(text)))) => {
+            match serde_json::from_str::<WsClientMessage>(text.as_str()) {
+                Ok(WsClientMessage::Auth { token }) => Some(token),
+                Ok(_) => {
+                    warn!("Expected an auth frame before other WebSocket messages");
+                    None
+                }
+                Err(e) => {
+                    warn!("Invalid WebSocket auth frame: {}", e);
+                    None
+                }
+            }
+        }
+        Ok(_) => None,
+        Err(_) => {
+            warn!("WebSocket auth frame timed out");
+            None
+        }
+    };
+
+    let session = match token {
+        Some(token) => match authorize_socket(&state, &token).await {
+            Ok(session) => Some(session),
+            Err(e) => {
+                error!("WebSocket authorization failed: {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    match session {
+        Some(session) => handle_socket(socket, state, session, format).await,
+        None => {
+            let _ = socket
+                .send(Message::Close(Some(CloseFrame {
+                    code: CLOSE_UNAUTHORIZED,
+                    reason: "Unauthorized".into(),
+                })))
+                .await;
         }
     }
 }
 
+/// 校验 Token 并准备连接所需的用户与投递游标
+async fn authorize_socket(state: &Arc<AppState>, token: &str) -> Result<WsSession, AppError> {
+    let claims = verify_ws_token(token, state)
+        .await
+        .inspect_err(|e| state.auth_failures.record_error("websocket", None, e))?;
+    info!(
+        "WebSocket connection authorized for token usage: {}",
+        claims.usage
+    );
+
+    // 异步验证 token 是否在数据库中存在
+    let state_clone = Arc::clone(state);
+    let token_clone = token.to_string();
+
+    tokio::spawn(async move {
+        match check_token_exists(&token_clone, &state_clone).await {
+            Ok(true) => {
+                info!("Token verified in database for WebSocket connection");
+            }
+            Ok(false) => {
+                warn!("Token not found in database for WebSocket connection");
+            }
+            Err(e) => {
+                error!("Database errors during WebSocket token verification: {}", e);
+            }
+        }
+    });
+
+    // 绑定用户的 Token 才能收到定向给该用户的值班通知
+    let token_hash = generate_token_hash(token);
+    let username = match token_ops::find_token_username(&state.db, &token_hash).await {
+        Ok(username) => username,
+        Err(e) => {
+            warn!("Failed to resolve WebSocket token owner: {}", e);
+            None
+        }
+    };
+
+    // 记录在库的 Token 才跟踪投递进度并在重连时补发
+    let cursor = match token_ops::find_token(&state.db, &token_hash).await {
+        Ok(Some(token)) => match DeliveryCursor::open(state, &token).await {
+            Ok(cursor) => Some(cursor),
+            Err(e) => {
+                warn!("Failed to open delivery cursor: {}", e);
+                None
+            }
+        },
+        Ok(None) => None,
+        Err(e) => {
+            warn!("Failed to find WebSocket token: {}", e);
+            None
+        }
+    };
+
+    Ok(WsSession {
+        usage: claims.usage,
+        username,
+        cursor,
+    })
+}
+
 async fn handle_socket(
     mut socket: WebSocket,
     state: Arc<AppState>,
-    claims: crate::services::auth::auth::TokenClaims,
+    session: WsSession,
+    format: WireFormat,
 ) {
+    let WsSession {
+        usage,
+        username,
+        mut cursor,
+    } = session;
+    let _connection = state.connections.open();
     let mut rx = state.tx.subscribe();
+    let mut save_interval = tokio::time::interval(CURSOR_SAVE_INTERVAL);
 
     info!(
-        "WebSocket connection established for usage: {}",
-        claims.usage
+        "WebSocket connection established for usage: {} ({})",
+        usage,
+        format.as_str()
     );
 
+    // 先订阅再补发，补发期间到达的实时通知由游标去重
+    if let Some(cursor) = cursor.as_mut()
+        && replay_missed(
+            &mut socket,
+            &state,
+            cursor,
+            &usage,
+            username.as_deref(),
+            format,
+            true,
+        )
+        .await
+        .is_err()
+    {
+        warn!("Failed to replay missed notifications for usage: {}", usage);
+        cursor.save(&state).await;
+        return;
+    }
+
     loop {
         tokio::select! {
             msg = socket.recv() => {
                 match msg {
                     Some(Ok(Message::Close(_))) | None => {
-                        info!("WebSocket connection closed for usage: {}", claims.usage);
+                        info!("WebSocket connection closed for usage: {}", usage);
                         break;
                     }
-                    Some(Ok(_)) => {}
+                    Some(Ok(message)) => {
+                        if let Some(WsClientMessage::Ack { id }) = client_message(&message, format) {
+                            let token_id = cursor.as_ref().map(DeliveryCursor::token_id);
+                            if let Err(e) = delivery::acknowledge(
+                                &state,
+                                id,
+                                &usage,
+                                username.as_deref(),
+                                token_id,
+                            )
+                            .await
+                            {
+                                warn!("Failed to record ack for notify {}: {}", id, e);
+                            }
+                        }
+                    }
                     Some(Err(err)) => {
-                        error!(error = %err, "websocket receive errors for usage: {}", claims.usage);
+                        error!(error = %err, "websocket receive errors for usage: {}", usage);
                         break;
                     }
                 }
@@ -140,27 +398,332 @@ async fn handle_socket(
             event = rx.recv() => {
                 match event {
                     Ok(event) => {
-                        match serde_json::to_string(&event) {
-                            Ok(text) => {
-                                if socket.send(Message::Text(text.into())).await.is_err() {
-                                    warn!("Failed to send message to WebSocket for usage: {}", claims.usage);
-                                    break;
-                                }
-                            }
-                            Err(err) => {
-                                error!(error = %err, "websocket serialize errors for usage: {}", claims.usage);
-                            }
+                        // 当前连接使用的 Token 被撤销：通知客户端后关闭，游标随 Token 一起删除
+                        if event.event == EventKind::TokenRevoked
+                            && cursor
+                                .as_ref()
+                                .is_some_and(|cursor| event.revoked_token_id() == Some(cursor.token_id()))
+                        {
+                            let _ = send_event(&mut socket, &event, format).await;
+                            info!("Token revoked, closing WebSocket for usage: {}", usage);
+                            cursor = None;
+                            break;
+                        }
+                        if !is_visible_to(&event.data, username.as_deref()) {
+                            continue;
+                        }
+                        if cursor.as_ref().is_some_and(|cursor| cursor.already_sent(&event)) {
+                            continue;
+                        }
+                        if send_event(&mut socket, &event, format).await.is_err() {
+                            warn!("Failed to send message to WebSocket for usage: {}", usage);
+                            break;
+                        }
+                        let token_id = cursor.as_ref().map(DeliveryCursor::token_id);
+                        if let Some(receipt) = delivery::websocket_receipt(&event, &usage, token_id) {
+                            delivery::record_receipt(&state, receipt);
+                        }
+                        if let Some(cursor) = cursor.as_mut() {
+                            cursor.advance(&event);
                         }
                     }
                     Err(broadcast::error::RecvError::Closed) => {
-                        info!("Broadcast channel closed for usage: {}", claims.usage);
+                        info!("Broadcast channel closed for usage: {}", usage);
                         break;
                     }
                     Err(broadcast::error::RecvError::Lagged(_)) => {
-                        warn!("WebSocket client lagged for usage: {}", claims.usage);
+                        warn!("WebSocket client lagged for usage: {}", usage);
+                        // 丢失的实时事件从数据库补齐
+                        if let Some(cursor) = cursor.as_mut()
+                            && replay_missed(
+                                &mut socket,
+                                &state,
+                                cursor,
+                                &usage,
+                                username.as_deref(),
+                                format,
+                                false,
+                            )
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
                     }
                 }
             }
+            _ = save_interval.tick() => {
+                if let Some(cursor) = cursor.as_mut() {
+                    cursor.save(&state).await;
+                }
+            }
+        }
+    }
+
+    if let Some(cursor) = cursor.as_mut() {
+        cursor.save(&state).await;
+    }
+}
+
+/// 补发游标之后错过的通知，`report` 为真时先发送一条错过数量的提示
+///
+/// 只有发送失败（连接已断开）时返回错误。
+async fn replay_missed(
+    socket: &mut WebSocket,
+    state: &AppState,
+    cursor: &mut DeliveryCursor,
+    usage: &str,
+    username: Option<&str>,
+    format: WireFormat,
+    report: bool,
+) -> Result<(), axum::Error> {
+    let (total, events) = match cursor.missed(state, username).await {
+        Ok(missed) => missed,
+        Err(e) => {
+            warn!("Failed to load missed notifications: {}", e);
+            return Ok(());
+        }
+    };
+    if total == 0 {
+        return Ok(());
+    }
+
+    info!(
+        "Replaying {} of {} missed notifications",
+        events.len(),
+        total
+    );
+    if report {
+        send_event(
+            socket,
+            &delivery::missed_report(total, events.len()),
+            format,
+        )
+        .await?;
+    }
+    for event in events {
+        send_event(socket, &event, format).await?;
+        cursor.advance(&event);
+        if let Some(receipt) = delivery::websocket_receipt(&event, usage, Some(cursor.token_id())) {
+            delivery::record_receipt(state, receipt);
+        }
+    }
+    Ok(())
+}
+
+/// 解析客户端消息：文本帧总是 JSON，二进制帧使用连接协商的格式
+fn client_message(message: &Message, format: WireFormat) -> Option<WsClientMessage> {
+    let parsed = match message {
+        Message::Text(text) => serde_json::from_str(text.as_str()).map_err(|e| e.to_string()),
+        Message::Binary(bytes) => format.decode(bytes).map_err(|e| e.to_string()),
+        _ => return None,
+    };
+    parsed
+        .inspect_err(|e| warn!("Invalid WebSocket client message: {}", e))
+        .ok()
+}
+
+async fn send_event(
+    socket: &mut WebSocket,
+    event: &NotifyEvent,
+    format: WireFormat,
+) -> Result<(), axum::Error> {
+    match format.encode(event) {
+        Ok(WireFrame::Text(text)) => socket.send(Message::Text(text.into())).await,
+        Ok(WireFrame::Binary(bytes)) => socket.send(Message::Binary(bytes.into())).await,
+        Err(err) => {
+            error!(error = %err, "websocket encode errors");
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::auth::mode::{AuthMode, AuthPolicy};
+    use axum::body::Body;
+    use axum::http::Request;
+    use axum::http::header::CONTENT_TYPE;
+    use tower::ServiceExt;
+
+    async fn post_notify(policy: AuthPolicy, authorization: Option<&str>) -> StatusCode {
+        let state = AppState::for_tests(policy).await;
+        let app = router(Arc::clone(&state)).with_state(state);
+
+        let mut request = Request::post("/").header(CONTENT_TYPE, "application/json");
+        if let Some(authorization) = authorization {
+            request = request.header(AUTHORIZATION, authorization);
+        }
+        let request = request.body(Body::from(r#"{"notify":"hello"}"#)).unwrap();
+        app.oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_notify_route_auth() {
+        let policy = |mode, open_private_only| AuthPolicy {
+            mode,
+            open_private_only,
+        };
+
+        // token 与 strict 模式必须携带有效的通知 Token
+        for mode in [AuthMode::Token, AuthMode::Strict] {
+            assert_eq!(
+                post_notify(policy(mode, true), None).await,
+                StatusCode::UNAUTHORIZED
+            );
+            assert_eq!(
+                post_notify(policy(mode, true), Some("Bearer not-a-jwt")).await,
+                StatusCode::UNAUTHORIZED
+            );
+        }
+
+        // open 模式限制私有网段时，取不到来源地址按公网处理
+        assert_eq!(
+            post_notify(policy(AuthMode::Open, true), None).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            post_notify(policy(AuthMode::Open, false), None).await,
+            StatusCode::OK
+        );
+        // open 模式下携带的 Token 照常校验
+        assert_eq!(
+            post_notify(policy(AuthMode::Open, false), Some("Bearer not-a-jwt")).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[tokio::test]
+    async fn test_open_mode_verifies_token_signature() {
+        use crate::db::users::UserRole;
+        use crate::services::auth::auth::create_token;
+        use crate::services::auth::user::UserClaims;
+        use base64::Engine;
+        use base64::engine::general_purpose::STANDARD;
+        use ed25519_dalek::{Signer, SigningKey};
+        use rutify_core::{CreateTokenRequest, NotifySignature};
+        use serde_json::json;
+
+        let state = AppState::for_tests(AuthPolicy {
+            mode: AuthMode::Open,
+            open_private_only: false,
+        })
+        .await;
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let public_key = STANDARD.encode(key.verifying_key().as_bytes());
+        let user = UserClaims {
+            sub: String::new(),
+            username: "alice".to_string(),
+            role: UserRole::User,
+            iat: 0,
+            exp: 0,
+            jti: "x".to_string(),
+            token_type: "user_jwt".to_string(),
+        };
+        let Json(created) = create_token(
+            State(Arc::clone(&state)),
+            Extension(user),
+            Json(CreateTokenRequest {
+                usage: "deploy".to_string(),
+                signing_key: Some(public_key.clone()),
+                ..Default::default()
+            }),
+        )
+        .await
+        .unwrap();
+        let authorization = format!("Bearer {}", created["token"].as_str().unwrap());
+
+        let app = router(Arc::clone(&state)).with_state(state);
+        let post = |body: serde_json::Value| {
+            let app = app.clone();
+            let request = Request::post("/")
+                .header(CONTENT_TYPE, "application/json")
+                .header(AUTHORIZATION, &authorization)
+                .body(Body::from(body.to_string()))
+                .unwrap();
+            async move { app.oneshot(request).await.unwrap().status() }
+        };
+
+        let signature = key.sign(&NotifySignature::message("Deploy", "v2 is live"));
+        let mut signed = json!({
+            "notify": "v2 is live",
+            "title": "Deploy",
+            "signature": {
+                "public_key": public_key,
+                "signature": STANDARD.encode(signature.to_bytes()),
+            },
+        });
+        assert_eq!(post(signed.clone()).await, StatusCode::OK);
+
+        // 登记了公钥的 Token 在 open 模式下同样拒绝未签名或被篡改的通知
+        assert_eq!(
+            post(json!({ "notify": "v2 is live", "title": "Deploy" })).await,
+            StatusCode::UNAUTHORIZED
+        );
+        signed["notify"] = json!("v2 rolled back");
+        assert_eq!(post(signed).await, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_get_notify_dedup() {
+        let state = AppState::for_tests(AuthPolicy {
+            mode: AuthMode::Open,
+            open_private_only: false,
+        })
+        .await;
+        let app = router(Arc::clone(&state)).with_state(state);
+        let get = |uri: &'static str| {
+            let app = app.clone();
+            async move {
+                let response = app
+                    .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+                    .await
+                    .unwrap();
+                let duplicate = response.headers()[DUPLICATE_HEADER].to_str().unwrap() == "true";
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                (duplicate, body["data"]["id"].clone())
+            }
+        };
+
+        let (duplicate, id) = get("/?notify=hello").await;
+        assert!(!duplicate);
+        assert!(id.is_number());
+        // 预取后的重复请求返回首次的 ID
+        assert_eq!(get("/?notify=hello").await, (true, id.clone()));
+        let (duplicate, other) = get("/?notify=hello&title=t").await;
+        assert!(!duplicate);
+        assert_ne!(other, id);
+    }
+
+    #[test]
+    fn test_token_defaults() {
+        // 旧 Token 的 claims 中没有默认字段
+        let claims: TokenClaims = serde_json::from_str(
+            r#"{"sub":"1","usage":"ci","token_type":"notify_bearer","iat":0,"exp":0,"jti":"x"}"#,
+        )
+        .unwrap();
+        assert!(claims.defaults.is_empty());
+
+        let defaults = rutify_core::TokenDefaults {
+            title: Some(" CI ".to_string()),
+            device: Some("runner".to_string()),
+            channel: Some(String::new()),
         }
+        .normalized();
+        let claims = TokenClaims { defaults, ..claims };
+
+        let mut input = NotificationInput {
+            notify: "build passed".to_string(),
+            device: Some("laptop".to_string()),
+            ..Default::default()
+        };
+        claims.defaults.apply(&mut input);
+        assert_eq!(input.title.as_deref(), Some("CI"));
+        assert_eq!(input.device.as_deref(), Some("laptop"));
+        assert_eq!(input.channel, None);
     }
 }
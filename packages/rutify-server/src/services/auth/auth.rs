@@ -1,6 +1,6 @@
 use axum::{
-    Json,
-    extract::{Path, Request, State},
+    Extension, Json,
+    extract::{ConnectInfo, Path, Request, State},
     http::StatusCode,
     http::header::AUTHORIZATION,
     middleware::Next,
@@ -8,14 +8,27 @@ use axum::{
     response::Response,
 };
 use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use rutify_core::{
+    CreateTokenRequest, CreateTokenResponse, EventKind, TokenDefaults, TokenId, TokenInfo,
+};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::net::SocketAddr;
 use std::sync::Arc;
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
+use crate::bootstrap::config::ServerConfig;
 use crate::db::token_ops;
+use crate::db::tokens::Model as TokenModel;
+use crate::db::users::{Model as UserModel, UserRole};
 use crate::error::AppError;
+use crate::response::with_legacy_fields;
+use crate::services::auth::failures;
+use crate::services::auth::token_cache::TokenLookup;
+use crate::services::auth::user::UserClaims;
+use crate::services::notify;
+use crate::services::signing;
 use crate::state::AppState;
 
 // /notify 使用key走bearer token
@@ -41,57 +54,17 @@ pub struct TokenClaims {
     pub iat: i64,           // 签发时间
     pub exp: i64,           // 过期时间
     pub jti: String,        // JWT ID
-}
-
-/// Token 创建请求
-#[derive(Debug, Deserialize)]
-pub struct CreateTokenRequest {
-    pub usage: String,
-    pub expires_in_hours: Option<u64>,
-    pub device_info: Option<String>,
-}
-
-/// Token 创建响应
-#[derive(Debug, Serialize)]
-pub struct CreateTokenResponse {
-    pub token: String,
-    pub token_id: String,
-    pub usage: String,
-    pub token_type: String,
-    pub expires_at: String,
-}
-
-/// Token信息响应
-#[derive(Debug, Serialize)]
-pub struct TokenInfoResponse {
-    pub id: i32,
-    pub usage: String,
-    pub token_type: String,
-    pub device_info: Option<String>,
-    pub created_at: String,
-    pub expires_at: String,
-    pub last_used_at: Option<String>,
+    /// 发送方未提供标题、设备或频道时使用的默认值
+    #[serde(default, skip_serializing_if = "TokenDefaults::is_empty")]
+    pub defaults: TokenDefaults,
+    /// 登记的发送方签名公钥（base64），设置后只接受该公钥签名的通知
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_key: Option<String>,
 }
 
 /// Bearer Token 提取器
 pub struct BearerToken(pub String);
 
-/// JWT 密钥 (从环境变量获取，默认使用固定密钥)
-fn get_jwt_secret() -> String {
-    let secret = std::env::var("RUTIFY_JWT_SECRET").unwrap_or_else(|_| {
-        warn!("Using default JWT secret. Please set RUTIFY_JWT_SECRET environment variable in production!");
-        "rutify_default_jwt_secret_change_in_production".to_string()
-    });
-
-    // 验证密钥强度
-    if secret.len() < 32 {
-        error!("JWT secret is too short (minimum 32 characters required)");
-        panic!("JWT secret must be at least 32 characters long");
-    }
-
-    secret
-}
-
 /// 生成 Token Hash
 pub fn generate_token_hash(token: &str) -> String {
     let mut hasher = Sha256::new();
@@ -102,12 +75,20 @@ pub fn generate_token_hash(token: &str) -> String {
 /// 创建新的通知 JWT Token
 pub async fn create_token(
     State(state): State<Arc<AppState>>,
+    Extension(user_claims): Extension<UserClaims>,
     Json(request): Json<CreateTokenRequest>,
-) -> Result<Json<CreateTokenResponse>, AppError> {
+) -> Result<Json<serde_json::Value>, AppError> {
     let token_id = Uuid::new_v4().to_string();
     let now = chrono::Utc::now();
     let expires_in = request.expires_in_hours.unwrap_or(24); // 默认24小时
     let expires_at = now + chrono::Duration::hours(expires_in as i64);
+    let signing_key = match request.signing_key.as_deref().map(str::trim) {
+        Some(key) => {
+            signing::parse_public_key(key)?;
+            Some(key.to_string())
+        }
+        None => None,
+    };
 
     let claims = TokenClaims {
         sub: token_id.clone(),
@@ -116,25 +97,30 @@ pub async fn create_token(
         iat: now.timestamp(),
         exp: expires_at.timestamp(),
         jti: Uuid::new_v4().to_string(),
+        defaults: request.defaults.normalized(),
+        signing_key,
     };
 
-    let secret = get_jwt_secret();
-
     // 明确指定HS256算法
     let header = Header::new(jsonwebtoken::Algorithm::HS256);
 
-    let token =
-        encode(&header, &claims, &EncodingKey::from_secret(secret.as_ref())).map_err(|e| {
-            error!("Failed to encode JWT: {}", e);
-            AppError::AuthError("Failed to create token".to_string())
-        })?;
+    let token = encode(
+        &header,
+        &claims,
+        &EncodingKey::from_secret(state.config.jwt_key()),
+    )
+    .map_err(|e| {
+        error!("Failed to encode JWT: {}", e);
+        AppError::AuthError("Failed to create token".to_string())
+    })?;
 
-    // 保存 token hash 到数据库
+    // 保存 token hash 到数据库，记录所属用户以便定向投递
     let token_hash = generate_token_hash(&token);
     token_ops::create_notify_token(
         &state.db,
         &token_hash,
         &request.usage,
+        user_claims.sub.parse().ok(),
         expires_at,
         request.device_info,
     )
@@ -142,54 +128,104 @@ pub async fn create_token(
 
     info!("Created new notify token for usage: {}", request.usage);
 
-    Ok(Json(CreateTokenResponse {
+    with_legacy_fields(CreateTokenResponse {
         token,
         token_id,
         usage: request.usage,
         token_type: "notify_bearer".to_string(),
-        expires_at: expires_at.to_string(),
-    }))
+        expires_at,
+    })
+}
+
+fn token_info(item: TokenModel) -> TokenInfo {
+    TokenInfo {
+        id: TokenId(item.id),
+        usage: item.usage,
+        token_type: match item.token_type {
+            crate::db::tokens::TokenType::UserJwt => "user_jwt".to_string(),
+            crate::db::tokens::TokenType::NotifyBearer => "notify_bearer".to_string(),
+        },
+        device_info: item.device_info,
+        created_at: item.created_at,
+        expires_at: item.expires_at,
+        last_used_at: item.last_used_at,
+        owner: None,
+    }
+}
+
+/// 列出当前用户的 Token
+pub async fn get_tokens(
+    State(state): State<Arc<AppState>>,
+    Extension(user): Extension<UserModel>,
+) -> Result<impl IntoResponse, AppError> {
+    let data = token_ops::get_user_tokens(&state.db, user.id).await?;
+    let tokens: Vec<TokenInfo> = data.into_iter().map(token_info).collect();
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": tokens })),
+    ))
 }
 
-pub async fn get_tokens(State(state): State<Arc<AppState>>) -> Result<impl IntoResponse, AppError> {
+/// 列出所有用户的 Token，仅管理员可用
+pub async fn get_all_tokens(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, AppError> {
     let data = token_ops::list_all_tokens(&state.db).await?;
-    let tokens: Vec<TokenInfoResponse> = data
+    let owners = token_ops::usernames_by_id(&state.db).await?;
+    let tokens: Vec<TokenInfo> = data
         .into_iter()
-        .map(|item| TokenInfoResponse {
-            id: item.id,
-            usage: item.usage,
-            token_type: match item.token_type {
-                crate::db::tokens::TokenType::UserJwt => "user_jwt".to_string(),
-                crate::db::tokens::TokenType::NotifyBearer => "notify_bearer".to_string(),
-            },
-            device_info: item.device_info,
-            created_at: item.created_at.to_string(),
-            expires_at: item.expires_at.to_string(),
-            last_used_at: item.last_used_at.map(|dt| dt.to_string()),
+        .map(|item| {
+            let owner = item.user_id.and_then(|id| owners.get(&id).cloned());
+            TokenInfo {
+                owner,
+                ..token_info(item)
+            }
         })
         .collect();
-    Ok((StatusCode::OK, Json(tokens)))
+    Ok((
+        StatusCode::OK,
+        Json(serde_json::json!({ "status": "ok", "data": tokens })),
+    ))
 }
 
+/// 删除 Token，普通用户只能删除自己的 Token
 pub async fn delete_token(
     State(state): State<Arc<AppState>>,
-    Path(token_id): Path<i32>,
+    Extension(user): Extension<UserModel>,
+    Path(TokenId(token_id)): Path<TokenId>,
 ) -> Result<impl IntoResponse, AppError> {
+    let token = token_ops::find_token_by_id(&state.db, token_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Token not found".to_string()))?;
+    if token.user_id != Some(user.id) && user.role != UserRole::Admin {
+        warn!(
+            "User {} attempted to delete token {} owned by another user",
+            user.username, token_id
+        );
+        // 不暴露其他用户 Token 的存在
+        return Err(AppError::NotFound("Token not found".to_string()));
+    }
+
+    // 撤销事件只发给 Token 的所有者，未绑定用户时发给执行删除的用户
+    let owner = token_ops::find_token_username(&state.db, &token.token_hash).await?;
     let deleted = token_ops::delete_token_by_id(&state.db, token_id).await?;
     if deleted {
+        state.token_cache.invalidate_all();
+        let mut data = notify::system_data(
+            "Token revoked",
+            format!("Token '{}' was revoked", token.usage),
+        );
+        data.token_id = Some(TokenId(token.id));
+        data.recipient = Some(owner.unwrap_or(user.username));
+        notify::broadcast(&state, EventKind::TokenRevoked, data);
         Ok((StatusCode::OK, Json(serde_json::json!({ "status": "ok" }))))
     } else {
-        Ok((
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({ "errors": "Token not found" })),
-        ))
+        Err(AppError::NotFound("Token not found".to_string()))
     }
 }
 
 /// 验证通知 JWT Token
-pub fn verify_notify_token(token: &str) -> Result<TokenClaims, AppError> {
-    let secret = get_jwt_secret();
-
+pub fn verify_notify_token(config: &ServerConfig, token: &str) -> Result<TokenClaims, AppError> {
     // 创建严格的验证配置
     let mut validation = Validation::new(jsonwebtoken::Algorithm::HS256);
     validation.validate_exp = true; // 验证过期时间
@@ -197,7 +233,7 @@ pub fn verify_notify_token(token: &str) -> Result<TokenClaims, AppError> {
 
     let token_data = decode::<TokenClaims>(
         token,
-        &DecodingKey::from_secret(secret.as_ref()),
+        &DecodingKey::from_secret(config.jwt_key()),
         &validation,
     )
     .map_err(|e| {
@@ -231,27 +267,61 @@ pub fn extract_bearer_token(request: &Request) -> Result<BearerToken, AppError>
     Ok(BearerToken(token))
 }
 
+/// 校验通知 Token：验证 JWT、确认数据库中存在且未过期，并更新最后使用时间
+///
+/// 验证结果会缓存一段时间，缓存命中时不访问数据库。
+pub async fn authorize_notify_token(
+    state: &AppState,
+    token: &str,
+) -> Result<TokenClaims, AppError> {
+    // 验证 JWT
+    let claims = verify_notify_token(&state.config, token)?;
+
+    // 验证 token 是否在数据库中存在且未过期
+    let token_hash = generate_token_hash(token);
+    match state.token_cache.is_active(&state.db, &token_hash).await? {
+        TokenLookup::Cached => {}
+        TokenLookup::Loaded if state.read_only => {}
+        TokenLookup::Loaded => {
+            // 更新最后使用时间
+            token_ops::update_token_last_used(&state.db, &token_hash).await?;
+        }
+        TokenLookup::Missing => {
+            return Err(AppError::AuthError(
+                "Token not found or expired".to_string(),
+            ));
+        }
+    }
+
+    Ok(claims)
+}
+
 /// 通知Token授权中间件
+///
+/// open 模式下未携带 `Authorization` 头的请求按来源地址放行；携带 Token 时照常校验，
+/// 使 Token 的默认字段、签名公钥与归属在 open 模式下同样生效。
 pub async fn notify_token_middleware(
     State(state): State<Arc<AppState>>,
     mut request: Request,
     next: Next,
 ) -> Result<Response, AppError> {
-    let BearerToken(token) = extract_bearer_token(&request)?;
-
-    // 验证 JWT
-    let claims = verify_notify_token(&token)?;
-
-    // 验证 token 是否在数据库中存在且未过期
-    let token_hash = generate_token_hash(&token);
-    if !token_ops::verify_token_exists(&state.db, &token_hash).await? {
-        return Err(AppError::AuthError(
-            "Token not found or expired".to_string(),
-        ));
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    if !request.headers().contains_key(AUTHORIZATION) && state.auth_policy.allows_anonymous(peer) {
+        return Ok(next.run(request).await);
     }
 
-    // 更新最后使用时间
-    token_ops::update_token_last_used(&state.db, &token_hash).await?;
+    let claims = match extract_bearer_token(&request) {
+        Ok(BearerToken(token)) => authorize_notify_token(&state, &token).await,
+        Err(e) => Err(e),
+    }
+    .inspect_err(|e| {
+        state
+            .auth_failures
+            .record_error("notify_token", failures::peer(&request), e)
+    })?;
 
     // 将 claims 添加到请求扩展中，供后续处理使用
     request.extensions_mut().insert(claims);
@@ -260,20 +330,20 @@ pub async fn notify_token_middleware(
 }
 
 /// WebSocket 授权验证 (同步版本，仅验证JWT)
-pub fn verify_ws_token_jwt(token: &str) -> Result<TokenClaims, AppError> {
+pub fn verify_ws_token_jwt(config: &ServerConfig, token: &str) -> Result<TokenClaims, AppError> {
     // 验证 JWT
-    let claims = verify_notify_token(token)?;
+    let claims = verify_notify_token(config, token)?;
     Ok(claims)
 }
 
 /// WebSocket 授权验证 (完整版本，包含数据库验证)
 pub async fn verify_ws_token(token: &str, state: &AppState) -> Result<TokenClaims, AppError> {
     // 验证 JWT
-    let claims = verify_notify_token(token)?;
+    let claims = verify_notify_token(&state.config, token)?;
 
     // 验证 token 是否在数据库中存在且未过期
     let token_hash = generate_token_hash(token);
-    if !token_ops::verify_token_exists(&state.db, &token_hash).await? {
+    if state.token_cache.is_active(&state.db, &token_hash).await? == TokenLookup::Missing {
         return Err(AppError::AuthError(
             "Token not found or expired".to_string(),
         ));
@@ -285,5 +355,6 @@ pub async fn verify_ws_token(token: &str, state: &AppState) -> Result<TokenClaim
 /// 检查 Token 是否存在 (异步版本)
 pub async fn check_token_exists(token: &str, state: &AppState) -> Result<bool, AppError> {
     let token_hash = generate_token_hash(token);
-    token_ops::verify_token_exists(&state.db, &token_hash).await
+    let lookup = state.token_cache.is_active(&state.db, &token_hash).await?;
+    Ok(lookup != TokenLookup::Missing)
 }
@@ -0,0 +1,88 @@
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+
+use crate::db::notifies;
+use crate::db::users::Model as UserModel;
+use crate::error::AppError;
+use crate::services::auth::auth::{
+    BearerToken, authorize_notify_token, extract_bearer_token, generate_token_hash,
+};
+use crate::services::auth::failures;
+use crate::services::auth::user::{find_active_user, verify_user_jwt_token};
+use crate::state::AppState;
+
+/// 修改已发送通知的调用方
+#[derive(Debug, Clone)]
+pub(crate) enum NotifyEditor {
+    /// 已登录用户，可修改任意通知
+    User,
+    /// 通知 Token，只能修改自己发送的通知
+    Token { token_hash: String },
+}
+
+impl NotifyEditor {
+    /// 确认调用方可以修改这条通知
+    pub(crate) fn check(&self, notify: &notifies::Model) -> Result<(), AppError> {
+        match self {
+            NotifyEditor::User => Ok(()),
+            NotifyEditor::Token { token_hash }
+                if notify.token_hash.as_deref() == Some(token_hash.as_str()) =>
+            {
+                Ok(())
+            }
+            NotifyEditor::Token { .. } => Err(AppError::Forbidden(
+                "Notify was not sent with this token".to_string(),
+            )),
+        }
+    }
+
+    /// 批量操作只允许用户执行
+    pub(crate) fn require_user(&self) -> Result<(), AppError> {
+        match self {
+            NotifyEditor::User => Ok(()),
+            NotifyEditor::Token { .. } => {
+                Err(AppError::Forbidden("User login required".to_string()))
+            }
+        }
+    }
+}
+
+/// 修改通知的鉴权中间件，不论鉴权模式都要求用户 JWT 或通知 Token
+pub(crate) async fn notify_editor_middleware(
+    State(state): State<Arc<AppState>>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    // strict 模式下用户已由 user_auth_middleware 验证
+    let editor = if request.extensions().get::<UserModel>().is_some() {
+        NotifyEditor::User
+    } else {
+        let editor = match extract_bearer_token(&request) {
+            Ok(BearerToken(token)) => authorize_editor(&state, &token).await,
+            Err(e) => Err(e),
+        };
+        editor.inspect_err(|e| {
+            state
+                .auth_failures
+                .record_error("notify_editor", failures::peer(&request), e)
+        })?
+    };
+    request.extensions_mut().insert(editor);
+
+    Ok(next.run(request).await)
+}
+
+async fn authorize_editor(state: &Arc<AppState>, token: &str) -> Result<NotifyEditor, AppError> {
+    if let Ok(claims) = verify_user_jwt_token(&state.config, token) {
+        find_active_user(state, &claims).await?;
+        return Ok(NotifyEditor::User);
+    }
+    authorize_notify_token(state, token).await?;
+    Ok(NotifyEditor::Token {
+        token_hash: generate_token_hash(token),
+    })
+}
@@ -0,0 +1,223 @@
+use axum::{
+    Json,
+    extract::{Query, State},
+};
+use chrono::Utc;
+use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use rutify_core::{DEFAULT_PRIORITY, NotificationData};
+use sea_orm::{ActiveModelTrait, EntityTrait, IntoActiveModel, Set};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::bootstrap::config::ServerConfig;
+use crate::db::users::{Entity as Users, Model as UserModel};
+use crate::error::AppError;
+use crate::services::auth::user::{LoginRequest, find_user_by_username, verify_password};
+use crate::services::sinks::Sink;
+use crate::state::AppState;
+
+/// 验证链接的有效期
+const VERIFICATION_TTL_HOURS: i64 = 24;
+
+/// 邮箱验证链接中的 JWT Claims
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EmailVerificationClaims {
+    pub sub: String,        // User ID
+    pub email: String,      // 待验证的邮箱，修改邮箱后旧链接失效
+    pub iat: i64,           // 签发时间
+    pub exp: i64,           // 过期时间
+    pub token_type: String, // Token type (email_verify)
+}
+
+/// 验证邮箱请求
+#[derive(Debug, Deserialize)]
+pub struct VerifyEmailQuery {
+    pub token: String,
+}
+
+/// 签发邮箱验证 Token
+pub fn create_verification_token(
+    config: &ServerConfig,
+    user: &UserModel,
+) -> Result<String, AppError> {
+    let now = Utc::now();
+    let claims = EmailVerificationClaims {
+        sub: user.id.to_string(),
+        email: user.email.clone(),
+        iat: now.timestamp(),
+        exp: (now + chrono::Duration::hours(VERIFICATION_TTL_HOURS)).timestamp(),
+        token_type: "email_verify".to_string(),
+    };
+
+    let header = Header::new(jsonwebtoken::Algorithm::HS256);
+    encode(
+        &header,
+        &claims,
+        &EncodingKey::from_secret(config.jwt_key()),
+    )
+    .map_err(|e| {
+        error!("Failed to encode email verification token: {}", e);
+        AppError::AuthError("Failed to create verification token".to_string())
+    })
+}
+
+/// 校验邮箱验证 Token
+pub fn verify_verification_token(
+    config: &ServerConfig,
+    token: &str,
+) -> Result<EmailVerificationClaims, AppError> {
+    let validation = Validation::new(jsonwebtoken::Algorithm::HS256);
+    let token_data = decode::<EmailVerificationClaims>(
+        token,
+        &DecodingKey::from_secret(config.jwt_key()),
+        &validation,
+    )
+    .map_err(|e| {
+        warn!("Email verification token rejected: {}", e);
+        AppError::AuthError("Invalid or expired verification link".to_string())
+    })?;
+
+    if token_data.claims.token_type != "email_verify" {
+        return Err(AppError::AuthError("Invalid token type".to_string()));
+    }
+
+    Ok(token_data.claims)
+}
+
+/// 经通知转发所用的 SMTP 中继发送验证邮件
+pub async fn send_verification_email(
+    config: &ServerConfig,
+    user: &UserModel,
+) -> Result<(), AppError> {
+    let token = create_verification_token(config, user)?;
+    let link = format!(
+        "{}/auth/verify-email?token={}",
+        config.public_url.trim_end_matches('/'),
+        token
+    );
+    let data = NotificationData {
+        id: None,
+        notify: format!(
+            "Hi {},\n\nOpen the link below within {} hours to verify your email address:\n\n{}\n",
+            user.username, VERIFICATION_TTL_HOURS, link
+        ),
+        title: "Verify your rutify email address".to_string(),
+        device: "rutify".to_string(),
+        priority: DEFAULT_PRIORITY,
+        tags: Vec::new(),
+        recipient: None,
+        channel: None,
+        expires_at: None,
+        progress: None,
+        link_previews: Vec::new(),
+        signature: None,
+        correlation_id: None,
+        token_id: None,
+    };
+
+    Sink::Email
+        .deliver(config, &user.email, &data)
+        .await
+        .map_err(|e| {
+            error!("Failed to send verification email to {}: {}", user.email, e);
+            AppError::Upstream("Failed to send verification email".to_string())
+        })
+}
+
+/// 打开验证链接，标记邮箱已验证
+pub async fn verify_email(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<VerifyEmailQuery>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let claims = verify_verification_token(&state.config, &query.token)?;
+    let user_id: Uuid = claims
+        .sub
+        .parse()
+        .map_err(|_| AppError::AuthError("Invalid user ID".to_string()))?;
+
+    let user = Users::find_by_id(user_id)
+        .one(&state.db)
+        .await
+        .map_err(|e| {
+            error!("Database errors finding user: {}", e);
+            AppError::DatabaseError("Failed to find user".to_string())
+        })?
+        .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+    if user.email != claims.email {
+        return Err(AppError::AuthError(
+            "Verification link does not match the current email".to_string(),
+        ));
+    }
+
+    if !user.email_verified {
+        let username = user.username.clone();
+        let mut active = user.into_active_model();
+        active.email_verified = Set(true);
+        active.updated_at = Set(Utc::now().into());
+        active.update(&state.db).await.map_err(|e| {
+            error!("Failed to mark email verified: {}", e);
+            AppError::DatabaseError("Failed to verify email".to_string())
+        })?;
+        info!("Email verified for user: {}", username);
+    }
+
+    Ok(Json(serde_json::json!({ "status": "ok" })))
+}
+
+/// 重新发送验证邮件，需提供用户名与密码
+pub async fn resend_verification(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<LoginRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    let user = find_user_by_username(&state, &request.username)
+        .await?
+        .ok_or_else(|| AppError::AuthError("Invalid username or password".to_string()))?;
+    if !verify_password(&request.password, &user.password_hash)? {
+        return Err(AppError::AuthError(
+            "Invalid username or password".to_string(),
+        ));
+    }
+    if user.email_verified {
+        return Err(AppError::BadRequest("Email already verified".to_string()));
+    }
+
+    send_verification_email(&state.config, &user).await?;
+    info!("Verification email resent to user: {}", user.username);
+
+    Ok(Json(serde_json::json!({ "status": "ok" })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::config::ServerConfig;
+    use crate::db::users::UserRole;
+
+    #[test]
+    fn test_verification_token_roundtrip() {
+        let user = UserModel {
+            id: Uuid::new_v4(),
+            username: "alice".to_string(),
+            password_hash: String::new(),
+            email: "alice@example.com".to_string(),
+            role: UserRole::User,
+            email_verified: false,
+            disabled: false,
+            created_at: Utc::now().into(),
+            updated_at: Utc::now().into(),
+        };
+
+        let config = ServerConfig::for_tests();
+        let token = create_verification_token(&config, &user).unwrap();
+        let claims = verify_verification_token(&config, &token).unwrap();
+        assert_eq!(claims.sub, user.id.to_string());
+        assert_eq!(claims.email, "alice@example.com");
+
+        // 用户 JWT 不能当作验证链接使用
+        let user_jwt = crate::services::auth::user::create_user_jwt_token(&config, &user).unwrap();
+        assert!(verify_verification_token(&config, &user_jwt).is_err());
+    }
+}
@@ -0,0 +1,93 @@
+use axum::extract::{ConnectInfo, Request};
+use chrono::Utc;
+use rutify_core::AuthFailure;
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::error::AppError;
+
+/// 保留的最近失败记录数
+const RECENT_LIMIT: usize = 20;
+
+/// 认证失败记录，仅保存在内存中，重启后清空
+pub(crate) struct AuthFailureLog {
+    recent: Mutex<VecDeque<AuthFailure>>,
+    total: AtomicU64,
+}
+
+impl AuthFailureLog {
+    pub(crate) fn new() -> Self {
+        Self {
+            recent: Mutex::new(VecDeque::with_capacity(RECENT_LIMIT)),
+            total: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn record(&self, kind: &str, subject: Option<String>, reason: &str) {
+        self.total.fetch_add(1, Ordering::Relaxed);
+        let mut recent = self.recent.lock().unwrap();
+        if recent.len() == RECENT_LIMIT {
+            recent.pop_back();
+        }
+        recent.push_front(AuthFailure {
+            at: Utc::now(),
+            kind: kind.to_string(),
+            subject,
+            reason: reason.to_string(),
+        });
+    }
+
+    /// 只记录凭据无效的错误，数据库错误等不计入
+    pub(crate) fn record_error(&self, kind: &str, subject: Option<String>, error: &AppError) {
+        if let AppError::AuthError(reason) = error {
+            self.record(kind, subject, reason);
+        }
+    }
+
+    /// 最近的失败记录，新的在前
+    pub(crate) fn recent(&self) -> Vec<AuthFailure> {
+        self.recent.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// 启动以来的失败总数
+    pub(crate) fn total(&self) -> u64 {
+        self.total.load(Ordering::Relaxed)
+    }
+}
+
+/// 请求的来源地址，作为 Token 认证失败的记录对象
+pub(crate) fn peer(request: &Request) -> Option<String> {
+    request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auth_failure_log() {
+        let log = AuthFailureLog::new();
+        log.record_error("login", None, &AppError::NotFound("x".to_string()));
+        assert_eq!(log.total(), 0);
+
+        for i in 0..RECENT_LIMIT + 5 {
+            log.record(
+                "login",
+                Some(format!("user{i}")),
+                "Invalid username or password",
+            );
+        }
+        let recent = log.recent();
+        assert_eq!(log.total(), (RECENT_LIMIT + 5) as u64);
+        assert_eq!(recent.len(), RECENT_LIMIT);
+        assert_eq!(
+            recent[0].subject.as_deref(),
+            Some(format!("user{}", RECENT_LIMIT + 4).as_str())
+        );
+    }
+}
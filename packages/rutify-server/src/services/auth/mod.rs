@@ -1,2 +1,9 @@
 pub mod auth;
+pub(crate) mod editor;
+pub(crate) mod email_verification;
+pub(crate) mod failures;
+pub(crate) mod mode;
+pub(crate) mod registration_gate;
+pub(crate) mod token_cache;
 pub(crate) mod user;
+pub(crate) mod validation;
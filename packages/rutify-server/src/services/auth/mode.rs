@@ -0,0 +1,119 @@
+use anyhow::{Context, Result, bail};
+use std::net::IpAddr;
+
+use crate::bootstrap::source::ConfigSource;
+
+/// 鉴权模式，来自 `RUTIFY_AUTH_MODE`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum AuthMode {
+    /// `/notify` 与 WebSocket 不校验通知 Token，适合家庭内网
+    Open,
+    /// `/notify` 与 WebSocket 需要通知 Token，`/api` 的只读接口开放；修改与删除通知需要用户 JWT
+    /// 或发送该通知的 Token
+    #[default]
+    Token,
+    /// 在 `token` 基础上，`/api` 与 `/monitor` 也需要用户 JWT
+    Strict,
+}
+
+impl AuthMode {
+    fn parse(text: &str) -> Option<Self> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "open" => Some(Self::Open),
+            "token" => Some(Self::Token),
+            "strict" => Some(Self::Strict),
+            _ => None,
+        }
+    }
+}
+
+/// 各路由的鉴权策略
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct AuthPolicy {
+    pub(crate) mode: AuthMode,
+    /// open 模式下只放行私有网段与回环地址的请求
+    pub(crate) open_private_only: bool,
+}
+
+impl AuthPolicy {
+    /// 读取 `RUTIFY_AUTH_MODE`（`open`、`token`、`strict`，默认 `token`）
+    /// 与 `RUTIFY_AUTH_OPEN_PRIVATE_ONLY`（默认 `true`）
+    pub(crate) fn from_source(source: &ConfigSource) -> Result<Self> {
+        let mode = match source.var("RUTIFY_AUTH_MODE") {
+            Ok(text) => match AuthMode::parse(&text) {
+                Some(mode) => mode,
+                None => bail!("invalid RUTIFY_AUTH_MODE: {text}, expected open, token or strict"),
+            },
+            Err(_) => AuthMode::default(),
+        };
+        let open_private_only = match source.var("RUTIFY_AUTH_OPEN_PRIVATE_ONLY") {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid RUTIFY_AUTH_OPEN_PRIVATE_ONLY: {text}"))?,
+            Err(_) => true,
+        };
+
+        Ok(Self {
+            mode,
+            open_private_only,
+        })
+    }
+
+    /// 来自 `peer` 的通知与 WebSocket 请求是否免 Token
+    ///
+    /// 无法取得对端地址时按公网处理。
+    pub(crate) fn allows_anonymous(&self, peer: Option<IpAddr>) -> bool {
+        if self.mode != AuthMode::Open {
+            return false;
+        }
+        !self.open_private_only || peer.is_some_and(is_private)
+    }
+
+    /// `/api` 与 `/monitor` 是否需要用户 JWT
+    pub(crate) fn requires_user_auth(&self) -> bool {
+        self.mode == AuthMode::Strict
+    }
+}
+
+/// RFC 1918 私有网段、回环、链路本地以及 IPv6 唯一本地地址
+pub(crate) fn is_private(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => ip.is_private() || ip.is_loopback() || ip.is_link_local(),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_private(IpAddr::V4(ip)),
+            None => ip.is_loopback() || ip.is_unique_local() || ip.is_unicast_link_local(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allows_anonymous() {
+        let ip = |text: &str| Some(text.parse::<IpAddr>().unwrap());
+        let open = AuthPolicy {
+            mode: AuthMode::Open,
+            open_private_only: true,
+        };
+        assert!(open.allows_anonymous(ip("192.168.1.20")));
+        assert!(open.allows_anonymous(ip("10.0.0.1")));
+        assert!(open.allows_anonymous(ip("127.0.0.1")));
+        assert!(open.allows_anonymous(ip("::ffff:172.16.0.3")));
+        assert!(open.allows_anonymous(ip("fd12::1")));
+        assert!(!open.allows_anonymous(ip("8.8.8.8")));
+        assert!(!open.allows_anonymous(None));
+
+        let anywhere = AuthPolicy {
+            open_private_only: false,
+            ..open
+        };
+        assert!(anywhere.allows_anonymous(ip("8.8.8.8")));
+
+        assert!(!AuthPolicy::default().allows_anonymous(ip("127.0.0.1")));
+        assert_eq!(AuthMode::parse(" Strict "), Some(AuthMode::Strict));
+        assert_eq!(AuthMode::parse("none"), None);
+    }
+}
@@ -0,0 +1,278 @@
+use anyhow::{Context, Result, bail};
+use axum::{Json, extract::State};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use moka::future::Cache;
+use rutify_core::{ProofOfWorkSolution, RegistrationChallenge, pow};
+use serde::Deserialize;
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, warn};
+use uuid::Uuid;
+
+use crate::bootstrap::source::ConfigSource;
+use crate::error::AppError;
+use crate::state::AppState;
+
+/// 工作量证明题目的有效期，也是已用题目的记录时长
+const CHALLENGE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// 验证码服务请求的超时时间
+const CAPTCHA_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 验证码服务商
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CaptchaProvider {
+    Hcaptcha,
+    Turnstile,
+}
+
+impl CaptchaProvider {
+    fn verify_url(self) -> &'static str {
+        match self {
+            Self::Hcaptcha => "https://api.hcaptcha.com/siteverify",
+            Self::Turnstile => "https://challenges.cloudflare.com/turnstile/v0/siteverify",
+        }
+    }
+}
+
+/// 注册前的人机验证
+pub(crate) enum RegistrationGate {
+    None,
+    ProofOfWork {
+        difficulty: u8,
+        /// 已使用的题目，防止同一答案重复注册
+        used: Cache<String, ()>,
+    },
+    Captcha {
+        provider: CaptchaProvider,
+        site_key: String,
+        secret: String,
+        client: reqwest::Client,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct CaptchaVerifyResponse {
+    success: bool,
+}
+
+impl RegistrationGate {
+    /// 工作量证明，`difficulty` 为要求的前导零比特数
+    pub(crate) fn proof_of_work(difficulty: u8) -> Self {
+        Self::ProofOfWork {
+            difficulty,
+            used: Cache::builder().time_to_live(CHALLENGE_TTL).build(),
+        }
+    }
+
+    /// 读取 `RUTIFY_REGISTRATION_CHALLENGE`（`none`、`pow`、`hcaptcha`、`turnstile`，默认 `none`）；
+    /// `pow` 的难度来自 `RUTIFY_POW_DIFFICULTY`（默认 20），验证码需要
+    /// `RUTIFY_CAPTCHA_SITE_KEY` 与 `RUTIFY_CAPTCHA_SECRET`
+    pub(crate) fn from_source(source: &ConfigSource) -> Result<Self> {
+        let kind = source
+            .var("RUTIFY_REGISTRATION_CHALLENGE")
+            .unwrap_or_default();
+        let provider = match kind.trim().to_ascii_lowercase().as_str() {
+            "" | "none" => return Ok(Self::None),
+            "pow" => {
+                let difficulty: u8 = match source.var("RUTIFY_POW_DIFFICULTY") {
+                    Ok(text) => text
+                        .trim()
+                        .parse()
+                        .with_context(|| format!("invalid RUTIFY_POW_DIFFICULTY: {text}"))?,
+                    Err(_) => 20,
+                };
+                if difficulty > 32 {
+                    bail!("RUTIFY_POW_DIFFICULTY must be at most 32, got {difficulty}");
+                }
+                return Ok(Self::proof_of_work(difficulty));
+            }
+            "hcaptcha" => CaptchaProvider::Hcaptcha,
+            "turnstile" => CaptchaProvider::Turnstile,
+            _ => bail!(
+                "invalid RUTIFY_REGISTRATION_CHALLENGE: {kind}, expected none, pow, hcaptcha or turnstile"
+            ),
+        };
+
+        let site_key = source
+            .var("RUTIFY_CAPTCHA_SITE_KEY")
+            .context("RUTIFY_CAPTCHA_SITE_KEY is required for captcha registration")?;
+        let secret = source
+            .var("RUTIFY_CAPTCHA_SECRET")
+            .context("RUTIFY_CAPTCHA_SECRET is required for captcha registration")?;
+        let client = reqwest::Client::builder()
+            .timeout(CAPTCHA_TIMEOUT)
+            .build()
+            .context("failed to build captcha HTTP client")?;
+
+        Ok(Self::Captcha {
+            provider,
+            site_key,
+            secret,
+            client,
+        })
+    }
+
+    /// 发给客户端的题目；工作量证明题目为 `{过期时间}.{随机值}.{签名}`，以 `key` 签名，服务端无需保存
+    pub(crate) fn challenge(&self, key: &[u8]) -> RegistrationChallenge {
+        match self {
+            Self::None => RegistrationChallenge::None,
+            Self::ProofOfWork { difficulty, .. } => {
+                let expires = Utc::now().timestamp() + CHALLENGE_TTL.as_secs() as i64;
+                let payload = format!("{}.{}", expires, Uuid::new_v4().simple());
+                RegistrationChallenge::ProofOfWork {
+                    challenge: format!("{}.{}", payload, hex::encode(sign(key, &payload))),
+                    difficulty: *difficulty,
+                }
+            }
+            Self::Captcha {
+                provider: CaptchaProvider::Hcaptcha,
+                site_key,
+                ..
+            } => RegistrationChallenge::Hcaptcha {
+                site_key: site_key.clone(),
+            },
+            Self::Captcha {
+                provider: CaptchaProvider::Turnstile,
+                site_key,
+                ..
+            } => RegistrationChallenge::Turnstile {
+                site_key: site_key.clone(),
+            },
+        }
+    }
+
+    /// 校验注册请求携带的验证结果
+    pub(crate) async fn verify(
+        &self,
+        key: &[u8],
+        captcha_token: Option<&str>,
+        proof_of_work: Option<&ProofOfWorkSolution>,
+    ) -> Result<(), AppError> {
+        match self {
+            Self::None => Ok(()),
+            Self::ProofOfWork { difficulty, used } => {
+                let solution = proof_of_work.ok_or_else(|| {
+                    AppError::Forbidden("Proof of work required for registration".to_string())
+                })?;
+                if !challenge_is_valid(key, &solution.challenge)
+                    || !pow::verify(&solution.challenge, solution.nonce, *difficulty)
+                {
+                    return Err(AppError::Forbidden("Invalid proof of work".to_string()));
+                }
+                // 第一次写入才算有效，重复提交同一题目会被拒绝
+                let entry = used.entry(solution.challenge.clone()).or_insert(()).await;
+                if !entry.is_fresh() {
+                    return Err(AppError::Forbidden(
+                        "Proof of work already used".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+            Self::Captcha {
+                provider,
+                secret,
+                client,
+                ..
+            } => {
+                let token = captcha_token.ok_or_else(|| {
+                    AppError::Forbidden("Captcha required for registration".to_string())
+                })?;
+                let response = client
+                    .post(provider.verify_url())
+                    .form(&[("secret", secret.as_str()), ("response", token)])
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status())
+                    .map_err(|e| {
+                        error!("Captcha verification request failed: {}", e);
+                        AppError::Upstream("Captcha verification unavailable".to_string())
+                    })?;
+                let result: CaptchaVerifyResponse = response.json().await.map_err(|e| {
+                    error!("Invalid captcha verification response: {}", e);
+                    AppError::Upstream("Captcha verification unavailable".to_string())
+                })?;
+                if !result.success {
+                    warn!("Captcha verification rejected a registration");
+                    return Err(AppError::Forbidden(
+                        "Captcha verification failed".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn sign(key: &[u8], payload: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(payload.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// 题目由本服务签发且未过期
+fn challenge_is_valid(key: &[u8], challenge: &str) -> bool {
+    let Some((payload, signature)) = challenge.rsplit_once('.') else {
+        return false;
+    };
+    let Ok(signature) = hex::decode(signature) else {
+        return false;
+    };
+    let expires = payload
+        .split_once('.')
+        .and_then(|(expires, _)| expires.parse::<i64>().ok());
+    if expires.is_none_or(|expires| expires <= Utc::now().timestamp()) {
+        return false;
+    }
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(payload.as_bytes());
+    mac.verify_slice(&signature).is_ok()
+}
+
+/// 注册前获取人机验证题目
+pub async fn registration_challenge(State(state): State<Arc<AppState>>) -> Json<serde_json::Value> {
+    let challenge = state.registration_gate.challenge(state.config.jwt_key());
+    Json(serde_json::json!({ "status": "ok", "data": challenge }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_proof_of_work_gate() {
+        let key = b"registration-test-key";
+        let gate = RegistrationGate::proof_of_work(8);
+        let RegistrationChallenge::ProofOfWork {
+            challenge,
+            difficulty,
+        } = gate.challenge(key)
+        else {
+            panic!("expected a proof of work challenge");
+        };
+        assert_eq!(difficulty, 8);
+
+        assert!(gate.verify(key, None, None).await.is_err());
+
+        let solution = pow::solve(&challenge, difficulty);
+        assert!(gate.verify(key, None, Some(&solution)).await.is_ok());
+        // 同一题目不能重复使用
+        assert!(gate.verify(key, None, Some(&solution)).await.is_err());
+
+        // 自行编造的题目签名不匹配
+        let forged = pow::solve(&format!("{}.{}.00", i64::MAX, Uuid::new_v4()), 8);
+        assert!(gate.verify(key, None, Some(&forged)).await.is_err());
+        // 其他密钥签发的题目同样无效
+        let RegistrationChallenge::ProofOfWork { challenge, .. } = gate.challenge(b"other-key")
+        else {
+            panic!("expected a proof of work challenge");
+        };
+        let solution = pow::solve(&challenge, difficulty);
+        assert!(gate.verify(key, None, Some(&solution)).await.is_err());
+
+        assert!(RegistrationGate::None.verify(key, None, None).await.is_ok());
+    }
+}
@@ -0,0 +1,128 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use moka::future::Cache;
+use sea_orm::DatabaseConnection;
+use std::time::Duration;
+
+use crate::bootstrap::source::ConfigSource;
+use crate::db::token_ops;
+use crate::error::AppError;
+
+/// 已验证通知 Token 的缓存：哈希 → 过期时间
+///
+/// 命中时跳过数据库查询与最近使用时间的更新，因此 `last_used_at` 的精度约为一个 TTL。
+pub(crate) struct TokenCache {
+    cache: Option<Cache<String, DateTime<Utc>>>,
+}
+
+impl TokenCache {
+    /// `ttl` 为零时关闭缓存
+    pub(crate) fn new(ttl: Duration, capacity: u64) -> Self {
+        let cache = (!ttl.is_zero()).then(|| {
+            Cache::builder()
+                .max_capacity(capacity)
+                .time_to_live(ttl)
+                .build()
+        });
+        Self { cache }
+    }
+
+    /// 读取 `RUTIFY_TOKEN_CACHE_TTL_SECS`（默认 60，0 表示关闭）与 `RUTIFY_TOKEN_CACHE_CAPACITY`（默认 10000）
+    pub(crate) fn from_source(source: &ConfigSource) -> Result<Self> {
+        let ttl_secs: u64 = match source.var("RUTIFY_TOKEN_CACHE_TTL_SECS") {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid RUTIFY_TOKEN_CACHE_TTL_SECS: {text}"))?,
+            Err(_) => 60,
+        };
+        let capacity: u64 = match source.var("RUTIFY_TOKEN_CACHE_CAPACITY") {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid RUTIFY_TOKEN_CACHE_CAPACITY: {text}"))?,
+            Err(_) => 10_000,
+        };
+        Ok(Self::new(Duration::from_secs(ttl_secs), capacity))
+    }
+
+    /// 缓存中是否有该 Token 且尚未过期
+    pub(crate) async fn contains(&self, token_hash: &str) -> bool {
+        let Some(cache) = &self.cache else {
+            return false;
+        };
+        match cache.get(token_hash).await {
+            Some(expires_at) => expires_at > Utc::now(),
+            None => false,
+        }
+    }
+
+    pub(crate) async fn insert(&self, token_hash: String, expires_at: DateTime<Utc>) {
+        if let Some(cache) = &self.cache {
+            cache.insert(token_hash, expires_at).await;
+        }
+    }
+
+    /// Token 被删除后调用，避免已删除的 Token 在 TTL 内继续可用
+    pub(crate) fn invalidate_all(&self) {
+        if let Some(cache) = &self.cache {
+            cache.invalidate_all();
+        }
+    }
+
+    /// Token 是否存在且未过期，未命中时查询数据库并写入缓存
+    ///
+    /// 返回值表示是否访问了数据库，调用方据此决定是否更新最近使用时间。
+    pub(crate) async fn is_active(
+        &self,
+        db: &DatabaseConnection,
+        token_hash: &str,
+    ) -> Result<TokenLookup, AppError> {
+        if self.contains(token_hash).await {
+            return Ok(TokenLookup::Cached);
+        }
+        match token_ops::find_token(db, token_hash).await? {
+            Some(token) => {
+                self.insert(token_hash.to_string(), token.expires_at).await;
+                Ok(TokenLookup::Loaded)
+            }
+            None => Ok(TokenLookup::Missing),
+        }
+    }
+}
+
+/// [`TokenCache::is_active`] 的查询结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TokenLookup {
+    /// 缓存命中
+    Cached,
+    /// 从数据库读取并写入缓存
+    Loaded,
+    /// 不存在或已过期
+    Missing,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_token_cache() {
+        let cache = TokenCache::new(Duration::from_secs(60), 100);
+        let future = Utc::now() + chrono::Duration::hours(1);
+        let past = Utc::now() - chrono::Duration::seconds(1);
+
+        cache.insert("live".to_string(), future).await;
+        cache.insert("expired".to_string(), past).await;
+        assert!(cache.contains("live").await);
+        assert!(!cache.contains("expired").await);
+        assert!(!cache.contains("unknown").await);
+
+        cache.invalidate_all();
+        assert!(!cache.contains("live").await);
+
+        let disabled = TokenCache::new(Duration::ZERO, 100);
+        disabled.insert("live".to_string(), future).await;
+        assert!(!disabled.contains("live").await);
+    }
+}
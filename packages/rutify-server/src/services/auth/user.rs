@@ -6,20 +6,26 @@ use axum::{
     response::Response,
 };
 use bcrypt::{DEFAULT_COST, hash, verify};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation, decode, encode};
-use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+use rutify_core::ProofOfWorkSolution;
+use sea_orm::{ActiveModelTrait, EntityTrait, PaginatorTrait, Set};
 use sea_orm::{ColumnTrait, QueryFilter};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
+use crate::bootstrap::config::ServerConfig;
 use crate::db::tokens::{self, Entity as Tokens, TokenType};
 use crate::db::users::{
     self, ActiveModel as UserActiveModel, Entity as Users, Model as UserModel, UserRole,
 };
 use crate::error::AppError;
+use crate::response::with_legacy_fields;
+use crate::services::auth::email_verification::send_verification_email;
+use crate::services::auth::failures;
+use crate::services::auth::validation::validate_register;
 use crate::state::AppState;
 
 /// 用户登录请求
@@ -35,6 +41,12 @@ pub struct RegisterRequest {
     pub username: String,
     pub password: String,
     pub email: String,
+    /// hCaptcha / Turnstile 控件返回的 Token
+    #[serde(default)]
+    pub captcha_token: Option<String>,
+    /// 工作量证明答案
+    #[serde(default)]
+    pub proof_of_work: Option<ProofOfWorkSolution>,
 }
 
 /// 用户登录响应
@@ -45,7 +57,7 @@ pub struct LoginResponse {
     pub email: String,
     pub role: UserRole,
     pub jwt_token: String,
-    pub expires_at: String,
+    pub expires_at: DateTime<Utc>,
 }
 
 /// 用户信息响应
@@ -55,7 +67,8 @@ pub struct UserResponse {
     pub username: String,
     pub email: String,
     pub role: UserRole,
-    pub created_at: String,
+    pub email_verified: bool,
+    pub created_at: DateTime<Utc>,
 }
 
 /// 用户JWT Claims
@@ -130,8 +143,7 @@ pub fn verify_password(password: &str, hash: &str) -> Result<bool, AppError> {
 }
 
 /// 创建用户JWT Token
-pub fn create_user_jwt_token(user: &UserModel) -> Result<String, AppError> {
-    let secret = get_jwt_secret();
+pub fn create_user_jwt_token(config: &ServerConfig, user: &UserModel) -> Result<String, AppError> {
     let now = Utc::now();
     let expires_at = now + chrono::Duration::days(7); // 7天有效期
 
@@ -147,23 +159,26 @@ pub fn create_user_jwt_token(user: &UserModel) -> Result<String, AppError> {
 
     let header = Header::new(jsonwebtoken::Algorithm::HS256);
 
-    encode(&header, &claims, &EncodingKey::from_secret(secret.as_ref())).map_err(|e| {
+    encode(
+        &header,
+        &claims,
+        &EncodingKey::from_secret(config.jwt_key()),
+    )
+    .map_err(|e| {
         error!("Failed to encode user JWT: {}", e);
         AppError::AuthError("Failed to create user token".to_string())
     })
 }
 
 /// 验证用户JWT Token
-pub fn verify_user_jwt_token(token: &str) -> Result<UserClaims, AppError> {
-    let secret = get_jwt_secret();
-
+pub fn verify_user_jwt_token(config: &ServerConfig, token: &str) -> Result<UserClaims, AppError> {
     let mut validation = Validation::new(jsonwebtoken::Algorithm::HS256);
     validation.validate_exp = true;
     validation.leeway = 60;
 
     let token_data = decode::<UserClaims>(
         token,
-        &DecodingKey::from_secret(secret.as_ref()),
+        &DecodingKey::from_secret(config.jwt_key()),
         &validation,
     )
     .map_err(|e| {
@@ -179,23 +194,13 @@ pub fn verify_user_jwt_token(token: &str) -> Result<UserClaims, AppError> {
     Ok(token_data.claims)
 }
 
-/// JWT 密钥
-fn get_jwt_secret() -> String {
-    let secret = std::env::var("RUTIFY_JWT_SECRET").unwrap_or_else(|_| {
-        warn!("Using default JWT secret. Please set RUTIFY_JWT_SECRET environment variable in production!");
-        "rutify_default_jwt_secret_change_in_production".to_string()
-    });
-
-    if secret.len() < 32 {
-        error!("JWT secret is too short (minimum 32 characters required)");
-        panic!("JWT secret must be at least 32 characters long");
-    }
-
-    secret
-}
+/// 未设置 `RUTIFY_JWT_SECRET` 时使用的密钥，启动时会给出警告
+pub(crate) const DEFAULT_JWT_SECRET: &str = "rutify_default_jwt_secret_change_in_production";
+/// JWT 密钥的最小长度
+pub(crate) const MIN_JWT_SECRET_LEN: usize = 32;
 
 /// 查找用户的辅助函数
-async fn find_user_by_username(
+pub(crate) async fn find_user_by_username(
     state: &Arc<AppState>,
     username: &str,
 ) -> Result<Option<UserModel>, AppError> {
@@ -230,7 +235,8 @@ fn create_user_response(user: &UserModel) -> UserResponse {
         username: user.username.clone(),
         email: user.email.clone(),
         role: user.role.clone(),
-        created_at: user.created_at.to_string(),
+        email_verified: user.email_verified,
+        created_at: user.created_at.with_timezone(&Utc),
     }
 }
 
@@ -238,7 +244,17 @@ fn create_user_response(user: &UserModel) -> UserResponse {
 pub async fn register_user(
     State(state): State<Arc<AppState>>,
     Json(request): Json<RegisterRequest>,
-) -> Result<Json<UserResponse>, AppError> {
+) -> Result<Json<serde_json::Value>, AppError> {
+    state
+        .registration_gate
+        .verify(
+            state.config.jwt_key(),
+            request.captcha_token.as_deref(),
+            request.proof_of_work.as_ref(),
+        )
+        .await?;
+    validate_register(&request, &state.password_policy)?;
+
     // 检查用户名是否已存在
     let existing_user = find_user_by_username(&state, &request.username).await?;
 
@@ -249,13 +265,29 @@ pub async fn register_user(
     // 哈希密码
     let password_hash = hash_password(&request.password)?;
 
+    // 第一个注册的用户自动成为管理员，便于初始化实例
+    let user_count = Users::find().count(&state.db).await.map_err(|e| {
+        error!("Database errors counting users: {}", e);
+        AppError::DatabaseError("Failed to count users".to_string())
+    })?;
+    let role = if user_count == 0 {
+        UserRole::Admin
+    } else {
+        UserRole::User
+    };
+
+    // 管理员无需验证邮箱，避免未配置邮件中继时无法初始化实例
+    let require_verification = state.config.require_email_verification && role != UserRole::Admin;
+
     // 创建用户
     let new_user = UserActiveModel {
         id: Set(Uuid::new_v4()),
         username: Set(request.username.clone()),
         password_hash: Set(password_hash),
         email: Set(request.email.clone()),
-        role: Set(UserRole::User), // 默认为普通用户
+        role: Set(role),
+        email_verified: Set(!require_verification),
+        disabled: Set(false),
         created_at: Set(Utc::now().into()),
         updated_at: Set(Utc::now().into()),
     };
@@ -267,49 +299,70 @@ pub async fn register_user(
 
     info!("User registered successfully: {}", user.username);
 
-    Ok(Json(create_user_response(&user)))
+    // 发送失败不影响注册，用户可通过重发接口再次获取验证邮件
+    if require_verification && let Err(e) = send_verification_email(&state.config, &user).await {
+        warn!("Verification email not sent for {}: {}", user.username, e);
+    }
+
+    with_legacy_fields(create_user_response(&user))
 }
 
 /// 用户登录
 pub async fn login_user(
     State(state): State<Arc<AppState>>,
     Json(request): Json<LoginRequest>,
-) -> Result<Json<LoginResponse>, AppError> {
+) -> Result<Json<serde_json::Value>, AppError> {
     // 查找用户
     let user = find_user_by_username(&state, &request.username).await?;
 
-    let user =
-        user.ok_or_else(|| AppError::AuthError("Invalid username or password".to_string()))?;
-
     // 验证密码
-    let is_valid = verify_password(&request.password, &user.password_hash)?;
-    if !is_valid {
-        return Err(AppError::AuthError(
-            "Invalid username or password".to_string(),
+    let is_valid = match &user {
+        Some(user) => verify_password(&request.password, &user.password_hash)?,
+        None => false,
+    };
+    let user = match user {
+        Some(user) if is_valid => user,
+        _ => {
+            let reason = "Invalid username or password";
+            state
+                .auth_failures
+                .record("login", Some(request.username.clone()), reason);
+            return Err(AppError::AuthError(reason.to_string()));
+        }
+    };
+
+    if user.disabled {
+        warn!("Disabled user attempted to log in: {}", user.username);
+        return Err(AppError::Forbidden("Account disabled".to_string()));
+    }
+
+    if state.config.require_email_verification && !user.email_verified {
+        return Err(AppError::Forbidden(
+            "Email address not verified".to_string(),
         ));
     }
 
     // 创建JWT token
-    let jwt_token = create_user_jwt_token(&user)?;
+    let jwt_token = create_user_jwt_token(&state.config, &user)?;
     let expires_at = Utc::now() + chrono::Duration::days(7);
 
     info!("User logged in successfully: {}", user.username);
 
-    Ok(Json(LoginResponse {
+    with_legacy_fields(LoginResponse {
         user_id: user.id,
         username: user.username,
         email: user.email,
         role: user.role,
         jwt_token,
-        expires_at: expires_at.to_string(),
-    }))
+        expires_at,
+    })
 }
 
 /// 获取用户信息
 pub async fn get_user_profile(
     State(state): State<Arc<AppState>>,
     Extension(claims): Extension<UserClaims>,
-) -> Result<Json<UserResponse>, AppError> {
+) -> Result<Json<serde_json::Value>, AppError> {
     let user_id: Uuid = claims
         .sub
         .parse()
@@ -319,7 +372,26 @@ pub async fn get_user_profile(
 
     let user = user.ok_or_else(|| AppError::AuthError("User not found".to_string()))?;
 
-    Ok(Json(create_user_response(&user)))
+    with_legacy_fields(create_user_response(&user))
+}
+
+/// 验证 JWT 对应的用户仍然存在且未被禁用
+pub(crate) async fn find_active_user(
+    state: &Arc<AppState>,
+    claims: &UserClaims,
+) -> Result<UserModel, AppError> {
+    let user_id: Uuid = claims
+        .sub
+        .parse()
+        .map_err(|_| AppError::AuthError("Invalid user ID".to_string()))?;
+
+    let user = find_user_by_id(state, user_id).await?;
+
+    let user = user.ok_or_else(|| AppError::AuthError("User not found".to_string()))?;
+    if user.disabled {
+        return Err(AppError::Forbidden("Account disabled".to_string()));
+    }
+    Ok(user)
 }
 
 /// 用户认证中间件
@@ -328,9 +400,33 @@ pub async fn user_auth_middleware(
     mut request: Request,
     next: Next,
 ) -> Result<Response, AppError> {
-    let UserJwt(claims) = extract_user_jwt(&request)?;
+    let UserJwt(claims) = extract_user_jwt(&state.config, &request).inspect_err(|e| {
+        state
+            .auth_failures
+            .record_error("user_token", failures::peer(&request), e)
+    })?;
+
+    let user = find_active_user(&state, &claims).await?;
+
+    // 将用户信息和claims添加到请求扩展中
+    request.extensions_mut().insert(user);
+    request.extensions_mut().insert(claims);
+
+    Ok(next.run(request).await)
+}
+
+/// 管理员认证中间件
+pub async fn admin_auth_middleware(
+    State(state): State<Arc<AppState>>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let UserJwt(claims) = extract_user_jwt(&state.config, &request).inspect_err(|e| {
+        state
+            .auth_failures
+            .record_error("user_token", failures::peer(&request), e)
+    })?;
 
-    // 验证用户是否仍然存在且活跃
     let user_id: Uuid = claims
         .sub
         .parse()
@@ -340,7 +436,15 @@ pub async fn user_auth_middleware(
 
     let user = user.ok_or_else(|| AppError::AuthError("User not found".to_string()))?;
 
-    // 将用户信息和claims添加到请求扩展中
+    // 以数据库中的角色为准，避免旧 JWT 中的角色信息过期
+    if user.disabled {
+        return Err(AppError::Forbidden("Account disabled".to_string()));
+    }
+    if user.role != UserRole::Admin {
+        warn!("Non-admin user attempted admin access: {}", user.username);
+        return Err(AppError::Forbidden("Admin role required".to_string()));
+    }
+
     request.extensions_mut().insert(user);
     request.extensions_mut().insert(claims);
 
@@ -348,7 +452,7 @@ pub async fn user_auth_middleware(
 }
 
 /// 从请求中提取用户JWT
-pub fn extract_user_jwt(request: &Request) -> Result<UserJwt, AppError> {
+pub fn extract_user_jwt(config: &ServerConfig, request: &Request) -> Result<UserJwt, AppError> {
     let auth_header = request
         .headers()
         .get(AUTHORIZATION)
@@ -362,7 +466,7 @@ pub fn extract_user_jwt(request: &Request) -> Result<UserJwt, AppError> {
     }
 
     let token = auth_header.trim_start_matches("Bearer ");
-    let claims = verify_user_jwt_token(token)?;
+    let claims = verify_user_jwt_token(config, token)?;
 
     Ok(UserJwt(claims))
 }
@@ -0,0 +1,266 @@
+use anyhow::{Context, Result};
+use axum::{Json, extract::State};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::bootstrap::source::ConfigSource;
+use crate::error::{AppError, FieldError};
+use crate::services::auth::user::RegisterRequest;
+use crate::state::AppState;
+
+const USERNAME_MIN_LEN: usize = 3;
+const USERNAME_MAX_LEN: usize = 32;
+/// RFC 5321 对地址总长与本地部分的限制
+const EMAIL_MAX_LEN: usize = 254;
+const EMAIL_LOCAL_MAX_LEN: usize = 64;
+
+/// 密码策略
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PasswordPolicy {
+    pub(crate) min_length: usize,
+    /// 估算熵的下限（比特），0 表示不检查
+    pub(crate) min_entropy_bits: f64,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            min_entropy_bits: 0.0,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// 读取 `RUTIFY_PASSWORD_MIN_LENGTH`（默认 8）与 `RUTIFY_PASSWORD_MIN_ENTROPY`（默认 0，不检查）
+    pub(crate) fn from_source(source: &ConfigSource) -> Result<Self> {
+        let defaults = Self::default();
+        let min_length = match source.var("RUTIFY_PASSWORD_MIN_LENGTH") {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid RUTIFY_PASSWORD_MIN_LENGTH: {text}"))?,
+            Err(_) => defaults.min_length,
+        };
+        let min_entropy_bits = match source.var("RUTIFY_PASSWORD_MIN_ENTROPY") {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid RUTIFY_PASSWORD_MIN_ENTROPY: {text}"))?,
+            Err(_) => defaults.min_entropy_bits,
+        };
+
+        Ok(Self {
+            min_length,
+            min_entropy_bits,
+        })
+    }
+
+    /// 评估密码强度并列出不满足策略的原因
+    pub(crate) fn evaluate(&self, password: &str) -> PasswordStrength {
+        let length = password.chars().count();
+        let entropy_bits = estimate_entropy(password);
+
+        let mut problems = Vec::new();
+        if length < self.min_length {
+            problems.push(format!("must be at least {} characters", self.min_length));
+        }
+        if entropy_bits < self.min_entropy_bits {
+            problems.push(format!(
+                "is too predictable ({:.0} bits, {:.0} required); mix cases, digits and symbols or use a longer passphrase",
+                entropy_bits, self.min_entropy_bits
+            ));
+        }
+
+        let score = match entropy_bits {
+            bits if bits < 28.0 => 0,
+            bits if bits < 36.0 => 1,
+            bits if bits < 60.0 => 2,
+            bits if bits < 128.0 => 3,
+            _ => 4,
+        };
+
+        PasswordStrength {
+            length,
+            entropy_bits: (entropy_bits * 10.0).round() / 10.0,
+            score,
+            acceptable: problems.is_empty(),
+            problems,
+        }
+    }
+}
+
+/// 密码强度评估结果
+#[derive(Debug, Serialize)]
+pub(crate) struct PasswordStrength {
+    pub(crate) length: usize,
+    pub(crate) entropy_bits: f64,
+    /// 0（很弱）到 4（很强）
+    pub(crate) score: u8,
+    /// 是否满足服务端密码策略
+    pub(crate) acceptable: bool,
+    pub(crate) problems: Vec<String>,
+}
+
+/// 按出现的字符类别估算暴力破解熵：长度 × log2(字符集大小)
+fn estimate_entropy(password: &str) -> f64 {
+    let mut pool = 0u32;
+    if password.chars().any(|c| c.is_ascii_lowercase()) {
+        pool += 26;
+    }
+    if password.chars().any(|c| c.is_ascii_uppercase()) {
+        pool += 26;
+    }
+    if password.chars().any(|c| c.is_ascii_digit()) {
+        pool += 10;
+    }
+    if password
+        .chars()
+        .any(|c| c.is_ascii_punctuation() || c == ' ')
+    {
+        pool += 33;
+    }
+    if !password.is_ascii() {
+        pool += 100;
+    }
+    if pool == 0 {
+        return 0.0;
+    }
+    password.chars().count() as f64 * f64::from(pool).log2()
+}
+
+/// 用户名：3–32 个字符，仅字母、数字、`_`、`-`、`.`，且以字母或数字开头
+fn validate_username(username: &str) -> Option<String> {
+    let length = username.chars().count();
+    if !(USERNAME_MIN_LEN..=USERNAME_MAX_LEN).contains(&length) {
+        return Some(format!(
+            "must be {USERNAME_MIN_LEN}-{USERNAME_MAX_LEN} characters"
+        ));
+    }
+    if !username.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+        return Some("must start with a letter or digit".to_string());
+    }
+    if !username
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    {
+        return Some("may only contain letters, digits, '_', '-' and '.'".to_string());
+    }
+    None
+}
+
+/// 邮箱：`local@domain` 形式，域名至少包含两个标签
+fn validate_email(email: &str) -> Option<String> {
+    let invalid = || Some("must be a valid email address".to_string());
+    if email.len() > EMAIL_MAX_LEN || email.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return invalid();
+    }
+    let Some((local, domain)) = email.rsplit_once('@') else {
+        return invalid();
+    };
+    if local.is_empty()
+        || local.len() > EMAIL_LOCAL_MAX_LEN
+        || local.contains('@')
+        || local.starts_with('.')
+        || local.ends_with('.')
+        || local.contains("..")
+    {
+        return invalid();
+    }
+
+    let labels: Vec<&str> = domain.split('.').collect();
+    let label_ok = |label: &&str| {
+        !label.is_empty()
+            && label.len() <= 63
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+            && label.chars().all(|c| c.is_alphanumeric() || c == '-')
+    };
+    if labels.len() < 2 || !labels.iter().all(label_ok) {
+        return invalid();
+    }
+    None
+}
+
+/// 校验注册请求，所有字段的问题一并返回
+pub(crate) fn validate_register(
+    request: &RegisterRequest,
+    policy: &PasswordPolicy,
+) -> Result<(), AppError> {
+    let mut errors = Vec::new();
+    if let Some(message) = validate_username(&request.username) {
+        errors.push(FieldError::new("username", message));
+    }
+    if let Some(message) = validate_email(&request.email) {
+        errors.push(FieldError::new("email", message));
+    }
+    for problem in policy.evaluate(&request.password).problems {
+        errors.push(FieldError::new("password", problem));
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::Validation(errors))
+    }
+}
+
+/// 密码强度预检请求
+#[derive(Debug, Deserialize)]
+pub struct PasswordStrengthRequest {
+    pub password: String,
+}
+
+/// 供界面在注册前预检密码强度
+pub async fn password_strength(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<PasswordStrengthRequest>,
+) -> Json<serde_json::Value> {
+    let strength = state.password_policy.evaluate(&request.password);
+    Json(serde_json::json!({ "status": "ok", "data": strength }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_validation() {
+        assert_eq!(validate_username("alice_01"), None);
+        assert!(validate_username("al").is_some());
+        assert!(validate_username("_alice").is_some());
+        assert!(validate_username("alice smith").is_some());
+
+        assert_eq!(validate_email("alice@example.com"), None);
+        assert_eq!(validate_email("a.b+tag@mail.example.org"), None);
+        assert!(validate_email("alice").is_some());
+        assert!(validate_email("alice@localhost").is_some());
+        assert!(validate_email("alice@@example.com").is_some());
+        assert!(validate_email("alice@-example.com").is_some());
+        assert!(validate_email("al ice@example.com").is_some());
+
+        let policy = PasswordPolicy {
+            min_length: 8,
+            min_entropy_bits: 40.0,
+        };
+        let weak = policy.evaluate("abcdefgh");
+        assert!(!weak.acceptable);
+        assert_eq!(weak.problems.len(), 1);
+        assert!(policy.evaluate("Tr0ub4dor&3").acceptable);
+
+        let request = RegisterRequest {
+            username: "x".to_string(),
+            password: "short".to_string(),
+            email: "nope".to_string(),
+            captcha_token: None,
+            proof_of_work: None,
+        };
+        match validate_register(&request, &policy) {
+            Err(AppError::Validation(errors)) => {
+                let fields: Vec<&str> = errors.iter().map(|error| error.field).collect();
+                assert_eq!(fields, ["username", "email", "password", "password"]);
+            }
+            other => panic!("expected validation errors, got {other:?}"),
+        }
+    }
+}
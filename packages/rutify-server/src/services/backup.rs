@@ -0,0 +1,213 @@
+use anyhow::{Context, Result, bail};
+use chrono::Utc;
+use sea_orm::{ConnectionTrait, DatabaseConnection, DbBackend};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::info;
+
+/// 备份包内的清单文件
+const MANIFEST_ENTRY: &str = "manifest.json";
+/// SQLite 数据库快照
+const SQLITE_ENTRY: &str = "rutify.db";
+/// `pg_dump --format=custom` 导出的 PostgreSQL 数据
+const POSTGRES_ENTRY: &str = "rutify.pgdump";
+
+/// 当前备份格式版本，格式不兼容时递增
+const FORMAT_VERSION: u32 = 1;
+
+const ZSTD_LEVEL: i32 = 3;
+
+/// 备份清单，恢复时用于校验格式与数据库类型
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    format_version: u32,
+    server_version: String,
+    backend: String,
+    created_at: chrono::DateTime<Utc>,
+}
+
+/// 生成 `.tar.zst` 备份：数据库快照与清单
+///
+/// SQLite 使用 `VACUUM INTO` 在线生成一致的快照，PostgreSQL 调用 `pg_dump`。
+pub(crate) async fn create_backup(db: &DatabaseConnection, db_url: &str, out: &Path) -> Result<()> {
+    let workdir = tempfile::tempdir().context("failed to create temporary directory")?;
+    let backend = db.get_database_backend();
+    let (entry, snapshot) = match backend {
+        DbBackend::Sqlite => {
+            let snapshot = workdir.path().join(SQLITE_ENTRY);
+            let target = snapshot.to_string_lossy().replace('\'', "''");
+            db.execute_unprepared(&format!("VACUUM INTO '{target}'"))
+                .await
+                .context("failed to snapshot sqlite database")?;
+            (SQLITE_ENTRY, snapshot)
+        }
+        DbBackend::Postgres => {
+            let snapshot = workdir.path().join(POSTGRES_ENTRY);
+            let mut command = Command::new("pg_dump");
+            command
+                .arg("--format=custom")
+                .arg("--file")
+                .arg(&snapshot)
+                .arg(db_url);
+            tokio::task::spawn_blocking(move || run_tool(&mut command))
+                .await
+                .context("pg_dump task panicked")??;
+            (POSTGRES_ENTRY, snapshot)
+        }
+        other => bail!("backup is not supported for {other:?} databases"),
+    };
+
+    let manifest = Manifest {
+        format_version: FORMAT_VERSION,
+        server_version: env!("CARGO_PKG_VERSION").to_string(),
+        backend: backend_name(backend).to_string(),
+        created_at: Utc::now(),
+    };
+    let out = out.to_path_buf();
+    tokio::task::spawn_blocking(move || write_archive(&out, &manifest, entry, &snapshot))
+        .await
+        .context("backup task panicked")??;
+
+    Ok(())
+}
+
+fn write_archive(out: &Path, manifest: &Manifest, entry: &str, snapshot: &Path) -> Result<()> {
+    let file = File::create(out).with_context(|| format!("failed to create {}", out.display()))?;
+    let encoder = zstd::Encoder::new(file, ZSTD_LEVEL)?;
+    let mut archive = tar::Builder::new(encoder);
+
+    let manifest = serde_json::to_vec_pretty(manifest)?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(Utc::now().timestamp().max(0) as u64);
+    header.set_cksum();
+    archive.append_data(&mut header, MANIFEST_ENTRY, manifest.as_slice())?;
+    archive.append_path_with_name(snapshot, entry)?;
+
+    archive.into_inner()?.finish()?.sync_all()?;
+    info!("Backup written to {}", out.display());
+    Ok(())
+}
+
+/// 从备份恢复数据库，需在服务停止时执行
+///
+/// SQLite 直接替换数据库文件（并删除遗留的 WAL 文件），PostgreSQL 调用 `pg_restore --clean`。
+pub(crate) fn restore_backup(archive: &Path, db_url: &str) -> Result<()> {
+    let workdir = tempfile::tempdir().context("failed to create temporary directory")?;
+    let file =
+        File::open(archive).with_context(|| format!("failed to open {}", archive.display()))?;
+    tar::Archive::new(zstd::Decoder::new(file)?)
+        .unpack(workdir.path())
+        .context("failed to unpack backup archive")?;
+
+    let manifest: Manifest = serde_json::from_slice(
+        &std::fs::read(workdir.path().join(MANIFEST_ENTRY))
+            .context("backup archive has no manifest")?,
+    )?;
+    if manifest.format_version > FORMAT_VERSION {
+        bail!(
+            "backup format {} is newer than supported version {}",
+            manifest.format_version,
+            FORMAT_VERSION
+        );
+    }
+
+    let backend = url_backend(db_url)?;
+    if manifest.backend != backend_name(backend) {
+        bail!(
+            "backup was taken from a {} database but RUTIFY_DB_URL points to {}",
+            manifest.backend,
+            backend_name(backend)
+        );
+    }
+
+    match backend {
+        DbBackend::Sqlite => {
+            let target = sqlite_path(db_url)?;
+            for suffix in ["-wal", "-shm"] {
+                let mut stale = target.clone().into_os_string();
+                stale.push(suffix);
+                let _ = std::fs::remove_file(stale);
+            }
+            std::fs::copy(workdir.path().join(SQLITE_ENTRY), &target)
+                .with_context(|| format!("failed to write {}", target.display()))?;
+        }
+        _ => run_tool(
+            Command::new("pg_restore")
+                .args(["--clean", "--if-exists", "--no-owner", "--dbname"])
+                .arg(db_url)
+                .arg(workdir.path().join(POSTGRES_ENTRY)),
+        )?,
+    }
+
+    info!(
+        "Restored backup created at {} by rutify-server {}",
+        manifest.created_at, manifest.server_version
+    );
+    Ok(())
+}
+
+fn backend_name(backend: DbBackend) -> &'static str {
+    match backend {
+        DbBackend::Sqlite => "sqlite",
+        DbBackend::Postgres => "postgres",
+        _ => "unsupported",
+    }
+}
+
+fn url_backend(db_url: &str) -> Result<DbBackend> {
+    if db_url.starts_with("sqlite:") {
+        Ok(DbBackend::Sqlite)
+    } else if db_url.starts_with("postgres://") || db_url.starts_with("postgresql://") {
+        Ok(DbBackend::Postgres)
+    } else {
+        bail!("restore is not supported for database url {db_url}")
+    }
+}
+
+/// `sqlite://rutify.db?mode=rwc` 对应的文件路径
+pub(crate) fn sqlite_path(db_url: &str) -> Result<PathBuf> {
+    let path = db_url
+        .trim_start_matches("sqlite:")
+        .trim_start_matches("//")
+        .split('?')
+        .next()
+        .unwrap_or_default();
+    if path.is_empty() || path == ":memory:" {
+        bail!("cannot restore into in-memory sqlite database {db_url}");
+    }
+    Ok(PathBuf::from(path))
+}
+
+fn run_tool(command: &mut Command) -> Result<()> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let status = command
+        .status()
+        .with_context(|| format!("failed to run {program}, is it installed?"))?;
+    if !status.success() {
+        bail!("{program} exited with {status}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sqlite_path() {
+        assert_eq!(
+            sqlite_path("sqlite://rutify.db?mode=rwc").unwrap(),
+            PathBuf::from("rutify.db")
+        );
+        assert_eq!(
+            sqlite_path("sqlite:///var/lib/rutify/rutify.db").unwrap(),
+            PathBuf::from("/var/lib/rutify/rutify.db")
+        );
+        assert!(sqlite_path("sqlite::memory:").is_err());
+        assert!(url_backend("mysql://localhost/rutify").is_err());
+    }
+}
@@ -0,0 +1,248 @@
+use chrono::{DateTime, Utc};
+use rutify_core::{CheckResult, CheckStatus, NotificationInput};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::db::{check_results, checks};
+use crate::error::AppError;
+use crate::services::notify;
+use crate::state::AppState;
+
+/// 调度循环的间隔，也是检查间隔的最小精度
+const TICK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 检查历史的保留时长
+const HISTORY_RETENTION_DAYS: i64 = 7;
+
+/// 允许配置的最短检查间隔与最长超时
+pub(crate) const MIN_INTERVAL_SECS: u64 = 10;
+pub(crate) const MAX_TIMEOUT_SECS: u64 = 60;
+
+/// 状态变化通知的设备名
+const CHECK_DEVICE: &str = "rutify-check";
+
+/// 检查地址只允许 http 与 https
+pub(crate) fn validate_url(url: &str) -> Result<(), AppError> {
+    match reqwest::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => Ok(()),
+        _ => Err(AppError::BadRequest(
+            "url must be an absolute http or https URL".to_string(),
+        )),
+    }
+}
+
+/// 根据状态码判断是否正常，返回不正常的原因
+fn evaluate(status_code: u16, expected: Option<u16>) -> Result<(), String> {
+    match expected {
+        Some(expected) if status_code != expected => {
+            Err(format!("expected status {expected}, got {status_code}"))
+        }
+        None if !(200..300).contains(&status_code) => {
+            Err(format!("unexpected status {status_code}"))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// 是否到了下一次检查的时间
+fn is_due(check: &checks::Model, now: DateTime<Utc>) -> bool {
+    match check.last_checked_at {
+        Some(at) => at + chrono::Duration::seconds(check.interval_secs) <= now,
+        None => true,
+    }
+}
+
+/// 请求一次检查地址
+async fn probe(client: &reqwest::Client, check: &checks::Model) -> CheckResult {
+    let checked_at = Utc::now();
+    let started = Instant::now();
+    let timeout = Duration::from_secs(check.timeout_secs.clamp(1, MAX_TIMEOUT_SECS as i64) as u64);
+    let response = client.get(&check.url).timeout(timeout).send().await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    match response {
+        Ok(response) => {
+            let status_code = response.status().as_u16();
+            let expected = check
+                .expected_status
+                .and_then(|code| u16::try_from(code).ok());
+            let error = evaluate(status_code, expected).err();
+            CheckResult {
+                checked_at,
+                up: error.is_none(),
+                status_code: Some(status_code),
+                latency_ms,
+                error,
+            }
+        }
+        Err(e) => CheckResult {
+            checked_at,
+            up: false,
+            status_code: None,
+            latency_ms,
+            error: Some(if e.is_timeout() {
+                format!("timed out after {}s", timeout.as_secs())
+            } else {
+                e.to_string()
+            }),
+        },
+    }
+}
+
+fn transition_input(check: &checks::Model, result: &CheckResult) -> NotificationInput {
+    let (title, notify) = if result.up {
+        (
+            format!("{} is up", check.name),
+            format!("{} responded in {} ms", check.url, result.latency_ms),
+        )
+    } else {
+        (
+            format!("{} is down", check.name),
+            format!(
+                "{}: {}",
+                check.url,
+                result.error.as_deref().unwrap_or("check failed")
+            ),
+        )
+    };
+    NotificationInput {
+        notify,
+        title: Some(title),
+        device: Some(CHECK_DEVICE.to_string()),
+        priority: u8::try_from(check.priority).ok(),
+        tags: vec!["check".to_string()],
+        channel: check.channel.clone(),
+        ttl_secs: None,
+        progress: None,
+        signature: None,
+        correlation_id: None,
+    }
+}
+
+/// 执行一次检查，保存结果并在状态变化时通知
+pub(crate) async fn run_check(
+    state: &AppState,
+    client: &reqwest::Client,
+    check: checks::Model,
+) -> Result<CheckResult, AppError> {
+    let result = probe(client, &check).await;
+
+    check_results::ActiveModel {
+        check_id: Set(check.id),
+        checked_at: Set(result.checked_at),
+        up: Set(result.up),
+        status_code: Set(result.status_code.map(i32::from)),
+        latency_ms: Set(result.latency_ms as i64),
+        error: Set(result.error.clone()),
+        ..Default::default()
+    }
+    .insert(&state.db)
+    .await?;
+    check_results::Entity::delete_many()
+        .filter(check_results::Column::CheckId.eq(check.id))
+        .filter(
+            check_results::Column::CheckedAt
+                .lt(result.checked_at - chrono::Duration::days(HISTORY_RETENTION_DAYS)),
+        )
+        .exec(&state.db)
+        .await?;
+
+    let previous = check.check_status();
+    let current = if result.up {
+        CheckStatus::Up
+    } else {
+        CheckStatus::Down
+    };
+    let mut active: checks::ActiveModel = check.clone().into();
+    active.status = Set(current.as_str().to_string());
+    active.last_checked_at = Set(Some(result.checked_at));
+    active.last_latency_ms = Set(Some(result.latency_ms as i64));
+    active.update(&state.db).await?;
+
+    // 首次检查正常时不通知
+    let group_key = format!("check:{}", check.id);
+    match (previous, current) {
+        (CheckStatus::Up | CheckStatus::Unknown, CheckStatus::Down) => {
+            warn!("Check '{}' is down: {:?}", check.name, result.error);
+            let input = transition_input(&check, &result);
+            notify::ingest_grouped(state, input, Some(group_key)).await;
+        }
+        (CheckStatus::Down, CheckStatus::Up) => {
+            info!("Check '{}' is back up", check.name);
+            let input = transition_input(&check, &result);
+            notify::resolve(state, &group_key, input).await;
+        }
+        _ => {}
+    }
+
+    Ok(result)
+}
+
+/// 启动检查调度任务，每次检查在独立任务中执行，同一检查不会并发；只在主节点上调度
+pub(crate) fn spawn(state: Arc<AppState>) -> anyhow::Result<()> {
+    let client = reqwest::Client::builder()
+        .user_agent(concat!("rutify-check/", env!("CARGO_PKG_VERSION")))
+        .build()?;
+    let running: Arc<Mutex<HashSet<i32>>> = Arc::new(Mutex::new(HashSet::new()));
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(TICK_INTERVAL);
+        loop {
+            ticker.tick().await;
+            if !state.leadership.is_leader() {
+                continue;
+            }
+            let enabled = match checks::Entity::find()
+                .filter(checks::Column::Enabled.eq(true))
+                .all(&state.db)
+                .await
+            {
+                Ok(enabled) => enabled,
+                Err(e) => {
+                    warn!("Database errors loading checks: {}", e);
+                    continue;
+                }
+            };
+
+            let now = Utc::now();
+            for check in enabled.into_iter().filter(|check| is_due(check, now)) {
+                if !running.lock().unwrap().insert(check.id) {
+                    continue;
+                }
+                let state = Arc::clone(&state);
+                let client = client.clone();
+                let running = Arc::clone(&running);
+                tokio::spawn(async move {
+                    let id = check.id;
+                    if let Err(e) = run_check(&state, &client, check).await {
+                        warn!("Check {} errors: {}", id, e);
+                    }
+                    running.lock().unwrap().remove(&id);
+                });
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_rules() {
+        assert!(evaluate(200, None).is_ok());
+        assert!(evaluate(204, None).is_ok());
+        assert!(evaluate(301, None).is_err());
+        assert!(evaluate(503, None).is_err());
+        assert!(evaluate(401, Some(401)).is_ok());
+        assert!(evaluate(200, Some(401)).is_err());
+
+        assert!(validate_url("https://example.com/health").is_ok());
+        assert!(validate_url("ftp://example.com").is_err());
+        assert!(validate_url("example.com").is_err());
+    }
+}
@@ -0,0 +1,292 @@
+//! 多实例部署时经 Redis 或 NATS 在节点间转发 NotifyEvent
+//!
+//! 数据库仍是唯一的数据来源，这里只负责让连接在其他节点上的 WebSocket、gRPC 客户端实时收到事件；
+//! 断线期间错过的事件由客户端重连时按通知 ID 从数据库补发。
+
+use anyhow::{Context, Result, bail};
+use rutify_core::NotifyEvent;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::bootstrap::source::ConfigSource;
+
+const DEFAULT_CHANNEL: &str = "rutify.events";
+
+/// 等待发布的事件上限，后端长时间不可用时丢弃新事件
+const OUTGOING_CAPACITY: usize = 1024;
+
+#[cfg_attr(
+    not(any(feature = "cluster-redis", feature = "cluster-nats")),
+    allow(dead_code)
+)]
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// 集群广播后端
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ClusterBackend {
+    Redis,
+    Nats,
+}
+
+/// 集群广播配置
+#[derive(Debug, Clone)]
+pub(crate) struct ClusterConfig {
+    pub(crate) backend: ClusterBackend,
+    pub(crate) url: String,
+    /// Redis 频道或 NATS 主题
+    pub(crate) channel: String,
+}
+
+impl ClusterConfig {
+    /// 读取 `RUTIFY_CLUSTER_URL`（`redis://`、`rediss://` 或 `nats://`）与
+    /// `RUTIFY_CLUSTER_CHANNEL`（默认 `rutify.events`），未设置地址时返回 `None`
+    pub(crate) fn from_source(source: &ConfigSource) -> Result<Option<Self>> {
+        let Some(url) = source
+            .var("RUTIFY_CLUSTER_URL")
+            .ok()
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty())
+        else {
+            return Ok(None);
+        };
+        let backend = match url.split_once("://").map(|(scheme, _)| scheme) {
+            Some("redis" | "rediss") => ClusterBackend::Redis,
+            Some("nats" | "tls") => ClusterBackend::Nats,
+            _ => bail!("invalid RUTIFY_CLUSTER_URL: {url}, expected redis:// or nats://"),
+        };
+        let channel = source
+            .var("RUTIFY_CLUSTER_CHANNEL")
+            .ok()
+            .map(|channel| channel.trim().to_string())
+            .filter(|channel| !channel.is_empty())
+            .unwrap_or_else(|| DEFAULT_CHANNEL.to_string());
+
+        Ok(Some(Self {
+            backend,
+            url,
+            channel,
+        }))
+    }
+}
+
+/// 节点间传递的消息，带上来源节点以忽略自己发出的事件
+#[derive(Debug, Serialize, Deserialize)]
+struct Envelope {
+    node: Uuid,
+    event: NotifyEvent,
+}
+
+/// 本节点的集群连接，发布本地产生的事件
+pub(crate) struct Cluster {
+    outgoing: mpsc::Sender<NotifyEvent>,
+}
+
+impl Cluster {
+    /// 把本地事件发往其他节点；不等待发送完成
+    pub(crate) fn publish(&self, event: &NotifyEvent) {
+        if let Err(mpsc::error::TrySendError::Full(_)) = self.outgoing.try_send(event.clone()) {
+            warn!("Cluster publish queue is full, event not replicated");
+        }
+    }
+}
+
+/// 连接集群后端，收到其他节点的事件后写入本地广播通道
+#[cfg_attr(
+    not(any(feature = "cluster-redis", feature = "cluster-nats")),
+    allow(unused_variables, unreachable_code)
+)]
+pub(crate) async fn spawn(
+    config: ClusterConfig,
+    tx: broadcast::Sender<NotifyEvent>,
+) -> Result<Cluster> {
+    let node = Uuid::new_v4();
+    let (outgoing, outgoing_rx) = mpsc::channel(OUTGOING_CAPACITY);
+    match config.backend {
+        #[cfg(feature = "cluster-redis")]
+        ClusterBackend::Redis => redis_backend::spawn(&config, node, outgoing_rx, tx).await?,
+        #[cfg(feature = "cluster-nats")]
+        ClusterBackend::Nats => nats_backend::spawn(&config, node, outgoing_rx, tx).await?,
+        #[cfg(not(feature = "cluster-redis"))]
+        ClusterBackend::Redis => {
+            bail!("RUTIFY_CLUSTER_URL requires rutify-server built with the cluster-redis feature")
+        }
+        #[cfg(not(feature = "cluster-nats"))]
+        ClusterBackend::Nats => {
+            bail!("RUTIFY_CLUSTER_URL requires rutify-server built with the cluster-nats feature")
+        }
+    }
+    info!(
+        "Cluster broadcast via {:?} channel '{}' as node {}",
+        config.backend, config.channel, node
+    );
+    Ok(Cluster { outgoing })
+}
+
+#[cfg_attr(
+    not(any(feature = "cluster-redis", feature = "cluster-nats")),
+    allow(dead_code)
+)]
+fn encode(node: Uuid, event: NotifyEvent) -> Result<Vec<u8>> {
+    serde_json::to_vec(&Envelope { node, event }).context("failed to serialize cluster event")
+}
+
+/// 解析其他节点发来的事件，本节点发出的与无法解析的消息返回 `None`
+#[cfg_attr(
+    not(any(feature = "cluster-redis", feature = "cluster-nats")),
+    allow(dead_code)
+)]
+fn decode(node: Uuid, payload: &[u8]) -> Option<NotifyEvent> {
+    match serde_json::from_slice::<Envelope>(payload) {
+        Ok(envelope) if envelope.node == node => None,
+        Ok(envelope) => Some(envelope.event),
+        Err(e) => {
+            warn!("Ignoring invalid cluster message: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(feature = "cluster-redis")]
+mod redis_backend {
+    use super::{ClusterConfig, RECONNECT_DELAY, decode, encode};
+    use anyhow::{Context, Result};
+    use futures_util::StreamExt;
+    use redis::AsyncCommands;
+    use rutify_core::NotifyEvent;
+    use tokio::sync::{broadcast, mpsc};
+    use tracing::warn;
+    use uuid::Uuid;
+
+    pub(super) async fn spawn(
+        config: &ClusterConfig,
+        node: Uuid,
+        mut outgoing: mpsc::Receiver<NotifyEvent>,
+        tx: broadcast::Sender<NotifyEvent>,
+    ) -> Result<()> {
+        let client = redis::Client::open(config.url.as_str())
+            .with_context(|| format!("invalid RUTIFY_CLUSTER_URL: {}", config.url))?;
+        // ConnectionManager 断线后自动重连
+        let mut publisher = client
+            .get_connection_manager()
+            .await
+            .with_context(|| format!("failed to connect to {}", config.url))?;
+
+        let channel = config.channel.clone();
+        tokio::spawn(async move {
+            while let Some(event) = outgoing.recv().await {
+                let Ok(payload) = encode(node, event) else {
+                    continue;
+                };
+                if let Err(e) = publisher.publish::<_, _, ()>(&channel, payload).await {
+                    warn!("Cluster publish to '{}' failed: {}", channel, e);
+                }
+            }
+        });
+
+        let channel = config.channel.clone();
+        tokio::spawn(async move {
+            loop {
+                match client.get_async_pubsub().await {
+                    Ok(mut pubsub) => match pubsub.subscribe(&channel).await {
+                        Ok(()) => {
+                            let mut messages = pubsub.on_message();
+                            while let Some(message) = messages.next().await {
+                                if let Some(event) = decode(node, message.get_payload_bytes()) {
+                                    let _ = tx.send(event);
+                                }
+                            }
+                            warn!("Cluster subscription to '{}' closed, reconnecting", channel);
+                        }
+                        Err(e) => warn!("Cluster subscribe to '{}' failed: {}", channel, e),
+                    },
+                    Err(e) => warn!("Cluster connection errors: {}, retrying", e),
+                }
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        });
+        Ok(())
+    }
+}
+
+#[cfg(feature = "cluster-nats")]
+mod nats_backend {
+    use super::{ClusterConfig, RECONNECT_DELAY, decode, encode};
+    use anyhow::{Context, Result};
+    use futures_util::StreamExt;
+    use rutify_core::NotifyEvent;
+    use tokio::sync::{broadcast, mpsc};
+    use tracing::warn;
+    use uuid::Uuid;
+
+    pub(super) async fn spawn(
+        config: &ClusterConfig,
+        node: Uuid,
+        mut outgoing: mpsc::Receiver<NotifyEvent>,
+        tx: broadcast::Sender<NotifyEvent>,
+    ) -> Result<()> {
+        // 客户端断线后自动重连并恢复订阅
+        let client = async_nats::connect(config.url.as_str())
+            .await
+            .with_context(|| format!("failed to connect to {}", config.url))?;
+
+        let publisher = client.clone();
+        let subject = config.channel.clone();
+        tokio::spawn(async move {
+            while let Some(event) = outgoing.recv().await {
+                let Ok(payload) = encode(node, event) else {
+                    continue;
+                };
+                if let Err(e) = publisher.publish(subject.clone(), payload.into()).await {
+                    warn!("Cluster publish to '{}' failed: {}", subject, e);
+                }
+            }
+        });
+
+        let subject = config.channel.clone();
+        tokio::spawn(async move {
+            loop {
+                match client.subscribe(subject.clone()).await {
+                    Ok(mut messages) => {
+                        while let Some(message) = messages.next().await {
+                            if let Some(event) = decode(node, &message.payload) {
+                                let _ = tx.send(event);
+                            }
+                        }
+                        warn!(
+                            "Cluster subscription to '{}' closed, resubscribing",
+                            subject
+                        );
+                    }
+                    Err(e) => warn!("Cluster subscribe to '{}' failed: {}", subject, e),
+                }
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_skips_own_events() {
+        let event: NotifyEvent = serde_json::from_value(serde_json::json!({
+            "event": "notify",
+            "data": { "notify": "disk full", "title": "db", "device": "db-1" },
+            "timestamp": "2026-01-01T00:00:00Z",
+        }))
+        .unwrap();
+        let node = Uuid::new_v4();
+        let payload = encode(node, event).unwrap();
+
+        assert!(decode(node, &payload).is_none());
+        let received = decode(Uuid::new_v4(), &payload).unwrap();
+        assert_eq!(received.data.notify, "disk full");
+        assert!(decode(node, b"not json").is_none());
+    }
+}
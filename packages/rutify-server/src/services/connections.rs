@@ -0,0 +1,50 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// 当前 WebSocket 连接计数
+pub(crate) struct ConnectionTracker {
+    active: AtomicUsize,
+}
+
+impl ConnectionTracker {
+    pub(crate) fn new() -> Self {
+        Self {
+            active: AtomicUsize::new(0),
+        }
+    }
+
+    /// 登记一个连接，返回的守卫释放时计数减一
+    pub(crate) fn open(self: &Arc<Self>) -> ConnectionGuard {
+        self.active.fetch_add(1, Ordering::Relaxed);
+        ConnectionGuard(Arc::clone(self))
+    }
+
+    pub(crate) fn active(&self) -> usize {
+        self.active.load(Ordering::Relaxed)
+    }
+}
+
+pub(crate) struct ConnectionGuard(Arc<ConnectionTracker>);
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.active.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connection_guard() {
+        let tracker = Arc::new(ConnectionTracker::new());
+        let first = tracker.open();
+        let second = tracker.open();
+        assert_eq!(tracker.active(), 2);
+        drop(first);
+        assert_eq!(tracker.active(), 1);
+        drop(second);
+        assert_eq!(tracker.active(), 0);
+    }
+}
@@ -0,0 +1,390 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use rutify_core::EventKind;
+use sea_orm::{
+    ConnectionTrait, DatabaseConnection, DbBackend, DbErr, EntityTrait, FromQueryResult,
+    PaginatorTrait, Statement,
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+use crate::bootstrap::source::ConfigSource;
+use crate::db;
+use crate::services::backup;
+use crate::services::notify;
+use crate::state::AppState;
+
+/// 检查 notifies 行数阈值的间隔
+const THRESHOLD_CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// PostgreSQL 死元组占比超过该值时提示调整 autovacuum
+const DEAD_TUPLE_RATIO: f64 = 0.2;
+
+/// 数据库维护配置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DbMaintenanceConfig {
+    /// 维护间隔，`None` 表示不定期执行
+    pub(crate) interval: Option<Duration>,
+    /// notifies 表行数告警阈值，升序
+    pub(crate) notifies_thresholds: Vec<u64>,
+    /// 是否定期删除已过期的通知
+    pub(crate) purge_expired: bool,
+}
+
+impl DbMaintenanceConfig {
+    /// 读取 `RUTIFY_DB_MAINTENANCE_HOURS`（默认 24，0 表示关闭）、`RUTIFY_NOTIFIES_WARN_ROWS`
+    /// 与 `RUTIFY_PURGE_EXPIRED`（默认开启）
+    pub(crate) fn from_source(source: &ConfigSource) -> Result<Self> {
+        let hours: u64 = match source.var("RUTIFY_DB_MAINTENANCE_HOURS") {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid RUTIFY_DB_MAINTENANCE_HOURS: {text}"))?,
+            Err(_) => 24,
+        };
+
+        let mut notifies_thresholds = match source.var("RUTIFY_NOTIFIES_WARN_ROWS") {
+            Ok(text) => text
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| {
+                    item.parse::<u64>()
+                        .with_context(|| format!("invalid RUTIFY_NOTIFIES_WARN_ROWS entry: {item}"))
+                })
+                .collect::<Result<Vec<_>>>()?,
+            Err(_) => vec![100_000, 1_000_000],
+        };
+        notifies_thresholds.sort_unstable();
+        notifies_thresholds.dedup();
+
+        let purge_expired = match source.var("RUTIFY_PURGE_EXPIRED") {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid RUTIFY_PURGE_EXPIRED: {text}"))?,
+            Err(_) => true,
+        };
+
+        Ok(Self {
+            interval: (hours > 0).then(|| Duration::from_secs(hours * 60 * 60)),
+            notifies_thresholds,
+            purge_expired,
+        })
+    }
+}
+
+/// 一次维护的结果
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct MaintenanceRun {
+    pub(crate) started_at: DateTime<Utc>,
+    pub(crate) duration_ms: u64,
+    pub(crate) error: Option<String>,
+}
+
+/// `/api/admin/db/status` 的返回内容
+#[derive(Debug, Serialize)]
+pub(crate) struct DbStatus {
+    backend: &'static str,
+    /// 数据库文件（含 WAL）或 PostgreSQL 库的字节数
+    size_bytes: Option<u64>,
+    row_counts: BTreeMap<&'static str, u64>,
+    notifies_thresholds: Vec<u64>,
+    maintenance_interval_secs: Option<u64>,
+    last_maintenance: Option<MaintenanceRun>,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct DatabaseSize {
+    size: i64,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct TupleStats {
+    relname: String,
+    n_live_tup: i64,
+    n_dead_tup: i64,
+}
+
+/// 定期 VACUUM/ANALYZE 与 notifies 表规模监控
+pub(crate) struct DbMaintenance {
+    /// 可在运行时重新加载
+    config: Mutex<DbMaintenanceConfig>,
+    last_run: Mutex<Option<MaintenanceRun>>,
+    /// 已经告警过的最高阈值，行数回落后重置
+    reported_threshold: AtomicU64,
+}
+
+impl DbMaintenance {
+    pub(crate) fn new(config: DbMaintenanceConfig) -> Self {
+        Self {
+            config: Mutex::new(config),
+            last_run: Mutex::new(None),
+            reported_threshold: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn config(&self) -> DbMaintenanceConfig {
+        self.config.lock().unwrap().clone()
+    }
+
+    /// 替换配置，新的维护间隔从下一次检查开始生效
+    pub(crate) fn set_config(&self, config: DbMaintenanceConfig) {
+        *self.config.lock().unwrap() = config;
+    }
+
+    /// 执行一次维护：SQLite 为 `VACUUM` 与 `ANALYZE`，PostgreSQL 为 `ANALYZE` 并检查死元组
+    pub(crate) async fn run(&self, db: &DatabaseConnection) -> MaintenanceRun {
+        let started_at = Utc::now();
+        let started = Instant::now();
+        let result = match db.get_database_backend() {
+            DbBackend::Sqlite => vacuum_sqlite(db).await,
+            DbBackend::Postgres => analyze_postgres(db).await,
+            _ => Ok(()),
+        };
+
+        let run = MaintenanceRun {
+            started_at,
+            duration_ms: started.elapsed().as_millis() as u64,
+            error: result.err().map(|err| err.to_string()),
+        };
+        match &run.error {
+            Some(error) => warn!("Database maintenance failed: {error}"),
+            None => info!("Database maintenance finished in {} ms", run.duration_ms),
+        }
+        *self.last_run.lock().unwrap() = Some(run.clone());
+        run
+    }
+
+    pub(crate) async fn status(
+        &self,
+        db: &DatabaseConnection,
+        db_url: &str,
+    ) -> Result<DbStatus, DbErr> {
+        let backend = db.get_database_backend();
+        let size_bytes = database_size(db, db_url).await?;
+
+        let row_counts = row_counts(db).await?;
+        self.check_thresholds(row_counts["notifies"]);
+        let config = self.config();
+
+        Ok(DbStatus {
+            backend: match backend {
+                DbBackend::Sqlite => "sqlite",
+                DbBackend::Postgres => "postgres",
+                _ => "other",
+            },
+            size_bytes,
+            row_counts,
+            notifies_thresholds: config.notifies_thresholds,
+            maintenance_interval_secs: config.interval.map(|interval| interval.as_secs()),
+            last_maintenance: self.last_run.lock().unwrap().clone(),
+        })
+    }
+
+    /// 行数越过新的阈值时告警一次，返回本次告警的阈值
+    fn check_thresholds(&self, rows: u64) -> Option<u64> {
+        let crossed = self
+            .config
+            .lock()
+            .unwrap()
+            .notifies_thresholds
+            .iter()
+            .copied()
+            .filter(|threshold| rows >= *threshold)
+            .max()
+            .unwrap_or(0);
+        let previous = self.reported_threshold.swap(crossed, Ordering::Relaxed);
+        if crossed > previous {
+            warn!(
+                rows,
+                threshold = crossed,
+                "notifies table crossed size threshold, consider purging old notifications"
+            );
+            Some(crossed)
+        } else {
+            None
+        }
+    }
+}
+
+async fn vacuum_sqlite(db: &DatabaseConnection) -> Result<(), DbErr> {
+    db.execute_unprepared("VACUUM").await?;
+    db.execute_unprepared("ANALYZE").await?;
+    Ok(())
+}
+
+/// PostgreSQL 的清理由 autovacuum 负责，这里只刷新统计信息并在死元组过多时提示
+async fn analyze_postgres(db: &DatabaseConnection) -> Result<(), DbErr> {
+    db.execute_unprepared("ANALYZE").await?;
+    let stats = TupleStats::find_by_statement(Statement::from_string(
+        DbBackend::Postgres,
+        "SELECT relname::text AS relname, n_live_tup, n_dead_tup FROM pg_stat_user_tables",
+    ))
+    .all(db)
+    .await?;
+    for table in stats {
+        let live = table.n_live_tup.max(1) as f64;
+        if table.n_dead_tup as f64 / live > DEAD_TUPLE_RATIO {
+            warn!(
+                table = %table.relname,
+                dead = table.n_dead_tup,
+                live = table.n_live_tup,
+                "many dead tuples, consider lowering autovacuum_vacuum_scale_factor for this table"
+            );
+        }
+    }
+    Ok(())
+}
+
+/// 数据库文件（含 WAL）或 PostgreSQL 库的字节数，其他后端返回 `None`
+pub(crate) async fn database_size(
+    db: &DatabaseConnection,
+    db_url: &str,
+) -> Result<Option<u64>, DbErr> {
+    let backend = db.get_database_backend();
+    Ok(match backend {
+        DbBackend::Sqlite => sqlite_size(db_url),
+        DbBackend::Postgres => DatabaseSize::find_by_statement(Statement::from_string(
+            backend,
+            "SELECT pg_database_size(current_database()) AS size",
+        ))
+        .one(db)
+        .await?
+        .map(|row| row.size.max(0) as u64),
+        _ => None,
+    })
+}
+
+fn sqlite_size(db_url: &str) -> Option<u64> {
+    let path = backup::sqlite_path(db_url).ok()?;
+    let mut size = std::fs::metadata(&path).ok()?.len();
+    let mut wal = path.into_os_string();
+    wal.push("-wal");
+    size += std::fs::metadata(wal).map(|meta| meta.len()).unwrap_or(0);
+    Some(size)
+}
+
+async fn row_counts(db: &DatabaseConnection) -> Result<BTreeMap<&'static str, u64>, DbErr> {
+    Ok(BTreeMap::from([
+        ("notifies", db::Notifies::find().count(db).await?),
+        ("attachments", db::Attachments::find().count(db).await?),
+        ("deliveries", db::Deliveries::find().count(db).await?),
+        ("tokens", db::Tokens::find().count(db).await?),
+        ("users", db::Users::find().count(db).await?),
+        ("silences", db::Silences::find().count(db).await?),
+        ("escalations", db::Escalations::find().count(db).await?),
+        ("plugins", db::Plugins::find().count(db).await?),
+        (
+            "oncall_schedules",
+            db::OncallSchedules::find().count(db).await?,
+        ),
+        ("oncall_members", db::OncallMembers::find().count(db).await?),
+    ]))
+}
+
+/// 启动维护任务：定期清理过期通知与遗留附件、送达记录，检查 notifies 行数，并按配置间隔执行维护；只在主节点上运行
+pub(crate) fn spawn(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let maintenance = &state.db_maintenance;
+        let mut interval = maintenance.config().interval;
+        let mut next_run = interval.map(|interval| Instant::now() + interval);
+        let mut ticker = tokio::time::interval(THRESHOLD_CHECK_INTERVAL);
+
+        loop {
+            ticker.tick().await;
+            if !state.leadership.is_leader() {
+                continue;
+            }
+            let config = maintenance.config();
+            // 重新加载后维护间隔变化时从现在重新计时
+            if config.interval != interval {
+                interval = config.interval;
+                next_run = interval.map(|interval| Instant::now() + interval);
+            }
+            if config.purge_expired {
+                match db::notifies::purge_expired(&state.db).await {
+                    Ok(0) => {}
+                    Ok(deleted) => {
+                        info!("Purged {deleted} expired notifications");
+                        notify::broadcast(
+                            &state,
+                            EventKind::StatsChanged,
+                            notify::system_data(
+                                "Notifications expired",
+                                format!("{deleted} expired notifications were removed"),
+                            ),
+                        );
+                    }
+                    Err(err) => warn!("failed to purge expired notifications: {err}"),
+                }
+            }
+            match db::attachments::delete_orphaned(&state.db).await {
+                Ok(0) => {}
+                Ok(deleted) => info!("Removed {deleted} orphaned attachments"),
+                Err(err) => warn!("failed to remove orphaned attachments: {err}"),
+            }
+            match db::deliveries::delete_orphaned(&state.db).await {
+                Ok(0) => {}
+                Ok(deleted) => info!("Removed {deleted} orphaned delivery receipts"),
+                Err(err) => warn!("failed to remove orphaned delivery receipts: {err}"),
+            }
+            match db::unifiedpush_registrations::delete_orphaned(&state.db).await {
+                Ok(0) => {}
+                Ok(deleted) => info!("Removed {deleted} orphaned UnifiedPush registrations"),
+                Err(err) => warn!("failed to remove orphaned UnifiedPush registrations: {err}"),
+            }
+            match db::web_push_subscriptions::delete_orphaned(&state.db).await {
+                Ok(0) => {}
+                Ok(deleted) => info!("Removed {deleted} orphaned Web Push subscriptions"),
+                Err(err) => warn!("failed to remove orphaned Web Push subscriptions: {err}"),
+            }
+            match db::user_preferences::delete_orphaned(&state.db).await {
+                Ok(0) => {}
+                Ok(deleted) => info!("Removed {deleted} orphaned notification preferences"),
+                Err(err) => warn!("failed to remove orphaned notification preferences: {err}"),
+            }
+            match db::Notifies::find().count(&state.db).await {
+                Ok(rows) => {
+                    maintenance.check_thresholds(rows);
+                }
+                Err(err) => warn!("failed to count notifies: {err}"),
+            }
+
+            let Some(interval) = interval else {
+                continue;
+            };
+            if next_run.is_some_and(|due| Instant::now() >= due) {
+                maintenance.run(&state.db).await;
+                next_run = Some(Instant::now() + interval);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_thresholds() {
+        let maintenance = DbMaintenance::new(DbMaintenanceConfig {
+            interval: None,
+            notifies_thresholds: vec![100, 1_000],
+            purge_expired: false,
+        });
+
+        assert_eq!(maintenance.check_thresholds(50), None);
+        assert_eq!(maintenance.check_thresholds(150), Some(100));
+        assert_eq!(maintenance.check_thresholds(900), None);
+        assert_eq!(maintenance.check_thresholds(1_200), Some(1_000));
+        // 清理后回落，再次越过时重新告警
+        assert_eq!(maintenance.check_thresholds(10), None);
+        assert_eq!(maintenance.check_thresholds(120), Some(100));
+    }
+}
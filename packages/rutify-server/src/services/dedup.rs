@@ -0,0 +1,123 @@
+use anyhow::{Context, Result};
+use moka::future::Cache;
+use rutify_core::NotificationInput;
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+
+use crate::bootstrap::source::ConfigSource;
+
+/// 标记 `GET /notify` 请求是否被判定为重复的响应头，值为 `true` 或 `false`
+pub(crate) const DUPLICATE_HEADER: &str = "x-rutify-duplicate";
+
+/// 窗口内最多记录的请求数
+const CAPACITY: u64 = 10_000;
+
+/// `GET /notify` 的重复请求抑制：键为 Token 与请求内容的哈希，值为首次入库的通知 ID
+///
+/// 浏览器会预取并重试 `GET /notify?notify=...` 链接，同一 Token 在窗口内发送相同内容时只入库一次。
+/// `POST /notify` 不受影响。
+pub(crate) struct GetDedup {
+    cache: Option<Cache<String, Option<i32>>>,
+}
+
+impl GetDedup {
+    /// `window` 为零时关闭
+    pub(crate) fn new(window: Duration) -> Self {
+        let cache = (!window.is_zero()).then(|| {
+            Cache::builder()
+                .max_capacity(CAPACITY)
+                .time_to_live(window)
+                .build()
+        });
+        Self { cache }
+    }
+
+    /// 读取 `RUTIFY_GET_DEDUP_SECS`（默认 10，0 表示关闭）
+    pub(crate) fn from_source(source: &ConfigSource) -> Result<Self> {
+        let secs: u64 = match source.var("RUTIFY_GET_DEDUP_SECS") {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid RUTIFY_GET_DEDUP_SECS: {text}"))?,
+            Err(_) => 10,
+        };
+        Ok(Self::new(Duration::from_secs(secs)))
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.cache.is_some()
+    }
+
+    /// 请求的去重键；open 模式的匿名请求没有 Token，只按内容区分
+    pub(crate) fn key(
+        token_id: Option<&str>,
+        payload: &NotificationInput,
+    ) -> serde_json::Result<String> {
+        let mut hasher = Sha256::new();
+        hasher.update(token_id.unwrap_or_default().as_bytes());
+        hasher.update([0]);
+        hasher.update(serde_json::to_vec(payload)?);
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    /// 登记请求；窗口内已有相同请求时返回其通知 ID（通知被丢弃或仍在入库时为 `None`）
+    ///
+    /// 并发的相同请求只有一个会登记成功。
+    pub(crate) async fn claim(&self, key: &str) -> Option<Option<i32>> {
+        let cache = self.cache.as_ref()?;
+        let entry = cache.entry_by_ref(key).or_insert(None).await;
+        (!entry.is_fresh()).then(|| entry.into_value())
+    }
+
+    /// 入库完成后记录通知 ID
+    pub(crate) async fn record(&self, key: String, id: Option<i32>) {
+        if let Some(cache) = &self.cache {
+            cache.insert(key, id).await;
+        }
+    }
+
+    /// 入库失败时撤销登记，允许客户端重试
+    pub(crate) async fn release(&self, key: &str) {
+        if let Some(cache) = &self.cache {
+            cache.invalidate(key).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(notify: &str) -> NotificationInput {
+        NotificationInput {
+            notify: notify.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_dedup() {
+        let dedup = GetDedup::new(Duration::from_secs(60));
+        let key = GetDedup::key(Some("token-a"), &input("hello")).unwrap();
+        assert_ne!(
+            key,
+            GetDedup::key(Some("token-b"), &input("hello")).unwrap()
+        );
+        assert_ne!(key, GetDedup::key(None, &input("hello")).unwrap());
+        assert_ne!(key, GetDedup::key(Some("token-a"), &input("bye")).unwrap());
+
+        assert_eq!(dedup.claim(&key).await, None);
+        assert_eq!(dedup.claim(&key).await, Some(None));
+        dedup.record(key.clone(), Some(7)).await;
+        assert_eq!(dedup.claim(&key).await, Some(Some(7)));
+
+        // 失败后允许重试
+        dedup.release(&key).await;
+        assert_eq!(dedup.claim(&key).await, None);
+
+        let disabled = GetDedup::new(Duration::ZERO);
+        assert!(!disabled.is_enabled());
+        assert_eq!(disabled.claim(&key).await, None);
+        assert_eq!(disabled.claim(&key).await, None);
+    }
+}
@@ -0,0 +1,466 @@
+use rutify_core::{DeliveryChannel, EventKind, NotificationData, NotifyEvent, NotifyId, TokenId};
+use sea_orm::EntityTrait;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::db::deliveries::{self, NewDelivery};
+use crate::db::{notifies, token_ops, tokens};
+use crate::error::AppError;
+use crate::services::notify;
+use crate::state::AppState;
+
+/// 重连时最多补发的通知数
+const MAX_REPLAY: u64 = 500;
+
+/// 通知对某个订阅者（通知 Token）的投递状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum DeliveryStatus {
+    /// 已投递到该 Token 的连接
+    Delivered,
+    /// 尚未投递，下次连接时补发
+    Pending,
+    /// 定向给其他用户，不投递给该 Token
+    NotAddressed,
+    /// 命中静默规则，不投递
+    Silenced,
+}
+
+/// 定向通知只投递给对应用户，其余通知对所有连接可见
+pub(crate) fn is_visible_to(data: &NotificationData, username: Option<&str>) -> bool {
+    match data.recipient.as_deref() {
+        Some(recipient) => username == Some(recipient),
+        None => true,
+    }
+}
+
+/// 根据订阅者的投递游标判断通知的投递状态
+pub(crate) fn delivery_status(
+    notify: &notifies::Model,
+    token: &tokens::Model,
+    username: Option<&str>,
+) -> DeliveryStatus {
+    if notify.silenced {
+        DeliveryStatus::Silenced
+    } else if !is_visible_to(&notify.to_data(), username) {
+        DeliveryStatus::NotAddressed
+    } else if token
+        .last_delivered_id
+        .is_some_and(|last_id| last_id >= notify.id)
+    {
+        DeliveryStatus::Delivered
+    } else {
+        DeliveryStatus::Pending
+    }
+}
+
+/// 单个 WebSocket 连接的投递游标
+///
+/// 游标只在内存中推进，由连接定期调用 [`DeliveryCursor::save`] 持久化。
+pub(crate) struct DeliveryCursor {
+    token_id: i32,
+    last_id: i32,
+    dirty: bool,
+}
+
+impl DeliveryCursor {
+    /// 读取 Token 的投递游标；首次连接时从最新通知开始，不补发历史通知
+    pub(crate) async fn open(state: &AppState, token: &tokens::Model) -> Result<Self, AppError> {
+        let last_id = match token.last_delivered_id {
+            Some(last_id) => last_id,
+            None => {
+                let last_id = notifies::latest_id(&state.db).await?.unwrap_or(0);
+                token_ops::update_delivery_cursor(&state.db, token.id, last_id).await?;
+                last_id
+            }
+        };
+
+        Ok(Self {
+            token_id: token.id,
+            last_id,
+            dirty: false,
+        })
+    }
+
+    /// 游标所属的 Token
+    pub(crate) fn token_id(&self) -> TokenId {
+        TokenId(self.token_id)
+    }
+
+    /// 实时事件是否已通过补发送达（更新类与系统事件总是需要发送）
+    pub(crate) fn already_sent(&self, event: &NotifyEvent) -> bool {
+        event.ack_id().is_some_and(|id| id.get() <= self.last_id)
+    }
+
+    /// 记录已发送的事件，只有新通知与补发的通知推进游标
+    pub(crate) fn advance(&mut self, event: &NotifyEvent) {
+        if let Some(id) = event.ack_id().filter(|id| id.get() > self.last_id) {
+            self.last_id = id.get();
+            self.dirty = true;
+        }
+    }
+
+    /// 读取游标之后错过的通知，返回错过的总数与需要补发的事件（事件类型为 `replay`）
+    ///
+    /// 维护期间不补发，错过的通知会在维护结束时随暂存队列一起送达。
+    pub(crate) async fn missed(
+        &self,
+        state: &AppState,
+        username: Option<&str>,
+    ) -> Result<(u64, Vec<NotifyEvent>), AppError> {
+        if state.maintenance.status().enabled {
+            return Ok((0, Vec::new()));
+        }
+
+        let (total, items) =
+            notifies::missed_since(&state.db, self.last_id, username, MAX_REPLAY).await?;
+        let events = items
+            .iter()
+            .map(|item| NotifyEvent {
+                event: EventKind::Replayed,
+                data: item.to_data(),
+                timestamp: chrono::Utc::now(),
+            })
+            .collect();
+        Ok((total, events))
+    }
+
+    /// 持久化游标，没有推进或实例只读时跳过
+    pub(crate) async fn save(&mut self, state: &AppState) {
+        if !self.dirty || state.read_only {
+            return;
+        }
+        match token_ops::update_delivery_cursor(&state.db, self.token_id, self.last_id).await {
+            Ok(()) => self.dirty = false,
+            Err(e) => warn!(
+                "Failed to save delivery cursor for token {}: {}",
+                self.token_id, e
+            ),
+        }
+    }
+}
+
+/// 在后台写入一条送达记录，只读实例不记录
+pub(crate) fn record_receipt(state: &AppState, delivery: NewDelivery) {
+    if state.read_only {
+        return;
+    }
+    let db = state.db.clone();
+    tokio::spawn(async move {
+        if let Err(e) = deliveries::record(&db, delivery).await {
+            warn!("Failed to record delivery receipt: {}", e);
+        }
+    });
+}
+
+/// 通过 WebSocket 推送后需要记录回执的事件：新通知与重连补发的通知
+pub(crate) fn websocket_receipt(
+    event: &NotifyEvent,
+    usage: &str,
+    token_id: Option<TokenId>,
+) -> Option<NewDelivery> {
+    Some(NewDelivery {
+        notify_id: event.ack_id()?.get(),
+        channel: DeliveryChannel::Websocket,
+        target: usage.to_string(),
+        token_id: token_id.map(TokenId::get),
+        error: None,
+    })
+}
+
+/// 订阅者确认已处理通知：写入送达回执，首次确认时广播 `delivered` 事件
+///
+/// 不存在或对该订阅者不可见的通知直接忽略。
+pub(crate) async fn acknowledge(
+    state: &AppState,
+    notify_id: NotifyId,
+    usage: &str,
+    username: Option<&str>,
+    token_id: Option<TokenId>,
+) -> Result<(), AppError> {
+    let Some(notify) = notifies::Entity::find_by_id(notify_id.get())
+        .one(&state.db)
+        .await?
+    else {
+        return Ok(());
+    };
+    let data = notify.to_data();
+    if !is_visible_to(&data, username) {
+        return Ok(());
+    }
+
+    let first = state.read_only
+        || deliveries::acknowledge(
+            &state.db,
+            NewDelivery {
+                notify_id: notify_id.get(),
+                channel: DeliveryChannel::Websocket,
+                target: usage.to_string(),
+                token_id: token_id.map(TokenId::get),
+                error: None,
+            },
+        )
+        .await?;
+    if first {
+        notify::broadcast(state, EventKind::Delivered, data);
+    }
+    Ok(())
+}
+
+/// 错过通知的提示事件
+pub(crate) fn missed_report(total: u64, replayed: usize) -> NotifyEvent {
+    let notify = if total > replayed as u64 {
+        format!(
+            "{total} notifications were missed while disconnected, replaying the latest {replayed}"
+        )
+    } else {
+        format!("{total} notifications were missed while disconnected")
+    };
+
+    NotifyEvent {
+        event: EventKind::Missed,
+        data: NotificationData {
+            id: None,
+            notify,
+            title: "Missed notifications".to_string(),
+            device: "rutify".to_string(),
+            priority: rutify_core::DEFAULT_PRIORITY,
+            tags: Vec::new(),
+            recipient: None,
+            channel: None,
+            expires_at: None,
+            progress: None,
+            link_previews: Vec::new(),
+            signature: None,
+            correlation_id: None,
+            token_id: None,
+        },
+        timestamp: chrono::Utc::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::auth::mode::AuthPolicy;
+    use chrono::Utc;
+
+    fn notify(id: i32, recipient: Option<&str>, silenced: bool) -> notifies::Model {
+        notifies::Model {
+            id,
+            notify: "body".to_string(),
+            title: None,
+            device: None,
+            received_at: Utc::now(),
+            priority: 3,
+            tags: None,
+            group_key: None,
+            resolved_at: None,
+            acked_at: None,
+            recipient: recipient.map(str::to_string),
+            channel: None,
+            silenced,
+            expires_at: None,
+            pinned: false,
+            starred: false,
+            progress_current: None,
+            progress_total: None,
+            progress_status: None,
+            device_source: None,
+            link_previews: None,
+            signature_key: None,
+            signature: None,
+            correlation_id: None,
+            token_hash: None,
+        }
+    }
+
+    fn token(last_delivered_id: Option<i32>) -> tokens::Model {
+        tokens::Model {
+            id: 1,
+            token_hash: "hash".to_string(),
+            usage: "phone".to_string(),
+            token_type: tokens::TokenType::NotifyBearer,
+            user_id: None,
+            device_info: None,
+            created_at: Utc::now(),
+            expires_at: Utc::now(),
+            last_used_at: None,
+            last_delivered_id,
+            last_delivered_at: None,
+        }
+    }
+
+    #[test]
+    fn test_delivery_status() {
+        let delivered = token(Some(10));
+        assert_eq!(
+            delivery_status(&notify(10, None, false), &delivered, None),
+            DeliveryStatus::Delivered
+        );
+        assert_eq!(
+            delivery_status(&notify(11, None, false), &delivered, None),
+            DeliveryStatus::Pending
+        );
+        assert_eq!(
+            delivery_status(&notify(5, None, true), &delivered, None),
+            DeliveryStatus::Silenced
+        );
+        assert_eq!(
+            delivery_status(&notify(5, Some("alice"), false), &delivered, Some("bob")),
+            DeliveryStatus::NotAddressed
+        );
+        assert_eq!(
+            delivery_status(&notify(12, None, false), &token(None), None),
+            DeliveryStatus::Pending
+        );
+    }
+
+    #[test]
+    fn test_cursor_skips_replayed_notifies() {
+        let mut cursor = DeliveryCursor {
+            token_id: 1,
+            last_id: 10,
+            dirty: false,
+        };
+        let mut event = missed_report(1, 1);
+        event.event = EventKind::Created;
+        event.data.id = Some(NotifyId(9));
+        assert!(cursor.already_sent(&event));
+
+        event.event = EventKind::Updated;
+        assert!(!cursor.already_sent(&event));
+
+        event.event = EventKind::Created;
+        event.data.id = Some(NotifyId(11));
+        assert!(!cursor.already_sent(&event));
+        cursor.advance(&event);
+        assert_eq!(cursor.last_id, 11);
+        assert!(cursor.dirty);
+    }
+
+    #[tokio::test]
+    async fn test_token_revoked_does_not_advance_cursor() {
+        use crate::db::users::{Model as UserModel, UserRole};
+        use crate::services::auth::auth::delete_token;
+        use axum::Extension;
+        use axum::extract::{Path, State};
+
+        let state = AppState::for_tests(AuthPolicy::default()).await;
+        let mut tokens = Vec::new();
+        for usage in ["phone", "a", "b", "c", "d"] {
+            let token = token_ops::create_notify_token(
+                &state.db,
+                usage,
+                usage,
+                None,
+                Utc::now() + chrono::Duration::days(1),
+                None,
+            )
+            .await
+            .unwrap();
+            tokens.push(token);
+        }
+        let mut cursor = DeliveryCursor::open(&state, &tokens[0]).await.unwrap();
+        assert_eq!(cursor.last_id, 0);
+
+        // 撤销 ID 较大的 Token，事件经过同一连接
+        let mut rx = state.tx.subscribe();
+        let admin = UserModel {
+            id: uuid::Uuid::new_v4(),
+            username: "admin".to_string(),
+            password_hash: String::new(),
+            email: "admin@example.com".to_string(),
+            role: UserRole::Admin,
+            email_verified: true,
+            disabled: false,
+            created_at: Utc::now().into(),
+            updated_at: Utc::now().into(),
+        };
+        let revoked = TokenId(tokens[4].id);
+        delete_token(State(state.clone()), Extension(admin), Path(revoked))
+            .await
+            .unwrap();
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.revoked_token_id(), Some(revoked));
+        assert_eq!(event.data.id, None);
+        assert!(!cursor.already_sent(&event));
+        cursor.advance(&event);
+        assert_eq!(cursor.last_id, 0);
+
+        // ID 小于被撤销 Token 的新通知仍然送达
+        let created = notify::ingest(
+            &state,
+            rutify_core::NotificationInput {
+                notify: "disk full".to_string(),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert!(created.data.id.unwrap().get() < revoked.get());
+        assert!(!cursor.already_sent(&created));
+        cursor.advance(&created);
+        assert_eq!(cursor.last_id, created.data.id.unwrap().get());
+
+        // 其他事件即使携带通知 ID 也不推进游标
+        let mut updated = created.clone();
+        updated.event = EventKind::Updated;
+        updated.data.id = Some(NotifyId(revoked.get()));
+        assert!(!cursor.already_sent(&updated));
+        cursor.advance(&updated);
+        assert_eq!(cursor.last_id, created.data.id.unwrap().get());
+    }
+
+    #[tokio::test]
+    async fn test_websocket_receipts() {
+        let mut event = missed_report(1, 1);
+        assert!(websocket_receipt(&event, "phone", Some(TokenId(1))).is_none());
+
+        event.event = EventKind::Created;
+        event.data.id = Some(NotifyId(7));
+        let receipt = websocket_receipt(&event, "phone", Some(TokenId(1))).unwrap();
+        assert_eq!(receipt.notify_id, 7);
+        assert_eq!(receipt.channel, DeliveryChannel::Websocket);
+        event.event = EventKind::Updated;
+        assert!(websocket_receipt(&event, "phone", Some(TokenId(1))).is_none());
+
+        let state = AppState::for_tests(AuthPolicy::default()).await;
+        deliveries::record(&state.db, receipt).await.unwrap();
+        let receipts = deliveries::list_for_notify(&state.db, 7).await.unwrap();
+        assert_eq!(receipts.len(), 1);
+        let receipt = receipts[0].clone().into_receipt();
+        assert_eq!(receipt.target, "phone");
+        assert_eq!(receipt.token_id, Some(TokenId(1)));
+
+        // 通知 7 不存在，回执随维护任务清理
+        assert_eq!(deliveries::delete_orphaned(&state.db).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_acknowledge_updates_receipt_once() {
+        let state = AppState::for_tests(AuthPolicy::default()).await;
+        let mut event = missed_report(1, 1);
+        event.event = EventKind::Created;
+        event.data.id = Some(NotifyId(7));
+        let receipt = websocket_receipt(&event, "phone", Some(TokenId(1))).unwrap();
+
+        deliveries::record(&state.db, receipt.clone())
+            .await
+            .unwrap();
+        assert!(
+            deliveries::acknowledge(&state.db, receipt.clone())
+                .await
+                .unwrap()
+        );
+        assert!(!deliveries::acknowledge(&state.db, receipt).await.unwrap());
+
+        // 匿名连接没有送达记录时补写一条已确认的记录
+        let anonymous = websocket_receipt(&event, "anonymous", None).unwrap();
+        assert!(deliveries::acknowledge(&state.db, anonymous).await.unwrap());
+
+        let receipts = deliveries::list_for_notify(&state.db, 7).await.unwrap();
+        assert_eq!(receipts.len(), 2);
+        assert!(receipts.iter().all(|receipt| receipt.acked_at.is_some()));
+    }
+}
@@ -0,0 +1,178 @@
+use rutify_core::{NotificationInput, TokenDefaults};
+use tracing::warn;
+
+use crate::db::token_ops;
+use crate::services::auth::auth::generate_token_hash;
+use crate::state::AppState;
+
+/// 通知设备名的来源，随通知入库便于排查
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DeviceSource {
+    /// 发送方在通知中指定
+    Payload,
+    /// 通知 Token 的默认设备
+    TokenDefault,
+    /// 通知 Token 创建时填写的设备信息
+    TokenInfo,
+    /// 由请求的 User-Agent 推断
+    UserAgent,
+    /// 以上都没有，使用内置默认值
+    Default,
+}
+
+impl DeviceSource {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            DeviceSource::Payload => "payload",
+            DeviceSource::TokenDefault => "token_default",
+            DeviceSource::TokenInfo => "token_info",
+            DeviceSource::UserAgent => "user_agent",
+            DeviceSource::Default => "default",
+        }
+    }
+
+    /// 未经推断的通知：有设备名即为发送方指定
+    pub(crate) fn of(input: &NotificationInput) -> Self {
+        if input.device.is_some() {
+            DeviceSource::Payload
+        } else {
+            DeviceSource::Default
+        }
+    }
+}
+
+/// 发送请求的上下文，用于补全缺失的设备名
+pub(crate) struct SenderContext<'a> {
+    pub(crate) defaults: Option<&'a TokenDefaults>,
+    /// 原始通知 Token，用于查询创建时填写的设备信息
+    pub(crate) token: Option<&'a str>,
+    pub(crate) user_agent: Option<&'a str>,
+}
+
+/// 依次使用通知中的设备名、Token 默认设备、Token 设备信息与 User-Agent 补全设备名，
+/// 同时应用 Token 的其他默认字段
+pub(crate) async fn derive(
+    state: &AppState,
+    input: &mut NotificationInput,
+    sender: SenderContext<'_>,
+) -> DeviceSource {
+    let source = match input.device {
+        Some(_) => DeviceSource::Payload,
+        None => {
+            let (device, source) = fallback_device(state, &sender).await;
+            input.device = device;
+            source
+        }
+    };
+
+    if let Some(defaults) = sender.defaults {
+        defaults.apply(input);
+    }
+    source
+}
+
+async fn fallback_device(
+    state: &AppState,
+    sender: &SenderContext<'_>,
+) -> (Option<String>, DeviceSource) {
+    if let Some(device) = sender.defaults.and_then(|defaults| defaults.device.clone()) {
+        return (Some(device), DeviceSource::TokenDefault);
+    }
+    if let Some(token) = sender.token
+        && let Some(device) = token_device_info(state, token).await
+    {
+        return (Some(device), DeviceSource::TokenInfo);
+    }
+    match sender.user_agent.and_then(user_agent_device) {
+        Some(device) => (Some(device), DeviceSource::UserAgent),
+        None => (None, DeviceSource::Default),
+    }
+}
+
+async fn token_device_info(state: &AppState, token: &str) -> Option<String> {
+    match token_ops::find_token(&state.db, &generate_token_hash(token)).await {
+        Ok(token) => token?
+            .device_info
+            .map(|info| info.trim().to_string())
+            .filter(|info| !info.is_empty()),
+        Err(e) => {
+            warn!("Failed to load token device info: {}", e);
+            None
+        }
+    }
+}
+
+/// 取 User-Agent 的第一个产品名，如 `curl/8.4.0` 得到 `curl`；浏览器统一为 `browser`
+fn user_agent_device(user_agent: &str) -> Option<String> {
+    let product = user_agent.split_whitespace().next()?;
+    let name = product.split('/').next()?.trim();
+    if name.is_empty() {
+        return None;
+    }
+    if name.eq_ignore_ascii_case("mozilla") {
+        return Some("browser".to_string());
+    }
+    Some(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::auth::mode::AuthPolicy;
+
+    #[test]
+    fn test_user_agent_device() {
+        assert_eq!(user_agent_device("curl/8.4.0").as_deref(), Some("curl"));
+        assert_eq!(
+            user_agent_device("python-requests/2.31.0").as_deref(),
+            Some("python-requests")
+        );
+        assert_eq!(
+            user_agent_device("Mozilla/5.0 (X11; Linux x86_64) Firefox/128.0").as_deref(),
+            Some("browser")
+        );
+        assert_eq!(user_agent_device("  "), None);
+    }
+
+    async fn derive_with(
+        state: &AppState,
+        device: Option<&str>,
+        defaults: Option<&TokenDefaults>,
+    ) -> (DeviceSource, NotificationInput) {
+        let mut input = NotificationInput {
+            notify: "hi".to_string(),
+            device: device.map(str::to_string),
+            ..Default::default()
+        };
+        let sender = SenderContext {
+            defaults,
+            token: None,
+            user_agent: Some("curl/8.4.0"),
+        };
+        let source = derive(state, &mut input, sender).await;
+        (source, input)
+    }
+
+    #[tokio::test]
+    async fn test_derive_order() {
+        let state = AppState::for_tests(AuthPolicy::default()).await;
+        let defaults = TokenDefaults {
+            title: Some("CI".to_string()),
+            device: Some("runner".to_string()),
+            channel: None,
+        };
+
+        let (source, input) = derive_with(&state, Some("laptop"), Some(&defaults)).await;
+        assert_eq!(source, DeviceSource::Payload);
+        assert_eq!(input.device.as_deref(), Some("laptop"));
+        assert_eq!(input.title.as_deref(), Some("CI"));
+
+        let (source, input) = derive_with(&state, None, Some(&defaults)).await;
+        assert_eq!(source, DeviceSource::TokenDefault);
+        assert_eq!(input.device.as_deref(), Some("runner"));
+
+        let (source, input) = derive_with(&state, None, None).await;
+        assert_eq!(source, DeviceSource::UserAgent);
+        assert_eq!(input.device.as_deref(), Some("curl"));
+    }
+}
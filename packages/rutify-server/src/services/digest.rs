@@ -0,0 +1,172 @@
+//! 邮件摘要：按用户偏好的频率汇总期间收到的通知，经 SMTP 中继发送
+
+use chrono::{DateTime, Utc};
+use rutify_core::DigestFrequency;
+use sea_orm::EntityTrait;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::db::{notifies, user_preferences, users};
+use crate::services::notify;
+use crate::services::sinks::Sink;
+use crate::state::AppState;
+
+/// 检查是否有摘要到期的间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 摘要中列出的通知条数上限，其余只计数
+const MAX_LISTED: u64 = 20;
+
+fn period(frequency: DigestFrequency) -> Option<chrono::Duration> {
+    match frequency {
+        DigestFrequency::Off => None,
+        DigestFrequency::Hourly => Some(chrono::Duration::hours(1)),
+        DigestFrequency::Daily => Some(chrono::Duration::days(1)),
+    }
+}
+
+/// 摘要正文：总数加上逐条的优先级、设备与标题
+fn render(total: u64, items: &[notifies::Model]) -> String {
+    let mut body = format!("{total} notification(s) since the last digest:\n");
+    for item in items {
+        let data = item.to_data();
+        body.push_str(&format!(
+            "\n[P{}] {} - {}",
+            data.priority, data.device, data.title
+        ));
+    }
+    if total > items.len() as u64 {
+        body.push_str(&format!("\n\n... and {} more", total - items.len() as u64));
+    }
+    body
+}
+
+/// 为到期的用户发送摘要；发送失败同样推进时间，避免每分钟重试
+async fn send_due(state: &AppState, now: DateTime<Utc>) -> Result<(), sea_orm::DbErr> {
+    for row in user_preferences::with_digest(&state.db).await? {
+        let preferences = row.preferences();
+        let Some(period) = period(preferences.digest) else {
+            continue;
+        };
+        let since = row.last_digest_at.unwrap_or(row.updated_at);
+        if now - since < period {
+            continue;
+        }
+        user_preferences::record_digest(&state.db, row.user_id, now).await?;
+
+        let Some(user) = users::Entity::find_by_id(row.user_id)
+            .one(&state.db)
+            .await?
+        else {
+            continue;
+        };
+        if user.disabled || !user.email_verified || user.email.is_empty() {
+            continue;
+        }
+        let (total, items) = notifies::digest_between(
+            &state.db,
+            since,
+            now,
+            &user.username,
+            &preferences.channels,
+            MAX_LISTED,
+        )
+        .await?;
+        if total == 0 {
+            continue;
+        }
+
+        let data = notify::system_data("Rutify digest", render(total, &items));
+        match Sink::Email.deliver(&state.config, &user.email, &data).await {
+            Ok(()) => info!(
+                "Sent digest of {} notifications to {}",
+                total, user.username
+            ),
+            Err(e) => warn!("Failed to send digest to {}: {:#}", user.username, e),
+        }
+    }
+    Ok(())
+}
+
+/// 启动摘要任务，仅主节点发送
+pub(crate) fn spawn(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            ticker.tick().await;
+            if !state.leadership.is_leader() {
+                continue;
+            }
+            if let Err(e) = send_due(&state, Utc::now()).await {
+                warn!("Digest check errors: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::auth::mode::AuthPolicy;
+    use rutify_core::{NotificationData, NotificationPreferences};
+    use sea_orm::{ActiveModelTrait, Set};
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_digest_window() {
+        let state = AppState::for_tests(AuthPolicy::default()).await;
+        let user = users::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            username: Set("alice".to_string()),
+            password_hash: Set(String::new()),
+            email: Set("alice@example.com".to_string()),
+            role: Set(users::UserRole::User),
+            email_verified: Set(true),
+            disabled: Set(false),
+            created_at: Set(Utc::now().into()),
+            updated_at: Set(Utc::now().into()),
+        }
+        .insert(&state.db)
+        .await
+        .unwrap();
+        let preferences = NotificationPreferences {
+            channels: vec!["ops".to_string()],
+            digest: DigestFrequency::Hourly,
+            ..Default::default()
+        };
+        user_preferences::save(&state.db, user.id, &preferences)
+            .await
+            .unwrap();
+        let since = user_preferences::with_digest(&state.db).await.unwrap()[0]
+            .last_digest_at
+            .unwrap();
+
+        let send = |channel: Option<&str>, recipient: Option<&str>| {
+            let mut data: NotificationData = notify::system_data("Disk", "full".to_string());
+            data.channel = channel.map(str::to_string);
+            data.recipient = recipient.map(str::to_string);
+            notifies::insert_new_notify(&state.db, data, None, false, "payload", None)
+        };
+        send(Some("ops"), None).await.unwrap();
+        send(None, Some("alice")).await.unwrap();
+        send(Some("ci"), None).await.unwrap();
+        send(None, Some("bob")).await.unwrap();
+
+        let (total, items) = notifies::digest_between(
+            &state.db,
+            since,
+            Utc::now() + chrono::Duration::seconds(1),
+            "alice",
+            &preferences.channels,
+            1,
+        )
+        .await
+        .unwrap();
+        assert_eq!((total, items.len()), (2, 1));
+        let body = render(total, &items);
+        assert!(body.starts_with("2 notification(s)"));
+        assert!(body.contains("[P3] rutify - Disk"));
+        assert!(body.ends_with("... and 1 more"));
+    }
+}
@@ -0,0 +1,251 @@
+use chrono::Utc;
+use rutify_core::{EventKind, MAX_PRIORITY};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter,
+};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+use std::sync::{Arc, RwLock};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+use crate::db::{escalations, notifies};
+use crate::error::AppError;
+use crate::services::notify;
+use crate::services::sinks::Sink;
+use crate::state::AppState;
+
+/// 升级动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EscalationAction {
+    /// 以 `escalate` 事件重新广播
+    Rebroadcast,
+    /// 优先级加一并广播 `update` 事件
+    Bump,
+    /// 转发到外部渠道
+    Forward,
+}
+
+impl EscalationAction {
+    pub(crate) fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "rebroadcast" => Some(Self::Rebroadcast),
+            "bump" => Some(Self::Bump),
+            "forward" => Some(Self::Forward),
+            _ => None,
+        }
+    }
+}
+
+/// 待触发的升级任务，按到期时间排序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct EscalationJob {
+    due: Instant,
+    notify_id: i32,
+    rule_id: i32,
+}
+
+/// 已启用升级规则的缓存与任务调度入口
+pub(crate) struct EscalationRegistry {
+    rules: RwLock<Vec<escalations::Model>>,
+    jobs: mpsc::UnboundedSender<EscalationJob>,
+}
+
+impl EscalationRegistry {
+    /// 返回注册表与任务接收端，接收端交给 [`spawn`] 启动的调度任务
+    pub(crate) fn new() -> (Self, mpsc::UnboundedReceiver<EscalationJob>) {
+        let (jobs, receiver) = mpsc::unbounded_channel();
+        let registry = Self {
+            rules: RwLock::new(Vec::new()),
+            jobs,
+        };
+        (registry, receiver)
+    }
+
+    /// 从数据库重新加载所有启用的升级规则
+    pub(crate) async fn reload(&self, db: &DatabaseConnection) -> Result<(), AppError> {
+        let rules = escalations::Entity::find()
+            .filter(escalations::Column::Enabled.eq(true))
+            .all(db)
+            .await?;
+
+        info!("Loaded {} escalation rules", rules.len());
+        *self.rules.write().unwrap() = rules;
+        Ok(())
+    }
+
+    /// 为通知安排所有匹配规则的升级任务，已过期的规则不再补发
+    pub(crate) fn track(&self, notify: &notifies::Model) {
+        if notify.acked_at.is_some() || notify.resolved_at.is_some() {
+            return;
+        }
+
+        let now = Utc::now();
+        let rules = self.rules.read().unwrap();
+        for rule in rules
+            .iter()
+            .filter(|rule| notify.priority >= rule.min_priority)
+        {
+            let deadline =
+                notify.received_at + chrono::Duration::minutes(rule.after_minutes.into());
+            let Ok(delay) = (deadline - now).to_std() else {
+                continue;
+            };
+            let _ = self.jobs.send(EscalationJob {
+                due: Instant::now() + delay,
+                notify_id: notify.id,
+                rule_id: rule.id,
+            });
+        }
+    }
+
+    fn rule(&self, rule_id: i32) -> Option<escalations::Model> {
+        self.rules
+            .read()
+            .unwrap()
+            .iter()
+            .find(|rule| rule.id == rule_id)
+            .cloned()
+    }
+
+    /// 重启后恢复尚未确认通知的升级任务
+    pub(crate) async fn restore(&self, db: &DatabaseConnection) -> Result<(), AppError> {
+        let Some(max_minutes) = self
+            .rules
+            .read()
+            .unwrap()
+            .iter()
+            .map(|rule| rule.after_minutes)
+            .max()
+        else {
+            return Ok(());
+        };
+
+        let since = Utc::now() - chrono::Duration::minutes(max_minutes.into());
+        let pending = notifies::Entity::find()
+            .filter(notifies::Column::ReceivedAt.gt(since))
+            .filter(notifies::Column::AckedAt.is_null())
+            .filter(notifies::Column::ResolvedAt.is_null())
+            .filter(notifies::not_expired(Utc::now()))
+            .all(db)
+            .await?;
+
+        for notify in &pending {
+            self.track(notify);
+        }
+        Ok(())
+    }
+}
+
+/// 启动升级调度任务
+///
+/// 升级任务在接收通知的节点上安排与触发；本节点成为主节点时从数据库恢复尚未确认通知的升级任务，
+/// 接管重启或失联节点上丢失的任务。
+pub(crate) fn spawn(state: Arc<AppState>, mut jobs: mpsc::UnboundedReceiver<EscalationJob>) {
+    let restore_state = Arc::clone(&state);
+    tokio::spawn(async move {
+        let mut leader = restore_state.leadership.subscribe();
+        loop {
+            if leader.wait_for(|leader| *leader).await.is_err() {
+                break;
+            }
+            if let Err(e) = restore_state.escalations.restore(&restore_state.db).await {
+                warn!("Failed to restore escalations: {}", e);
+            }
+            if leader.wait_for(|leader| !*leader).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    tokio::spawn(async move {
+        let mut queue: BinaryHeap<Reverse<EscalationJob>> = BinaryHeap::new();
+        // 已排队的（通知, 规则），再次成为主节点时恢复的任务不会重复触发
+        let mut queued: HashSet<(i32, i32)> = HashSet::new();
+
+        loop {
+            let next_due = queue.peek().map(|Reverse(job)| job.due);
+            tokio::select! {
+                job = jobs.recv() => match job {
+                    Some(job) => {
+                        if queued.insert((job.notify_id, job.rule_id)) {
+                            queue.push(Reverse(job));
+                        }
+                    }
+                    None => break,
+                },
+                _ = wait_until(next_due) => {
+                    let now = Instant::now();
+                    while let Some(Reverse(job)) = queue.peek().copied() {
+                        if job.due > now {
+                            break;
+                        }
+                        queue.pop();
+                        queued.remove(&(job.notify_id, job.rule_id));
+                        escalate(&state, job).await;
+                    }
+                }
+            }
+        }
+    });
+}
+
+async fn wait_until(due: Option<Instant>) {
+    match due {
+        Some(due) => tokio::time::sleep_until(due).await,
+        None => std::future::pending().await,
+    }
+}
+
+async fn escalate(state: &AppState, job: EscalationJob) {
+    // 规则可能已被删除或停用
+    let Some(rule) = state.escalations.rule(job.rule_id) else {
+        return;
+    };
+    let notify = match notifies::Entity::find_by_id(job.notify_id)
+        .one(&state.db)
+        .await
+    {
+        Ok(Some(notify)) => notify,
+        Ok(None) => return,
+        Err(e) => {
+            warn!("Database errors finding notify {}: {}", job.notify_id, e);
+            return;
+        }
+    };
+    if notify.acked_at.is_some() || notify.resolved_at.is_some() {
+        return;
+    }
+
+    info!(
+        "Escalating notify {} by rule '{}' ({})",
+        notify.id, rule.name, rule.action
+    );
+    match EscalationAction::parse(&rule.action) {
+        Some(EscalationAction::Rebroadcast) => {
+            notify::broadcast(state, EventKind::Escalated, notify.to_data());
+        }
+        Some(EscalationAction::Bump) => {
+            let priority = (notify.priority + 1).min(MAX_PRIORITY as i32);
+            let mut active_model: notifies::ActiveModel = notify.into();
+            active_model.priority = ActiveValue::Set(priority);
+            match active_model.update(&state.db).await {
+                Ok(notify) => {
+                    notify::broadcast(state, EventKind::Updated, notify.to_data());
+                }
+                Err(e) => warn!("Database errors bumping notify {}: {}", job.notify_id, e),
+            }
+        }
+        Some(EscalationAction::Forward) => {
+            let (Some(sink), Some(target)) =
+                (rule.sink.as_deref().and_then(Sink::parse), rule.target)
+            else {
+                warn!("Escalation rule '{}' has no valid sink", rule.name);
+                return;
+            };
+            notify::forward(state, sink, target, notify.to_data());
+        }
+        None => warn!("Escalation rule '{}' has unknown action", rule.name),
+    }
+}
@@ -0,0 +1,131 @@
+use rutify_core::{DEFAULT_PRIORITY, MAX_PRIORITY, NotificationInput};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::{HookNotification, str_field};
+use crate::error::AppError;
+
+/// 不作为标签输出的标签名（已体现在标题、设备名或优先级中）
+const RESERVED_LABELS: [&str; 4] = ["alertname", "severity", "priority", "instance"];
+
+/// 转换 Alertmanager Webhook（version 4）
+///
+/// 同一 alertname 的告警合并为一条通知；该组全部恢复时生成恢复通知，
+/// 按 `alertmanager:<groupKey>:<alertname>` 更新原通知而不是新增一条。
+pub(super) fn transform(payload: &Value) -> Result<Vec<HookNotification>, AppError> {
+    let alerts = payload
+        .get("alerts")
+        .and_then(Value::as_array)
+        .ok_or_else(|| {
+            AppError::BadRequest("Missing alerts in Alertmanager payload".to_string())
+        })?;
+    let group_status = str_field(payload, "/status").unwrap_or("firing");
+    let group_key = str_field(payload, "/groupKey").unwrap_or("");
+
+    let mut groups: BTreeMap<&str, Vec<&Value>> = BTreeMap::new();
+    for alert in alerts {
+        let name = str_field(alert, "/labels/alertname").unwrap_or("alert");
+        groups.entry(name).or_default().push(alert);
+    }
+
+    Ok(groups
+        .into_iter()
+        .map(|(name, alerts)| group_to_notification(group_key, group_status, name, &alerts))
+        .collect())
+}
+
+fn group_to_notification(
+    group_key: &str,
+    group_status: &str,
+    name: &str,
+    alerts: &[&Value],
+) -> HookNotification {
+    let resolved_count = alerts
+        .iter()
+        .filter(|alert| str_field(alert, "/status").unwrap_or(group_status) == "resolved")
+        .count();
+    let resolved = resolved_count == alerts.len();
+    let firing = alerts.len() - resolved_count;
+
+    let title = if resolved {
+        format!("[RESOLVED] {name}")
+    } else if alerts.len() > 1 {
+        format!("[FIRING:{firing}] {name}")
+    } else {
+        format!("[FIRING] {name}")
+    };
+
+    let lines: Vec<String> = alerts
+        .iter()
+        .map(|alert| {
+            let summary = str_field(alert, "/annotations/summary")
+                .or_else(|| str_field(alert, "/annotations/description"))
+                .or_else(|| str_field(alert, "/annotations/message"))
+                .unwrap_or(name);
+            match (alerts.len(), str_field(alert, "/labels/instance")) {
+                (1, _) | (_, None) => summary.to_string(),
+                (_, Some(instance)) => format!("{instance}: {summary}"),
+            }
+        })
+        .collect();
+
+    let instances: BTreeSet<&str> = alerts
+        .iter()
+        .filter_map(|alert| {
+            str_field(alert, "/labels/instance").or_else(|| str_field(alert, "/labels/job"))
+        })
+        .collect();
+    let device = match instances.len() {
+        1 => instances.first().copied().unwrap_or("alertmanager"),
+        _ => "alertmanager",
+    };
+
+    let priority = alerts
+        .iter()
+        .filter_map(|alert| label_priority(alert))
+        .max()
+        .unwrap_or(DEFAULT_PRIORITY);
+
+    let tags: BTreeSet<String> = alerts
+        .iter()
+        .filter_map(|alert| alert.get("labels").and_then(Value::as_object))
+        .flat_map(|labels| labels.iter())
+        .filter(|(key, _)| !RESERVED_LABELS.contains(&key.as_str()))
+        .filter_map(|(key, value)| value.as_str().map(|value| format!("{key}={value}")))
+        .collect();
+
+    HookNotification {
+        input: NotificationInput {
+            notify: lines.join("\n"),
+            title: Some(title),
+            device: Some(device.to_string()),
+            priority: Some(priority),
+            tags: tags.into_iter().collect(),
+            ..Default::default()
+        },
+        group_key: Some(format!("alertmanager:{group_key}:{name}")),
+        resolved,
+    }
+}
+
+/// 由 `priority` 或 `severity` 标签推导优先级
+fn label_priority(alert: &Value) -> Option<u8> {
+    if let Some(priority) = str_field(alert, "/labels/priority") {
+        if let Ok(value) = priority.parse::<u8>() {
+            return Some(value.clamp(1, MAX_PRIORITY));
+        }
+        return severity_priority(priority);
+    }
+    str_field(alert, "/labels/severity").and_then(severity_priority)
+}
+
+fn severity_priority(severity: &str) -> Option<u8> {
+    match severity.to_ascii_lowercase().as_str() {
+        "critical" | "page" | "emergency" | "disaster" | "p1" => Some(5),
+        "high" | "error" | "major" | "p2" => Some(4),
+        "warning" | "warn" | "average" | "p3" => Some(3),
+        "info" | "minor" | "p4" => Some(2),
+        "low" | "none" | "debug" | "p5" => Some(1),
+        _ => None,
+    }
+}
@@ -0,0 +1,62 @@
+use rutify_core::NotificationInput;
+use serde_json::Value;
+
+use super::str_field;
+use crate::error::AppError;
+
+/// 转换 Discord Webhook 负载（`content` 与 `embeds`）
+///
+/// 每条请求生成一条通知：标题取首个 embed 的 title 或 username，
+/// 正文由 content 与各 embed 的 description/fields 拼接。
+pub(super) fn transform(payload: &Value) -> Result<Vec<NotificationInput>, AppError> {
+    let embeds = payload
+        .get("embeds")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let mut lines: Vec<String> = Vec::new();
+    lines.extend(str_field(payload, "/content").map(str::to_string));
+    for (index, embed) in embeds.iter().enumerate() {
+        // 首个 embed 的标题已用作通知标题
+        if index > 0 {
+            lines.extend(str_field(embed, "/title").map(str::to_string));
+        }
+        lines.extend(str_field(embed, "/description").map(str::to_string));
+        for field in embed
+            .get("fields")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+        {
+            if let (Some(name), Some(value)) =
+                (str_field(field, "/name"), str_field(field, "/value"))
+            {
+                lines.push(format!("{name}: {value}"));
+            }
+        }
+    }
+
+    let title = embeds
+        .first()
+        .and_then(|embed| str_field(embed, "/title"))
+        .or_else(|| str_field(payload, "/username"))
+        .unwrap_or("discord");
+
+    if lines.is_empty() {
+        // 仅有标题的 embed 也是合法消息
+        if embeds.is_empty() {
+            return Err(AppError::BadRequest(
+                "Missing content or embeds in Discord payload".to_string(),
+            ));
+        }
+        lines.push(title.to_string());
+    }
+
+    Ok(vec![NotificationInput {
+        notify: lines.join("\n"),
+        title: Some(title.to_string()),
+        device: Some("discord".to_string()),
+        ..Default::default()
+    }])
+}
@@ -0,0 +1,142 @@
+use rutify_core::NotificationInput;
+use serde_json::Value;
+
+use super::str_field;
+
+/// push 通知中最多列出的提交数
+const MAX_LISTED_COMMITS: usize = 5;
+
+/// 按 `X-GitHub-Event` 转换 GitHub Webhook；不关心的事件返回 `None`
+pub(super) fn transform(event: &str, payload: &Value) -> Option<NotificationInput> {
+    let repo = str_field(payload, "/repository/full_name").unwrap_or("github");
+    let sender = str_field(payload, "/sender/login");
+    let action = str_field(payload, "/action").unwrap_or("updated");
+
+    let (title, notify) = match event {
+        "ping" => (
+            "webhook connected".to_string(),
+            str_field(payload, "/zen").unwrap_or("pong").to_string(),
+        ),
+        "push" => push(payload)?,
+        "pull_request" => (
+            format!(
+                "PR #{} {}",
+                payload.pointer("/pull_request/number")?,
+                pull_request_action(payload, action)
+            ),
+            format!(
+                "{}\n{}",
+                str_field(payload, "/pull_request/title").unwrap_or(""),
+                str_field(payload, "/pull_request/html_url").unwrap_or("")
+            ),
+        ),
+        "issues" => (
+            format!("Issue #{} {}", payload.pointer("/issue/number")?, action),
+            format!(
+                "{}\n{}",
+                str_field(payload, "/issue/title").unwrap_or(""),
+                str_field(payload, "/issue/html_url").unwrap_or("")
+            ),
+        ),
+        "issue_comment" => (
+            format!("New comment on #{}", payload.pointer("/issue/number")?),
+            str_field(payload, "/comment/body")
+                .unwrap_or("")
+                .to_string(),
+        ),
+        "release" => (
+            format!(
+                "Release {} {}",
+                str_field(payload, "/release/tag_name").unwrap_or(""),
+                action
+            ),
+            str_field(payload, "/release/html_url")
+                .unwrap_or("")
+                .to_string(),
+        ),
+        "workflow_run" => {
+            if action != "completed" {
+                return None;
+            }
+            let conclusion = str_field(payload, "/workflow_run/conclusion").unwrap_or("unknown");
+            (
+                format!(
+                    "Workflow {} {}",
+                    str_field(payload, "/workflow_run/name").unwrap_or(""),
+                    conclusion
+                ),
+                str_field(payload, "/workflow_run/html_url")
+                    .unwrap_or("")
+                    .to_string(),
+            )
+        }
+        "star" => (
+            format!("Star {}", action),
+            format!("{} starred the repository", sender.unwrap_or("someone")),
+        ),
+        _ => (
+            format!("{} {}", event, action),
+            sender
+                .map(|sender| format!("Triggered by {sender}"))
+                .unwrap_or_default(),
+        ),
+    };
+
+    Some(NotificationInput {
+        notify: notify.trim().to_string(),
+        title: Some(format!("[{repo}] {title}")),
+        device: Some("github".to_string()),
+        ..Default::default()
+    })
+}
+
+fn push(payload: &Value) -> Option<(String, String)> {
+    let branch = str_field(payload, "/ref")?;
+    let branch = branch
+        .strip_prefix("refs/heads/")
+        .or_else(|| branch.strip_prefix("refs/tags/"))
+        .unwrap_or(branch);
+    let commits = payload
+        .get("commits")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    if commits.is_empty() {
+        return None;
+    }
+
+    let mut lines: Vec<String> = commits
+        .iter()
+        .take(MAX_LISTED_COMMITS)
+        .filter_map(|commit| str_field(commit, "/message"))
+        .map(|message| format!("- {}", message.lines().next().unwrap_or("")))
+        .collect();
+    if commits.len() > MAX_LISTED_COMMITS {
+        lines.push(format!("… and {} more", commits.len() - MAX_LISTED_COMMITS));
+    }
+    if let Some(pusher) = str_field(payload, "/pusher/name") {
+        lines.push(format!("by {pusher}"));
+    }
+
+    let noun = if commits.len() == 1 {
+        "commit"
+    } else {
+        "commits"
+    };
+    Some((
+        format!("{} {} pushed to {}", commits.len(), noun, branch),
+        lines.join("\n"),
+    ))
+}
+
+fn pull_request_action<'a>(payload: &Value, action: &'a str) -> &'a str {
+    let merged = payload
+        .pointer("/pull_request/merged")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    if action == "closed" && merged {
+        "merged"
+    } else {
+        action
+    }
+}
@@ -0,0 +1,38 @@
+use rutify_core::NotificationInput;
+use serde_json::Value;
+
+use super::str_field;
+use crate::error::AppError;
+
+/// 转换 Grafana 告警 Webhook，兼容统一告警与旧版告警负载
+pub(super) fn transform(payload: &Value) -> Result<Vec<NotificationInput>, AppError> {
+    let title = str_field(payload, "/title")
+        .or_else(|| str_field(payload, "/ruleName"))
+        .ok_or_else(|| AppError::BadRequest("Missing title in Grafana payload".to_string()))?;
+
+    let notify = str_field(payload, "/message")
+        .map(str::to_string)
+        .or_else(|| {
+            // 统一告警在 message 为空时退回到各条告警的 summary
+            let summaries: Vec<&str> = payload
+                .get("alerts")
+                .and_then(Value::as_array)?
+                .iter()
+                .filter_map(|alert| str_field(alert, "/annotations/summary"))
+                .collect();
+            (!summaries.is_empty()).then(|| summaries.join("\n"))
+        })
+        .unwrap_or_else(|| {
+            str_field(payload, "/state")
+                .or_else(|| str_field(payload, "/status"))
+                .unwrap_or("alert")
+                .to_string()
+        });
+
+    Ok(vec![NotificationInput {
+        notify,
+        title: Some(title.to_string()),
+        device: Some("grafana".to_string()),
+        ..Default::default()
+    }])
+}
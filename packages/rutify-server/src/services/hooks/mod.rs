@@ -0,0 +1,396 @@
+use axum::http::HeaderMap;
+use hmac::{Hmac, Mac};
+use rutify_core::NotificationInput;
+use serde_json::Value;
+use sha2::Sha256;
+
+use crate::error::AppError;
+
+mod alertmanager;
+mod discord;
+mod github;
+mod grafana;
+mod slack;
+mod uptime_kuma;
+
+/// Webhook 转换结果
+pub(crate) struct HookNotification {
+    pub input: NotificationInput,
+    /// 分组键，用于恢复通知时定位原记录
+    pub group_key: Option<String>,
+    /// 为 true 时更新原通知而不是新增
+    pub resolved: bool,
+}
+
+impl From<NotificationInput> for HookNotification {
+    fn from(input: NotificationInput) -> Self {
+        Self {
+            input,
+            group_key: None,
+            resolved: false,
+        }
+    }
+}
+
+/// 支持的 Webhook 来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Provider {
+    Github,
+    Grafana,
+    Alertmanager,
+    UptimeKuma,
+    Slack,
+    Discord,
+}
+
+impl Provider {
+    pub(crate) const ALL: [Self; 6] = [
+        Self::Github,
+        Self::Grafana,
+        Self::Alertmanager,
+        Self::UptimeKuma,
+        Self::Slack,
+        Self::Discord,
+    ];
+
+    pub(crate) fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "github" => Some(Self::Github),
+            "grafana" => Some(Self::Grafana),
+            "alertmanager" => Some(Self::Alertmanager),
+            "uptime-kuma" | "uptime_kuma" | "uptimekuma" => Some(Self::UptimeKuma),
+            "slack" => Some(Self::Slack),
+            "discord" => Some(Self::Discord),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Self::Github => "github",
+            Self::Grafana => "grafana",
+            Self::Alertmanager => "alertmanager",
+            Self::UptimeKuma => "uptime-kuma",
+            Self::Slack => "slack",
+            Self::Discord => "discord",
+        }
+    }
+
+    /// 共享密钥的配置项 `RUTIFY_HOOK_SECRET_<PROVIDER>`，如 `RUTIFY_HOOK_SECRET_UPTIME_KUMA`
+    pub(crate) fn secret_key(self) -> String {
+        format!(
+            "RUTIFY_HOOK_SECRET_{}",
+            self.name().replace('-', "_").to_ascii_uppercase()
+        )
+    }
+
+    /// 校验共享密钥
+    ///
+    /// GitHub 使用 `X-Hub-Signature-256` 的 HMAC 签名；其他来源接受
+    /// `X-Rutify-Secret` 头、`Authorization: Bearer <secret>` 或 `?secret=` 查询参数。
+    pub(crate) fn verify_secret(
+        self,
+        secret: &str,
+        headers: &HeaderMap,
+        query_secret: Option<&str>,
+        body: &[u8],
+    ) -> Result<(), AppError> {
+        let valid = match self {
+            Self::Github => headers
+                .get("x-hub-signature-256")
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|signature| verify_github_signature(secret, signature, body)),
+            _ => headers
+                .get("x-rutify-secret")
+                .and_then(|value| value.to_str().ok())
+                .or_else(|| {
+                    headers
+                        .get(axum::http::header::AUTHORIZATION)
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(|value| value.strip_prefix("Bearer "))
+                })
+                .or(query_secret)
+                .is_some_and(|provided| constant_time_eq(provided.as_bytes(), secret.as_bytes())),
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(AppError::AuthError(format!(
+                "Invalid {} webhook secret",
+                self.name()
+            )))
+        }
+    }
+
+    /// 将来源的原生 Webhook 负载转换为通知，单次请求可能产生多条通知
+    pub(crate) fn transform(
+        self,
+        headers: &HeaderMap,
+        payload: &Value,
+    ) -> Result<Vec<HookNotification>, AppError> {
+        let inputs = match self {
+            Self::Github => {
+                let event = headers
+                    .get("x-github-event")
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or("unknown");
+                github::transform(event, payload).into_iter().collect()
+            }
+            Self::Grafana => grafana::transform(payload)?,
+            Self::Alertmanager => return alertmanager::transform(payload),
+            Self::UptimeKuma => uptime_kuma::transform(payload)?,
+            Self::Slack => slack::transform(payload)?,
+            Self::Discord => discord::transform(payload)?,
+        };
+        Ok(inputs.into_iter().map(HookNotification::from).collect())
+    }
+}
+
+fn verify_github_signature(secret: &str, signature: &str, body: &[u8]) -> bool {
+    let Some(expected) = signature
+        .strip_prefix("sha256=")
+        .and_then(|hex_text| hex::decode(hex_text).ok())
+    else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(body);
+    mac.verify_slice(&expected).is_ok()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// 读取 JSON 字符串字段，缺失或为空时返回 `None`
+fn str_field<'a>(value: &'a Value, pointer: &str) -> Option<&'a str> {
+    value
+        .pointer(pointer)
+        .and_then(Value::as_str)
+        .filter(|text| !text.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_provider() {
+        assert_eq!(Provider::parse("GitHub"), Some(Provider::Github));
+        assert_eq!(Provider::parse("uptime-kuma"), Some(Provider::UptimeKuma));
+        assert_eq!(Provider::parse("jenkins"), None);
+    }
+
+    #[test]
+    fn test_github_signature() {
+        let body = br#"{"zen":"Keep it logically awesome."}"#;
+        let mut mac = Hmac::<Sha256>::new_from_slice(b"s3cret").unwrap();
+        mac.update(body);
+        let signature = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-hub-signature-256", signature.parse().unwrap());
+        assert!(
+            Provider::Github
+                .verify_secret("s3cret", &headers, None, body)
+                .is_ok()
+        );
+        assert!(
+            Provider::Github
+                .verify_secret("other", &headers, None, body)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_shared_secret_from_query() {
+        let headers = HeaderMap::new();
+        assert!(
+            Provider::Grafana
+                .verify_secret("abc", &headers, Some("abc"), b"{}")
+                .is_ok()
+        );
+        assert!(
+            Provider::Grafana
+                .verify_secret("abc", &headers, Some("abd"), b"{}")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_github_push() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-github-event", "push".parse().unwrap());
+        let payload = json!({
+            "ref": "refs/heads/main",
+            "repository": { "full_name": "alone-wolf/rutify" },
+            "pusher": { "name": "alice" },
+            "commits": [{ "message": "Fix bug\n\nDetails" }, { "message": "Add docs" }],
+        });
+
+        let items = Provider::Github.transform(&headers, &payload).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(
+            items[0].input.title.as_deref(),
+            Some("[alone-wolf/rutify] 2 commits pushed to main")
+        );
+        assert!(items[0].input.notify.contains("- Fix bug"));
+        assert_eq!(items[0].input.device.as_deref(), Some("github"));
+    }
+
+    #[test]
+    fn test_alertmanager_alerts() {
+        let payload = json!({
+            "status": "firing",
+            "groupKey": "{}:{alertname=\"HighCPU\"}",
+            "alerts": [{
+                "status": "firing",
+                "labels": { "alertname": "HighCPU", "instance": "web1:9100", "severity": "critical", "team": "ops" },
+                "annotations": { "summary": "CPU above 90%" },
+            }],
+        });
+
+        let items = Provider::Alertmanager
+            .transform(&HeaderMap::new(), &payload)
+            .unwrap();
+        assert_eq!(items.len(), 1);
+        assert!(!items[0].resolved);
+        assert_eq!(items[0].input.title.as_deref(), Some("[FIRING] HighCPU"));
+        assert_eq!(items[0].input.notify, "CPU above 90%");
+        assert_eq!(items[0].input.device.as_deref(), Some("web1:9100"));
+        assert_eq!(items[0].input.priority, Some(5));
+        assert_eq!(items[0].input.tags, vec!["team=ops".to_string()]);
+        assert_eq!(
+            items[0].group_key.as_deref(),
+            Some("alertmanager:{}:{alertname=\"HighCPU\"}:HighCPU")
+        );
+    }
+
+    #[test]
+    fn test_alertmanager_groups_by_alertname() {
+        let payload = json!({
+            "status": "resolved",
+            "groupKey": "g",
+            "alerts": [
+                { "status": "resolved", "labels": { "alertname": "DiskFull", "instance": "a", "severity": "warning" } },
+                { "status": "resolved", "labels": { "alertname": "DiskFull", "instance": "b", "severity": "warning" } },
+                { "status": "firing", "labels": { "alertname": "Down", "instance": "c" } },
+            ],
+        });
+
+        let items = Provider::Alertmanager
+            .transform(&HeaderMap::new(), &payload)
+            .unwrap();
+        assert_eq!(items.len(), 2);
+
+        let disk = &items[0];
+        assert!(disk.resolved);
+        assert_eq!(disk.input.title.as_deref(), Some("[RESOLVED] DiskFull"));
+        assert_eq!(disk.input.notify, "a: DiskFull\nb: DiskFull");
+        assert_eq!(disk.input.device.as_deref(), Some("alertmanager"));
+        assert_eq!(disk.input.priority, Some(3));
+        assert_eq!(disk.group_key.as_deref(), Some("alertmanager:g:DiskFull"));
+
+        assert!(!items[1].resolved);
+        assert_eq!(items[1].input.title.as_deref(), Some("[FIRING] Down"));
+    }
+
+    #[test]
+    fn test_grafana_alert() {
+        let payload = json!({
+            "title": "[FIRING:1] Disk full",
+            "message": "Disk usage is 97%",
+            "status": "firing",
+        });
+
+        let items = Provider::Grafana
+            .transform(&HeaderMap::new(), &payload)
+            .unwrap();
+        assert_eq!(
+            items[0].input.title.as_deref(),
+            Some("[FIRING:1] Disk full")
+        );
+        assert_eq!(items[0].input.notify, "Disk usage is 97%");
+    }
+
+    #[test]
+    fn test_uptime_kuma_down() {
+        let payload = json!({
+            "heartbeat": { "status": 0, "msg": "Connection refused" },
+            "monitor": { "name": "Blog", "url": "https://blog.example.com" },
+            "msg": "[Blog] [🔴 Down] Connection refused",
+        });
+
+        let items = Provider::UptimeKuma
+            .transform(&HeaderMap::new(), &payload)
+            .unwrap();
+        assert_eq!(items[0].input.title.as_deref(), Some("Blog is DOWN"));
+        assert_eq!(items[0].input.notify, "[Blog] [🔴 Down] Connection refused");
+        assert_eq!(items[0].input.device.as_deref(), Some("Blog"));
+    }
+
+    #[test]
+    fn test_slack_text() {
+        let payload = json!({ "text": "Deploy finished", "username": "ci-bot" });
+
+        let items = Provider::Slack
+            .transform(&HeaderMap::new(), &payload)
+            .unwrap();
+        assert_eq!(items[0].input.title.as_deref(), Some("ci-bot"));
+        assert_eq!(items[0].input.notify, "Deploy finished");
+        assert_eq!(items[0].input.device.as_deref(), Some("slack"));
+    }
+
+    #[test]
+    fn test_slack_attachments_fallback() {
+        let payload = json!({
+            "attachments": [{ "title": "Backup", "fallback": "Backup failed on db1" }],
+        });
+
+        let items = Provider::Slack
+            .transform(&HeaderMap::new(), &payload)
+            .unwrap();
+        assert_eq!(items[0].input.title.as_deref(), Some("Backup"));
+        assert_eq!(items[0].input.notify, "Backup failed on db1");
+    }
+
+    #[test]
+    fn test_discord_embeds() {
+        let payload = json!({
+            "content": "New release",
+            "embeds": [{
+                "title": "v1.2.0",
+                "description": "Bug fixes",
+                "fields": [{ "name": "Author", "value": "alice" }],
+            }],
+        });
+
+        let items = Provider::Discord
+            .transform(&HeaderMap::new(), &payload)
+            .unwrap();
+        assert_eq!(items[0].input.title.as_deref(), Some("v1.2.0"));
+        assert_eq!(
+            items[0].input.notify,
+            "New release\nBug fixes\nAuthor: alice"
+        );
+        assert_eq!(items[0].input.device.as_deref(), Some("discord"));
+    }
+
+    #[test]
+    fn test_invalid_payload_is_rejected() {
+        assert!(
+            Provider::Alertmanager
+                .transform(&HeaderMap::new(), &json!({ "foo": 1 }))
+                .is_err()
+        );
+        assert!(
+            Provider::Discord
+                .transform(&HeaderMap::new(), &json!({ "username": "bot" }))
+                .is_err()
+        );
+    }
+}
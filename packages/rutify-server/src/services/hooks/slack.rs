@@ -0,0 +1,66 @@
+use rutify_core::NotificationInput;
+use serde_json::Value;
+
+use super::str_field;
+use crate::error::AppError;
+
+/// 转换 Slack Incoming Webhook 负载
+///
+/// 优先使用 `text`，缺失时依次退回到 blocks 与 attachments 中的文本。
+pub(super) fn transform(payload: &Value) -> Result<Vec<NotificationInput>, AppError> {
+    let attachments = payload
+        .get("attachments")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let mut lines: Vec<String> = Vec::new();
+    if let Some(text) = str_field(payload, "/text") {
+        lines.push(text.to_string());
+    } else {
+        lines.extend(block_texts(payload));
+    }
+    if lines.is_empty() {
+        for attachment in attachments {
+            let text = str_field(attachment, "/text")
+                .or_else(|| str_field(attachment, "/fallback"))
+                .or_else(|| str_field(attachment, "/pretext"));
+            lines.extend(text.map(str::to_string));
+        }
+    }
+    if lines.is_empty() {
+        return Err(AppError::BadRequest(
+            "Missing text in Slack payload".to_string(),
+        ));
+    }
+
+    let title = attachments
+        .iter()
+        .find_map(|attachment| str_field(attachment, "/title"))
+        .or_else(|| str_field(payload, "/username"))
+        .unwrap_or("slack");
+
+    Ok(vec![NotificationInput {
+        notify: lines.join("\n"),
+        title: Some(title.to_string()),
+        device: Some(
+            str_field(payload, "/channel")
+                .unwrap_or("slack")
+                .to_string(),
+        ),
+        ..Default::default()
+    }])
+}
+
+/// 提取 Block Kit 中 section/header 块的文本
+fn block_texts(payload: &Value) -> Vec<String> {
+    payload
+        .get("blocks")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|block| str_field(block, "/text/text"))
+        .map(str::to_string)
+        .collect()
+}
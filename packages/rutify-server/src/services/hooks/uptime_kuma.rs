@@ -0,0 +1,31 @@
+use rutify_core::NotificationInput;
+use serde_json::Value;
+
+use super::str_field;
+use crate::error::AppError;
+
+/// 转换 Uptime Kuma Webhook；测试通知没有 heartbeat/monitor，仅携带 msg
+pub(super) fn transform(payload: &Value) -> Result<Vec<NotificationInput>, AppError> {
+    let msg = str_field(payload, "/msg")
+        .or_else(|| str_field(payload, "/heartbeat/msg"))
+        .ok_or_else(|| AppError::BadRequest("Missing msg in Uptime Kuma payload".to_string()))?;
+    let monitor = str_field(payload, "/monitor/name");
+
+    let title = match (
+        monitor,
+        payload.pointer("/heartbeat/status").and_then(Value::as_i64),
+    ) {
+        (Some(name), Some(0)) => format!("{name} is DOWN"),
+        (Some(name), Some(1)) => format!("{name} is UP"),
+        (Some(name), Some(3)) => format!("{name} is under maintenance"),
+        (Some(name), _) => name.to_string(),
+        (None, _) => "Uptime Kuma".to_string(),
+    };
+
+    Ok(vec![NotificationInput {
+        notify: msg.to_string(),
+        title: Some(title),
+        device: Some(monitor.unwrap_or("uptime-kuma").to_string()),
+        ..Default::default()
+    }])
+}
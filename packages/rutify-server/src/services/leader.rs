@@ -0,0 +1,152 @@
+//! 多个实例共用一个数据库时，通过 leases 表中的租约选出唯一执行后台任务的节点
+//!
+//! 过期清理、数据库维护、心跳逾期检查、主动检查与升级任务恢复只在持有租约的节点上运行；
+//! 单实例部署时本节点启动后立即成为主节点。
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::db::leases;
+use crate::state::AppState;
+
+const LEASE_NAME: &str = "background-jobs";
+
+/// 租约有效期；主节点失联后其他节点最多等待这么久接管
+const LEASE_TTL: Duration = Duration::from_secs(30);
+
+/// 续约与抢占的间隔，远小于有效期，避免续约稍有延迟就失去租约
+const RENEW_INTERVAL: Duration = Duration::from_secs(10);
+
+/// 本节点是否为主节点
+pub(crate) struct Leadership {
+    node: String,
+    leader: watch::Sender<bool>,
+}
+
+impl Leadership {
+    pub(crate) fn new() -> Self {
+        Self {
+            node: Uuid::new_v4().to_string(),
+            leader: watch::Sender::new(false),
+        }
+    }
+
+    /// 始终为主节点，不参与选举
+    #[cfg(test)]
+    pub(crate) fn always() -> Self {
+        Self {
+            node: "test".to_string(),
+            leader: watch::Sender::new(true),
+        }
+    }
+
+    pub(crate) fn is_leader(&self) -> bool {
+        *self.leader.borrow()
+    }
+
+    /// 主节点身份变化的通知
+    pub(crate) fn subscribe(&self) -> watch::Receiver<bool> {
+        self.leader.subscribe()
+    }
+
+    fn set(&self, leader: bool) {
+        let changed = self.leader.send_if_modified(|current| {
+            let changed = *current != leader;
+            *current = leader;
+            changed
+        });
+        if changed && leader {
+            info!("Node {} is now the leader for background jobs", self.node);
+        } else if changed {
+            warn!(
+                "Node {} is no longer the leader for background jobs",
+                self.node
+            );
+        }
+    }
+}
+
+/// 启动选举任务：定期取得或续约租约；数据库出错时立即退为从节点，等待租约到期后由其他节点接管
+pub(crate) fn spawn(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let leadership = &state.leadership;
+        let ttl = chrono::Duration::from_std(LEASE_TTL).expect("lease ttl fits chrono");
+        let mut ticker = tokio::time::interval(RENEW_INTERVAL);
+        loop {
+            ticker.tick().await;
+            match leases::try_acquire(&state.db, LEASE_NAME, &leadership.node, ttl).await {
+                Ok(acquired) => leadership.set(acquired),
+                Err(e) => {
+                    warn!("Failed to renew leader lease: {}", e);
+                    leadership.set(false);
+                }
+            }
+        }
+    });
+}
+
+/// 停止时释放租约，其他节点下次抢占即可接管
+pub(crate) async fn release(state: &AppState) {
+    if !state.leadership.is_leader() {
+        return;
+    }
+    state.leadership.set(false);
+    if let Err(e) = leases::release(&state.db, LEASE_NAME, &state.leadership.node).await {
+        warn!("Failed to release leader lease: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_lease_has_single_holder() {
+        let db = sea_orm::Database::connect("sqlite::memory:")
+            .await
+            .expect("in-memory sqlite");
+        crate::db::initialize::initial(&db).await;
+        let ttl = chrono::Duration::seconds(30);
+
+        assert!(
+            leases::try_acquire(&db, LEASE_NAME, "a", ttl)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !leases::try_acquire(&db, LEASE_NAME, "b", ttl)
+                .await
+                .unwrap()
+        );
+        // 持有者续约成功
+        assert!(
+            leases::try_acquire(&db, LEASE_NAME, "a", ttl)
+                .await
+                .unwrap()
+        );
+
+        // 释放后其他节点立即接管
+        leases::release(&db, LEASE_NAME, "a").await.unwrap();
+        assert!(
+            leases::try_acquire(&db, LEASE_NAME, "b", ttl)
+                .await
+                .unwrap()
+        );
+
+        // 过期的租约可以被抢占
+        let expired = chrono::Duration::seconds(-1);
+        assert!(
+            leases::try_acquire(&db, LEASE_NAME, "b", expired)
+                .await
+                .unwrap()
+        );
+        assert!(
+            leases::try_acquire(&db, LEASE_NAME, "a", ttl)
+                .await
+                .unwrap()
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use anyhow::{Context, Result, bail};
+use rutify_core::{MAX_CORRELATION_ID_LEN, NotificationInput, UpdateNotifyRequest, is_e2e};
+
+use crate::bootstrap::source::ConfigSource;
+use crate::error::AppError;
+
+/// 截断后追加的标记
+const TRUNCATED_MARKER: &str = " … (truncated)";
+
+/// 超出大小限制时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum OversizePolicy {
+    /// 拒绝请求，HTTP 返回 413
+    Reject,
+    /// 截断并追加标记，完整正文保存为附件
+    Truncate,
+}
+
+/// 通知标题与正文的大小限制（按 UTF-8 字节计）
+#[derive(Debug, Clone)]
+pub(crate) struct SizeLimits {
+    pub(crate) max_title_bytes: usize,
+    pub(crate) max_body_bytes: usize,
+    pub(crate) policy: OversizePolicy,
+}
+
+impl Default for SizeLimits {
+    fn default() -> Self {
+        Self {
+            max_title_bytes: 256,
+            max_body_bytes: 64 * 1024,
+            policy: OversizePolicy::Truncate,
+        }
+    }
+}
+
+impl SizeLimits {
+    /// 读取 `RUTIFY_MAX_TITLE_BYTES`（默认 256）、`RUTIFY_MAX_BODY_BYTES`（默认 65536）
+    /// 与 `RUTIFY_OVERSIZE_POLICY`（`truncate` 或 `reject`，默认 `truncate`）
+    pub(crate) fn from_source(source: &ConfigSource) -> Result<Self> {
+        let defaults = Self::default();
+        let max_title_bytes = match source.var("RUTIFY_MAX_TITLE_BYTES") {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid RUTIFY_MAX_TITLE_BYTES: {text}"))?,
+            Err(_) => defaults.max_title_bytes,
+        };
+        let max_body_bytes = match source.var("RUTIFY_MAX_BODY_BYTES") {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid RUTIFY_MAX_BODY_BYTES: {text}"))?,
+            Err(_) => defaults.max_body_bytes,
+        };
+        let policy = match source.var("RUTIFY_OVERSIZE_POLICY") {
+            Ok(text) => match text.trim().to_ascii_lowercase().as_str() {
+                "truncate" => OversizePolicy::Truncate,
+                "reject" => OversizePolicy::Reject,
+                _ => bail!("invalid RUTIFY_OVERSIZE_POLICY: {text} (expected truncate or reject)"),
+            },
+            Err(_) => defaults.policy,
+        };
+        // 限制必须能容纳截断标记
+        if max_title_bytes <= TRUNCATED_MARKER.len() || max_body_bytes <= TRUNCATED_MARKER.len() {
+            bail!(
+                "RUTIFY_MAX_TITLE_BYTES and RUTIFY_MAX_BODY_BYTES must be greater than {}",
+                TRUNCATED_MARKER.len()
+            );
+        }
+        Ok(Self {
+            max_title_bytes,
+            max_body_bytes,
+            policy,
+        })
+    }
+
+    /// 拒绝策略下，超出限制时返回 413 错误；截断策略下只拒绝超长的端到端加密正文与
+    /// 带签名的通知，截断后密文无法解密、签名无法验证。关联 ID 超长时总是拒绝
+    pub(crate) fn check(&self, input: &NotificationInput) -> Result<(), AppError> {
+        if input
+            .correlation_id
+            .as_ref()
+            .is_some_and(|id| id.len() > MAX_CORRELATION_ID_LEN)
+        {
+            return Err(AppError::PayloadTooLarge(format!(
+                "correlation_id exceeds {MAX_CORRELATION_ID_LEN} bytes"
+            )));
+        }
+        self.check_fields(
+            input.title.as_deref(),
+            Some(&input.notify),
+            input.signature.is_some(),
+        )
+    }
+
+    /// 截断超长的标题与正文，正文被截断时返回原始正文
+    pub(crate) fn truncate(&self, input: &mut NotificationInput) -> Option<String> {
+        self.truncate_fields(input.title.as_mut(), Some(&mut input.notify))
+    }
+
+    /// 更新通知时同样受限制：拒绝策略下返回 413，截断策略下截断并返回原始正文
+    pub(crate) fn apply_to_update(
+        &self,
+        request: &mut UpdateNotifyRequest,
+    ) -> Result<Option<String>, AppError> {
+        self.check_fields(request.title.as_deref(), request.notify.as_deref(), false)?;
+        Ok(self.truncate_fields(request.title.as_mut(), request.notify.as_mut()))
+    }
+
+    fn check_fields(
+        &self,
+        title: Option<&str>,
+        body: Option<&str>,
+        signed: bool,
+    ) -> Result<(), AppError> {
+        let reject = self.policy == OversizePolicy::Reject || signed;
+        if reject && title.is_some_and(|title| title.len() > self.max_title_bytes) {
+            return Err(AppError::PayloadTooLarge(format!(
+                "title exceeds {} bytes",
+                self.max_title_bytes
+            )));
+        }
+        if body.is_some_and(|body| body.len() > self.max_body_bytes && (reject || is_e2e(body))) {
+            return Err(AppError::PayloadTooLarge(format!(
+                "notify exceeds {} bytes",
+                self.max_body_bytes
+            )));
+        }
+        Ok(())
+    }
+
+    fn truncate_fields(
+        &self,
+        title: Option<&mut String>,
+        body: Option<&mut String>,
+    ) -> Option<String> {
+        if let Some(title) = title
+            && title.len() > self.max_title_bytes
+        {
+            *title = truncated(title, self.max_title_bytes);
+        }
+        let body = body?;
+        if body.len() > self.max_body_bytes {
+            let short = truncated(body, self.max_body_bytes);
+            return Some(std::mem::replace(body, short));
+        }
+        None
+    }
+}
+
+/// 在字符边界截断并追加标记，结果不超过 `max_bytes`
+fn truncated(text: &str, max_bytes: usize) -> String {
+    let mut end = max_bytes.saturating_sub(TRUNCATED_MARKER.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}{}", &text[..end], TRUNCATED_MARKER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size_limits() {
+        let limits = SizeLimits {
+            max_title_bytes: 32,
+            max_body_bytes: 64,
+            policy: OversizePolicy::Truncate,
+        };
+        let mut input = NotificationInput {
+            notify: "日志".repeat(40),
+            title: Some("short".to_string()),
+            ..Default::default()
+        };
+        assert!(limits.check(&input).is_ok());
+
+        let full = limits.truncate(&mut input).expect("body truncated");
+        assert_eq!(full, "日志".repeat(40));
+        assert!(input.notify.len() <= 64);
+        assert!(input.notify.ends_with(TRUNCATED_MARKER));
+        assert_eq!(input.title.as_deref(), Some("short"));
+        assert_eq!(limits.truncate(&mut input), None);
+
+        let encrypted = NotificationInput {
+            notify: format!("{}{}", rutify_core::E2E_PREFIX, "A".repeat(64)),
+            ..Default::default()
+        };
+        assert!(matches!(
+            limits.check(&encrypted),
+            Err(AppError::PayloadTooLarge(_))
+        ));
+
+        let reject = SizeLimits {
+            policy: OversizePolicy::Reject,
+            ..limits
+        };
+        let input = NotificationInput {
+            notify: "x".repeat(65),
+            ..Default::default()
+        };
+        assert!(matches!(
+            reject.check(&input),
+            Err(AppError::PayloadTooLarge(_))
+        ));
+
+        let correlated = NotificationInput {
+            notify: "x".to_string(),
+            correlation_id: Some("c".repeat(MAX_CORRELATION_ID_LEN + 1)),
+            ..Default::default()
+        };
+        assert!(matches!(
+            limits.check(&correlated),
+            Err(AppError::PayloadTooLarge(_))
+        ));
+    }
+}
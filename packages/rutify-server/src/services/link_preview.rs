@@ -0,0 +1,346 @@
+use anyhow::Context;
+use futures_util::future::join_all;
+use reqwest::Url;
+use reqwest::header::CONTENT_TYPE;
+use rutify_core::{EventKind, LinkPreview, NotificationData};
+use std::time::Duration;
+use tracing::{debug, warn};
+
+use crate::bootstrap::source::ConfigSource;
+use crate::db::notifies;
+use crate::services::auth::mode::is_private;
+use crate::services::notify;
+use crate::state::AppState;
+
+/// 每条通知最多预览的链接数
+const MAX_LINKS: usize = 3;
+
+/// 只读取页面开头的这些字节，`<head>` 通常都在其中
+const MAX_HTML_BYTES: usize = 256 * 1024;
+
+/// 标题最大字符数
+const MAX_TITLE_CHARS: usize = 200;
+
+/// 链接预览配置
+#[derive(Debug)]
+pub(crate) struct LinkPreviewConfig {
+    timeout: Duration,
+    allow_private: bool,
+}
+
+impl LinkPreviewConfig {
+    /// `RUTIFY_LINK_PREVIEW` 为 `true` 时开启；`RUTIFY_LINK_PREVIEW_TIMEOUT_SECS` 为单个链接的
+    /// 抓取超时（默认 5 秒），`RUTIFY_LINK_PREVIEW_ALLOW_PRIVATE` 允许抓取内网地址（默认 `false`）
+    pub(crate) fn from_source(source: &ConfigSource) -> anyhow::Result<Option<Self>> {
+        let enabled = match source.var("RUTIFY_LINK_PREVIEW") {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid RUTIFY_LINK_PREVIEW: {text}"))?,
+            Err(_) => false,
+        };
+        if !enabled {
+            return Ok(None);
+        }
+        let timeout_secs = match source.var("RUTIFY_LINK_PREVIEW_TIMEOUT_SECS") {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid RUTIFY_LINK_PREVIEW_TIMEOUT_SECS: {text}"))?,
+            Err(_) => 5,
+        };
+        let allow_private = match source.var("RUTIFY_LINK_PREVIEW_ALLOW_PRIVATE") {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid RUTIFY_LINK_PREVIEW_ALLOW_PRIVATE: {text}"))?,
+            Err(_) => false,
+        };
+        Ok(Some(Self {
+            timeout: Duration::from_secs(timeout_secs),
+            allow_private,
+        }))
+    }
+}
+
+/// 抓取链接标题与图标
+pub(crate) struct LinkPreviewer {
+    client: reqwest::Client,
+    allow_private: bool,
+}
+
+impl LinkPreviewer {
+    pub(crate) fn new(config: LinkPreviewConfig) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder()
+            .user_agent(concat!("rutify-preview/", env!("CARGO_PKG_VERSION")))
+            .timeout(config.timeout)
+            .redirect(reqwest::redirect::Policy::limited(3))
+            .build()?;
+        Ok(Self {
+            client,
+            allow_private: config.allow_private,
+        })
+    }
+
+    async fn fetch(&self, url: Url) -> Option<LinkPreview> {
+        if !self.allow_private && !resolves_to_public(&url).await {
+            debug!("Skipping link preview for non-public address {}", url);
+            return None;
+        }
+        let mut response = self
+            .client
+            .get(url.clone())
+            .send()
+            .await
+            .inspect_err(|e| debug!("Link preview for {} errors: {}", url, e))
+            .ok()?;
+        let is_html = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| {
+                value.starts_with("text/html") || value.starts_with("application/xhtml+xml")
+            });
+        if !response.status().is_success() || !is_html {
+            return None;
+        }
+
+        let base = response.url().clone();
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.ok()? {
+            body.extend_from_slice(&chunk);
+            if body.len() >= MAX_HTML_BYTES {
+                break;
+            }
+        }
+        let mut preview = parse_html(&base, &String::from_utf8_lossy(&body));
+        // 保留正文中的原始地址，便于客户端与正文对应
+        preview.url = url.to_string();
+        Some(preview)
+    }
+}
+
+/// 通知正文包含链接时在后台抓取预览并保存，随后广播 `update` 事件；静默的通知只保存不广播
+pub(crate) fn enrich(state: &AppState, data: &NotificationData, silenced: bool) {
+    let (Some(previewer), Some(id)) = (state.link_previewer.clone(), data.id) else {
+        return;
+    };
+    let urls = extract_urls(&data.notify);
+    if urls.is_empty() {
+        return;
+    }
+    let state = state.clone();
+    let body = data.notify.clone();
+    tokio::spawn(async move {
+        let previews: Vec<LinkPreview> = join_all(urls.into_iter().map(|url| previewer.fetch(url)))
+            .await
+            .into_iter()
+            .flatten()
+            .collect();
+        if previews.is_empty() {
+            return;
+        }
+        match notifies::set_link_previews(&state.db, id.get(), &body, &previews).await {
+            Ok(Some(model)) if !silenced => {
+                notify::broadcast(&state, EventKind::Updated, model.to_data());
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Failed to save link previews for notify {}: {}", id, e),
+        }
+    });
+}
+
+/// 正文中的 http/https 链接，去重后最多取前 [`MAX_LINKS`] 个
+fn extract_urls(text: &str) -> Vec<Url> {
+    let mut urls: Vec<Url> = Vec::new();
+    for word in text.split_whitespace() {
+        let Some(start) = word.find("https://").or_else(|| word.find("http://")) else {
+            continue;
+        };
+        let candidate = word[start..].trim_end_matches(|c: char| {
+            matches!(
+                c,
+                '.' | ',' | ';' | ':' | '!' | '?' | ')' | ']' | '}' | '>' | '"' | '\''
+            )
+        });
+        if let Ok(url) = Url::parse(candidate)
+            && url.host_str().is_some()
+            && !urls.contains(&url)
+        {
+            urls.push(url);
+            if urls.len() == MAX_LINKS {
+                break;
+            }
+        }
+    }
+    urls
+}
+
+/// 链接解析到的地址都是公网地址；解析失败视为不可抓取
+async fn resolves_to_public(url: &Url) -> bool {
+    let (Some(host), Some(port)) = (url.host_str(), url.port_or_known_default()) else {
+        return false;
+    };
+    match tokio::net::lookup_host((host, port)).await {
+        Ok(addrs) => {
+            let addrs: Vec<_> = addrs.collect();
+            !addrs.is_empty()
+                && addrs
+                    .iter()
+                    .all(|addr| !is_private(addr.ip()) && !addr.ip().is_unspecified())
+        }
+        Err(_) => false,
+    }
+}
+
+/// 从页面开头提取标题（优先 `og:title`）与图标，页面未声明图标时使用 `/favicon.ico`
+fn parse_html(base: &Url, html: &str) -> LinkPreview {
+    let lower = html.to_ascii_lowercase();
+
+    let og_title = tags(html, &lower, "meta").find_map(|tag| {
+        let property = attr(tag, "property").or_else(|| attr(tag, "name"))?;
+        if property.eq_ignore_ascii_case("og:title") {
+            attr(tag, "content")
+        } else {
+            None
+        }
+    });
+    let title = og_title
+        .or_else(|| {
+            let start = lower.find("<title")?;
+            let start = start + lower[start..].find('>')? + 1;
+            let end = start + lower[start..].find("</title")?;
+            Some(decode_entities(&html[start..end]))
+        })
+        .map(|title| {
+            title
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .chars()
+                .take(MAX_TITLE_CHARS)
+                .collect::<String>()
+        })
+        .filter(|title| !title.is_empty());
+
+    let favicon = tags(html, &lower, "link")
+        .find_map(|tag| {
+            let rel = attr(tag, "rel")?;
+            rel.split_whitespace()
+                .any(|rel| rel.eq_ignore_ascii_case("icon"))
+                .then(|| attr(tag, "href"))
+                .flatten()
+        })
+        .and_then(|href| base.join(&href).ok())
+        .or_else(|| base.join("/favicon.ico").ok())
+        .map(String::from);
+
+    LinkPreview {
+        url: base.to_string(),
+        title,
+        favicon,
+    }
+}
+
+/// 依次返回名为 `name` 的标签，如 `<meta property="og:title" content="...">`
+fn tags<'a>(html: &'a str, lower: &'a str, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    let open = format!("<{name}");
+    let mut from = 0;
+    std::iter::from_fn(move || {
+        loop {
+            let start = from + lower[from..].find(&open)?;
+            let end = start + lower[start..].find('>')?;
+            from = end + 1;
+            // 排除 `<metadata>` 这类同前缀的标签
+            if lower[start + open.len()..]
+                .starts_with(|c: char| c.is_ascii_whitespace() || c == '/')
+            {
+                return Some(&html[start..end]);
+            }
+        }
+    })
+}
+
+/// 标签中的属性值，支持双引号、单引号与不带引号的写法
+fn attr(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(pos) = lower[from..].find(name) {
+        let start = from + pos;
+        from = start + name.len();
+        if !lower[..start].ends_with(|c: char| c.is_ascii_whitespace()) {
+            continue;
+        }
+        let Some(rest) = tag[from..].trim_start().strip_prefix('=') else {
+            continue;
+        };
+        let rest = rest.trim_start();
+        let value = match rest.chars().next()? {
+            quote @ ('"' | '\'') => rest[1..].split(quote).next()?,
+            _ => rest.split(|c: char| c.is_ascii_whitespace()).next()?,
+        };
+        return Some(decode_entities(value.trim()));
+    }
+    None
+}
+
+/// 解码常见的 HTML 实体
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_urls() {
+        let urls = extract_urls(
+            "Build failed: https://ci.example.com/jobs/42. See (https://docs.example.com/x) \
+             and [log](http://logs.example.com/a?b=1), again https://ci.example.com/jobs/42",
+        );
+        let urls: Vec<&str> = urls.iter().map(Url::as_str).collect();
+        assert_eq!(
+            urls,
+            [
+                "https://ci.example.com/jobs/42",
+                "https://docs.example.com/x",
+                "http://logs.example.com/a?b=1",
+            ]
+        );
+        assert!(extract_urls("no links, just http:// here").is_empty());
+    }
+
+    #[test]
+    fn test_parse_html() {
+        let base = Url::parse("https://example.com/blog/post").unwrap();
+        let html = r#"<html><head>
+            <metadata></metadata>
+            <title>
+              Fallback   title
+            </title>
+            <meta property="og:title" content="Tom &amp; Jerry">
+            <link rel="stylesheet" href="/style.css">
+            <LINK REL='shortcut icon' HREF='../static/icon.png'>
+            </head>"#;
+        let preview = parse_html(&base, html);
+        assert_eq!(preview.title.as_deref(), Some("Tom & Jerry"));
+        assert_eq!(
+            preview.favicon.as_deref(),
+            Some("https://example.com/static/icon.png")
+        );
+
+        let preview = parse_html(&base, "<title>Fallback   title</title>");
+        assert_eq!(preview.title.as_deref(), Some("Fallback title"));
+        assert_eq!(
+            preview.favicon.as_deref(),
+            Some("https://example.com/favicon.ico")
+        );
+    }
+}
@@ -0,0 +1,324 @@
+use chrono::{DateTime, Utc};
+use rutify_core::{DEFAULT_PRIORITY, EventKind, NotificationData, NotifyEvent};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use tracing::warn;
+
+use crate::services::sinks::Sink;
+
+/// 维护期间最多暂存的投递数量，超出部分只计数不保留
+const MAX_QUEUED: usize = 10_000;
+
+/// 汇总通知中列出的标题数量
+const SUMMARY_TITLES: usize = 10;
+
+/// 一次对外投递
+#[derive(Debug, Clone)]
+pub(crate) enum Delivery {
+    /// WebSocket 广播
+    Event(NotifyEvent),
+    /// 外部渠道转发（Telegram、邮件、Webhook）
+    Sink {
+        sink: Sink,
+        target: String,
+        data: NotificationData,
+    },
+}
+
+/// 结束维护时暂存投递的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReleaseMode {
+    /// 按原顺序逐条补发
+    Flush,
+    /// 每个投递目标只发送一条汇总
+    Summary,
+}
+
+impl ReleaseMode {
+    pub(crate) fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "flush" => Some(Self::Flush),
+            "summary" => Some(Self::Summary),
+            _ => None,
+        }
+    }
+}
+
+/// 维护状态
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct MaintenanceStatus {
+    pub enabled: bool,
+    pub reason: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub queued: usize,
+    pub dropped: usize,
+}
+
+struct Window {
+    reason: Option<String>,
+    started_at: DateTime<Utc>,
+    queue: Vec<Delivery>,
+    dropped: usize,
+}
+
+/// 已结束的维护窗口
+pub(crate) struct FinishedWindow {
+    pub started_at: DateTime<Utc>,
+    pub deliveries: Vec<Delivery>,
+    pub dropped: usize,
+}
+
+/// 维护模式开关与暂存队列（仅保存在内存中，重启后维护状态与队列丢失，通知本身已入库）
+pub(crate) struct Maintenance {
+    window: Mutex<Option<Window>>,
+}
+
+impl Maintenance {
+    pub(crate) fn new() -> Self {
+        Self {
+            window: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn status(&self) -> MaintenanceStatus {
+        match self.window.lock().unwrap().as_ref() {
+            Some(window) => MaintenanceStatus {
+                enabled: true,
+                reason: window.reason.clone(),
+                started_at: Some(window.started_at),
+                queued: window.queue.len(),
+                dropped: window.dropped,
+            },
+            None => MaintenanceStatus {
+                enabled: false,
+                reason: None,
+                started_at: None,
+                queued: 0,
+                dropped: 0,
+            },
+        }
+    }
+
+    /// 进入维护模式；已在维护中时只更新原因，保留已暂存的投递
+    pub(crate) fn start(&self, reason: Option<String>) -> MaintenanceStatus {
+        {
+            let mut window = self.window.lock().unwrap();
+            match window.as_mut() {
+                Some(window) => window.reason = reason,
+                None => {
+                    *window = Some(Window {
+                        reason,
+                        started_at: Utc::now(),
+                        queue: Vec::new(),
+                        dropped: 0,
+                    })
+                }
+            }
+        }
+        self.status()
+    }
+
+    /// 维护期间暂存投递并返回 `None`；未处于维护时原样返回，由调用方立即投递
+    pub(crate) fn hold(&self, delivery: Delivery) -> Option<Delivery> {
+        let mut window = self.window.lock().unwrap();
+        let Some(window) = window.as_mut() else {
+            return Some(delivery);
+        };
+        if window.queue.len() < MAX_QUEUED {
+            window.queue.push(delivery);
+        } else {
+            if window.dropped == 0 {
+                warn!("Maintenance queue is full, further deliveries are dropped");
+            }
+            window.dropped += 1;
+        }
+        None
+    }
+
+    /// 退出维护模式，取出暂存的投递；未处于维护时返回 `None`
+    pub(crate) fn finish(&self) -> Option<FinishedWindow> {
+        self.window
+            .lock()
+            .unwrap()
+            .take()
+            .map(|window| FinishedWindow {
+                started_at: window.started_at,
+                deliveries: window.queue,
+                dropped: window.dropped,
+            })
+    }
+}
+
+impl FinishedWindow {
+    /// 按处理方式生成需要实际发送的投递
+    pub(crate) fn release(self, mode: ReleaseMode) -> Vec<Delivery> {
+        match mode {
+            ReleaseMode::Flush => self.deliveries,
+            ReleaseMode::Summary => self.summarize(),
+        }
+    }
+
+    /// WebSocket 与每个外部渠道目标各生成一条汇总通知
+    ///
+    /// 只发给特定接收人的通知（值班频道）不进入公共汇总，按原样补发。
+    fn summarize(self) -> Vec<Delivery> {
+        let mut direct = Vec::new();
+        let mut events = Vec::new();
+        let mut targets: BTreeMap<(Sink, String), Vec<NotificationData>> = BTreeMap::new();
+
+        for delivery in self.deliveries {
+            match delivery {
+                Delivery::Event(event) if event.data.recipient.is_some() => {
+                    direct.push(Delivery::Event(event))
+                }
+                Delivery::Event(event) if event.event == EventKind::Created => {
+                    events.push(event.data)
+                }
+                Delivery::Event(_) => {}
+                Delivery::Sink { sink, target, data } => {
+                    targets.entry((sink, target)).or_default().push(data)
+                }
+            }
+        }
+
+        let mut summaries = direct;
+        if !events.is_empty() || self.dropped > 0 {
+            summaries.push(Delivery::Event(NotifyEvent {
+                event: EventKind::Created,
+                data: summary_data(self.started_at, &events, self.dropped),
+                timestamp: Utc::now(),
+            }));
+        }
+        for ((sink, target), items) in targets {
+            summaries.push(Delivery::Sink {
+                sink,
+                target,
+                data: summary_data(self.started_at, &items, 0),
+            });
+        }
+        summaries
+    }
+}
+
+fn summary_data(
+    started_at: DateTime<Utc>,
+    items: &[NotificationData],
+    dropped: usize,
+) -> NotificationData {
+    let total = items.len() + dropped;
+    let listed = items.len().min(SUMMARY_TITLES);
+    let mut lines = vec![format!(
+        "{} notifications were held during maintenance since {}",
+        total,
+        started_at.format("%Y-%m-%d %H:%M:%S UTC")
+    )];
+    lines.extend(
+        items
+            .iter()
+            .take(listed)
+            .map(|item| format!("- [{}] {}", item.device, item.title)),
+    );
+    if total > listed {
+        lines.push(format!("... and {} more", total - listed));
+    }
+
+    NotificationData {
+        id: None,
+        notify: lines.join("\n"),
+        title: "Maintenance summary".to_string(),
+        device: "rutify".to_string(),
+        priority: items
+            .iter()
+            .map(|item| item.priority)
+            .max()
+            .unwrap_or(DEFAULT_PRIORITY),
+        tags: vec!["maintenance".to_string()],
+        recipient: None,
+        channel: None,
+        expires_at: None,
+        progress: None,
+        link_previews: Vec::new(),
+        signature: None,
+        correlation_id: None,
+        token_id: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(title: &str, priority: u8) -> NotificationData {
+        NotificationData {
+            notify: "body".to_string(),
+            title: title.to_string(),
+            device: "web1".to_string(),
+            priority,
+            ..Default::default()
+        }
+    }
+
+    fn event(kind: EventKind, title: &str) -> Delivery {
+        Delivery::Event(NotifyEvent {
+            event: kind,
+            data: data(title, 3),
+            timestamp: Utc::now(),
+        })
+    }
+
+    #[test]
+    fn test_hold_only_during_maintenance() {
+        let maintenance = Maintenance::new();
+        assert!(maintenance.hold(event(EventKind::Created, "a")).is_some());
+        assert!(maintenance.finish().is_none());
+
+        maintenance.start(Some("deploy".to_string()));
+        assert!(maintenance.hold(event(EventKind::Created, "b")).is_none());
+        assert!(maintenance.hold(event(EventKind::Updated, "b")).is_none());
+        assert_eq!(maintenance.status().queued, 2);
+
+        let window = maintenance.finish().unwrap();
+        assert_eq!(window.release(ReleaseMode::Flush).len(), 2);
+        assert!(!maintenance.status().enabled);
+    }
+
+    #[test]
+    fn test_summary_groups_by_target() {
+        let maintenance = Maintenance::new();
+        maintenance.start(None);
+        maintenance.hold(event(EventKind::Created, "disk full"));
+        maintenance.hold(event(EventKind::Updated, "disk full"));
+        for title in ["a", "b"] {
+            maintenance.hold(Delivery::Sink {
+                sink: Sink::Email,
+                target: "ops@example.com".to_string(),
+                data: data(title, 5),
+            });
+        }
+
+        let summaries = maintenance.finish().unwrap().release(ReleaseMode::Summary);
+        assert_eq!(summaries.len(), 2);
+        match &summaries[0] {
+            Delivery::Event(event) => {
+                assert!(event.data.notify.starts_with("1 notifications"));
+                assert!(event.data.notify.contains("[web1] disk full"));
+            }
+            other => panic!("unexpected delivery: {other:?}"),
+        }
+        match &summaries[1] {
+            Delivery::Sink { target, data, .. } => {
+                assert_eq!(target, "ops@example.com");
+                assert_eq!(data.priority, 5);
+            }
+            other => panic!("unexpected delivery: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_release_mode_parse() {
+        assert_eq!(ReleaseMode::parse("Flush"), Some(ReleaseMode::Flush));
+        assert_eq!(ReleaseMode::parse("summary"), Some(ReleaseMode::Summary));
+        assert_eq!(ReleaseMode::parse("drop"), None);
+    }
+}
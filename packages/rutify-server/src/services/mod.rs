@@ -1 +1,27 @@
 pub(crate) mod auth;
+pub(crate) mod backup;
+pub(crate) mod checks;
+pub(crate) mod cluster;
+pub(crate) mod connections;
+pub(crate) mod db_maintenance;
+pub(crate) mod dedup;
+pub(crate) mod delivery;
+pub(crate) mod device;
+pub(crate) mod digest;
+pub(crate) mod escalation;
+pub(crate) mod hooks;
+pub(crate) mod leader;
+pub(crate) mod limits;
+pub(crate) mod link_preview;
+pub(crate) mod maintenance;
+pub(crate) mod monitors;
+pub(crate) mod notify;
+pub(crate) mod oncall;
+pub(crate) mod plugins;
+pub(crate) mod preferences;
+pub(crate) mod push;
+pub(crate) mod signing;
+pub(crate) mod silences;
+pub(crate) mod sinks;
+pub(crate) mod unifiedpush;
+pub(crate) mod web_push;
@@ -0,0 +1,202 @@
+use chrono::{DateTime, Utc};
+use rutify_core::{Monitor, MonitorStatus, NotificationInput};
+use sea_orm::sea_query::Expr;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::db::monitors;
+use crate::error::AppError;
+use crate::services::notify;
+use crate::state::AppState;
+
+/// 检查逾期监控的间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// 名称最大长度
+const NAME_MAX_LEN: usize = 64;
+
+/// 告警通知的设备名
+const MONITOR_DEVICE: &str = "rutify-monitor";
+
+/// 未指定宽限期时取间隔的十分之一，至少 60 秒
+pub(crate) fn default_grace_secs(interval_secs: u64) -> u64 {
+    (interval_secs / 10).max(60)
+}
+
+/// 名称用于 URL 路径：1–64 个字符，仅字母、数字、`_`、`-`、`.`
+pub(crate) fn validate_name(name: &str) -> Result<(), AppError> {
+    let valid = !name.is_empty()
+        && name.len() <= NAME_MAX_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(format!(
+            "Monitor name must be 1-{NAME_MAX_LEN} characters of letters, digits, '_', '-' or '.'"
+        )))
+    }
+}
+
+/// 下一次心跳的截止时间
+fn due_after(beat_at: DateTime<Utc>, monitor: &monitors::Model) -> DateTime<Utc> {
+    beat_at + chrono::Duration::seconds(monitor.interval_secs + monitor.grace_secs)
+}
+
+/// 告警与恢复通知共用的分组键
+fn group_key(name: &str) -> String {
+    format!("monitor:{name}")
+}
+
+fn alert_input(monitor: &monitors::Model, notify: String, title: String) -> NotificationInput {
+    NotificationInput {
+        notify,
+        title: Some(title),
+        device: Some(MONITOR_DEVICE.to_string()),
+        priority: u8::try_from(monitor.priority).ok(),
+        tags: vec!["monitor".to_string(), monitor.name.clone()],
+        channel: monitor.channel.clone(),
+        ttl_secs: None,
+        progress: None,
+        signature: None,
+        correlation_id: None,
+    }
+}
+
+pub(crate) async fn find_by_name(
+    state: &AppState,
+    name: &str,
+) -> Result<monitors::Model, AppError> {
+    monitors::Entity::find()
+        .filter(monitors::Column::Name.eq(name))
+        .one(&state.db)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Monitor not found".to_string()))
+}
+
+/// 记录一次心跳；监控此前已告警时发送恢复通知
+pub(crate) async fn beat(state: &AppState, name: &str) -> Result<Monitor, AppError> {
+    let current = find_by_name(state, name).await?;
+    let was_down = current.status == MonitorStatus::Down.as_str();
+
+    let now = Utc::now();
+    let due_at = due_after(now, &current);
+    let mut monitor: monitors::ActiveModel = current.into();
+    monitor.status = Set(MonitorStatus::Up.as_str().to_string());
+    monitor.last_beat_at = Set(Some(now));
+    monitor.due_at = Set(Some(due_at));
+    monitor.updated_at = Set(now);
+    let monitor = monitor.update(&state.db).await?;
+
+    if was_down {
+        info!("Monitor '{}' is back up", monitor.name);
+        let input = alert_input(
+            &monitor,
+            format!("Heartbeat received again at {}", now.to_rfc3339()),
+            format!("Monitor {} is back up", monitor.name),
+        );
+        notify::resolve(state, &group_key(&monitor.name), input).await;
+    }
+
+    Ok(monitor.into_monitor())
+}
+
+/// 将逾期的监控标记为 `down` 并发送告警
+async fn check_overdue(state: &AppState) -> Result<(), AppError> {
+    let now = Utc::now();
+    let overdue = monitors::Entity::find()
+        .filter(monitors::Column::Status.eq(MonitorStatus::Up.as_str()))
+        .filter(monitors::Column::DueAt.lt(now))
+        .all(&state.db)
+        .await?;
+
+    for monitor in overdue {
+        // 条件更新，避免与同时到达的心跳竞争
+        let result = monitors::Entity::update_many()
+            .col_expr(
+                monitors::Column::Status,
+                Expr::value(MonitorStatus::Down.as_str()),
+            )
+            .col_expr(monitors::Column::UpdatedAt, Expr::value(now))
+            .filter(monitors::Column::Id.eq(monitor.id))
+            .filter(monitors::Column::Status.eq(MonitorStatus::Up.as_str()))
+            .filter(monitors::Column::DueAt.lt(now))
+            .exec(&state.db)
+            .await?;
+        if result.rows_affected == 0 {
+            continue;
+        }
+
+        warn!("Monitor '{}' missed its heartbeat", monitor.name);
+        let last_beat = monitor
+            .last_beat_at
+            .map(|at| at.to_rfc3339())
+            .unwrap_or_else(|| "never".to_string());
+        let input = alert_input(
+            &monitor,
+            format!(
+                "No heartbeat since {} (expected every {}s, grace {}s)",
+                last_beat, monitor.interval_secs, monitor.grace_secs
+            ),
+            format!("Monitor {} is down", monitor.name),
+        );
+        notify::ingest_grouped(state, input, Some(group_key(&monitor.name))).await;
+    }
+    Ok(())
+}
+
+/// 启动逾期检查任务，只在主节点上检查
+pub(crate) fn spawn(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            ticker.tick().await;
+            if !state.leadership.is_leader() {
+                continue;
+            }
+            if let Err(e) = check_overdue(&state).await {
+                warn!("Monitor check errors: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_monitor_rules() {
+        assert_eq!(default_grace_secs(60), 60);
+        assert_eq!(default_grace_secs(3600), 360);
+
+        assert!(validate_name("nightly-backup.nas_1").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("a/b").is_err());
+        assert!(validate_name(&"x".repeat(NAME_MAX_LEN + 1)).is_err());
+
+        let now = Utc::now();
+        let monitor = monitors::Model {
+            id: 1,
+            name: "backup".to_string(),
+            interval_secs: 3600,
+            grace_secs: 300,
+            status: "up".to_string(),
+            priority: 4,
+            channel: None,
+            last_beat_at: Some(now),
+            due_at: None,
+            created_by: None,
+            created_at: now,
+            updated_at: now,
+        };
+        assert_eq!(
+            due_after(now, &monitor),
+            now + chrono::Duration::seconds(3900)
+        );
+        assert_eq!(group_key(&monitor.name), "monitor:backup");
+    }
+}
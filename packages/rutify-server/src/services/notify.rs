@@ -0,0 +1,363 @@
+use rutify_core::{
+    DEFAULT_PRIORITY, EventKind, MAX_PRIORITY, NotificationData, NotificationInput, NotifyEvent,
+    NotifyId,
+};
+use tracing::{error, info, warn};
+
+use crate::db::deliveries::{self, NewDelivery};
+use crate::db::user_preferences;
+use crate::services::device::DeviceSource;
+use crate::services::link_preview;
+use crate::services::maintenance::Delivery;
+use crate::services::oncall::{self, OnCall};
+use crate::services::plugins::PluginOutcome;
+use crate::services::preferences;
+use crate::services::sinks::Sink;
+use crate::state::AppState;
+
+const DEFAULT_TITLE: &str = "default title";
+const DEFAULT_DEVICE: &str = "default device";
+/// 系统事件使用的设备名
+const SYSTEM_DEVICE: &str = "rutify";
+
+/// 通知入库与广播的统一入口
+///
+/// 依次执行：大小限制 -> 字段补全 -> 插件处理 -> 持久化 -> WebSocket 广播，开启链接预览时
+/// 在后台抓取正文中链接的标题与图标。
+/// 超长的通知按配置截断（完整正文另存为附件）或丢弃，命中静默规则的通知只入库不广播，维护期间的对外投递暂存到维护结束；
+/// 被插件丢弃或入库失败时返回 `None`。
+pub(crate) async fn ingest(state: &AppState, payload: NotificationInput) -> Option<NotifyEvent> {
+    ingest_grouped(state, payload, None).await
+}
+
+/// 带分组键入库，供外部告警在恢复时定位原通知
+pub(crate) async fn ingest_grouped(
+    state: &AppState,
+    payload: NotificationInput,
+    group_key: Option<String>,
+) -> Option<NotifyEvent> {
+    let device_source = DeviceSource::of(&payload);
+    ingest_derived(state, payload, group_key, device_source, None).await
+}
+
+/// 设备名已由 [`crate::services::device::derive`] 补全的通知入库，同时记录设备名的来源与发送方
+/// Token 的哈希，之后只有该 Token 或用户能修改这条通知
+pub(crate) async fn ingest_derived(
+    state: &AppState,
+    mut payload: NotificationInput,
+    group_key: Option<String>,
+    device_source: DeviceSource,
+    token_hash: Option<String>,
+) -> Option<NotifyEvent> {
+    let full_body = limit_size(state, &mut payload)?;
+    let channel = payload.channel.clone();
+    let mut data = apply_plugins(state, payload)?;
+    let oncall = match channel.as_deref() {
+        Some(channel) => route_oncall(state, channel, &mut data).await,
+        None => None,
+    };
+
+    let silence = state.silences.matching(&data, channel.as_deref());
+
+    let model = match crate::db::notifies::insert_new_notify(
+        &state.db,
+        data.clone(),
+        group_key,
+        silence.is_some(),
+        device_source.as_str(),
+        token_hash,
+    )
+    .await
+    {
+        Ok(model) => model,
+        Err(e) => {
+            error!("Failed to insert notify: {}", e);
+            return None;
+        }
+    };
+    data.id = Some(NotifyId(model.id));
+    if let Some(body) = full_body {
+        save_full_body(state, model.id, body).await;
+    }
+    link_preview::enrich(state, &data, silence.is_some());
+    if let Some(silence_id) = silence {
+        info!("Notification silenced by silence {}", silence_id);
+        return Some(new_event(EventKind::Created, data));
+    }
+    state.escalations.track(&model);
+    if let Some(oncall) = oncall {
+        forward_to_oncall(state, oncall, data.clone()).await;
+    }
+    Some(broadcast(state, EventKind::Created, data))
+}
+
+/// 恢复同一分组键下未恢复的通知（更新原记录并广播 `update` 事件）
+///
+/// 找不到原通知时按新通知入库。
+pub(crate) async fn resolve(
+    state: &AppState,
+    group_key: &str,
+    mut payload: NotificationInput,
+) -> Option<NotifyEvent> {
+    let full_body = limit_size(state, &mut payload)?;
+    let channel = payload.channel.clone();
+    let device_source = DeviceSource::of(&payload);
+    let mut data = apply_plugins(state, payload)?;
+    let silenced = state.silences.matching(&data, channel.as_deref()).is_some();
+    let emit = |event: EventKind, data: NotificationData| {
+        if silenced {
+            new_event(event, data)
+        } else {
+            broadcast(state, event, data)
+        }
+    };
+
+    match crate::db::notifies::resolve_open_notify(&state.db, group_key, data.clone()).await {
+        Ok(Some(model)) => {
+            data.id = Some(NotifyId(model.id));
+            if let Some(body) = full_body {
+                save_full_body(state, model.id, body).await;
+            }
+            link_preview::enrich(state, &data, silenced);
+            Some(emit(EventKind::Updated, data))
+        }
+        Ok(None) => {
+            // 恢复通知无需升级，不安排升级任务
+            match crate::db::notifies::insert_new_notify(
+                &state.db,
+                data.clone(),
+                Some(group_key.to_string()),
+                silenced,
+                device_source.as_str(),
+                None,
+            )
+            .await
+            {
+                Ok(model) => {
+                    data.id = Some(NotifyId(model.id));
+                    if let Some(body) = full_body {
+                        save_full_body(state, model.id, body).await;
+                    }
+                    link_preview::enrich(state, &data, silenced);
+                }
+                Err(e) => {
+                    error!("Failed to insert notify: {}", e);
+                    return None;
+                }
+            }
+            Some(emit(EventKind::Created, data))
+        }
+        Err(e) => {
+            error!("Failed to resolve notify '{}': {}", group_key, e);
+            None
+        }
+    }
+}
+
+/// 应用大小限制，返回被截断前的完整正文
+///
+/// 拒绝策略下超限的通知返回 `None`；HTTP 与 gRPC 入口会先行检查并返回错误，
+/// 到这里的只有桥接等无法回报错误的来源。
+fn limit_size(state: &AppState, payload: &mut NotificationInput) -> Option<Option<String>> {
+    if let Err(e) = state.size_limits.check(payload) {
+        warn!("Notification dropped: {}", e);
+        return None;
+    }
+    Some(state.size_limits.truncate(payload))
+}
+
+/// 保存被截断通知的完整正文，失败只记录日志，不影响通知本身
+pub(crate) async fn save_full_body(state: &AppState, notify_id: i32, body: String) {
+    if let Err(e) = crate::db::attachments::insert_full_body(&state.db, notify_id, body).await {
+        error!("Failed to save full body of notify {}: {}", notify_id, e);
+    }
+}
+
+/// 值班频道的通知只投递给当前值班人；无人值班时退回为广播
+async fn route_oncall(
+    state: &AppState,
+    channel: &str,
+    data: &mut NotificationData,
+) -> Option<OnCall> {
+    let schedule = oncall::schedule_for_channel(channel)?;
+    match oncall::current_by_name(&state.db, schedule).await {
+        Ok(Some(oncall)) => {
+            data.recipient = Some(oncall.info.username.clone());
+            Some(oncall)
+        }
+        Ok(None) => {
+            warn!("No on-call user for schedule '{}', broadcasting", schedule);
+            None
+        }
+        Err(e) => {
+            error!(
+                "Database errors resolving on-call schedule '{}': {}",
+                schedule, e
+            );
+            None
+        }
+    }
+}
+
+/// 值班人配置了转发渠道时额外投递一份，按值班人的通知偏好过滤
+async fn forward_to_oncall(state: &AppState, oncall: OnCall, data: NotificationData) {
+    let (Some(sink), Some(target)) = (
+        oncall.member.sink.as_deref().and_then(Sink::parse),
+        oncall.member.target,
+    ) else {
+        return;
+    };
+    match user_preferences::for_username(&state.db, &oncall.info.username).await {
+        Ok(prefs) => {
+            if !preferences::allows(&prefs, &data, sink.channel(), chrono::Utc::now()) {
+                return;
+            }
+        }
+        Err(e) => warn!(
+            "Failed to load preferences of '{}': {}",
+            oncall.info.username, e
+        ),
+    }
+    forward(state, sink, target, data);
+}
+
+/// 在后台将通知转发到外部渠道，维护期间暂存
+pub(crate) fn forward(state: &AppState, sink: Sink, target: String, data: NotificationData) {
+    if let Some(delivery) = state
+        .maintenance
+        .hold(Delivery::Sink { sink, target, data })
+    {
+        dispatch(state, delivery);
+    }
+}
+
+/// 立即执行一次投递，不经过维护模式检查
+pub(crate) fn dispatch(state: &AppState, delivery: Delivery) {
+    match delivery {
+        Delivery::Event(event) => {
+            if let Some(cluster) = &state.cluster {
+                cluster.publish(&event);
+            }
+            let _ = state.tx.send(event);
+        }
+        Delivery::Sink { sink, target, data } => {
+            let config = state.config.clone();
+            // 只读实例不记录送达回执
+            let receipts = (!state.read_only).then(|| state.db.clone());
+            tokio::spawn(async move {
+                let result = sink.deliver(&config, &target, &data).await;
+                if let Err(e) = &result {
+                    warn!("Delivery to {:?} '{}' errors: {}", sink, target, e);
+                }
+                if let (Some(db), Some(notify_id)) = (receipts, data.id) {
+                    let delivery = NewDelivery {
+                        notify_id: notify_id.get(),
+                        channel: sink.channel(),
+                        target: sink.receipt_target(&target),
+                        token_id: None,
+                        error: result.err().map(|e| format!("{e:#}")),
+                    };
+                    if let Err(e) = deliveries::record(&db, delivery).await {
+                        warn!("Failed to record delivery receipt: {}", e);
+                    }
+                }
+            });
+        }
+    }
+}
+
+fn apply_plugins(state: &AppState, payload: NotificationInput) -> Option<NotificationData> {
+    match state.plugins.apply(normalize_notification(payload)) {
+        PluginOutcome::Keep(data) => Some(*data),
+        PluginOutcome::Drop { plugin } => {
+            info!("Notification dropped by plugin '{}'", plugin);
+            None
+        }
+    }
+}
+
+/// 广播通知事件，维护期间暂存
+pub(crate) fn broadcast(state: &AppState, event: EventKind, data: NotificationData) -> NotifyEvent {
+    let event = new_event(event, data);
+    if let Some(delivery) = state.maintenance.hold(Delivery::Event(event.clone())) {
+        dispatch(state, delivery);
+    }
+    event
+}
+
+/// 不对应单条通知的系统事件数据，例如批量删除后的 `stats_changed`
+pub(crate) fn system_data(title: &str, notify: String) -> NotificationData {
+    NotificationData {
+        id: None,
+        notify,
+        title: title.to_string(),
+        device: SYSTEM_DEVICE.to_string(),
+        priority: DEFAULT_PRIORITY,
+        tags: Vec::new(),
+        recipient: None,
+        channel: None,
+        expires_at: None,
+        progress: None,
+        link_previews: Vec::new(),
+        signature: None,
+        correlation_id: None,
+        token_id: None,
+    }
+}
+
+fn new_event(event: EventKind, data: NotificationData) -> NotifyEvent {
+    NotifyEvent {
+        event,
+        data,
+        timestamp: chrono::Utc::now(),
+    }
+}
+
+fn normalize_notification(payload: NotificationInput) -> NotificationData {
+    NotificationData {
+        id: None,
+        notify: payload.notify,
+        title: payload.title.unwrap_or_else(|| DEFAULT_TITLE.to_string()),
+        device: payload.device.unwrap_or_else(|| DEFAULT_DEVICE.to_string()),
+        priority: payload
+            .priority
+            .unwrap_or(DEFAULT_PRIORITY)
+            .clamp(1, MAX_PRIORITY),
+        tags: payload.tags,
+        recipient: None,
+        channel: payload.channel,
+        expires_at: payload.ttl_secs.and_then(expires_at),
+        progress: payload.progress,
+        link_previews: Vec::new(),
+        signature: payload.signature,
+        correlation_id: payload.correlation_id,
+        token_id: None,
+    }
+}
+
+/// 由有效期计算过期时间，超出可表示范围时视为不过期
+fn expires_at(ttl_secs: u64) -> Option<chrono::DateTime<chrono::Utc>> {
+    let ttl = chrono::Duration::try_seconds(i64::try_from(ttl_secs).ok()?)?;
+    chrono::Utc::now().checked_add_signed(ttl)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_ttl() {
+        let input = NotificationInput {
+            notify: "backup running".to_string(),
+            ttl_secs: Some(60),
+            ..Default::default()
+        };
+        let data = normalize_notification(input);
+        let expiry = data.expires_at.expect("expires_at");
+        assert!(expiry > chrono::Utc::now());
+        assert!(!data.is_expired(chrono::Utc::now()));
+        assert!(data.is_expired(expiry));
+
+        assert_eq!(expires_at(u64::MAX), None);
+    }
+}
@@ -0,0 +1,138 @@
+use chrono::{DateTime, Utc};
+use rutify_core::OnCallInfo;
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder};
+
+use crate::db::{oncall_members, oncall_schedules, users};
+
+/// 频道 `oncall` 对应的排班名
+pub(crate) const DEFAULT_SCHEDULE: &str = "default";
+
+/// 当前值班人及其转发渠道
+pub(crate) struct OnCall {
+    pub info: OnCallInfo,
+    pub member: oncall_members::Model,
+}
+
+/// 解析值班频道：`oncall` 对应 `default` 排班，`oncall:<name>` 对应指定排班
+///
+/// 非值班频道返回 `None`。
+pub(crate) fn schedule_for_channel(channel: &str) -> Option<&str> {
+    match channel.split_once(':') {
+        Some(("oncall", name)) if !name.is_empty() => Some(name),
+        None if channel == "oncall" => Some(DEFAULT_SCHEDULE),
+        _ => None,
+    }
+}
+
+/// 计算当前轮到的成员下标及本轮结束时间
+///
+/// 排班尚未开始时由第一位成员值班。
+pub(crate) fn current_slot(
+    starts_at: DateTime<Utc>,
+    rotation_hours: i32,
+    members: usize,
+    now: DateTime<Utc>,
+) -> Option<(usize, DateTime<Utc>)> {
+    if members == 0 || rotation_hours < 1 {
+        return None;
+    }
+
+    let rotation = chrono::Duration::hours(rotation_hours.into());
+    if now < starts_at {
+        return Some((0, starts_at + rotation));
+    }
+    let elapsed = (now - starts_at).num_seconds() / rotation.num_seconds();
+    let until = starts_at + rotation * (elapsed as i32 + 1);
+    Some(((elapsed as usize) % members, until))
+}
+
+/// 查询排班的当前值班人，排班不存在或没有成员时返回 `None`
+pub(crate) async fn current(
+    db: &DatabaseConnection,
+    schedule: &oncall_schedules::Model,
+) -> Result<Option<OnCall>, DbErr> {
+    let members = oncall_members::Entity::find()
+        .filter(oncall_members::Column::ScheduleId.eq(schedule.id))
+        .order_by_asc(oncall_members::Column::Position)
+        .order_by_asc(oncall_members::Column::Id)
+        .all(db)
+        .await?;
+
+    let Some((index, until)) = current_slot(
+        schedule.starts_at,
+        schedule.rotation_hours,
+        members.len(),
+        Utc::now(),
+    ) else {
+        return Ok(None);
+    };
+    let member = members[index].clone();
+
+    let Some(user) = users::Entity::find_by_id(member.user_id).one(db).await? else {
+        return Ok(None);
+    };
+
+    Ok(Some(OnCall {
+        info: OnCallInfo {
+            schedule: schedule.name.clone(),
+            username: user.username,
+            until,
+        },
+        member,
+    }))
+}
+
+/// 按排班名查询当前值班人
+pub(crate) async fn current_by_name(
+    db: &DatabaseConnection,
+    name: &str,
+) -> Result<Option<OnCall>, DbErr> {
+    let schedule = oncall_schedules::Entity::find()
+        .filter(oncall_schedules::Column::Name.eq(name))
+        .one(db)
+        .await?;
+
+    match schedule {
+        Some(schedule) => current(db, &schedule).await,
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_schedule_for_channel() {
+        assert_eq!(schedule_for_channel("oncall"), Some("default"));
+        assert_eq!(schedule_for_channel("oncall:db"), Some("db"));
+        assert_eq!(schedule_for_channel("oncall:"), None);
+        assert_eq!(schedule_for_channel("ops"), None);
+    }
+
+    #[test]
+    fn test_current_slot_rotates() {
+        let start = Utc.with_ymd_and_hms(2026, 1, 5, 9, 0, 0).unwrap();
+
+        let (index, until) = current_slot(start, 24, 3, start).unwrap();
+        assert_eq!(index, 0);
+        assert_eq!(until, start + chrono::Duration::hours(24));
+
+        let now = start + chrono::Duration::hours(50);
+        let (index, until) = current_slot(start, 24, 3, now).unwrap();
+        assert_eq!(index, 2);
+        assert_eq!(until, start + chrono::Duration::hours(72));
+
+        let now = start + chrono::Duration::hours(73);
+        assert_eq!(current_slot(start, 24, 3, now).unwrap().0, 0);
+    }
+
+    #[test]
+    fn test_current_slot_before_start_and_empty() {
+        let start = Utc.with_ymd_and_hms(2026, 1, 5, 9, 0, 0).unwrap();
+        let before = start - chrono::Duration::hours(1);
+        assert_eq!(current_slot(start, 24, 2, before).unwrap().0, 0);
+        assert!(current_slot(start, 24, 0, start).is_none());
+    }
+}
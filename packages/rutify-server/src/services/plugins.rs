@@ -0,0 +1,216 @@
+use rhai::{AST, Dynamic, Engine, Scope};
+use rutify_core::NotificationData;
+use sea_orm::{DatabaseConnection, EntityTrait, QueryOrder};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Mutex, RwLock};
+use std::time::Instant;
+use tracing::{info, warn};
+
+use crate::db::plugins;
+use crate::error::AppError;
+
+/// 单个脚本允许执行的最大操作数，防止死循环拖垮通知链路
+const MAX_OPERATIONS: u64 = 50_000;
+
+/// 插件执行统计
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct PluginMetrics {
+    pub executions: u64,
+    pub dropped: u64,
+    pub errors: u64,
+    pub total_micros: u64,
+    pub last_error: Option<String>,
+}
+
+/// 插件处理结果
+pub(crate) enum PluginOutcome {
+    Keep(Box<NotificationData>),
+    Drop { plugin: String },
+}
+
+struct LoadedPlugin {
+    id: i32,
+    name: String,
+    ast: AST,
+}
+
+/// 已启用插件的编译缓存与执行统计
+pub(crate) struct PluginRegistry {
+    engine: Engine,
+    plugins: RwLock<Vec<LoadedPlugin>>,
+    metrics: Mutex<HashMap<i32, PluginMetrics>>,
+}
+
+impl PluginRegistry {
+    pub(crate) fn new() -> Self {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        engine.set_max_expr_depths(64, 32);
+        engine.set_max_string_size(1024 * 1024);
+
+        Self {
+            engine,
+            plugins: RwLock::new(Vec::new()),
+            metrics: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 编译脚本，用于创建/更新插件前的语法校验
+    pub(crate) fn compile(&self, script: &str) -> Result<AST, AppError> {
+        self.engine
+            .compile(script)
+            .map_err(|e| AppError::BadRequest(format!("Invalid plugin script: {e}")))
+    }
+
+    /// 从数据库重新加载所有启用的插件
+    pub(crate) async fn reload(&self, db: &DatabaseConnection) -> Result<(), AppError> {
+        let rows = plugins::Entity::find()
+            .order_by_asc(plugins::Column::SortOrder)
+            .order_by_asc(plugins::Column::Id)
+            .all(db)
+            .await?;
+
+        let mut loaded = Vec::new();
+        for row in rows.into_iter().filter(|row| row.enabled) {
+            match self.engine.compile(&row.script) {
+                Ok(ast) => loaded.push(LoadedPlugin {
+                    id: row.id,
+                    name: row.name,
+                    ast,
+                }),
+                Err(e) => warn!("Skipping plugin '{}': {}", row.name, e),
+            }
+        }
+
+        info!("Loaded {} notification plugins", loaded.len());
+        *self.plugins.write().unwrap() = loaded;
+        Ok(())
+    }
+
+    /// 依次执行所有启用的插件
+    ///
+    /// 脚本可直接修改 `title`、`notify`、`device` 变量；返回 `false` 时丢弃该通知。
+    /// 执行出错的插件会被跳过，不影响通知投递。
+    pub(crate) fn apply(&self, mut data: NotificationData) -> PluginOutcome {
+        let plugins = self.plugins.read().unwrap();
+
+        for plugin in plugins.iter() {
+            let mut scope = Scope::new();
+            scope.push("title", data.title.clone());
+            scope.push("notify", data.notify.clone());
+            scope.push("device", data.device.clone());
+
+            let started = Instant::now();
+            let result = self
+                .engine
+                .eval_ast_with_scope::<Dynamic>(&mut scope, &plugin.ast);
+            let elapsed = started.elapsed().as_micros() as u64;
+
+            let mut metrics = self.metrics.lock().unwrap();
+            let entry = metrics.entry(plugin.id).or_default();
+            entry.executions += 1;
+            entry.total_micros += elapsed;
+
+            match result {
+                Ok(value) if value.as_bool() == Ok(false) => {
+                    entry.dropped += 1;
+                    return PluginOutcome::Drop {
+                        plugin: plugin.name.clone(),
+                    };
+                }
+                Ok(_) => {
+                    data.title = scope.get_value::<String>("title").unwrap_or(data.title);
+                    data.notify = scope.get_value::<String>("notify").unwrap_or(data.notify);
+                    data.device = scope.get_value::<String>("device").unwrap_or(data.device);
+                }
+                Err(e) => {
+                    warn!("Plugin '{}' failed: {}", plugin.name, e);
+                    entry.errors += 1;
+                    entry.last_error = Some(e.to_string());
+                }
+            }
+        }
+
+        PluginOutcome::Keep(Box::new(data))
+    }
+
+    pub(crate) fn metrics(&self, plugin_id: i32) -> PluginMetrics {
+        self.metrics
+            .lock()
+            .unwrap()
+            .get(&plugin_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn forget(&self, plugin_id: i32) {
+        self.metrics.lock().unwrap().remove(&plugin_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> NotificationData {
+        NotificationData {
+            notify: "password=hunter2".to_string(),
+            title: "deploy".to_string(),
+            device: "web1".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn registry_with(script: &str) -> PluginRegistry {
+        let registry = PluginRegistry::new();
+        let ast = registry.compile(script).unwrap();
+        registry.plugins.write().unwrap().push(LoadedPlugin {
+            id: 1,
+            name: "test".to_string(),
+            ast,
+        });
+        registry
+    }
+
+    #[test]
+    fn test_plugin_rewrites_fields() {
+        let registry =
+            registry_with(r#"title = title.to_upper(); notify.replace("hunter2", "***");"#);
+
+        match registry.apply(sample()) {
+            PluginOutcome::Keep(data) => {
+                assert_eq!(data.title, "DEPLOY");
+                assert_eq!(data.notify, "password=***");
+                assert_eq!(data.device, "web1");
+            }
+            PluginOutcome::Drop { .. } => panic!("Expected notification to be kept"),
+        }
+        assert_eq!(registry.metrics(1).executions, 1);
+    }
+
+    #[test]
+    fn test_plugin_drops_notification() {
+        let registry = registry_with(r#"if device == "web1" { return false; }"#);
+
+        assert!(matches!(
+            registry.apply(sample()),
+            PluginOutcome::Drop { .. }
+        ));
+        assert_eq!(registry.metrics(1).dropped, 1);
+    }
+
+    #[test]
+    fn test_plugin_error_keeps_notification() {
+        let registry = registry_with("loop { }");
+
+        assert!(matches!(registry.apply(sample()), PluginOutcome::Keep(_)));
+        assert_eq!(registry.metrics(1).errors, 1);
+    }
+
+    #[test]
+    fn test_compile_rejects_invalid_script() {
+        let registry = PluginRegistry::new();
+        assert!(registry.compile("let = ;").is_err());
+    }
+}
@@ -0,0 +1,252 @@
+//! 用户通知偏好：保存前的校验，以及推送时按频道、免打扰时段与渠道优先级的过滤
+
+use chrono::{DateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
+use rutify_core::{DeliveryChannel, MAX_PRIORITY, NotificationData, NotificationPreferences};
+use sea_orm::DatabaseConnection;
+use std::collections::HashMap;
+use tracing::warn;
+
+use crate::db::user_preferences;
+use crate::error::AppError;
+
+/// 订阅频道的数量上限
+const MAX_CHANNELS: usize = 64;
+
+/// 频道名最大字符数
+const MAX_CHANNEL_CHARS: usize = 64;
+
+fn parse_time(text: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(text.trim(), "%H:%M").ok()
+}
+
+fn validate_priority(name: &str, priority: u8) -> Result<(), AppError> {
+    if (1..=MAX_PRIORITY).contains(&priority) {
+        Ok(())
+    } else {
+        Err(AppError::BadRequest(format!(
+            "{name} must be between 1 and {MAX_PRIORITY}"
+        )))
+    }
+}
+
+/// 校验并规范化偏好：频道去空白、去重，时间统一为 `HH:MM`
+pub(crate) fn validate(
+    mut preferences: NotificationPreferences,
+) -> Result<NotificationPreferences, AppError> {
+    let mut channels: Vec<String> = Vec::new();
+    for channel in preferences.channels {
+        let channel = channel.trim().to_string();
+        if channel.is_empty() || channels.contains(&channel) {
+            continue;
+        }
+        if channel.chars().count() > MAX_CHANNEL_CHARS {
+            return Err(AppError::BadRequest(format!(
+                "channel names must be at most {MAX_CHANNEL_CHARS} characters"
+            )));
+        }
+        channels.push(channel);
+    }
+    if channels.len() > MAX_CHANNELS {
+        return Err(AppError::BadRequest(format!(
+            "at most {MAX_CHANNELS} channels can be subscribed"
+        )));
+    }
+    preferences.channels = channels;
+
+    if let Some(quiet) = &mut preferences.quiet_hours {
+        let (Some(start), Some(end)) = (parse_time(&quiet.start), parse_time(&quiet.end)) else {
+            return Err(AppError::BadRequest(
+                "quiet_hours.start and quiet_hours.end must be HH:MM".to_string(),
+            ));
+        };
+        if start == end {
+            return Err(AppError::BadRequest(
+                "quiet_hours.start and quiet_hours.end must differ".to_string(),
+            ));
+        }
+        quiet.start = start.format("%H:%M").to_string();
+        quiet.end = end.format("%H:%M").to_string();
+        quiet.timezone = quiet.timezone.trim().to_string();
+        if quiet.timezone.parse::<Tz>().is_err() {
+            return Err(AppError::BadRequest(format!(
+                "unknown timezone: {}",
+                quiet.timezone
+            )));
+        }
+        validate_priority("quiet_hours.min_priority", quiet.min_priority)?;
+    }
+
+    for (channel, priority) in &preferences.min_priority {
+        if DeliveryChannel::parse(channel).is_none() {
+            return Err(AppError::BadRequest(format!(
+                "unknown delivery channel: {channel}"
+            )));
+        }
+        validate_priority("min_priority values", *priority)?;
+    }
+    Ok(preferences)
+}
+
+/// `now` 是否处于免打扰时段；时段无效时视为不在时段内
+fn in_quiet_hours(preferences: &NotificationPreferences, now: DateTime<Utc>) -> bool {
+    let Some(quiet) = &preferences.quiet_hours else {
+        return false;
+    };
+    let (Some(start), Some(end), Ok(timezone)) = (
+        parse_time(&quiet.start),
+        parse_time(&quiet.end),
+        quiet.timezone.parse::<Tz>(),
+    ) else {
+        return false;
+    };
+    let local = now.with_timezone(&timezone).time();
+    if start <= end {
+        start <= local && local < end
+    } else {
+        local >= start || local < end
+    }
+}
+
+/// 偏好是否允许经 `channel` 推送该通知
+pub(crate) fn allows(
+    preferences: &NotificationPreferences,
+    data: &NotificationData,
+    channel: DeliveryChannel,
+    now: DateTime<Utc>,
+) -> bool {
+    let subscribed = match &data.channel {
+        Some(name) => preferences.channels.is_empty() || preferences.channels.contains(name),
+        None => true,
+    };
+    let min_priority = preferences
+        .min_priority
+        .get(channel.as_str())
+        .copied()
+        .unwrap_or(0);
+    let quiet = preferences
+        .quiet_hours
+        .as_ref()
+        .is_some_and(|quiet| data.priority < quiet.min_priority)
+        && in_quiet_hours(preferences, now);
+    subscribed && data.priority >= min_priority && !quiet
+}
+
+/// 一次推送批量加载全部偏好；加载失败时不过滤
+pub(crate) async fn load(db: &DatabaseConnection) -> HashMap<String, NotificationPreferences> {
+    user_preferences::by_username(db).await.unwrap_or_else(|e| {
+        warn!("Failed to load notification preferences: {}", e);
+        HashMap::new()
+    })
+}
+
+/// 按用户名查找偏好后判断；未关联用户或没有偏好记录时总是允许
+pub(crate) fn allows_user(
+    all: &HashMap<String, NotificationPreferences>,
+    username: Option<&str>,
+    data: &NotificationData,
+    channel: DeliveryChannel,
+    now: DateTime<Utc>,
+) -> bool {
+    username
+        .and_then(|username| all.get(username))
+        .is_none_or(|preferences| allows(preferences, data, channel, now))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use rutify_core::QuietHours;
+
+    fn sample(priority: u8, channel: Option<&str>) -> NotificationData {
+        let mut data = crate::services::notify::system_data("Disk", "disk full".to_string());
+        data.priority = priority;
+        data.channel = channel.map(str::to_string);
+        data
+    }
+
+    fn quiet_hours(start: &str, end: &str, timezone: &str) -> Option<QuietHours> {
+        Some(QuietHours {
+            start: start.to_string(),
+            end: end.to_string(),
+            timezone: timezone.to_string(),
+            min_priority: MAX_PRIORITY,
+        })
+    }
+
+    #[test]
+    fn test_validate_preferences() {
+        let preferences = validate(NotificationPreferences {
+            channels: vec![" ops ".to_string(), "ops".to_string(), String::new()],
+            quiet_hours: quiet_hours("22:00", "7:30", " Asia/Shanghai "),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(preferences.channels, ["ops"]);
+        let quiet = preferences.quiet_hours.unwrap();
+        assert_eq!(
+            (quiet.end.as_str(), quiet.timezone.as_str()),
+            ("07:30", "Asia/Shanghai")
+        );
+
+        let invalid = [
+            NotificationPreferences {
+                quiet_hours: quiet_hours("22:00", "22:00", "UTC"),
+                ..Default::default()
+            },
+            NotificationPreferences {
+                quiet_hours: quiet_hours("22:00", "07:00", "Mars/Olympus"),
+                ..Default::default()
+            },
+            NotificationPreferences {
+                min_priority: [("pager".to_string(), 3)].into(),
+                ..Default::default()
+            },
+            NotificationPreferences {
+                min_priority: [("web_push".to_string(), 9)].into(),
+                ..Default::default()
+            },
+        ];
+        for preferences in invalid {
+            assert!(validate(preferences).is_err());
+        }
+    }
+
+    #[test]
+    fn test_allows() {
+        let preferences = NotificationPreferences {
+            channels: vec!["ops".to_string()],
+            quiet_hours: quiet_hours("22:00", "07:00", "Asia/Shanghai"),
+            min_priority: [("telegram".to_string(), 4)].into(),
+            ..Default::default()
+        };
+        // 上海 12:00 与 23:00
+        let noon = Utc.with_ymd_and_hms(2026, 1, 1, 4, 0, 0).unwrap();
+        let night = Utc.with_ymd_and_hms(2026, 1, 1, 15, 0, 0).unwrap();
+        let push = DeliveryChannel::WebPush;
+
+        assert!(allows(&preferences, &sample(3, Some("ops")), push, noon));
+        assert!(allows(&preferences, &sample(3, None), push, noon));
+        assert!(!allows(&preferences, &sample(3, Some("ci")), push, noon));
+        assert!(!allows(
+            &preferences,
+            &sample(3, None),
+            DeliveryChannel::Telegram,
+            noon
+        ));
+        assert!(!allows(&preferences, &sample(4, None), push, night));
+        assert!(allows(
+            &preferences,
+            &sample(MAX_PRIORITY, None),
+            push,
+            night
+        ));
+        assert!(allows(
+            &NotificationPreferences::default(),
+            &sample(1, Some("ci")),
+            push,
+            night
+        ));
+    }
+}
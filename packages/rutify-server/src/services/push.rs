@@ -0,0 +1,187 @@
+//! UnifiedPush 与 Web Push 共用的推送地址校验、消息构造与结果判断
+
+use reqwest::StatusCode;
+use rutify_core::{NotificationData, is_e2e};
+
+use crate::error::AppError;
+
+/// 推送地址最大长度
+const MAX_ENDPOINT_LEN: usize = 2048;
+
+/// 截断正文后追加的标记
+const TRUNCATED_MARKER: &str = " … (truncated)";
+
+/// 一次推送的结果
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum PushOutcome {
+    Delivered,
+    /// 订阅已失效（应用卸载或浏览器取消订阅），推送服务返回 404/410，需要删除该订阅
+    Gone,
+    Failed(String),
+}
+
+impl PushOutcome {
+    pub(crate) fn of(response: reqwest::Result<reqwest::Response>) -> Self {
+        match response {
+            Ok(response) if response.status().is_success() => Self::Delivered,
+            Ok(response)
+                if matches!(response.status(), StatusCode::NOT_FOUND | StatusCode::GONE) =>
+            {
+                Self::Gone
+            }
+            Ok(response) => Self::Failed(format!("HTTP {}", response.status())),
+            Err(e) => Self::Failed(e.to_string()),
+        }
+    }
+
+    /// 送达记录中的错误信息
+    pub(crate) fn error(self) -> Option<String> {
+        match self {
+            Self::Delivered => None,
+            Self::Gone => Some("endpoint is gone".to_string()),
+            Self::Failed(error) => Some(error),
+        }
+    }
+}
+
+/// 校验推送地址，只接受 http 与 https
+pub(crate) fn validate_endpoint(endpoint: &str) -> Result<String, AppError> {
+    let endpoint = endpoint.trim();
+    if endpoint.len() > MAX_ENDPOINT_LEN {
+        return Err(AppError::BadRequest(format!(
+            "endpoint must be at most {MAX_ENDPOINT_LEN} bytes"
+        )));
+    }
+    let url = reqwest::Url::parse(endpoint)
+        .map_err(|e| AppError::BadRequest(format!("invalid endpoint: {e}")))?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        return Err(AppError::BadRequest(
+            "endpoint must be an http or https URL".to_string(),
+        ));
+    }
+    Ok(endpoint.to_string())
+}
+
+/// 消息在推送服务中保留的时长：不超过通知剩余的有效期，已过期的通知返回 `None`
+pub(crate) fn ttl_secs(data: &NotificationData, default_ttl_secs: u64) -> Option<u64> {
+    match data.expires_at {
+        Some(expires_at) => {
+            let remaining = (expires_at - chrono::Utc::now()).num_seconds();
+            (remaining > 0).then(|| (remaining as u64).min(default_ttl_secs))
+        }
+        None => Some(default_ttl_secs),
+    }
+}
+
+/// Web Push 的 `Urgency` 头，推送服务据此决定是否立即唤醒设备
+pub(crate) fn urgency(priority: u8) -> &'static str {
+    match priority {
+        0 | 1 => "very-low",
+        2 => "low",
+        3 => "normal",
+        _ => "high",
+    }
+}
+
+/// 推送的消息体：通知的 JSON；超出 `max_bytes` 时去掉链接预览并截断正文，
+/// 端到端加密或带签名的正文无法截断，改为留空，客户端可按 `id` 拉取完整通知
+pub(crate) fn message(data: &NotificationData, max_bytes: usize) -> Vec<u8> {
+    let encode = |data: &NotificationData| serde_json::to_vec(data).unwrap_or_default();
+    let body = encode(data);
+    if body.len() <= max_bytes {
+        return body;
+    }
+
+    let mut data = data.clone();
+    data.link_previews.clear();
+    let body = encode(&data);
+    if body.len() <= max_bytes {
+        return body;
+    }
+    if is_e2e(&data.notify) || data.signature.is_some() {
+        data.notify.clear();
+        data.signature = None;
+    } else {
+        // 转义后的 JSON 不短于原文，从正文截掉超出的字节数即可放下
+        let excess = body.len() - max_bytes + TRUNCATED_MARKER.len();
+        let mut end = data.notify.len().saturating_sub(excess);
+        while !data.notify.is_char_boundary(end) {
+            end -= 1;
+        }
+        data.notify.truncate(end);
+        data.notify.push_str(TRUNCATED_MARKER);
+    }
+    let body = encode(&data);
+    if body.len() <= max_bytes {
+        return body;
+    }
+    data.notify.clear();
+    data.title.clear();
+    encode(&data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rutify_core::{DEFAULT_PRIORITY, NotifyId, NotifySignature};
+
+    fn sample(notify: String) -> NotificationData {
+        NotificationData {
+            id: Some(NotifyId(1)),
+            notify,
+            title: "Disk".to_string(),
+            device: "server".to_string(),
+            priority: DEFAULT_PRIORITY,
+            tags: Vec::new(),
+            recipient: None,
+            channel: None,
+            expires_at: None,
+            progress: None,
+            link_previews: Vec::new(),
+            signature: None,
+            correlation_id: None,
+            token_id: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_endpoint() {
+        assert!(validate_endpoint("https://ntfy.sh/upAbC123?up=1").is_ok());
+        assert!(validate_endpoint("ftp://ntfy.sh/up").is_err());
+        assert!(validate_endpoint("not a url").is_err());
+    }
+
+    #[test]
+    fn test_message_fits_limit() {
+        let short = sample("disk full".to_string());
+        assert_eq!(message(&short, 4096), serde_json::to_vec(&short).unwrap());
+
+        let body = message(&sample("磁盘\"".repeat(2000)), 4096);
+        assert!(body.len() <= 4096);
+        let data: NotificationData = serde_json::from_slice(&body).unwrap();
+        assert!(data.notify.ends_with(TRUNCATED_MARKER));
+        assert_eq!(data.title, "Disk");
+
+        let mut signed = sample("x".repeat(5000));
+        signed.signature = Some(NotifySignature {
+            public_key: "key".to_string(),
+            signature: "sig".to_string(),
+        });
+        let data: NotificationData = serde_json::from_slice(&message(&signed, 4096)).unwrap();
+        assert!(data.notify.is_empty() && data.signature.is_none());
+    }
+
+    #[test]
+    fn test_ttl_and_urgency() {
+        let mut data = sample("body".to_string());
+        assert_eq!(ttl_secs(&data, 600), Some(600));
+        data.expires_at = Some(chrono::Utc::now() + chrono::Duration::seconds(120));
+        assert!(ttl_secs(&data, 600).is_some_and(|ttl| ttl <= 120));
+        data.expires_at = Some(chrono::Utc::now() - chrono::Duration::seconds(1));
+        assert_eq!(ttl_secs(&data, 600), None);
+
+        assert_eq!(urgency(1), "very-low");
+        assert_eq!(urgency(DEFAULT_PRIORITY), "normal");
+        assert_eq!(urgency(5), "high");
+    }
+}
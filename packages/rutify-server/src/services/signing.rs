@@ -0,0 +1,119 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use ed25519_dalek::{Signature, VerifyingKey};
+use rutify_core::{NotificationInput, NotifySignature};
+
+use crate::error::AppError;
+
+/// 解析 base64 编码的 ed25519 公钥，创建 Token 时校验登记的公钥
+pub(crate) fn parse_public_key(text: &str) -> Result<VerifyingKey, AppError> {
+    let bytes: [u8; 32] = STANDARD
+        .decode(text.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| {
+            AppError::BadRequest("signing_key must be a base64 ed25519 public key".to_string())
+        })?;
+    VerifyingKey::from_bytes(&bytes)
+        .map_err(|_| AppError::BadRequest("signing_key is not a valid ed25519 key".to_string()))
+}
+
+/// 按发送 Token 登记的公钥校验通知签名
+///
+/// 登记了公钥时，通知必须带有该公钥对标题与正文的有效签名，否则返回 401；
+/// 未登记公钥时无从验证，丢弃通知中的签名，避免客户端把未经验证的签名显示为可信。
+pub(crate) fn verify_sender(
+    signing_key: Option<&str>,
+    input: &mut NotificationInput,
+) -> Result<(), AppError> {
+    let Some(signing_key) = signing_key else {
+        input.signature = None;
+        return Ok(());
+    };
+    let signature = input.signature.as_ref().ok_or_else(|| {
+        AppError::AuthError("This token requires signed notifications".to_string())
+    })?;
+    let title = input.title.as_deref().ok_or_else(|| {
+        AppError::BadRequest("Signed notifications must include a title".to_string())
+    })?;
+    if signature.public_key.trim() != signing_key {
+        return Err(AppError::AuthError(
+            "Notification is signed with a key not registered for this token".to_string(),
+        ));
+    }
+    if !verify(
+        &parse_public_key(signing_key)?,
+        title,
+        &input.notify,
+        signature,
+    ) {
+        return Err(AppError::AuthError(
+            "Invalid notification signature".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn verify(key: &VerifyingKey, title: &str, notify: &str, signature: &NotifySignature) -> bool {
+    let Some(bytes) = STANDARD
+        .decode(&signature.signature)
+        .ok()
+        .and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+    else {
+        return false;
+    };
+    key.verify_strict(
+        &NotifySignature::message(title, notify),
+        &Signature::from_bytes(&bytes),
+    )
+    .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+
+    fn signed(key: &SigningKey, title: &str, notify: &str) -> NotificationInput {
+        let signature = key.sign(&NotifySignature::message(title, notify));
+        NotificationInput {
+            notify: notify.to_string(),
+            title: Some(title.to_string()),
+            signature: Some(NotifySignature {
+                public_key: STANDARD.encode(key.verifying_key().as_bytes()),
+                signature: STANDARD.encode(signature.to_bytes()),
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_verify_sender() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let registered = STANDARD.encode(key.verifying_key().as_bytes());
+        assert!(parse_public_key(&registered).is_ok());
+        assert!(parse_public_key("not a key").is_err());
+
+        let mut input = signed(&key, "Deploy", "v2 is live");
+        assert!(verify_sender(Some(&registered), &mut input).is_ok());
+        assert!(input.signature.is_some());
+
+        // 签名后被篡改的正文
+        input.notify = "v2 rolled back".to_string();
+        assert!(matches!(
+            verify_sender(Some(&registered), &mut input),
+            Err(AppError::AuthError(_))
+        ));
+
+        // 其他密钥签名或未签名
+        let mut forged = signed(&SigningKey::from_bytes(&[9; 32]), "Deploy", "v2 is live");
+        assert!(verify_sender(Some(&registered), &mut forged).is_err());
+        forged.signature = None;
+        assert!(verify_sender(Some(&registered), &mut forged).is_err());
+
+        // Token 未登记公钥时丢弃签名
+        let mut input = signed(&key, "Deploy", "v2 is live");
+        assert!(verify_sender(None, &mut input).is_ok());
+        assert_eq!(input.signature, None);
+    }
+}
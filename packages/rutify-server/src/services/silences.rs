@@ -0,0 +1,174 @@
+use chrono::Utc;
+use rutify_core::NotificationData;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use std::sync::RwLock;
+use tracing::info;
+
+use crate::db::silences;
+use crate::error::AppError;
+
+/// 未过期静默规则的缓存
+pub(crate) struct SilenceRegistry {
+    rules: RwLock<Vec<silences::Model>>,
+}
+
+impl SilenceRegistry {
+    pub(crate) fn new() -> Self {
+        Self {
+            rules: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// 从数据库重新加载所有未过期的静默规则
+    pub(crate) async fn reload(&self, db: &DatabaseConnection) -> Result<(), AppError> {
+        let rules = silences::Entity::find()
+            .filter(silences::Column::ExpiresAt.gt(Utc::now()))
+            .all(db)
+            .await?;
+
+        info!("Loaded {} active silences", rules.len());
+        *self.rules.write().unwrap() = rules;
+        Ok(())
+    }
+
+    /// 返回命中的静默规则 ID，已过期的规则在匹配时跳过
+    pub(crate) fn matching(&self, data: &NotificationData, channel: Option<&str>) -> Option<i32> {
+        let now = Utc::now();
+        self.rules
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|rule| rule.expires_at > now)
+            .find(|rule| rule_matches(rule, data, channel))
+            .map(|rule| rule.id)
+    }
+
+    pub(crate) fn active_count(&self) -> usize {
+        let now = Utc::now();
+        self.rules
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|rule| rule.expires_at > now)
+            .count()
+    }
+}
+
+/// 所有已设置的条件都匹配时命中；没有任何条件的规则不匹配任何通知
+fn rule_matches(rule: &silences::Model, data: &NotificationData, channel: Option<&str>) -> bool {
+    if rule.device.is_none() && rule.channel.is_none() && rule.tag.is_none() {
+        return false;
+    }
+
+    let device = rule
+        .device
+        .as_deref()
+        .is_none_or(|pattern| glob_match(pattern, &data.device));
+    let channel = rule
+        .channel
+        .as_deref()
+        .is_none_or(|pattern| channel.is_some_and(|channel| glob_match(pattern, channel)));
+    let tag = rule
+        .tag
+        .as_deref()
+        .is_none_or(|pattern| data.tags.iter().any(|tag| glob_match(pattern, tag)));
+    device && channel && tag
+}
+
+/// 简单通配符匹配，`*` 匹配任意长度字符，大小写不敏感
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let text = text.to_lowercase();
+    let mut parts = pattern.split('*');
+
+    // 第一段必须是前缀
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // 没有通配符时要求完全相等
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(device: Option<&str>, channel: Option<&str>, tag: Option<&str>) -> silences::Model {
+        silences::Model {
+            id: 1,
+            device: device.map(str::to_string),
+            channel: channel.map(str::to_string),
+            tag: tag.map(str::to_string),
+            comment: None,
+            created_by: None,
+            expires_at: Utc::now() + chrono::Duration::hours(1),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn data(device: &str, tags: &[&str]) -> NotificationData {
+        NotificationData {
+            notify: "disk full".to_string(),
+            title: "alert".to_string(),
+            device: device.to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("web1", "WEB1"));
+        assert!(glob_match("web*", "web1"));
+        assert!(glob_match("*-prod", "db-prod"));
+        assert!(glob_match("a*c*e", "abcde"));
+        assert!(glob_match("*", "anything"));
+        assert!(!glob_match("web", "web1"));
+        assert!(!glob_match("a*c*e", "abcd"));
+    }
+
+    #[test]
+    fn test_rule_requires_all_conditions() {
+        let silence = rule(Some("web*"), None, Some("env=staging"));
+        assert!(rule_matches(
+            &silence,
+            &data("web1", &["env=staging"]),
+            None
+        ));
+        assert!(!rule_matches(&silence, &data("web1", &["env=prod"]), None));
+        assert!(!rule_matches(
+            &silence,
+            &data("db1", &["env=staging"]),
+            None
+        ));
+    }
+
+    #[test]
+    fn test_channel_and_empty_rules() {
+        let silence = rule(None, Some("oncall*"), None);
+        assert!(rule_matches(
+            &silence,
+            &data("web1", &[]),
+            Some("oncall:db")
+        ));
+        assert!(!rule_matches(&silence, &data("web1", &[]), None));
+
+        assert!(!rule_matches(
+            &rule(None, None, None),
+            &data("web1", &[]),
+            None
+        ));
+    }
+}
@@ -0,0 +1,299 @@
+//! UnifiedPush 推送：移动应用登记分发器（如 ntfy）分配的推送地址，新通知以 HTTP POST 发送到
+//! 该地址，由分发器唤醒应用
+//!
+//! 只在主节点上推送，集群中其他节点入库的通知经集群广播到达主节点后统一推送。
+
+use anyhow::Context;
+use reqwest::header::CONTENT_TYPE;
+use rutify_core::{DeliveryChannel, EventKind, NotificationData};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+use crate::bootstrap::source::ConfigSource;
+use crate::db::deliveries::NewDelivery;
+use crate::db::unifiedpush_registrations::{self as registrations, Model as Registration};
+use crate::error::AppError;
+use crate::services::push::{PushOutcome, message, ttl_secs, urgency};
+use crate::services::{delivery, preferences};
+use crate::state::AppState;
+
+/// UnifiedPush 规范要求分发器至少支持 4096 字节的消息
+const MAX_MESSAGE_BYTES: usize = 4096;
+
+/// 实例名最大字符数
+const MAX_INSTANCE_CHARS: usize = 64;
+
+/// 单次推送的超时时间
+const PUSH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 限流的统计窗口
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// UnifiedPush 推送配置
+#[derive(Debug, Clone)]
+pub(crate) struct UnifiedPushConfig {
+    /// 每个推送地址每分钟最多推送的通知数，超出的通知不推送
+    pub(crate) rate_per_minute: u32,
+    /// 通知未设置有效期时分发器保留消息的时长
+    pub(crate) default_ttl_secs: u64,
+}
+
+impl UnifiedPushConfig {
+    /// 读取 `RUTIFY_UNIFIEDPUSH_RATE_PER_MINUTE`（默认 30）与
+    /// `RUTIFY_UNIFIEDPUSH_TTL_SECS`（默认 86400）
+    pub(crate) fn from_source(source: &ConfigSource) -> anyhow::Result<Self> {
+        let rate_per_minute = match source.var("RUTIFY_UNIFIEDPUSH_RATE_PER_MINUTE") {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid RUTIFY_UNIFIEDPUSH_RATE_PER_MINUTE: {text}"))?,
+            Err(_) => 30,
+        };
+        let default_ttl_secs = match source.var("RUTIFY_UNIFIEDPUSH_TTL_SECS") {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid RUTIFY_UNIFIEDPUSH_TTL_SECS: {text}"))?,
+            Err(_) => 24 * 60 * 60,
+        };
+        if rate_per_minute == 0 {
+            anyhow::bail!("RUTIFY_UNIFIEDPUSH_RATE_PER_MINUTE must be greater than 0");
+        }
+        Ok(Self {
+            rate_per_minute,
+            default_ttl_secs,
+        })
+    }
+}
+
+/// 空实例名视为未指定
+pub(crate) fn validate_instance(instance: Option<String>) -> Result<Option<String>, AppError> {
+    let instance = instance
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    if instance
+        .as_ref()
+        .is_some_and(|value| value.chars().count() > MAX_INSTANCE_CHARS)
+    {
+        return Err(AppError::BadRequest(format!(
+            "instance must be at most {MAX_INSTANCE_CHARS} characters"
+        )));
+    }
+    Ok(instance)
+}
+
+/// 按推送地址限流，每个窗口内最多推送 `limit` 条
+struct RateLimiter {
+    limit: u32,
+    windows: HashMap<i32, (Instant, u32)>,
+}
+
+impl RateLimiter {
+    fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            windows: HashMap::new(),
+        }
+    }
+
+    fn allow(&mut self, registration_id: i32, now: Instant) -> bool {
+        let (started, count) = self.windows.entry(registration_id).or_insert((now, 0));
+        if now.duration_since(*started) >= RATE_WINDOW {
+            *started = now;
+            *count = 0;
+        }
+        if *count >= self.limit {
+            return false;
+        }
+        *count += 1;
+        true
+    }
+
+    /// 丢弃已删除登记的计数
+    fn retain(&mut self, registrations: &[(Registration, Option<String>)]) {
+        self.windows
+            .retain(|id, _| registrations.iter().any(|(item, _)| item.id == *id));
+    }
+}
+
+async fn push(
+    client: &reqwest::Client,
+    endpoint: &str,
+    body: Vec<u8>,
+    ttl_secs: u64,
+    urgency: &str,
+) -> PushOutcome {
+    let response = client
+        .post(endpoint)
+        .header(CONTENT_TYPE, "application/json")
+        .header("TTL", ttl_secs)
+        .header("Urgency", urgency)
+        .body(body)
+        .send()
+        .await;
+    PushOutcome::of(response)
+}
+
+/// 送达记录中的目标：实例名或推送地址的主机名，推送地址的路径通常包含密钥
+fn receipt_target(registration: &Registration) -> String {
+    registration.instance.clone().unwrap_or_else(|| {
+        reqwest::Url::parse(&registration.endpoint)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_else(|| "(invalid url)".to_string())
+    })
+}
+
+/// 启动推送任务：订阅新通知，推送给登记地址中可见该通知的那些
+pub(crate) fn spawn(state: Arc<AppState>, config: UnifiedPushConfig) -> anyhow::Result<()> {
+    let client = reqwest::Client::builder()
+        .user_agent(concat!("rutify/", env!("CARGO_PKG_VERSION")))
+        .timeout(PUSH_TIMEOUT)
+        .build()?;
+    let mut rx = state.tx.subscribe();
+    info!(
+        "UnifiedPush enabled, at most {} pushes per endpoint per minute",
+        config.rate_per_minute
+    );
+
+    tokio::spawn(async move {
+        let mut limiter = RateLimiter::new(config.rate_per_minute);
+        loop {
+            let event = match rx.recv().await {
+                Ok(event) => event,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("UnifiedPush dispatcher lagged, skipped {} events", skipped);
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            if event.event != EventKind::Created || !state.leadership.is_leader() {
+                continue;
+            }
+            fan_out(&state, &client, &config, &mut limiter, event.data).await;
+        }
+    });
+    Ok(())
+}
+
+async fn fan_out(
+    state: &Arc<AppState>,
+    client: &reqwest::Client,
+    config: &UnifiedPushConfig,
+    limiter: &mut RateLimiter,
+    data: NotificationData,
+) {
+    let Some(ttl_secs) = ttl_secs(&data, config.default_ttl_secs) else {
+        return;
+    };
+    let targets = match registrations::active(&state.db).await {
+        Ok(targets) => targets,
+        Err(e) => {
+            warn!("Failed to load UnifiedPush registrations: {}", e);
+            return;
+        }
+    };
+    limiter.retain(&targets);
+    if targets.is_empty() {
+        return;
+    }
+
+    let body = message(&data, MAX_MESSAGE_BYTES);
+    let urgency = urgency(data.priority);
+    let all_preferences = preferences::load(&state.db).await;
+    let now = Instant::now();
+    let wall_clock = chrono::Utc::now();
+    for (registration, username) in targets {
+        if !delivery::is_visible_to(&data, username.as_deref())
+            || !preferences::allows_user(
+                &all_preferences,
+                username.as_deref(),
+                &data,
+                DeliveryChannel::UnifiedPush,
+                wall_clock,
+            )
+        {
+            continue;
+        }
+        let receipt = data.id.map(|notify_id| NewDelivery {
+            notify_id: notify_id.get(),
+            channel: DeliveryChannel::UnifiedPush,
+            target: receipt_target(&registration),
+            token_id: Some(registration.token_id),
+            error: None,
+        });
+        if !limiter.allow(registration.id, now) {
+            if let Some(receipt) = receipt {
+                delivery::record_receipt(
+                    state,
+                    NewDelivery {
+                        error: Some("rate limited".to_string()),
+                        ..receipt
+                    },
+                );
+            }
+            continue;
+        }
+
+        let state = Arc::clone(state);
+        let client = client.clone();
+        let body = body.clone();
+        tokio::spawn(async move {
+            let outcome = push(&client, &registration.endpoint, body, ttl_secs, urgency).await;
+            let error = match &outcome {
+                PushOutcome::Delivered => {
+                    registrations::record_success(&state.db, registration.id).await
+                }
+                PushOutcome::Gone => {
+                    info!(
+                        "UnifiedPush endpoint {} is gone, removing registration {}",
+                        receipt_target(&registration),
+                        registration.id
+                    );
+                    registrations::delete(&state.db, registration.id).await
+                }
+                PushOutcome::Failed(error) => {
+                    warn!(
+                        "UnifiedPush to {} errors: {}",
+                        receipt_target(&registration),
+                        error
+                    );
+                    registrations::record_failure(&state.db, registration.id, error).await
+                }
+            };
+            if let Err(e) = error {
+                warn!("Failed to update UnifiedPush registration: {}", e);
+            }
+            if let Some(receipt) = receipt {
+                let error = outcome.error();
+                delivery::record_receipt(&state, NewDelivery { error, ..receipt });
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_instance() {
+        assert_eq!(validate_instance(Some("  ".to_string())).unwrap(), None);
+        assert!(validate_instance(Some("x".repeat(65))).is_err());
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let mut limiter = RateLimiter::new(2);
+        let now = Instant::now();
+        assert!(limiter.allow(1, now));
+        assert!(limiter.allow(1, now));
+        assert!(!limiter.allow(1, now));
+        assert!(limiter.allow(2, now));
+        assert!(limiter.allow(1, now + RATE_WINDOW));
+    }
+}
@@ -0,0 +1,520 @@
+//! Web Push 推送：浏览器经 Push API 订阅，新通知按 RFC 8291 加密、以 VAPID（RFC 8292）签名后
+//! 发送到浏览器的推送服务，页面关闭后由 Service Worker 显示
+//!
+//! 只在主节点上推送，集群中其他节点入库的通知经集群广播到达主节点后统一推送。
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, KeyInit, OsRng};
+use aes_gcm::{Aes128Gcm, Nonce};
+use anyhow::{Context, bail};
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use hkdf::Hkdf;
+use p256::ecdsa::signature::Signer;
+use p256::ecdsa::{Signature, SigningKey};
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use p256::{PublicKey, SecretKey};
+use reqwest::header::{AUTHORIZATION, CONTENT_ENCODING, CONTENT_TYPE};
+use rutify_core::{DeliveryChannel, EventKind, NotificationData, PushSubscriptionKeys};
+use sha2::Sha256;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+use crate::bootstrap::source::ConfigSource;
+use crate::db::deliveries::NewDelivery;
+use crate::db::web_push_subscriptions::{self as subscriptions, Model as Subscription};
+use crate::error::AppError;
+use crate::services::push::{PushOutcome, message, ttl_secs, urgency};
+use crate::services::{delivery, preferences};
+use crate::state::AppState;
+
+/// 加密记录的大小，整条消息只用一个记录
+const RECORD_SIZE: u32 = 4096;
+
+/// 推送服务接受的请求体不超过 4096 字节，扣除 86 字节头部、16 字节认证标签与 1 字节填充分隔符
+const MAX_MESSAGE_BYTES: usize = RECORD_SIZE as usize - 86 - 16 - 1;
+
+/// 通知未设置有效期时推送服务保留消息的时长
+const DEFAULT_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// VAPID JWT 的有效期，规范要求不超过 24 小时
+const VAPID_EXPIRY: chrono::Duration = chrono::Duration::hours(12);
+
+/// 单次推送的超时时间
+const PUSH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Web Push 配置
+#[derive(Debug, Clone)]
+pub(crate) struct WebPushConfig {
+    /// VAPID 私钥
+    pub(crate) key: SigningKey,
+    /// VAPID 联系方式，推送服务在出现问题时据此联系服务端运营者
+    pub(crate) subject: String,
+}
+
+impl WebPushConfig {
+    /// 读取 `RUTIFY_VAPID_KEY_FILE` 与 `RUTIFY_VAPID_SUBJECT`（默认项目主页），未设置密钥文件时不启用
+    pub(crate) fn from_source(source: &ConfigSource) -> anyhow::Result<Option<Self>> {
+        let Ok(path) = source.var("RUTIFY_VAPID_KEY_FILE") else {
+            return Ok(None);
+        };
+        let key = load_key(Path::new(path.trim()))?;
+        let subject = match source.var("RUTIFY_VAPID_SUBJECT") {
+            Ok(subject) => subject.trim().to_string(),
+            Err(_) => env!("CARGO_PKG_REPOSITORY").to_string(),
+        };
+        if !subject.starts_with("mailto:") && !subject.starts_with("https://") {
+            bail!("RUTIFY_VAPID_SUBJECT must be a mailto: or https:// URL, got {subject}");
+        }
+        Ok(Some(Self { key, subject }))
+    }
+}
+
+/// 密钥文件内容为 32 字节 P-256 私钥的 base64 编码，也接受 base64url（其他 Web Push 工具生成的格式）
+pub(crate) fn load_key(path: &Path) -> anyhow::Result<SigningKey> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read VAPID key file {}", path.display()))?;
+    let text = text.trim();
+    let bytes = STANDARD
+        .decode(text)
+        .or_else(|_| URL_SAFE_NO_PAD.decode(text.trim_end_matches('=')))
+        .with_context(|| format!("VAPID key file {} is not valid base64", path.display()))?;
+    SigningKey::from_slice(&bytes).map_err(|_| {
+        anyhow::anyhow!(
+            "VAPID key file {} must contain a 32-byte P-256 private key",
+            path.display()
+        )
+    })
+}
+
+/// 生成新的 VAPID 私钥并写入文件，文件已存在时报错；Unix 下权限为 0600
+pub(crate) fn generate_key_file(path: &Path) -> anyhow::Result<()> {
+    let key = SigningKey::random(&mut OsRng);
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("failed to create VAPID key file {}", path.display()))?;
+    writeln!(file, "{}", STANDARD.encode(key.to_bytes()))?;
+    Ok(())
+}
+
+/// base64url 编码的未压缩公钥，即浏览器订阅时的 `applicationServerKey`
+pub(crate) fn public_key(key: &SigningKey) -> String {
+    URL_SAFE_NO_PAD.encode(key.verifying_key().to_encoded_point(false).as_bytes())
+}
+
+/// 解码浏览器提供的 base64url 字段，兼容带填充的写法
+fn decode(text: &str) -> Option<Vec<u8>> {
+    URL_SAFE_NO_PAD
+        .decode(text.trim().trim_end_matches('='))
+        .ok()
+}
+
+/// 校验订阅密钥：p256dh 须为 P-256 公钥，auth 须为 16 字节
+pub(crate) fn validate_keys(keys: &PushSubscriptionKeys) -> Result<(), AppError> {
+    let p256dh = decode(&keys.p256dh)
+        .ok_or_else(|| AppError::BadRequest("keys.p256dh is not valid base64url".to_string()))?;
+    if PublicKey::from_sec1_bytes(&p256dh).is_err() {
+        return Err(AppError::BadRequest(
+            "keys.p256dh is not a P-256 public key".to_string(),
+        ));
+    }
+    match decode(&keys.auth) {
+        Some(auth) if auth.len() == 16 => Ok(()),
+        _ => Err(AppError::BadRequest(
+            "keys.auth must be 16 bytes of base64url".to_string(),
+        )),
+    }
+}
+
+/// 由共享密钥派生内容加密密钥与 nonce（RFC 8291 第 3.4 节、RFC 8188 第 2.2 节）
+fn derive_key(
+    shared_secret: &[u8],
+    auth: &[u8],
+    ua_public: &[u8],
+    as_public: &[u8],
+    salt: &[u8],
+) -> ([u8; 16], [u8; 12]) {
+    let mut info = b"WebPush: info\0".to_vec();
+    info.extend_from_slice(ua_public);
+    info.extend_from_slice(as_public);
+    let mut ikm = [0u8; 32];
+    Hkdf::<Sha256>::new(Some(auth), shared_secret)
+        .expand(&info, &mut ikm)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+
+    let hkdf = Hkdf::<Sha256>::new(Some(salt), &ikm);
+    let mut key = [0u8; 16];
+    let mut nonce = [0u8; 12];
+    hkdf.expand(b"Content-Encoding: aes128gcm\0", &mut key)
+        .and_then(|_| hkdf.expand(b"Content-Encoding: nonce\0", &mut nonce))
+        .expect("key and nonce are valid HKDF-SHA256 output lengths");
+    (key, nonce)
+}
+
+/// 以一次性的服务端密钥对按 aes128gcm 加密消息，返回请求体
+fn encrypt(p256dh: &[u8], auth: &[u8], payload: &[u8]) -> Result<Vec<u8>, String> {
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    encrypt_with(&SecretKey::random(&mut OsRng), salt, p256dh, auth, payload)
+}
+
+fn encrypt_with(
+    server_key: &SecretKey,
+    salt: [u8; 16],
+    p256dh: &[u8],
+    auth: &[u8],
+    payload: &[u8],
+) -> Result<Vec<u8>, String> {
+    let ua_public =
+        PublicKey::from_sec1_bytes(p256dh).map_err(|_| "invalid p256dh key".to_string())?;
+    let as_public = server_key.public_key().to_encoded_point(false);
+    let shared = p256::ecdh::diffie_hellman(server_key.to_nonzero_scalar(), ua_public.as_affine());
+    let (key, nonce) = derive_key(
+        shared.raw_secret_bytes(),
+        auth,
+        p256dh,
+        as_public.as_bytes(),
+        &salt,
+    );
+
+    // 单个记录，也是最后一个记录，填充分隔符为 0x02
+    let mut plaintext = payload.to_vec();
+    plaintext.push(2);
+    let ciphertext = Aes128Gcm::new(&key.into())
+        .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+        .map_err(|_| "failed to encrypt message".to_string())?;
+
+    let mut body = Vec::with_capacity(86 + ciphertext.len());
+    body.extend_from_slice(&salt);
+    body.extend_from_slice(&RECORD_SIZE.to_be_bytes());
+    body.push(as_public.as_bytes().len() as u8);
+    body.extend_from_slice(as_public.as_bytes());
+    body.extend_from_slice(&ciphertext);
+    Ok(body)
+}
+
+/// 已启用的 Web Push，持有 VAPID 密钥
+pub(crate) struct WebPush {
+    key: SigningKey,
+    /// base64url 编码的未压缩公钥
+    public_key: String,
+    subject: String,
+    client: reqwest::Client,
+}
+
+impl WebPush {
+    pub(crate) fn new(config: WebPushConfig) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder()
+            .user_agent(concat!("rutify/", env!("CARGO_PKG_VERSION")))
+            .timeout(PUSH_TIMEOUT)
+            .build()?;
+        let public_key = public_key(&config.key);
+        Ok(Self {
+            key: config.key,
+            public_key,
+            subject: config.subject,
+            client,
+        })
+    }
+
+    pub(crate) fn public_key(&self) -> &str {
+        &self.public_key
+    }
+
+    /// 推送服务所在源的 VAPID 凭据，作为 `Authorization` 头
+    fn authorization(&self, endpoint: &str) -> Result<String, String> {
+        let url = reqwest::Url::parse(endpoint).map_err(|e| e.to_string())?;
+        let claims = serde_json::json!({
+            "aud": url.origin().ascii_serialization(),
+            "exp": (chrono::Utc::now() + VAPID_EXPIRY).timestamp(),
+            "sub": self.subject,
+        });
+        let signing_input = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(r#"{"typ":"JWT","alg":"ES256"}"#),
+            URL_SAFE_NO_PAD.encode(claims.to_string())
+        );
+        let signature: Signature = self.key.sign(signing_input.as_bytes());
+        Ok(format!(
+            "vapid t={signing_input}.{}, k={}",
+            URL_SAFE_NO_PAD.encode(signature.to_bytes()),
+            self.public_key
+        ))
+    }
+
+    async fn push(
+        &self,
+        subscription: &Subscription,
+        body: &[u8],
+        ttl_secs: u64,
+        urgency: &str,
+    ) -> PushOutcome {
+        let (Some(p256dh), Some(auth)) = (decode(&subscription.p256dh), decode(&subscription.auth))
+        else {
+            return PushOutcome::Failed("invalid subscription keys".to_string());
+        };
+        let request = encrypt(&p256dh, &auth, body).and_then(|body| {
+            let authorization = self.authorization(&subscription.endpoint)?;
+            Ok((body, authorization))
+        });
+        let (body, authorization) = match request {
+            Ok(request) => request,
+            Err(error) => return PushOutcome::Failed(error),
+        };
+        let response = self
+            .client
+            .post(&subscription.endpoint)
+            .header(AUTHORIZATION, authorization)
+            .header(CONTENT_ENCODING, "aes128gcm")
+            .header(CONTENT_TYPE, "application/octet-stream")
+            .header("TTL", ttl_secs)
+            .header("Urgency", urgency)
+            .body(body)
+            .send()
+            .await;
+        PushOutcome::of(response)
+    }
+}
+
+/// 日志中的推送地址只记录主机名，路径通常包含密钥
+fn endpoint_host(endpoint: &str) -> String {
+    reqwest::Url::parse(endpoint)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| "(invalid url)".to_string())
+}
+
+/// 启动推送任务：订阅新通知，推送给可见该通知的用户的浏览器订阅
+pub(crate) fn spawn(state: Arc<AppState>) {
+    let Some(web_push) = state.web_push.clone() else {
+        return;
+    };
+    let mut rx = state.tx.subscribe();
+    info!("Web Push enabled, VAPID subject {}", web_push.subject);
+
+    tokio::spawn(async move {
+        loop {
+            let event = match rx.recv().await {
+                Ok(event) => event,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("Web Push dispatcher lagged, skipped {} events", skipped);
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            if event.event != EventKind::Created || !state.leadership.is_leader() {
+                continue;
+            }
+            fan_out(&state, &web_push, event.data).await;
+        }
+    });
+}
+
+async fn fan_out(state: &Arc<AppState>, web_push: &Arc<WebPush>, data: NotificationData) {
+    let Some(ttl_secs) = ttl_secs(&data, DEFAULT_TTL_SECS) else {
+        return;
+    };
+    let targets = match subscriptions::active(&state.db).await {
+        Ok(targets) => targets,
+        Err(e) => {
+            warn!("Failed to load Web Push subscriptions: {}", e);
+            return;
+        }
+    };
+    if targets.is_empty() {
+        return;
+    }
+
+    let body = Arc::new(message(&data, MAX_MESSAGE_BYTES));
+    let urgency = urgency(data.priority);
+    let all_preferences = preferences::load(&state.db).await;
+    let now = chrono::Utc::now();
+    for (subscription, username) in targets {
+        if !delivery::is_visible_to(&data, Some(&username))
+            || !preferences::allows_user(
+                &all_preferences,
+                Some(&username),
+                &data,
+                DeliveryChannel::WebPush,
+                now,
+            )
+        {
+            continue;
+        }
+        let receipt = data.id.map(|notify_id| NewDelivery {
+            notify_id: notify_id.get(),
+            channel: DeliveryChannel::WebPush,
+            target: username,
+            token_id: None,
+            error: None,
+        });
+
+        let state = Arc::clone(state);
+        let web_push = Arc::clone(web_push);
+        let body = Arc::clone(&body);
+        tokio::spawn(async move {
+            let outcome = web_push.push(&subscription, &body, ttl_secs, urgency).await;
+            let error = match &outcome {
+                PushOutcome::Delivered => {
+                    subscriptions::record_success(&state.db, subscription.id).await
+                }
+                PushOutcome::Gone => {
+                    info!(
+                        "Web Push subscription {} on {} is gone, removing it",
+                        subscription.id,
+                        endpoint_host(&subscription.endpoint)
+                    );
+                    subscriptions::delete(&state.db, subscription.id).await
+                }
+                PushOutcome::Failed(error) => {
+                    warn!(
+                        "Web Push to {} errors: {}",
+                        endpoint_host(&subscription.endpoint),
+                        error
+                    );
+                    subscriptions::record_failure(&state.db, subscription.id, error).await
+                }
+            };
+            if let Err(e) = error {
+                warn!("Failed to update Web Push subscription: {}", e);
+            }
+            if let Some(receipt) = receipt {
+                let error = outcome.error();
+                delivery::record_receipt(&state, NewDelivery { error, ..receipt });
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::ecdsa::VerifyingKey;
+    use p256::ecdsa::signature::Verifier;
+
+    /// 浏览器一侧的解密，用于验证请求体格式
+    fn decrypt(ua_key: &SecretKey, auth: &[u8], body: &[u8]) -> Vec<u8> {
+        let (salt, rest) = body.split_at(16);
+        assert_eq!(rest[..4], RECORD_SIZE.to_be_bytes());
+        let id_len = rest[4] as usize;
+        let (as_public, ciphertext) = rest[5..].split_at(id_len);
+        let as_public = PublicKey::from_sec1_bytes(as_public).unwrap();
+        let shared = p256::ecdh::diffie_hellman(ua_key.to_nonzero_scalar(), as_public.as_affine());
+        let ua_public = ua_key.public_key().to_encoded_point(false);
+        let (key, nonce) = derive_key(
+            shared.raw_secret_bytes(),
+            auth,
+            ua_public.as_bytes(),
+            as_public.to_encoded_point(false).as_bytes(),
+            salt,
+        );
+        let mut plaintext = Aes128Gcm::new(&key.into())
+            .decrypt(Nonce::from_slice(&nonce), ciphertext)
+            .unwrap();
+        assert_eq!(plaintext.pop(), Some(2));
+        plaintext
+    }
+
+    #[test]
+    fn test_encrypt_rfc8291_example() {
+        // RFC 8291 附录 A 的示例
+        let decode = |text: &str| URL_SAFE_NO_PAD.decode(text).unwrap();
+        let server_key =
+            SecretKey::from_slice(&decode("yfWPiYE-n46HLnH0KqZOF1fJJU3MYrct3AELtAQ-oRw")).unwrap();
+        let salt: [u8; 16] = decode("DGv6ra1nlYgDCS1FRnbzlw").try_into().unwrap();
+        let body = encrypt_with(
+            &server_key,
+            salt,
+            &decode(
+                "BCVxsr7N_eNgVRqvHtD0zTZsEc6-VV-JvLexhqUzORcxaOzi6-AYWXvTBHm4bjyPjs7Vd8pZGH6SRpkNtoIAiw4",
+            ),
+            &decode("BTBZMqHH6r4Tts7J_aSIgg"),
+            b"When I grow up, I want to be a watermelon",
+        )
+        .unwrap();
+        assert_eq!(
+            URL_SAFE_NO_PAD.encode(body),
+            "DGv6ra1nlYgDCS1FRnbzlwAAEABBBP4z9KsN6nGRTbVYI_c7VJSPQTBtkgcy27mlmlMoZIIgDll6e3vCYLocInmYWAmS6TlzAC8wEqKK6PBru3jl7A_yl95bQpu6cVPTpK4Mqgkf1CXztLVBSt2Ks3oZwbuwXPXLWyouBWLVWGNWQexSgSxsj_Qulcy4a-fN"
+        );
+    }
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let ua_key = SecretKey::random(&mut OsRng);
+        let ua_public = ua_key.public_key().to_encoded_point(false);
+        let auth = [7u8; 16];
+
+        let payload = vec![b'x'; MAX_MESSAGE_BYTES];
+        let body = encrypt(ua_public.as_bytes(), &auth, &payload).unwrap();
+        assert_eq!(body.len(), RECORD_SIZE as usize);
+        assert_eq!(decrypt(&ua_key, &auth, &body), payload);
+        assert!(encrypt(b"not a key", &auth, &payload).is_err());
+    }
+
+    #[test]
+    fn test_validate_keys() {
+        let ua_public = SecretKey::random(&mut OsRng)
+            .public_key()
+            .to_encoded_point(false);
+        let keys = PushSubscriptionKeys {
+            p256dh: URL_SAFE_NO_PAD.encode(ua_public.as_bytes()),
+            auth: URL_SAFE_NO_PAD.encode([1u8; 16]),
+        };
+        assert!(validate_keys(&keys).is_ok());
+        assert!(
+            validate_keys(&PushSubscriptionKeys {
+                auth: URL_SAFE_NO_PAD.encode([1u8; 8]),
+                ..keys.clone()
+            })
+            .is_err()
+        );
+        assert!(
+            validate_keys(&PushSubscriptionKeys {
+                p256dh: URL_SAFE_NO_PAD.encode([4u8; 65]),
+                ..keys
+            })
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_vapid_authorization() {
+        let web_push = WebPush::new(WebPushConfig {
+            key: SigningKey::random(&mut OsRng),
+            subject: "mailto:ops@example.com".to_string(),
+        })
+        .unwrap();
+        let header = web_push
+            .authorization("https://fcm.googleapis.com/fcm/send/abc")
+            .unwrap();
+        let (token, public_key) = header
+            .strip_prefix("vapid t=")
+            .and_then(|rest| rest.split_once(", k="))
+            .unwrap();
+        assert_eq!(public_key, web_push.public_key());
+
+        let (signing_input, signature) = token.rsplit_once('.').unwrap();
+        let signature = Signature::from_slice(&URL_SAFE_NO_PAD.decode(signature).unwrap()).unwrap();
+        let verifying_key =
+            VerifyingKey::from_sec1_bytes(&URL_SAFE_NO_PAD.decode(public_key).unwrap()).unwrap();
+        assert!(
+            verifying_key
+                .verify(signing_input.as_bytes(), &signature)
+                .is_ok()
+        );
+
+        let claims = signing_input.split('.').nth(1).unwrap();
+        let claims: serde_json::Value =
+            serde_json::from_slice(&URL_SAFE_NO_PAD.decode(claims).unwrap()).unwrap();
+        assert_eq!(claims["aud"], "https://fcm.googleapis.com");
+        assert_eq!(claims["sub"], "mailto:ops@example.com");
+    }
+}
@@ -1,11 +1,113 @@
-use common_http_server_rs::MonitoringState;
-use rutify_core::NotifyEvent;
+use crate::bootstrap::config::ServerConfig;
+use crate::bootstrap::monitoring::MonitoringState;
+use crate::bootstrap::reload::ConfigReloader;
+use crate::services::auth::failures::AuthFailureLog;
+use crate::services::auth::mode::AuthPolicy;
+use crate::services::auth::registration_gate::RegistrationGate;
+use crate::services::auth::token_cache::TokenCache;
+use crate::services::auth::validation::PasswordPolicy;
+use crate::services::cluster::Cluster;
+use crate::services::connections::ConnectionTracker;
+use crate::services::db_maintenance::DbMaintenance;
+use crate::services::dedup::GetDedup;
+use crate::services::escalation::EscalationRegistry;
+use crate::services::leader::Leadership;
+use crate::services::limits::SizeLimits;
+use crate::services::link_preview::LinkPreviewer;
+use crate::services::maintenance::Maintenance;
+use crate::services::plugins::PluginRegistry;
+use crate::services::silences::SilenceRegistry;
+use crate::services::web_push::WebPush;
+use rutify_core::{Capabilities, NotifyEvent};
 use sea_orm::DatabaseConnection;
+use std::sync::Arc;
 use tokio::sync::broadcast;
 
 #[derive(Clone)]
 pub(crate) struct AppState {
+    /// 启动时加载的核心配置
+    pub(crate) config: Arc<ServerConfig>,
     pub(crate) db: DatabaseConnection,
     pub(crate) tx: broadcast::Sender<NotifyEvent>,
     pub(crate) monitoring: MonitoringState,
+    pub(crate) plugins: Arc<PluginRegistry>,
+    pub(crate) escalations: Arc<EscalationRegistry>,
+    pub(crate) silences: Arc<SilenceRegistry>,
+    pub(crate) maintenance: Arc<Maintenance>,
+    pub(crate) db_maintenance: Arc<DbMaintenance>,
+    pub(crate) token_cache: Arc<TokenCache>,
+    pub(crate) auth_policy: AuthPolicy,
+    pub(crate) password_policy: PasswordPolicy,
+    pub(crate) registration_gate: Arc<RegistrationGate>,
+    pub(crate) auth_failures: Arc<AuthFailureLog>,
+    pub(crate) connections: Arc<ConnectionTracker>,
+    pub(crate) size_limits: SizeLimits,
+    pub(crate) get_dedup: Arc<GetDedup>,
+    /// 未开启链接预览时为空
+    pub(crate) link_previewer: Option<Arc<LinkPreviewer>>,
+    /// 未配置 VAPID 密钥或只读实例时为空
+    pub(crate) web_push: Option<Arc<WebPush>>,
+    pub(crate) config_reloader: Arc<ConfigReloader>,
+    /// 未配置集群广播时为空
+    pub(crate) cluster: Option<Arc<Cluster>>,
+    pub(crate) leadership: Arc<Leadership>,
+    /// 只读实例不入库、不修改数据，也不参与选主
+    pub(crate) read_only: bool,
+    /// 启动时确定的可选子系统，由 `GET /api/capabilities` 返回
+    pub(crate) capabilities: Capabilities,
+}
+
+#[cfg(test)]
+impl AppState {
+    /// 使用内存 SQLite 的测试状态，后台任务不会启动
+    pub(crate) async fn for_tests(auth_policy: AuthPolicy) -> Arc<Self> {
+        use crate::bootstrap::config::CorsOrigins;
+        use crate::bootstrap::protection::ProtectionConfig;
+        use crate::bootstrap::reload::RuntimeConfig;
+        use crate::services::db_maintenance::DbMaintenanceConfig;
+
+        let db = sea_orm::Database::connect("sqlite::memory:")
+            .await
+            .expect("in-memory sqlite");
+        crate::db::initialize::initial(&db).await;
+        let (tx, _) = broadcast::channel(16);
+        let (escalations, _) = EscalationRegistry::new();
+        let runtime_config = RuntimeConfig {
+            log_filter: "info".to_string(),
+            protection: ProtectionConfig::default(),
+            cors_origins: CorsOrigins::Any,
+            db_maintenance: DbMaintenanceConfig {
+                interval: None,
+                notifies_thresholds: Vec::new(),
+                purge_expired: false,
+            },
+        };
+
+        Arc::new(Self {
+            config: Arc::new(ServerConfig::for_tests()),
+            db,
+            tx,
+            monitoring: MonitoringState::new(),
+            plugins: Arc::new(PluginRegistry::new()),
+            escalations: Arc::new(escalations),
+            silences: Arc::new(SilenceRegistry::new()),
+            maintenance: Arc::new(Maintenance::new()),
+            db_maintenance: Arc::new(DbMaintenance::new(runtime_config.db_maintenance.clone())),
+            token_cache: Arc::new(TokenCache::new(std::time::Duration::ZERO, 0)),
+            auth_policy,
+            password_policy: PasswordPolicy::default(),
+            registration_gate: Arc::new(RegistrationGate::None),
+            auth_failures: Arc::new(AuthFailureLog::new()),
+            connections: Arc::new(ConnectionTracker::new()),
+            size_limits: SizeLimits::default(),
+            get_dedup: Arc::new(GetDedup::new(std::time::Duration::from_secs(10))),
+            link_previewer: None,
+            web_push: None,
+            config_reloader: Arc::new(ConfigReloader::new(runtime_config)),
+            cluster: None,
+            leadership: Arc::new(Leadership::always()),
+            read_only: false,
+            capabilities: Capabilities::default(),
+        })
+    }
 }